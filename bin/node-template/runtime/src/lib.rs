@@ -102,6 +102,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
+	features: sp_version::runtime_features!(),
 };
 
 /// This determines the average expected block time that we are targeting.
@@ -197,12 +198,14 @@ impl pallet_randomness_collective_flip::Config for Runtime {}
 
 parameter_types! {
 	pub const MaxAuthorities: u32 = 32;
+	pub const MaxTimestampDrift: u64 = 0;
 }
 
 impl pallet_aura::Config for Runtime {
 	type AuthorityId = AuraId;
 	type DisabledValidators = ();
 	type MaxAuthorities = MaxAuthorities;
+	type MaxTimestampDrift = MaxTimestampDrift;
 }
 
 impl pallet_grandpa::Config for Runtime {
@@ -221,6 +224,8 @@ impl pallet_grandpa::Config for Runtime {
 
 	type HandleEquivocation = ();
 
+	type HandleEquivocationReward = ();
+
 	type WeightInfo = ();
 }
 
@@ -389,6 +394,10 @@ impl_runtime_apis! {
 		fn authorities() -> Vec<AuraId> {
 			Aura::authorities().into_inner()
 		}
+
+		fn next_authorities() -> Vec<AuraId> {
+			Aura::next_authorities().into_inner()
+		}
 	}
 
 	impl sp_session::SessionKeys<Block> for Runtime {
@@ -452,6 +461,16 @@ impl_runtime_apis! {
 		) -> pallet_transaction_payment::FeeDetails<Balance> {
 			TransactionPayment::query_fee_details(uxt, len)
 		}
+		fn query_tip_for_priority(
+			uxt: <Block as BlockT>::Extrinsic,
+			len: u32,
+			priority: sp_runtime::transaction_validity::TransactionPriority,
+		) -> Balance {
+			TransactionPayment::query_tip_for_priority(uxt, len, priority)
+		}
+		fn query_max_block_length() -> u32 {
+			TransactionPayment::max_block_length()
+		}
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
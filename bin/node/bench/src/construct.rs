@@ -30,8 +30,8 @@ use std::{borrow::Cow, collections::HashMap, pin::Pin, sync::Arc};
 use node_primitives::Block;
 use node_testing::bench::{BenchDb, BlockType, DatabaseType, KeyTypes, Profile};
 use sc_transaction_pool_api::{
-	ImportNotificationStream, PoolFuture, PoolStatus, TransactionFor, TransactionSource,
-	TransactionStatusStreamFor, TxHash,
+	BlockHash, ImportNotificationStream, PoolEventStream, PoolFuture, PoolStatus, TransactionFor,
+	TransactionSource, TransactionStatusStreamFor, TxHash,
 };
 use sp_consensus::{Environment, Proposer};
 use sp_inherents::InherentDataProvider;
@@ -212,6 +212,10 @@ impl sc_transaction_pool_api::InPoolTransaction for PoolTransaction {
 	fn is_propagable(&self) -> bool {
 		unimplemented!()
 	}
+
+	fn source(&self) -> sc_transaction_pool_api::TransactionSource {
+		unimplemented!()
+	}
 }
 
 #[derive(Clone, Debug)]
@@ -282,6 +286,10 @@ impl sc_transaction_pool_api::TransactionPool for Transactions {
 		unimplemented!()
 	}
 
+	fn event_stream(&self) -> PoolEventStream<TxHash<Self>, BlockHash<Self>> {
+		unimplemented!()
+	}
+
 	fn on_broadcasted(&self, _propagations: HashMap<TxHash<Self>, Vec<String>>) {
 		unimplemented!()
 	}
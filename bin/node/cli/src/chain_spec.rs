@@ -363,6 +363,8 @@ pub fn testnet_genesis(
 		vesting: Default::default(),
 		gilt: Default::default(),
 		transaction_storage: Default::default(),
+		parameters: Default::default(),
+		grandpa_bridge: Default::default(),
 	}
 }
 
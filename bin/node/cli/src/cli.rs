@@ -28,6 +28,9 @@ pub struct Cli {
 	#[allow(missing_docs)]
 	#[structopt(flatten)]
 	pub run: RunCmd,
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub storage_monitor: sc_storage_monitor::StorageMonitorParams,
 }
 
 /// Possible subcommands of the main binary.
@@ -47,6 +50,27 @@ pub enum Subcommand {
 	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
 
+	/// The benchmark subcommand measuring the weights of the storage reads and writes.
+	#[structopt(
+		name = "benchmark-storage",
+		about = "Benchmark the reads and writes of the storage of the node."
+	)]
+	BenchmarkStorage(frame_benchmarking_cli::StorageCmd),
+
+	/// The benchmark subcommand measuring the base weights of blocks and extrinsics.
+	#[structopt(
+		name = "benchmark-overhead",
+		about = "Benchmark the execution overhead of blocks and extrinsics."
+	)]
+	BenchmarkOverhead(frame_benchmarking_cli::OverheadCmd),
+
+	/// The benchmark subcommand checking the hardware of the host against the requirements.
+	#[structopt(
+		name = "benchmark-machine",
+		about = "Check that the hardware of the host meets the requirements of a validator."
+	)]
+	BenchmarkMachine(frame_benchmarking_cli::MachineCmd),
+
 	/// Try some command against runtime state.
 	#[cfg(feature = "try-runtime")]
 	TryRuntime(try_runtime_cli::TryRuntimeCmd),
@@ -82,6 +106,9 @@ pub enum Subcommand {
 	/// Remove the whole chain.
 	PurgeChain(sc_cli::PurgeChainCmd),
 
+	/// Database management cli utilities.
+	Db(sc_cli::DbSubcommand),
+
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
 }
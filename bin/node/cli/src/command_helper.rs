@@ -0,0 +1,85 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Contains code to setup the command invocations in [`super::command`] which would
+//! otherwise bloat that module.
+
+use crate::service::{create_extrinsic, FullClient};
+
+use frame_support::{metadata::RuntimeMetadata, BasicExternalities};
+use node_runtime::{Runtime, SystemCall};
+use sc_cli::Result;
+use sc_service::StorageNames;
+use sp_inherents::{InherentData, InherentDataProvider};
+use sp_keyring::Sr25519Keyring;
+use sp_runtime::OpaqueExtrinsic;
+
+use std::{sync::Arc, time::Duration};
+
+/// Generates extrinsics for the `benchmark-overhead` command.
+pub struct BenchmarkExtrinsicBuilder {
+	client: Arc<FullClient>,
+}
+
+impl BenchmarkExtrinsicBuilder {
+	/// Creates a new [`Self`] from the given client.
+	pub fn new(client: Arc<FullClient>) -> Self {
+		Self { client }
+	}
+}
+
+impl frame_benchmarking_cli::ExtrinsicBuilder for BenchmarkExtrinsicBuilder {
+	fn remark(&self, nonce: u32) -> std::result::Result<OpaqueExtrinsic, &'static str> {
+		let extrinsic = create_extrinsic(
+			self.client.as_ref(),
+			Sr25519Keyring::Bob.pair(),
+			SystemCall::remark { remark: vec![] },
+			nonce,
+		);
+
+		Ok(extrinsic.into())
+	}
+}
+
+/// Generates inherent data for the `benchmark-overhead` command.
+///
+/// The timestamp is zero, the blocks must be built on top of the genesis block, e.g. of a `--dev`
+/// chain.
+pub fn inherent_benchmark_data() -> Result<InherentData> {
+	let mut inherent_data = InherentData::new();
+	let timestamp = sp_timestamp::InherentDataProvider::new(Duration::from_millis(0).into());
+
+	timestamp
+		.provide_inherent_data(&mut inherent_data)
+		.map_err(|e| format!("creating inherent data: {:?}", e))?;
+	Ok(inherent_data)
+}
+
+/// The names of the storage items of the runtime, for `build-spec --raw --reverse`.
+pub fn storage_names() -> StorageNames {
+	// The metadata of some constants, e.g. of dynamic parameters, is read from the storage.
+	let metadata = BasicExternalities::default().execute_with(Runtime::metadata);
+	let pallets = match metadata.1 {
+		RuntimeMetadata::V14(metadata) => metadata.pallets,
+		_ => return StorageNames::default(),
+	};
+	StorageNames::new(pallets.into_iter().filter_map(|pallet| pallet.storage).flat_map(|storage| {
+		let prefix = storage.prefix;
+		storage.entries.into_iter().map(move |entry| (prefix.clone(), entry.name))
+	}))
+}
@@ -20,6 +20,7 @@
 
 //! Service implementation. Specialized wrapper over substrate service.
 
+use codec::Encode;
 use futures::prelude::*;
 use node_executor::ExecutorDispatch;
 use node_primitives::Block;
@@ -29,11 +30,17 @@ use sc_consensus_babe::{self, SlotProportion};
 use sc_executor::NativeElseWasmExecutor;
 use sc_network::{Event, NetworkService};
 use sc_service::{config::Configuration, error::Error as ServiceError, RpcHandlers, TaskManager};
-use sc_telemetry::{Telemetry, TelemetryWorker};
-use sp_runtime::traits::Block as BlockT;
+use sc_storage_monitor::{StorageMonitorParams, StorageMonitorService};
+use sc_telemetry::{Telemetry, TelemetryHandle, TelemetryWorker};
+use sp_core::Pair;
+use sp_runtime::{
+	generic::{BlockId, Era},
+	traits::Block as BlockT,
+};
 use std::sync::Arc;
 
-type FullClient =
+/// The full client type definition.
+pub type FullClient =
 	sc_service::TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<ExecutorDispatch>>;
 type FullBackend = sc_service::TFullBackend<Block>;
 type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
@@ -42,6 +49,42 @@ type FullGrandpaBlockImport =
 type LightClient =
 	sc_service::TLightClient<Block, RuntimeApi, NativeElseWasmExecutor<ExecutorDispatch>>;
 
+/// Create a transaction calling `function`, signed by `sender` with the given `nonce`.
+///
+/// The transaction is immortal and pays no tip.
+pub fn create_extrinsic(
+	client: &FullClient,
+	sender: sp_core::sr25519::Pair,
+	function: impl Into<node_runtime::Call>,
+	nonce: u32,
+) -> node_runtime::UncheckedExtrinsic {
+	let genesis_hash = client.chain_info().genesis_hash;
+	let best_block_id = BlockId::Hash(client.chain_info().best_hash);
+	let version = client
+		.runtime_version_at(&best_block_id)
+		.expect("the runtime version of the best block is known; qed");
+
+	let extra: node_runtime::SignedExtra = (
+		frame_system::CheckSpecVersion::new(),
+		frame_system::CheckTxVersion::new(),
+		frame_system::CheckGenesis::new(),
+		frame_system::CheckEra::from(Era::Immortal),
+		frame_system::CheckNonce::from(nonce),
+		frame_system::CheckWeight::new(),
+		pallet_transaction_payment::ChargeTransactionPayment::from(0),
+	);
+	let raw_payload = node_runtime::SignedPayload::from_raw(
+		function.into(),
+		extra,
+		(version.spec_version, version.transaction_version, genesis_hash, genesis_hash, (), (), ()),
+	);
+	let signature = raw_payload.using_encoded(|payload| sender.sign(payload));
+	let (function, extra, _) = raw_payload.deconstruct();
+	let signer = sp_runtime::AccountId32::from(sender.public());
+
+	node_runtime::UncheckedExtrinsic::new_signed(function, signer.into(), signature.into(), extra)
+}
+
 pub fn new_partial(
 	config: &Configuration,
 ) -> Result<
@@ -171,6 +214,31 @@ pub fn new_partial(
 		let select_chain = select_chain.clone();
 		let keystore = keystore_container.sync_keystore();
 		let chain_spec = config.chain_spec.cloned_box();
+		let spawn_handle = task_manager.spawn_handle();
+
+		// The previewed block gets the inherents of the blocks authored by the node.
+		let preview_client = client.clone();
+		let create_preview_inherent_data_providers = move |parent, ()| {
+			let client = preview_client.clone();
+			async move {
+				let uncles =
+					sc_consensus_uncles::create_uncles_inherent_data_provider(&*client, parent)?;
+
+				let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+
+				let slot =
+					sp_consensus_babe::inherents::InherentDataProvider::from_timestamp_and_duration(
+						*timestamp,
+						slot_duration,
+					);
+
+				let storage_proof = sp_transaction_storage_proof::registration::new_data_provider(
+					&*client, &parent,
+				)?;
+
+				Ok((timestamp, slot, uncles, storage_proof))
+			}
+		};
 
 		let rpc_extensions_builder = move |deny_unsafe, subscription_executor| {
 			let deps = node_rpc::FullDeps {
@@ -191,6 +259,17 @@ pub fn new_partial(
 					subscription_executor,
 					finality_provider: finality_proof_provider.clone(),
 				},
+				preview: node_rpc::PreviewDeps {
+					// The metrics and telemetry are those of the proposer authoring the blocks.
+					env: sc_basic_authorship::ProposerFactory::new(
+						spawn_handle.clone(),
+						client.clone(),
+						pool.clone(),
+						None,
+						None,
+					),
+					create_inherent_data_providers: create_preview_inherent_data_providers.clone(),
+				},
 			};
 
 			node_rpc::create_full(deps).map_err(Into::into)
@@ -216,6 +295,7 @@ pub struct NewFullBase {
 	pub client: Arc<FullClient>,
 	pub network: Arc<NetworkService<Block, <Block as BlockT>::Hash>>,
 	pub transaction_pool: Arc<sc_transaction_pool::FullPool<Block, FullClient>>,
+	pub telemetry: Option<TelemetryHandle>,
 }
 
 /// Creates a full service from the configuration.
@@ -348,6 +428,7 @@ pub fn new_full_base(
 			block_proposal_slot_portion: SlotProportion::new(0.5),
 			max_block_proposal_slot_portion: None,
 			telemetry: telemetry.as_ref().map(|x| x.handle()),
+			registry: prometheus_registry.clone(),
 		};
 
 		let babe = sc_consensus_babe::start_babe(babe_config)?;
@@ -424,12 +505,32 @@ pub fn new_full_base(
 	}
 
 	network_starter.start_network();
-	Ok(NewFullBase { task_manager, client, network, transaction_pool })
+	Ok(NewFullBase {
+		task_manager,
+		client,
+		network,
+		transaction_pool,
+		telemetry: telemetry.as_ref().map(|x| x.handle()),
+	})
 }
 
-/// Builds a new service for a full client.
-pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
-	new_full_base(config, |_, _| ()).map(|NewFullBase { task_manager, .. }| task_manager)
+/// Builds a new service for a full client, stopped when its database storage runs out of space.
+pub fn new_full(
+	config: Configuration,
+	storage_monitor: StorageMonitorParams,
+) -> Result<TaskManager, ServiceError> {
+	let database_source = config.database.clone();
+	let NewFullBase { task_manager, telemetry, .. } = new_full_base(config, |_, _| ())?;
+
+	StorageMonitorService::try_spawn(
+		storage_monitor,
+		database_source,
+		&task_manager.spawn_essential_handle(),
+		telemetry,
+	)
+	.map_err(|e| ServiceError::Application(e.into()))?;
+
+	Ok(task_manager)
 }
 
 pub fn new_light_base(
@@ -37,6 +37,7 @@ fn database_role_subdir_migration() {
 		let _old_db = LightStorage::<Block>::new(DatabaseSettings {
 			state_cache_size: 0,
 			state_cache_child_ratio: None,
+			trie_cache_size: 0,
 			state_pruning: PruningMode::ArchiveAll,
 			source: DatabaseSource::RocksDb { path: path.to_path_buf(), cache_size: 128 },
 			keep_blocks: KeepBlocks::All,
@@ -82,6 +83,7 @@ fn database_role_subdir_migration_fail_on_different_role() {
 		let _old_db = LightStorage::<Block>::new(DatabaseSettings {
 			state_cache_size: 0,
 			state_cache_child_ratio: None,
+			trie_cache_size: 0,
 			state_pruning: PruningMode::ArchiveAll,
 			source: DatabaseSource::RocksDb { path: path.to_path_buf(), cache_size: 128 },
 			keep_blocks: KeepBlocks::All,
@@ -57,9 +57,10 @@ pub fn bloaty_code_unwrap() -> &'static [u8] {
 /// at block `n`, it must be called prior to executing block `n` to do the calculation with the
 /// correct multiplier.
 fn transfer_fee<E: Encode>(extrinsic: &E) -> Balance {
-	TransactionPayment::compute_fee(
+	TransactionPayment::compute_actual_fee(
 		extrinsic.encode().len() as u32,
 		&default_transfer_call().get_dispatch_info(),
+		&Some(default_transfer_weight()).into(),
 		0,
 	)
 }
@@ -356,7 +357,7 @@ fn full_native_block_import_works() {
 	let mut alice_last_known_balance: Balance = Default::default();
 	let mut fees = t.execute_with(|| transfer_fee(&xt()));
 
-	let transfer_weight = default_transfer_call().get_dispatch_info().weight;
+	let transfer_weight = default_transfer_weight();
 	let timestamp_weight = pallet_timestamp::Call::set::<Runtime> { now: Default::default() }
 		.get_dispatch_info()
 		.weight;
@@ -392,7 +393,7 @@ fn full_native_block_import_works() {
 					bob().into(),
 					69 * DOLLARS,
 				)),
-				topics: vec![],
+				topics: vec![System::account_topic(&alice()), System::account_topic(&bob())],
 			},
 			EventRecord {
 				phase: Phase::ApplyExtrinsic(1),
@@ -446,7 +447,7 @@ fn full_native_block_import_works() {
 					alice().into(),
 					5 * DOLLARS,
 				)),
-				topics: vec![],
+				topics: vec![System::account_topic(&bob()), System::account_topic(&alice())],
 			},
 			EventRecord {
 				phase: Phase::ApplyExtrinsic(1),
@@ -468,7 +469,7 @@ fn full_native_block_import_works() {
 					bob().into(),
 					15 * DOLLARS,
 				)),
-				topics: vec![],
+				topics: vec![System::account_topic(&alice()), System::account_topic(&bob())],
 			},
 			EventRecord {
 				phase: Phase::ApplyExtrinsic(2),
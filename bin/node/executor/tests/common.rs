@@ -16,7 +16,7 @@
 // limitations under the License.
 
 use codec::{Decode, Encode};
-use frame_support::Hashable;
+use frame_support::{weights::Weight, Hashable};
 use frame_system::offchain::AppCrypto;
 use sc_executor::{error::Result, NativeElseWasmExecutor, WasmExecutionMethod};
 use sp_consensus_babe::{
@@ -42,6 +42,7 @@ use node_runtime::{
 	UncheckedExtrinsic,
 };
 use node_testing::keyring::*;
+use pallet_balances::WeightInfo;
 use sp_externalities::Externalities;
 
 pub const TEST_KEY_TYPE_ID: KeyTypeId = KeyTypeId(*b"test");
@@ -91,6 +92,15 @@ pub fn default_transfer_call() -> pallet_balances::Call<Runtime> {
 	pallet_balances::Call::<Runtime>::transfer { dest: bob().into(), value: 69 * DOLLARS }
 }
 
+/// The weight charged for the `default_transfer_call`.
+///
+/// The sender stays alive, so the call refunds down to `transfer_keep_alive`, but never
+/// charges more than the `transfer` weight it was dispatched with.
+pub fn default_transfer_weight() -> Weight {
+	type BalancesWeight = <Runtime as pallet_balances::Config>::WeightInfo;
+	BalancesWeight::transfer().min(BalancesWeight::transfer_keep_alive())
+}
+
 pub fn from_block_number(n: u32) -> Header {
 	Header::new(n, Default::default(), Default::default(), [69; 32].into(), Default::default())
 }
@@ -18,9 +18,7 @@
 use codec::{Encode, Joiner};
 use frame_support::{
 	traits::Currency,
-	weights::{
-		constants::ExtrinsicBaseWeight, GetDispatchInfo, IdentityFee, WeightToFeePolynomial,
-	},
+	weights::{constants::ExtrinsicBaseWeight, IdentityFee, WeightToFeePolynomial},
 };
 use node_primitives::Balance;
 use node_runtime::{
@@ -202,7 +200,7 @@ fn transaction_fee_is_correct() {
 		let length_fee = TransactionByteFee::get() * (xt.clone().encode().len() as Balance);
 		balance_alice -= length_fee;
 
-		let weight = default_transfer_call().get_dispatch_info().weight;
+		let weight = default_transfer_weight();
 		let weight_fee = IdentityFee::<Balance>::calc(&weight);
 
 		// we know that weight to fee multiplier is effect-less in block 1.
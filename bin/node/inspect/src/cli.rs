@@ -42,6 +42,9 @@ pub struct InspectCmd {
 #[derive(Debug, StructOpt)]
 pub enum InspectSubCmd {
 	/// Decode block with native version of runtime and print out the details.
+	///
+	/// The digest items are printed too, along with the events if the block is retrieved from
+	/// the database.
 	Block {
 		/// Address of the block to print out.
 		///
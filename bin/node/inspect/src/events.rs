@@ -0,0 +1,347 @@
+// This file is part of Substrate.
+//
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Decoding of the events of a block with the metadata of its runtime.
+
+use codec::{Compact, Decode};
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed, StorageEntryType};
+use scale_info::{
+	form::PortableForm, Field, PortableRegistry, TypeDef, TypeDefPrimitive, TypeDefVariant,
+};
+use sp_core::{hashing::twox_128, hexdisplay::HexDisplay};
+use std::fmt::Write;
+
+/// The location and the type of the events in the storage of a runtime, as declared by its
+/// metadata.
+pub struct EventsMetadata {
+	key: Vec<u8>,
+	ty: u32,
+	types: PortableRegistry,
+}
+
+impl EventsMetadata {
+	/// Find the events in the SCALE-encoded runtime `metadata`, i.e. the `Events` storage value
+	/// of the pallet declaring it.
+	pub fn new(metadata: &[u8]) -> Result<Self, String> {
+		let metadata = RuntimeMetadataPrefixed::decode(&mut &metadata[..])
+			.map_err(|e| format!("Failed to decode the runtime metadata: {}", e))?;
+		let metadata = match metadata.1 {
+			RuntimeMetadata::V14(metadata) => metadata,
+			_ => return Err("Unsupported runtime metadata version".into()),
+		};
+		for storage in metadata.pallets.into_iter().filter_map(|pallet| pallet.storage) {
+			for entry in storage.entries {
+				match entry.ty {
+					StorageEntryType::Plain(ty) if entry.name == "Events" =>
+						return Ok(Self {
+							key: [twox_128(storage.prefix.as_bytes()), twox_128(b"Events")]
+								.concat(),
+							ty: ty.id(),
+							types: metadata.types,
+						}),
+					_ => {},
+				}
+			}
+		}
+		Err("The runtime doesn't store any events".into())
+	}
+
+	/// The storage key of the events.
+	pub fn key(&self) -> &[u8] {
+		&self.key
+	}
+
+	/// Decode the SCALE-encoded `events`, formatting each one of them on its own.
+	pub fn decode(&self, events: &[u8]) -> Result<Vec<String>, String> {
+		let input = &mut &events[..];
+		let decoded = match self.resolve(self.ty).map(|ty| ty.type_def()) {
+			Ok(TypeDef::Sequence(sequence)) => {
+				let len = Compact::<u32>::decode(input).map_err(|e| e.to_string())?.0;
+				(0..len)
+					.map(|_| self.decode_value(sequence.type_param().id(), input))
+					.collect::<Result<_, _>>()?
+			},
+			_ => vec![self.decode_value(self.ty, input)?],
+		};
+		if !input.is_empty() {
+			return Err(format!("{} bytes left after decoding the events", input.len()))
+		}
+		Ok(decoded)
+	}
+
+	fn resolve(&self, ty: u32) -> Result<&scale_info::Type<PortableForm>, String> {
+		self.types
+			.resolve(ty)
+			.ok_or_else(|| format!("Unknown type {} in the metadata", ty))
+	}
+
+	fn decode_value(&self, ty: u32, input: &mut &[u8]) -> Result<String, String> {
+		let mut out = String::new();
+		self.write_value(ty, input, &mut out)?;
+		Ok(out)
+	}
+
+	fn write_value(&self, ty: u32, input: &mut &[u8], out: &mut String) -> Result<(), String> {
+		let ty = self.resolve(ty)?;
+		match ty.type_def() {
+			TypeDef::Composite(composite) => {
+				out.push_str(ty.path().ident().as_deref().unwrap_or(""));
+				self.write_fields(composite.fields(), input, out)
+			},
+			TypeDef::Variant(variant) => self.write_variant(variant, input, out),
+			TypeDef::Sequence(sequence) => {
+				let len = Compact::<u32>::decode(input).map_err(|e| e.to_string())?.0;
+				self.write_items(sequence.type_param().id(), len, input, out)
+			},
+			TypeDef::Array(array) =>
+				self.write_items(array.type_param().id(), array.len(), input, out),
+			TypeDef::Tuple(tuple) => {
+				out.push('(');
+				for (idx, field) in tuple.fields().iter().enumerate() {
+					if idx > 0 {
+						out.push_str(", ");
+					}
+					self.write_value(field.id(), input, out)?;
+				}
+				out.push(')');
+				Ok(())
+			},
+			TypeDef::Primitive(primitive) => write_primitive(primitive, input, out),
+			TypeDef::Compact(compact) => self.write_compact(compact.type_param().id(), input, out),
+			TypeDef::BitSequence(bits) => {
+				let len = Compact::<u32>::decode(input).map_err(|e| e.to_string())?.0 as usize;
+				let store = match self.resolve(bits.bit_store_type().id())?.type_def() {
+					TypeDef::Primitive(TypeDefPrimitive::U16) => 2,
+					TypeDef::Primitive(TypeDefPrimitive::U32) => 4,
+					TypeDef::Primitive(TypeDefPrimitive::U64) => 8,
+					_ => 1,
+				};
+				let bytes = (len + 8 * store - 1) / (8 * store) * store;
+				let bits = take(input, bytes)?;
+				write!(out, "{} bits: 0x{:?}", len, HexDisplay::from(&bits))
+					.map_err(|e| e.to_string())
+			},
+		}
+	}
+
+	fn write_variant(
+		&self,
+		variant: &TypeDefVariant<PortableForm>,
+		input: &mut &[u8],
+		out: &mut String,
+	) -> Result<(), String> {
+		let index = u8::decode(input).map_err(|e| e.to_string())?;
+		let variant = variant
+			.variants()
+			.iter()
+			.find(|variant| variant.index() == index)
+			.ok_or_else(|| format!("Unknown variant index {}", index))?;
+		out.push_str(variant.name());
+		self.write_fields(variant.fields(), input, out)
+	}
+
+	// Write `(a, b)` for unnamed fields, and ` { x: a, y: b }` for named ones.
+	fn write_fields(
+		&self,
+		fields: &[Field<PortableForm>],
+		input: &mut &[u8],
+		out: &mut String,
+	) -> Result<(), String> {
+		if fields.is_empty() {
+			return Ok(())
+		}
+		let named = fields.iter().all(|field| field.name().is_some());
+		out.push_str(if named { " { " } else { "(" });
+		for (idx, field) in fields.iter().enumerate() {
+			if idx > 0 {
+				out.push_str(", ");
+			}
+			if let Some(name) = field.name().filter(|_| named) {
+				write!(out, "{}: ", name).map_err(|e| e.to_string())?;
+			}
+			self.write_value(field.ty().id(), input, out)?;
+		}
+		out.push_str(if named { " }" } else { ")" });
+		Ok(())
+	}
+
+	// Byte sequences and arrays, e.g. hashes and account ids, are written as hex.
+	fn write_items(
+		&self,
+		ty: u32,
+		len: u32,
+		input: &mut &[u8],
+		out: &mut String,
+	) -> Result<(), String> {
+		if let TypeDef::Primitive(TypeDefPrimitive::U8) = self.resolve(ty)?.type_def() {
+			let bytes = take(input, len as usize)?;
+			return write!(out, "0x{:?}", HexDisplay::from(&bytes)).map_err(|e| e.to_string())
+		}
+		out.push('[');
+		for idx in 0..len {
+			if idx > 0 {
+				out.push_str(", ");
+			}
+			self.write_value(ty, input, out)?;
+		}
+		out.push(']');
+		Ok(())
+	}
+
+	fn write_compact(&self, ty: u32, input: &mut &[u8], out: &mut String) -> Result<(), String> {
+		let ty = self.resolve(ty)?;
+		match ty.type_def() {
+			TypeDef::Primitive(
+				TypeDefPrimitive::U8 |
+				TypeDefPrimitive::U16 |
+				TypeDefPrimitive::U32 |
+				TypeDefPrimitive::U64 |
+				TypeDefPrimitive::U128,
+			) => {
+				let value = Compact::<u128>::decode(input).map_err(|e| e.to_string())?.0;
+				write!(out, "{}", value).map_err(|e| e.to_string())
+			},
+			// Compact encoded wrappers of a single integer, e.g. `Perbill`.
+			TypeDef::Composite(composite) if composite.fields().len() == 1 => {
+				out.push_str(ty.path().ident().as_deref().unwrap_or(""));
+				out.push('(');
+				self.write_compact(composite.fields()[0].ty().id(), input, out)?;
+				out.push(')');
+				Ok(())
+			},
+			_ => Err(format!("Unsupported compact type {:?}", ty.path())),
+		}
+	}
+}
+
+fn write_primitive(
+	primitive: &TypeDefPrimitive,
+	input: &mut &[u8],
+	out: &mut String,
+) -> Result<(), String> {
+	fn value<T: Decode + ToString>(input: &mut &[u8]) -> Result<String, String> {
+		T::decode(input).map(|value| value.to_string()).map_err(|e| e.to_string())
+	}
+
+	let value = match primitive {
+		TypeDefPrimitive::Bool => value::<bool>(input)?,
+		TypeDefPrimitive::Char => {
+			let c = u32::decode(input).map_err(|e| e.to_string())?;
+			format!("{:?}", char::from_u32(c).ok_or("Invalid char")?)
+		},
+		TypeDefPrimitive::Str => format!("{:?}", String::decode(input).map_err(|e| e.to_string())?),
+		TypeDefPrimitive::U8 => value::<u8>(input)?,
+		TypeDefPrimitive::U16 => value::<u16>(input)?,
+		TypeDefPrimitive::U32 => value::<u32>(input)?,
+		TypeDefPrimitive::U64 => value::<u64>(input)?,
+		TypeDefPrimitive::U128 => value::<u128>(input)?,
+		TypeDefPrimitive::I8 => value::<i8>(input)?,
+		TypeDefPrimitive::I16 => value::<i16>(input)?,
+		TypeDefPrimitive::I32 => value::<i32>(input)?,
+		TypeDefPrimitive::I64 => value::<i64>(input)?,
+		TypeDefPrimitive::I128 => value::<i128>(input)?,
+		TypeDefPrimitive::U256 | TypeDefPrimitive::I256 =>
+			format!("0x{:?}", HexDisplay::from(&take(input, 32)?)),
+	};
+	out.push_str(&value);
+	Ok(())
+}
+
+fn take<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8], String> {
+	if input.len() < len {
+		return Err("Not enough data to decode the events".into())
+	}
+	let (bytes, rest) = input.split_at(len);
+	*input = rest;
+	Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+	use scale_info::{MetaType, Registry, TypeInfo};
+
+	#[derive(Encode, TypeInfo)]
+	enum Phase {
+		ApplyExtrinsic(u32),
+		Finalization,
+	}
+
+	#[derive(Encode, TypeInfo)]
+	enum BalancesEvent {
+		Transfer { from: [u8; 4], to: [u8; 4], amount: u128 },
+	}
+
+	#[derive(Encode, TypeInfo)]
+	enum Event {
+		System,
+		Balances(BalancesEvent),
+	}
+
+	#[derive(Encode, TypeInfo)]
+	struct EventRecord {
+		phase: Phase,
+		event: Event,
+		topics: Vec<[u8; 2]>,
+	}
+
+	fn events_metadata() -> EventsMetadata {
+		let mut registry = Registry::new();
+		let ty = registry.register_type(&MetaType::new::<Vec<EventRecord>>()).id();
+		EventsMetadata { key: Vec::new(), ty, types: registry.into() }
+	}
+
+	#[test]
+	fn should_decode_events_with_the_metadata() {
+		let events = vec![
+			EventRecord { phase: Phase::ApplyExtrinsic(1), event: Event::System, topics: vec![] },
+			EventRecord {
+				phase: Phase::Finalization,
+				event: Event::Balances(BalancesEvent::Transfer {
+					from: [1; 4],
+					to: [2; 4],
+					amount: 42,
+				}),
+				topics: vec![[0xab, 0xcd]],
+			},
+		];
+
+		assert_eq!(
+			events_metadata().decode(&events.encode()),
+			Ok(vec![
+				"EventRecord { phase: ApplyExtrinsic(1), event: System, topics: [] }".to_string(),
+				"EventRecord { phase: Finalization, event: Balances(Transfer { from: 0x01010101, \
+				 to: 0x02020202, amount: 42 }), topics: [0xabcd] }"
+					.to_string(),
+			])
+		);
+	}
+
+	#[test]
+	fn should_reject_invalid_events() {
+		let metadata = events_metadata();
+		let mut events =
+			vec![EventRecord { phase: Phase::Finalization, event: Event::System, topics: vec![] }]
+				.encode();
+
+		assert!(metadata.decode(&events[..events.len() - 1]).is_err());
+		events.push(0);
+		assert!(metadata.decode(&events).is_err());
+	}
+}
@@ -20,21 +20,27 @@
 //! about blocks and extrinsics.
 //!
 //! The blocks and extrinsics can either be retrieved from the database (on-chain),
-//! or a raw SCALE-encoding can be provided.
+//! or a raw SCALE-encoding can be provided. The events of the blocks retrieved from the
+//! database are printed along with their digest items.
 
 #![warn(missing_docs)]
 
 pub mod cli;
 pub mod command;
+pub mod events;
 
 use codec::{Decode, Encode};
-use sc_client_api::BlockBackend;
+use events::EventsMetadata;
+use sc_client_api::{BlockBackend, CallExecutor, ExecutorProvider, StorageProvider};
+use sc_executor::RuntimeVersionOf;
+use sc_service::TFullClient;
 use sp_blockchain::HeaderBackend;
-use sp_core::hexdisplay::HexDisplay;
+use sp_core::{hexdisplay::HexDisplay, storage::StorageKey, traits::CodeExecutor};
 use sp_runtime::{
 	generic::BlockId,
-	traits::{Block, Hash, HashFor, NumberFor},
+	traits::{Block, Hash, HashFor, Header, NumberFor},
 };
+use sp_state_machine::ExecutionStrategy;
 use std::{fmt, fmt::Debug, marker::PhantomData, str::FromStr};
 
 /// A helper type for a generic block input.
@@ -48,6 +54,14 @@ pub trait PrettyPrinter<TBlock: Block> {
 	/// Nicely format extrinsic.
 	fn fmt_extrinsic(&self, fmt: &mut fmt::Formatter, extrinsic: &TBlock::Extrinsic)
 		-> fmt::Result;
+	/// Nicely format the events of a block, decoded with the metadata of its runtime.
+	fn fmt_events(&self, fmt: &mut fmt::Formatter, events: &[String]) -> fmt::Result {
+		writeln!(fmt, "Events ({})", events.len())?;
+		for (idx, event) in events.iter().enumerate() {
+			writeln!(fmt, "- {}: {}", idx, event)?;
+		}
+		Ok(())
+	}
 }
 
 /// Default dummy debug printer.
@@ -58,6 +72,7 @@ impl<TBlock: Block> PrettyPrinter<TBlock> for DebugPrinter {
 		writeln!(fmt, "Header:")?;
 		writeln!(fmt, "{:?}", block.header())?;
 		writeln!(fmt, "Block bytes: {:?}", HexDisplay::from(&block.encode()))?;
+		fmt_digest::<TBlock>(fmt, block.header())?;
 		writeln!(fmt, "Extrinsics ({})", block.extrinsics().len())?;
 		for (idx, ex) in block.extrinsics().iter().enumerate() {
 			writeln!(fmt, "- {}:", idx)?;
@@ -77,6 +92,16 @@ impl<TBlock: Block> PrettyPrinter<TBlock> for DebugPrinter {
 	}
 }
 
+/// Format the digest items of `header`, one per line.
+pub fn fmt_digest<TBlock: Block>(fmt: &mut fmt::Formatter, header: &TBlock::Header) -> fmt::Result {
+	let logs = header.digest().logs();
+	writeln!(fmt, "Digest items ({})", logs.len())?;
+	for (idx, item) in logs.iter().enumerate() {
+		writeln!(fmt, "- {}: {:?}", idx, item)?;
+	}
+	Ok(())
+}
+
 /// Aggregated error for `Inspector` operations.
 #[derive(Debug, derive_more::From, derive_more::Display)]
 pub enum Error {
@@ -98,13 +123,58 @@ impl std::error::Error for Error {
 	}
 }
 
-/// A helper trait to access block headers and bodies.
-pub trait ChainAccess<TBlock: Block>: HeaderBackend<TBlock> + BlockBackend<TBlock> {}
+/// A helper trait to read the storage and the runtime metadata of blocks.
+pub trait StorageAccess<TBlock: Block> {
+	/// The value at `key` in the storage of the block `id`.
+	fn storage_at(
+		&self,
+		id: &BlockId<TBlock>,
+		key: &[u8],
+	) -> sp_blockchain::Result<Option<Vec<u8>>>;
+
+	/// The SCALE-encoded metadata of the runtime of the block `id`.
+	fn metadata_at(&self, id: &BlockId<TBlock>) -> sp_blockchain::Result<Vec<u8>>;
+}
+
+impl<TBlock, RA, TExec> StorageAccess<TBlock> for TFullClient<TBlock, RA, TExec>
+where
+	TBlock: Block,
+	TExec: CodeExecutor + RuntimeVersionOf + Clone + 'static,
+{
+	fn storage_at(
+		&self,
+		id: &BlockId<TBlock>,
+		key: &[u8],
+	) -> sp_blockchain::Result<Option<Vec<u8>>> {
+		Ok(self.storage(id, &StorageKey(key.to_vec()))?.map(|data| data.0))
+	}
+
+	fn metadata_at(&self, id: &BlockId<TBlock>) -> sp_blockchain::Result<Vec<u8>> {
+		let metadata = self.executor().call(
+			id,
+			"Metadata_metadata",
+			&[],
+			ExecutionStrategy::NativeElseWasm,
+			None,
+		)?;
+		// The runtime API returns an `OpaqueMetadata`, i.e. the encoded metadata as `Vec<u8>`.
+		Decode::decode(&mut &metadata[..])
+			.map_err(|e| sp_blockchain::Error::CallResultDecode("Metadata_metadata", e))
+	}
+}
+
+/// A helper trait to access block headers, bodies and storage.
+pub trait ChainAccess<TBlock: Block>:
+	HeaderBackend<TBlock> + BlockBackend<TBlock> + StorageAccess<TBlock>
+{
+}
 
 impl<T, TBlock> ChainAccess<TBlock> for T
 where
 	TBlock: Block,
-	T: sp_blockchain::HeaderBackend<TBlock> + sc_client_api::BlockBackend<TBlock>,
+	T: sp_blockchain::HeaderBackend<TBlock>
+		+ sc_client_api::BlockBackend<TBlock>
+		+ StorageAccess<TBlock>,
 {
 }
 
@@ -130,16 +200,36 @@ impl<TBlock: Block, TPrinter: PrettyPrinter<TBlock>> Inspector<TBlock, TPrinter>
 	}
 
 	/// Get a pretty-printed block.
+	///
+	/// The events of the block are printed too, if it is retrieved from the database and its
+	/// state is still available, e.g. it hasn't been pruned.
 	pub fn block(&self, input: BlockAddressFor<TBlock>) -> Result<String, Error> {
-		struct BlockPrinter<'a, A, B>(A, &'a B);
+		struct BlockPrinter<'a, A, B>(A, Option<Result<Vec<String>, String>>, &'a B);
 		impl<'a, A: Block, B: PrettyPrinter<A>> fmt::Display for BlockPrinter<'a, A, B> {
 			fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-				self.1.fmt_block(fmt, &self.0)
+				self.2.fmt_block(fmt, &self.0)?;
+				match &self.1 {
+					Some(Ok(events)) => self.2.fmt_events(fmt, events),
+					Some(Err(e)) => writeln!(fmt, "Events unavailable: {}", e),
+					None => Ok(()),
+				}
 			}
 		}
 
+		let from_db = !matches!(input, BlockAddress::Bytes(_));
 		let block = self.get_block(input)?;
-		Ok(format!("{}", BlockPrinter(block, &self.printer)))
+		let events = if from_db { Some(self.events(&BlockId::hash(block.hash()))) } else { None };
+		Ok(format!("{}", BlockPrinter(block, events, &self.printer)))
+	}
+
+	/// The events of the block `id`, decoded with the metadata of its runtime.
+	fn events(&self, id: &BlockId<TBlock>) -> Result<Vec<String>, String> {
+		let metadata = self.chain.metadata_at(id).map_err(|e| e.to_string())?;
+		let metadata = EventsMetadata::new(&metadata)?;
+		match self.chain.storage_at(id, metadata.key()).map_err(|e| e.to_string())? {
+			Some(events) => metadata.decode(&events),
+			None => Ok(Vec::new()),
+		}
 	}
 
 	fn get_block(&self, input: BlockAddressFor<TBlock>) -> Result<TBlock, Error> {
@@ -74,4 +74,10 @@ pub mod time {
 	pub const MINUTES: BlockNumber = 60 / (SECS_PER_BLOCK as BlockNumber);
 	pub const HOURS: BlockNumber = MINUTES * 60;
 	pub const DAYS: BlockNumber = HOURS * 24;
+
+	// These time units are defined in milliseconds, and don't depend on the block time. They can be
+	// converted to blocks with `frame_support::traits::MillisInBlocks`.
+	pub const MILLISECS_PER_MINUTE: Moment = 60 * 1000;
+	pub const MILLISECS_PER_HOUR: Moment = MILLISECS_PER_MINUTE * 60;
+	pub const MILLISECS_PER_DAY: Moment = MILLISECS_PER_HOUR * 24;
 }
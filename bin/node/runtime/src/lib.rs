@@ -26,8 +26,9 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{
-		Currency, Everything, Imbalance, InstanceFilter, KeyOwnerProofSystem, LockIdentifier,
-		Nothing, OnUnbalanced, U128CurrencyToVote,
+		AsEnsureOriginWithArg, Currency, Everything, Imbalance, InstanceFilter,
+		KeyOwnerProofSystem, LockIdentifier, MillisInBlocks, Nothing, OnUnbalanced,
+		U128CurrencyToVote,
 	},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
@@ -37,7 +38,7 @@ use frame_support::{
 };
 use frame_system::{
 	limits::{BlockLength, BlockWeights},
-	EnsureOneOf, EnsureRoot,
+	EnsureOneOf, EnsureRoot, EnsureSigned,
 };
 pub use node_primitives::{AccountId, Signature};
 use node_primitives::{AccountIndex, Balance, BlockNumber, Hash, Index, Moment};
@@ -123,6 +124,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 2,
+	features: sp_version::runtime_features!(),
 };
 
 /// The BABE epoch configuration at genesis.
@@ -350,6 +352,7 @@ parameter_types! {
 	//       Attempting to do so will brick block production.
 	pub const EpochDuration: u64 = EPOCH_DURATION_IN_SLOTS;
 	pub const ExpectedBlockTime: Moment = MILLISECS_PER_BLOCK;
+	pub const MaxTimestampDrift: Moment = 0;
 	pub const ReportLongevity: u64 =
 		BondingDuration::get() as u64 * SessionsPerEra::get() as u64 * EpochDuration::get();
 }
@@ -357,6 +360,7 @@ parameter_types! {
 impl pallet_babe::Config for Runtime {
 	type EpochDuration = EpochDuration;
 	type ExpectedBlockTime = ExpectedBlockTime;
+	type MaxTimestampDrift = MaxTimestampDrift;
 	type EpochChangeTrigger = pallet_babe::ExternalTrigger;
 	type DisabledValidators = Session;
 
@@ -476,6 +480,7 @@ impl pallet_session::Config for Runtime {
 impl pallet_session::historical::Config for Runtime {
 	type FullIdentification = pallet_staking::Exposure<AccountId, Balance>;
 	type FullIdentificationOf = pallet_staking::ExposureOf<Runtime>;
+	type LegacyFullIdentification = ();
 }
 
 pallet_staking_reward_curve::build! {
@@ -495,7 +500,10 @@ parameter_types! {
 	pub const SlashDeferDuration: pallet_staking::EraIndex = 24 * 7; // 1/4 the bonding duration.
 	pub const RewardCurve: &'static PiecewiseLinear<'static> = &REWARD_CURVE;
 	pub const MaxNominatorRewardedPerValidator: u32 = 256;
+	pub const MaxSelfDisablesPerEra: u32 = 2;
 	pub OffchainRepeat: BlockNumber = 5;
+	pub const HighSeverityThreshold: Perbill = Perbill::from_percent(1);
+	pub const LowSeverityDisablingCap: Perbill = Perbill::from_percent(10);
 }
 
 use frame_election_provider_support::onchain;
@@ -523,9 +531,12 @@ impl pallet_staking::Config for Runtime {
 		pallet_collective::EnsureProportionAtLeast<_3, _4, AccountId, CouncilCollective>,
 	>;
 	type SessionInterface = Self;
+	type DisablingStrategy =
+		pallet_staking::SeverityBasedDisabling<HighSeverityThreshold, LowSeverityDisablingCap>;
 	type EraPayout = pallet_staking::ConvertCurve<RewardCurve>;
 	type NextNewSession = Session;
 	type MaxNominatorRewardedPerValidator = MaxNominatorRewardedPerValidator;
+	type MaxSelfDisablesPerEra = MaxSelfDisablesPerEra;
 	type ElectionProvider = ElectionProviderMultiPhase;
 	type GenesisElectionProvider = onchain::OnChainSequentialPhragmen<Self>;
 	// Alternatively, use pallet_staking::UseNominatorsMap<Runtime> to just use the nominators map.
@@ -660,19 +671,27 @@ impl pallet_bags_list::Config for Runtime {
 }
 
 parameter_types! {
-	pub const LaunchPeriod: BlockNumber = 28 * 24 * 60 * MINUTES;
-	pub const VotingPeriod: BlockNumber = 28 * 24 * 60 * MINUTES;
-	pub const FastTrackVotingPeriod: BlockNumber = 3 * 24 * 60 * MINUTES;
+	pub const LaunchDuration: Moment = 28 * MILLISECS_PER_DAY;
+	pub const VotingDuration: Moment = 28 * MILLISECS_PER_DAY;
+	pub const FastTrackVotingDuration: Moment = 3 * MILLISECS_PER_DAY;
 	pub const InstantAllowed: bool = true;
 	pub const MinimumDeposit: Balance = 100 * DOLLARS;
-	pub const EnactmentPeriod: BlockNumber = 30 * 24 * 60 * MINUTES;
-	pub const CooloffPeriod: BlockNumber = 28 * 24 * 60 * MINUTES;
+	pub const EnactmentDuration: Moment = 30 * MILLISECS_PER_DAY;
+	pub const CooloffDuration: Moment = 28 * MILLISECS_PER_DAY;
 	// One cent: $10,000 / MB
 	pub const PreimageByteDeposit: Balance = 1 * CENTS;
 	pub const MaxVotes: u32 = 100;
 	pub const MaxProposals: u32 = 100;
 }
 
+// The democracy periods are expressed in time, and converted to blocks using the expected block
+// time.
+pub type LaunchPeriod = MillisInBlocks<LaunchDuration, ExpectedBlockTime>;
+pub type VotingPeriod = MillisInBlocks<VotingDuration, ExpectedBlockTime>;
+pub type FastTrackVotingPeriod = MillisInBlocks<FastTrackVotingDuration, ExpectedBlockTime>;
+pub type EnactmentPeriod = MillisInBlocks<EnactmentDuration, ExpectedBlockTime>;
+pub type CooloffPeriod = MillisInBlocks<CooloffDuration, ExpectedBlockTime>;
+
 impl pallet_democracy::Config for Runtime {
 	type Proposal = Call;
 	type Event = Event;
@@ -1034,6 +1053,8 @@ impl pallet_grandpa::Config for Runtime {
 		ReportLongevity,
 	>;
 
+	type HandleEquivocationReward = ();
+
 	type WeightInfo = ();
 }
 
@@ -1049,7 +1070,7 @@ parameter_types! {
 impl pallet_identity::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
-	type BasicDeposit = BasicDeposit;
+	type BasicDeposit = pallet_parameters::Dynamic<Runtime, BasicDepositKey, BasicDeposit>;
 	type FieldDeposit = FieldDeposit;
 	type SubAccountDeposit = SubAccountDeposit;
 	type MaxSubAccounts = MaxSubAccounts;
@@ -1165,7 +1186,8 @@ impl pallet_assets::Config for Runtime {
 	type Balance = u64;
 	type AssetId = u32;
 	type Currency = Balances;
-	type ForceOrigin = EnsureRoot<AccountId>;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
+	type ForceOrigin = AsEnsureOriginWithArg<EnsureRoot<AccountId>>;
 	type AssetDeposit = AssetDeposit;
 	type MetadataDepositBase = MetadataDepositBase;
 	type MetadataDepositPerByte = MetadataDepositPerByte;
@@ -1217,7 +1239,8 @@ impl pallet_uniques::Config for Runtime {
 	type ClassId = u32;
 	type InstanceId = u32;
 	type Currency = Balances;
-	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
+	type ForceOrigin = AsEnsureOriginWithArg<EnsureRoot<AccountId>>;
 	type ClassDeposit = ClassDeposit;
 	type InstanceDeposit = InstanceDeposit;
 	type MetadataDepositBase = MetadataDepositBase;
@@ -1237,6 +1260,114 @@ impl pallet_transaction_storage::Config for Runtime {
 	type WeightInfo = pallet_transaction_storage::weights::SubstrateWeight<Runtime>;
 }
 
+/// The runtime parameters which can be updated by governance through `Parameters`.
+#[derive(
+	Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, MaxEncodedLen, scale_info::TypeInfo,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum RuntimeParameter {
+	/// The deposit reserved for an identity, which falls back to `BasicDeposit`.
+	IdentityBasicDeposit,
+}
+
+/// The values held by the runtime parameters.
+#[derive(
+	Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, MaxEncodedLen, scale_info::TypeInfo,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum RuntimeParameterValue {
+	/// An amount of the native currency.
+	Balance(Balance),
+}
+
+impl pallet_parameters::ParameterKey<RuntimeParameterValue> for RuntimeParameter {
+	fn accepts(&self, value: &RuntimeParameterValue) -> bool {
+		match (self, value) {
+			(RuntimeParameter::IdentityBasicDeposit, RuntimeParameterValue::Balance(_)) => true,
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn benchmark_parameter() -> (Self, RuntimeParameterValue) {
+		(
+			RuntimeParameter::IdentityBasicDeposit,
+			RuntimeParameterValue::Balance(BasicDeposit::get()),
+		)
+	}
+}
+
+impl sp_std::convert::TryFrom<RuntimeParameterValue> for Balance {
+	type Error = ();
+
+	fn try_from(value: RuntimeParameterValue) -> Result<Self, ()> {
+		match value {
+			RuntimeParameterValue::Balance(balance) => Ok(balance),
+		}
+	}
+}
+
+parameter_types! {
+	pub const BasicDepositKey: RuntimeParameter = RuntimeParameter::IdentityBasicDeposit;
+}
+
+impl pallet_parameters::Config for Runtime {
+	type Event = Event;
+	type Key = RuntimeParameter;
+	type Value = RuntimeParameterValue;
+	type AdminOrigin = EnsureRootOrHalfCouncil;
+	type WeightInfo = pallet_parameters::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub MigrationsMaxServiceWeight: Weight = Perbill::from_percent(80) *
+		RuntimeBlockWeights::get().max_block;
+}
+
+impl pallet_migrations::Config for Runtime {
+	type Event = Event;
+	type Migrations = ();
+	type MaxServiceWeight = MigrationsMaxServiceWeight;
+	type FailedMigrationHandler = ();
+	type WeightInfo = pallet_migrations::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const BridgedHeadersToKeep: u32 = 1024;
+}
+
+impl pallet_grandpa_bridge::Config for Runtime {
+	type Event = Event;
+	type BridgedBlockNumber = BlockNumber;
+	type BridgedHash = Hash;
+	type BridgedHeader = Header;
+	type HeadersToKeep = BridgedHeadersToKeep;
+	type WeightInfo = pallet_grandpa_bridge::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const MessageLanePalletId: PalletId = PalletId(*b"py/msgln");
+	pub const BridgedMessageLanePalletName: &'static str = "MessageLane";
+	pub const MaxMessagePayloadSize: u32 = 16 * 1024;
+	pub const MaxUnconfirmedMessages: pallet_message_lane::MessageNonce = 128;
+	pub const MaxUnrewardedRelayerEntries: u32 = 16;
+	pub const MaxMessagesToPruneAtOnce: pallet_message_lane::MessageNonce = 8;
+}
+
+impl pallet_message_lane::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type PalletId = MessageLanePalletId;
+	type BridgedHeader = Header;
+	type BridgedHeaderChain = GrandpaBridge;
+	type BridgedPalletName = BridgedMessageLanePalletName;
+	type MessageDispatch = ();
+	type MaxMessagePayloadSize = MaxMessagePayloadSize;
+	type MaxUnconfirmedMessages = MaxUnconfirmedMessages;
+	type MaxUnrewardedRelayerEntries = MaxUnrewardedRelayerEntries;
+	type MaxMessagesToPruneAtOnce = MaxMessagesToPruneAtOnce;
+	type WeightInfo = pallet_message_lane::weights::SubstrateWeight<Runtime>;
+}
+
 construct_runtime!(
 	pub enum Runtime where
 		Block = Block,
@@ -1284,6 +1415,10 @@ construct_runtime!(
 		Uniques: pallet_uniques::{Pallet, Call, Storage, Event<T>},
 		TransactionStorage: pallet_transaction_storage::{Pallet, Call, Storage, Inherent, Config<T>, Event<T>},
 		BagsList: pallet_bags_list::{Pallet, Call, Storage, Event<T>},
+		Parameters: pallet_parameters::{Pallet, Call, Storage, Event<T>, Config<T>},
+		Migrations: pallet_migrations::{Pallet, Call, Storage, Event},
+		GrandpaBridge: pallet_grandpa_bridge::{Pallet, Call, Storage, Event<T>, Config<T>},
+		MessageLane: pallet_message_lane::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -1538,6 +1673,26 @@ impl_runtime_apis! {
 		fn query_fee_details(uxt: <Block as BlockT>::Extrinsic, len: u32) -> FeeDetails<Balance> {
 			TransactionPayment::query_fee_details(uxt, len)
 		}
+		fn query_tip_for_priority(
+			uxt: <Block as BlockT>::Extrinsic,
+			len: u32,
+			priority: TransactionPriority,
+		) -> Balance {
+			TransactionPayment::query_tip_for_priority(uxt, len, priority)
+		}
+		fn query_max_block_length() -> u32 {
+			TransactionPayment::max_block_length()
+		}
+	}
+
+	impl pallet_staking_rpc_runtime_api::StakingDiagnosticsApi<Block, AccountId, Balance>
+		for Runtime
+	{
+		fn validator_diagnostics(
+			stash: AccountId,
+		) -> pallet_staking_rpc_runtime_api::ValidatorDiagnostics<Balance> {
+			Staking::validator_diagnostics(&stash)
+		}
 	}
 
 	impl pallet_mmr::primitives::MmrApi<
@@ -1589,6 +1744,10 @@ impl_runtime_apis! {
 			let weight = Executive::try_runtime_upgrade()?;
 			Ok((weight, RuntimeBlockWeights::get().max_block))
 		}
+
+		fn execute_block_no_check(block: Block) -> Weight {
+			Executive::execute_block_no_check(block)
+		}
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -1621,14 +1780,18 @@ impl_runtime_apis! {
 			list_benchmark!(list, extra, pallet_elections_phragmen, Elections);
 			list_benchmark!(list, extra, pallet_gilt, Gilt);
 			list_benchmark!(list, extra, pallet_grandpa, Grandpa);
+			list_benchmark!(list, extra, pallet_grandpa_bridge, GrandpaBridge);
 			list_benchmark!(list, extra, pallet_identity, Identity);
 			list_benchmark!(list, extra, pallet_im_online, ImOnline);
 			list_benchmark!(list, extra, pallet_indices, Indices);
 			list_benchmark!(list, extra, pallet_lottery, Lottery);
 			list_benchmark!(list, extra, pallet_membership, TechnicalMembership);
+			list_benchmark!(list, extra, pallet_message_lane, MessageLane);
+			list_benchmark!(list, extra, pallet_migrations, Migrations);
 			list_benchmark!(list, extra, pallet_mmr, Mmr);
 			list_benchmark!(list, extra, pallet_multisig, Multisig);
 			list_benchmark!(list, extra, pallet_offences, OffencesBench::<Runtime>);
+			list_benchmark!(list, extra, pallet_parameters, Parameters);
 			list_benchmark!(list, extra, pallet_proxy, Proxy);
 			list_benchmark!(list, extra, pallet_scheduler, Scheduler);
 			list_benchmark!(list, extra, pallet_session, SessionBench::<Runtime>);
@@ -1662,6 +1825,22 @@ impl_runtime_apis! {
 			impl pallet_session_benchmarking::Config for Runtime {}
 			impl pallet_offences_benchmarking::Config for Runtime {}
 			impl frame_system_benchmarking::Config for Runtime {}
+			impl pallet_message_lane::benchmarking::Config for Runtime {
+				fn import_bridged_header(state_root: Hash) -> Hash {
+					use sp_runtime::traits::Header as _;
+
+					let header = Header::new(
+						0,
+						Default::default(),
+						state_root,
+						Default::default(),
+						Default::default(),
+					);
+					let hash = header.hash();
+					pallet_grandpa_bridge::ImportedHeaders::<Runtime>::insert(hash, header);
+					hash
+				}
+			}
 
 			let whitelist: Vec<TrackedStorageKey> = vec![
 				// Block Number
@@ -1695,14 +1874,18 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, pallet_elections_phragmen, Elections);
 			add_benchmark!(params, batches, pallet_gilt, Gilt);
 			add_benchmark!(params, batches, pallet_grandpa, Grandpa);
+			add_benchmark!(params, batches, pallet_grandpa_bridge, GrandpaBridge);
 			add_benchmark!(params, batches, pallet_identity, Identity);
 			add_benchmark!(params, batches, pallet_im_online, ImOnline);
 			add_benchmark!(params, batches, pallet_indices, Indices);
 			add_benchmark!(params, batches, pallet_lottery, Lottery);
 			add_benchmark!(params, batches, pallet_membership, TechnicalMembership);
+			add_benchmark!(params, batches, pallet_message_lane, MessageLane);
+			add_benchmark!(params, batches, pallet_migrations, Migrations);
 			add_benchmark!(params, batches, pallet_mmr, Mmr);
 			add_benchmark!(params, batches, pallet_multisig, Multisig);
 			add_benchmark!(params, batches, pallet_offences, OffencesBench::<Runtime>);
+			add_benchmark!(params, batches, pallet_parameters, Parameters);
 			add_benchmark!(params, batches, pallet_proxy, Proxy);
 			add_benchmark!(params, batches, pallet_scheduler, Scheduler);
 			add_benchmark!(params, batches, pallet_session, SessionBench::<Runtime>);
@@ -1749,6 +1932,17 @@ mod tests {
 			maximum_chain_accuracy.iter().fold(0, |acc, x| acc.checked_add(*x).unwrap());
 	}
 
+	#[test]
+	fn democracy_periods_match_block_time() {
+		use frame_support::traits::Get;
+
+		assert_eq!(<LaunchPeriod as Get<BlockNumber>>::get(), 28 * DAYS);
+		assert_eq!(<VotingPeriod as Get<BlockNumber>>::get(), 28 * DAYS);
+		assert_eq!(<FastTrackVotingPeriod as Get<BlockNumber>>::get(), 3 * DAYS);
+		assert_eq!(<EnactmentPeriod as Get<BlockNumber>>::get(), 30 * DAYS);
+		assert_eq!(<CooloffPeriod as Get<BlockNumber>>::get(), 28 * DAYS);
+	}
+
 	#[test]
 	fn call_size() {
 		assert!(
@@ -1758,4 +1952,35 @@ mod tests {
 			If the limit is too strong, maybe consider increase the limit to 300.",
 		);
 	}
+
+	#[test]
+	fn metadata_describes_signed_extensions() {
+		use frame_support::metadata::RuntimeMetadata;
+
+		// The metadata of the dynamic parameters is read from the storage.
+		let metadata = sp_io::TestExternalities::default().execute_with(Runtime::metadata);
+		let metadata = match metadata.1 {
+			RuntimeMetadata::V14(metadata) => metadata,
+			_ => panic!("metadata has been bumped, test needs to be updated"),
+		};
+		let signed_extensions = &metadata.extrinsic.signed_extensions;
+
+		// wallets build the signed payload by encoding the extensions in this order.
+		assert_eq!(
+			signed_extensions.iter().map(|ext| ext.identifier.as_str()).collect::<Vec<_>>(),
+			vec![
+				"CheckSpecVersion",
+				"CheckTxVersion",
+				"CheckGenesis",
+				"CheckMortality",
+				"CheckNonce",
+				"CheckWeight",
+				"ChargeTransactionPayment",
+			],
+		);
+		for ext in signed_extensions {
+			assert!(metadata.types.resolve(ext.ty.id()).is_some());
+			assert!(metadata.types.resolve(ext.additional_signed.id()).is_some());
+		}
+	}
 }
@@ -100,5 +100,7 @@ pub fn config_endowed(
 		vesting: Default::default(),
 		gilt: Default::default(),
 		transaction_storage: Default::default(),
+		parameters: Default::default(),
+		grandpa_bridge: Default::default(),
 	}
 }
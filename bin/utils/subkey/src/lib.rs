@@ -17,8 +17,8 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use sc_cli::{
-	Error, GenerateCmd, GenerateNodeKeyCmd, InspectKeyCmd, InspectNodeKeyCmd, SignCmd, VanityCmd,
-	VerifyCmd,
+	Error, ExportKeysCmd, GenerateCmd, GenerateNodeKeyCmd, ImportKeysCmd, InspectKeyCmd,
+	InspectNodeKeyCmd, LedgerPayloadCmd, SignCmd, VanityCmd, VerifyCmd, VerifyLedgerCmd,
 };
 use structopt::StructOpt;
 
@@ -50,6 +50,19 @@ pub enum Subkey {
 
 	/// Verify a signature for a message, provided on STDIN, with a given (public or secret) key.
 	Verify(VerifyCmd),
+
+	/// Verify that the account shown by a Ledger device is derived from a given phrase.
+	VerifyLedger(VerifyLedgerCmd),
+
+	/// Export the signing payload of a transaction as the data of the sign APDUs of the Ledger
+	/// apps.
+	LedgerPayload(LedgerPayloadCmd),
+
+	/// Export the keys of the keystore of a node to a password protected key file.
+	Export(ExportKeysCmd),
+
+	/// Import the keys of a password protected key file to the keystore of a node.
+	Import(ImportKeysCmd),
 }
 
 /// Run the subkey command, given the appropriate runtime.
@@ -62,5 +75,9 @@ pub fn run() -> Result<(), Error> {
 		Subkey::Vanity(cmd) => cmd.run(),
 		Subkey::Verify(cmd) => cmd.run(),
 		Subkey::Sign(cmd) => cmd.run(),
+		Subkey::VerifyLedger(cmd) => cmd.run(),
+		Subkey::LedgerPayload(cmd) => cmd.run(),
+		Subkey::Export(cmd) => cmd.run(),
+		Subkey::Import(cmd) => cmd.run(),
 	}
 }
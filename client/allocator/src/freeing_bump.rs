@@ -320,6 +320,7 @@ impl IndexMut<Order> for FreeLists {
 ///
 /// Refer to the module-level documentation for further details.
 pub struct FreeingBumpHeapAllocator {
+	original_heap_base: u32,
 	bumper: u32,
 	free_lists: FreeLists,
 	total_size: u32,
@@ -329,13 +330,24 @@ pub struct FreeingBumpHeapAllocator {
 	last_observed_memory_size: u32,
 }
 
+/// Statistics of the heap usage of an allocator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocationStats {
+	/// The peak number of bytes allocated at once, including the allocation headers.
+	pub bytes_allocated_peak: u32,
+	/// The number of bytes of the heap which were ever used for allocations.
+	pub address_space_used: u32,
+}
+
 impl Drop for FreeingBumpHeapAllocator {
 	fn drop(&mut self) {
+		let stats = self.stats();
 		log::debug!(
 			target: LOG_TARGET,
-			"allocator being destroyed, max_total_size {}, max_bumper {}",
-			self.max_total_size,
-			self.max_bumper,
+			"allocator being destroyed, peak heap usage {} bytes, address space used {} bytes of {}",
+			stats.bytes_allocated_peak,
+			stats.address_space_used,
+			self.last_observed_memory_size.saturating_sub(self.original_heap_base),
 		)
 	}
 }
@@ -350,6 +362,7 @@ impl FreeingBumpHeapAllocator {
 		let aligned_heap_base = (heap_base + ALIGNMENT - 1) / ALIGNMENT * ALIGNMENT;
 
 		FreeingBumpHeapAllocator {
+			original_heap_base: aligned_heap_base,
 			bumper: aligned_heap_base,
 			free_lists: FreeLists::new(),
 			total_size: 0,
@@ -360,6 +373,14 @@ impl FreeingBumpHeapAllocator {
 		}
 	}
 
+	/// The statistics of the heap usage so far.
+	pub fn stats(&self) -> AllocationStats {
+		AllocationStats {
+			bytes_allocated_peak: self.max_total_size,
+			address_space_used: self.max_bumper - self.original_heap_base,
+		}
+	}
+
 	/// Gets requested number of bytes to allocate and returns a pointer.
 	/// The maximum size which can be allocated at once is 32 MiB.
 	/// There is no minimum size, but whatever size is passed into
@@ -861,6 +882,27 @@ mod tests {
 		assert_eq!(heap.total_size, 0);
 	}
 
+	#[test]
+	fn should_track_peak_heap_usage() {
+		// given
+		let mut mem = [0u8; PAGE_SIZE as usize];
+		let mut heap = FreeingBumpHeapAllocator::new(13);
+
+		// when
+		let ptr = heap.allocate(&mut mem[..], 42).unwrap();
+		heap.allocate(&mut mem[..], 9).unwrap();
+		heap.deallocate(&mut mem[..], ptr).unwrap();
+
+		// then
+		assert_eq!(
+			heap.stats(),
+			AllocationStats {
+				bytes_allocated_peak: (HEADER_SIZE + 64) + (HEADER_SIZE + 16),
+				address_space_used: (HEADER_SIZE + 64) + (HEADER_SIZE + 16),
+			},
+		);
+	}
+
 	#[test]
 	fn should_read_and_write_u64_correctly() {
 		// given
@@ -186,6 +186,8 @@ pub struct MemoryInfo {
 	pub state_cache: MemorySize,
 	/// Size of backend database cache.
 	pub database_cache: MemorySize,
+	/// Size of the trie node cache.
+	pub trie_cache: MemorySize,
 	/// Size of the state db.
 	pub state_db: StateDbMemoryInfo,
 }
@@ -215,6 +217,10 @@ pub struct IoInfo {
 	pub state_writes_cache: u64,
 	/// State write (trie nodes) to backend db.
 	pub state_writes_nodes: u64,
+	/// Trie node reads served by the trie node cache, since the client started.
+	pub trie_cache_hits: u64,
+	/// Trie node reads missing from the trie node cache, since the client started.
+	pub trie_cache_misses: u64,
 }
 
 /// Usage statistics for running client instance.
@@ -32,6 +32,12 @@ pub enum Error {
 	ReceivingDhtValueFoundEventWithNoRecords,
 	/// Failed to verify a dht payload with the given signature.
 	VerifyingDhtPayload,
+	/// Failed to verify the signature of a dht payload made with the network key of the node
+	/// it points to.
+	VerifyingPeerSignature,
+	/// Received a dht payload without a signature made with the network key of the node it
+	/// points to.
+	MissingPeerSignature,
 	/// Failed to hash the authority id to be used as a dht key.
 	HashingAuthorityId(libp2p::core::multiaddr::multihash::Error),
 	/// Failed calling into the Substrate runtime.
@@ -50,6 +56,8 @@ pub enum Error {
 	MissingSignature(CryptoTypePublicPair),
 	/// Failed to sign using all public keys.
 	Signing,
+	/// Failed to sign using the network key of the local node.
+	SigningWithNetworkKey(sc_network::SigningError),
 	/// Failed to register Prometheus metric.
 	Prometheus(prometheus_endpoint::PrometheusError),
 }
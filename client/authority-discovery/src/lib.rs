@@ -27,7 +27,7 @@
 //! See [`Worker`] and [`Service`] for more documentation.
 
 pub use crate::{
-	service::Service,
+	service::{AuthorityDiscovery, Service},
 	worker::{NetworkProvider, Role, Worker},
 };
 
@@ -75,6 +75,13 @@ pub struct WorkerConfig {
 	///
 	/// Defaults to `true` to avoid the surprise factor.
 	pub publish_non_global_ips: bool,
+
+	/// If `true`, addresses of other authorities are only accepted if they are signed with the
+	/// network key of the node they point to.
+	///
+	/// Defaults to `false`, in order to accept the addresses published by nodes which don't sign
+	/// them with their network key yet.
+	pub strict_record_validation: bool,
 }
 
 impl Default for WorkerConfig {
@@ -95,6 +102,7 @@ impl Default for WorkerConfig {
 			// `authority_discovery_dht_event_received`.
 			max_query_interval: Duration::from_secs(10 * 60),
 			publish_non_global_ips: true,
+			strict_record_validation: false,
 		}
 	}
 }
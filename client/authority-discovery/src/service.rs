@@ -20,6 +20,7 @@ use std::fmt::Debug;
 
 use crate::ServicetoWorkerMsg;
 
+use async_trait::async_trait;
 use futures::{
 	channel::{mpsc, oneshot},
 	SinkExt,
@@ -89,3 +90,35 @@ impl Service {
 		rx.await.ok().flatten()
 	}
 }
+
+/// Lookups of the addresses of authorities discovered via the DHT.
+///
+/// Other subsystems can depend on this trait rather than on [`Service`] directly, e.g. in order
+/// to be tested with a mock.
+#[async_trait]
+pub trait AuthorityDiscovery: Clone + Send {
+	/// Get the addresses for the given [`AuthorityId`], see
+	/// [`Service::get_addresses_by_authority_id`].
+	async fn get_addresses_by_authority_id(
+		&mut self,
+		authority: AuthorityId,
+	) -> Option<Vec<Multiaddr>>;
+
+	/// Get the [`AuthorityId`] for the given [`PeerId`], see
+	/// [`Service::get_authority_id_by_peer_id`].
+	async fn get_authority_id_by_peer_id(&mut self, peer_id: PeerId) -> Option<AuthorityId>;
+}
+
+#[async_trait]
+impl AuthorityDiscovery for Service {
+	async fn get_addresses_by_authority_id(
+		&mut self,
+		authority: AuthorityId,
+	) -> Option<Vec<Multiaddr>> {
+		Service::get_addresses_by_authority_id(self, authority).await
+	}
+
+	async fn get_authority_id_by_peer_id(&mut self, peer_id: PeerId) -> Option<AuthorityId> {
+		Service::get_authority_id_by_peer_id(self, peer_id).await
+	}
+}
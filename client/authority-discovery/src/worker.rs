@@ -87,9 +87,11 @@ pub enum Role {
 ///
 ///    2. Get the list of keys owned by the local node participating in the current authority set.
 ///
-///    3. Sign the addresses with the keys.
+///    3. Sign the addresses with the keys, and with the network key proving ownership of the peer
+///    id.
 ///
-///    4. Put addresses and signature as a record with the authority id as a key on a Kademlia DHT.
+///    4. Put addresses and signatures as a record with the authority id as a key on a Kademlia
+///    DHT.
 ///
 /// When constructed with either [`Role::PublishAndDiscover`] or [`Role::Discover`] a [`Worker`]
 /// will
@@ -98,7 +100,8 @@ pub enum Role {
 ///
 ///    2. Start DHT queries for the ids of the authorities.
 ///
-///    3. Validate the signatures of the retrieved key value pairs.
+///    3. Validate the signatures of the retrieved key value pairs, and drop the addresses that
+///    don't point to the node which signed them with its network key.
 ///
 ///    4. Add the retrieved external addresses as priority nodes to the
 ///    network peerset.
@@ -124,6 +127,8 @@ pub struct Worker<Client, Network, Block, DhtEventStream> {
 	latest_published_keys: HashSet<CryptoTypePublicPair>,
 	/// Same value as in the configuration.
 	publish_non_global_ips: bool,
+	/// Same value as in the configuration.
+	strict_record_validation: bool,
 
 	/// Interval at which to request addresses of authorities, refilling the pending lookups queue.
 	query_interval: ExpIncInterval,
@@ -197,6 +202,7 @@ where
 			publish_if_changed_interval,
 			latest_published_keys: HashSet::new(),
 			publish_non_global_ips: config.publish_non_global_ips,
+			strict_record_validation: config.strict_record_validation,
 			query_interval,
 			pending_lookups: Vec::new(),
 			in_flight_lookups: HashMap::new(),
@@ -327,6 +333,15 @@ where
 			.encode(&mut serialized_addresses)
 			.map_err(Error::EncodingProto)?;
 
+		let peer_signature = self
+			.network
+			.sign_with_local_identity(&serialized_addresses)
+			.map_err(Error::SigningWithNetworkKey)?;
+		let peer_signature = schema::PeerSignature {
+			signature: peer_signature.bytes,
+			public_key: peer_signature.public_key.into_protobuf_encoding(),
+		};
+
 		let keys_vec = keys.iter().cloned().collect::<Vec<_>>();
 		let signatures = key_store
 			.sign_with_all(
@@ -343,9 +358,13 @@ where
 			// Verify that all signatures exist for all provided keys.
 			let signature =
 				sign_result.ok().flatten().ok_or_else(|| Error::MissingSignature(key.clone()))?;
-			schema::SignedAuthorityAddresses { addresses: serialized_addresses.clone(), signature }
-				.encode(&mut signed_addresses)
-				.map_err(Error::EncodingProto)?;
+			schema::SignedAuthorityAddresses {
+				addresses: serialized_addresses.clone(),
+				signature,
+				peer_signature: Some(peer_signature.clone()),
+			}
+			.encode(&mut signed_addresses)
+			.map_err(Error::EncodingProto)?;
 
 			self.network.put_value(hash_authority_id(key.1.as_ref()), signed_addresses);
 		}
@@ -491,11 +510,12 @@ where
 			.ok_or(Error::ReceivingUnexpectedRecord)?;
 
 		let local_peer_id = self.network.local_peer_id();
+		let strict_record_validation = self.strict_record_validation;
 
 		let remote_addresses: Vec<Multiaddr> = values
 			.into_iter()
 			.map(|(_k, v)| {
-				let schema::SignedAuthorityAddresses { signature, addresses } =
+				let schema::SignedAuthorityAddresses { signature, addresses, peer_signature } =
 					schema::SignedAuthorityAddresses::decode(v.as_slice())
 						.map_err(Error::DecodingProto)?;
 
@@ -506,40 +526,44 @@ where
 					return Err(Error::VerifyingDhtPayload)
 				}
 
-				let addresses = schema::AuthorityAddresses::decode(addresses.as_slice())
-					.map(|a| a.addresses)
-					.map_err(Error::DecodingProto)?
-					.into_iter()
-					.map(|a| a.try_into())
-					.collect::<std::result::Result<_, _>>()
-					.map_err(Error::ParsingMultiaddress)?;
-
-				Ok(addresses)
+				// The node which signed the addresses with its network key, if any.
+				let signer = match peer_signature {
+					Some(peer_signature) => {
+						let public_key = libp2p::identity::PublicKey::from_protobuf_encoding(
+							&peer_signature.public_key,
+						)
+						.map_err(|_| Error::VerifyingPeerSignature)?;
+						let signer = public_key.clone().into_peer_id();
+						let signature =
+							sc_network::Signature { public_key, bytes: peer_signature.signature };
+						if !signature.verify(&addresses, &signer) {
+							return Err(Error::VerifyingPeerSignature)
+						}
+						Some(signer)
+					},
+					None if strict_record_validation => return Err(Error::MissingPeerSignature),
+					None => None,
+				};
+
+				let addresses: Vec<Multiaddr> =
+					schema::AuthorityAddresses::decode(addresses.as_slice())
+						.map(|a| a.addresses)
+						.map_err(Error::DecodingProto)?
+						.into_iter()
+						.map(|a| a.try_into())
+						.collect::<std::result::Result<_, _>>()
+						.map_err(Error::ParsingMultiaddress)?;
+
+				// Ignore addresses which don't point to the node which signed them.
+				Ok(addresses.into_iter().filter(move |addr| {
+					signer.map_or(true, |signer| peer_id_of(addr) == Some(signer))
+				}))
 			})
-			.collect::<Result<Vec<Vec<Multiaddr>>>>()?
+			.collect::<Result<Vec<_>>>()?
 			.into_iter()
 			.flatten()
 			// Ignore [`Multiaddr`]s without [`PeerId`] and own addresses.
-			.filter(|addr| {
-				addr.iter().any(|protocol| {
-					// Parse to PeerId first as Multihashes of old and new PeerId
-					// representation don't equal.
-					//
-					// See https://github.com/libp2p/rust-libp2p/issues/555 for
-					// details.
-					if let multiaddr::Protocol::P2p(hash) = protocol {
-						let peer_id = match PeerId::from_multihash(hash) {
-							Ok(peer_id) => peer_id,
-							Err(_) => return false, // Discard address.
-						};
-
-						// Discard if equal to local peer id, keep if it differs.
-						return !(peer_id == local_peer_id)
-					}
-
-					false // `protocol` is not a [`Protocol::P2p`], let's keep looking.
-				})
-			})
+			.filter(|addr| peer_id_of(addr).map_or(false, |peer_id| peer_id != local_peer_id))
 			.take(MAX_ADDRESSES_PER_AUTHORITY)
 			.collect();
 
@@ -598,6 +622,12 @@ pub trait NetworkProvider: NetworkStateInfo {
 
 	/// Start getting a value from the Dht.
 	fn get_value(&self, key: &libp2p::kad::record::Key);
+
+	/// Sign the given message with the network key of the local node.
+	fn sign_with_local_identity(
+		&self,
+		msg: &[u8],
+	) -> std::result::Result<sc_network::Signature, sc_network::SigningError>;
 }
 
 #[async_trait::async_trait]
@@ -612,12 +642,29 @@ where
 	fn get_value(&self, key: &libp2p::kad::record::Key) {
 		self.get_value(key)
 	}
+	fn sign_with_local_identity(
+		&self,
+		msg: &[u8],
+	) -> std::result::Result<sc_network::Signature, sc_network::SigningError> {
+		self.sign_with_local_identity(msg)
+	}
 }
 
 fn hash_authority_id(id: &[u8]) -> libp2p::kad::record::Key {
 	libp2p::kad::record::Key::new(&libp2p::multihash::Sha2_256::digest(id))
 }
 
+/// Returns the [`PeerId`] contained in the given [`Multiaddr`], if any.
+fn peer_id_of(addr: &Multiaddr) -> Option<PeerId> {
+	addr.iter().find_map(|protocol| match protocol {
+		// Parse to PeerId first as Multihashes of old and new PeerId representation don't equal.
+		//
+		// See https://github.com/libp2p/rust-libp2p/issues/555 for details.
+		multiaddr::Protocol::P2p(hash) => PeerId::from_multihash(hash).ok(),
+		_ => None,
+	})
+}
+
 /// Prometheus metrics for a [`Worker`].
 #[derive(Clone)]
 pub(crate) struct Metrics {
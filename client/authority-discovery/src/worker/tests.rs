@@ -31,7 +31,7 @@ use futures::{
 	sink::SinkExt,
 	task::LocalSpawn,
 };
-use libp2p::{core::multiaddr, kad, PeerId};
+use libp2p::{core::multiaddr, identity::Keypair, kad, PeerId};
 use prometheus_endpoint::prometheus::default_registry;
 
 use sp_api::{ApiRef, ProvideRuntimeApi};
@@ -118,6 +118,7 @@ pub enum TestNetworkEvent {
 
 pub struct TestNetwork {
 	peer_id: PeerId,
+	identity: Keypair,
 	external_addresses: Vec<Multiaddr>,
 	// Whenever functions on `TestNetwork` are called, the function arguments are added to the
 	// vectors below.
@@ -136,8 +137,10 @@ impl TestNetwork {
 impl Default for TestNetwork {
 	fn default() -> Self {
 		let (tx, rx) = mpsc::unbounded();
+		let identity = Keypair::generate_ed25519();
 		TestNetwork {
-			peer_id: PeerId::random(),
+			peer_id: identity.public().into_peer_id(),
+			identity,
 			external_addresses: vec!["/ip6/2001:db8::/tcp/30333".parse().unwrap()],
 			put_value_call: Default::default(),
 			get_value_call: Default::default(),
@@ -163,6 +166,12 @@ impl NetworkProvider for TestNetwork {
 			.unbounded_send(TestNetworkEvent::GetCalled(key.clone()))
 			.unwrap();
 	}
+	fn sign_with_local_identity(
+		&self,
+		msg: &[u8],
+	) -> std::result::Result<sc_network::Signature, sc_network::SigningError> {
+		sc_network::Signature::sign_message(msg, &self.identity)
+	}
 }
 
 impl NetworkStateInfo for TestNetwork {
@@ -179,6 +188,15 @@ async fn build_dht_event(
 	addresses: Vec<Multiaddr>,
 	public_key: AuthorityId,
 	key_store: &KeyStore,
+) -> (libp2p::kad::record::Key, Vec<u8>) {
+	build_dht_event_with_peer_signature(addresses, public_key, key_store, None).await
+}
+
+async fn build_dht_event_with_peer_signature(
+	addresses: Vec<Multiaddr>,
+	public_key: AuthorityId,
+	key_store: &KeyStore,
+	identity: Option<&Keypair>,
 ) -> (libp2p::kad::record::Key, Vec<u8>) {
 	let mut serialized_addresses = vec![];
 	schema::AuthorityAddresses { addresses: addresses.into_iter().map(|a| a.to_vec()).collect() }
@@ -196,10 +214,23 @@ async fn build_dht_event(
 		.unwrap()
 		.unwrap();
 
+	let peer_signature = identity.map(|identity| {
+		let signature =
+			sc_network::Signature::sign_message(&serialized_addresses, identity).unwrap();
+		schema::PeerSignature {
+			signature: signature.bytes,
+			public_key: signature.public_key.into_protobuf_encoding(),
+		}
+	});
+
 	let mut signed_addresses = vec![];
-	schema::SignedAuthorityAddresses { addresses: serialized_addresses.clone(), signature }
-		.encode(&mut signed_addresses)
-		.unwrap();
+	schema::SignedAuthorityAddresses {
+		addresses: serialized_addresses.clone(),
+		signature,
+		peer_signature,
+	}
+	.encode(&mut signed_addresses)
+	.unwrap();
 
 	let key = hash_authority_id(&public_key.to_raw_vec());
 	let value = signed_addresses;
@@ -567,6 +598,131 @@ fn do_not_cache_addresses_without_peer_id() {
 	);
 }
 
+fn discover_worker(
+	authority: AuthorityId,
+	config: crate::WorkerConfig,
+) -> Worker<TestApi, TestNetwork, Block, mpsc::Receiver<DhtEvent>> {
+	let (_dht_event_tx, dht_event_rx) = channel(1);
+	let (_to_worker, from_service) = mpsc::channel(0);
+	let mut worker = Worker::new(
+		from_service,
+		Arc::new(TestApi { authorities: vec![authority] }),
+		Arc::new(TestNetwork::default()),
+		dht_event_rx,
+		Role::Discover,
+		None,
+		config,
+	);
+
+	block_on(worker.refill_pending_lookups_queue()).unwrap();
+	worker.start_new_lookups();
+	worker
+}
+
+#[test]
+fn only_cache_addresses_of_the_node_signing_the_record() {
+	let remote_key_store = KeyStore::new();
+	let remote_public =
+		block_on(remote_key_store.sr25519_generate_new(key_types::AUTHORITY_DISCOVERY, None))
+			.unwrap();
+	let remote_identity = Keypair::generate_ed25519();
+	let remote_peer_id = remote_identity.public().into_peer_id();
+
+	let ip_address = "/ip6/2001:db8:0:0:0:0:0:2/tcp/30333"
+		.parse::<Multiaddr>()
+		.unwrap()
+		.with(multiaddr::Protocol::P2p(remote_peer_id.into()));
+	let dns_address = "/dns/example.com/tcp/30333"
+		.parse::<Multiaddr>()
+		.unwrap()
+		.with(multiaddr::Protocol::P2p(remote_peer_id.into()));
+	let other_node_address = "/ip6/2001:db8:0:0:0:0:0:3/tcp/30333"
+		.parse::<Multiaddr>()
+		.unwrap()
+		.with(multiaddr::Protocol::P2p(PeerId::random().into()));
+
+	let dht_event = block_on(build_dht_event_with_peer_signature(
+		vec![ip_address.clone(), dns_address.clone(), other_node_address],
+		remote_public.into(),
+		&remote_key_store,
+		Some(&remote_identity),
+	));
+
+	let mut worker = discover_worker(remote_public.into(), Default::default());
+	worker.handle_dht_value_found_event(vec![dht_event]).unwrap();
+
+	assert_eq!(
+		Some(&vec![ip_address, dns_address]),
+		worker.addr_cache.get_addresses_by_authority_id(&remote_public.into()),
+	);
+}
+
+#[test]
+fn reject_records_with_invalid_peer_signature() {
+	let remote_key_store = KeyStore::new();
+	let remote_public =
+		block_on(remote_key_store.sr25519_generate_new(key_types::AUTHORITY_DISCOVERY, None))
+			.unwrap();
+	let remote_identity = Keypair::generate_ed25519();
+	let address = "/ip6/2001:db8:0:0:0:0:0:2/tcp/30333"
+		.parse::<Multiaddr>()
+		.unwrap()
+		.with(multiaddr::Protocol::P2p(remote_identity.public().into_peer_id().into()));
+
+	let (key, value) = block_on(build_dht_event_with_peer_signature(
+		vec![address],
+		remote_public.into(),
+		&remote_key_store,
+		Some(&remote_identity),
+	));
+
+	// Claim the addresses were signed by another node.
+	let mut record = schema::SignedAuthorityAddresses::decode(value.as_slice()).unwrap();
+	record.peer_signature.as_mut().unwrap().public_key =
+		Keypair::generate_ed25519().public().into_protobuf_encoding();
+	let mut value = vec![];
+	record.encode(&mut value).unwrap();
+
+	let mut worker = discover_worker(remote_public.into(), Default::default());
+	assert!(matches!(
+		worker.handle_dht_value_found_event(vec![(key, value)]),
+		Err(Error::VerifyingPeerSignature)
+	));
+	assert!(worker.addr_cache.get_addresses_by_authority_id(&remote_public.into()).is_none());
+}
+
+#[test]
+fn strict_record_validation_rejects_records_without_peer_signature() {
+	let remote_key_store = KeyStore::new();
+	let remote_public =
+		block_on(remote_key_store.sr25519_generate_new(key_types::AUTHORITY_DISCOVERY, None))
+			.unwrap();
+	let address = "/ip6/2001:db8:0:0:0:0:0:2/tcp/30333"
+		.parse::<Multiaddr>()
+		.unwrap()
+		.with(multiaddr::Protocol::P2p(PeerId::random().into()));
+
+	let dht_event =
+		block_on(build_dht_event(vec![address.clone()], remote_public.into(), &remote_key_store));
+
+	let mut worker = discover_worker(
+		remote_public.into(),
+		crate::WorkerConfig { strict_record_validation: true, ..Default::default() },
+	);
+	assert!(matches!(
+		worker.handle_dht_value_found_event(vec![dht_event.clone()]),
+		Err(Error::MissingPeerSignature)
+	));
+
+	// Records without peer signature are accepted by default.
+	let mut worker = discover_worker(remote_public.into(), Default::default());
+	worker.handle_dht_value_found_event(vec![dht_event]).unwrap();
+	assert_eq!(
+		Some(&vec![address]),
+		worker.addr_cache.get_addresses_by_authority_id(&remote_public.into()),
+	);
+}
+
 #[test]
 fn addresses_to_publish_adds_p2p() {
 	let (_dht_event_tx, dht_event_rx) = channel(1000);
@@ -19,17 +19,29 @@
 //! Substrate chain configurations.
 #![warn(missing_docs)]
 
-use crate::{extension::GetExtension, ChainType, Properties, RuntimeGenesis};
+use crate::{
+	extension::GetExtension, genesis_config_builder::GenesisConfigBuilderRuntimeCaller, ChainType,
+	Properties, RuntimeGenesis,
+};
 use sc_network::config::MultiaddrWithPeerId;
 use sc_telemetry::TelemetryEndpoints;
 use serde::{Deserialize, Serialize};
 use serde_json as json;
 use sp_core::{
-	storage::{ChildInfo, Storage, StorageChild, StorageData, StorageKey},
-	Bytes,
+	storage::{well_known_keys, ChildInfo, Storage, StorageChild, StorageData, StorageKey},
+	Bytes, H256,
+};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	BuildStorage,
+};
+use std::{
+	borrow::Cow,
+	collections::HashMap,
+	fs::File,
+	path::PathBuf,
+	sync::{Arc, Mutex},
 };
-use sp_runtime::BuildStorage;
-use std::{borrow::Cow, collections::HashMap, fs::File, path::PathBuf, sync::Arc};
 
 enum GenesisSource<G> {
 	File(PathBuf),
@@ -70,30 +82,7 @@ impl<G: RuntimeGenesis> GenesisSource<G> {
 				Ok(genesis.genesis)
 			},
 			Self::Factory(f) => Ok(Genesis::Runtime(f())),
-			Self::Storage(storage) => {
-				let top = storage
-					.top
-					.iter()
-					.map(|(k, v)| (StorageKey(k.clone()), StorageData(v.clone())))
-					.collect();
-
-				let children_default = storage
-					.children_default
-					.iter()
-					.map(|(k, child)| {
-						(
-							StorageKey(k.clone()),
-							child
-								.data
-								.iter()
-								.map(|(k, v)| (StorageKey(k.clone()), StorageData(v.clone())))
-								.collect(),
-						)
-					})
-					.collect();
-
-				Ok(Genesis::Raw(RawGenesis { top, children_default }))
-			},
+			Self::Storage(storage) => Ok(Genesis::Raw(storage.clone().into())),
 		}
 	}
 }
@@ -137,6 +126,69 @@ pub struct RawGenesis {
 	pub children_default: HashMap<StorageKey, GenesisStorage>,
 }
 
+impl From<Storage> for RawGenesis {
+	fn from(storage: Storage) -> Self {
+		let top = storage.top.into_iter().map(|(k, v)| (StorageKey(k), StorageData(v))).collect();
+
+		let children_default = storage
+			.children_default
+			.into_iter()
+			.map(|(sk, child)| {
+				(
+					StorageKey(sk),
+					child.data.into_iter().map(|(k, v)| (StorageKey(k), StorageData(v))).collect(),
+				)
+			})
+			.collect();
+
+		RawGenesis { top, children_default }
+	}
+}
+
+/// Hashes identifying the genesis state of a chain.
+///
+/// They are computed assuming the chain uses Blake2-256 for its state trie, like all FRAME based
+/// chains do by default.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GenesisHashes {
+	/// The root of the genesis state, i.e. the state root of the genesis block.
+	pub state_root: H256,
+	/// The Blake2-256 hash of the runtime code of the genesis state, if any.
+	pub runtime_hash: Option<H256>,
+}
+
+impl GenesisHashes {
+	/// Compute the hashes of the given genesis `storage`.
+	pub fn from_storage(storage: &Storage) -> Self {
+		let mut top = storage.top.clone();
+		for child in storage.children_default.values() {
+			let child_root = BlakeTwo256::trie_root(
+				child.data.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+			);
+			// Empty child tries are not part of the state.
+			if child_root != empty_trie_root() {
+				top.insert(
+					child.child_info.prefixed_storage_key().into_inner(),
+					child_root.as_ref().to_vec(),
+				);
+			}
+		}
+
+		GenesisHashes {
+			state_root: BlakeTwo256::trie_root(top.into_iter().collect()),
+			runtime_hash: storage
+				.top
+				.get(well_known_keys::CODE)
+				.map(|code| BlakeTwo256::hash(code)),
+		}
+	}
+}
+
+fn empty_trie_root() -> H256 {
+	BlakeTwo256::trie_root(Vec::new())
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(deny_unknown_fields)]
@@ -170,6 +222,9 @@ struct ClientSpec<E> {
 	/// block hash onwards.
 	#[serde(default)]
 	code_substitutes: HashMap<String, Bytes>,
+	/// Hashes of the genesis state, recorded when the genesis is stored raw.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	genesis_hashes: Option<GenesisHashes>,
 }
 
 /// A type denoting empty extensions.
@@ -181,11 +236,17 @@ pub type NoExtension = Option<()>;
 pub struct ChainSpec<G, E = NoExtension> {
 	client_spec: ClientSpec<E>,
 	genesis: GenesisSource<G>,
+	/// Hashes computed the last time the genesis storage was built for a raw dump.
+	computed_genesis_hashes: Mutex<Option<GenesisHashes>>,
 }
 
 impl<G, E: Clone> Clone for ChainSpec<G, E> {
 	fn clone(&self) -> Self {
-		ChainSpec { client_spec: self.client_spec.clone(), genesis: self.genesis.clone() }
+		ChainSpec {
+			client_spec: self.client_spec.clone(),
+			genesis: self.genesis.clone(),
+			computed_genesis_hashes: Mutex::new(self.computed_genesis_hashes()),
+		}
 	}
 }
 
@@ -222,6 +283,22 @@ impl<G, E> ChainSpec<G, E> {
 		self.client_spec.properties.as_ref().unwrap_or(&json::map::Map::new()).clone()
 	}
 
+	/// Hashes of the genesis state of the chain spec.
+	///
+	/// These are the hashes recorded in a raw chain spec generated by `build-spec --raw`, or
+	/// otherwise the ones computed when this chain spec was last dumped raw. Returns `None` if
+	/// neither applies, as computing them requires building the genesis storage.
+	pub fn genesis_hashes(&self) -> Option<GenesisHashes> {
+		self.client_spec
+			.genesis_hashes
+			.clone()
+			.or_else(|| self.computed_genesis_hashes())
+	}
+
+	fn computed_genesis_hashes(&self) -> Option<GenesisHashes> {
+		self.computed_genesis_hashes.lock().unwrap_or_else(|e| e.into_inner()).clone()
+	}
+
 	/// Add a bootnode to the list.
 	pub fn add_boot_node(&mut self, addr: MultiaddrWithPeerId) {
 		self.client_spec.boot_nodes.push(addr)
@@ -261,9 +338,14 @@ impl<G, E> ChainSpec<G, E> {
 			consensus_engine: (),
 			genesis: Default::default(),
 			code_substitutes: HashMap::new(),
+			genesis_hashes: None,
 		};
 
-		ChainSpec { client_spec, genesis: GenesisSource::Factory(Arc::new(constructor)) }
+		ChainSpec {
+			client_spec,
+			genesis: GenesisSource::Factory(Arc::new(constructor)),
+			computed_genesis_hashes: Mutex::new(None),
+		}
 	}
 
 	/// Type of the chain.
@@ -278,7 +360,11 @@ impl<G, E: serde::de::DeserializeOwned> ChainSpec<G, E> {
 		let json = json.into();
 		let client_spec = json::from_slice(json.as_ref())
 			.map_err(|e| format!("Error parsing spec file: {}", e))?;
-		Ok(ChainSpec { client_spec, genesis: GenesisSource::Binary(json) })
+		Ok(ChainSpec {
+			client_spec,
+			genesis: GenesisSource::Binary(json),
+			computed_genesis_hashes: Mutex::new(None),
+		})
 	}
 
 	/// Parse json file into a `ChainSpec`
@@ -286,7 +372,11 @@ impl<G, E: serde::de::DeserializeOwned> ChainSpec<G, E> {
 		let file = File::open(&path).map_err(|e| format!("Error opening spec file: {}", e))?;
 		let client_spec =
 			json::from_reader(file).map_err(|e| format!("Error parsing spec file: {}", e))?;
-		Ok(ChainSpec { client_spec, genesis: GenesisSource::File(path) })
+		Ok(ChainSpec {
+			client_spec,
+			genesis: GenesisSource::File(path),
+			computed_genesis_hashes: Mutex::new(None),
+		})
 	}
 }
 
@@ -299,31 +389,24 @@ struct JsonContainer<G, E> {
 
 impl<G: RuntimeGenesis, E: serde::Serialize + Clone + 'static> ChainSpec<G, E> {
 	fn json_container(&self, raw: bool) -> Result<JsonContainer<G, E>, String> {
-		let genesis = match (raw, self.genesis.resolve()?) {
-			(true, Genesis::Runtime(g)) => {
-				let storage = g.build_storage()?;
-				let top =
-					storage.top.into_iter().map(|(k, v)| (StorageKey(k), StorageData(v))).collect();
-				let children_default = storage
-					.children_default
-					.into_iter()
-					.map(|(sk, child)| {
-						(
-							StorageKey(sk),
-							child
-								.data
-								.into_iter()
-								.map(|(k, v)| (StorageKey(k), StorageData(v)))
-								.collect(),
-						)
-					})
-					.collect();
-
-				Genesis::Raw(RawGenesis { top, children_default })
-			},
-			(_, genesis) => genesis,
+		let mut client_spec = self.client_spec.clone();
+		let genesis = if raw {
+			let storage = self.build_storage()?;
+			let hashes = match self.genesis_hashes() {
+				Some(hashes) => hashes,
+				None => {
+					let hashes = GenesisHashes::from_storage(&storage);
+					*self.computed_genesis_hashes.lock().unwrap_or_else(|e| e.into_inner()) =
+						Some(hashes.clone());
+					hashes
+				},
+			};
+			client_spec.genesis_hashes = Some(hashes);
+			Genesis::Raw(storage.into())
+		} else {
+			self.genesis.resolve()?
 		};
-		Ok(JsonContainer { client_spec: self.client_spec.clone(), genesis })
+		Ok(JsonContainer { client_spec, genesis })
 	}
 
 	/// Dump to json string.
@@ -333,6 +416,108 @@ impl<G: RuntimeGenesis, E: serde::Serialize + Clone + 'static> ChainSpec<G, E> {
 	}
 }
 
+/// Builder of a raw chain spec, of which the genesis storage is built by the `GenesisBuilder`
+/// runtime api of a wasm blob.
+///
+/// The node doesn't need to link the native runtime to generate the genesis of a chain, the
+/// default genesis config of the runtime being patched by the given JSON.
+pub struct ChainSpecBuilder<E = NoExtension> {
+	code: Vec<u8>,
+	genesis_config_patch: json::Value,
+	client_spec: ClientSpec<E>,
+}
+
+impl<E> ChainSpecBuilder<E> {
+	/// Create a builder of the chain spec of the runtime `code`, with the given extensions.
+	pub fn new(code: &[u8], extensions: E) -> Self {
+		ChainSpecBuilder {
+			code: code.to_vec(),
+			genesis_config_patch: json::Value::Object(Default::default()),
+			client_spec: ClientSpec {
+				name: "Development".into(),
+				id: "dev".into(),
+				chain_type: ChainType::Development,
+				boot_nodes: Vec::new(),
+				telemetry_endpoints: None,
+				protocol_id: None,
+				properties: None,
+				extensions,
+				consensus_engine: (),
+				genesis: Default::default(),
+				code_substitutes: HashMap::new(),
+				genesis_hashes: None,
+			},
+		}
+	}
+
+	/// Set the name of the chain.
+	pub fn with_name(mut self, name: &str) -> Self {
+		self.client_spec.name = name.into();
+		self
+	}
+
+	/// Set the id of the chain.
+	pub fn with_id(mut self, id: &str) -> Self {
+		self.client_spec.id = id.into();
+		self
+	}
+
+	/// Set the type of the chain.
+	pub fn with_chain_type(mut self, chain_type: ChainType) -> Self {
+		self.client_spec.chain_type = chain_type;
+		self
+	}
+
+	/// Set the bootnodes of the chain.
+	pub fn with_boot_nodes(mut self, boot_nodes: Vec<MultiaddrWithPeerId>) -> Self {
+		self.client_spec.boot_nodes = boot_nodes;
+		self
+	}
+
+	/// Set the telemetry endpoints of the chain.
+	pub fn with_telemetry_endpoints(mut self, endpoints: TelemetryEndpoints) -> Self {
+		self.client_spec.telemetry_endpoints = Some(endpoints);
+		self
+	}
+
+	/// Set the network protocol id of the chain.
+	pub fn with_protocol_id(mut self, protocol_id: &str) -> Self {
+		self.client_spec.protocol_id = Some(protocol_id.into());
+		self
+	}
+
+	/// Set the properties of the chain.
+	pub fn with_properties(mut self, properties: Properties) -> Self {
+		self.client_spec.properties = Some(properties);
+		self
+	}
+
+	/// Set the patch merged into the default genesis config of the runtime.
+	///
+	/// The objects are merged recursively, any other value replacing the default one.
+	pub fn with_genesis_config_patch(mut self, patch: json::Value) -> Self {
+		self.genesis_config_patch = patch;
+		self
+	}
+}
+
+impl<E: serde::Serialize> ChainSpecBuilder<E> {
+	/// Build the genesis storage by the runtime and dump the raw chain spec to a json string.
+	///
+	/// The runtime code is written to `:code`, overriding any code of the genesis config.
+	pub fn build(self) -> Result<String, String> {
+		let mut storage = GenesisConfigBuilderRuntimeCaller::new(&self.code)
+			.get_storage_for_patch(self.genesis_config_patch)?;
+		storage.top.insert(well_known_keys::CODE.to_vec(), self.code);
+
+		let mut client_spec = self.client_spec;
+		client_spec.genesis_hashes = Some(GenesisHashes::from_storage(&storage));
+		let container =
+			JsonContainer::<(), E> { client_spec, genesis: Genesis::Raw(storage.into()) };
+		json::to_string_pretty(&container).map_err(|e| format!("Error generating spec json: {}", e))
+	}
+}
+
 impl<G, E> crate::ChainSpec for ChainSpec<G, E>
 where
 	G: RuntimeGenesis + 'static,
@@ -382,6 +567,10 @@ where
 		ChainSpec::as_json(self, raw)
 	}
 
+	fn genesis_hashes(&self) -> Option<GenesisHashes> {
+		ChainSpec::genesis_hashes(self)
+	}
+
 	fn as_storage_builder(&self) -> &dyn BuildStorage {
 		self
 	}
@@ -392,6 +581,9 @@ where
 
 	fn set_storage(&mut self, storage: Storage) {
 		self.genesis = GenesisSource::Storage(storage);
+		// The known hashes no longer match the genesis.
+		self.client_spec.genesis_hashes = None;
+		*self.computed_genesis_hashes.get_mut().unwrap_or_else(|e| e.into_inner()) = None;
 	}
 
 	fn code_substitutes(&self) -> std::collections::HashMap<String, Vec<u8>> {
@@ -450,4 +642,57 @@ mod tests {
 
 		assert_eq!(spec.extensions().my_property, "Test Extension");
 	}
+
+	#[test]
+	fn raw_chain_spec_records_genesis_hashes() {
+		let spec = TestSpec::from_genesis(
+			"Test",
+			"test",
+			ChainType::Local,
+			|| Genesis(vec![(":code".to_string(), "wasm".to_string())].into_iter().collect()),
+			Vec::new(),
+			None,
+			None,
+			None,
+			None,
+		);
+		assert!(spec.genesis_hashes().is_none());
+
+		let raw = TestSpec::from_json_bytes(spec.as_json(true).unwrap().into_bytes()).unwrap();
+		let hashes = raw.genesis_hashes().unwrap();
+		assert_eq!(hashes, GenesisHashes::from_storage(&spec.build_storage().unwrap()));
+		assert_eq!(hashes.runtime_hash, Some(BlakeTwo256::hash(b"wasm")));
+		// The hashes computed for the raw dump are kept, and dropped with the genesis.
+		assert_eq!(spec.genesis_hashes(), Some(hashes));
+		let mut spec = spec;
+		crate::ChainSpec::set_storage(&mut spec, Default::default());
+		assert!(spec.genesis_hashes().is_none());
+
+		// The hashes are not recorded for a non-raw genesis.
+		let json: json::Value = json::from_str(&spec.as_json(false).unwrap()).unwrap();
+		assert!(json.get("genesisHashes").is_none());
+	}
+
+	#[test]
+	fn chain_spec_builder_emits_raw_chain_spec() {
+		let code = substrate_test_runtime::wasm_binary_unwrap();
+		let json = ChainSpecBuilder::<NoExtension>::new(code, None)
+			.with_name("Test")
+			.with_id("test")
+			.with_chain_type(ChainType::Local)
+			.with_genesis_config_patch(json::json!({ "heapPages": 32 }))
+			.build()
+			.unwrap();
+
+		let spec = TestSpec::from_json_bytes(json.into_bytes()).unwrap();
+		assert_eq!(spec.name(), "Test");
+		assert_eq!(spec.id(), "test");
+		let storage = spec.build_storage().unwrap();
+		assert_eq!(storage.top.get(well_known_keys::CODE), Some(&code.to_vec()));
+		assert_eq!(
+			storage.top.get(well_known_keys::HEAP_PAGES),
+			Some(&codec::Encode::encode(&32u64)),
+		);
+		assert_eq!(spec.genesis_hashes(), Some(GenesisHashes::from_storage(&storage)));
+	}
 }
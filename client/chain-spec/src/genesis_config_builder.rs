@@ -0,0 +1,154 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Building of the genesis storage by the `GenesisBuilder` runtime api of a wasm blob.
+
+use codec::{Decode, Encode};
+use sc_executor::{sp_wasm_interface::HostFunctions, WasmExecutionMethod, WasmExecutor};
+use sc_executor_common::runtime_blob::RuntimeBlob;
+use serde_json::Value;
+use sp_core::storage::Storage;
+use sp_runtime::RuntimeString;
+use sp_state_machine::BasicExternalities;
+
+/// Calls the `GenesisBuilder` runtime api of a wasm blob, without the native runtime.
+pub struct GenesisConfigBuilderRuntimeCaller {
+	code: Vec<u8>,
+	executor: WasmExecutor,
+}
+
+impl GenesisConfigBuilderRuntimeCaller {
+	/// Create a caller of the api of the given, possibly compressed, wasm blob.
+	pub fn new(code: &[u8]) -> Self {
+		GenesisConfigBuilderRuntimeCaller {
+			code: code.to_vec(),
+			executor: WasmExecutor::new(
+				WasmExecutionMethod::Interpreted,
+				None,
+				sp_io::SubstrateHostFunctions::host_functions(),
+				1,
+				None,
+			),
+		}
+	}
+
+	fn call(
+		&self,
+		ext: &mut BasicExternalities,
+		method: &str,
+		data: &[u8],
+	) -> Result<Vec<u8>, String> {
+		let blob = RuntimeBlob::uncompress_if_needed(&self.code)
+			.map_err(|e| format!("Invalid runtime code: {}", e))?;
+		self.executor
+			.uncached_call(blob, ext, true, method, data)
+			.map_err(|e| format!("Failed to call `{}`: {}", method, e))
+	}
+
+	/// The default genesis config of the runtime.
+	pub fn get_default_config(&self) -> Result<Value, String> {
+		let mut ext = BasicExternalities::new_empty();
+		let encoded = self.call(&mut ext, "GenesisBuilder_create_default_config", &[])?;
+		let json = Vec::<u8>::decode(&mut &encoded[..])
+			.map_err(|e| format!("Failed to decode the default genesis config: {}", e))?;
+		serde_json::from_slice(&json).map_err(|e| format!("Invalid default genesis config: {}", e))
+	}
+
+	/// Build the genesis storage from the given complete genesis `config`.
+	pub fn get_storage_for_config(&self, config: Value) -> Result<Storage, String> {
+		let mut ext = BasicExternalities::new_empty();
+		let json = config.to_string().into_bytes();
+		let encoded = self.call(&mut ext, "GenesisBuilder_build_config", &json.encode())?;
+		Result::<(), RuntimeString>::decode(&mut &encoded[..])
+			.map_err(|e| format!("Failed to decode the result of the genesis build: {}", e))?
+			.map_err(|e| format!("Failed to build the genesis storage: {}", e))?;
+		Ok(ext.into_storages())
+	}
+
+	/// Build the genesis storage from the default genesis config of the runtime, merged with
+	/// `patch`.
+	pub fn get_storage_for_patch(&self, patch: Value) -> Result<Storage, String> {
+		let mut config = self.get_default_config()?;
+		json_merge(&mut config, patch);
+		self.get_storage_for_config(config)
+	}
+}
+
+/// Merge `patch` into `config`, the objects being merged recursively and any other value of
+/// `patch` replacing the one of `config`.
+pub(crate) fn json_merge(config: &mut Value, patch: Value) {
+	match (config, patch) {
+		(Value::Object(config), Value::Object(patch)) =>
+			for (key, value) in patch {
+				json_merge(config.entry(key).or_insert(Value::Null), value);
+			},
+		(config, patch) => *config = patch,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+	use sp_core::{hashing::blake2_256, storage::well_known_keys};
+
+	#[test]
+	fn patch_is_merged_recursively() {
+		let mut config = json!({
+			"balances": { "balances": [["alice", 1]] },
+			"sudo": { "key": "alice" },
+			"system": { "code": "0x00" },
+		});
+		json_merge(
+			&mut config,
+			json!({
+				"balances": { "balances": [["bob", 2]] },
+				"sudo": { "key": "bob", "extra": true },
+			}),
+		);
+
+		assert_eq!(
+			config,
+			json!({
+				"balances": { "balances": [["bob", 2]] },
+				"sudo": { "key": "bob", "extra": true },
+				"system": { "code": "0x00" },
+			}),
+		);
+	}
+
+	#[test]
+	fn genesis_storage_is_built_by_the_wasm_runtime() {
+		let caller =
+			GenesisConfigBuilderRuntimeCaller::new(substrate_test_runtime::wasm_binary_unwrap());
+		assert_eq!(
+			caller.get_default_config().unwrap(),
+			json!({ "authorities": [], "balances": [], "heapPages": null }),
+		);
+
+		let account = [1u8; 32];
+		let storage = caller
+			.get_storage_for_patch(json!({ "balances": [[account, 100]], "heapPages": 32 }))
+			.unwrap();
+		let balance_key = blake2_256(&[&b"balance:"[..], &account[..]].concat());
+		assert_eq!(storage.top.get(&balance_key[..]), Some(&100u64.encode()));
+		assert_eq!(storage.top.get(well_known_keys::HEAP_PAGES), Some(&32u64.encode()));
+
+		assert!(caller.get_storage_for_patch(json!({ "unknown": 1 })).is_err());
+	}
+}
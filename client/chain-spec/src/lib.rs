@@ -106,14 +106,21 @@
 //!
 //! pub type MyChainSpec<G> = GenericChainSpec<G, Extension>;
 //! ```
+//!
+//! A raw chain spec can also be built by [`ChainSpecBuilder`] from the wasm blob of a runtime
+//! implementing the `GenesisBuilder` api of `sp-genesis-builder`, without the native runtime.
 
 mod chain_spec;
 mod extension;
+mod genesis_config_builder;
+mod reverse;
 
-pub use chain_spec::{ChainSpec as GenericChainSpec, NoExtension};
+pub use chain_spec::{ChainSpec as GenericChainSpec, ChainSpecBuilder, GenesisHashes, NoExtension};
 pub use extension::{
 	get_extension, get_extension_mut, Extension, Fork, Forks, GetExtension, Group,
 };
+pub use genesis_config_builder::GenesisConfigBuilderRuntimeCaller;
+pub use reverse::{reverse_storage, StorageNames};
 pub use sc_chain_spec_derive::{ChainSpecExtension, ChainSpecGroup};
 
 use sc_network::config::MultiaddrWithPeerId;
@@ -187,6 +194,10 @@ pub trait ChainSpec: BuildStorage + Send + Sync {
 	fn set_storage(&mut self, storage: Storage);
 	/// Returns code substitutes that should be used for the on chain wasm.
 	fn code_substitutes(&self) -> std::collections::HashMap<String, Vec<u8>>;
+	/// Hashes of the genesis state, if recorded in the spec or computed by a raw `as_json`.
+	///
+	/// The genesis storage is not built to compute them.
+	fn genesis_hashes(&self) -> Option<GenesisHashes>;
 }
 
 impl std::fmt::Debug for dyn ChainSpec {
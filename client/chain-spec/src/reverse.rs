@@ -0,0 +1,179 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Grouping of a raw genesis storage by storage item.
+//!
+//! The keys of the storage items of FRAME pallets start with the `twox_128` hashes of the prefix
+//! of the pallet and of the name of the item. Given the names of the items of the runtime, the raw
+//! keys are grouped by pallet and item, while the keys of no known item are kept raw.
+//!
+//! The values aren't decoded, this doesn't recover the genesis config of the pallets.
+
+use serde_json::{Map, Value};
+use sp_core::{hashing::twox_128, storage::Storage};
+use std::collections::HashMap;
+
+/// Names of the storage items of a runtime, indexed by the hashed prefix of their keys.
+#[derive(Debug, Clone, Default)]
+pub struct StorageNames {
+	items: HashMap<[u8; 32], (String, String)>,
+}
+
+impl StorageNames {
+	/// Create the names from the prefixes of the pallets and the names of their storage items.
+	pub fn new(items: impl IntoIterator<Item = (String, String)>) -> Self {
+		let items = items
+			.into_iter()
+			.map(|(pallet, item)| {
+				let mut prefix = [0u8; 32];
+				prefix[..16].copy_from_slice(&twox_128(pallet.as_bytes()));
+				prefix[16..].copy_from_slice(&twox_128(item.as_bytes()));
+				(prefix, (pallet, item))
+			})
+			.collect();
+		StorageNames { items }
+	}
+
+	/// The pallet and the name of the item `key` belongs to, along with the rest of the key.
+	fn lookup<'a>(&self, key: &'a [u8]) -> Option<(&str, &str, &'a [u8])> {
+		if key.len() < 32 {
+			return None
+		}
+		let mut prefix = [0u8; 32];
+		prefix.copy_from_slice(&key[..32]);
+		self.items
+			.get(&prefix)
+			.map(|(pallet, item)| (pallet.as_str(), item.as_str(), &key[32..]))
+	}
+}
+
+/// Group a raw genesis `storage` by storage item in a JSON value, as far as `names` allow.
+///
+/// The storage values stay SCALE encoded, hex encoded in the JSON:
+/// - `wellKnown` contains the well known keys like `:code`,
+/// - `pallets` contains the known items by pallet and name, the values of the maps being given by
+///   the rest of their keys,
+/// - `unknown` contains the keys of no known item,
+/// - `childrenDefault` contains the default child tries.
+pub fn reverse_storage(storage: &Storage, names: &StorageNames) -> Value {
+	let mut well_known = Map::new();
+	let mut pallets = Map::new();
+	let mut unknown = Map::new();
+
+	for (key, value) in &storage.top {
+		let value = Value::String(to_hex(value));
+		if key.starts_with(b":") {
+			well_known.insert(String::from_utf8_lossy(key).into_owned(), value);
+			continue
+		}
+		match names.lookup(key) {
+			Some((pallet, item, rest)) => {
+				let items = pallets
+					.entry(pallet)
+					.or_insert_with(|| Value::Object(Map::new()))
+					.as_object_mut()
+					.expect("pallets are objects; qed");
+				if rest.is_empty() {
+					items.insert(item.into(), value);
+					continue
+				}
+				match items.entry(item).or_insert_with(|| Value::Object(Map::new())) {
+					Value::Object(entries) => {
+						entries.insert(to_hex(rest), value);
+					},
+					// Only a map may have values at longer keys than its prefix.
+					_ => {
+						unknown.insert(to_hex(key), value);
+					},
+				}
+			},
+			None => {
+				unknown.insert(to_hex(key), value);
+			},
+		}
+	}
+
+	let children_default = storage
+		.children_default
+		.iter()
+		.map(|(child_key, child)| {
+			let data = child
+				.data
+				.iter()
+				.map(|(key, value)| (to_hex(key), Value::String(to_hex(value))))
+				.collect();
+			(to_hex(child_key), Value::Object(data))
+		})
+		.collect();
+
+	let mut reversed = Map::new();
+	reversed.insert("wellKnown".into(), Value::Object(well_known));
+	reversed.insert("pallets".into(), Value::Object(pallets));
+	reversed.insert("unknown".into(), Value::Object(unknown));
+	reversed.insert("childrenDefault".into(), Value::Object(children_default));
+	Value::Object(reversed)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+	sp_core::bytes::to_hex(bytes, false)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+	use sp_core::storage::{ChildInfo, StorageChild};
+
+	#[test]
+	fn raw_keys_are_mapped_to_items() {
+		let key = |pallet: &str, item: &str, rest: &[u8]| {
+			[&twox_128(pallet.as_bytes())[..], &twox_128(item.as_bytes())[..], rest].concat()
+		};
+		let mut storage = Storage::default();
+		storage.top.insert(b":code".to_vec(), vec![1, 2]);
+		storage.top.insert(key("System", "Number", &[]), vec![3]);
+		storage.top.insert(key("System", "Account", &[4, 5]), vec![6]);
+		storage.top.insert(key("Unknown", "Item", &[]), vec![7]);
+		let child_info = ChildInfo::new_default(b"child");
+		storage.children_default.insert(
+			child_info.prefixed_storage_key().into_inner(),
+			StorageChild { data: vec![(vec![8], vec![9])].into_iter().collect(), child_info },
+		);
+
+		let names = StorageNames::new(vec![
+			("System".to_string(), "Number".to_string()),
+			("System".to_string(), "Account".to_string()),
+		]);
+		let unknown_key = to_hex(&key("Unknown", "Item", &[]));
+		let child_key = to_hex(b":child_storage:default:child");
+		assert_eq!(
+			reverse_storage(&storage, &names),
+			json!({
+				"wellKnown": { ":code": "0x0102" },
+				"pallets": {
+					"System": {
+						"Number": "0x03",
+						"Account": { "0x0405": "0x06" },
+					},
+				},
+				"unknown": { unknown_key: "0x07" },
+				"childrenDefault": { child_key: { "0x08": "0x09" } },
+			}),
+		);
+	}
+}
@@ -208,7 +208,9 @@ impl std::str::FromStr for Database {
 	fn from_str(s: &str) -> Result<Self, String> {
 		if s.eq_ignore_ascii_case("rocksdb") {
 			Ok(Self::RocksDb)
-		} else if s.eq_ignore_ascii_case("paritydb-experimental") {
+		} else if s.eq_ignore_ascii_case("paritydb") ||
+			s.eq_ignore_ascii_case("paritydb-experimental")
+		{
 			Ok(Self::ParityDb)
 		} else if s.eq_ignore_ascii_case("auto") {
 			Ok(Self::Auto)
@@ -221,7 +223,7 @@ impl std::str::FromStr for Database {
 impl Database {
 	/// Returns all the variants of this enum to be shown in the cli.
 	pub fn variants() -> &'static [&'static str] {
-		&["rocksdb", "paritydb-experimental", "auto"]
+		&["rocksdb", "paritydb", "paritydb-experimental", "auto"]
 	}
 }
 
@@ -25,8 +25,9 @@ use log::info;
 use sc_network::config::build_multiaddr;
 use sc_service::{
 	config::{MultiaddrWithPeerId, NetworkConfiguration},
-	ChainSpec,
+	reverse_storage, ChainSpec, StorageNames,
 };
+use sp_runtime::BuildStorage;
 use std::io::Write;
 use structopt::StructOpt;
 
@@ -34,9 +35,21 @@ use structopt::StructOpt;
 #[derive(Debug, StructOpt, Clone)]
 pub struct BuildSpecCmd {
 	/// Force raw genesis storage output.
+	///
+	/// The raw output records the genesis state root and the hash of the genesis runtime, which
+	/// are also printed to the log.
 	#[structopt(long = "raw")]
 	pub raw: bool,
 
+	/// Group the raw genesis storage by the storage items of the runtime.
+	///
+	/// Only the keys are mapped back to the items, the values are kept SCALE encoded. The keys of
+	/// no known item, e.g. all of them if the node doesn't provide the names of the storage items
+	/// of its runtime, are kept raw. The output is meant to be read, it can't be used as a
+	/// specification.
+	#[structopt(long = "reverse", requires = "raw")]
+	pub reverse: bool,
+
 	/// Disable adding the default bootnode to the specification.
 	///
 	/// By default the `/ip4/127.0.0.1/tcp/30333/p2p/NODE_PEER_ID` bootnode is added to the
@@ -55,11 +68,17 @@ pub struct BuildSpecCmd {
 
 impl BuildSpecCmd {
 	/// Run the build-spec command
+	///
+	/// The `storage_names` of the runtime are used by `--reverse`, if given.
 	pub fn run(
 		&self,
 		mut spec: Box<dyn ChainSpec>,
 		network_config: NetworkConfiguration,
+		storage_names: Option<&StorageNames>,
 	) -> error::Result<()> {
+		if self.reverse {
+			return self.run_reverse(spec, storage_names.cloned().unwrap_or_default())
+		}
 		info!("Building chain spec");
 		let raw_output = self.raw;
 
@@ -74,6 +93,30 @@ impl BuildSpecCmd {
 		}
 
 		let json = sc_service::chain_ops::build_spec(&*spec, raw_output)?;
+		// Computed while dumping the raw genesis, so that its storage is not built again.
+		if let (true, Some(hashes)) = (raw_output, spec.genesis_hashes()) {
+			info!("Genesis state root: {:?}", hashes.state_root);
+			match hashes.runtime_hash {
+				Some(runtime_hash) => info!("Genesis runtime hash: {:?}", runtime_hash),
+				None => info!("Genesis state contains no runtime"),
+			}
+		}
+		if std::io::stdout().write_all(json.as_bytes()).is_err() {
+			let _ = std::io::stderr().write_all(b"Error writing to stdout\n");
+		}
+		Ok(())
+	}
+
+	/// Output the raw genesis storage of `spec` grouped by the storage items given by `names`.
+	fn run_reverse(&self, spec: Box<dyn ChainSpec>, names: StorageNames) -> error::Result<()> {
+		info!("Reversing the raw genesis storage of the chain spec");
+		let mut json: serde_json::Value = serde_json::from_str(&spec.as_json(true)?)
+			.map_err(|e| format!("Error parsing spec json: {}", e))?;
+		let reversed = reverse_storage(&spec.build_storage()?, &names);
+		json["genesis"] = serde_json::json!({ "reversed": reversed });
+
+		let json = serde_json::to_string_pretty(&json)
+			.map_err(|e| format!("Error generating spec json: {}", e))?;
 		if std::io::stdout().write_all(json.as_bytes()).is_err() {
 			let _ = std::io::stderr().write_all(b"Error writing to stdout\n");
 		}
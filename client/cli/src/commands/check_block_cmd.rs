@@ -33,12 +33,6 @@ pub struct CheckBlockCmd {
 	#[structopt(value_name = "HASH or NUMBER")]
 	pub input: BlockNumberOrHash,
 
-	/// The default number of 64KB pages to ever allocate for Wasm execution.
-	///
-	/// Don't alter this unless you know what you're doing.
-	#[structopt(long = "default-heap-pages", value_name = "COUNT")]
-	pub default_heap_pages: Option<u32>,
-
 	#[allow(missing_docs)]
 	#[structopt(flatten)]
 	pub shared_params: SharedParams,
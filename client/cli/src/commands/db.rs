@@ -0,0 +1,32 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Database related CLI utilities
+
+use structopt::StructOpt;
+
+use super::db_migrate_cmd::DbMigrateCmd;
+
+/// Database utilities for the cli.
+#[derive(Debug, StructOpt)]
+pub enum DbSubcommand {
+	/// Copy the RocksDB database of the node into a new ParityDB database.
+	///
+	/// Only archive nodes can be migrated, and only from RocksDB to ParityDB.
+	Migrate(DbMigrateCmd),
+}
@@ -0,0 +1,72 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{arg_enums::Database, error, params::SharedParams, CliConfiguration};
+use sc_service::{
+	config::{migrate_rocksdb_to_parity_db, DatabaseType},
+	DatabaseSource, Role,
+};
+use sp_runtime::traits::Block as BlockT;
+use structopt::StructOpt;
+
+/// The `db migrate` command used to convert the RocksDB database of a node to ParityDB.
+///
+/// The node must not be running. Only databases with the `archive` state pruning mode can be
+/// migrated, and there is no way back from ParityDB to RocksDB. The RocksDB database is left
+/// untouched, it can be removed once the node was started with `--database paritydb`.
+#[derive(Debug, StructOpt, Clone)]
+pub struct DbMigrateCmd {
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+}
+
+impl DbMigrateCmd {
+	/// Run the `db migrate` command
+	pub fn run<B: BlockT>(
+		&self,
+		database_config: DatabaseSource,
+		role: &Role,
+	) -> error::Result<()> {
+		let (rocksdb_path, paritydb_path) = match database_config {
+			DatabaseSource::Auto { rocksdb_path, paritydb_path, .. } =>
+				(rocksdb_path, paritydb_path),
+			_ => return Err(error::Error::Input("Cannot migrate this database".into())),
+		};
+		let db_type = match role {
+			Role::Light => DatabaseType::Light,
+			Role::Full | Role::Authority => DatabaseType::Full,
+		};
+
+		migrate_rocksdb_to_parity_db::<B>(&rocksdb_path, &paritydb_path, db_type)?;
+
+		println!("{:?} migrated to {:?}.", &rocksdb_path, &paritydb_path);
+		Ok(())
+	}
+}
+
+impl CliConfiguration for DbMigrateCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn database(&self) -> error::Result<Option<Database>> {
+		// both paths are only known for the detected database.
+		Ok(Some(Database::Auto))
+	}
+}
@@ -0,0 +1,89 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Implementation of the `export` subcommand
+
+use crate::{error, secret_string_from_str, utils, Error};
+use sp_core::crypto::{KeyTypeId, SecretString};
+use std::{collections::BTreeMap, convert::TryFrom, fs, path::PathBuf};
+use structopt::StructOpt;
+
+/// The `export` command
+///
+/// The key file is a passphrase encrypted age file of the secret URIs of the keys. PKCS#8 is out
+/// of scope, the keystore storing secret URIs and sr25519 having no PKCS#8 algorithm identifier.
+#[derive(Debug, StructOpt, Clone)]
+#[structopt(
+	name = "export",
+	about = "Export the keys of the keystore of a node to a password protected key file"
+)]
+pub struct ExportKeysCmd {
+	/// The keystore directory of the node.
+	#[structopt(long, value_name = "PATH", parse(from_os_str))]
+	keystore_path: PathBuf,
+
+	/// The key file to write.
+	#[structopt(long, value_name = "PATH", parse(from_os_str))]
+	output: PathBuf,
+
+	/// Only export the keys of the given type, examples: "gran", or "babe".
+	/// By default, all the keys are exported.
+	#[structopt(long)]
+	key_type: Option<String>,
+
+	/// The password of the key file.
+	/// If not given, you will be prompted for the password.
+	#[structopt(long, parse(try_from_str = secret_string_from_str))]
+	password: Option<SecretString>,
+}
+
+impl ExportKeysCmd {
+	/// Run the command
+	pub fn run(&self) -> error::Result<()> {
+		let key_type = self
+			.key_type
+			.as_ref()
+			.map(|key_type| {
+				KeyTypeId::try_from(key_type.as_str()).map_err(|_| Error::KeyTypeInvalid)
+			})
+			.transpose()?;
+
+		let mut keys = BTreeMap::new();
+		for entry in fs::read_dir(&self.keystore_path)? {
+			let path = entry?.path();
+			let name = match path.file_name().and_then(|name| name.to_str()) {
+				Some(name) if utils::is_key_file_name(name) => name.to_owned(),
+				_ => continue,
+			};
+			if key_type.map_or(false, |key_type| !name.starts_with(&hex::encode(key_type.0))) {
+				continue
+			}
+			let suri: String = serde_json::from_str(&fs::read_to_string(&path)?)
+				.map_err(|e| Error::Input(format!("Invalid key file {}: {}", name, e)))?;
+			keys.insert(name, suri);
+		}
+		if keys.is_empty() {
+			return Err(Error::Input("No key to export in the keystore".into()))
+		}
+
+		let password = utils::read_key_file_password(self.password.as_ref())?;
+		fs::write(&self.output, utils::encrypt_key_file(&keys, &password)?)?;
+		println!("Exported {} keys to {}", keys.len(), self.output.display());
+		Ok(())
+	}
+}
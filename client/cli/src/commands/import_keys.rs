@@ -0,0 +1,151 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Implementation of the `import` subcommand
+
+use crate::{error, secret_string_from_str, utils, Error};
+use sp_core::crypto::SecretString;
+use std::{fs, path::PathBuf};
+use structopt::StructOpt;
+
+/// The `import` command
+#[derive(Debug, StructOpt, Clone)]
+#[structopt(
+	name = "import",
+	about = "Import the keys of a password protected key file to the keystore of a node"
+)]
+pub struct ImportKeysCmd {
+	/// The key file written by the `export` command.
+	#[structopt(parse(from_os_str))]
+	input: PathBuf,
+
+	/// The keystore directory of the node.
+	#[structopt(long, value_name = "PATH", parse(from_os_str))]
+	keystore_path: PathBuf,
+
+	/// The password of the key file.
+	/// If not given, you will be prompted for the password.
+	#[structopt(long, parse(try_from_str = secret_string_from_str))]
+	password: Option<SecretString>,
+}
+
+impl ImportKeysCmd {
+	/// Run the command
+	pub fn run(&self) -> error::Result<()> {
+		let password = utils::read_key_file_password(self.password.as_ref())?;
+		let keys = utils::decrypt_key_file(&fs::read(&self.input)?, &password)?;
+
+		// Check all the keys before writing any of them.
+		for (name, suri) in &keys {
+			if !utils::is_key_file_name(name) {
+				return Err(Error::Input(format!("Invalid key file name {}", name)))
+			}
+			let path = self.keystore_path.join(name);
+			if path.exists() &&
+				serde_json::from_str::<String>(&fs::read_to_string(&path)?).ok().as_ref() !=
+					Some(suri)
+			{
+				return Err(Error::Input(format!(
+					"Another key is already stored at {}",
+					path.display()
+				)))
+			}
+		}
+
+		fs::create_dir_all(&self.keystore_path)?;
+		for (name, suri) in &keys {
+			let suri = serde_json::to_string(suri).map_err(|e| Error::Input(e.to_string()))?;
+			fs::write(self.keystore_path.join(name), suri)?;
+		}
+		println!("Imported {} keys to {}", keys.len(), self.keystore_path.display());
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::ExportKeysCmd;
+	use sc_keystore::LocalKeystore;
+	use sp_core::crypto::KeyTypeId;
+	use sp_keystore::SyncCryptoStore;
+	use tempfile::TempDir;
+
+	#[test]
+	fn export_and_import_keys() {
+		let source = TempDir::new().unwrap();
+		let keystore = LocalKeystore::open(source.path(), None).unwrap();
+		SyncCryptoStore::sr25519_generate_new(&keystore, KeyTypeId(*b"babe"), None).unwrap();
+		SyncCryptoStore::ed25519_generate_new(&keystore, KeyTypeId(*b"gran"), None).unwrap();
+
+		let file = TempDir::new().unwrap();
+		let output = file.path().join("keys.age");
+		let export = ExportKeysCmd::from_iter(&[
+			"export",
+			"--keystore-path",
+			source.path().to_str().unwrap(),
+			"--output",
+			output.to_str().unwrap(),
+			"--password",
+			"password",
+		]);
+		assert!(export.run().is_ok());
+
+		let target = TempDir::new().unwrap();
+		let import = |password: &str| {
+			ImportKeysCmd::from_iter(&[
+				"import",
+				output.to_str().unwrap(),
+				"--keystore-path",
+				target.path().to_str().unwrap(),
+				"--password",
+				password,
+			])
+			.run()
+		};
+		assert!(import("wrong").is_err());
+		assert!(import("password").is_ok());
+
+		let keystore = LocalKeystore::open(target.path(), None).unwrap();
+		assert_eq!(SyncCryptoStore::sr25519_public_keys(&keystore, KeyTypeId(*b"babe")).len(), 1);
+		assert_eq!(SyncCryptoStore::ed25519_public_keys(&keystore, KeyTypeId(*b"gran")).len(), 1);
+
+		// Importing the same keys again is fine.
+		assert!(import("password").is_ok());
+	}
+
+	#[test]
+	fn key_file_work_factor_is_bounded() {
+		let password = SecretString::new("password".into());
+		let keys = std::iter::once(("6772616e00".to_owned(), "//Alice".to_owned())).collect();
+		let file = utils::encrypt_key_file(&keys, &password).unwrap();
+		assert_eq!(utils::decrypt_key_file(&file, &password).unwrap(), keys);
+
+		let header_end = file.windows(4).position(|w| w == b"\n---").unwrap();
+		let header = std::str::from_utf8(&file[..header_end]).unwrap();
+		let stanza = header.lines().find(|line| line.starts_with("-> scrypt ")).unwrap();
+		let salt = stanza.split(' ').nth(2).unwrap();
+		let mut crafted =
+			header.replacen(stanza, &format!("-> scrypt {} 40", salt), 1).into_bytes();
+		crafted.extend_from_slice(&file[header_end..]);
+		match utils::decrypt_key_file(&crafted, &password) {
+			Err(Error::Input(e)) => assert!(e.contains("exceeds the maximum"), "{}", e),
+			result => panic!("Unexpected result: {:?}", result),
+		}
+	}
+}
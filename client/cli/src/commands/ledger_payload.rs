@@ -0,0 +1,85 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Implementation of the `ledger-payload` subcommand
+
+use crate::{error, utils};
+use sp_core::hexdisplay::HexDisplay;
+use structopt::StructOpt;
+
+/// The `ledger-payload` command
+#[derive(Debug, StructOpt, Clone)]
+#[structopt(
+	name = "ledger-payload",
+	about = "Export the signing payload of a transaction as the data of the sign APDUs of the \
+		Ledger apps"
+)]
+pub struct LedgerPayloadCmd {
+	/// The SCALE encoded signing payload, hex-encoded.
+	/// If not given, it is read from STDIN.
+	payload: Option<String>,
+
+	/// The BIP44 derivation path of the signing account, every index being hardened.
+	#[structopt(long, default_value = utils::DEFAULT_LEDGER_PATH)]
+	path: String,
+}
+
+impl LedgerPayloadCmd {
+	/// Run the command
+	pub fn run(&self) -> error::Result<()> {
+		let payload = utils::read_message(self.payload.as_ref(), true)?;
+		let path = utils::parse_bip44_path(&self.path)?;
+
+		for chunk in utils::ledger_sign_chunks(&path, &payload)? {
+			println!("0x{}", HexDisplay::from(&chunk));
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn ledger_payload() {
+		let payload = format!("0x{}", "00".repeat(300));
+
+		let export = LedgerPayloadCmd::from_iter(&["ledger-payload", &payload]);
+		assert!(export.run().is_ok());
+
+		let export =
+			LedgerPayloadCmd::from_iter(&["ledger-payload", &payload, "--path", "m/44'/354'/0'"]);
+		assert!(export.run().is_err());
+	}
+
+	#[test]
+	fn payload_is_split_after_the_path() {
+		let path = utils::parse_bip44_path("m/44'/354'/0'/0'/1'").unwrap();
+		assert_eq!(path, vec![0x8000_002c, 0x8000_0162, 0x8000_0000, 0x8000_0000, 0x8000_0001]);
+		assert!(utils::parse_bip44_path("44'/354'").is_err());
+		assert!(utils::parse_bip44_path("m/44'/354'/0'/0/1'").is_err());
+		assert!(utils::parse_bip44_path("m/2147483648'").is_err());
+
+		let chunks = utils::ledger_sign_chunks(&path, &[1u8; 300]).unwrap();
+		assert_eq!(chunks.len(), 3);
+		assert_eq!(chunks[0][..4], 0x8000_002cu32.to_le_bytes());
+		assert_eq!(chunks[1], vec![1u8; 250]);
+		assert_eq!(chunks[2], vec![1u8; 50]);
+	}
+}
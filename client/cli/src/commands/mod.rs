@@ -17,15 +17,20 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 mod build_spec_cmd;
 mod check_block_cmd;
+mod db;
+mod db_migrate_cmd;
 mod export_blocks_cmd;
+mod export_keys;
 mod export_state_cmd;
 mod generate;
 mod generate_node_key;
 mod import_blocks_cmd;
+mod import_keys;
 mod insert_key;
 mod inspect_key;
 mod inspect_node_key;
 mod key;
+mod ledger_payload;
 mod purge_chain_cmd;
 mod revert_cmd;
 mod run_cmd;
@@ -33,12 +38,15 @@ mod sign;
 pub mod utils;
 mod vanity;
 mod verify;
+mod verify_ledger;
 
 pub use self::{
-	build_spec_cmd::BuildSpecCmd, check_block_cmd::CheckBlockCmd,
-	export_blocks_cmd::ExportBlocksCmd, export_state_cmd::ExportStateCmd, generate::GenerateCmd,
-	generate_node_key::GenerateNodeKeyCmd, import_blocks_cmd::ImportBlocksCmd,
-	insert_key::InsertKeyCmd, inspect_key::InspectKeyCmd, inspect_node_key::InspectNodeKeyCmd,
-	key::KeySubcommand, purge_chain_cmd::PurgeChainCmd, revert_cmd::RevertCmd, run_cmd::RunCmd,
-	sign::SignCmd, vanity::VanityCmd, verify::VerifyCmd,
+	build_spec_cmd::BuildSpecCmd, check_block_cmd::CheckBlockCmd, db::DbSubcommand,
+	db_migrate_cmd::DbMigrateCmd, export_blocks_cmd::ExportBlocksCmd, export_keys::ExportKeysCmd,
+	export_state_cmd::ExportStateCmd, generate::GenerateCmd, generate_node_key::GenerateNodeKeyCmd,
+	import_blocks_cmd::ImportBlocksCmd, import_keys::ImportKeysCmd, insert_key::InsertKeyCmd,
+	inspect_key::InspectKeyCmd, inspect_node_key::InspectNodeKeyCmd, key::KeySubcommand,
+	ledger_payload::LedgerPayloadCmd, purge_chain_cmd::PurgeChainCmd, revert_cmd::RevertCmd,
+	run_cmd::RunCmd, sign::SignCmd, vanity::VanityCmd, verify::VerifyCmd,
+	verify_ledger::VerifyLedgerCmd,
 };
@@ -27,7 +27,7 @@ use crate::{
 };
 use regex::Regex;
 use sc_service::{
-	config::{BasePath, PrometheusConfig, TransactionPoolOptions},
+	config::{BasePath, PrometheusConfig, RpcLimits, TransactionPoolOptions},
 	ChainSpec, Role,
 };
 use sc_telemetry::TelemetryEndpoints;
@@ -105,6 +105,23 @@ pub struct RunCmd {
 	#[structopt(long = "rpc-max-payload")]
 	pub rpc_max_payload: Option<usize>,
 
+	/// Set the maximum number of RPC calls per second of each WS or IPC connection.
+	///
+	/// Calls exceeding this rate are rejected. Default is unlimited.
+	#[structopt(long = "rpc-max-calls-per-second", value_name = "COUNT")]
+	pub rpc_max_calls_per_second: Option<u32>,
+
+	/// Set the maximum number of subscriptions each WS or IPC connection may have open at the
+	/// same time. Default is unlimited.
+	#[structopt(long = "rpc-max-subscriptions-per-connection", value_name = "COUNT")]
+	pub rpc_max_subscriptions_per_connection: Option<u32>,
+
+	/// Set the maximum size of the response to a single RPC call, in megabytes.
+	///
+	/// Larger responses are replaced by an error. Default is unlimited.
+	#[structopt(long = "rpc-max-response-size", value_name = "MB")]
+	pub rpc_max_response_size: Option<usize>,
+
 	/// Expose Prometheus exporter on all interfaces.
 	///
 	/// Default is local.
@@ -432,6 +449,14 @@ impl CliConfiguration for RunCmd {
 		Ok(self.rpc_max_payload)
 	}
 
+	fn rpc_limits(&self) -> Result<RpcLimits> {
+		Ok(RpcLimits {
+			max_calls_per_second: self.rpc_max_calls_per_second,
+			max_subscriptions_per_connection: self.rpc_max_subscriptions_per_connection,
+			max_response_size: self.rpc_max_response_size.map(|mb| mb.saturating_mul(1024 * 1024)),
+		})
+	}
+
 	fn transaction_pool(&self) -> Result<TransactionPoolOptions> {
 		Ok(self.pool_config.transaction_pool())
 	}
@@ -589,4 +614,27 @@ mod tests {
 		assert!(is_node_name_valid("www.visit.me").is_err());
 		assert!(is_node_name_valid("email@domain").is_err());
 	}
+
+	#[test]
+	fn existing_paritydb_database_keeps_its_path() {
+		use crate::arg_enums::Database;
+		use sc_service::config::DatabaseSource;
+
+		let base_path = tempfile::tempdir().unwrap();
+		let base_path = base_path.path().to_path_buf();
+		let cmd = RunCmd::from_iter(&["test"]);
+		let paritydb_path =
+			|| match cmd.database_config(&base_path, 128, Database::ParityDb, &Role::Full).unwrap()
+			{
+				DatabaseSource::ParityDb { path } => path,
+				source => panic!("Unexpected database source {:?}", source),
+			};
+		assert_eq!(paritydb_path(), base_path.join("paritydb").join("full"));
+
+		// databases created before ParityDB got its own directory.
+		let legacy_path = base_path.join("db").join("full");
+		std::fs::create_dir_all(&legacy_path).unwrap();
+		std::fs::write(legacy_path.join("metadata"), b"").unwrap();
+		assert_eq!(paritydb_path(), legacy_path);
+	}
 }
@@ -24,11 +24,17 @@ use crate::{
 use serde_json::json;
 use sp_core::{
 	crypto::{ExposeSecret, SecretString, Ss58AddressFormat, Ss58Codec, Zeroize},
+	ed25519,
 	hexdisplay::HexDisplay,
 	Pair,
 };
 use sp_runtime::{traits::IdentifyAccount, MultiSigner};
-use std::{convert::TryFrom, io::Read, path::PathBuf};
+use std::{
+	collections::BTreeMap,
+	convert::TryFrom,
+	io::{Read, Write},
+	path::PathBuf,
+};
 
 /// Public key type for Runtime
 pub type PublicFor<P> = <P as sp_core::Pair>::Public;
@@ -272,6 +278,135 @@ pub fn decode_hex<T: AsRef<[u8]>>(message: T) -> Result<Vec<u8>, Error> {
 	Ok(hex::decode(message)?)
 }
 
+/// The BIP44 derivation path of the first Polkadot account of the Ledger apps.
+pub const DEFAULT_LEDGER_PATH: &str = "m/44'/354'/0'/0'/0'";
+
+/// The maximum size of the data of a sign APDU of the Ledger apps.
+const LEDGER_CHUNK_SIZE: usize = 250;
+
+/// Parse a BIP44 derivation path like `m/44'/354'/0'/0'/0'`.
+///
+/// The Ledger apps only derive hardened indices, so every index must be primed.
+pub fn parse_bip44_path(path: &str) -> Result<Vec<u32>, Error> {
+	let mut components = path.split('/');
+	if components.next() != Some("m") {
+		return Err(Error::Input(format!("BIP44 path `{}` must start with `m/`", path)))
+	}
+	components
+		.map(|index| {
+			index
+				.strip_suffix('\'')
+				.and_then(|index| index.parse::<u32>().ok())
+				.filter(|index| *index < ed25519::BIP44_HARDENED)
+				.map(|index| index | ed25519::BIP44_HARDENED)
+				.ok_or_else(|| {
+					Error::Input(format!(
+						"Invalid index `{}` in BIP44 path, it must be hardened",
+						index
+					))
+				})
+		})
+		.collect()
+}
+
+/// Split the signing `payload` of a transaction into the data of the sign APDUs of the Ledger
+/// apps.
+///
+/// The first chunk is the BIP44 `path` of the signing account, of five little endian indices,
+/// followed by the chunks of the SCALE encoded payload.
+pub fn ledger_sign_chunks(path: &[u32], payload: &[u8]) -> Result<Vec<Vec<u8>>, Error> {
+	if path.len() != 5 {
+		return Err(Error::Input(format!(
+			"The Ledger apps expect a BIP44 path of 5 indices, got {}",
+			path.len()
+		)))
+	}
+	let path = path.iter().flat_map(|index| index.to_le_bytes().to_vec()).collect();
+	Ok(std::iter::once(path)
+		.chain(payload.chunks(LEDGER_CHUNK_SIZE).map(|chunk| chunk.to_vec()))
+		.collect())
+}
+
+/// Highest scrypt work factor, as a base 2 logarithm, accepted in a key file.
+///
+/// The work factor of a key file is the one taking around a second on the exporting host, 18
+/// on current hardware. This bound leaves room for hosts 16 times as fast, while keeping the
+/// import of a crafted key file from running for much longer than 16 seconds.
+const KEY_FILE_MAX_WORK_FACTOR: u8 = 22;
+
+/// Whether `name` is the name of a key file of the keystore, i.e. the hex encoded key type
+/// followed by the hex encoded public key.
+pub fn is_key_file_name(name: &str) -> bool {
+	name.len() > 8 && name.len() % 2 == 0 && name.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Read the password of an encrypted key file, prompting for it if not given.
+pub fn read_key_file_password(password: Option<&SecretString>) -> Result<SecretString, Error> {
+	Ok(match password {
+		Some(password) => password.clone(),
+		None => SecretString::new(rpassword::read_password_from_tty(Some("Key file password: "))?),
+	})
+}
+
+/// Encrypt the secret URIs of the keystore `keys`, indexed by the names of their files in the
+/// keystore directory, to a key file protected by `password`.
+///
+/// The key file is a passphrase encrypted age file, which can also be decrypted by the `age`
+/// tool, of which the plaintext is the JSON map of the keys.
+pub fn encrypt_key_file(
+	keys: &BTreeMap<String, String>,
+	password: &SecretString,
+) -> Result<Vec<u8>, Error> {
+	let mut plaintext = serde_json::to_vec(keys).map_err(|e| Error::Input(e.to_string()))?;
+	let mut file = vec![];
+	let result = age::Encryptor::with_user_passphrase(password.clone())
+		.wrap_output(&mut file)
+		.map_err(|e| e.to_string())
+		.and_then(|mut writer| {
+			writer
+				.write_all(&plaintext)
+				.and_then(|_| writer.finish())
+				.map_err(|e| e.to_string())
+		});
+	plaintext.zeroize();
+	result.map_err(|e| Error::Input(format!("Failed to encrypt the keys: {}", e)))?;
+	Ok(file)
+}
+
+/// Decrypt a key file written by [`encrypt_key_file`], returning the secret URIs of the keys
+/// indexed by the names of their files in the keystore directory.
+///
+/// The scrypt work factor of the file can't exceed [`KEY_FILE_MAX_WORK_FACTOR`], so that a
+/// crafted file can't stall the import. There is no lower bound: the work factor only protects
+/// the file against whoever doesn't know its password, which the importer does.
+pub fn decrypt_key_file(
+	file: &[u8],
+	password: &SecretString,
+) -> Result<BTreeMap<String, String>, Error> {
+	let decryptor = match age::Decryptor::new(file)
+		.map_err(|e| Error::Input(format!("Invalid key file: {}", e)))?
+	{
+		age::Decryptor::Passphrase(decryptor) => decryptor,
+		_ => return Err(Error::Input("The key file is not protected by a password".into())),
+	};
+
+	let mut plaintext = vec![];
+	decryptor
+		.decrypt(password, Some(KEY_FILE_MAX_WORK_FACTOR))
+		.map_err(|e| match e {
+			age::DecryptError::ExcessiveWork { required, .. } => Error::Input(format!(
+				"The work factor 2^{} of the key file exceeds the maximum 2^{}",
+				required, KEY_FILE_MAX_WORK_FACTOR,
+			)),
+			e => Error::Input(format!("Wrong password or corrupted key file: {}", e)),
+		})?
+		.read_to_end(&mut plaintext)
+		.map_err(|e| Error::Input(format!("Corrupted key file: {}", e)))?;
+	let keys = serde_json::from_slice(&plaintext).map_err(|e| Error::Input(e.to_string()));
+	plaintext.zeroize();
+	keys
+}
+
 /// checks if message is Some, otherwise reads message from stdin and optionally decodes hex
 pub fn read_message(msg: Option<&String>, should_decode: bool) -> Result<Vec<u8>, Error> {
 	let mut message = vec![];
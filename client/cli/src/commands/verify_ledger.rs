@@ -0,0 +1,158 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Implementation of the `verify-ledger` subcommand
+
+use crate::{error, utils, CryptoScheme, KeystoreParams};
+use sp_core::{
+	crypto::{AccountId32, ExposeSecret, Ss58Codec},
+	ed25519,
+	hexdisplay::HexDisplay,
+	sr25519, Pair,
+};
+use structopt::StructOpt;
+
+/// The `verify-ledger` command
+#[derive(Debug, StructOpt, Clone)]
+#[structopt(
+	name = "verify-ledger",
+	about = "Verify that the account shown by a Ledger device is derived from a given phrase"
+)]
+pub struct VerifyLedgerCmd {
+	/// The SS58 address of the account shown by the device.
+	address: String,
+
+	/// The BIP39 phrase the device was set up with.
+	/// If the value is a file, the file content is used as phrase.
+	/// If not given, you will be prompted for the phrase.
+	#[structopt(long)]
+	phrase: Option<String>,
+
+	/// The BIP44 derivation path of the account, every index being hardened.
+	#[structopt(long, default_value = utils::DEFAULT_LEDGER_PATH)]
+	path: String,
+
+	/// The cryptography scheme of the account.
+	#[structopt(
+		long,
+		value_name = "SCHEME",
+		possible_values = &["Ed25519", "Sr25519"],
+		case_insensitive = true,
+		default_value = "Ed25519"
+	)]
+	scheme: CryptoScheme,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub keystore_params: KeystoreParams,
+}
+
+impl VerifyLedgerCmd {
+	/// Run the command
+	pub fn run(&self) -> error::Result<()> {
+		let (expected, format) = AccountId32::from_ss58check_with_version(&self.address)
+			.map_err(error::Error::InvalidUri)?;
+		let path = utils::parse_bip44_path(&self.path)?;
+		let phrase = utils::read_uri(self.phrase.as_ref())?;
+		let password = self.keystore_params.read_password()?;
+		let password = password.as_ref().map(|password| password.expose_secret().as_str());
+
+		let public = match self.scheme {
+			CryptoScheme::Ed25519 =>
+				ed25519::Public::from_ledger_phrase(&phrase, password, &path).map(|public| public.0),
+			CryptoScheme::Sr25519 => sr25519::Pair::from_ledger_phrase(&phrase, password, &path)
+				.map(|pair| pair.public().0),
+			CryptoScheme::Ecdsa =>
+				return Err(error::Error::Input(
+					"The Ledger apps only derive ed25519 and sr25519 accounts".into(),
+				)),
+		}
+		.map_err(|e| format!("Invalid phrase {:?}", e))?;
+		let public = AccountId32::from(public);
+
+		println!(
+			"Derivation path:    {}\n  \
+			 Public key (hex):   0x{}\n  \
+			 SS58 Address:       {}",
+			self.path,
+			HexDisplay::from(&public.as_ref()),
+			public.to_ss58check_with_version(format),
+		);
+
+		if public == expected {
+			println!("The account of the device is derived from the phrase.");
+			Ok(())
+		} else {
+			Err(error::Error::Input(format!(
+				"The account derived from the phrase is not {}",
+				self.address
+			)))
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use sp_core::crypto::DEV_PHRASE;
+
+	#[test]
+	fn verify_ledger() {
+		let path = utils::parse_bip44_path(utils::DEFAULT_LEDGER_PATH).unwrap();
+		let address = ed25519::Public::from_ledger_phrase(DEV_PHRASE, None, &path)
+			.unwrap()
+			.to_ss58check();
+
+		let verify =
+			VerifyLedgerCmd::from_iter(&["verify-ledger", &address, "--phrase", DEV_PHRASE]);
+		assert!(verify.run().is_ok());
+
+		let verify = VerifyLedgerCmd::from_iter(&[
+			"verify-ledger",
+			&address,
+			"--phrase",
+			DEV_PHRASE,
+			"--path",
+			"m/44'/354'/1'/0'/0'",
+		]);
+		assert!(verify.run().is_err());
+
+		let verify = VerifyLedgerCmd::from_iter(&[
+			"verify-ledger",
+			&address,
+			"--phrase",
+			DEV_PHRASE,
+			"--scheme",
+			"sr25519",
+		]);
+		assert!(verify.run().is_err());
+		let address = sr25519::Pair::from_ledger_phrase(DEV_PHRASE, None, &path)
+			.unwrap()
+			.public()
+			.to_ss58check();
+		let verify = VerifyLedgerCmd::from_iter(&[
+			"verify-ledger",
+			&address,
+			"--phrase",
+			DEV_PHRASE,
+			"--scheme",
+			"sr25519",
+		]);
+		assert!(verify.run().is_ok());
+	}
+}
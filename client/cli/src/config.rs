@@ -28,8 +28,8 @@ use sc_client_api::execution_extensions::ExecutionStrategies;
 use sc_service::{
 	config::{
 		BasePath, Configuration, DatabaseSource, KeystoreConfig, NetworkConfiguration,
-		NodeKeyConfig, OffchainWorkerConfig, PrometheusConfig, PruningMode, Role, RpcMethods,
-		TelemetryEndpoints, TransactionPoolOptions, WasmExecutionMethod,
+		NodeKeyConfig, OffchainWorkerConfig, PrometheusConfig, PruningMode, Role, RpcLimits,
+		RpcMethods, TelemetryEndpoints, TransactionPoolOptions, WasmExecutionMethod,
 	},
 	ChainSpec, KeepBlocks, TracingReceiver, TransactionStorageMode,
 };
@@ -229,7 +229,11 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		let paritydb_path = base_path.join("paritydb").join(role_dir);
 		Ok(match database {
 			Database::RocksDb => DatabaseSource::RocksDb { path: rocksdb_path, cache_size },
-			Database::ParityDb => DatabaseSource::ParityDb { path: rocksdb_path },
+			// ParityDB databases used to be created in the RocksDB directory, keep opening them
+			// there instead of creating a new database.
+			Database::ParityDb if rocksdb_path.join("metadata").exists() =>
+				DatabaseSource::ParityDb { path: rocksdb_path },
+			Database::ParityDb => DatabaseSource::ParityDb { path: paritydb_path },
 			Database::Auto => DatabaseSource::Auto { paritydb_path, rocksdb_path, cache_size },
 		})
 	}
@@ -241,6 +245,13 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(self.import_params().map(|x| x.state_cache_size()).unwrap_or_default())
 	}
 
+	/// Get the trie node cache size.
+	///
+	/// By default this is retrieved from `ImportParams` if it is available. Otherwise its `0`.
+	fn trie_cache_size(&self) -> Result<usize> {
+		Ok(self.import_params().map(|x| x.trie_cache_size()).unwrap_or_default())
+	}
+
 	/// Get the state cache child ratio (if any).
 	///
 	/// By default this is `None`.
@@ -297,6 +308,14 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		self.import_params().map(|x| x.wasm_runtime_overrides()).unwrap_or_default()
 	}
 
+	/// Get the number of blocks below the last finalized block from which unknown blocks are
+	/// rejected before verification.
+	///
+	/// By default this is `None`.
+	fn stale_import_limit(&self) -> Option<u32> {
+		self.import_params().and_then(|x| x.stale_import_limit())
+	}
+
 	/// Get the execution strategies.
 	///
 	/// By default this is retrieved from `ImportParams` if it is available. Otherwise its
@@ -360,6 +379,13 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(None)
 	}
 
+	/// Get the resource limits of each RPC connection.
+	///
+	/// By default no limits are enforced.
+	fn rpc_limits(&self) -> Result<RpcLimits> {
+		Ok(Default::default())
+	}
+
 	/// Get the prometheus configuration (`None` if disabled)
 	///
 	/// By default this is `None`.
@@ -379,9 +405,9 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 
 	/// Get the default value for heap pages
 	///
-	/// By default this is `None`.
+	/// By default this is retrieved from `ImportParams` if it is available. Otherwise its `None`.
 	fn default_heap_pages(&self) -> Result<Option<u64>> {
-		Ok(None)
+		Ok(self.import_params().and_then(|x| x.default_heap_pages()))
 	}
 
 	/// Returns an offchain worker config wrapped in `Ok(_)`
@@ -500,11 +526,13 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			database: self.database_config(&config_dir, database_cache_size, database, &role)?,
 			state_cache_size: self.state_cache_size()?,
 			state_cache_child_ratio: self.state_cache_child_ratio()?,
+			trie_cache_size: self.trie_cache_size()?,
 			state_pruning: self.state_pruning(unsafe_pruning, &role)?,
 			keep_blocks: self.keep_blocks()?,
 			transaction_storage: self.database_transaction_storage()?,
 			wasm_method: self.wasm_method()?,
 			wasm_runtime_overrides: self.wasm_runtime_overrides(),
+			stale_import_limit: self.stale_import_limit(),
 			execution_strategies: self.execution_strategies(is_dev, is_validator)?,
 			rpc_http: self.rpc_http(DCV::rpc_http_listen_port())?,
 			rpc_ws: self.rpc_ws(DCV::rpc_ws_listen_port())?,
@@ -513,6 +541,7 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			rpc_ws_max_connections: self.rpc_ws_max_connections()?,
 			rpc_cors: self.rpc_cors(is_dev)?,
 			rpc_max_payload: self.rpc_max_payload()?,
+			rpc_limits: self.rpc_limits()?,
 			prometheus_config: self.prometheus_config(DCV::prometheus_listen_port())?,
 			telemetry_endpoints,
 			default_heap_pages: self.default_heap_pages()?,
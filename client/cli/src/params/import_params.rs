@@ -69,6 +69,20 @@ pub struct ImportParams {
 	#[structopt(long, value_name = "PATH", parse(from_os_str))]
 	pub wasm_runtime_overrides: Option<PathBuf>,
 
+	/// The default number of 64KB pages to allocate for the runtime heap, used when the chain
+	/// doesn't set the number in its storage.
+	///
+	/// Don't alter this unless you know what you're doing.
+	#[structopt(long, value_name = "COUNT")]
+	pub default_heap_pages: Option<u64>,
+
+	/// Reject unknown blocks more than this number of blocks below the last finalized block
+	/// before verifying them.
+	///
+	/// Protects against peers spamming ancient blocks.
+	#[structopt(long, value_name = "COUNT")]
+	pub stale_import_limit: Option<u32>,
+
 	#[allow(missing_docs)]
 	#[structopt(flatten)]
 	pub execution_strategies: ExecutionStrategiesParams,
@@ -76,6 +90,12 @@ pub struct ImportParams {
 	/// Specify the state cache size.
 	#[structopt(long = "state-cache-size", value_name = "Bytes", default_value = "67108864")]
 	pub state_cache_size: usize,
+
+	/// Specify the size of the cache of trie nodes shared by block import and state queries.
+	///
+	/// Setting it to `0` disables the cache.
+	#[structopt(long = "trie-cache-size", value_name = "Bytes", default_value = "67108864")]
+	pub trie_cache_size: usize,
 }
 
 impl ImportParams {
@@ -84,6 +104,11 @@ impl ImportParams {
 		self.state_cache_size
 	}
 
+	/// Specify the trie node cache size.
+	pub fn trie_cache_size(&self) -> usize {
+		self.trie_cache_size
+	}
+
 	/// Get the WASM execution method from the parameters
 	pub fn wasm_method(&self) -> sc_service::config::WasmExecutionMethod {
 		self.wasm_method.into()
@@ -95,6 +120,17 @@ impl ImportParams {
 		self.wasm_runtime_overrides.clone()
 	}
 
+	/// Get the default number of heap pages for runtime calls.
+	pub fn default_heap_pages(&self) -> Option<u64> {
+		self.default_heap_pages
+	}
+
+	/// Get the number of blocks below the last finalized block from which unknown blocks are
+	/// rejected early.
+	pub fn stale_import_limit(&self) -> Option<u32> {
+		self.stale_import_limit
+	}
+
 	/// Get execution strategies for the parameters
 	pub fn execution_strategies(&self, is_dev: bool, is_validator: bool) -> ExecutionStrategies {
 		let exec = &self.execution_strategies;
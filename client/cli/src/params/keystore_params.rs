@@ -32,6 +32,9 @@ const DEFAULT_KEYSTORE_CONFIG_PATH: &'static str = "keystore";
 #[derive(Debug, StructOpt, Clone)]
 pub struct KeystoreParams {
 	/// Specify custom URIs to connect to for keystore-services
+	///
+	/// Signing requests are forwarded over JSON-RPC to the given HTTPS URI, or HTTP one of the
+	/// local host, the keys are then not read from the local keystore.
 	#[structopt(long = "keystore-uri")]
 	pub keystore_uri: Option<String>,
 
@@ -19,7 +19,8 @@
 use crate::{arg_enums::SyncMode, params::node_key_params::NodeKeyParams};
 use sc_network::{
 	config::{
-		NetworkConfiguration, NodeKeyConfig, NonReservedPeerMode, SetConfig, TransportConfig,
+		NetworkConfiguration, NodeKeyConfig, NonReservedPeerMode, ReputationConfig, SetConfig,
+		TransportConfig,
 	},
 	multiaddr::Protocol,
 };
@@ -27,7 +28,7 @@ use sc_service::{
 	config::{Multiaddr, MultiaddrWithPeerId},
 	ChainSpec, ChainType,
 };
-use std::{borrow::Cow, path::PathBuf};
+use std::{borrow::Cow, path::PathBuf, time::Duration};
 use structopt::StructOpt;
 
 /// Parameters used to create the network configuration.
@@ -131,11 +132,33 @@ pub struct NetworkParams {
 	///
 	/// - `Full`: Download and validate full blockchain history.
 	///
-	/// - `Fast`: Download blocks and the latest state only.
+	/// - `Fast`: Download blocks and the latest state only. The state of a recent finalized block
+	///   is downloaded in proof-verified chunks instead of executing all historical blocks.
 	///
 	/// - `FastUnsafe`: Same as `Fast`, but skip downloading state proofs.
+	///
+	/// - `Warp`: Download the latest finality proofs and state only.
 	#[structopt(long, value_name = "SYNC_MODE", default_value = "Full")]
 	pub sync: SyncMode,
+
+	/// Every second, the reputation of each peer moves towards zero by `1 / DIVISOR` of its
+	/// value.
+	///
+	/// A higher value makes peers keep their good or bad reputation for longer. Defaults to `50`.
+	#[structopt(long, value_name = "DIVISOR")]
+	pub peer_reputation_decay_divisor: Option<i32>,
+
+	/// Reputation under which peers are disconnected and not accepted.
+	#[structopt(long, value_name = "REPUTATION", allow_hyphen_values = true)]
+	pub peer_ban_threshold: Option<i32>,
+
+	/// Minimum number of seconds during which a peer is not accepted after being banned, even
+	/// if its reputation recovers in the meantime.
+	///
+	/// Defaults to `0`, peers being accepted again as soon as their reputation is back above
+	/// the threshold.
+	#[structopt(long, value_name = "SECONDS")]
+	pub peer_ban_duration: Option<u64>,
 }
 
 impl NetworkParams {
@@ -191,6 +214,19 @@ impl NetworkParams {
 			self.discover_local ||
 				is_dev || matches!(chain_type, ChainType::Local | ChainType::Development);
 
+		let default_reputation = ReputationConfig::default();
+		let peer_reputation = ReputationConfig {
+			decay_divisor: self
+				.peer_reputation_decay_divisor
+				.unwrap_or(default_reputation.decay_divisor),
+			banned_threshold: self
+				.peer_ban_threshold
+				.unwrap_or(default_reputation.banned_threshold),
+			ban_duration: self
+				.peer_ban_duration
+				.map_or(default_reputation.ban_duration, Duration::from_secs),
+		};
+
 		let allow_private_ipv4 = match (self.allow_private_ipv4, self.no_private_ipv4) {
 			(true, true) => unreachable!("`*_private_ipv4` flags are mutually exclusive; qed"),
 			(true, false) => true,
@@ -230,6 +266,7 @@ impl NetworkParams {
 			yamux_window_size: None,
 			ipfs_server: self.ipfs_server,
 			sync_mode: self.sync.into(),
+			peer_reputation,
 		}
 	}
 }
@@ -50,6 +50,19 @@ pub struct OffchainWorkerParams {
 	/// DB during block import.
 	#[structopt(long = "enable-offchain-indexing", value_name = "ENABLE_OFFCHAIN_INDEXING")]
 	pub indexing_enabled: bool,
+
+	/// Only allow the HTTP requests of offchain workers to target the given host.
+	///
+	/// Can be passed multiple times. By default any host is allowed.
+	#[structopt(long = "offchain-http-allowed-host", value_name = "HOST", number_of_values = 1)]
+	pub http_allowed_hosts: Vec<String>,
+
+	/// Maximum number of blocks whose offchain workers may run concurrently.
+	///
+	/// The offchain workers of new blocks are skipped while as many blocks are waiting for theirs
+	/// to run. Defaults to the number of CPUs.
+	#[structopt(long = "offchain-worker-max-concurrent", value_name = "COUNT")]
+	pub max_concurrent_workers: Option<usize>,
 }
 
 impl OffchainWorkerParams {
@@ -63,6 +76,13 @@ impl OffchainWorkerParams {
 		};
 
 		let indexing_enabled = self.indexing_enabled;
-		Ok(OffchainWorkerConfig { enabled, indexing_enabled })
+		let http_allowed_hosts =
+			Some(self.http_allowed_hosts.clone()).filter(|hosts| !hosts.is_empty());
+		Ok(OffchainWorkerConfig {
+			enabled,
+			indexing_enabled,
+			http_allowed_hosts,
+			max_concurrent_workers: self.max_concurrent_workers,
+		})
 	}
 }
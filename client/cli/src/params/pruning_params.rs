@@ -23,18 +23,28 @@ use structopt::StructOpt;
 /// Parameters to define the pruning mode
 #[derive(Debug, StructOpt, Clone)]
 pub struct PruningParams {
-	/// Specify the state pruning mode, a number of blocks to keep or 'archive'.
+	/// Specify the state pruning mode, a number of blocks to keep, 'archive' or
+	/// 'archive-canonical'.
+	///
+	/// 'archive-canonical' keeps the states of all finalized blocks only. The
+	/// number of blocks to keep may change across restarts, as long as it stays
+	/// above the number of unfinalized blocks.
 	///
 	/// Default is to keep all block states if the node is running as a
 	/// validator (i.e. 'archive'), otherwise state is only kept for the last
 	/// 256 blocks.
-	#[structopt(long = "pruning", value_name = "PRUNING_MODE")]
+	#[structopt(long = "pruning", alias = "state-pruning", value_name = "PRUNING_MODE")]
 	pub pruning: Option<String>,
-	/// Specify the number of finalized blocks to keep in the database.
+	/// Specify the blocks pruning mode, a number of finalized blocks to keep,
+	/// 'archive' or 'archive-canonical'.
+	///
+	/// 'archive' keeps all blocks, including the ones of forks displaced by
+	/// finality. 'archive-canonical' and a number of blocks both discard the
+	/// blocks of displaced forks.
 	///
-	/// Default is to keep all blocks.
-	#[structopt(long, value_name = "COUNT")]
-	pub keep_blocks: Option<u32>,
+	/// Default is 'archive', i.e. to keep all blocks.
+	#[structopt(long = "blocks-pruning", alias = "keep-blocks", value_name = "PRUNING_MODE")]
+	pub keep_blocks: Option<String>,
 }
 
 impl PruningParams {
@@ -46,6 +56,7 @@ impl PruningParams {
 		// unless `unsafe_pruning` is set.
 		Ok(match &self.pruning {
 			Some(ref s) if s == "archive" => PruningMode::ArchiveAll,
+			Some(ref s) if s == "archive-canonical" => PruningMode::ArchiveCanonical,
 			None if role.is_authority() => PruningMode::ArchiveAll,
 			None => PruningMode::default(),
 			Some(s) => {
@@ -66,9 +77,13 @@ impl PruningParams {
 
 	/// Get the block pruning value from the parameters
 	pub fn keep_blocks(&self) -> error::Result<KeepBlocks> {
-		Ok(match self.keep_blocks {
-			Some(n) => KeepBlocks::Some(n),
+		Ok(match &self.keep_blocks {
+			Some(ref s) if s == "archive" => KeepBlocks::All,
+			Some(ref s) if s == "archive-canonical" => KeepBlocks::Finalized,
 			None => KeepBlocks::All,
+			Some(s) => KeepBlocks::Some(s.parse().map_err(|_| {
+				error::Error::Input("Invalid blocks pruning mode specified".to_string())
+			})?),
 		})
 	}
 }
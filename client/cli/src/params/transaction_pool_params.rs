@@ -17,6 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use sc_service::config::TransactionPoolOptions;
+use sp_runtime::Percent;
 use structopt::StructOpt;
 
 /// Parameters used to create the pool configuration.
@@ -29,6 +30,21 @@ pub struct TransactionPoolParams {
 	/// Maximum number of kilobytes of all transactions stored in the pool.
 	#[structopt(long = "pool-kbytes", value_name = "COUNT", default_value = "20480")]
 	pub pool_kbytes: usize,
+
+	/// Maximum number of future transactions of a single sender in the transaction pool.
+	#[structopt(long = "pool-future-per-sender", value_name = "COUNT", default_value = "64")]
+	pub pool_future_per_sender: usize,
+
+	/// Minimal priority increase (in percent) required to replace a future transaction with the
+	/// same nonce.
+	#[structopt(long = "pool-replacement-bump", value_name = "PERCENT", default_value = "10")]
+	pub pool_replacement_bump: u8,
+
+	/// Do not propagate locally submitted transactions to other peers.
+	///
+	/// Such transactions are only included in blocks authored by this node.
+	#[structopt(long = "pool-private-local")]
+	pub pool_private_local: bool,
 }
 
 impl TransactionPoolParams {
@@ -44,6 +60,11 @@ impl TransactionPoolParams {
 		let factor = 10;
 		opts.future.count = self.pool_limit / factor;
 		opts.future.total_bytes = self.pool_kbytes * 1024 / factor;
+		if let Some(ref mut per_sender) = opts.future_per_sender {
+			per_sender.count = self.pool_future_per_sender;
+		}
+		opts.future_replacement_bump = Percent::from_percent(self.pool_replacement_bump);
+		opts.private_local_transactions = self.pool_private_local;
 
 		opts
 	}
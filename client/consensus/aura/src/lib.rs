@@ -47,10 +47,10 @@ use codec::{Codec, Decode, Encode};
 use sc_client_api::{backend::AuxStore, BlockOf, UsageProvider};
 use sc_consensus::{BlockImport, BlockImportParams, ForkChoiceStrategy, StateAction};
 use sc_consensus_slots::{
-	BackoffAuthoringBlocksStrategy, InherentDataProviderExt, SlotInfo, StorageChanges,
+	InherentDataProviderExt, NextAuthorityKeysCheck, SlotInfo, StorageChanges,
 };
 use sc_telemetry::TelemetryHandle;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_application_crypto::{AppKey, AppPublic};
 use sp_blockchain::{HeaderBackend, ProvideCache, Result as CResult};
 use sp_consensus::{
@@ -65,13 +65,18 @@ use sp_runtime::{
 	traits::{Block as BlockT, DigestItemFor, Header, Member, NumberFor, Zero},
 };
 
+use prometheus_endpoint::Registry;
+
 mod import_queue;
 
 pub use import_queue::{
 	build_verifier, import_queue, AuraVerifier, BuildVerifierParams, CheckForEquivocation,
 	ImportQueueParams,
 };
-pub use sc_consensus_slots::SlotProportion;
+pub use sc_consensus_slots::{
+	BackoffAuthoringBlocksStrategy, BackoffAuthoringOnFinalizedHeadLagging, SlotLenienceType,
+	SlotProportion,
+};
 pub use sp_consensus::SyncOracle;
 pub use sp_consensus_aura::{
 	digests::CompatibleDigestItem,
@@ -149,8 +154,12 @@ pub struct StartAuraParams<C, SC, I, PF, SO, L, CIDP, BS, CAW> {
 	/// The maximum proportion of the slot dedicated to proposing with any lenience factor applied
 	/// due to no blocks being produced.
 	pub max_block_proposal_slot_portion: Option<SlotProportion>,
+	/// How the proposing lenience grows with the number of slots skipped since the last block.
+	pub slot_lenience_type: SlotLenienceType,
 	/// Telemetry instance used to report telemetry metrics.
 	pub telemetry: Option<TelemetryHandle>,
+	/// Registry to report whether the local keys are part of the next authority set.
+	pub registry: Option<Registry>,
 }
 
 /// Start the aura worker. The returned future should be run in a futures executor.
@@ -170,7 +179,9 @@ pub fn start_aura<P, B, C, SC, I, PF, SO, L, CIDP, BS, CAW, Error>(
 		can_author_with,
 		block_proposal_slot_portion,
 		max_block_proposal_slot_portion,
+		slot_lenience_type,
 		telemetry,
+		registry,
 	}: StartAuraParams<C, SC, I, PF, SO, L, CIDP, BS, CAW>,
 ) -> Result<impl Future<Output = ()>, sp_consensus::Error>
 where
@@ -204,6 +215,8 @@ where
 		telemetry,
 		block_proposal_slot_portion,
 		max_block_proposal_slot_portion,
+		slot_lenience_type,
+		registry,
 	});
 
 	Ok(sc_consensus_slots::start_slot_worker(
@@ -243,8 +256,12 @@ pub struct BuildAuraWorkerParams<C, I, PF, SO, L, BS> {
 	/// The maximum proportion of the slot dedicated to proposing with any lenience factor applied
 	/// due to no blocks being produced.
 	pub max_block_proposal_slot_portion: Option<SlotProportion>,
+	/// How the proposing lenience grows with the number of slots skipped since the last block.
+	pub slot_lenience_type: SlotLenienceType,
 	/// Telemetry instance used to report telemetry metrics.
 	pub telemetry: Option<TelemetryHandle>,
+	/// Registry to report whether the local keys are part of the next authority set.
+	pub registry: Option<Registry>,
 }
 
 /// Build the aura worker.
@@ -261,8 +278,10 @@ pub fn build_aura_worker<P, B, C, PF, I, SO, L, BS, Error>(
 		keystore,
 		block_proposal_slot_portion,
 		max_block_proposal_slot_portion,
+		slot_lenience_type,
 		telemetry,
 		force_authoring,
+		registry,
 	}: BuildAuraWorkerParams<C, I, PF, SO, L, BS>,
 ) -> impl sc_consensus_slots::SlotWorker<B, <PF::Proposer as Proposer<B>>::Proof>
 where
@@ -280,11 +299,18 @@ where
 	L: sc_consensus::JustificationSyncLink<B>,
 	BS: BackoffAuthoringBlocksStrategy<NumberFor<B>> + Send + Sync + 'static,
 {
+	let next_authority_keys = NextAuthorityKeysCheck::new(
+		keystore.clone(),
+		<AuthorityId<P> as AppKey>::ID,
+		registry.as_ref(),
+	);
+
 	AuraWorker {
 		client,
 		block_import,
 		env: proposer_factory,
 		keystore,
+		next_authority_keys,
 		sync_oracle,
 		justification_sync_link,
 		force_authoring,
@@ -292,6 +318,7 @@ where
 		telemetry,
 		block_proposal_slot_portion,
 		max_block_proposal_slot_portion,
+		slot_lenience_type,
 		_key_type: PhantomData::<P>,
 	}
 }
@@ -301,12 +328,14 @@ struct AuraWorker<C, E, I, P, SO, L, BS> {
 	block_import: I,
 	env: E,
 	keystore: SyncCryptoStorePtr,
+	next_authority_keys: NextAuthorityKeysCheck,
 	sync_oracle: SO,
 	justification_sync_link: L,
 	force_authoring: bool,
 	backoff_authoring_blocks: Option<BS>,
 	block_proposal_slot_portion: SlotProportion,
 	max_block_proposal_slot_portion: Option<SlotProportion>,
+	slot_lenience_type: SlotLenienceType,
 	telemetry: Option<TelemetryHandle>,
 	_key_type: PhantomData<P>,
 }
@@ -377,6 +406,23 @@ where
 		})
 	}
 
+	fn notify_slot(&self, header: &B::Header, _slot: Slot, _epoch_data: &Self::EpochData) {
+		let at = BlockId::Hash(header.hash());
+		let runtime_api = self.client.runtime_api();
+		let has_next_authorities = runtime_api
+			.has_api_with::<dyn AuraApi<B, AuthorityId<P>>, _>(&at, |v| v >= 2)
+			.unwrap_or(false);
+		if !has_next_authorities {
+			return
+		}
+
+		if let Ok(next_authorities) = runtime_api.next_authorities(&at) {
+			self.next_authority_keys.check(&next_authorities, || {
+				Some(next_authorities.iter().map(|id| id.to_raw_vec()).collect())
+			});
+		}
+	}
+
 	fn pre_digest_data(
 		&self,
 		slot: Slot,
@@ -486,7 +532,7 @@ where
 			slot_info,
 			&self.block_proposal_slot_portion,
 			self.max_block_proposal_slot_portion.as_ref(),
-			sc_consensus_slots::SlotLenienceType::Exponential,
+			self.slot_lenience_type,
 			self.logging_target(),
 		)
 	}
@@ -773,7 +819,9 @@ mod tests {
 					can_author_with: sp_consensus::AlwaysCanAuthor,
 					block_proposal_slot_portion: SlotProportion::new(0.5),
 					max_block_proposal_slot_portion: None,
+					slot_lenience_type: SlotLenienceType::Exponential,
 					telemetry: None,
+					registry: None,
 				})
 				.expect("Starts aura"),
 			);
@@ -826,11 +874,13 @@ mod tests {
 		let client = peer.client().as_full().expect("full clients are created").clone();
 		let environ = DummyFactory(client.clone());
 
+		let keystore: SyncCryptoStorePtr = keystore.into();
 		let worker = AuraWorker {
 			client: client.clone(),
 			block_import: client,
 			env: environ,
-			keystore: keystore.into(),
+			keystore: keystore.clone(),
+			next_authority_keys: NextAuthorityKeysCheck::new(keystore, AuthorityPair::ID, None),
 			sync_oracle: DummyOracle.clone(),
 			justification_sync_link: (),
 			force_authoring: false,
@@ -839,6 +889,7 @@ mod tests {
 			_key_type: PhantomData::<AuthorityPair>,
 			block_proposal_slot_portion: SlotProportion::new(0.5),
 			max_block_proposal_slot_portion: None,
+			slot_lenience_type: SlotLenienceType::Exponential,
 		};
 
 		let head = Header::new(
@@ -878,11 +929,13 @@ mod tests {
 		let client = peer.client().as_full().expect("full clients are created").clone();
 		let environ = DummyFactory(client.clone());
 
+		let keystore: SyncCryptoStorePtr = keystore.into();
 		let mut worker = AuraWorker {
 			client: client.clone(),
 			block_import: client.clone(),
 			env: environ,
-			keystore: keystore.into(),
+			keystore: keystore.clone(),
+			next_authority_keys: NextAuthorityKeysCheck::new(keystore, AuthorityPair::ID, None),
 			sync_oracle: DummyOracle.clone(),
 			justification_sync_link: (),
 			force_authoring: false,
@@ -891,6 +944,7 @@ mod tests {
 			_key_type: PhantomData::<AuthorityPair>,
 			block_proposal_slot_portion: SlotProportion::new(0.5),
 			max_block_proposal_slot_portion: None,
+			slot_lenience_type: SlotLenienceType::Exponential,
 		};
 
 		let head = client.header(&BlockId::Number(0)).unwrap().unwrap();
@@ -908,5 +962,20 @@ mod tests {
 
 		// The returned block should be imported and we should be able to get its header by now.
 		assert!(client.header(&BlockId::Hash(res.block.hash())).unwrap().is_some());
+
+		// three slots were skipped since the returned block.
+		let slot_info = SlotInfo {
+			slot: 4.into(),
+			timestamp: 0.into(),
+			ends_at: Instant::now() + Duration::from_secs(100),
+			inherent_data: InherentData::new(),
+			duration: Duration::from_millis(1000),
+			chain_head: res.block.header,
+			block_size_limit: None,
+		};
+		assert_eq!(worker.proposing_remaining_duration(&slot_info), Duration::from_millis(1500));
+
+		worker.slot_lenience_type = SlotLenienceType::Linear;
+		assert_eq!(worker.proposing_remaining_duration(&slot_info), Duration::from_millis(2000));
 	}
 }
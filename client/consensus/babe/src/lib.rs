@@ -67,7 +67,13 @@
 #![warn(missing_docs)]
 
 use std::{
-	borrow::Cow, collections::HashMap, convert::TryInto, pin::Pin, sync::Arc, time::Duration, u64,
+	borrow::Cow,
+	collections::{BTreeSet, HashMap},
+	convert::TryInto,
+	pin::Pin,
+	sync::Arc,
+	time::Duration,
+	u64,
 };
 
 use codec::{Decode, Encode};
@@ -96,8 +102,8 @@ use sc_consensus_epochs::{
 	descendent_query, Epoch as EpochT, EpochChangesFor, SharedEpochChanges, ViableEpochDescriptor,
 };
 use sc_consensus_slots::{
-	check_equivocation, BackoffAuthoringBlocksStrategy, CheckedHeader, InherentDataProviderExt,
-	SlotInfo, StorageChanges,
+	check_equivocation, CheckedHeader, InherentDataProviderExt, NextAuthorityKeysCheck, SlotInfo,
+	StorageChanges, MAX_SLOT_CAPACITY,
 };
 use sc_telemetry::{telemetry, TelemetryHandle, CONSENSUS_DEBUG, CONSENSUS_TRACE};
 use sp_api::{ApiExt, NumberFor, ProvideRuntimeApi};
@@ -120,7 +126,9 @@ use sp_runtime::{
 	traits::{Block as BlockT, DigestItemFor, Header, Zero},
 };
 
-pub use sc_consensus_slots::SlotProportion;
+pub use sc_consensus_slots::{
+	BackoffAuthoringBlocksStrategy, BackoffAuthoringOnFinalizedHeadLagging, SlotProportion,
+};
 pub use sp_consensus::SyncOracle;
 pub use sp_consensus_babe::{
 	digests::{
@@ -287,6 +295,9 @@ pub enum Error<B: BlockT> {
 	/// Unexpected config change.
 	#[display(fmt = "Unexpected config change")]
 	UnexpectedConfigChange,
+	/// Invalid config change.
+	#[display(fmt = "Invalid config change, c must be in (0, 1]")]
+	InvalidConfigChange,
 	/// Unexpected epoch change
 	#[display(fmt = "Unexpected epoch change")]
 	UnexpectedEpochChange,
@@ -440,6 +451,9 @@ pub struct BabeParams<B: BlockT, C, SC, E, I, SO, L, CIDP, BS, CAW> {
 
 	/// Handle use to report telemetries.
 	pub telemetry: Option<TelemetryHandle>,
+
+	/// Registry to report whether the local keys are part of the next authority set.
+	pub registry: Option<Registry>,
 }
 
 /// Start the babe worker.
@@ -460,6 +474,7 @@ pub fn start_babe<B, C, SC, E, I, SO, CIDP, BS, CAW, L, Error>(
 		block_proposal_slot_portion,
 		max_block_proposal_slot_portion,
 		telemetry,
+		registry,
 	}: BabeParams<B, C, SC, E, I, SO, L, CIDP, BS, CAW>,
 ) -> Result<BabeWorker<B>, sp_consensus::Error>
 where
@@ -494,6 +509,9 @@ where
 	let config = babe_link.config;
 	let slot_notification_sinks = Arc::new(Mutex::new(Vec::new()));
 
+	let next_authority_keys =
+		NextAuthorityKeysCheck::new(keystore.clone(), AuthorityId::ID, registry.as_ref());
+
 	let worker = BabeSlotWorker {
 		client: client.clone(),
 		block_import,
@@ -503,6 +521,7 @@ where
 		force_authoring,
 		backoff_authoring_blocks,
 		keystore,
+		next_authority_keys,
 		epoch_changes: babe_link.epoch_changes.clone(),
 		slot_notification_sinks: slot_notification_sinks.clone(),
 		config: config.clone(),
@@ -664,6 +683,7 @@ struct BabeSlotWorker<B: BlockT, C, E, I, SO, L, BS> {
 	force_authoring: bool,
 	backoff_authoring_blocks: Option<BS>,
 	keystore: SyncCryptoStorePtr,
+	next_authority_keys: NextAuthorityKeysCheck,
 	epoch_changes: SharedEpochChanges<B, Epoch>,
 	slot_notification_sinks: SlotNotificationSinks<B>,
 	config: Config,
@@ -756,10 +776,26 @@ where
 
 	fn notify_slot(
 		&self,
-		_parent_header: &B::Header,
+		parent_header: &B::Header,
 		slot: Slot,
 		epoch_descriptor: &ViableEpochDescriptor<B::Hash, NumberFor<B>, Epoch>,
 	) {
+		let epoch_start = epoch_descriptor.start_slot();
+		self.next_authority_keys.check(epoch_start, || {
+			let next_epoch = self
+				.client
+				.runtime_api()
+				.next_epoch(&BlockId::Hash(parent_header.hash()))
+				.ok()?;
+
+			// the parent is still in the previous epoch, check once it is in the current one.
+			if next_epoch.start_slot <= epoch_start {
+				return None
+			}
+
+			Some(next_epoch.authorities.iter().map(|(id, _)| id.to_raw_vec()).collect())
+		});
+
 		self.slot_notification_sinks.lock().retain_mut(|sink| {
 			match sink.try_send((slot, epoch_descriptor.clone())) {
 				Ok(()) => true,
@@ -792,7 +828,8 @@ where
 				StorageChanges<I::Transaction, B>,
 				Self::Claim,
 				Self::EpochData,
-			) -> Result<sc_consensus::BlockImportParams<B, I::Transaction>, sp_consensus::Error>
+			)
+				-> Result<sc_consensus::BlockImportParams<B, I::Transaction>, sp_consensus::Error>
 			+ Send
 			+ 'static,
 	> {
@@ -981,6 +1018,30 @@ impl<Block: BlockT> BabeLink<Block> {
 	}
 }
 
+/// The equivocations already reported to the runtime by a [`BabeVerifier`].
+///
+/// Every further header of an equivocating author at the same slot is detected as an
+/// equivocation again, the report is only submitted once.
+#[derive(Default)]
+struct ReportedEquivocations(BTreeSet<(Slot, AuthorityId)>);
+
+impl ReportedEquivocations {
+	/// Whether the equivocation of `author` at `slot` was already reported.
+	///
+	/// Reports of slots which are too old to be checked for equivocations at `slot_now` are
+	/// forgotten.
+	fn contains(&mut self, slot_now: Slot, slot: Slot, author: &AuthorityId) -> bool {
+		let oldest_checked = slot_now.saturating_sub(MAX_SLOT_CAPACITY);
+		self.0 = self.0.split_off(&(oldest_checked, AuthorityId::default()));
+		self.0.contains(&(slot, author.clone()))
+	}
+
+	/// Note that the equivocation of `author` at `slot` was reported.
+	fn insert(&mut self, slot: Slot, author: AuthorityId) {
+		self.0.insert((slot, author));
+	}
+}
+
 /// A verifier for Babe blocks.
 pub struct BabeVerifier<Block: BlockT, Client, SelectChain, CAW, CIDP> {
 	client: Arc<Client>,
@@ -990,6 +1051,7 @@ pub struct BabeVerifier<Block: BlockT, Client, SelectChain, CAW, CIDP> {
 	epoch_changes: SharedEpochChanges<Block, Epoch>,
 	can_author_with: CAW,
 	telemetry: Option<TelemetryHandle>,
+	reported_equivocations: Mutex<ReportedEquivocations>,
 }
 
 impl<Block, Client, SelectChain, CAW, CIDP> BabeVerifier<Block, Client, SelectChain, CAW, CIDP>
@@ -1060,6 +1122,16 @@ where
 				None => return Ok(()),
 			};
 
+		if self.reported_equivocations.lock().contains(slot_now, slot, author) {
+			debug!(
+				target: "babe",
+				"Equivocation of {:?} at slot {} was already reported.",
+				author,
+				slot,
+			);
+			return Ok(())
+		}
+
 		info!(
 			"Slot author {:?} is equivocating at slot {} with headers {:?} and {:?}",
 			author,
@@ -1113,6 +1185,7 @@ where
 			)
 			.map_err(Error::RuntimeApi)?;
 
+		self.reported_equivocations.lock().insert(slot, author.clone());
 		info!(target: "babe", "Submitted equivocation report for author {:?}", author);
 
 		Ok(())
@@ -1532,9 +1605,15 @@ where
 						ConsensusError::ClientImport(Error::<Block>::FetchEpoch(parent_hash).into())
 					})?;
 
-				let epoch_config = next_config_digest
-					.map(Into::into)
-					.unwrap_or_else(|| viable_epoch.as_ref().config.clone());
+				let epoch_config = match next_config_digest.map(BabeEpochConfiguration::from) {
+					// an invalid `c` would break the threshold calculation of the next epoch.
+					Some(config) if !config.is_valid() =>
+						return Err(ConsensusError::ClientImport(
+							babe_err(Error::<Block>::InvalidConfigChange).into(),
+						)),
+					Some(config) => config,
+					None => viable_epoch.as_ref().config.clone(),
+				};
 
 				// restrict info logging during initial sync to avoid spam
 				let log_level = if block.origin == BlockOrigin::NetworkInitialSync {
@@ -1770,6 +1849,7 @@ where
 		can_author_with,
 		telemetry,
 		client,
+		reported_equivocations: Default::default(),
 	};
 
 	Ok(BasicQueue::new(verifier, Box::new(block_import), justification_import, spawner, registry))
@@ -347,6 +347,7 @@ impl TestNetFactory for BabeTestNet {
 				epoch_changes: data.link.epoch_changes.clone(),
 				can_author_with: AlwaysCanAuthor,
 				telemetry: None,
+				reported_equivocations: Default::default(),
 			},
 			mutator: MUTATOR.with(|m| m.borrow().clone()),
 		}
@@ -465,6 +466,7 @@ fn run_one_test(mutator: impl Fn(&mut TestHeader, Stage) + Send + Sync + 'static
 				block_proposal_slot_portion: SlotProportion::new(0.5),
 				max_block_proposal_slot_portion: None,
 				telemetry: None,
+				registry: None,
 			})
 			.expect("Starts babe"),
 		);
@@ -863,6 +865,24 @@ fn verify_slots_are_strictly_increasing() {
 	propose_and_import_block(&b1, Some(999.into()), &mut proposer_factory, &mut block_import);
 }
 
+#[test]
+fn equivocations_are_only_reported_once() {
+	let author = AuthorityPair::from_seed(&[1; 32]).public();
+	let other = AuthorityPair::from_seed(&[2; 32]).public();
+	let mut reported = ReportedEquivocations::default();
+
+	assert!(!reported.contains(10.into(), 10.into(), &author));
+	reported.insert(10.into(), author.clone());
+	assert!(reported.contains(11.into(), 10.into(), &author));
+	assert!(!reported.contains(11.into(), 10.into(), &other));
+	assert!(!reported.contains(11.into(), 11.into(), &author));
+
+	// reports are forgotten once their slot can't be checked anymore.
+	let slot_now = Slot::from(10 + MAX_SLOT_CAPACITY + 1);
+	assert!(!reported.contains(slot_now, 10.into(), &author));
+	assert!(reported.0.is_empty());
+}
+
 #[test]
 fn babe_transcript_generation_match() {
 	sp_tracing::try_init_simple();
@@ -40,6 +40,8 @@ pub enum ImportResult {
 	UnknownParent,
 	/// Parent state is missing.
 	MissingState,
+	/// Block is too far below the last finalized block to be imported.
+	Stale,
 }
 
 /// Auxiliary data associated with an imported block result.
@@ -173,6 +173,8 @@ pub enum BlockImportError {
 	MissingState,
 	/// Block has an unknown parent
 	UnknownParent,
+	/// Block is too far below the last finalized block to be imported.
+	Stale,
 	/// Block import has been cancelled. This can happen if the parent block fails to be imported.
 	Cancelled,
 	/// Other error.
@@ -240,6 +242,10 @@ pub(crate) async fn import_single_block_metered<
 					number, hash, parent_hash);
 			Err(BlockImportError::UnknownParent)
 		},
+		Ok(ImportResult::Stale) => {
+			debug!(target: "sync", "Stale block {}: {:?}", number, hash);
+			Err(BlockImportError::Stale)
+		},
 		Ok(ImportResult::KnownBad) => {
 			debug!(target: "sync", "Peer gave us a bad block {}: {:?}", number, hash);
 			Err(BlockImportError::BadBlock(peer.clone()))
@@ -302,8 +308,10 @@ pub(crate) async fn import_single_block_metered<
 
 	let cache = HashMap::from_iter(maybe_keys.unwrap_or_default());
 	let import_block = import_block.clear_storage_changes_and_mutate();
+	let import_started = std::time::Instant::now();
 	let imported = import_handle.import_block(import_block, cache).await;
 	if let Some(metrics) = metrics.as_ref() {
+		metrics.report_import_time(&block_origin, import_started.elapsed());
 		metrics.report_verification_and_import(started.elapsed());
 	}
 	import_handler(imported)
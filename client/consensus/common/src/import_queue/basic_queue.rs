@@ -48,6 +48,8 @@ pub struct BasicQueue<B: BlockT, Transaction> {
 	block_import_sender: TracingUnboundedSender<worker_messages::ImportBlocks<B>>,
 	/// Results coming from the worker task.
 	result_port: BufferedLinkReceiver<B>,
+	/// Prometheus metrics, shared with the worker task.
+	metrics: Option<Metrics>,
 	_phantom: PhantomData<Transaction>,
 }
 
@@ -86,12 +88,18 @@ impl<B: BlockT, Transaction: Send + 'static> BasicQueue<B, Transaction> {
 			verifier,
 			block_import,
 			justification_import,
-			metrics,
+			metrics.clone(),
 		);
 
 		spawner.spawn_essential_blocking("basic-block-import-worker", future.boxed());
 
-		Self { justification_sender, block_import_sender, result_port, _phantom: PhantomData }
+		Self {
+			justification_sender,
+			block_import_sender,
+			result_port,
+			metrics,
+			_phantom: PhantomData,
+		}
 	}
 }
 
@@ -102,6 +110,10 @@ impl<B: BlockT, Transaction: Send> ImportQueue<B> for BasicQueue<B, Transaction>
 		}
 
 		trace!(target: "sync", "Scheduling {} blocks for import", blocks.len());
+		if let Some(metrics) = self.metrics.as_ref() {
+			metrics.import_queue_blocks.add(blocks.len() as u64);
+		}
+
 		let res = self
 			.block_import_sender
 			.unbounded_send(worker_messages::ImportBlocks(origin, blocks));
@@ -388,6 +400,7 @@ async fn import_many_blocks<B: BlockT, V: Verifier<B>, Transaction: Send + 'stat
 
 		if let Some(metrics) = metrics.as_ref() {
 			metrics.report_import::<B>(&import_result);
+			metrics.import_queue_blocks.dec();
 		}
 
 		if import_result.is_ok() {
@@ -19,10 +19,11 @@
 //! Metering tools for consensus
 
 use prometheus_endpoint::{
-	register, CounterVec, Histogram, HistogramOpts, HistogramVec, Opts, PrometheusError, Registry,
-	U64,
+	register, CounterVec, Gauge, Histogram, HistogramOpts, HistogramVec, Opts, PrometheusError,
+	Registry, U64,
 };
 
+use sp_consensus::BlockOrigin;
 use sp_runtime::traits::{Block as BlockT, NumberFor};
 
 use crate::import_queue::{BlockImportError, BlockImportStatus};
@@ -31,7 +32,9 @@ use crate::import_queue::{BlockImportError, BlockImportStatus};
 #[derive(Clone)]
 pub(crate) struct Metrics {
 	pub import_queue_processed: CounterVec<U64>,
+	pub import_queue_blocks: Gauge<U64>,
 	pub block_verification_time: HistogramVec,
+	pub block_import_time: HistogramVec,
 	pub block_verification_and_import_time: Histogram,
 	pub justification_import_time: Histogram,
 }
@@ -46,6 +49,10 @@ impl Metrics {
 				)?,
 				registry,
 			)?,
+			import_queue_blocks: register(
+				Gauge::new("import_queue_blocks", "Number of blocks waiting in the import queue")?,
+				registry,
+			)?,
 			block_verification_time: register(
 				HistogramVec::new(
 					HistogramOpts::new("block_verification_time", "Time taken to verify blocks"),
@@ -53,6 +60,16 @@ impl Metrics {
 				)?,
 				registry,
 			)?,
+			block_import_time: register(
+				HistogramVec::new(
+					HistogramOpts::new(
+						"block_import_time",
+						"Time taken to import verified blocks, by block origin",
+					),
+					&["origin"],
+				)?,
+				registry,
+			)?,
 			block_verification_and_import_time: register(
 				Histogram::with_opts(HistogramOpts::new(
 					"block_verification_and_import_time",
@@ -81,6 +98,7 @@ impl Metrics {
 			Err(BlockImportError::BadBlock(_)) => "bad_block",
 			Err(BlockImportError::MissingState) => "missing_state",
 			Err(BlockImportError::UnknownParent) => "unknown_parent",
+			Err(BlockImportError::Stale) => "stale",
 			Err(BlockImportError::Cancelled) => "cancelled",
 			Err(BlockImportError::Other(_)) => "failed",
 		};
@@ -94,6 +112,19 @@ impl Metrics {
 			.observe(time.as_secs_f64());
 	}
 
+	pub fn report_import_time(&self, origin: &BlockOrigin, time: std::time::Duration) {
+		let label = match origin {
+			BlockOrigin::Genesis => "genesis",
+			BlockOrigin::NetworkInitialSync => "network_initial_sync",
+			BlockOrigin::NetworkBroadcast => "network_broadcast",
+			BlockOrigin::ConsensusBroadcast => "consensus_broadcast",
+			BlockOrigin::Own => "own",
+			BlockOrigin::File => "file",
+		};
+
+		self.block_import_time.with_label_values(&[label]).observe(time.as_secs_f64());
+	}
+
 	pub fn report_verification_and_import(&self, time: std::time::Duration) {
 		self.block_verification_and_import_time.observe(time.as_secs_f64());
 	}
@@ -21,7 +21,10 @@
 
 use futures::prelude::*;
 use prometheus_endpoint::Registry;
-use sc_client_api::backend::{Backend as ClientBackend, Finalizer};
+use sc_client_api::{
+	backend::{Backend as ClientBackend, Finalizer},
+	BlockchainEvents,
+};
 use sc_consensus::{
 	block_import::{BlockImport, BlockImportParams, ForkChoiceStrategy},
 	import_queue::{BasicQueue, BoxBlockImport, Verifier},
@@ -29,7 +32,10 @@ use sc_consensus::{
 use sp_blockchain::HeaderBackend;
 use sp_consensus::{CacheKeyId, Environment, Proposer, SelectChain};
 use sp_inherents::CreateInherentDataProviders;
-use sp_runtime::{traits::Block as BlockT, ConsensusEngineId};
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT, Saturating},
+	ConsensusEngineId,
+};
 use std::{marker::PhantomData, sync::Arc};
 
 mod error;
@@ -245,6 +251,54 @@ pub async fn run_instant_seal<B, BI, CB, E, C, TP, SC, CIDP>(
 	.await
 }
 
+/// Params required to start the delayed finalization task.
+pub struct DelayedFinalizeParams<C> {
+	/// Client instance
+	pub client: Arc<C>,
+
+	/// The number of blocks the finalized block is kept behind the best block.
+	pub finality_lag: u32,
+}
+
+/// runs the background task finalizing the block `finality_lag` blocks behind every new best
+/// block. Together with the manual or instant seal engine, this simulates a finality gadget
+/// lagging behind block production.
+pub async fn run_delayed_finalize<B, CB, C>(
+	DelayedFinalizeParams { client, finality_lag }: DelayedFinalizeParams<C>,
+) where
+	B: BlockT + 'static,
+	CB: ClientBackend<B> + 'static,
+	C: HeaderBackend<B> + Finalizer<B, CB> + BlockchainEvents<B> + 'static,
+{
+	let mut import_stream = client.import_notification_stream();
+
+	while let Some(notification) = import_stream.next().await {
+		if !notification.is_new_best {
+			continue
+		}
+
+		let target = notification.header.number().saturating_sub(finality_lag.into());
+		if target <= client.info().finalized_number {
+			continue
+		}
+
+		// the best chain was updated before the notification, the target is on it.
+		let hash = match client.hash(target) {
+			Ok(Some(hash)) => hash,
+			_ => continue,
+		};
+
+		finalize_block(FinalizeBlockParams {
+			hash,
+			sender: None,
+			justification: None,
+			finalizer: client.clone(),
+			_phantom: PhantomData,
+		})
+		.await
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -519,4 +573,60 @@ mod tests {
 		// assert that fork block is in the db
 		assert!(client.header(&BlockId::Hash(imported.hash)).unwrap().is_some())
 	}
+
+	#[tokio::test]
+	async fn delayed_finalize() {
+		let builder = TestClientBuilder::new();
+		let (client, select_chain) = builder.build_with_longest_chain();
+		let client = Arc::new(client);
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let pool = Arc::new(BasicPool::with_revalidation_type(
+			Options::default(),
+			true.into(),
+			api(),
+			None,
+			RevalidationType::Full,
+			spawner.clone(),
+			0,
+		));
+		let env = ProposerFactory::new(spawner.clone(), client.clone(), pool.clone(), None, None);
+		// this test checks that blocks are finalized two blocks behind the best block.
+		let (mut sink, commands_stream) = futures::channel::mpsc::channel(1024);
+		let seal_future = run_manual_seal(ManualSealParams {
+			block_import: client.clone(),
+			env,
+			client: client.clone(),
+			pool: pool.clone(),
+			commands_stream,
+			select_chain,
+			consensus_data_provider: None,
+			create_inherent_data_providers: |_, _| async { Ok(()) },
+		});
+		let finalize_future =
+			run_delayed_finalize(DelayedFinalizeParams { client: client.clone(), finality_lag: 2 });
+		let mut finality_stream = client.finality_notification_stream();
+		std::thread::spawn(|| {
+			let rt = tokio::runtime::Runtime::new().unwrap();
+			// spawn the background authorship and finalization tasks
+			rt.block_on(future::join(seal_future, finalize_future));
+		});
+
+		for _ in 0..4 {
+			let (tx, rx) = futures::channel::oneshot::channel();
+			sink.send(EngineCommand::SealNewBlock {
+				parent_hash: None,
+				sender: Some(tx),
+				create_empty: true,
+				finalize: false,
+			})
+			.await
+			.unwrap();
+			assert!(rx.await.unwrap().is_ok());
+		}
+
+		// blocks 1 and 2 are finalized once blocks 3 and 4 are imported.
+		assert_eq!(*finality_stream.next().await.unwrap().header.number(), 1);
+		assert_eq!(*finality_stream.next().await.unwrap().header.number(), 2);
+		assert_eq!(client.info().best_number, 4);
+	}
 }
@@ -29,7 +29,8 @@
 //! the worker handle, you can pull the metadata needed to start the
 //! mining process via [`MiningWorker::metadata`], and then do the actual
 //! mining on a standalone thread. Finally, when a seal is found, call
-//! [`MiningWorker::submit`] to build the block.
+//! [`MiningWorker::submit`] to build the block. External miners can do the same over RPC, see
+//! [`rpc::MiningApi`].
 //!
 //! The auxiliary storage for PoW engine only stores the total difficulty.
 //! For other storage requirements for particular PoW algorithm (such as
@@ -39,6 +40,7 @@
 //! as the storage, but it is not recommended as it won't work well with light
 //! clients.
 
+pub mod rpc;
 mod worker;
 
 pub use crate::worker::{MiningBuild, MiningMetadata, MiningWorker};
@@ -546,8 +548,8 @@ where
 	let worker = Arc::new(Mutex::new(MiningWorker {
 		build: None,
 		algorithm: algorithm.clone(),
-		block_import,
-		justification_sync_link,
+		block_import: Arc::new(futures::lock::Mutex::new(block_import)),
+		justification_sync_link: Arc::new(justification_sync_link),
 	}));
 	let worker_ret = worker.clone();
 
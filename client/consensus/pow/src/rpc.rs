@@ -0,0 +1,283 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC interface for external miners, on top of the [`MiningWorker`].
+
+pub use self::gen_client::Client as MiningClient;
+use crate::{worker::MiningWorker, PowAlgorithm};
+use codec::Encode;
+use jsonrpc_core::{Error, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use parking_lot::Mutex;
+use sc_rpc_api::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+use sp_core::Bytes;
+use sp_runtime::traits::Block as BlockT;
+use std::sync::Arc;
+
+/// Error code returned when the submitted work is not the current mining build.
+const STALE_WORK: i64 = 1;
+
+/// The work of the current mining build, as sent to external miners.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Work<Hash> {
+	/// Best hash the block is built on.
+	pub best_hash: Hash,
+	/// The pre-hash to seal.
+	pub pre_hash: Hash,
+	/// Pre-runtime digest item, if any.
+	pub pre_runtime: Option<Bytes>,
+	/// SCALE encoded target difficulty.
+	pub difficulty: Bytes,
+}
+
+/// RPC trait that allows external miners to fetch work and submit seals.
+#[rpc]
+pub trait MiningApi<Hash> {
+	/// Get the work of the current mining build. `None` if there is none, e.g. while syncing.
+	#[rpc(name = "mining_getWork")]
+	fn get_work(&self) -> Result<Option<Work<Hash>>>;
+
+	/// Submit a seal for the work with the given pre-hash. Returns whether the sealed block was
+	/// imported, the work being kept if the seal is invalid.
+	///
+	/// This method is unsafe, any caller submitting a valid seal authors a block of the node.
+	#[rpc(name = "mining_submitWork")]
+	fn submit_work(&self, pre_hash: Hash, seal: Bytes) -> Result<bool>;
+}
+
+/// A struct that implements the [`MiningApi`].
+pub struct Mining<
+	Block: BlockT,
+	Algorithm: PowAlgorithm<Block>,
+	C: sp_api::ProvideRuntimeApi<Block>,
+	L: sc_consensus::JustificationSyncLink<Block>,
+	Proof,
+> {
+	worker: Arc<Mutex<MiningWorker<Block, Algorithm, C, L, Proof>>>,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl<Block, Algorithm, C, L, Proof> Mining<Block, Algorithm, C, L, Proof>
+where
+	Block: BlockT,
+	Algorithm: PowAlgorithm<Block>,
+	C: sp_api::ProvideRuntimeApi<Block>,
+	L: sc_consensus::JustificationSyncLink<Block>,
+{
+	/// Create new `Mining` with the worker returned by [`crate::start_mining_worker`].
+	pub fn new(
+		worker: Arc<Mutex<MiningWorker<Block, Algorithm, C, L, Proof>>>,
+		deny_unsafe: DenyUnsafe,
+	) -> Self {
+		Self { worker, deny_unsafe }
+	}
+}
+
+impl<Block, Algorithm, C, L, Proof> MiningApi<Block::Hash> for Mining<Block, Algorithm, C, L, Proof>
+where
+	Block: BlockT,
+	Algorithm: PowAlgorithm<Block> + Send + 'static,
+	Algorithm::Difficulty: Send + 'static,
+	C: sp_api::ProvideRuntimeApi<Block> + Send + Sync + 'static,
+	L: sc_consensus::JustificationSyncLink<Block> + Send + 'static,
+	Proof: Send + 'static,
+	sp_api::TransactionFor<C, Block>: Send + 'static,
+{
+	fn get_work(&self) -> Result<Option<Work<Block::Hash>>> {
+		Ok(self.worker.lock().metadata().map(|metadata| Work {
+			best_hash: metadata.best_hash,
+			pre_hash: metadata.pre_hash,
+			pre_runtime: metadata.pre_runtime.map(Into::into),
+			difficulty: metadata.difficulty.encode().into(),
+		}))
+	}
+
+	fn submit_work(&self, pre_hash: Block::Hash, seal: Bytes) -> Result<bool> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let import = {
+			let mut worker = self.worker.lock();
+			// a new build replaced the one the seal was mined for.
+			if worker.metadata().map(|metadata| metadata.pre_hash) != Some(pre_hash) {
+				return Err(Error {
+					code: ErrorCode::ServerError(STALE_WORK),
+					message: "Submitted work is stale".into(),
+					data: None,
+				})
+			}
+			worker.verify_seal(seal.to_vec())
+		};
+
+		// The worker is unlocked while the block is imported.
+		Ok(match import {
+			Some(import) => futures::executor::block_on(import),
+			None => false,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::worker::{MiningBuild, MiningMetadata};
+	use sc_consensus::{
+		BlockCheckParams, BlockImport, BlockImportParams, BoxBlockImport, ImportResult,
+	};
+	use sp_consensus::{CacheKeyId, Proposal};
+	use sp_runtime::generic::BlockId;
+	use std::{
+		collections::HashMap,
+		sync::atomic::{AtomicUsize, Ordering},
+	};
+	use substrate_test_runtime_client::{
+		runtime::{Block, Hash, Header},
+		TestClient,
+	};
+
+	type Transaction = sp_api::TransactionFor<TestClient, Block>;
+	type TestMining = Mining<Block, Algorithm, TestClient, (), ()>;
+
+	#[derive(Clone)]
+	struct Algorithm;
+
+	impl PowAlgorithm<Block> for Algorithm {
+		type Difficulty = u128;
+
+		fn difficulty(&self, _parent: Hash) -> std::result::Result<u128, crate::Error<Block>> {
+			Ok(1)
+		}
+
+		fn verify(
+			&self,
+			_parent: &BlockId<Block>,
+			_pre_hash: &Hash,
+			_pre_digest: Option<&[u8]>,
+			seal: &crate::Seal,
+			_difficulty: u128,
+		) -> std::result::Result<bool, crate::Error<Block>> {
+			Ok(&seal[..] == b"valid")
+		}
+	}
+
+	struct CountingImport(Arc<AtomicUsize>);
+
+	#[async_trait::async_trait]
+	impl BlockImport<Block> for CountingImport {
+		type Error = sp_consensus::Error;
+		type Transaction = Transaction;
+
+		async fn check_block(
+			&mut self,
+			_block: BlockCheckParams<Block>,
+		) -> std::result::Result<ImportResult, Self::Error> {
+			Ok(ImportResult::imported(false))
+		}
+
+		async fn import_block(
+			&mut self,
+			_block: BlockImportParams<Block, Transaction>,
+			_cache: HashMap<CacheKeyId, Vec<u8>>,
+		) -> std::result::Result<ImportResult, Self::Error> {
+			self.0.fetch_add(1, Ordering::SeqCst);
+			Ok(ImportResult::imported(true))
+		}
+	}
+
+	fn pre_hash() -> Hash {
+		[1u8; 32].into()
+	}
+
+	fn mining(deny_unsafe: DenyUnsafe) -> (TestMining, Arc<AtomicUsize>) {
+		let imported = Arc::new(AtomicUsize::new(0));
+		let header = Header::new(
+			1,
+			Default::default(),
+			Default::default(),
+			Default::default(),
+			Default::default(),
+		);
+		let build = MiningBuild {
+			metadata: MiningMetadata {
+				best_hash: Default::default(),
+				pre_hash: pre_hash(),
+				pre_runtime: None,
+				difficulty: 1,
+			},
+			proposal: Proposal {
+				block: Block { header, extrinsics: Vec::new() },
+				proof: (),
+				storage_changes: sp_state_machine::StorageChanges {
+					main_storage_changes: Vec::new(),
+					child_storage_changes: Vec::new(),
+					offchain_storage_changes: Default::default(),
+					transaction: Default::default(),
+					transaction_storage_root: Default::default(),
+					changes_trie_transaction: None,
+					transaction_index_changes: Vec::new(),
+				},
+			},
+		};
+		let block_import: BoxBlockImport<Block, Transaction> =
+			Box::new(CountingImport(imported.clone()));
+		let worker = MiningWorker {
+			build: Some(build),
+			algorithm: Algorithm,
+			block_import: Arc::new(futures::lock::Mutex::new(block_import)),
+			justification_sync_link: Arc::new(()),
+		};
+		(Mining::new(Arc::new(Mutex::new(worker)), deny_unsafe), imported)
+	}
+
+	#[test]
+	fn submit_work_imports_a_valid_seal() {
+		let (mining, imported) = mining(DenyUnsafe::No);
+		assert_eq!(mining.get_work().unwrap().map(|work| work.pre_hash), Some(pre_hash()));
+
+		assert_eq!(mining.submit_work(pre_hash(), b"valid".to_vec().into()), Ok(true));
+		assert_eq!(imported.load(Ordering::SeqCst), 1);
+		assert_eq!(mining.get_work(), Ok(None));
+	}
+
+	#[test]
+	fn submit_work_keeps_the_build_on_an_invalid_seal() {
+		let (mining, imported) = mining(DenyUnsafe::No);
+
+		assert_eq!(mining.submit_work(pre_hash(), b"junk".to_vec().into()), Ok(false));
+		assert_eq!(imported.load(Ordering::SeqCst), 0);
+		assert_eq!(mining.get_work().unwrap().map(|work| work.pre_hash), Some(pre_hash()));
+
+		// A stale pre-hash is rejected, the build being kept too.
+		let stale = mining.submit_work(Default::default(), b"valid".to_vec().into()).unwrap_err();
+		assert_eq!(stale.code, ErrorCode::ServerError(STALE_WORK));
+		assert!(mining.get_work().unwrap().is_some());
+
+		assert_eq!(mining.submit_work(pre_hash(), b"valid".to_vec().into()), Ok(true));
+		assert_eq!(imported.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn submit_work_is_unsafe() {
+		let (mining, imported) = mining(DenyUnsafe::Yes);
+
+		assert!(mining.submit_work(pre_hash(), b"valid".to_vec().into()).is_err());
+		assert_eq!(imported.load(Ordering::SeqCst), 0);
+		assert!(mining.get_work().unwrap().is_some());
+	}
+}
@@ -17,6 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use futures::{
+	future::BoxFuture,
 	prelude::*,
 	task::{Context, Poll},
 };
@@ -30,7 +31,7 @@ use sp_runtime::{
 	traits::{Block as BlockT, Header as HeaderT},
 	DigestItem,
 };
-use std::{borrow::Cow, collections::HashMap, pin::Pin, time::Duration};
+use std::{borrow::Cow, collections::HashMap, pin::Pin, sync::Arc, time::Duration};
 
 use crate::{PowAlgorithm, PowIntermediate, Seal, INTERMEDIATE_KEY, POW_ENGINE_ID};
 
@@ -70,8 +71,9 @@ pub struct MiningWorker<
 > {
 	pub(crate) build: Option<MiningBuild<Block, Algorithm, C, Proof>>,
 	pub(crate) algorithm: Algorithm,
-	pub(crate) block_import: BoxBlockImport<Block, sp_api::TransactionFor<C, Block>>,
-	pub(crate) justification_sync_link: L,
+	pub(crate) block_import:
+		Arc<futures::lock::Mutex<BoxBlockImport<Block, sp_api::TransactionFor<C, Block>>>>,
+	pub(crate) justification_sync_link: Arc<L>,
 }
 
 impl<Block, Algorithm, C, L, Proof> MiningWorker<Block, Algorithm, C, L, Proof>
@@ -104,82 +106,111 @@ where
 
 	/// Submit a mined seal. The seal will be validated again. Returns true if the submission is
 	/// successful.
-	pub async fn submit(&mut self, seal: Seal) -> bool {
-		if let Some(build) = self.build.take() {
-			match self.algorithm.verify(
-				&BlockId::Hash(build.metadata.best_hash),
-				&build.metadata.pre_hash,
-				build.metadata.pre_runtime.as_ref().map(|v| &v[..]),
-				&seal,
-				build.metadata.difficulty,
-			) {
-				Ok(true) => (),
-				Ok(false) => {
-					warn!(
-						target: "pow",
-						"Unable to import mined block: seal is invalid",
-					);
-					return false
-				},
-				Err(err) => {
-					warn!(
-						target: "pow",
-						"Unable to import mined block: {:?}",
-						err,
-					);
-					return false
-				},
-			}
+	pub async fn submit(&mut self, seal: Seal) -> bool
+	where
+		L: 'static,
+	{
+		match self.verify_seal(seal) {
+			Some(import) => import.await,
+			None => false,
+		}
+	}
 
-			let seal = DigestItem::Seal(POW_ENGINE_ID, seal);
-			let (header, body) = build.proposal.block.deconstruct();
-
-			let mut import_block = BlockImportParams::new(BlockOrigin::Own, header);
-			import_block.post_digests.push(seal);
-			import_block.body = Some(body);
-			import_block.state_action =
-				StateAction::ApplyChanges(StorageChanges::Changes(build.proposal.storage_changes));
-
-			let intermediate = PowIntermediate::<Algorithm::Difficulty> {
-				difficulty: Some(build.metadata.difficulty),
-			};
-
-			import_block
-				.intermediates
-				.insert(Cow::from(INTERMEDIATE_KEY), Box::new(intermediate) as Box<_>);
-
-			let header = import_block.post_header();
-			match self.block_import.import_block(import_block, HashMap::default()).await {
-				Ok(res) => {
-					res.handle_justification(
-						&header.hash(),
-						*header.number(),
-						&mut self.justification_sync_link,
-					);
-
-					info!(
-						target: "pow",
-						"✅ Successfully mined block on top of: {}",
-						build.metadata.best_hash
-					);
-					true
-				},
-				Err(err) => {
-					warn!(
-						target: "pow",
-						"Unable to import mined block: {:?}",
-						err,
-					);
-					false
-				},
-			}
+	/// Verify a mined seal against the current build, which is only taken if the seal is valid.
+	///
+	/// The returned future imports the sealed block. It doesn't borrow the worker, so that the
+	/// worker can be unlocked while the block is imported.
+	pub fn verify_seal(&mut self, seal: Seal) -> Option<BoxFuture<'static, bool>>
+	where
+		L: 'static,
+	{
+		let build = if let Some(build) = self.build.as_ref() {
+			build
 		} else {
 			warn!(
 				target: "pow",
 				"Unable to import mined block: build does not exist",
 			);
-			false
+			return None
+		};
+
+		match self.algorithm.verify(
+			&BlockId::Hash(build.metadata.best_hash),
+			&build.metadata.pre_hash,
+			build.metadata.pre_runtime.as_ref().map(|v| &v[..]),
+			&seal,
+			build.metadata.difficulty,
+		) {
+			Ok(true) => (),
+			Ok(false) => {
+				warn!(
+					target: "pow",
+					"Unable to import mined block: seal is invalid",
+				);
+				return None
+			},
+			Err(err) => {
+				warn!(
+					target: "pow",
+					"Unable to import mined block: {:?}",
+					err,
+				);
+				return None
+			},
 		}
+
+		let build = self.build.take().expect("the build exists, checked above; qed");
+		let seal = DigestItem::Seal(POW_ENGINE_ID, seal);
+		let (header, body) = build.proposal.block.deconstruct();
+
+		let mut import_block = BlockImportParams::new(BlockOrigin::Own, header);
+		import_block.post_digests.push(seal);
+		import_block.body = Some(body);
+		import_block.state_action =
+			StateAction::ApplyChanges(StorageChanges::Changes(build.proposal.storage_changes));
+
+		let intermediate = PowIntermediate::<Algorithm::Difficulty> {
+			difficulty: Some(build.metadata.difficulty),
+		};
+
+		import_block
+			.intermediates
+			.insert(Cow::from(INTERMEDIATE_KEY), Box::new(intermediate) as Box<_>);
+
+		let block_import = self.block_import.clone();
+		let mut justification_sync_link = self.justification_sync_link.clone();
+		let best_hash = build.metadata.best_hash;
+		Some(
+			async move {
+				let header = import_block.post_header();
+				let mut block_import = block_import.lock().await;
+				match block_import.import_block(import_block, HashMap::default()).await {
+					Ok(res) => {
+						res.handle_justification(
+							&header.hash(),
+							*header.number(),
+							&mut justification_sync_link,
+						);
+
+						info!(
+							target: "pow",
+							"✅ Successfully mined block on top of: {}",
+							best_hash
+						);
+						true
+					},
+					Err(err) => {
+						warn!(
+							target: "pow",
+							"Unable to import mined block: {:?}",
+							err,
+						);
+						false
+					},
+				}
+			}
+			.boxed(),
+		)
 	}
 }
 
@@ -0,0 +1,147 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Detection of local authority keys that are missing from the next authority set.
+//!
+//! Operators rotating their session keys without registering the new ones on chain
+//! silently lose their authoring slots once the next session starts.
+
+use codec::Encode;
+use log::{debug, warn};
+use parking_lot::Mutex;
+use prometheus_endpoint::{register, Gauge, PrometheusError, Registry, U64};
+use sp_core::crypto::KeyTypeId;
+use sp_keystore::{SyncCryptoStore, SyncCryptoStorePtr};
+
+/// Compares the authority set queued for the next session against the local keystore.
+///
+/// The check is performed once per session, it warns when the keystore holds authority keys
+/// but none of them is part of the next authority set.
+pub struct NextAuthorityKeysCheck {
+	keystore: SyncCryptoStorePtr,
+	key_type: KeyTypeId,
+	last_session: Mutex<Option<Vec<u8>>>,
+	keys_missing: Option<Gauge<U64>>,
+}
+
+impl NextAuthorityKeysCheck {
+	/// Create a new check for keys of the given type, registering its metric in `registry`.
+	pub fn new(
+		keystore: SyncCryptoStorePtr,
+		key_type: KeyTypeId,
+		registry: Option<&Registry>,
+	) -> Self {
+		let keys_missing = registry.and_then(|registry| {
+			Self::register(registry)
+				.map_err(
+					|err| warn!(target: "slots", "Failed to register next authority keys metric: {:?}", err),
+				)
+				.ok()
+		});
+
+		Self { keystore, key_type, last_session: Mutex::new(None), keys_missing }
+	}
+
+	fn register(registry: &Registry) -> Result<Gauge<U64>, PrometheusError> {
+		register(
+			Gauge::new(
+				"next_authority_keys_missing",
+				"Whether none of the local authority keys is part of the next authority set.",
+			)?,
+			registry,
+		)
+	}
+
+	/// Check the next authority set, given as raw public keys, if the session changed.
+	///
+	/// `session` identifies the current session, `next_authorities` is only called when it
+	/// differs from the one of the previous check. Returns whether the local keys are missing.
+	pub fn check<S: Encode>(
+		&self,
+		session: S,
+		next_authorities: impl FnOnce() -> Option<Vec<Vec<u8>>>,
+	) -> Option<bool> {
+		let session = session.encode();
+		let mut last_session = self.last_session.lock();
+		if last_session.as_ref() == Some(&session) {
+			return None
+		}
+
+		let next_authorities = next_authorities()?;
+		*last_session = Some(session);
+
+		let local_keys = match SyncCryptoStore::keys(&*self.keystore, self.key_type) {
+			Ok(keys) => keys,
+			Err(err) => {
+				debug!(target: "slots", "Failed to list local authority keys: {:?}", err);
+				return None
+			},
+		};
+
+		// not an authority node, nothing to warn about.
+		let missing = !local_keys.is_empty() &&
+			!local_keys.iter().any(|key| next_authorities.iter().any(|next| next == &key.1));
+
+		if missing {
+			warn!(
+				target: "slots",
+				"⚠️  None of the local {:?} keys is part of the next authority set. \
+				 This node will not author blocks in the next session, make sure \
+				 the rotated keys were registered on chain with `set_keys`.",
+				self.key_type,
+			);
+		}
+
+		if let Some(ref keys_missing) = self.keys_missing {
+			keys_missing.set(missing as u64);
+		}
+
+		Some(missing)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::{sr25519, testing::SR25519};
+	use sp_keystore::testing::KeyStore;
+	use std::sync::Arc;
+
+	#[test]
+	fn warns_once_per_session_about_missing_keys() {
+		let keystore = Arc::new(KeyStore::new());
+		let local = SyncCryptoStore::sr25519_generate_new(&*keystore, SR25519, None).unwrap();
+		let other = sr25519::Public::from_raw([1; 32]);
+		let check = NextAuthorityKeysCheck::new(keystore, SR25519, None);
+
+		assert_eq!(check.check(1u64, || Some(vec![other.0.to_vec()])), Some(true));
+		// the same session is only checked once.
+		assert_eq!(check.check(1u64, || panic!("session already checked")), None);
+		assert_eq!(
+			check.check(2u64, || Some(vec![other.0.to_vec(), local.0.to_vec()])),
+			Some(false)
+		);
+	}
+
+	#[test]
+	fn does_not_warn_without_local_keys() {
+		let check = NextAuthorityKeysCheck::new(Arc::new(KeyStore::new()), SR25519, None);
+
+		assert_eq!(check.check(1u64, || Some(vec![vec![1; 32]])), Some(false));
+	}
+}
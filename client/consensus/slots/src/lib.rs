@@ -25,9 +25,11 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+mod authority_keys;
 mod aux_schema;
 mod slots;
 
+pub use authority_keys::NextAuthorityKeysCheck;
 pub use aux_schema::{check_equivocation, MAX_SLOT_CAPACITY, PRUNING_BOUND};
 pub use slots::SlotInfo;
 use slots::Slots;
@@ -48,7 +50,7 @@ use sp_runtime::{
 	traits::{Block as BlockT, HashFor, Header as HeaderT, NumberFor},
 };
 use sp_timestamp::Timestamp;
-use std::{fmt::Debug, ops::Deref, time::Duration};
+use std::{fmt::Debug, ops::Deref, sync::Arc, time::Duration};
 
 /// The changes that need to applied to the storage to create the state for a block.
 ///
@@ -641,6 +643,7 @@ impl SlotProportion {
 
 /// The strategy used to calculate the slot lenience used to increase the block proposal time when
 /// slots have been skipped with no blocks authored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SlotLenienceType {
 	/// Increase the lenience linearly with the number of skipped slots.
 	Linear,
@@ -658,7 +661,8 @@ impl SlotLenienceType {
 }
 
 /// Calculate the remaining duration for block proposal taking into account whether any slots have
-/// been skipped and applying the given lenience strategy. If `max_block_proposal_slot_portion` is
+/// been skipped and applying the given lenience strategy, or whether the previous block arrived
+/// late. If `max_block_proposal_slot_portion` is
 /// not none this method guarantees that the returned duration must be lower or equal to
 /// `slot_info.duration * max_block_proposal_slot_portion`.
 pub fn proposing_remaining_duration<Block: BlockT>(
@@ -690,26 +694,43 @@ pub fn proposing_remaining_duration<Block: BlockT>(
 		None => return proposing_duration,
 	};
 
+	// if we defined a maximum portion of the slot for proposal then we must make sure the
+	// lenience doesn't go over it
+	let cap_proposing_duration = |lenient_proposing_duration: Duration| {
+		if let Some(ref max_block_proposal_slot_portion) = max_block_proposal_slot_portion {
+			std::cmp::min(
+				lenient_proposing_duration,
+				slot_info.duration.mul_f32(max_block_proposal_slot_portion.get()),
+			)
+		} else {
+			lenient_proposing_duration
+		}
+	};
+
+	if let Some(slot_lenience) =
+		slot_lenience_late_previous_block(parent_slot, slot_info, block_proposal_slot_portion)
+	{
+		let lenient_proposing_duration = cap_proposing_duration(proposing_duration + slot_lenience);
+
+		debug!(
+			target: log_target,
+			"Previous block arrived {}ms late. Applying lenience, total proposing duration: {}ms",
+			slot_lenience.as_millis(),
+			lenient_proposing_duration.as_millis(),
+		);
+
+		return lenient_proposing_duration
+	}
+
 	let slot_lenience = match slot_lenience_type {
 		SlotLenienceType::Exponential => slot_lenience_exponential(parent_slot, slot_info),
 		SlotLenienceType::Linear => slot_lenience_linear(parent_slot, slot_info),
 	};
 
 	if let Some(slot_lenience) = slot_lenience {
-		let lenient_proposing_duration =
-			proposing_duration + slot_lenience.mul_f32(block_proposal_slot_portion.get());
-
-		// if we defined a maximum portion of the slot for proposal then we must make sure the
-		// lenience doesn't go over it
-		let lenient_proposing_duration =
-			if let Some(ref max_block_proposal_slot_portion) = max_block_proposal_slot_portion {
-				std::cmp::min(
-					lenient_proposing_duration,
-					slot_info.duration.mul_f32(max_block_proposal_slot_portion.get()),
-				)
-			} else {
-				lenient_proposing_duration
-			};
+		let lenient_proposing_duration = cap_proposing_duration(
+			proposing_duration + slot_lenience.mul_f32(block_proposal_slot_portion.get()),
+		);
 
 		debug!(
 			target: log_target,
@@ -785,6 +806,31 @@ pub fn slot_lenience_linear<Block: BlockT>(
 	}
 }
 
+/// Calculate a slot duration lenience when the parent block was authored in the previous slot, but
+/// arrived so late into the current one that less than `block_proposal_slot_portion` of the slot
+/// is left for proposing. The lenience is the proposing time lost to the late arrival, so that it
+/// grows proportionally with the delay. If the parent isn't from the previous slot, or enough of
+/// the slot is left, this method will return `None`.
+pub fn slot_lenience_late_previous_block<Block: BlockT>(
+	parent_slot: Slot,
+	slot_info: &SlotInfo<Block>,
+	block_proposal_slot_portion: &SlotProportion,
+) -> Option<Duration> {
+	if slot_info.slot != parent_slot + 1 {
+		return None
+	}
+
+	let proposing_duration = slot_info.duration.mul_f32(block_proposal_slot_portion.get());
+	let slot_remaining = slot_info
+		.ends_at
+		.checked_duration_since(std::time::Instant::now())
+		.unwrap_or_default();
+
+	proposing_duration
+		.checked_sub(slot_remaining)
+		.filter(|lenience| !lenience.is_zero())
+}
+
 /// Trait for providing the strategy for when to backoff block authoring.
 pub trait BackoffAuthoringBlocksStrategy<N> {
 	/// Returns true if we should backoff authoring new blocks.
@@ -883,6 +929,50 @@ impl<N> BackoffAuthoringBlocksStrategy<N> for () {
 	}
 }
 
+/// Allows choosing the strategy at runtime, e.g. from the service configuration, by passing a
+/// `Box<dyn BackoffAuthoringBlocksStrategy<N> + Send + Sync>` to the slot workers.
+impl<N, T: BackoffAuthoringBlocksStrategy<N> + ?Sized> BackoffAuthoringBlocksStrategy<N>
+	for Box<T>
+{
+	fn should_backoff(
+		&self,
+		chain_head_number: N,
+		chain_head_slot: Slot,
+		finalized_number: N,
+		slot_now: Slot,
+		logging_target: &str,
+	) -> bool {
+		(**self).should_backoff(
+			chain_head_number,
+			chain_head_slot,
+			finalized_number,
+			slot_now,
+			logging_target,
+		)
+	}
+}
+
+impl<N, T: BackoffAuthoringBlocksStrategy<N> + ?Sized> BackoffAuthoringBlocksStrategy<N>
+	for Arc<T>
+{
+	fn should_backoff(
+		&self,
+		chain_head_number: N,
+		chain_head_slot: Slot,
+		finalized_number: N,
+		slot_now: Slot,
+		logging_target: &str,
+	) -> bool {
+		(**self).should_backoff(
+			chain_head_number,
+			chain_head_slot,
+			finalized_number,
+			slot_now,
+			logging_target,
+		)
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -983,6 +1073,44 @@ mod test {
 		);
 	}
 
+	#[test]
+	fn late_previous_block_slot_lenience() {
+		// enough of the slot is left for proposing
+		assert_eq!(
+			super::slot_lenience_late_previous_block(1u64.into(), &slot(2), &SlotProportion(0.5)),
+			None,
+		);
+
+		// the parent isn't from the previous slot
+		let mut late = slot(3);
+		late.ends_at = Instant::now() + SLOT_DURATION / 10;
+		assert_eq!(
+			super::slot_lenience_late_previous_block(1u64.into(), &late, &SlotProportion(0.5)),
+			None,
+		);
+
+		// the previous block arrived late, so the lost proposing time is given back
+		late.slot = 2.into();
+		let lenience =
+			super::slot_lenience_late_previous_block(1u64.into(), &late, &SlotProportion(0.5))
+				.unwrap();
+		assert!(lenience >= SLOT_DURATION.mul_f32(0.4));
+		assert!(lenience <= SLOT_DURATION.mul_f32(0.5));
+
+		// but never more than the maximum proposal slot portion
+		assert_eq!(
+			proposing_remaining_duration(
+				Some(1.into()),
+				&late,
+				&SlotProportion(0.5),
+				Some(SlotProportion(0.3)).as_ref(),
+				SlotLenienceType::Linear,
+				"test",
+			),
+			SLOT_DURATION.mul_f32(0.3),
+		);
+	}
+
 	#[derive(PartialEq, Debug)]
 	struct HeadState {
 		head_number: NumberFor<Block>,
@@ -1317,4 +1445,24 @@ mod test {
 		assert_eq!((block_for_max_interval, time_to_reach_limit), expected);
 		assert_eq!((block_for_max_interval, time_to_reach_limit), (250, 60906));
 	}
+
+	#[test]
+	fn boxed_strategies_can_be_chosen_at_runtime() {
+		struct AlwaysBackoff;
+
+		impl<N> BackoffAuthoringBlocksStrategy<N> for AlwaysBackoff {
+			fn should_backoff(&self, _: N, _: Slot, _: N, _: Slot, _: &str) -> bool {
+				true
+			}
+		}
+
+		let strategies: Vec<Box<dyn BackoffAuthoringBlocksStrategy<u64> + Send + Sync>> =
+			vec![Box::new(()), Box::new(AlwaysBackoff), Box::new(Arc::new(AlwaysBackoff))];
+
+		let should_backoff: Vec<bool> = strategies
+			.iter()
+			.map(|strategy| strategy.should_backoff(2, 10.into(), 1, 11.into(), "slots"))
+			.collect();
+		assert_eq!(should_backoff, vec![false, true, true]);
+	}
 }
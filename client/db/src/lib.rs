@@ -41,6 +41,7 @@ mod children;
 mod parity_db;
 mod stats;
 mod storage_cache;
+mod trie_node_cache;
 #[cfg(any(feature = "with-kvdb-rocksdb", test))]
 mod upgrade;
 mod utils;
@@ -59,7 +60,8 @@ use crate::{
 	changes_tries_storage::{DbChangesTrieStorage, DbChangesTrieStorageTransaction},
 	stats::StateUsageStats,
 	storage_cache::{new_shared_cache, CachingState, SharedCache, SyncingCachingState},
-	utils::{meta_keys, read_db, read_meta, DatabaseType, Meta},
+	trie_node_cache::TrieNodeCache,
+	utils::{meta_keys, read_db, read_meta, Meta},
 };
 use codec::{Decode, Encode};
 use hash_db::Prefix;
@@ -100,6 +102,7 @@ use sp_trie::{prefixed_key, MemoryDB, PrefixedMemoryDB};
 // Re-export the Database trait so that one can pass an implementation of it.
 pub use sc_state_db::PruningMode;
 pub use sp_database::Database;
+pub use utils::{migrate_rocksdb_to_parity_db, DatabaseType};
 
 #[cfg(any(feature = "with-kvdb-rocksdb", test))]
 pub use bench::BenchmarkingState;
@@ -294,6 +297,8 @@ pub struct DatabaseSettings {
 	pub state_cache_size: usize,
 	/// Ratio of cache size dedicated to child tries.
 	pub state_cache_child_ratio: Option<(usize, usize)>,
+	/// Trie node cache size, `0` disables the cache.
+	pub trie_cache_size: usize,
 	/// State pruning mode.
 	pub state_pruning: PruningMode,
 	/// Where to find the database.
@@ -309,7 +314,9 @@ pub struct DatabaseSettings {
 pub enum KeepBlocks {
 	/// Keep full block history.
 	All,
-	/// Keep N recent finalized blocks.
+	/// Keep all finalized blocks, discarding the blocks of forks displaced by finality.
+	Finalized,
+	/// Keep N recent finalized blocks, discarding the blocks of forks displaced by finality.
 	Some(u32),
 }
 
@@ -984,17 +991,28 @@ struct StorageDb<Block: BlockT> {
 	pub db: Arc<dyn Database<DbHash>>,
 	pub state_db: StateDb<Block::Hash, Vec<u8>>,
 	prefix_keys: bool,
+	trie_node_cache: Option<TrieNodeCache>,
 }
 
 impl<Block: BlockT> sp_state_machine::Storage<HashFor<Block>> for StorageDb<Block> {
 	fn get(&self, key: &Block::Hash, prefix: Prefix) -> Result<Option<DBValue>, String> {
-		if self.prefix_keys {
-			let key = prefixed_key::<HashFor<Block>>(key, prefix);
-			self.state_db.get(&key, self)
+		let key = if self.prefix_keys {
+			prefixed_key::<HashFor<Block>>(key, prefix)
 		} else {
-			self.state_db.get(key.as_ref(), self)
+			key.as_ref().to_vec()
+		};
+		if let Some(node) = self.trie_node_cache.as_ref().and_then(|cache| cache.get(&key)) {
+			return Ok(Some(node))
+		}
+
+		let node = self
+			.state_db
+			.get(&key, self)
+			.map_err(|e| format!("Database backend error: {:?}", e))?;
+		if let (Some(cache), Some(node)) = (&self.trie_node_cache, &node) {
+			cache.insert(key, node.clone());
 		}
-		.map_err(|e| format!("Database backend error: {:?}", e))
+		Ok(node)
 	}
 }
 
@@ -1132,6 +1150,7 @@ impl<Block: BlockT> Backend<Block> {
 		let db_setting = DatabaseSettings {
 			state_cache_size: 16777216,
 			state_cache_child_ratio: Some((50, 100)),
+			trie_cache_size: 16777216,
 			state_pruning: PruningMode::keep_blocks(keep_blocks),
 			source: DatabaseSource::Custom(db),
 			keep_blocks: KeepBlocks::Some(keep_blocks),
@@ -1141,6 +1160,18 @@ impl<Block: BlockT> Backend<Block> {
 		Self::new(db_setting, canonicalization_delay).expect("failed to create test-db")
 	}
 
+	/// Expose the database and the column of the state tries, to benchmark the storage.
+	///
+	/// Writing to the state column bypasses the state database and can corrupt the state.
+	pub fn expose_db(&self) -> (Arc<dyn Database<DbHash>>, sp_database::ColumnId) {
+		(self.storage.db.clone(), columns::STATE)
+	}
+
+	/// Expose the storage of the state tries, to benchmark the storage.
+	pub fn expose_storage(&self) -> Arc<dyn sp_state_machine::Storage<HashFor<Block>>> {
+		self.storage.clone()
+	}
+
 	fn from_database(
 		db: Arc<dyn Database<DbHash>>,
 		canonicalization_delay: u64,
@@ -1156,8 +1187,13 @@ impl<Block: BlockT> Backend<Block> {
 			&StateMetaDb(&*db),
 		)
 		.map_err(map_e)?;
-		let storage_db =
-			StorageDb { db: db.clone(), state_db, prefix_keys: !db.supports_ref_counting() };
+		let storage_db = StorageDb {
+			db: db.clone(),
+			state_db,
+			prefix_keys: !db.supports_ref_counting(),
+			trie_node_cache: (config.trie_cache_size > 0)
+				.then(|| TrieNodeCache::new(config.trie_cache_size)),
+		};
 		let offchain_storage = offchain::LocalStorage::new(db.clone());
 		let changes_tries_storage = DbChangesTrieStorage::new(
 			db,
@@ -1782,32 +1818,47 @@ impl<Block: BlockT> Backend<Block> {
 		finalized: NumberFor<Block>,
 		displaced: &FinalizationDisplaced<Block::Hash, NumberFor<Block>>,
 	) -> ClientResult<()> {
-		if let KeepBlocks::Some(keep_blocks) = self.keep_blocks {
+		let keep_blocks = match self.keep_blocks {
+			KeepBlocks::All => return Ok(()),
+			KeepBlocks::Finalized => None,
+			KeepBlocks::Some(keep_blocks) => Some(keep_blocks),
+		};
+
+		if let Some(keep_blocks) = keep_blocks {
 			// Always keep the last finalized block
 			let keep = std::cmp::max(keep_blocks, 1);
 			if finalized >= keep.into() {
 				let number = finalized.saturating_sub(keep.into());
 				self.prune_block(transaction, BlockId::<Block>::number(number))?;
 			}
+		}
 
-			// Also discard all blocks from displaced branches
-			for h in displaced.leaves() {
-				let mut number = finalized;
-				let mut hash = h.clone();
-				// Follow displaced chains back until we reach a finalized block.
-				// Since leaves are discarded due to finality, they can't have parents
-				// that are canonical, but not yet finalized. So we stop deletig as soon as
-				// we reach canonical chain.
-				while self.blockchain.hash(number)? != Some(hash.clone()) {
-					let id = BlockId::<Block>::hash(hash.clone());
-					match self.blockchain.header(id)? {
-						Some(header) => {
-							self.prune_block(transaction, id)?;
-							number = header.number().saturating_sub(One::one());
-							hash = header.parent_hash().clone();
-						},
-						None => break,
-					}
+		// Also discard all blocks from displaced branches
+		for h in displaced.leaves() {
+			let mut number = finalized;
+			let mut hash = h.clone();
+			// Follow displaced chains back until we reach a finalized block.
+			// Since leaves are discarded due to finality, they can't have parents
+			// that are canonical, but not yet finalized. So we stop deletig as soon as
+			// we reach canonical chain.
+			while self.blockchain.hash(number)? != Some(hash.clone()) {
+				let id = BlockId::<Block>::hash(hash.clone());
+				match self.blockchain.header(id)? {
+					Some(header) => {
+						self.prune_block(transaction, id)?;
+						// Justifications of finalized blocks are kept, e.g. to prove authority
+						// set changes, the ones of displaced blocks are useless.
+						utils::remove_from_db(
+							transaction,
+							&*self.storage.db,
+							columns::KEY_LOOKUP,
+							columns::JUSTIFICATIONS,
+							id,
+						)?;
+						number = header.number().saturating_sub(One::one());
+						hash = header.parent_hash().clone();
+					},
+					None => break,
 				}
 			}
 		}
@@ -2123,10 +2174,16 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 		let database_cache = MemorySize::from_bytes(0);
 		let state_cache =
 			MemorySize::from_bytes((*&self.shared_cache).read().used_storage_cache_size());
+		let trie_cache = self.storage.trie_node_cache.as_ref();
 		let state_db = self.storage.state_db.memory_info();
 
 		Some(UsageInfo {
-			memory: MemoryInfo { state_cache, database_cache, state_db },
+			memory: MemoryInfo {
+				state_cache,
+				database_cache,
+				trie_cache: MemorySize::from_bytes(trie_cache.map_or(0, |c| c.used_size())),
+				state_db,
+			},
 			io: IoInfo {
 				transactions: io_stats.transactions,
 				bytes_read: io_stats.bytes_read,
@@ -2139,6 +2196,8 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 				state_writes_cache: state_stats.overlay_writes.ops,
 				state_reads_cache: state_stats.cache_reads.ops,
 				state_writes_nodes: state_stats.nodes_writes.ops,
+				trie_cache_hits: trie_cache.map_or(0, |c| c.hits()),
+				trie_cache_misses: trie_cache.map_or(0, |c| c.misses()),
 			},
 		})
 	}
@@ -2540,6 +2599,7 @@ pub(crate) mod tests {
 			DatabaseSettings {
 				state_cache_size: 16777216,
 				state_cache_child_ratio: Some((50, 100)),
+				trie_cache_size: 16777216,
 				state_pruning: PruningMode::keep_blocks(1),
 				source: DatabaseSource::Custom(backing),
 				keep_blocks: KeepBlocks::All,
@@ -3325,6 +3385,61 @@ pub(crate) mod tests {
 		assert_eq!(Some(vec![4.into()]), bc.body(BlockId::hash(blocks[4])).unwrap());
 	}
 
+	#[test]
+	fn prune_displaced_forks_when_keeping_finalized_blocks() {
+		let backend = Backend::<Block>::new(
+			DatabaseSettings {
+				state_cache_size: 16777216,
+				state_cache_child_ratio: Some((50, 100)),
+				trie_cache_size: 16777216,
+				state_pruning: PruningMode::keep_blocks(2),
+				source: DatabaseSource::Custom(sp_database::as_database(kvdb_memorydb::create(
+					crate::utils::NUM_COLUMNS,
+				))),
+				keep_blocks: KeepBlocks::Finalized,
+				transaction_storage: TransactionStorageMode::BlockBody,
+			},
+			10,
+		)
+		.unwrap();
+		let mut blocks = Vec::new();
+		let mut prev_hash = Default::default();
+		for i in 0..5 {
+			let hash = insert_block(
+				&backend,
+				i,
+				prev_hash,
+				None,
+				Default::default(),
+				vec![i.into()],
+				None,
+			);
+			blocks.push(hash);
+			prev_hash = hash;
+		}
+
+		// insert a fork at block 2
+		let fork_hash_root =
+			insert_block(&backend, 2, blocks[1], None, H256::random(), vec![2.into()], None);
+		let fork_hash_leaf =
+			insert_block(&backend, 3, fork_hash_root, None, H256::random(), vec![3.into()], None);
+
+		let mut op = backend.begin_operation().unwrap();
+		backend.begin_state_operation(&mut op, BlockId::Hash(blocks[4])).unwrap();
+		op.mark_head(BlockId::Hash(blocks[4])).unwrap();
+		for i in 1..5 {
+			op.mark_finalized(BlockId::Hash(blocks[i]), None).unwrap();
+		}
+		backend.commit_operation(op).unwrap();
+
+		let bc = backend.blockchain();
+		for (i, hash) in blocks.iter().enumerate() {
+			assert_eq!(Some(vec![(i as u64).into()]), bc.body(BlockId::hash(*hash)).unwrap());
+		}
+		assert_eq!(None, bc.body(BlockId::hash(fork_hash_root)).unwrap());
+		assert_eq!(None, bc.body(BlockId::hash(fork_hash_leaf)).unwrap());
+	}
+
 	#[test]
 	fn renew_transaction_storage() {
 		let backend =
@@ -597,6 +597,7 @@ where
 			memory: MemoryInfo {
 				database_cache,
 				state_cache: Default::default(),
+				trie_cache: Default::default(),
 				state_db: Default::default(),
 			},
 			io: IoInfo {
@@ -612,6 +613,8 @@ where
 				state_reads_cache: 0,
 				state_writes_cache: 0,
 				state_writes_nodes: 0,
+				trie_cache_hits: 0,
+				trie_cache_misses: 0,
 			},
 		})
 	}
@@ -75,6 +75,30 @@ impl sp_core::offchain::OffchainStorage for LocalStorage {
 		self.db.get(columns::OFFCHAIN, &concatenate_prefix_and_key(prefix, key))
 	}
 
+	fn entries_with_prefix(
+		&self,
+		prefix: &[u8],
+		key_prefix: &[u8],
+		start_after: Option<&[u8]>,
+		count: usize,
+	) -> Option<Vec<(Vec<u8>, Vec<u8>)>> {
+		let mut entries = Vec::new();
+		if count == 0 {
+			return Some(entries)
+		}
+		let start_after = start_after.map(|key| concatenate_prefix_and_key(prefix, key));
+		let supported = self.db.iter_with_prefix(
+			columns::OFFCHAIN,
+			&concatenate_prefix_and_key(prefix, key_prefix),
+			start_after.as_deref(),
+			&mut |key, value| {
+				entries.push((key[prefix.len()..].to_vec(), value.to_vec()));
+				entries.len() < count
+			},
+		);
+		supported.then(|| entries)
+	}
+
 	fn compare_and_set(
 		&mut self,
 		prefix: &[u8],
@@ -147,4 +171,26 @@ mod tests {
 		assert_eq!(storage.get(prefix, key), Some(b"asd".to_vec()));
 		assert!(storage.locks.lock().is_empty(), "Locks map should be empty!");
 	}
+
+	#[test]
+	fn should_enumerate_entries_with_prefix() {
+		let mut storage = LocalStorage::new_test();
+		storage.set(b"prefix", b"key2", b"2");
+		storage.set(b"prefix", b"key1", b"1");
+		storage.set(b"prefix", b"other", b"3");
+		storage.set(b"other", b"key3", b"4");
+
+		assert_eq!(
+			storage.entries_with_prefix(b"prefix", b"key", None, 10),
+			Some(vec![(b"key1".to_vec(), b"1".to_vec()), (b"key2".to_vec(), b"2".to_vec())]),
+		);
+		assert_eq!(
+			storage.entries_with_prefix(b"prefix", b"key", None, 1),
+			Some(vec![(b"key1".to_vec(), b"1".to_vec())]),
+		);
+		assert_eq!(
+			storage.entries_with_prefix(b"prefix", b"key", Some(b"key1"), 10),
+			Some(vec![(b"key2".to_vec(), b"2".to_vec())]),
+		);
+	}
 }
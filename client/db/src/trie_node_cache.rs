@@ -0,0 +1,121 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Global trie node cache. Maintains recently read encoded trie nodes of any state.
+//!
+//! Nodes are addressed by their hash, a key always refers to the same node, so a single cache
+//! is shared by block import, block production and state queries, without any invalidation.
+//! Storage values are cached separately, by the state cache.
+
+use linked_hash_map::LinkedHashMap;
+use parking_lot::Mutex;
+use sp_state_machine::DBValue;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Size bounded LRU cache of encoded trie nodes, keyed by their database key.
+pub struct TrieNodeCache {
+	nodes: Mutex<LruNodes>,
+	hits: AtomicU64,
+	misses: AtomicU64,
+}
+
+struct LruNodes {
+	map: LinkedHashMap<Vec<u8>, DBValue>,
+	used_size: usize,
+	limit: usize,
+}
+
+impl TrieNodeCache {
+	/// Create a new cache holding up to `limit` bytes of keys and nodes.
+	pub fn new(limit: usize) -> Self {
+		TrieNodeCache {
+			nodes: Mutex::new(LruNodes { map: LinkedHashMap::new(), used_size: 0, limit }),
+			hits: AtomicU64::new(0),
+			misses: AtomicU64::new(0),
+		}
+	}
+
+	/// Get the node stored under `key`, marking it as recently used.
+	pub fn get(&self, key: &[u8]) -> Option<DBValue> {
+		let node = self.nodes.lock().map.get_refresh(key).cloned();
+		let counter = if node.is_some() { &self.hits } else { &self.misses };
+		counter.fetch_add(1, Ordering::Relaxed);
+		node
+	}
+
+	/// Cache a node read from the database, evicting the least recently used ones if needed.
+	pub fn insert(&self, key: Vec<u8>, node: DBValue) {
+		let size = key.len() + node.len();
+		let mut nodes = self.nodes.lock();
+		if size > nodes.limit || nodes.map.contains_key(&key) {
+			return
+		}
+
+		while nodes.used_size + size > nodes.limit {
+			match nodes.map.pop_front() {
+				Some((key, node)) => nodes.used_size -= key.len() + node.len(),
+				None => break,
+			}
+		}
+		nodes.used_size += size;
+		nodes.map.insert(key, node);
+	}
+
+	/// Size of the cached keys and nodes in bytes.
+	pub fn used_size(&self) -> usize {
+		self.nodes.lock().used_size
+	}
+
+	/// Number of lookups served by the cache since it was created.
+	pub fn hits(&self) -> u64 {
+		self.hits.load(Ordering::Relaxed)
+	}
+
+	/// Number of lookups which had to read the database since the cache was created.
+	pub fn misses(&self) -> u64 {
+		self.misses.load(Ordering::Relaxed)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn evicts_least_recently_used_nodes() {
+		let cache = TrieNodeCache::new(12);
+		cache.insert(vec![1], vec![1; 3]);
+		cache.insert(vec![2], vec![2; 3]);
+		cache.insert(vec![3], vec![3; 3]);
+		assert_eq!(cache.used_size(), 12);
+
+		// refresh the first node, the second one is evicted instead.
+		assert_eq!(cache.get(&[1]), Some(vec![1; 3]));
+		cache.insert(vec![4], vec![4; 3]);
+		assert_eq!(cache.get(&[2]), None);
+		assert_eq!(cache.get(&[1]), Some(vec![1; 3]));
+		assert_eq!(cache.used_size(), 12);
+
+		// nodes larger than the cache are never cached.
+		cache.insert(vec![5], vec![5; 12]);
+		assert_eq!(cache.get(&[5]), None);
+		assert_eq!(cache.get(&[3]), Some(vec![3; 3]));
+
+		assert_eq!((cache.hits(), cache.misses()), (3, 2));
+	}
+}
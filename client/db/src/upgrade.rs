@@ -191,6 +191,7 @@ mod tests {
 			&DatabaseSettings {
 				state_cache_size: 0,
 				state_cache_child_ratio: None,
+				trie_cache_size: 0,
 				state_pruning: PruningMode::ArchiveAll,
 				source: DatabaseSource::RocksDb { path: db_path.to_owned(), cache_size: 128 },
 				keep_blocks: KeepBlocks::All,
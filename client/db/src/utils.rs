@@ -384,6 +384,99 @@ fn open_kvdb_rocksdb<Block: BlockT>(
 	Err(OpenDbError::NotEnabled("with-kvdb-rocksdb"))
 }
 
+/// The number of entries copied at once by [`migrate_rocksdb_to_parity_db`].
+#[cfg(all(any(feature = "with-kvdb-rocksdb", test), feature = "with-parity-db"))]
+const MIGRATION_BATCH_SIZE: usize = 10_000;
+
+/// Copy the RocksDB database at `rocksdb_path` into a new ParityDB database at `paritydb_path`.
+///
+/// Only databases with the `archive` state pruning mode can be migrated, since the state db
+/// journal of pruned databases refers to the state entries by their RocksDB keys. The migration
+/// is one way: ParityDB stores the state entries without the trie path prefix RocksDB needs.
+///
+/// The database is built in a temporary directory next to `paritydb_path`, which is only moved
+/// into place once every column was copied.
+#[cfg(all(any(feature = "with-kvdb-rocksdb", test), feature = "with-parity-db"))]
+pub fn migrate_rocksdb_to_parity_db<Block: BlockT>(
+	rocksdb_path: &Path,
+	paritydb_path: &Path,
+	db_type: DatabaseType,
+) -> sp_blockchain::Result<()> {
+	use kvdb::KeyValueDB;
+
+	if paritydb_path.exists() {
+		return Err(backend_err("a ParityDB database already exists"))
+	}
+
+	crate::upgrade::upgrade_db::<Block>(rocksdb_path, db_type)
+		.map_err(|e| sp_blockchain::Error::Backend(e.to_string()))?;
+
+	let mut db_config = kvdb_rocksdb::DatabaseConfig::with_columns(NUM_COLUMNS);
+	db_config.create_if_missing = false;
+	let source = kvdb_rocksdb::Database::open(&db_config, rocksdb_path)
+		.map_err(|e| sp_blockchain::Error::Backend(OpenDbError::from(e).to_string()))?;
+
+	// RocksDB stores the state entries under keys prefixed with their path in the trie, while
+	// ParityDB uses the hash alone.
+	let strip_state_prefix = db_type == DatabaseType::Full;
+	if strip_state_prefix {
+		let pruning_mode = source
+			.get(crate::columns::STATE_META, b"mode")
+			.map_err(|e| sp_blockchain::Error::Backend(e.to_string()))?;
+		if pruning_mode.as_deref() != Some(&b"archive"[..]) {
+			return Err(backend_err("only databases with archive state pruning can be migrated"))
+		}
+	}
+
+	let mut tmp_path = paritydb_path.as_os_str().to_owned();
+	tmp_path.push(".migrating");
+	let tmp_path = std::path::PathBuf::from(tmp_path);
+	// Left over by an interrupted migration.
+	if tmp_path.exists() {
+		fs::remove_dir_all(&tmp_path).map_err(|e| sp_blockchain::Error::Backend(e.to_string()))?;
+	}
+
+	let target = open_parity_db::<Block>(&tmp_path, db_type, true)?;
+	let hash_len = Block::Hash::default().as_ref().len();
+
+	for col in 0..NUM_COLUMNS {
+		let mut transaction = Transaction::new();
+		let mut batch_len = 0;
+		for (key, value) in source.iter(col) {
+			let key = match strip_state_prefix && col == crate::columns::STATE {
+				true => &key[key.len().saturating_sub(hash_len)..],
+				false => &key[..],
+			};
+			transaction.set_from_vec(col, key, value.into_vec());
+
+			batch_len += 1;
+			if batch_len == MIGRATION_BATCH_SIZE {
+				target.commit(std::mem::take(&mut transaction))?;
+				batch_len = 0;
+			}
+		}
+		target.commit(transaction)?;
+
+		info!("Migrated column {} to ParityDB", col);
+	}
+
+	drop(target);
+	fs::rename(&tmp_path, paritydb_path)
+		.map_err(|e| sp_blockchain::Error::Backend(e.to_string()))?;
+
+	Ok(())
+}
+
+/// Copy the RocksDB database at `rocksdb_path` into a new ParityDB database at `paritydb_path`.
+#[cfg(not(all(any(feature = "with-kvdb-rocksdb", test), feature = "with-parity-db")))]
+pub fn migrate_rocksdb_to_parity_db<Block: BlockT>(
+	_rocksdb_path: &Path,
+	_paritydb_path: &Path,
+	_db_type: DatabaseType,
+) -> sp_blockchain::Result<()> {
+	Err(backend_err("the `with-kvdb-rocksdb` and `with-parity-db` features are required"))
+}
+
 /// Check database type.
 pub fn check_database_type(
 	db: &dyn Database<DbHash>,
@@ -627,6 +720,47 @@ mod tests {
 	use std::path::PathBuf;
 	type Block = RawBlock<ExtrinsicWrapper<u32>>;
 
+	#[cfg(feature = "with-parity-db")]
+	#[test]
+	fn rocksdb_to_parity_db_migration() {
+		let base_path = tempfile::TempDir::new().unwrap();
+		let rocksdb_path = base_path.path().join("db");
+		let paritydb_path = base_path.path().join("paritydb");
+		let hash = [1u8; 32];
+
+		{
+			let db =
+				open_kvdb_rocksdb::<Block>(&rocksdb_path, DatabaseType::Full, true, 128).unwrap();
+			let mut transaction = Transaction::new();
+			transaction.set(crate::columns::STATE_META, b"mode", b"archive");
+			transaction.set(
+				crate::columns::STATE,
+				&[&[0x12, 0x34][..], &hash[..]].concat(),
+				b"node",
+			);
+			transaction.set(crate::columns::HEADER, b"header", b"value");
+			db.commit(transaction).unwrap();
+		}
+
+		migrate_rocksdb_to_parity_db::<Block>(&rocksdb_path, &paritydb_path, DatabaseType::Full)
+			.unwrap();
+		assert!(!base_path.path().join("paritydb.migrating").exists());
+
+		let db = open_parity_db::<Block>(&paritydb_path, DatabaseType::Full, false).unwrap();
+		// state entries are stored without the trie path prefix.
+		assert_eq!(db.get(crate::columns::STATE, &hash), Some(b"node".to_vec()));
+		assert_eq!(db.get(crate::columns::HEADER, b"header"), Some(b"value".to_vec()));
+		assert!(
+			migrate_rocksdb_to_parity_db::<Block>(
+				&rocksdb_path,
+				&paritydb_path,
+				DatabaseType::Full
+			)
+			.is_err(),
+			"Migrating into an existing database should fail",
+		);
+	}
+
 	#[cfg(any(feature = "with-kvdb-rocksdb", test))]
 	#[test]
 	fn database_type_subdir_migration() {
@@ -753,6 +887,7 @@ mod tests {
 		DatabaseSettings {
 			state_cache_size: 0,
 			state_cache_child_ratio: None,
+			trie_cache_size: 0,
 			state_pruning: PruningMode::ArchiveAll,
 			source,
 			keep_blocks: KeepBlocks::All,
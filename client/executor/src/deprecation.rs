@@ -0,0 +1,196 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tracking of calls into deprecated versions of host functions.
+//!
+//! Host functions declared with `sp-runtime-interface` are exported to the runtime as
+//! `ext_<interface>_<function>_version_<N>`. Older versions stay registered so that old runtimes
+//! can still be executed, but they are deprecated as soon as a newer version of the same
+//! function is registered alongside them.
+
+use parking_lot::Mutex;
+use sp_wasm_interface::{Function, FunctionContext, Result, Signature, Value};
+use std::{collections::HashMap, sync::Once};
+
+lazy_static::lazy_static! {
+	/// The wrappers of the deprecated host functions, by the name and the address of the wrapped
+	/// function, its latest version and the policy they apply.
+	///
+	/// The host functions are usually zero sized, so their address alone doesn't identify them.
+	///
+	/// The wrappers have to outlive every executor, they are shared between the executors so
+	/// creating executors doesn't allocate new ones.
+	static ref DEPRECATED_FUNCTIONS: Mutex<
+		HashMap<(&'static str, usize, u32, DeprecationPolicy), &'static DeprecatedFunction>,
+	> = Default::default();
+}
+
+/// What to do when the runtime calls a deprecated host function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeprecationPolicy {
+	/// Execute the call, logging a warning the first time the function is called.
+	Warn,
+	/// Fail the call.
+	Deny,
+}
+
+/// A deprecated host function, calls into it are handled according to `policy`.
+struct DeprecatedFunction {
+	function: &'static dyn Function,
+	latest_version: u32,
+	policy: DeprecationPolicy,
+	warned: Once,
+}
+
+impl Function for DeprecatedFunction {
+	fn name(&self) -> &str {
+		self.function.name()
+	}
+
+	fn signature(&self) -> Signature {
+		self.function.signature()
+	}
+
+	fn execute(
+		&self,
+		context: &mut dyn FunctionContext,
+		args: &mut dyn Iterator<Item = Value>,
+	) -> Result<Option<Value>> {
+		match self.policy {
+			DeprecationPolicy::Warn => {
+				self.warned.call_once(|| {
+					log::warn!(
+						target: "wasm-runtime",
+						"Runtime called the deprecated host function `{}`, version {} should be used \
+						 instead.",
+						self.name(),
+						self.latest_version,
+					)
+				});
+				self.function.execute(context, args)
+			},
+			DeprecationPolicy::Deny => Err(format!(
+				"Host function `{}` is deprecated, version {} should be used instead",
+				self.name(),
+				self.latest_version,
+			)),
+		}
+	}
+}
+
+/// Split the name of a host function into its unversioned name and its version.
+fn split_version(name: &str) -> Option<(&str, u32)> {
+	let (name, version) = name.rsplit_once("_version_")?;
+	version.parse().ok().map(|version| (name, version))
+}
+
+/// Apply `policy` to the given host functions which are superseded by a newer version of the
+/// same function.
+///
+/// This is applied once by the constructors of [`crate::WasmExecutor`] and
+/// [`crate::NativeElseWasmExecutor`]. Every deprecated function is wrapped at most once per
+/// policy for the lifetime of the process, the wrappers are reused by later calls.
+pub fn check_deprecated_host_functions(
+	host_functions: Vec<&'static dyn Function>,
+	policy: DeprecationPolicy,
+) -> Vec<&'static dyn Function> {
+	let mut latest_versions = HashMap::new();
+	for (name, version) in
+		host_functions.iter().filter_map(|function| split_version(function.name()))
+	{
+		let latest_version = latest_versions.entry(name).or_insert(version);
+		*latest_version = version.max(*latest_version);
+	}
+
+	let mut deprecated_functions = DEPRECATED_FUNCTIONS.lock();
+	host_functions
+		.iter()
+		.map(|function| match split_version(function.name()) {
+			Some((name, version)) if latest_versions[name] > version => {
+				let latest_version = latest_versions[name];
+				let key = (
+					function.name(),
+					*function as *const dyn Function as *const () as usize,
+					latest_version,
+					policy,
+				);
+				let deprecated: &'static dyn Function =
+					*deprecated_functions.entry(key).or_insert_with(|| {
+						Box::leak(Box::new(DeprecatedFunction {
+							function: *function,
+							latest_version,
+							policy,
+							warned: Once::new(),
+						}))
+					});
+				deprecated
+			},
+			_ => *function,
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_wasm_interface::HostFunctions;
+
+	#[test]
+	fn split_version_works() {
+		assert_eq!(split_version("ext_storage_get_version_1"), Some(("ext_storage_get", 1)));
+		assert_eq!(split_version("ext_storage_get"), None);
+		assert_eq!(split_version("ext_storage_get_version_x"), None);
+	}
+
+	#[test]
+	fn only_superseded_versions_are_deprecated() {
+		let host_functions = sp_io::SubstrateHostFunctions::host_functions();
+		let checked =
+			check_deprecated_host_functions(host_functions.clone(), DeprecationPolicy::Deny);
+
+		let deprecated = host_functions
+			.iter()
+			.zip(&checked)
+			.filter(|(function, checked)| {
+				!std::ptr::eq(**function as *const _ as *const u8, **checked as *const _ as *const u8)
+			})
+			.map(|(function, _)| function.name())
+			.collect::<Vec<_>>();
+
+		assert!(deprecated.contains(&"ext_crypto_secp256k1_ecdsa_recover_version_1"));
+		assert!(!deprecated.contains(&"ext_crypto_secp256k1_ecdsa_recover_version_2"));
+		assert!(!deprecated.contains(&"ext_crypto_ed25519_verify_version_1"));
+		assert_eq!(
+			checked.iter().map(|function| function.name()).collect::<Vec<_>>(),
+			host_functions.iter().map(|function| function.name()).collect::<Vec<_>>(),
+		);
+	}
+
+	#[test]
+	fn wrappers_are_reused() {
+		let host_functions = sp_io::SubstrateHostFunctions::host_functions();
+		let first =
+			check_deprecated_host_functions(host_functions.clone(), DeprecationPolicy::Warn);
+		let second = check_deprecated_host_functions(host_functions, DeprecationPolicy::Warn);
+
+		assert!(first.iter().zip(&second).all(|(first, second)| std::ptr::eq(
+			*first as *const _ as *const u8,
+			*second as *const _ as *const u8
+		)));
+	}
+}
@@ -448,6 +448,42 @@ fn should_trap_when_heap_exhausted(wasm_method: WasmExecutionMethod) {
 	assert!(err.contains("Allocator ran out of space"));
 }
 
+test_wasm_execution!(deterministic_stack_limit_is_reached_first);
+fn deterministic_stack_limit_is_reached_first(wasm_method: WasmExecutionMethod) {
+	let mut ext = TestExternalities::default();
+	// Every frame holds 1024 locals, so the logical limit is reached after a few dozen frames.
+	let wasm = wat::parse_str(format!(
+		r#"
+		(module
+			(import "env" "memory" (memory 1))
+
+			(global (export "__heap_base") i32 (i32.const 0))
+			(func $recurse
+				(local {})
+				(call $recurse)
+			)
+			(func (export "main")
+				(param i32 i32) (result i64)
+				(call $recurse)
+				(i64.const 0)
+			)
+		)
+		"#,
+		"i64 ".repeat(1024),
+	))
+	.unwrap();
+
+	let executor =
+		crate::WasmExecutor::new(wasm_method, Some(1), HostFunctions::host_functions(), 8, None);
+
+	let err = executor
+		.uncached_call(RuntimeBlob::new(&wasm).unwrap(), &mut ext.ext(), true, "main", &[])
+		.unwrap_err();
+
+	// The instrumentation traps with `unreachable`, before the executor runs out of stack.
+	assert!(err.to_lowercase().contains("unreachable"), "{}", err);
+}
+
 fn mk_test_runtime(wasm_method: WasmExecutionMethod, pages: u64) -> Arc<dyn WasmModule> {
 	let blob = RuntimeBlob::uncompress_if_needed(&wasm_binary_unwrap()[..])
 		.expect("failed to create a runtime blob out of test runtime");
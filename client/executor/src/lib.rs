@@ -31,6 +31,7 @@
 #![warn(missing_docs)]
 #![recursion_limit = "128"]
 
+mod deprecation;
 #[macro_use]
 mod native_executor;
 #[cfg(test)]
@@ -38,6 +39,7 @@ mod integration_tests;
 mod wasm_runtime;
 
 pub use codec::Codec;
+pub use deprecation::{check_deprecated_host_functions, DeprecationPolicy};
 pub use native_executor::{
 	with_externalities_safe, NativeElseWasmExecutor, NativeExecutionDispatch, WasmExecutor,
 };
@@ -46,7 +48,9 @@ pub use sp_core::traits::Externalities;
 pub use sp_version::{NativeVersion, RuntimeVersion};
 #[doc(hidden)]
 pub use sp_wasm_interface;
-pub use wasm_runtime::{read_embedded_version, WasmExecutionMethod};
+pub use wasm_runtime::{
+	read_embedded_version, WasmExecutionMethod, DETERMINISTIC_STACK_LOGICAL_MAX,
+};
 pub use wasmi;
 
 pub use sc_executor_common::{error, sandbox};
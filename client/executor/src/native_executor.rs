@@ -17,6 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
+	deprecation::{check_deprecated_host_functions, DeprecationPolicy},
 	error::{Error, Result},
 	wasm_runtime::{RuntimeCache, WasmExecutionMethod},
 	RuntimeVersionOf,
@@ -126,17 +127,46 @@ impl WasmExecutor {
 	/// `cache_path` - A path to a directory where the executor can place its files for purposes of
 	///   caching. This may be important in cases when there are many different modules with the
 	///   compiled execution method is used.
+	///
+	/// Calls into deprecated host functions are logged, see
+	/// [`WasmExecutor::new_with_deprecation_policy`].
 	pub fn new(
 		method: WasmExecutionMethod,
 		default_heap_pages: Option<u64>,
 		host_functions: Vec<&'static dyn Function>,
 		max_runtime_instances: usize,
 		cache_path: Option<PathBuf>,
+	) -> Self {
+		Self::new_with_deprecation_policy(
+			method,
+			default_heap_pages,
+			host_functions,
+			max_runtime_instances,
+			cache_path,
+			DeprecationPolicy::Warn,
+		)
+	}
+
+	/// Create new instance which applies `deprecation_policy` to the calls of the runtimes into
+	/// deprecated host functions.
+	///
+	/// See [`WasmExecutor::new`] for the other parameters and
+	/// [`check_deprecated_host_functions`].
+	pub fn new_with_deprecation_policy(
+		method: WasmExecutionMethod,
+		default_heap_pages: Option<u64>,
+		host_functions: Vec<&'static dyn Function>,
+		max_runtime_instances: usize,
+		cache_path: Option<PathBuf>,
+		deprecation_policy: DeprecationPolicy,
 	) -> Self {
 		WasmExecutor {
 			method,
 			default_heap_pages: default_heap_pages.unwrap_or(DEFAULT_HEAP_PAGES),
-			host_functions: Arc::new(host_functions),
+			host_functions: Arc::new(check_deprecated_host_functions(
+				host_functions,
+				deprecation_policy,
+			)),
 			cache: Arc::new(RuntimeCache::new(max_runtime_instances, cache_path.clone())),
 			max_runtime_instances,
 			cache_path,
@@ -329,10 +359,31 @@ impl<D: NativeExecutionDispatch> NativeElseWasmExecutor<D> {
 	///
 	/// `default_heap_pages` - Number of 64KB pages to allocate for Wasm execution.
 	/// 	Defaults to `DEFAULT_HEAP_PAGES` if `None` is provided.
+	///
+	/// Calls into deprecated host functions are logged, see
+	/// [`NativeElseWasmExecutor::new_with_deprecation_policy`].
 	pub fn new(
 		fallback_method: WasmExecutionMethod,
 		default_heap_pages: Option<u64>,
 		max_runtime_instances: usize,
+	) -> Self {
+		Self::new_with_deprecation_policy(
+			fallback_method,
+			default_heap_pages,
+			max_runtime_instances,
+			DeprecationPolicy::Warn,
+		)
+	}
+
+	/// Create new instance which applies `deprecation_policy` to the calls of the fallback
+	/// executor into deprecated host functions.
+	///
+	/// See [`NativeElseWasmExecutor::new`] for the other parameters.
+	pub fn new_with_deprecation_policy(
+		fallback_method: WasmExecutionMethod,
+		default_heap_pages: Option<u64>,
+		max_runtime_instances: usize,
+		deprecation_policy: DeprecationPolicy,
 	) -> Self {
 		let extended = D::ExtendHostFunctions::host_functions();
 		let mut host_functions = sp_io::SubstrateHostFunctions::host_functions()
@@ -348,12 +399,13 @@ impl<D: NativeExecutionDispatch> NativeElseWasmExecutor<D> {
 
 		// Add the custom host functions provided by the user.
 		host_functions.extend(extended);
-		let wasm_executor = WasmExecutor::new(
+		let wasm_executor = WasmExecutor::new_with_deprecation_policy(
 			fallback_method,
 			default_heap_pages,
 			host_functions,
 			max_runtime_instances,
 			None,
+			deprecation_policy,
 		);
 
 		NativeElseWasmExecutor {
@@ -54,6 +54,20 @@ impl Default for WasmExecutionMethod {
 	}
 }
 
+/// The number of logical values, i.e. locals, arguments and operands, which the runtimes can push
+/// on their stack.
+///
+/// The runtime code is always instrumented to count the logical depth of its stack and to trap
+/// once it exceeds this limit, so the limit is the same for every execution method and doesn't
+/// depend on the native stack size of the host.
+pub const DETERMINISTIC_STACK_LOGICAL_MAX: u32 = 65536;
+
+/// The maximum number of bytes of native stack used by the compiled runtime code.
+///
+/// This is large enough for [`DETERMINISTIC_STACK_LOGICAL_MAX`] to always be reached first.
+#[cfg(feature = "wasmtime")]
+const DETERMINISTIC_STACK_NATIVE_MAX: u32 = 256 * 1024 * 1024;
+
 /// A Wasm runtime object along with its cached runtime version.
 struct VersionedRuntime {
 	/// Runtime code hash.
@@ -290,6 +304,8 @@ impl RuntimeCache {
 }
 
 /// Create a wasm runtime with the given `code`.
+///
+/// The code is instrumented to limit its stack depth to [`DETERMINISTIC_STACK_LOGICAL_MAX`].
 pub fn create_wasm_runtime_with_code(
 	wasm_method: WasmExecutionMethod,
 	heap_pages: u64,
@@ -306,6 +322,8 @@ pub fn create_wasm_runtime_with_code(
 			// compiling without the `wasmtime` flag.
 			drop(cache_path);
 
+			let blob = blob.inject_stack_depth_metering(DETERMINISTIC_STACK_LOGICAL_MAX)?;
+
 			sc_executor_wasmi::create_runtime(
 				blob,
 				heap_pages,
@@ -324,7 +342,12 @@ pub fn create_wasm_runtime_with_code(
 				cache_path: cache_path.map(ToOwned::to_owned),
 				semantics: sc_executor_wasmtime::Semantics {
 					fast_instance_reuse: true,
-					deterministic_stack_limit: None,
+					deterministic_stack_limit: Some(
+						sc_executor_wasmtime::DeterministicStackLimit {
+							logical_max: DETERMINISTIC_STACK_LOGICAL_MAX,
+							native_stack_max: DETERMINISTIC_STACK_NATIVE_MAX,
+						},
+					),
 					canonicalize_nans: false,
 				},
 			},
@@ -380,12 +403,30 @@ pub fn read_embedded_version(blob: &RuntimeBlob) -> Result<Option<RuntimeVersion
 		// of a special API in the `apis` field to treat the input as a non-legacy version. However
 		// the structure found in the `runtime_version` always contain an empty `apis` field.
 		// Therefore the version read will be mistakenly treated as an legacy one.
-		let mut decoded_version = sp_api::RuntimeVersion::decode(&mut version_section)
-			.map_err(|_| WasmError::Instantiation("failed to decode version section".into()))?;
+		//
+		// Check if there is a special section that encodes all runtime APIs, as the version of
+		// the `Core` api defines the encoding of the version. Runtimes encoding a `Core` version
+		// of 4 or later always embed this section, so fall back to version 3 without it.
+		let apis = blob
+			.custom_section_contents("runtime_apis")
+			.map(decode_runtime_apis)
+			.transpose()?;
+		let core_api_id = sp_core::hashing::blake2_64(b"Core");
+		let core_version = apis
+			.as_ref()
+			.and_then(|apis| {
+				apis.iter().find_map(|(id, version)| (*id == core_api_id).then(|| *version))
+			})
+			.unwrap_or(3);
+
+		let mut decoded_version = sp_api::RuntimeVersion::decode_with_version_hint(
+			&mut version_section,
+			Some(core_version),
+		)
+		.map_err(|_| WasmError::Instantiation("failed to decode version section".into()))?;
 
-		// Don't stop on this and check if there is a special section that encodes all runtime APIs.
-		if let Some(apis_section) = blob.custom_section_contents("runtime_apis") {
-			decoded_version.apis = decode_runtime_apis(apis_section)?.into();
+		if let Some(apis) = apis {
+			decoded_version.apis = apis.into();
 		}
 
 		Ok(Some(decoded_version))
@@ -507,12 +548,36 @@ mod tests {
 			impl_version: 1,
 			apis: sp_api::create_apis_vec!([(<dyn Core::<Block>>::ID, 3)]),
 			transaction_version: 3,
+			features: Default::default(),
 		};
 
 		let version = decode_version(&old_runtime_version.encode()).unwrap();
 		assert_eq!(3, version.transaction_version);
 	}
 
+	#[test]
+	fn runtime_version_features_decode_from_core_version_4() {
+		let mut runtime_version = sp_api::RuntimeVersion {
+			spec_name: "test".into(),
+			impl_name: "test".into(),
+			authoring_version: 1,
+			spec_version: 1,
+			impl_version: 1,
+			apis: sp_api::create_apis_vec!([(<dyn Core::<Block>>::ID, 3)]),
+			transaction_version: 3,
+			features: Default::default(),
+		};
+
+		// `Core` versions before 4 don't encode the features.
+		let mut encoded = runtime_version.encode();
+		encoded.truncate(encoded.len() - 4);
+		assert_eq!(decode_version(&encoded).unwrap(), runtime_version);
+
+		runtime_version.apis = sp_api::create_apis_vec!([(<dyn Core::<Block>>::ID, 4)]);
+		runtime_version.features = sp_version::RuntimeFeatures::TRY_RUNTIME;
+		assert_eq!(decode_version(&runtime_version.encode()).unwrap(), runtime_version);
+	}
+
 	#[test]
 	fn embed_runtime_version_works() {
 		let wasm = sp_maybe_compressed_blob::decompress(
@@ -527,8 +592,9 @@ mod tests {
 			authoring_version: 100,
 			spec_version: 100,
 			impl_version: 100,
-			apis: sp_api::create_apis_vec!([(<dyn Core::<Block>>::ID, 3)]),
+			apis: sp_api::create_apis_vec!([(<dyn Core::<Block>>::ID, 4)]),
 			transaction_version: 100,
+			features: sp_version::RuntimeFeatures::TRY_RUNTIME,
 		};
 
 		let embedded = sp_version::embed::embed_runtime_version(&wasm, runtime_version.clone())
@@ -415,12 +415,14 @@ impl InstanceWrapper {
 		self.memory.data_ptr()
 	}
 
-	/// Removes physical backing from the allocated linear memory. This leads to returning the
-	/// memory back to the system. While the memory is zeroed this is considered as a side-effect
-	/// and is not relied upon. Thus this function acts as a hint.
-	pub fn decommit(&self) {
+	/// Removes physical backing from the allocated linear memory and zeroes it.
+	///
+	/// On Linux the memory is returned back to the system and zeroed lazily on the next access,
+	/// on other platforms it is zeroed in place. Returns `false` if the memory could not be
+	/// zeroed, in which case the instance must not be reused.
+	pub fn decommit(&self) -> bool {
 		if self.memory.data_size() == 0 {
-			return
+			return true
 		}
 
 		cfg_if::cfg_if! {
@@ -441,8 +443,20 @@ impl InstanceWrapper {
 								std::io::Error::last_os_error(),
 							);
 						});
+
+						return false
 					}
 				}
+
+				true
+			} else {
+				// This should be safe since no references to the memory are held while the
+				// instance is not executing.
+				unsafe {
+					self.memory_as_slice_mut().fill(0);
+				}
+
+				true
 			}
 		}
 	}
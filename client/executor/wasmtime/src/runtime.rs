@@ -34,6 +34,8 @@ use sc_executor_common::{
 use sp_runtime_interface::unpack_ptr_and_len;
 use sp_wasm_interface::{Function, Pointer, Value, WordSize};
 use std::{
+	cell::Cell,
+	fs,
 	path::{Path, PathBuf},
 	rc::Rc,
 	sync::Arc,
@@ -46,6 +48,11 @@ enum Strategy {
 		globals_snapshot: GlobalsSnapshot<wasmtime::Global>,
 		data_segments_snapshot: Arc<DataSegmentsSnapshot>,
 		heap_base: u32,
+		/// Used to recreate the instance for every call once the linear memory of
+		/// `instance_wrapper` could not be zeroed.
+		instance_creator: InstanceCreator,
+		/// Whether the linear memory of `instance_wrapper` could not be zeroed after a call.
+		dirty: Cell<bool>,
 	},
 	RecreateInstance(InstanceCreator),
 }
@@ -61,6 +68,16 @@ impl InstanceCreator {
 	fn instantiate(&self) -> Result<InstanceWrapper> {
 		InstanceWrapper::new(&self.store, &*self.module, &*self.imports, self.heap_pages)
 	}
+
+	/// Perform the call in a new instance.
+	fn call(&self, method: InvokeMethod, data: &[u8]) -> Result<Vec<u8>> {
+		let instance_wrapper = self.instantiate()?;
+		let heap_base = instance_wrapper.extract_heap_base()?;
+		let entrypoint = instance_wrapper.resolve_entrypoint(method)?;
+
+		let allocator = FreeingBumpHeapAllocator::new(heap_base);
+		perform_call(data, Rc::new(instance_wrapper), entrypoint, allocator)
+	}
 }
 
 /// Data required for creating instances with the fast instance reuse strategy.
@@ -110,9 +127,15 @@ impl WasmModule for WasmtimeRuntime {
 			self.config.allow_missing_func_imports,
 		)?;
 
+		let instance_creator = InstanceCreator {
+			imports: Arc::new(imports),
+			module: self.module.clone(),
+			store,
+			heap_pages: self.config.heap_pages,
+		};
+
 		let strategy = if let Some(ref snapshot_data) = self.snapshot_data {
-			let instance_wrapper =
-				InstanceWrapper::new(&store, &self.module, &imports, self.config.heap_pages)?;
+			let instance_wrapper = instance_creator.instantiate()?;
 			let heap_base = instance_wrapper.extract_heap_base()?;
 
 			// This function panics if the instance was created from a runtime blob different from
@@ -127,14 +150,11 @@ impl WasmModule for WasmtimeRuntime {
 				globals_snapshot,
 				data_segments_snapshot: snapshot_data.data_segments_snapshot.clone(),
 				heap_base,
+				instance_creator,
+				dirty: Cell::new(false),
 			}
 		} else {
-			Strategy::RecreateInstance(InstanceCreator {
-				imports: Arc::new(imports),
-				module: self.module.clone(),
-				store,
-				heap_pages: self.config.heap_pages,
-			})
+			Strategy::RecreateInstance(instance_creator)
 		};
 
 		Ok(Box::new(WasmtimeInstance { strategy }))
@@ -159,7 +179,13 @@ impl WasmInstance for WasmtimeInstance {
 				globals_snapshot,
 				data_segments_snapshot,
 				heap_base,
+				instance_creator,
+				dirty,
 			} => {
+				if dirty.get() {
+					return instance_creator.call(method, data)
+				}
+
 				let entrypoint = instance_wrapper.resolve_entrypoint(method)?;
 
 				data_segments_snapshot.apply(|offset, contents| {
@@ -172,19 +198,15 @@ impl WasmInstance for WasmtimeInstance {
 					perform_call(data, Rc::clone(&instance_wrapper), entrypoint, allocator);
 
 				// Signal to the OS that we are done with the linear memory and that it can be
-				// reclaimed.
-				instance_wrapper.decommit();
+				// reclaimed. This also zeroes the memory for the next call, if that isn't
+				// possible the instance is not reused anymore.
+				if !instance_wrapper.decommit() {
+					dirty.set(true);
+				}
 
 				result
 			},
-			Strategy::RecreateInstance(instance_creator) => {
-				let instance_wrapper = instance_creator.instantiate()?;
-				let heap_base = instance_wrapper.extract_heap_base()?;
-				let entrypoint = instance_wrapper.resolve_entrypoint(method)?;
-
-				let allocator = FreeingBumpHeapAllocator::new(heap_base);
-				perform_call(data, Rc::new(instance_wrapper), entrypoint, allocator)
-			},
+			Strategy::RecreateInstance(instance_creator) => instance_creator.call(method, data),
 		}
 	}
 
@@ -204,48 +226,171 @@ impl WasmInstance for WasmtimeInstance {
 				// associated with it.
 				None
 			},
-			Strategy::FastInstanceReuse { instance_wrapper, .. } =>
-				Some(instance_wrapper.base_ptr()),
+			Strategy::FastInstanceReuse { instance_wrapper, dirty, .. } =>
+				if dirty.get() {
+					None
+				} else {
+					Some(instance_wrapper.base_ptr())
+				},
 		}
 	}
 }
 
-/// Prepare a directory structure and a config file to enable wasmtime caching.
+/// The soft limit of the total size of the compiled artifacts cache, in bytes.
+///
+/// Once the cache grows over the limit, the least recently written artifacts are removed first.
+const CACHE_TOTAL_SIZE_SOFT_LIMIT: u64 = 1024 * 1024 * 1024;
+
+/// The soft limit of the number of compiled artifacts in the cache.
+const CACHE_FILE_COUNT_SOFT_LIMIT: usize = 64;
+
+/// The extension of the compiled artifact files in the cache directory.
+const CACHE_FILE_EXTENSION: &str = "artifact";
+
+/// An on-disk cache of compiled artifacts.
 ///
-/// In case of an error the caching will not be enabled.
-fn setup_wasmtime_caching(
-	cache_path: &Path,
-	config: &mut wasmtime::Config,
-) -> std::result::Result<(), String> {
-	use std::fs;
-
-	let wasmtime_cache_root = cache_path.join("wasmtime");
-	fs::create_dir_all(&wasmtime_cache_root)
-		.map_err(|err| format!("cannot create the dirs to cache: {:?}", err))?;
-
-	// Canonicalize the path after creating the directories.
-	let wasmtime_cache_root = wasmtime_cache_root
-		.canonicalize()
-		.map_err(|err| format!("failed to canonicalize the path: {:?}", err))?;
-
-	// Write the cache config file
-	let cache_config_path = wasmtime_cache_root.join("cache-config.toml");
-	let config_content = format!(
-		"\
-[cache]
-enabled = true
-directory = \"{cache_dir}\"
-",
-		cache_dir = wasmtime_cache_root.display()
-	);
-	fs::write(&cache_config_path, config_content)
-		.map_err(|err| format!("cannot write the cache config: {:?}", err))?;
-
-	config
-		.cache_config_load(cache_config_path)
-		.map_err(|err| format!("failed to parse the config: {:?}", err))?;
-
-	Ok(())
+/// Artifacts are keyed by the hash of the instrumented code and the settings of the compiler, so
+/// they survive restarts and can be reused when switching back to a previous runtime. Every file
+/// starts with the hash of the artifact it holds, which is checked before the artifact is loaded.
+struct ArtifactCache {
+	dir: PathBuf,
+}
+
+impl ArtifactCache {
+	/// Open the cache in the `wasmtime` directory of `cache_path`, creating it if needed.
+	fn open(cache_path: &Path) -> std::result::Result<Self, String> {
+		let dir = cache_path.join("wasmtime");
+		fs::create_dir_all(&dir)
+			.map_err(|err| format!("cannot create the dirs to cache: {:?}", err))?;
+		Ok(Self { dir })
+	}
+
+	/// The path of the artifact compiled from `code` with the given `semantics`.
+	fn artifact_path(&self, code: &[u8], semantics: &Semantics) -> PathBuf {
+		let mut key = code.to_vec();
+		key.push(semantics.canonicalize_nans as u8);
+		if let Some(DeterministicStackLimit { native_stack_max, .. }) =
+			semantics.deterministic_stack_limit
+		{
+			key.extend_from_slice(&native_stack_max.to_le_bytes());
+		}
+		let hash = sp_core::hashing::blake2_256(&key);
+		self.dir
+			.join(sp_core::hexdisplay::HexDisplay::from(&hash).to_string())
+			.with_extension(CACHE_FILE_EXTENSION)
+	}
+
+	/// Read the artifact at `path`, if it exists and its checksum is valid.
+	///
+	/// Corrupted artifacts are removed.
+	fn load(&self, path: &Path) -> Option<Vec<u8>> {
+		let mut content = fs::read(path).ok()?;
+		if content.len() < 32 || sp_core::hashing::blake2_256(&content[32..]) != content[..32] {
+			log::warn!(
+				target: "wasmtime",
+				"the cached compiled artifact {} is corrupted, removing it",
+				path.display(),
+			);
+			let _ = fs::remove_file(path);
+			return None
+		}
+		Some(content.split_off(32))
+	}
+
+	/// Write `artifact` to `path`, then prune the cache down to its limits.
+	fn store(&self, path: &Path, artifact: &[u8]) -> std::result::Result<(), String> {
+		// Write to a temporary file first, so that a partially written artifact is never loaded.
+		let tmp_path = path.with_extension("tmp");
+		let mut content = sp_core::hashing::blake2_256(artifact).to_vec();
+		content.extend_from_slice(artifact);
+		fs::write(&tmp_path, content)
+			.and_then(|_| fs::rename(&tmp_path, path))
+			.map_err(|err| format!("cannot write the compiled artifact: {:?}", err))?;
+		self.prune();
+		Ok(())
+	}
+
+	/// Remove the least recently written artifacts while the cache exceeds its limits.
+	fn prune(&self) {
+		let entries = match fs::read_dir(&self.dir) {
+			Ok(entries) => entries,
+			Err(_) => return,
+		};
+		let mut artifacts = entries
+			.filter_map(|entry| {
+				let entry = entry.ok()?;
+				let path = entry.path();
+				if path.extension()? != CACHE_FILE_EXTENSION {
+					return None
+				}
+				let metadata = entry.metadata().ok()?;
+				Some((metadata.modified().ok()?, metadata.len(), path))
+			})
+			.collect::<Vec<_>>();
+		// The most recently written first.
+		artifacts.sort_by(|a, b| b.0.cmp(&a.0));
+
+		let mut total_size = 0u64;
+		for (index, (_, size, path)) in artifacts.into_iter().enumerate() {
+			total_size = total_size.saturating_add(size);
+			if index >= CACHE_FILE_COUNT_SOFT_LIMIT || total_size > CACHE_TOTAL_SIZE_SOFT_LIMIT {
+				let _ = fs::remove_file(path);
+			}
+		}
+	}
+}
+
+/// Compile `code` into a module, going through the compiled artifacts cache in `cache_path`, if
+/// any.
+fn compile_module(
+	engine: &Engine,
+	code: &[u8],
+	cache_path: Option<&Path>,
+	semantics: &Semantics,
+) -> std::result::Result<wasmtime::Module, WasmError> {
+	let cache = match cache_path.map(ArtifactCache::open) {
+		Some(Ok(cache)) => Some(cache),
+		Some(Err(reason)) => {
+			log::warn!(
+				"failed to setup wasmtime cache. Performance may degrade significantly: {}.",
+				reason,
+			);
+			None
+		},
+		None => None,
+	};
+	let artifact_path = cache.as_ref().map(|cache| cache.artifact_path(code, semantics));
+
+	if let (Some(cache), Some(path)) = (&cache, &artifact_path) {
+		if let Some(artifact) = cache.load(path) {
+			// SAFETY: The artifact was serialized by `compile_module` and its checksum is
+			//         valid. Artifacts from another version of wasmtime are rejected
+			//         deterministically.
+			match unsafe { wasmtime::Module::deserialize(engine, &artifact) } {
+				Ok(module) => return Ok(module),
+				Err(e) => log::debug!(
+					target: "wasmtime",
+					"cannot load the cached compiled artifact, recompiling: {}",
+					e,
+				),
+			}
+		}
+	}
+
+	let module = wasmtime::Module::new(engine, code)
+		.map_err(|e| WasmError::Other(format!("cannot create module: {}", e)))?;
+
+	if let (Some(cache), Some(path)) = (&cache, &artifact_path) {
+		let stored = module
+			.serialize()
+			.map_err(|e| format!("cannot serialize the module: {}", e))
+			.and_then(|artifact| cache.store(path, &artifact));
+		if let Err(reason) = stored {
+			log::warn!(target: "wasmtime", "failed to cache the compiled artifact: {}", reason);
+		}
+	}
+
+	Ok(module)
 }
 
 fn common_config(semantics: &Semantics) -> std::result::Result<wasmtime::Config, WasmError> {
@@ -323,12 +468,10 @@ pub struct Semantics {
 	/// extermely fast.
 	///
 	/// Primarily this is achieved by not recreating the instance for each call and performing a
-	/// bare minimum clean up: reapplying the data segments and restoring the values for global
-	/// variables. The vast majority of the linear memory is not restored, meaning that effects
-	/// of previous executions on the same [`WasmInstance`] can be observed there.
-	///
-	/// This is not a problem for a standard substrate runtime execution because it's up to the
-	/// runtime itself to make sure that it doesn't involve any non-determinism.
+	/// bare minimum clean up: zeroing the linear memory, reapplying the data segments and
+	/// restoring the values for global variables. Thus every call starts with a fresh heap and
+	/// effects of previous executions on the same [`WasmInstance`] cannot be observed. Should the
+	/// linear memory fail to be zeroed, the instance is recreated for every following call.
 	///
 	/// Since this feature depends on instrumentation, it can be set only if runtime is
 	/// instantiated using the runtime blob, e.g. using [`create_runtime`].
@@ -386,7 +529,7 @@ pub struct Config {
 	/// functions will be resolved using stubs. These stubs will trap upon a call.
 	pub allow_missing_func_imports: bool,
 
-	/// A directory in which wasmtime can store its compiled artifacts cache.
+	/// A directory in which the compiled artifacts are cached.
 	pub cache_path: Option<PathBuf>,
 
 	/// Tuning of various semantics of the wasmtime executor.
@@ -455,16 +598,7 @@ unsafe fn do_create_runtime(
 	host_functions: Vec<&'static dyn Function>,
 ) -> std::result::Result<WasmtimeRuntime, WasmError> {
 	// Create the engine, store and finally the module from the given code.
-	let mut wasmtime_config = common_config(&config.semantics)?;
-	if let Some(ref cache_path) = config.cache_path {
-		if let Err(reason) = setup_wasmtime_caching(cache_path, &mut wasmtime_config) {
-			log::warn!(
-				"failed to setup wasmtime cache. Performance may degrade significantly: {}.",
-				reason,
-			);
-		}
-	}
-
+	let wasmtime_config = common_config(&config.semantics)?;
 	let engine = Engine::new(&wasmtime_config)
 		.map_err(|e| WasmError::Other(format!("cannot create the engine for runtime: {}", e)))?;
 
@@ -480,13 +614,21 @@ unsafe fn do_create_runtime(
 
 				let mutable_globals = ExposedMutableGlobalsSet::collect(&blob);
 
-				let module = wasmtime::Module::new(&engine, &blob.serialize())
-					.map_err(|e| WasmError::Other(format!("cannot create module: {}", e)))?;
+				let module = compile_module(
+					&engine,
+					&blob.serialize(),
+					config.cache_path.as_deref(),
+					&config.semantics,
+				)?;
 
 				(module, Some(InstanceSnapshotData { data_segments_snapshot, mutable_globals }))
 			} else {
-				let module = wasmtime::Module::new(&engine, &blob.serialize())
-					.map_err(|e| WasmError::Other(format!("cannot create module: {}", e)))?;
+				let module = compile_module(
+					&engine,
+					&blob.serialize(),
+					config.cache_path.as_deref(),
+					&config.semantics,
+				)?;
 				(module, None)
 			}
 		},
@@ -19,7 +19,7 @@
 use codec::{Decode as _, Encode as _};
 use sc_executor_common::{runtime_blob::RuntimeBlob, wasm_runtime::WasmModule};
 use sc_runtime_test::wasm_binary_unwrap;
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 type HostFunctions = sp_io::SubstrateHostFunctions;
 
@@ -30,6 +30,7 @@ struct RuntimeBuilder {
 	deterministic_stack: bool,
 	heap_pages: u32,
 	max_memory_pages: Option<u32>,
+	cache_path: Option<PathBuf>,
 }
 
 impl RuntimeBuilder {
@@ -43,9 +44,14 @@ impl RuntimeBuilder {
 			deterministic_stack: false,
 			heap_pages: 1024,
 			max_memory_pages: None,
+			cache_path: None,
 		}
 	}
 
+	fn fast_instance_reuse(&mut self, fast_instance_reuse: bool) {
+		self.fast_instance_reuse = fast_instance_reuse;
+	}
+
 	fn use_wat(&mut self, code: &'static str) {
 		self.code = Some(code);
 	}
@@ -62,6 +68,10 @@ impl RuntimeBuilder {
 		self.max_memory_pages = max_memory_pages;
 	}
 
+	fn cache_path(&mut self, cache_path: Option<PathBuf>) {
+		self.cache_path = cache_path;
+	}
+
 	fn build(self) -> Arc<dyn WasmModule> {
 		let blob = {
 			let wasm: Vec<u8>;
@@ -84,7 +94,7 @@ impl RuntimeBuilder {
 				heap_pages: self.heap_pages,
 				max_memory_pages: self.max_memory_pages,
 				allow_missing_func_imports: true,
-				cache_path: None,
+				cache_path: self.cache_path,
 				semantics: crate::Semantics {
 					fast_instance_reuse: self.fast_instance_reuse,
 					deterministic_stack_limit: match self.deterministic_stack {
@@ -307,3 +317,89 @@ fn test_max_memory_pages() {
 	)
 	.unwrap();
 }
+
+#[test]
+fn test_fast_instance_reuse_starts_with_fresh_heap() {
+	let runtime = {
+		let mut builder = RuntimeBuilder::new_on_demand();
+		builder.use_wat(
+			r#"
+			(module
+				(import "env" "memory" (memory 1))
+
+				(global (export "__heap_base") i32 (i32.const 0))
+				(func (export "main")
+					(param i32 i32) (result i64)
+
+					;; assert(the byte written by the previous call was cleared)
+					(if
+						(i32.load8_u
+							(i32.const 65536)
+						)
+						(unreachable)
+					)
+					(i32.store8
+						(i32.const 65536)
+						(i32.const 1)
+					)
+
+					(i64.const 0)
+				)
+			)
+			"#,
+		);
+		builder.fast_instance_reuse(true);
+		builder.build()
+	};
+	let instance = runtime.new_instance().expect("failed to instantiate a runtime");
+
+	instance.call_export("main", &[]).unwrap();
+	instance.call_export("main", &[]).unwrap();
+}
+
+#[test]
+fn test_cached_artifact_is_checked() {
+	const CODE: &str = r#"
+		(module
+			(import "env" "memory" (memory 1))
+
+			(global (export "__heap_base") i32 (i32.const 0))
+			(func (export "main")
+				(param i32 i32) (result i64)
+				(i64.const 0)
+			)
+		)
+		"#;
+
+	let cache_dir = tempfile::tempdir().unwrap();
+	let build = || {
+		let mut builder = RuntimeBuilder::new_on_demand();
+		builder.use_wat(CODE);
+		builder.cache_path(Some(cache_dir.path().to_owned()));
+		builder.build()
+	};
+	let artifacts = || {
+		std::fs::read_dir(cache_dir.path().join("wasmtime"))
+			.unwrap()
+			.map(|entry| entry.unwrap().path())
+			.collect::<Vec<_>>()
+	};
+
+	build().new_instance().expect("failed to instantiate a runtime");
+	let cached = artifacts();
+	assert_eq!(cached.len(), 1);
+
+	// A corrupted artifact is not loaded, but replaced.
+	let mut corrupted = std::fs::read(&cached[0]).unwrap();
+	let last = corrupted.len() - 1;
+	corrupted[last] ^= 0xff;
+	std::fs::write(&cached[0], &corrupted).unwrap();
+
+	build().new_instance().expect("failed to instantiate a runtime");
+	assert_eq!(artifacts(), cached);
+	// wasmtime doesn't serialize a module deterministically, so only check that the stored
+	// artifact is a valid one again.
+	let replaced = std::fs::read(&cached[0]).unwrap();
+	assert_ne!(replaced, corrupted);
+	assert_eq!(replaced[..32], sp_core::hashing::blake2_256(&replaced[32..]));
+}
@@ -16,6 +16,8 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use sc_rpc_api::errors::Module;
+
 #[derive(derive_more::Display, derive_more::From)]
 /// Top-level error type for the RPC handler
 pub enum Error {
@@ -33,7 +35,8 @@ pub enum Error {
 	ProveFinalityFailed(sc_finality_grandpa::FinalityProofError),
 }
 
-/// The error codes returned by jsonrpc.
+/// The error codes returned by jsonrpc, relative to the base code of the GRANDPA module.
+#[derive(Clone, Copy)]
 pub enum ErrorCode {
 	/// Returned when Grandpa RPC endpoint is not ready.
 	NotReady = 1,
@@ -56,14 +59,31 @@ impl From<Error> for ErrorCode {
 	}
 }
 
+impl ErrorCode {
+	/// The reason reported in the error data.
+	fn reason(self) -> &'static str {
+		match self {
+			ErrorCode::NotReady => "notReady",
+			ErrorCode::AuthoritySetTooLarge => "authoritySetTooLarge",
+			ErrorCode::VoterStateTooLarge => "voterStateTooLarge",
+			ErrorCode::ProveFinality => "proveFinality",
+		}
+	}
+}
+
 impl From<Error> for jsonrpc_core::Error {
 	fn from(error: Error) -> Self {
 		let message = format!("{}", error);
 		let code = ErrorCode::from(error);
-		jsonrpc_core::Error {
+		let error = Module::Grandpa.error(
+			Module::Grandpa.base_code() + code as i64,
+			code.reason(),
 			message,
-			code: jsonrpc_core::ErrorCode::ServerError(code as i64),
-			data: None,
+		);
+		match code {
+			// the voter is not started yet.
+			ErrorCode::NotReady => error.retryable().into(),
+			_ => error.into(),
 		}
 	}
 }
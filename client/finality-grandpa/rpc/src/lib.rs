@@ -128,7 +128,7 @@ where
 
 	fn subscribe_justifications(
 		&self,
-		_metadata: Self::Metadata,
+		metadata: Self::Metadata,
 		subscriber: Subscriber<JustificationNotification>,
 	) {
 		let stream = self
@@ -136,10 +136,11 @@ where
 			.subscribe()
 			.map(|x| Ok(Ok::<_, jsonrpc_core::Error>(JustificationNotification::from(x))));
 
+		let subscription = metadata.open_subscription();
 		self.manager.add(subscriber, |sink| {
 			stream
 				.forward(sink.sink_map_err(|e| warn!("Error sending notifications: {:?}", e)))
-				.map(|_| ())
+				.map(move |_| drop(subscription))
 		});
 	}
 
@@ -310,7 +311,7 @@ mod tests {
 		let (io, _) = setup_io_handler(EmptyVoterState);
 
 		let request = r#"{"jsonrpc":"2.0","method":"grandpa_roundState","params":[],"id":1}"#;
-		let response = r#"{"jsonrpc":"2.0","error":{"code":1,"message":"GRANDPA RPC endpoint not ready"},"id":1}"#;
+		let response = r#"{"jsonrpc":"2.0","error":{"code":6001,"message":"GRANDPA RPC endpoint not ready","data":{"module":"grandpa","reason":"notReady","retryable":true}},"id":1}"#;
 
 		let meta = sc_rpc::Metadata::default();
 		assert_eq!(Some(response.into()), io.handle_request_sync(request, meta));
@@ -16,15 +16,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use std::{
-	collections::{HashMap, HashSet},
-	sync::Arc,
-};
+use std::{collections::HashSet, sync::Arc};
 
-use finality_grandpa::{voter_set::VoterSet, Error as GrandpaError};
+use finality_grandpa::voter_set::VoterSet;
 use parity_scale_codec::{Decode, Encode};
 use sp_blockchain::{Error as ClientError, HeaderBackend};
-use sp_finality_grandpa::AuthorityId;
+use sp_finality_grandpa::{justification::Error as JustificationError, AuthorityId};
 use sp_runtime::{
 	generic::BlockId,
 	traits::{Block as BlockT, Header as HeaderT, NumberFor},
@@ -40,11 +37,13 @@ use crate::{AuthorityList, Commit, Error};
 ///
 /// This is meant to be stored in the db and passed around the network to other
 /// nodes, and are used by syncing nodes to prove authority set handoffs.
+///
+/// The verification is implemented by [`sp_finality_grandpa::justification`], which can
+/// be used to verify the encoded justification from within the runtime.
 #[derive(Clone, Encode, Decode, PartialEq, Eq, Debug)]
 pub struct GrandpaJustification<Block: BlockT> {
-	round: u64,
-	pub(crate) commit: Commit<Block>,
-	votes_ancestries: Vec<Block::Header>,
+	pub(crate) justification:
+		sp_finality_grandpa::justification::GrandpaJustification<Block::Header>,
 }
 
 impl<Block: BlockT> GrandpaJustification<Block> {
@@ -90,7 +89,13 @@ impl<Block: BlockT> GrandpaJustification<Block> {
 			}
 		}
 
-		Ok(GrandpaJustification { round, commit, votes_ancestries })
+		Ok(GrandpaJustification {
+			justification: sp_finality_grandpa::justification::GrandpaJustification {
+				round,
+				commit,
+				votes_ancestries,
+			},
+		})
 	}
 
 	/// Decode a GRANDPA justification and validate the commit and the votes'
@@ -104,17 +109,14 @@ impl<Block: BlockT> GrandpaJustification<Block> {
 	where
 		NumberFor<Block>: finality_grandpa::BlockNumberOps,
 	{
-		let justification = GrandpaJustification::<Block>::decode(&mut &*encoded)
-			.map_err(|_| ClientError::JustificationDecode)?;
-
-		if (justification.commit.target_hash, justification.commit.target_number) !=
-			finalized_target
-		{
-			let msg = "invalid commit target in grandpa justification".to_string();
-			Err(ClientError::BadJustification(msg))
-		} else {
-			justification.verify_with_voter_set(set_id, voters).map(|_| justification)
-		}
+		sp_finality_grandpa::justification::GrandpaJustification::decode_and_verify_finalizes(
+			encoded,
+			finalized_target,
+			set_id,
+			voters,
+		)
+		.map(|justification| GrandpaJustification { justification })
+		.map_err(into_client_error)
 	}
 
 	/// Validate the commit and the votes' ancestry proofs.
@@ -122,10 +124,7 @@ impl<Block: BlockT> GrandpaJustification<Block> {
 	where
 		NumberFor<Block>: finality_grandpa::BlockNumberOps,
 	{
-		let voters = VoterSet::new(authorities.iter().cloned())
-			.ok_or(ClientError::Consensus(sp_consensus::Error::InvalidAuthoritiesSet))?;
-
-		self.verify_with_voter_set(set_id, &voters)
+		self.justification.verify(set_id, authorities).map_err(into_client_error)
 	}
 
 	/// Validate the commit and the votes' ancestry proofs.
@@ -137,114 +136,38 @@ impl<Block: BlockT> GrandpaJustification<Block> {
 	where
 		NumberFor<Block>: finality_grandpa::BlockNumberOps,
 	{
-		use finality_grandpa::Chain;
-
-		let ancestry_chain = AncestryChain::<Block>::new(&self.votes_ancestries);
-
-		match finality_grandpa::validate_commit(&self.commit, voters, &ancestry_chain) {
-			Ok(ref result) if result.ghost().is_some() => {},
-			_ => {
-				let msg = "invalid commit in grandpa justification".to_string();
-				return Err(ClientError::BadJustification(msg))
-			},
-		}
-
-		let mut buf = Vec::new();
-		let mut visited_hashes = HashSet::new();
-		for signed in self.commit.precommits.iter() {
-			if !sp_finality_grandpa::check_message_signature_with_buffer(
-				&finality_grandpa::Message::Precommit(signed.precommit.clone()),
-				&signed.id,
-				&signed.signature,
-				self.round,
-				set_id,
-				&mut buf,
-			) {
-				return Err(ClientError::BadJustification(
-					"invalid signature for precommit in grandpa justification".to_string(),
-				))
-			}
-
-			if self.commit.target_hash == signed.precommit.target_hash {
-				continue
-			}
-
-			match ancestry_chain.ancestry(self.commit.target_hash, signed.precommit.target_hash) {
-				Ok(route) => {
-					// ancestry starts from parent hash but the precommit target hash has been
-					// visited
-					visited_hashes.insert(signed.precommit.target_hash);
-					for hash in route {
-						visited_hashes.insert(hash);
-					}
-				},
-				_ =>
-					return Err(ClientError::BadJustification(
-						"invalid precommit ancestry proof in grandpa justification".to_string(),
-					)),
-			}
-		}
-
-		let ancestry_hashes =
-			self.votes_ancestries.iter().map(|h: &Block::Header| h.hash()).collect();
-
-		if visited_hashes != ancestry_hashes {
-			return Err(ClientError::BadJustification(
-				"invalid precommit ancestries in grandpa justification with unused headers"
-					.to_string(),
-			))
-		}
-
-		Ok(())
+		self.justification
+			.verify_with_voter_set(set_id, voters)
+			.map_err(into_client_error)
 	}
 
 	/// The target block number and hash that this justifications proves finality for.
 	pub fn target(&self) -> (NumberFor<Block>, Block::Hash) {
-		(self.commit.target_number, self.commit.target_hash)
+		(self.justification.commit.target_number, self.justification.commit.target_hash)
 	}
 }
 
-/// A utility trait implementing `finality_grandpa::Chain` using a given set of headers.
-/// This is useful when validating commits, using the given set of headers to
-/// verify a valid ancestry route to the target commit block.
-struct AncestryChain<Block: BlockT> {
-	ancestry: HashMap<Block::Hash, Block::Header>,
-}
-
-impl<Block: BlockT> AncestryChain<Block> {
-	fn new(ancestry: &[Block::Header]) -> AncestryChain<Block> {
-		let ancestry: HashMap<_, _> =
-			ancestry.iter().cloned().map(|h: Block::Header| (h.hash(), h)).collect();
-
-		AncestryChain { ancestry }
+impl<Block: BlockT> From<GrandpaJustification<Block>>
+	for sp_finality_grandpa::justification::GrandpaJustification<Block::Header>
+{
+	fn from(justification: GrandpaJustification<Block>) -> Self {
+		justification.justification
 	}
 }
 
-impl<Block: BlockT> finality_grandpa::Chain<Block::Hash, NumberFor<Block>> for AncestryChain<Block>
-where
-	NumberFor<Block>: finality_grandpa::BlockNumberOps,
-{
-	fn ancestry(
-		&self,
-		base: Block::Hash,
-		block: Block::Hash,
-	) -> Result<Vec<Block::Hash>, GrandpaError> {
-		let mut route = Vec::new();
-		let mut current_hash = block;
-		loop {
-			if current_hash == base {
-				break
-			}
-			match self.ancestry.get(&current_hash) {
-				Some(current_header) => {
-					current_hash = *current_header.parent_hash();
-					route.push(current_hash);
-				},
-				_ => return Err(GrandpaError::NotDescendent),
-			}
-		}
-		route.pop(); // remove the base
-
-		Ok(route)
-	}
+fn into_client_error(error: JustificationError) -> ClientError {
+	let msg = match error {
+		JustificationError::JustificationDecode => return ClientError::JustificationDecode,
+		JustificationError::InvalidAuthorityList =>
+			return ClientError::Consensus(sp_consensus::Error::InvalidAuthoritiesSet),
+		JustificationError::InvalidTarget => "invalid commit target in grandpa justification",
+		JustificationError::InvalidCommit => "invalid commit in grandpa justification",
+		JustificationError::InvalidPrecommitSignature =>
+			"invalid signature for precommit in grandpa justification",
+		JustificationError::InvalidPrecommitAncestry =>
+			"invalid precommit ancestry proof in grandpa justification",
+		JustificationError::UnusedAncestry =>
+			"invalid precommit ancestries in grandpa justification with unused headers",
+	};
+	ClientError::BadJustification(msg.to_string())
 }
@@ -27,6 +27,10 @@ use std::io;
 mod local;
 pub use local::LocalKeystore;
 
+/// Remote keystore implementation
+mod remote;
+pub use remote::{RemoteKeystore, REQUEST_TIMEOUT as REMOTE_REQUEST_TIMEOUT};
+
 /// Keystore error.
 #[derive(Debug, derive_more::Display, derive_more::From)]
 pub enum Error {
@@ -49,6 +53,10 @@ pub enum Error {
 	/// Keystore unavailable
 	#[display(fmt = "Keystore unavailable")]
 	Unavailable,
+	/// Invalid URI of a remote keystore
+	#[display(fmt = "Invalid remote keystore URI: {}", _0)]
+	#[from(ignore)]
+	InvalidUri(String),
 }
 
 /// Keystore Result
@@ -61,6 +69,7 @@ impl From<Error> for TraitError {
 			Error::InvalidSeed | Error::InvalidPhrase | Error::InvalidPassword =>
 				TraitError::ValidationError(error.to_string()),
 			Error::Unavailable => TraitError::Unavailable,
+			Error::InvalidUri(e) => TraitError::Other(e),
 			Error::Io(e) => TraitError::Other(e.to_string()),
 			Error::Json(e) => TraitError::Other(e.to_string()),
 		}
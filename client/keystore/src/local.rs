@@ -37,6 +37,9 @@ use std::{
 	sync::Arc,
 };
 
+#[cfg(feature = "bls-experimental")]
+use sp_application_crypto::bls381;
+
 use crate::{Error, Result};
 
 /// A local based keystore that is either memory-based or filesystem-based.
@@ -112,6 +115,20 @@ impl CryptoStore for LocalKeystore {
 		SyncCryptoStore::ecdsa_generate_new(self, id, seed)
 	}
 
+	#[cfg(feature = "bls-experimental")]
+	async fn bls381_public_keys(&self, id: KeyTypeId) -> Vec<bls381::Public> {
+		SyncCryptoStore::bls381_public_keys(self, id)
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	async fn bls381_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<bls381::Public, TraitError> {
+		SyncCryptoStore::bls381_generate_new(self, id, seed)
+	}
+
 	async fn insert_unknown(
 		&self,
 		id: KeyTypeId,
@@ -167,6 +184,8 @@ impl SyncCryptoStore for LocalKeystore {
 		Ok(raw_keys.into_iter().fold(Vec::new(), |mut v, k| {
 			v.push(CryptoTypePublicPair(sr25519::CRYPTO_ID, k.clone()));
 			v.push(CryptoTypePublicPair(ed25519::CRYPTO_ID, k.clone()));
+			#[cfg(feature = "bls-experimental")]
+			v.push(CryptoTypePublicPair(bls381::CRYPTO_ID, k.clone()));
 			v.push(CryptoTypePublicPair(ecdsa::CRYPTO_ID, k));
 			v
 		}))
@@ -215,6 +234,16 @@ impl SyncCryptoStore for LocalKeystore {
 					.map_err(|e| TraitError::from(e))?;
 				key_pair.map(|k| k.sign(msg).encode()).map(Ok).transpose()
 			},
+			#[cfg(feature = "bls-experimental")]
+			bls381::CRYPTO_ID => {
+				let pub_key = bls381::Public::from_slice(key.1.as_slice());
+				let key_pair = self
+					.0
+					.read()
+					.key_pair_by_type::<bls381::Pair>(&pub_key, id)
+					.map_err(|e| TraitError::from(e))?;
+				key_pair.map(|k| k.sign(msg).encode()).map(Ok).transpose()
+			},
 			_ => Err(TraitError::KeyNotSupported(id)),
 		}
 	}
@@ -288,6 +317,34 @@ impl SyncCryptoStore for LocalKeystore {
 		Ok(pair.public())
 	}
 
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_public_keys(&self, key_type: KeyTypeId) -> Vec<bls381::Public> {
+		use std::convert::TryFrom;
+
+		self.0
+			.read()
+			.raw_public_keys(key_type)
+			// keys of the other crypto types are filtered out by their size
+			.map(|v| v.into_iter().filter_map(|k| bls381::Public::try_from(&k[..]).ok()).collect())
+			.unwrap_or_default()
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<bls381::Public, TraitError> {
+		let pair = match seed {
+			Some(seed) =>
+				self.0.write().insert_ephemeral_from_seed_by_type::<bls381::Pair>(seed, id),
+			None => self.0.write().generate_by_type::<bls381::Pair>(id),
+		}
+		.map_err(|e| -> TraitError { e.into() })?;
+
+		Ok(pair.public())
+	}
+
 	fn insert_unknown(
 		&self,
 		key_type: KeyTypeId,
@@ -0,0 +1,672 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Remote keystore implementation
+//!
+//! Forwards every request to a remote signer over JSON-RPC 2.0 on HTTP(S), so the private keys
+//! never have to be stored on the node host. Keys, signatures and messages are exchanged as hex
+//! encoded bytes, key types and crypto types as their four bytes identifier. The signer is
+//! expected to serve the following methods:
+//!
+//! - `keystore_keys(keyType)`: the `(cryptoType, public)` pairs of the given key type.
+//! - `keystore_publicKeys(keyType, cryptoType)`: the public keys of the given types.
+//! - `keystore_generateNew(keyType, cryptoType)`: the public key of a new key pair.
+//! - `keystore_hasKeys([[public, keyType]])`: whether all the private keys are available.
+//! - `keystore_signWith(keyType, cryptoType, public, message)`: the SCALE encoded signature, or
+//!   `null` if the key is unknown.
+//! - `keystore_sr25519VrfSign(keyType, public, transcript)`: the `{ output, proof }` of the SCALE
+//!   encoded transcript data, or `null` if the key is unknown.
+//! - `keystore_ecdsaSignPrehashed(keyType, public, message)`: the signature, or `null` if the key
+//!   is unknown.
+//!
+//! Requests time out after [`REQUEST_TIMEOUT`] by default, the remote keystore is then reported
+//! as unavailable.
+//!
+//! Secret material never leaves the node: keys can't be inserted, nor generated from a seed. The
+//! signer must be served over HTTPS, unless it runs on the node host.
+
+use async_trait::async_trait;
+use futures::{channel::oneshot, Future};
+use hyper::{client, header, Body, Client as HyperClient, Uri};
+use hyper_rustls::HttpsConnector;
+use prometheus_endpoint::{
+	register, CounterVec, HistogramOpts, HistogramVec, Opts, PrometheusError, Registry, U64,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+#[cfg(feature = "bls-experimental")]
+use sp_application_crypto::bls381;
+use sp_application_crypto::{ecdsa, ed25519, sr25519};
+use sp_core::{
+	crypto::{CryptoTypeId, CryptoTypePublicPair, KeyTypeId},
+	Bytes, Encode,
+};
+use sp_keystore::{
+	vrf::{VRFSignature, VRFTranscriptData},
+	CryptoStore, Error as TraitError, SyncCryptoStore,
+};
+use std::{
+	convert::TryFrom,
+	net::IpAddr,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		mpsc, Arc,
+	},
+	thread,
+	time::{Duration, Instant},
+};
+
+use crate::{Error, Result};
+
+/// Default time after which a request to the remote keystore fails.
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+const LOG_TARGET: &str = "remote-keystore";
+
+/// A keystore forwarding all requests to a remote signer.
+#[derive(Clone)]
+pub struct RemoteKeystore {
+	uri: Uri,
+	client: HyperClient<HttpsConnector<client::HttpConnector>, Body>,
+	runtime: tokio::runtime::Handle,
+	timeout: Duration,
+	next_id: Arc<AtomicU64>,
+	metrics: Option<Metrics>,
+	// Stops the runtime thread once the last clone of the keystore is dropped.
+	_shutdown: Arc<oneshot::Sender<()>>,
+}
+
+impl RemoteKeystore {
+	/// Create a keystore forwarding requests to the signer served at `uri`, which must be an
+	/// `https` URI, or an `http` one of the local host.
+	///
+	/// The requests are run on a dedicated thread. Their duration and failures are reported to
+	/// the given registry, if any.
+	pub fn open(uri: &str, registry: Option<&Registry>) -> Result<Self> {
+		let uri = uri.parse::<Uri>().map_err(|e| Error::InvalidUri(e.to_string()))?;
+		match uri.scheme_str() {
+			Some("https") => {},
+			Some("http") if is_local_host(&uri) => {},
+			_ => return Err(Error::InvalidUri(format!("{} is not served over HTTPS", uri))),
+		}
+
+		let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+		let handle = runtime.handle().clone();
+		let (shutdown, on_shutdown) = oneshot::channel::<()>();
+		thread::Builder::new().name("remote-keystore".into()).spawn(move || {
+			let _ = runtime.block_on(on_shutdown);
+		})?;
+
+		let metrics = registry.and_then(|registry| {
+			Metrics::register(registry)
+				.map_err(|e| log::warn!(target: LOG_TARGET, "Failed to register metrics: {}", e))
+				.ok()
+		});
+
+		Ok(Self {
+			uri,
+			client: HyperClient::builder().build(HttpsConnector::with_native_roots()),
+			runtime: handle,
+			timeout: REQUEST_TIMEOUT,
+			next_id: Arc::new(AtomicU64::new(0)),
+			metrics,
+			_shutdown: Arc::new(shutdown),
+		})
+	}
+
+	/// Fail the requests which take longer than `timeout`.
+	pub fn with_timeout(mut self, timeout: Duration) -> Self {
+		self.timeout = timeout;
+		self
+	}
+
+	/// Run `future` on the runtime of the keystore and wait for its result.
+	///
+	/// The current thread is blocked without running the future on the executor of the caller,
+	/// which may be the one the keystore is used from.
+	fn blocking<T: Send + 'static>(&self, future: impl Future<Output = T> + Send + 'static) -> T {
+		let (tx, rx) = mpsc::channel();
+		self.runtime.spawn(async move {
+			let _ = tx.send(future.await);
+		});
+		rx.recv().expect("the runtime lives as long as the keystore; qed")
+	}
+
+	/// Call `method` of the remote signer.
+	async fn call<R: DeserializeOwned>(
+		&self,
+		method: &'static str,
+		params: Value,
+	) -> std::result::Result<R, TraitError> {
+		let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+		let body = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+		let request = hyper::Request::post(self.uri.clone())
+			.header(header::CONTENT_TYPE, "application/json")
+			.body(Body::from(body.to_string()))
+			.map_err(|e| TraitError::Other(e.to_string()))?;
+
+		let client = self.client.clone();
+		let timeout = self.timeout;
+		let start = Instant::now();
+		let response = self
+			.runtime
+			.spawn(async move {
+				let response = tokio::time::timeout(timeout, async {
+					let response = client.request(request).await?;
+					hyper::body::to_bytes(response.into_body()).await
+				});
+				match response.await {
+					Ok(body) => body.map_err(|e| e.to_string()),
+					Err(_) => Err(format!("no response after {:?}", timeout)),
+				}
+			})
+			.await
+			.map_err(|e| e.to_string())
+			.and_then(|response| response);
+
+		if let Some(metrics) = &self.metrics {
+			metrics
+				.request_duration
+				.with_label_values(&[method])
+				.observe(start.elapsed().as_secs_f64());
+		}
+		let result = response
+			.map_err(|e| {
+				log::warn!(target: LOG_TARGET, "Request `{}` failed: {}", method, e);
+				TraitError::Unavailable
+			})
+			.and_then(|body| parse_response(&body))
+			.and_then(|result| {
+				serde_json::from_value(result).map_err(|e| {
+					TraitError::Other(format!("Invalid result of `{}`: {}", method, e))
+				})
+			});
+		if let (Err(_), Some(metrics)) = (&result, &self.metrics) {
+			metrics.request_failures.with_label_values(&[method]).inc();
+		}
+		result
+	}
+
+	async fn public_keys<P: for<'a> TryFrom<&'a [u8]>>(
+		&self,
+		id: KeyTypeId,
+		crypto_id: CryptoTypeId,
+	) -> Vec<P> {
+		self.call::<Vec<Bytes>>(
+			"keystore_publicKeys",
+			json!([Bytes(id.0.to_vec()), crypto(crypto_id)]),
+		)
+		.await
+		.unwrap_or_default()
+		.iter()
+		.filter_map(|public| P::try_from(&public[..]).ok())
+		.collect()
+	}
+
+	async fn generate_new<P: for<'a> TryFrom<&'a [u8]>>(
+		&self,
+		id: KeyTypeId,
+		crypto_id: CryptoTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<P, TraitError> {
+		if seed.is_some() {
+			return Err(TraitError::Other(
+				"Keys can't be generated from a seed, which would be sent to the remote".into(),
+			))
+		}
+
+		let public: Bytes = self
+			.call("keystore_generateNew", json!([Bytes(id.0.to_vec()), crypto(crypto_id)]))
+			.await?;
+		P::try_from(&public[..])
+			.map_err(|_| TraitError::Other("Invalid public key generated by the remote".into()))
+	}
+}
+
+/// Whether `uri` is one of the local host, which the requests don't leave.
+fn is_local_host(uri: &Uri) -> bool {
+	match uri.host() {
+		Some("localhost") => true,
+		Some(host) => host
+			.trim_start_matches('[')
+			.trim_end_matches(']')
+			.parse::<IpAddr>()
+			.map_or(false, |ip| ip.is_loopback()),
+		None => false,
+	}
+}
+
+/// The four bytes identifier of a crypto type, as sent to the remote signer.
+fn crypto(crypto_id: CryptoTypeId) -> Bytes {
+	Bytes(crypto_id.0.to_vec())
+}
+
+/// Extract the result of a JSON-RPC response.
+fn parse_response(body: &[u8]) -> std::result::Result<Value, TraitError> {
+	#[derive(Deserialize)]
+	struct Response {
+		#[serde(default)]
+		result: Value,
+		error: Option<ResponseError>,
+	}
+
+	#[derive(Deserialize)]
+	struct ResponseError {
+		code: i64,
+		message: String,
+	}
+
+	let response: Response = serde_json::from_slice(body)
+		.map_err(|e| TraitError::Other(format!("Invalid remote keystore response: {}", e)))?;
+	match response.error {
+		Some(error) => Err(TraitError::Other(format!(
+			"Remote keystore error {}: {}",
+			error.code, error.message
+		))),
+		None => Ok(response.result),
+	}
+}
+
+#[async_trait]
+impl CryptoStore for RemoteKeystore {
+	async fn keys(
+		&self,
+		id: KeyTypeId,
+	) -> std::result::Result<Vec<CryptoTypePublicPair>, TraitError> {
+		let keys: Vec<(Bytes, Bytes)> =
+			self.call("keystore_keys", json!([Bytes(id.0.to_vec())])).await?;
+		keys.into_iter()
+			.map(|(crypto_id, public)| {
+				let crypto_id = <[u8; 4]>::try_from(&crypto_id[..])
+					.map_err(|_| TraitError::Other("Invalid crypto type of remote key".into()))?;
+				Ok(CryptoTypePublicPair(CryptoTypeId(crypto_id), public.0))
+			})
+			.collect()
+	}
+
+	async fn sr25519_public_keys(&self, id: KeyTypeId) -> Vec<sr25519::Public> {
+		self.public_keys(id, sr25519::CRYPTO_ID).await
+	}
+
+	async fn sr25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<sr25519::Public, TraitError> {
+		self.generate_new(id, sr25519::CRYPTO_ID, seed).await
+	}
+
+	async fn ed25519_public_keys(&self, id: KeyTypeId) -> Vec<ed25519::Public> {
+		self.public_keys(id, ed25519::CRYPTO_ID).await
+	}
+
+	async fn ed25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ed25519::Public, TraitError> {
+		self.generate_new(id, ed25519::CRYPTO_ID, seed).await
+	}
+
+	async fn ecdsa_public_keys(&self, id: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.public_keys(id, ecdsa::CRYPTO_ID).await
+	}
+
+	async fn ecdsa_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ecdsa::Public, TraitError> {
+		self.generate_new(id, ecdsa::CRYPTO_ID, seed).await
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	async fn bls381_public_keys(&self, id: KeyTypeId) -> Vec<bls381::Public> {
+		self.public_keys(id, bls381::CRYPTO_ID).await
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	async fn bls381_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<bls381::Public, TraitError> {
+		self.generate_new(id, bls381::CRYPTO_ID, seed).await
+	}
+
+	async fn insert_unknown(
+		&self,
+		_id: KeyTypeId,
+		_suri: &str,
+		_public: &[u8],
+	) -> std::result::Result<(), ()> {
+		log::warn!(target: LOG_TARGET, "Secret keys can't be inserted into the remote keystore");
+		Err(())
+	}
+
+	async fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		let public_keys = public_keys
+			.iter()
+			.map(|(public, id)| (Bytes(public.clone()), Bytes(id.0.to_vec())))
+			.collect::<Vec<_>>();
+		self.call("keystore_hasKeys", json!([public_keys])).await.unwrap_or(false)
+	}
+
+	async fn supported_keys(
+		&self,
+		id: KeyTypeId,
+		keys: Vec<CryptoTypePublicPair>,
+	) -> std::result::Result<Vec<CryptoTypePublicPair>, TraitError> {
+		let all_keys = CryptoStore::keys(self, id).await?;
+		Ok(keys.into_iter().filter(|key| all_keys.contains(key)).collect())
+	}
+
+	async fn sign_with(
+		&self,
+		id: KeyTypeId,
+		key: &CryptoTypePublicPair,
+		msg: &[u8],
+	) -> std::result::Result<Option<Vec<u8>>, TraitError> {
+		let params =
+			json!([Bytes(id.0.to_vec()), crypto(key.0), Bytes(key.1.clone()), Bytes(msg.to_vec())]);
+		let signature: Option<Bytes> = self.call("keystore_signWith", params).await?;
+		Ok(signature.map(|signature| signature.0))
+	}
+
+	async fn sr25519_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		transcript_data: VRFTranscriptData,
+	) -> std::result::Result<Option<VRFSignature>, TraitError> {
+		#[derive(Deserialize)]
+		struct RemoteVRFSignature {
+			output: Bytes,
+			proof: Bytes,
+		}
+
+		let params = json!([
+			Bytes(key_type.0.to_vec()),
+			Bytes(public.0.to_vec()),
+			Bytes(transcript_data.encode()),
+		]);
+		let signature: Option<RemoteVRFSignature> =
+			self.call("keystore_sr25519VrfSign", params).await?;
+		signature
+			.map(|signature| {
+				let invalid = |_| TraitError::Other("Invalid VRF signature of the remote".into());
+				Ok(VRFSignature {
+					output: schnorrkel::vrf::VRFOutput::from_bytes(&signature.output)
+						.map_err(invalid)?,
+					proof: schnorrkel::vrf::VRFProof::from_bytes(&signature.proof)
+						.map_err(invalid)?,
+				})
+			})
+			.transpose()
+	}
+
+	async fn ecdsa_sign_prehashed(
+		&self,
+		id: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
+		let params =
+			json!([Bytes(id.0.to_vec()), Bytes(public.as_ref().to_vec()), Bytes(msg.to_vec())]);
+		let signature: Option<Bytes> = self.call("keystore_ecdsaSignPrehashed", params).await?;
+		signature
+			.map(|signature| {
+				ecdsa::Signature::try_from(&signature[..])
+					.map_err(|_| TraitError::Other("Invalid signature of the remote".into()))
+			})
+			.transpose()
+	}
+}
+
+impl SyncCryptoStore for RemoteKeystore {
+	fn sr25519_public_keys(&self, id: KeyTypeId) -> Vec<sr25519::Public> {
+		let keystore = self.clone();
+		self.blocking(async move { CryptoStore::sr25519_public_keys(&keystore, id).await })
+	}
+
+	fn sr25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<sr25519::Public, TraitError> {
+		let (keystore, seed) = (self.clone(), seed.map(str::to_owned));
+		self.blocking(async move {
+			CryptoStore::sr25519_generate_new(&keystore, id, seed.as_deref()).await
+		})
+	}
+
+	fn ed25519_public_keys(&self, id: KeyTypeId) -> Vec<ed25519::Public> {
+		let keystore = self.clone();
+		self.blocking(async move { CryptoStore::ed25519_public_keys(&keystore, id).await })
+	}
+
+	fn ed25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ed25519::Public, TraitError> {
+		let (keystore, seed) = (self.clone(), seed.map(str::to_owned));
+		self.blocking(async move {
+			CryptoStore::ed25519_generate_new(&keystore, id, seed.as_deref()).await
+		})
+	}
+
+	fn ecdsa_public_keys(&self, id: KeyTypeId) -> Vec<ecdsa::Public> {
+		let keystore = self.clone();
+		self.blocking(async move { CryptoStore::ecdsa_public_keys(&keystore, id).await })
+	}
+
+	fn ecdsa_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ecdsa::Public, TraitError> {
+		let (keystore, seed) = (self.clone(), seed.map(str::to_owned));
+		self.blocking(async move {
+			CryptoStore::ecdsa_generate_new(&keystore, id, seed.as_deref()).await
+		})
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_public_keys(&self, id: KeyTypeId) -> Vec<bls381::Public> {
+		let keystore = self.clone();
+		self.blocking(async move { CryptoStore::bls381_public_keys(&keystore, id).await })
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<bls381::Public, TraitError> {
+		let (keystore, seed) = (self.clone(), seed.map(str::to_owned));
+		self.blocking(async move {
+			CryptoStore::bls381_generate_new(&keystore, id, seed.as_deref()).await
+		})
+	}
+
+	fn insert_unknown(
+		&self,
+		_key_type: KeyTypeId,
+		_suri: &str,
+		_public: &[u8],
+	) -> std::result::Result<(), ()> {
+		log::warn!(target: LOG_TARGET, "Secret keys can't be inserted into the remote keystore");
+		Err(())
+	}
+
+	fn supported_keys(
+		&self,
+		id: KeyTypeId,
+		keys: Vec<CryptoTypePublicPair>,
+	) -> std::result::Result<Vec<CryptoTypePublicPair>, TraitError> {
+		let keystore = self.clone();
+		self.blocking(async move { CryptoStore::supported_keys(&keystore, id, keys).await })
+	}
+
+	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		let (keystore, public_keys) = (self.clone(), public_keys.to_vec());
+		self.blocking(async move { CryptoStore::has_keys(&keystore, &public_keys).await })
+	}
+
+	fn sign_with(
+		&self,
+		id: KeyTypeId,
+		key: &CryptoTypePublicPair,
+		msg: &[u8],
+	) -> std::result::Result<Option<Vec<u8>>, TraitError> {
+		let (keystore, key, msg) = (self.clone(), key.clone(), msg.to_vec());
+		self.blocking(async move { CryptoStore::sign_with(&keystore, id, &key, &msg).await })
+	}
+
+	fn sr25519_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		transcript_data: VRFTranscriptData,
+	) -> std::result::Result<Option<VRFSignature>, TraitError> {
+		let (keystore, public) = (self.clone(), *public);
+		self.blocking(async move {
+			CryptoStore::sr25519_vrf_sign(&keystore, key_type, &public, transcript_data).await
+		})
+	}
+
+	fn ecdsa_sign_prehashed(
+		&self,
+		id: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
+		let (keystore, public, msg) = (self.clone(), public.clone(), *msg);
+		self.blocking(async move {
+			CryptoStore::ecdsa_sign_prehashed(&keystore, id, &public, &msg).await
+		})
+	}
+}
+
+/// Remote keystore metrics.
+#[derive(Clone)]
+struct Metrics {
+	request_duration: HistogramVec,
+	request_failures: CounterVec<U64>,
+}
+
+impl Metrics {
+	fn register(registry: &Registry) -> std::result::Result<Self, PrometheusError> {
+		Ok(Self {
+			request_duration: register(
+				HistogramVec::new(
+					HistogramOpts::new(
+						"remote_keystore_request_duration_seconds",
+						"Time taken by the requests to the remote keystore",
+					),
+					&["method"],
+				)?,
+				registry,
+			)?,
+			request_failures: register(
+				CounterVec::new(
+					Opts::new(
+						"remote_keystore_request_failures_total",
+						"Number of failed requests to the remote keystore",
+					),
+					&["method"],
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::testing::SR25519;
+
+	#[test]
+	fn parse_response_works() {
+		assert_eq!(
+			parse_response(br#"{"jsonrpc":"2.0","id":0,"result":["0x0102"]}"#).unwrap(),
+			json!(["0x0102"]),
+		);
+		assert_eq!(
+			parse_response(br#"{"jsonrpc":"2.0","id":0,"result":null}"#).unwrap(),
+			Value::Null,
+		);
+		assert!(matches!(
+			parse_response(br#"{"jsonrpc":"2.0","id":0,"error":{"code":1,"message":"locked"}}"#),
+			Err(TraitError::Other(e)) if e.contains("locked"),
+		));
+	}
+
+	#[test]
+	fn unreachable_remote_is_unavailable() {
+		assert!(matches!(
+			RemoteKeystore::open("ws://127.0.0.1:1", None),
+			Err(Error::InvalidUri(_))
+		));
+
+		let keystore = RemoteKeystore::open("http://127.0.0.1:1", None)
+			.unwrap()
+			.with_timeout(Duration::from_secs(1));
+		assert!(SyncCryptoStore::sr25519_public_keys(&keystore, SR25519).is_empty());
+		assert!(matches!(
+			SyncCryptoStore::sign_with(
+				&keystore,
+				SR25519,
+				&CryptoTypePublicPair(sr25519::CRYPTO_ID, vec![0; 32]),
+				b"message",
+			),
+			Err(TraitError::Unavailable)
+		));
+	}
+
+	#[test]
+	fn only_https_or_local_remotes_are_accepted() {
+		for uri in ["http://example.com", "http://10.0.0.1:8080"] {
+			assert!(matches!(RemoteKeystore::open(uri, None), Err(Error::InvalidUri(_))));
+		}
+		for uri in
+			["https://example.com", "http://localhost:1", "http://127.0.0.1:1", "http://[::1]:1"]
+		{
+			assert!(RemoteKeystore::open(uri, None).is_ok());
+		}
+	}
+
+	#[test]
+	fn secrets_are_not_sent_to_the_remote() {
+		// Requests to the remote would time out, instead of failing right away.
+		let keystore = RemoteKeystore::open("https://192.0.2.1", None)
+			.unwrap()
+			.with_timeout(Duration::from_secs(60));
+
+		assert!(matches!(
+			SyncCryptoStore::sr25519_generate_new(&keystore, SR25519, Some("//Alice")),
+			Err(TraitError::Other(_))
+		));
+		assert_eq!(
+			SyncCryptoStore::insert_unknown(&keystore, SR25519, "//Alice", &[0; 32]),
+			Err(())
+		);
+	}
+}
@@ -60,11 +60,20 @@
 //! opens the door for neighbor status packets to be baked into the gossip protocol.
 //! These status packets will typically contain light pieces of information
 //! used to inform peers of a current view of protocol state.
+//!
+//! Validators can also adjust the reputation of peers sending them invalid messages through the
+//! `ValidatorContext`, and bound how long messages are kept for with
+//! `Validator::message_lifetime`. Protocols which should only accept messages from a known set of
+//! peers, e.g. the current authorities, can wrap their validator in an `AllowedSendersValidator`
+//! and update its `AllowedSenders` on session changes.
 
 pub use self::{
 	bridge::GossipEngine,
 	state_machine::TopicNotification,
-	validator::{DiscardAll, MessageIntent, ValidationResult, Validator, ValidatorContext},
+	validator::{
+		AllowedSenders, AllowedSendersValidator, DiscardAll, MessageIntent, ValidationResult,
+		Validator, ValidatorContext,
+	},
 };
 
 use futures::prelude::*;
@@ -21,7 +21,7 @@ use crate::{MessageIntent, Network, ValidationResult, Validator, ValidatorContex
 use libp2p::PeerId;
 use lru::LruCache;
 use prometheus_endpoint::{register, Counter, PrometheusError, Registry, U64};
-use sc_network::ObservedRole;
+use sc_network::{ObservedRole, ReputationChange};
 use sp_runtime::traits::{Block as BlockT, Hash, HashFor};
 use std::{
 	borrow::Cow,
@@ -73,6 +73,7 @@ struct MessageEntry<B: BlockT> {
 	topic: B::Hash,
 	message: Vec<u8>,
 	sender: Option<PeerId>,
+	registered: Instant,
 }
 
 /// Local implementation of `ValidatorContext`.
@@ -102,6 +103,11 @@ impl<'g, 'p, B: BlockT> ValidatorContext<B> for NetworkContext<'g, 'p, B> {
 	fn send_topic(&mut self, who: &PeerId, topic: B::Hash, force: bool) {
 		self.gossip.send_topic(self.network, who, topic, force);
 	}
+
+	/// Adjust the reputation of a peer.
+	fn report_peer(&mut self, who: &PeerId, reputation: ReputationChange) {
+		self.network.report_peer(who.clone(), reputation);
+	}
 }
 
 fn propagate<'a, B: BlockT, I>(
@@ -219,7 +225,13 @@ impl<B: BlockT> ConsensusGossip<B> {
 		sender: Option<PeerId>,
 	) {
 		if self.known_messages.put(message_hash.clone(), ()).is_none() {
-			self.messages.push(MessageEntry { message_hash, topic, message, sender });
+			self.messages.push(MessageEntry {
+				message_hash,
+				topic,
+				message,
+				sender,
+				registered: Instant::now(),
+			});
 
 			if let Some(ref metrics) = self.metrics {
 				metrics.registered_messages.inc();
@@ -297,7 +309,12 @@ impl<B: BlockT> ConsensusGossip<B> {
 		let before = self.messages.len();
 
 		let mut message_expired = self.validator.message_expired();
-		self.messages.retain(|entry| !message_expired(entry.topic, &entry.message));
+		let message_lifetime = self.validator.message_lifetime();
+		self.messages.retain(|entry| {
+			let too_old =
+				message_lifetime.map_or(false, |lifetime| entry.registered.elapsed() >= lifetime);
+			!too_old && !message_expired(entry.topic, &entry.message)
+		});
 
 		let expired_messages = before - self.messages.len();
 
@@ -527,8 +544,9 @@ impl Metrics {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::{AllowedSenders, AllowedSendersValidator};
 	use futures::prelude::*;
-	use sc_network::{Event, ReputationChange};
+	use sc_network::Event;
 	use sp_runtime::testing::{Block as RawBlock, ExtrinsicWrapper, H256};
 	use std::{
 		borrow::Cow,
@@ -546,6 +564,7 @@ mod tests {
 					topic: $topic,
 					message: $m,
 					sender: None,
+					registered: Instant::now(),
 				});
 			}
 		};
@@ -734,4 +753,68 @@ mod tests {
 			to_forward,
 		);
 	}
+
+	#[test]
+	fn collects_messages_older_than_their_lifetime() {
+		struct ShortLived;
+		impl Validator<Block> for ShortLived {
+			fn validate(
+				&self,
+				_context: &mut dyn ValidatorContext<Block>,
+				_sender: &PeerId,
+				_data: &[u8],
+			) -> ValidationResult<H256> {
+				ValidationResult::ProcessAndKeep(H256::default())
+			}
+
+			fn message_lifetime(&self) -> Option<time::Duration> {
+				Some(time::Duration::from_secs(60))
+			}
+		}
+
+		let mut consensus =
+			ConsensusGossip::<Block>::new(Arc::new(ShortLived), "/foo".into(), None);
+		let topic = [1; 32].into();
+		consensus.register_message(topic, vec![1, 2, 3]);
+		consensus.register_message(topic, vec![4, 5, 6]);
+		consensus.messages[0].registered -= time::Duration::from_secs(61);
+
+		consensus.collect_garbage();
+		assert_eq!(consensus.messages.len(), 1);
+		assert_eq!(consensus.messages[0].message, vec![4, 5, 6]);
+	}
+
+	#[test]
+	fn on_incoming_discards_and_reports_messages_of_not_allowed_senders() {
+		let mut network = NoOpNetwork::default();
+		let allowed = PeerId::random();
+		let not_allowed = PeerId::random();
+		let allowed_senders = AllowedSenders::new(vec![allowed.clone()]);
+		let mut consensus = ConsensusGossip::<Block>::new(
+			Arc::new(AllowedSendersValidator::new(AllowAll, allowed_senders.clone())),
+			"/foo".into(),
+			None,
+		);
+		consensus.new_peer(&mut network, allowed.clone(), ObservedRole::Authority);
+		consensus.new_peer(&mut network, not_allowed.clone(), ObservedRole::Full);
+
+		let to_forward =
+			consensus.on_incoming(&mut network, not_allowed.clone(), vec![vec![1, 2, 3]]);
+		assert!(to_forward.is_empty());
+		assert_eq!(
+			network.inner.lock().unwrap().peer_reports,
+			vec![(not_allowed.clone(), crate::validator::NOT_ALLOWED_SENDER)],
+		);
+
+		let to_forward = consensus.on_incoming(&mut network, allowed.clone(), vec![vec![1, 2, 3]]);
+		assert_eq!(to_forward.len(), 1);
+
+		// The allowed senders are rotated.
+		allowed_senders.set(vec![not_allowed.clone()]);
+		let to_forward =
+			consensus.on_incoming(&mut network, not_allowed.clone(), vec![vec![4, 5, 6]]);
+		assert_eq!(to_forward.len(), 1);
+		let to_forward = consensus.on_incoming(&mut network, allowed.clone(), vec![vec![7, 8, 9]]);
+		assert!(to_forward.is_empty());
+	}
 }
@@ -16,8 +16,17 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use sc_network::{ObservedRole, PeerId};
+use sc_network::{ObservedRole, PeerId, ReputationChange};
 use sp_runtime::traits::Block as BlockT;
+use std::{
+	collections::HashSet,
+	sync::{Arc, RwLock},
+	time::Duration,
+};
+
+/// Reputation change when a peer which isn't allowed to send messages sends us one.
+pub(crate) const NOT_ALLOWED_SENDER: ReputationChange =
+	ReputationChange::new(-(1 << 8), "Gossip from a peer which isn't allowed to send messages");
 
 /// Validates consensus messages.
 pub trait Validator<B: BlockT>: Send + Sync {
@@ -41,6 +50,14 @@ pub trait Validator<B: BlockT>: Send + Sync {
 		Box::new(move |_topic, _data| false)
 	}
 
+	/// Maximum duration messages are kept for, after which they expire regardless of
+	/// [`Validator::message_expired`].
+	///
+	/// Messages are kept until they are reported as expired by default.
+	fn message_lifetime(&self) -> Option<Duration> {
+		None
+	}
+
 	/// Produce a closure for filtering egress messages.
 	fn message_allowed<'a>(
 		&'a self,
@@ -59,6 +76,8 @@ pub trait ValidatorContext<B: BlockT> {
 	fn send_message(&mut self, who: &PeerId, message: Vec<u8>);
 	/// Send all messages with given topic to a peer.
 	fn send_topic(&mut self, who: &PeerId, topic: B::Hash, force: bool);
+	/// Adjust the reputation of a peer, e.g. because it sent an invalid message.
+	fn report_peer(&mut self, who: &PeerId, reputation: ReputationChange);
 }
 
 /// The reason for sending out the message.
@@ -106,3 +125,81 @@ impl<B: BlockT> Validator<B> for DiscardAll {
 		Box::new(move |_who, _intent, _topic, _data| false)
 	}
 }
+
+/// The set of peers allowed to send messages to an [`AllowedSendersValidator`].
+///
+/// The set can be updated at any time, e.g. on session boundaries with the peers of the
+/// authorities of the new session. Clones share the same set.
+#[derive(Clone, Default)]
+pub struct AllowedSenders(Arc<RwLock<HashSet<PeerId>>>);
+
+impl AllowedSenders {
+	/// Create a new set, allowing the given peers.
+	pub fn new(peers: impl IntoIterator<Item = PeerId>) -> Self {
+		Self(Arc::new(RwLock::new(peers.into_iter().collect())))
+	}
+
+	/// Replace the allowed peers.
+	pub fn set(&self, peers: impl IntoIterator<Item = PeerId>) {
+		*self.0.write().expect("Lock is never poisoned; qed") = peers.into_iter().collect();
+	}
+
+	/// Whether the given peer is allowed to send messages.
+	pub fn contains(&self, who: &PeerId) -> bool {
+		self.0.read().expect("Lock is never poisoned; qed").contains(who)
+	}
+}
+
+/// A gossip message validator which discards messages sent by peers that aren't part of the
+/// [`AllowedSenders`], e.g. peers which aren't authorities, and reports them.
+///
+/// Messages from allowed peers are validated by the inner validator.
+pub struct AllowedSendersValidator<V> {
+	inner: V,
+	allowed: AllowedSenders,
+}
+
+impl<V> AllowedSendersValidator<V> {
+	/// Create a new validator, accepting messages of the given peers only.
+	pub fn new(inner: V, allowed: AllowedSenders) -> Self {
+		Self { inner, allowed }
+	}
+}
+
+impl<B: BlockT, V: Validator<B>> Validator<B> for AllowedSendersValidator<V> {
+	fn new_peer(&self, context: &mut dyn ValidatorContext<B>, who: &PeerId, role: ObservedRole) {
+		self.inner.new_peer(context, who, role)
+	}
+
+	fn peer_disconnected(&self, context: &mut dyn ValidatorContext<B>, who: &PeerId) {
+		self.inner.peer_disconnected(context, who)
+	}
+
+	fn validate(
+		&self,
+		context: &mut dyn ValidatorContext<B>,
+		sender: &PeerId,
+		data: &[u8],
+	) -> ValidationResult<B::Hash> {
+		if !self.allowed.contains(sender) {
+			context.report_peer(sender, NOT_ALLOWED_SENDER);
+			return ValidationResult::Discard
+		}
+
+		self.inner.validate(context, sender, data)
+	}
+
+	fn message_expired<'a>(&'a self) -> Box<dyn FnMut(B::Hash, &[u8]) -> bool + 'a> {
+		self.inner.message_expired()
+	}
+
+	fn message_lifetime(&self) -> Option<Duration> {
+		self.inner.message_lifetime()
+	}
+
+	fn message_allowed<'a>(
+		&'a self,
+	) -> Box<dyn FnMut(&PeerId, MessageIntent, &B::Hash, &[u8]) -> bool + 'a> {
+		self.inner.message_allowed()
+	}
+}
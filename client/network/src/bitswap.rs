@@ -66,6 +66,9 @@ const MAX_PACKET_SIZE: usize = 16 * 1024 * 1024;
 const MAX_RESPONSE_QUEUE: usize = 20;
 // Max number of blocks per wantlist
 const MAX_WANTED_BLOCKS: usize = 16;
+// Max total size of the blocks sent in a single response, leaving room for the rest of the
+// message. Blocks that don't fit are reported as missing and will be requested again by the remote.
+const MAX_RESPONSE_BLOCKS_SIZE: usize = MAX_PACKET_SIZE - 64 * 1024;
 
 const PROTOCOL_NAME: &'static [u8] = b"/ipfs/bitswap/1.2.0";
 
@@ -238,6 +241,7 @@ impl<B: BlockT> NetworkBehaviour for Bitswap<B> {
 			trace!(target: LOG_TARGET, "Ignored request: too many entries");
 			return
 		}
+		let mut blocks_size = 0;
 		for entry in wantlist.entries {
 			let cid = match cid::Cid::read_bytes(entry.block.as_slice()) {
 				Ok(cid) => cid,
@@ -266,6 +270,16 @@ impl<B: BlockT> NetworkBehaviour for Bitswap<B> {
 				Some(transaction) => {
 					trace!(target: LOG_TARGET, "Found CID {:?}, hash {:?}", cid, hash);
 					if entry.want_type == WantType::Block as i32 {
+						if blocks_size + transaction.len() > MAX_RESPONSE_BLOCKS_SIZE {
+							// Let the remote know that it has to request the block again.
+							trace!(target: LOG_TARGET, "Skipping CID {:?}: response is full", cid);
+							response.block_presences.push(BlockPresence {
+								r#type: BlockPresenceType::DontHave as i32,
+								cid: cid.to_bytes(),
+							});
+							continue
+						}
+						blocks_size += transaction.len();
 						let prefix = Prefix {
 							version: cid.version(),
 							codec: cid.codec(),
@@ -333,3 +347,74 @@ pub enum BitswapError {
 	#[display(fmt = "Failed to send response.")]
 	SendResponse,
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::schema::bitswap::message::{wantlist::Entry, Wantlist};
+	use cid::multihash::{Code, MultihashDigest};
+	use futures::executor::block_on;
+	use sc_block_builder::BlockBuilderProvider;
+	use sp_consensus::BlockOrigin;
+	use substrate_test_runtime::Extrinsic;
+	use substrate_test_runtime_client::{
+		runtime::Block, BlockBuilderExt, ClientBlockImportExt, ExecutionStrategy,
+		TestClientBuilder, TestClientBuilderExt,
+	};
+
+	#[test]
+	fn blocks_exceeding_the_response_size_are_reported_missing() {
+		let mut client = TestClientBuilder::with_tx_storage(u32::MAX)
+			.set_execution_strategy(ExecutionStrategy::NativeWhenPossible)
+			.build();
+		// Two transactions which don't fit in a single response.
+		let transactions = [
+			vec![1u8; MAX_RESPONSE_BLOCKS_SIZE / 2 + 1],
+			vec![2u8; MAX_RESPONSE_BLOCKS_SIZE / 2 + 1],
+		];
+		let mut builder = client.new_block(Default::default()).unwrap();
+		for transaction in &transactions {
+			builder.push(Extrinsic::Store(transaction.clone())).unwrap();
+		}
+		let block = builder.build().unwrap().block;
+		block_on(client.import(BlockOrigin::Own, block)).unwrap();
+
+		let cids = transactions
+			.iter()
+			.map(|transaction| cid::Cid::new_v1(0x55, Code::Blake2b256.digest(transaction)))
+			.collect::<Vec<_>>();
+		let request = BitswapMessage {
+			wantlist: Some(Wantlist {
+				entries: cids
+					.iter()
+					.map(|cid| Entry {
+						block: cid.to_bytes(),
+						want_type: WantType::Block as i32,
+						send_dont_have: false,
+						..Default::default()
+					})
+					.collect(),
+				full: false,
+			}),
+			..Default::default()
+		};
+
+		let mut bitswap = Bitswap::<Block>::new(Arc::new(client));
+		bitswap.inject_event(
+			PeerId::random(),
+			ConnectionId::new(0),
+			HandlerEvent::Request(request),
+		);
+
+		let (_, response) = bitswap.ready_blocks.pop_front().unwrap();
+		assert_eq!(response.payload.len(), 1);
+		assert_eq!(response.payload[0].data, transactions[0]);
+		assert_eq!(
+			response.block_presences,
+			vec![BlockPresence {
+				r#type: BlockPresenceType::DontHave as i32,
+				cid: cids[1].to_bytes(),
+			}],
+		);
+	}
+}
@@ -32,6 +32,8 @@ use futures::{
 };
 use log::debug;
 use lru::LruCache;
+use parking_lot::Mutex;
+use prometheus_endpoint::{register, CounterVec, Opts, PrometheusError, Registry, U64};
 use prost::Message;
 use sp_runtime::{
 	generic::BlockId,
@@ -41,13 +43,26 @@ use std::{
 	cmp::min,
 	hash::{Hash, Hasher},
 	sync::Arc,
-	time::Duration,
+	time::{Duration, Instant},
 };
 
 const LOG_TARGET: &str = "sync";
 const MAX_BLOCKS_IN_RESPONSE: usize = 128;
 const MAX_BODY_BYTES: usize = 8 * 1024 * 1024;
 const MAX_NUMBER_OF_SAME_REQUESTS_PER_PEER: usize = 2;
+/// The period over which the bytes served to a peer are accounted.
+const SERVING_QUOTA_PERIOD: Duration = Duration::from_secs(10);
+/// The number of bytes served to all peers per period above which the handler is under load, and
+/// the serving quotas of the peers apply.
+const LOAD_THRESHOLD: usize = 128 * 1024 * 1024;
+/// The number of bytes served to a reciprocating peer per period before it is deprioritized.
+const RECIPROCATING_PEER_QUOTA: usize = 64 * 1024 * 1024;
+/// The number of bytes served to a leeching peer per period before its requests are dropped.
+const LEECHING_PEER_QUOTA: usize = 8 * 1024 * 1024;
+/// The maximum number of blocks served to a deprioritized peer in a single response.
+const MAX_BLOCKS_IN_DEPRIORITIZED_RESPONSE: usize = 16;
+/// The number of peers [`Reciprocity`] remembers.
+const RECIPROCITY_CACHE_SIZE: usize = 1024;
 
 mod rep {
 	use super::ReputationChange as Rep;
@@ -99,6 +114,127 @@ impl<B: BlockT> Hash for SeenRequestsKey<B> {
 	}
 }
 
+/// Whether a peer has served us blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeerClass {
+	/// The peer answered our block requests.
+	Reciprocating,
+	/// The peer never answered our block requests.
+	Leeching,
+}
+
+impl PeerClass {
+	fn as_str(&self) -> &'static str {
+		match self {
+			PeerClass::Reciprocating => "reciprocating",
+			PeerClass::Leeching => "leeching",
+		}
+	}
+}
+
+/// Tracks the peers which have served us blocks.
+///
+/// The sync protocol notes the peers answering our block requests, while the
+/// [`BlockRequestHandler`] favours these peers when serving blocks.
+#[derive(Clone)]
+pub struct Reciprocity(Arc<Mutex<LruCache<PeerId, ()>>>);
+
+impl Default for Reciprocity {
+	fn default() -> Self {
+		Self(Arc::new(Mutex::new(LruCache::new(RECIPROCITY_CACHE_SIZE))))
+	}
+}
+
+impl Reciprocity {
+	/// Note that `peer` has served us blocks.
+	pub fn note_served(&self, peer: &PeerId) {
+		self.0.lock().put(*peer, ());
+	}
+
+	/// Returns the class of `peer`.
+	pub fn class(&self, peer: &PeerId) -> PeerClass {
+		if self.0.lock().contains(peer) {
+			PeerClass::Reciprocating
+		} else {
+			PeerClass::Leeching
+		}
+	}
+}
+
+/// The bytes served during the current period.
+struct ServedBytes {
+	period_start: Instant,
+	bytes: usize,
+}
+
+impl ServedBytes {
+	fn new(bytes: usize) -> Self {
+		Self { period_start: Instant::now(), bytes }
+	}
+
+	/// The bytes served during the current period, zero if it is over.
+	fn current(&self) -> usize {
+		if self.period_start.elapsed() < SERVING_QUOTA_PERIOD {
+			self.bytes
+		} else {
+			0
+		}
+	}
+
+	/// Account `bytes` served, starting a new period if the current one is over.
+	fn add(&mut self, bytes: usize) {
+		if self.period_start.elapsed() < SERVING_QUOTA_PERIOD {
+			self.bytes = self.bytes.saturating_add(bytes);
+		} else {
+			*self = Self::new(bytes);
+		}
+	}
+}
+
+/// How a block request is served.
+#[derive(Debug, PartialEq, Eq)]
+enum Serving {
+	/// The peer is within its quota.
+	Normal,
+	/// The peer exceeded its quota, but reciprocates, so it is served fewer blocks.
+	Deprioritized,
+	/// The peer exceeded its quota and never reciprocated.
+	Dropped,
+}
+
+/// Prometheus metrics of the [`BlockRequestHandler`].
+struct Metrics {
+	served_bytes: CounterVec<U64>,
+	dropped_requests: CounterVec<U64>,
+}
+
+impl Metrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			served_bytes: register(
+				CounterVec::new(
+					Opts::new(
+						"sync_block_requests_served_bytes",
+						"Number of bytes served in response to block requests, by peer class",
+					),
+					&["class"],
+				)?,
+				registry,
+			)?,
+			dropped_requests: register(
+				CounterVec::new(
+					Opts::new(
+						"sync_block_requests_dropped",
+						"Number of block requests dropped because of the serving quota, by peer class",
+					),
+					&["class"],
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
 /// The value of [`BlockRequestHandler::seen_requests`].
 enum SeenRequestsValue {
 	/// First time we have seen the request.
@@ -115,14 +251,26 @@ pub struct BlockRequestHandler<B: BlockT> {
 	///
 	/// This is used to check if a peer is spamming us with the same request.
 	seen_requests: LruCache<SeenRequestsKey<B>, SeenRequestsValue>,
+	/// The peers which have served us blocks.
+	reciprocity: Reciprocity,
+	/// The bytes served to each peer during the current period.
+	served_bytes: LruCache<PeerId, ServedBytes>,
+	/// The bytes served to all peers during the current period.
+	total_served_bytes: ServedBytes,
+	/// Prometheus metrics.
+	metrics: Option<Metrics>,
 }
 
 impl<B: BlockT> BlockRequestHandler<B> {
 	/// Create a new [`BlockRequestHandler`].
+	///
+	/// Peers noted in `reciprocity` get a larger serving quota.
 	pub fn new(
 		protocol_id: &ProtocolId,
 		client: Arc<dyn Client<B>>,
 		num_peer_hint: usize,
+		reciprocity: Reciprocity,
+		metrics_registry: Option<&Registry>,
 	) -> (Self, ProtocolConfig) {
 		// Reserve enough request slots for one request per peer when we are at the maximum
 		// number of peers.
@@ -132,8 +280,28 @@ impl<B: BlockT> BlockRequestHandler<B> {
 		protocol_config.inbound_queue = Some(tx);
 
 		let seen_requests = LruCache::new(num_peer_hint * 2);
-
-		(Self { client, request_receiver, seen_requests }, protocol_config)
+		let served_bytes = LruCache::new(num_peer_hint * 2);
+
+		let metrics = metrics_registry.and_then(|registry| {
+			Metrics::register(registry)
+				.map_err(
+					|e| log::warn!(target: LOG_TARGET, "Failed to register block request metrics: {}", e),
+				)
+				.ok()
+		});
+
+		(
+			Self {
+				client,
+				request_receiver,
+				seen_requests,
+				reciprocity,
+				served_bytes,
+				total_served_bytes: ServedBytes::new(0),
+				metrics,
+			},
+			protocol_config,
+		)
 	}
 
 	/// Run [`BlockRequestHandler`].
@@ -219,7 +387,15 @@ impl<B: BlockT> BlockRequestHandler<B> {
 			attributes,
 		);
 
-		let result = if reputation_change.is_none() {
+		let class = self.reciprocity.class(peer);
+		let serving = self.serving(peer, class);
+
+		let result = if reputation_change.is_none() && serving != Serving::Dropped {
+			let max_blocks = if serving == Serving::Deprioritized {
+				min(max_blocks, MAX_BLOCKS_IN_DEPRIORITIZED_RESPONSE)
+			} else {
+				max_blocks
+			};
 			let block_response = self.get_block_response(
 				attributes,
 				from_block_id,
@@ -245,9 +421,15 @@ impl<B: BlockT> BlockRequestHandler<B> {
 
 			let mut data = Vec::with_capacity(block_response.encoded_len());
 			block_response.encode(&mut data)?;
+			self.note_served(peer, class, data.len());
 
 			Ok(data)
 		} else {
+			if let Some(metrics) = &self.metrics {
+				if serving == Serving::Dropped {
+					metrics.dropped_requests.with_label_values(&[class.as_str()]).inc();
+				}
+			}
 			Err(())
 		};
 
@@ -260,6 +442,38 @@ impl<B: BlockT> BlockRequestHandler<B> {
 			.map_err(|_| HandleRequestError::SendResponse)
 	}
 
+	/// Determine how to serve the next request of `peer`, given the bytes served to it during
+	/// the current period. The quotas only apply when the handler is under load.
+	fn serving(&mut self, peer: &PeerId, class: PeerClass) -> Serving {
+		if self.total_served_bytes.current() < LOAD_THRESHOLD {
+			return Serving::Normal
+		}
+
+		let served = self.served_bytes.get(peer).map_or(0, ServedBytes::current);
+
+		match class {
+			PeerClass::Reciprocating if served >= RECIPROCATING_PEER_QUOTA =>
+				Serving::Deprioritized,
+			PeerClass::Leeching if served >= LEECHING_PEER_QUOTA => Serving::Dropped,
+			_ => Serving::Normal,
+		}
+	}
+
+	/// Account `bytes` served to `peer`.
+	fn note_served(&mut self, peer: &PeerId, class: PeerClass, bytes: usize) {
+		match self.served_bytes.get_mut(peer) {
+			Some(served) => served.add(bytes),
+			None => {
+				self.served_bytes.put(*peer, ServedBytes::new(bytes));
+			},
+		}
+		self.total_served_bytes.add(bytes);
+
+		if let Some(metrics) = &self.metrics {
+			metrics.served_bytes.with_label_values(&[class.as_str()]).inc_by(bytes as u64);
+		}
+	}
+
 	fn get_block_response(
 		&self,
 		attributes: BlockAttributes,
@@ -394,3 +608,53 @@ enum HandleRequestError {
 	#[display(fmt = "Failed to send response.")]
 	SendResponse,
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use substrate_test_runtime_client::runtime::Block;
+
+	fn handler(reciprocity: Reciprocity) -> BlockRequestHandler<Block> {
+		let client = Arc::new(substrate_test_runtime_client::new());
+		BlockRequestHandler::new(&ProtocolId::from("test"), client, 50, reciprocity, None).0
+	}
+
+	#[test]
+	fn quotas_only_apply_under_load() {
+		let reciprocity = Reciprocity::default();
+		let mut handler = handler(reciprocity.clone());
+		let leecher = PeerId::random();
+		let reciprocating = PeerId::random();
+		reciprocity.note_served(&reciprocating);
+
+		handler.note_served(&leecher, PeerClass::Leeching, RECIPROCATING_PEER_QUOTA);
+		handler.note_served(&reciprocating, PeerClass::Reciprocating, RECIPROCATING_PEER_QUOTA);
+		assert_eq!(handler.serving(&leecher, PeerClass::Leeching), Serving::Normal);
+		assert_eq!(handler.serving(&reciprocating, PeerClass::Reciprocating), Serving::Normal);
+
+		// the requests of other peers bring the handler under load.
+		handler.note_served(&PeerId::random(), PeerClass::Leeching, LOAD_THRESHOLD);
+		assert_eq!(handler.serving(&leecher, PeerClass::Leeching), Serving::Dropped);
+		assert_eq!(
+			handler.serving(&reciprocating, PeerClass::Reciprocating),
+			Serving::Deprioritized,
+		);
+		assert_eq!(handler.serving(&PeerId::random(), PeerClass::Leeching), Serving::Normal);
+	}
+
+	#[test]
+	fn quotas_depend_on_the_peer_class() {
+		let mut handler = handler(Reciprocity::default());
+		let leecher = PeerId::random();
+		let reciprocating = PeerId::random();
+		handler.note_served(&PeerId::random(), PeerClass::Leeching, LOAD_THRESHOLD);
+
+		handler.note_served(&leecher, PeerClass::Leeching, LEECHING_PEER_QUOTA - 1);
+		handler.note_served(&reciprocating, PeerClass::Reciprocating, LEECHING_PEER_QUOTA);
+		assert_eq!(handler.serving(&leecher, PeerClass::Leeching), Serving::Normal);
+		assert_eq!(handler.serving(&reciprocating, PeerClass::Reciprocating), Serving::Normal);
+
+		handler.note_served(&leecher, PeerClass::Leeching, 1);
+		assert_eq!(handler.serving(&leecher, PeerClass::Leeching), Serving::Dropped);
+	}
+}
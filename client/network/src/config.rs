@@ -22,6 +22,7 @@
 //! See the documentation of [`Params`].
 
 pub use crate::{
+	block_request_handler::Reciprocity,
 	chain::Client,
 	on_demand_layer::{AlwaysBadChecker, OnDemand},
 	request_responses::{
@@ -30,6 +31,7 @@ pub use crate::{
 	warp_request_handler::WarpSyncProvider,
 };
 pub use libp2p::{build_multiaddr, core::PublicKey, identity};
+pub use sc_peerset::ReputationConfig;
 
 // Note: this re-export shouldn't be part of the public API of the crate and will be removed in
 // the future.
@@ -121,6 +123,12 @@ pub struct Params<B: BlockT, H: ExHashT> {
 	/// incoming requests.
 	pub block_request_protocol_config: RequestResponseConfig,
 
+	/// The peers which have answered our block requests.
+	///
+	/// Should be shared with the [`crate::block_request_handler::BlockRequestHandler`], which
+	/// favours these peers when serving blocks.
+	pub block_request_reciprocity: Reciprocity,
+
 	/// Request response configuration for the light client request protocol.
 	///
 	/// Can be constructed either via
@@ -434,6 +442,8 @@ pub struct NetworkConfiguration {
 	pub max_parallel_downloads: u32,
 	/// Initial syncing mode.
 	pub sync_mode: SyncMode,
+	/// Decay rate of the reputation of peers, and when they get banned.
+	pub peer_reputation: ReputationConfig,
 
 	/// True if Kademlia random discovery should be enabled.
 	///
@@ -493,6 +503,7 @@ impl NetworkConfiguration {
 			transport: TransportConfig::Normal { enable_mdns: false, allow_private_ipv4: true },
 			max_parallel_downloads: 5,
 			sync_mode: SyncMode::Full,
+			peer_reputation: Default::default(),
 			enable_dht_random_walk: true,
 			allow_non_globals_in_dht: false,
 			kademlia_disjoint_query_paths: false,
@@ -501,6 +512,15 @@ impl NetworkConfiguration {
 		}
 	}
 
+	/// Register a request-response protocol, requests can then be sent with
+	/// [`NetworkService::request`](crate::NetworkService::request) or through the
+	/// [`NetworkRequest`](crate::NetworkRequest) trait.
+	///
+	/// Starting the network fails if two protocols are registered with the same name.
+	pub fn add_request_response_protocol(&mut self, protocol: RequestResponseConfig) {
+		self.request_response_protocols.push(protocol);
+	}
+
 	/// Create new default configuration for localhost-only connection with random port (useful for
 	/// testing)
 	pub fn new_local() -> NetworkConfiguration {
@@ -97,6 +97,8 @@ fn build_test_full_node(network_config: config::NetworkConfiguration)
 			&protocol_id,
 			client.clone(),
 			50,
+			Default::default(),
+			None,
 		);
 		async_std::task::spawn(handler.run().boxed());
 		protocol_config
@@ -107,6 +109,7 @@ fn build_test_full_node(network_config: config::NetworkConfiguration)
 			&protocol_id,
 			client.clone(),
 			50,
+			None,
 		);
 		async_std::task::spawn(handler.run().boxed());
 		protocol_config
@@ -136,6 +139,7 @@ fn build_test_full_node(network_config: config::NetworkConfiguration)
 		),
 		metrics_registry: None,
 		block_request_protocol_config,
+		block_request_reciprocity: Default::default(),
 		state_request_protocol_config,
 		light_client_request_protocol_config,
 		warp_sync: None,
@@ -271,15 +271,17 @@ pub use libp2p::{multiaddr, Multiaddr, PeerId};
 pub use protocol::{
 	event::{DhtEvent, Event, ObservedRole},
 	sync::{StateDownloadProgress, SyncState, WarpSyncPhase, WarpSyncProgress},
-	PeerInfo,
+	PeerDetails, PeerInfo, PeerProtocol,
 };
 pub use service::{
 	IfDisconnected, NetworkService, NetworkWorker, NotificationSender, NotificationSenderReady,
-	OutboundFailure, RequestFailure,
+	OutboundFailure, RequestFailure, Signature, SigningError,
 };
 
+use futures::channel::oneshot;
 pub use sc_peerset::ReputationChange;
 use sp_runtime::traits::{Block as BlockT, NumberFor};
+use std::borrow::Cow;
 
 /// The maximum allowed number of established connections per peer.
 ///
@@ -308,6 +310,39 @@ pub trait NetworkStateInfo {
 	fn local_peer_id(&self) -> PeerId;
 }
 
+/// Trait for sending requests over the request-response protocols registered in
+/// [`NetworkConfiguration::request_response_protocols`](
+/// crate::config::NetworkConfiguration::request_response_protocols).
+///
+/// Allows downstream protocols to send requests without depending on the type of the
+/// [`NetworkService`].
+#[async_trait::async_trait]
+pub trait NetworkRequest {
+	/// Sends a single targeted request to a specific peer. On success, returns the response of
+	/// the peer.
+	///
+	/// See [`NetworkService::request`].
+	async fn request(
+		&self,
+		target: PeerId,
+		protocol: Cow<'static, str>,
+		request: Vec<u8>,
+		connect: IfDisconnected,
+	) -> Result<Vec<u8>, RequestFailure>;
+
+	/// Starts a request whose response is delivered on the provided channel.
+	///
+	/// See [`NetworkService::start_request`].
+	fn start_request(
+		&self,
+		target: PeerId,
+		protocol: Cow<'static, str>,
+		request: Vec<u8>,
+		tx: oneshot::Sender<Result<Vec<u8>, RequestFailure>>,
+		connect: IfDisconnected,
+	);
+}
+
 /// Overview status of the network.
 #[derive(Clone)]
 pub struct NetworkStatus<B: BlockT> {
@@ -17,6 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
+	block_request_handler::Reciprocity,
 	chain::Client,
 	config::{self, ProtocolId, WarpSyncProvider},
 	error,
@@ -176,6 +177,8 @@ pub struct Protocol<B: BlockT> {
 	peerset_handle: sc_peerset::PeersetHandle,
 	/// Handles opening the unique substream and sending and receiving raw messages.
 	behaviour: Notifications,
+	/// Name of the block announces protocol, associated to the default peer set.
+	block_announces_protocol: Cow<'static, str>,
 	/// List of notifications protocols that have been registered.
 	notification_protocols: Vec<Cow<'static, str>>,
 	/// If we receive a new "substream open" event that contains an invalid handshake, we ask the
@@ -191,6 +194,8 @@ pub struct Protocol<B: BlockT> {
 	boot_node_ids: HashSet<PeerId>,
 	/// A cache for the data that was associated to a block announcement.
 	block_announce_data_cache: lru::LruCache<B::Hash, Vec<u8>>,
+	/// The peers which have answered our block requests.
+	reciprocity: Reciprocity,
 }
 
 #[derive(Debug)]
@@ -221,6 +226,29 @@ pub struct PeerInfo<B: BlockT> {
 	pub best_number: <B::Header as HeaderT>::Number,
 }
 
+/// State of a peer in the peer set manager, across all the notifications protocols.
+#[derive(Clone, Debug)]
+pub struct PeerDetails<B: BlockT> {
+	/// Reputation of the peer. Reputations are global and not specific to a protocol.
+	pub reputation: i32,
+	/// Known state of the peer, if it is connected on the block announces protocol.
+	pub info: Option<PeerInfo<B>>,
+	/// What we are currently syncing from the peer, if it is connected on the block announces
+	/// protocol.
+	pub sync_state: Option<String>,
+	/// Notifications protocols we have an open substream with the peer on.
+	pub protocols: Vec<PeerProtocol>,
+}
+
+/// State of a peer on a notifications protocol.
+#[derive(Clone, Debug)]
+pub struct PeerProtocol {
+	/// Name of the notifications protocol.
+	pub name: Cow<'static, str>,
+	/// Whether the peer is a reserved peer of the protocol.
+	pub reserved: bool,
+}
+
 /// Configuration for the Substrate-specific part of the networking layer.
 #[derive(Clone)]
 pub struct ProtocolConfig {
@@ -288,6 +316,7 @@ impl<B: BlockT> Protocol<B> {
 		block_announce_validator: Box<dyn BlockAnnounceValidator<B> + Send>,
 		metrics_registry: Option<&Registry>,
 		warp_sync_provider: Option<Arc<dyn WarpSyncProvider<B>>>,
+		reciprocity: Reciprocity,
 	) -> error::Result<(Protocol<B>, sc_peerset::PeersetHandle, Vec<(PeerId, Multiaddr)>)> {
 		let info = chain.info();
 		let sync = ChainSync::new(
@@ -371,7 +400,10 @@ impl<B: BlockT> Protocol<B> {
 				});
 			}
 
-			sc_peerset::Peerset::from_config(sc_peerset::PeersetConfig { sets })
+			sc_peerset::Peerset::from_config(sc_peerset::PeersetConfig {
+				sets,
+				reputation: network_config.peer_reputation.clone(),
+			})
 		};
 
 		let block_announces_protocol: Cow<'static, str> = Cow::from({
@@ -392,7 +424,7 @@ impl<B: BlockT> Protocol<B> {
 					.encode();
 
 			let sync_protocol_config = notifications::ProtocolConfig {
-				name: block_announces_protocol,
+				name: block_announces_protocol.clone(),
 				fallback_names: Vec::new(),
 				handshake: block_announces_handshake,
 				max_notification_size: MAX_BLOCK_ANNOUNCE_SIZE,
@@ -429,6 +461,7 @@ impl<B: BlockT> Protocol<B> {
 			important_peers,
 			peerset_handle: peerset_handle.clone(),
 			behaviour,
+			block_announces_protocol,
 			notification_protocols: network_config
 				.extra_sets
 				.iter()
@@ -442,6 +475,7 @@ impl<B: BlockT> Protocol<B> {
 			},
 			boot_node_ids,
 			block_announce_data_cache,
+			reciprocity,
 		};
 
 		Ok((protocol, peerset_handle, known_addresses))
@@ -481,6 +515,55 @@ impl<B: BlockT> Protocol<B> {
 		self.behaviour.peerset_debug_info()
 	}
 
+	/// Returns the state of all the peers we have an open channel to, on each of the
+	/// notifications protocols.
+	pub fn peers_details(&mut self) -> Vec<(PeerId, PeerDetails<B>)> {
+		let open_peers = self.behaviour.open_peers().cloned().collect::<HashSet<_>>();
+		let sets = iter::once(&self.block_announces_protocol)
+			.chain(self.notification_protocols.iter())
+			.enumerate()
+			.map(|(index, name)| (sc_peerset::SetId::from(index), name))
+			.collect::<Vec<_>>();
+
+		let mut details = Vec::with_capacity(open_peers.len());
+		for peer_id in open_peers {
+			let protocols = sets
+				.iter()
+				.filter(|(set_id, _)| self.behaviour.is_open(&peer_id, *set_id))
+				.map(|(set_id, name)| PeerProtocol {
+					name: (*name).clone(),
+					reserved: self.behaviour.reserved_peers(*set_id).any(|p| *p == peer_id),
+				})
+				.collect();
+
+			details.push((
+				peer_id,
+				PeerDetails {
+					reputation: self.behaviour.peer_reputation(&peer_id).unwrap_or(0),
+					info: self.peers.get(&peer_id).map(|peer| peer.info.clone()),
+					sync_state: self
+						.sync
+						.peer_sync_state(&peer_id)
+						.map(|state| format!("{:?}", state)),
+					protocols,
+				},
+			));
+		}
+
+		details
+	}
+
+	/// Returns the reputation of the given peer, or `None` if the peerset doesn't know it.
+	pub fn peer_reputation(&mut self, peer_id: &PeerId) -> Option<i32> {
+		self.behaviour.peer_reputation(peer_id)
+	}
+
+	/// Returns true if `protocol` is one of the registered notifications protocols, excluding
+	/// the block announces one.
+	pub fn is_notifications_protocol(&self, protocol: &str) -> bool {
+		self.notification_protocols.iter().any(|p| *p == protocol)
+	}
+
 	/// Returns the number of peers we're connected to.
 	pub fn num_connected_peers(&self) -> usize {
 		self.peers.values().count()
@@ -655,6 +738,10 @@ impl<B: BlockT> Protocol<B> {
 			},
 		};
 
+		if !blocks.is_empty() {
+			self.reciprocity.note_served(&peer_id);
+		}
+
 		let block_response = message::BlockResponse::<B> { id: request.id, blocks };
 
 		let blocks_range = || match (
@@ -625,6 +625,11 @@ impl Notifications {
 		self.peerset.debug_info()
 	}
 
+	/// Returns the reputation of the given peer in the peerset manager, if it is known.
+	pub fn peer_reputation(&mut self, peer_id: &PeerId) -> Option<i32> {
+		self.peerset.peer_reputation(peer_id)
+	}
+
 	/// Function that is called when the peerset wants us to connect to a peer.
 	fn peerset_report_connect(&mut self, peer_id: PeerId, set_id: sc_peerset::SetId) {
 		// If `PeerId` is unknown to us, insert an entry, start dialing, and return early.
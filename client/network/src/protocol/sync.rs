@@ -61,6 +61,7 @@ use std::{
 	ops::Range,
 	pin::Pin,
 	sync::Arc,
+	time::Duration,
 };
 use warp::{WarpProofRequest, WarpSync, WarpSyncProvider};
 
@@ -93,6 +94,12 @@ const MAX_CONCURRENT_BLOCK_ANNOUNCE_VALIDATIONS: usize = 256;
 /// See [`MAX_CONCURRENT_BLOCK_ANNOUNCE_VALIDATIONS`] for more information.
 const MAX_CONCURRENT_BLOCK_ANNOUNCE_VALIDATIONS_PER_PEER: usize = 4;
 
+/// Maximum duration of a block announce validation.
+///
+/// Validations taking longer are ignored, to release the slot of the peer which sent the block
+/// announcement. The peer isn't punished, as it isn't responsible for the delay.
+const BLOCK_ANNOUNCE_VALIDATION_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// Pick the state to sync as the latest finalized number minus this.
 const STATE_SYNC_FINALITY_THRESHOLD: u32 = 8;
 
@@ -217,6 +224,8 @@ pub struct ChainSync<B: BlockT> {
 		FuturesUnordered<Pin<Box<dyn Future<Output = PreValidateBlockAnnounce<B::Header>> + Send>>>,
 	/// Stats per peer about the number of concurrent block announce validations.
 	block_announce_validation_per_peer_stats: HashMap<PeerId, usize>,
+	/// Maximum duration of a block announce validation.
+	block_announce_validation_timeout: Duration,
 	/// State sync in progress, if any.
 	state_sync: Option<StateSync<B>>,
 	/// Warp sync in progress, if any.
@@ -551,6 +560,7 @@ impl<B: BlockT> ChainSync<B> {
 			downloaded_blocks: 0,
 			block_announce_validation: Default::default(),
 			block_announce_validation_per_peer_stats: Default::default(),
+			block_announce_validation_timeout: BLOCK_ANNOUNCE_VALIDATION_TIMEOUT,
 			state_sync: None,
 			warp_sync: None,
 			warp_sync_provider,
@@ -592,6 +602,13 @@ impl<B: BlockT> ChainSync<B> {
 			.map(|p| PeerInfo { best_hash: p.best_hash, best_number: p.best_number })
 	}
 
+	/// Returns what we are currently syncing from the given peer.
+	///
+	/// Returns `None` if the peer is unknown.
+	pub fn peer_sync_state(&self, who: &PeerId) -> Option<PeerSyncState<B>> {
+		self.peers.get(who).map(|p| p.state.clone())
+	}
+
 	/// Returns the current sync status.
 	pub fn status(&self) -> Status<B> {
 		let best_seen = self.peers.values().map(|p| p.best_number).max();
@@ -1532,6 +1549,11 @@ impl<B: BlockT> ChainSync<B> {
 					// Don't mark it as bad as it still may be synced if explicitly requested.
 					trace!(target: "sync", "Obsolete block {:?}", hash);
 				},
+				Err(BlockImportError::Stale) => {
+					// Honest peers may still announce blocks of forks abandoned long ago, don't
+					// punish them.
+					trace!(target: "sync", "Stale block {:?}", hash);
+				},
 				e @ Err(BlockImportError::UnknownParent) | e @ Err(BlockImportError::Other(_)) => {
 					warn!(target: "sync", "💔 Error importing block {:?}: {:?}", hash, e);
 					self.state_sync = None;
@@ -1736,11 +1758,25 @@ impl<B: BlockT> ChainSync<B> {
 		// Let external validator check the block announcement.
 		let assoc_data = announce.data.as_ref().map_or(&[][..], |v| v.as_slice());
 		let future = self.block_announce_validator.validate(&header, assoc_data);
+		let timeout = futures_timer::Delay::new(self.block_announce_validation_timeout);
 		let hash = hash.clone();
 
 		self.block_announce_validation.push(
 			async move {
-				match future.await {
+				let result = match futures::future::select(future, timeout).await {
+					futures::future::Either::Left((result, _)) => result,
+					futures::future::Either::Right(_) => {
+						debug!(
+							target: "sync",
+							"💔 Block announcement validation of block {:?} from {} timed out",
+							hash,
+							who,
+						);
+						return PreValidateBlockAnnounce::Error { who }
+					},
+				};
+
+				match result {
 					Ok(Validation::Success { is_new_best }) => PreValidateBlockAnnounce::Process {
 						is_new_best: is_new_best || is_best,
 						announce,
@@ -2570,6 +2606,52 @@ mod test {
 		assert_eq!(sync.peers.get(&peer_id3).unwrap().common_number, 50);
 	}
 
+	#[test]
+	fn block_announce_validation_times_out() {
+		struct PendingBlockAnnounceValidator;
+
+		impl BlockAnnounceValidator<Block> for PendingBlockAnnounceValidator {
+			fn validate(
+				&mut self,
+				_: &Header,
+				_: &[u8],
+			) -> Pin<
+				Box<
+					dyn Future<Output = Result<Validation, Box<dyn std::error::Error + Send>>>
+						+ Send,
+				>,
+			> {
+				futures::future::pending().boxed()
+			}
+		}
+
+		let mut client = Arc::new(TestClientBuilder::new().build());
+		let peer_id = PeerId::random();
+		let mut sync = ChainSync::new(
+			SyncMode::Full,
+			client.clone(),
+			Box::new(PendingBlockAnnounceValidator),
+			1,
+			None,
+		)
+		.unwrap();
+		sync.block_announce_validation_timeout = Duration::from_millis(10);
+
+		let block = build_block(&mut client, None, false);
+		let announce = BlockAnnounce {
+			header: block.header().clone(),
+			state: Some(BlockState::Best),
+			data: Some(Vec::new()),
+		};
+		sync.push_block_announce_validation(peer_id.clone(), block.hash(), announce, true);
+		assert_eq!(sync.block_announce_validation_per_peer_stats.get(&peer_id), Some(&1));
+
+		// The validation is ignored and the slot of the peer is released.
+		let result = block_on(poll_fn(|cx| sync.poll_block_announce_validation(cx)));
+		assert_eq!(result, PollBlockAnnounceValidation::Skip);
+		assert!(sync.block_announce_validation_per_peer_stats.is_empty());
+	}
+
 	/// Send a block annoucnement for the given `header`.
 	fn send_block_announce(header: Header, peer_id: &PeerId, sync: &mut ChainSync<Block>) {
 		let block_annnounce = BlockAnnounce {
@@ -43,9 +43,10 @@ use crate::{
 		event::Event,
 		message::generic::Roles,
 		sync::{Status as SyncStatus, SyncState},
-		NotificationsSink, NotifsHandlerError, PeerInfo, Protocol, Ready,
+		NotificationsSink, NotifsHandlerError, PeerDetails, PeerInfo, Protocol, Ready,
 	},
-	transactions, transport, DhtEvent, ExHashT, NetworkStateInfo, NetworkStatus, ReputationChange,
+	transactions, transport, DhtEvent, ExHashT, NetworkRequest, NetworkStateInfo, NetworkStatus,
+	ReputationChange,
 };
 
 use codec::Encode as _;
@@ -56,6 +57,7 @@ use libp2p::{
 		either::EitherError,
 		upgrade, ConnectedPoint, Executor,
 	},
+	identity::Keypair,
 	kad::record,
 	multiaddr,
 	ping::handler::PingFailure,
@@ -92,9 +94,11 @@ use std::{
 pub use behaviour::{
 	IfDisconnected, InboundFailure, OutboundFailure, RequestFailure, ResponseFailure,
 };
+pub use signature::{Signature, SigningError};
 
 mod metrics;
 mod out_events;
+mod signature;
 #[cfg(test)]
 mod tests;
 
@@ -108,6 +112,8 @@ pub struct NetworkService<B: BlockT + 'static, H: ExHashT> {
 	is_major_syncing: Arc<AtomicBool>,
 	/// Local copy of the `PeerId` of the local node.
 	local_peer_id: PeerId,
+	/// The `KeyPair` that defines the `PeerId` of the local node.
+	local_identity: Keypair,
 	/// Bandwidth logging system. Can be queried to know the average bandwidth consumed.
 	bandwidth: Arc<transport::BandwidthSinks>,
 	/// Peerset manager (PSM); manages the reputation of nodes and indicates the network which
@@ -210,6 +216,7 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkWorker<B, H> {
 			params.block_announce_validator,
 			params.metrics_registry.as_ref(),
 			warp_sync_provider,
+			params.block_request_reciprocity,
 		)?;
 
 		// List of multiaddresses that we know in the network.
@@ -334,7 +341,7 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkWorker<B, H> {
 				};
 
 				transport::build_transport(
-					local_identity,
+					local_identity.clone(),
 					config_mem,
 					params.network_config.yamux_window_size,
 					yamux_maximum_buffer_size,
@@ -422,6 +429,7 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkWorker<B, H> {
 			is_major_syncing: is_major_syncing.clone(),
 			peerset: peerset_handle,
 			local_peer_id,
+			local_identity,
 			to_worker,
 			peers_notifications_sinks: peers_notifications_sinks.clone(),
 			notifications_sizes_metric: metrics
@@ -676,6 +684,47 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkWorker<B, H> {
 	pub fn reserved_peers(&self) -> impl Iterator<Item = &PeerId> {
 		self.network_service.behaviour().user_protocol().reserved_peers()
 	}
+
+	/// Get the state of the currently connected peers on each notifications protocol.
+	pub fn peers_details(&mut self) -> Vec<(PeerId, PeerDetails<B>)> {
+		self.network_service.behaviour_mut().user_protocol_mut().peers_details()
+	}
+
+	/// Returns the reputation of the given peer, or `None` if the peerset doesn't know it.
+	pub fn peer_reputation(&mut self, peer_id: &PeerId) -> Option<i32> {
+		self.network_service
+			.behaviour_mut()
+			.user_protocol_mut()
+			.peer_reputation(peer_id)
+	}
+
+	/// Returns true if `protocol` is one of the registered notifications protocols, excluding
+	/// the block announces one.
+	pub fn is_notifications_protocol(&self, protocol: &str) -> bool {
+		self.network_service
+			.behaviour()
+			.user_protocol()
+			.is_notifications_protocol(protocol)
+	}
+
+	/// Adds a `PeerId` and its address as reserved on the given notifications protocol. The
+	/// string should encode the address and peer ID of the remote node.
+	pub fn add_set_reserved_peer(
+		&self,
+		protocol: Cow<'static, str>,
+		peer: String,
+	) -> Result<(), String> {
+		let addr = peer.parse::<Multiaddr>().map_err(|e| e.to_string())?;
+		self.service.add_peers_to_reserved_set(protocol, iter::once(addr).collect())
+	}
+
+	/// Removes a `PeerId` from the reserved peers of the given notifications protocol.
+	pub fn remove_set_reserved_peer(&self, protocol: Cow<'static, str>, peer: PeerId) {
+		self.network_service
+			.behaviour()
+			.user_protocol()
+			.remove_set_reserved_peer(protocol, peer);
+	}
 }
 
 impl<B: BlockT + 'static, H: ExHashT> NetworkService<B, H> {
@@ -684,6 +733,14 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkService<B, H> {
 		&self.local_peer_id
 	}
 
+	/// Signs the message with the `KeyPair` that defines the local `PeerId`.
+	pub fn sign_with_local_identity(
+		&self,
+		msg: impl AsRef<[u8]>,
+	) -> Result<Signature, SigningError> {
+		Signature::sign_message(msg.as_ref(), &self.local_identity)
+	}
+
 	/// Set authorized peers.
 	///
 	/// Need a better solution to manage authorized peers, but now just use reserved peers for
@@ -774,9 +831,9 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkService<B, H> {
 	/// a receiver. With a `NotificationSender` at hand, sending a notification is done in two
 	/// steps:
 	///
-	/// 1.  [`NotificationSender::ready`] is used to wait for the sender to become ready
+	/// 1. [`NotificationSender::ready`] is used to wait for the sender to become ready
 	/// for another notification, yielding a [`NotificationSenderReady`] token.
-	/// 2.  [`NotificationSenderReady::send`] enqueues the notification for sending. This operation
+	/// 2. [`NotificationSenderReady::send`] enqueues the notification for sending. This operation
 	/// can only fail if the underlying notification substream or connection has suddenly closed.
 	///
 	/// An error is returned by [`NotificationSenderReady::send`] if there exists no open
@@ -1302,6 +1359,34 @@ where
 	}
 }
 
+#[async_trait::async_trait]
+impl<B, H> NetworkRequest for NetworkService<B, H>
+where
+	B: BlockT + 'static,
+	H: ExHashT,
+{
+	async fn request(
+		&self,
+		target: PeerId,
+		protocol: Cow<'static, str>,
+		request: Vec<u8>,
+		connect: IfDisconnected,
+	) -> Result<Vec<u8>, RequestFailure> {
+		NetworkService::request(self, target, protocol, request, connect).await
+	}
+
+	fn start_request(
+		&self,
+		target: PeerId,
+		protocol: Cow<'static, str>,
+		request: Vec<u8>,
+		tx: oneshot::Sender<Result<Vec<u8>, RequestFailure>>,
+		connect: IfDisconnected,
+	) {
+		NetworkService::start_request(self, target, protocol, request, tx, connect)
+	}
+}
+
 /// A `NotificationSender` allows for sending notifications to a peer with a chosen protocol.
 #[must_use]
 pub struct NotificationSender {
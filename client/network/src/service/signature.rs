@@ -0,0 +1,56 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Signatures made with the key of the local node.
+
+use libp2p::{
+	identity::{Keypair, PublicKey},
+	PeerId,
+};
+
+pub use libp2p::identity::error::SigningError;
+
+/// A result of signing a message with the network identity of a node.
+///
+/// Since a `PeerId` is potentially a hash of a `PublicKey`, the `PublicKey` is revealed next to
+/// the signature, so that the verifier can check that the signature was made by the node that
+/// controls a given `PeerId`.
+pub struct Signature {
+	/// The public key derived from the network identity that signed the message.
+	pub public_key: PublicKey,
+	/// The signature of the message.
+	pub bytes: Vec<u8>,
+}
+
+impl Signature {
+	/// Sign a message with the given network identity.
+	pub fn sign_message(
+		message: impl AsRef<[u8]>,
+		keypair: &Keypair,
+	) -> Result<Self, SigningError> {
+		let public_key = keypair.public();
+		let bytes = keypair.sign(message.as_ref())?;
+		Ok(Self { public_key, bytes })
+	}
+
+	/// Verify that the signature was made for `message` by the node that controls `peer_id`.
+	pub fn verify(&self, message: impl AsRef<[u8]>, peer_id: &PeerId) -> bool {
+		*peer_id == self.public_key.clone().into_peer_id() &&
+			self.public_key.verify(message.as_ref(), &self.bytes)
+	}
+}
@@ -19,7 +19,8 @@
 use crate::{
 	block_request_handler::BlockRequestHandler, config,
 	light_client_requests::handler::LightClientRequestHandler,
-	state_request_handler::StateRequestHandler, Event, NetworkService, NetworkWorker,
+	state_request_handler::StateRequestHandler, Event, IfDisconnected, NetworkRequest,
+	NetworkService, NetworkWorker,
 };
 
 use futures::prelude::*;
@@ -90,13 +91,15 @@ fn build_test_full_node(
 	let protocol_id = config::ProtocolId::from("/test-protocol-name");
 
 	let block_request_protocol_config = {
-		let (handler, protocol_config) = BlockRequestHandler::new(&protocol_id, client.clone(), 50);
+		let (handler, protocol_config) =
+			BlockRequestHandler::new(&protocol_id, client.clone(), 50, Default::default(), None);
 		async_std::task::spawn(handler.run().boxed());
 		protocol_config
 	};
 
 	let state_request_protocol_config = {
-		let (handler, protocol_config) = StateRequestHandler::new(&protocol_id, client.clone(), 50);
+		let (handler, protocol_config) =
+			StateRequestHandler::new(&protocol_id, client.clone(), 50, None);
 		async_std::task::spawn(handler.run().boxed());
 		protocol_config
 	};
@@ -125,6 +128,7 @@ fn build_test_full_node(
 		),
 		metrics_registry: None,
 		block_request_protocol_config,
+		block_request_reciprocity: Default::default(),
 		state_request_protocol_config,
 		light_client_request_protocol_config,
 		warp_sync: None,
@@ -539,6 +543,67 @@ fn fallback_name_working() {
 	});
 }
 
+#[test]
+fn custom_request_response_protocol_works() {
+	const ECHO_PROTOCOL_NAME: Cow<'static, str> = Cow::Borrowed("/test/echo/1");
+
+	let listen_addr = config::build_multiaddr![Memory(rand::random::<u64>())];
+	let (inbound_tx, mut inbound_rx) = futures::channel::mpsc::channel(16);
+
+	let mut config1 = config::NetworkConfiguration {
+		listen_addresses: vec![listen_addr.clone()],
+		transport: config::TransportConfig::MemoryOnly,
+		..config::NetworkConfiguration::new_local()
+	};
+	config1.add_request_response_protocol(config::RequestResponseConfig {
+		name: ECHO_PROTOCOL_NAME,
+		max_request_size: 1024,
+		max_response_size: 1024,
+		request_timeout: Duration::from_secs(10),
+		inbound_queue: Some(inbound_tx),
+	});
+	let (node1, _) = build_test_full_node(config1);
+
+	let mut config2 = config::NetworkConfiguration {
+		boot_nodes: vec![config::MultiaddrWithPeerId {
+			multiaddr: listen_addr,
+			peer_id: node1.local_peer_id().clone(),
+		}],
+		listen_addresses: vec![],
+		transport: config::TransportConfig::MemoryOnly,
+		..config::NetworkConfiguration::new_local()
+	};
+	config2.add_request_response_protocol(config::RequestResponseConfig {
+		name: ECHO_PROTOCOL_NAME,
+		max_request_size: 1024,
+		max_response_size: 1024,
+		request_timeout: Duration::from_secs(10),
+		inbound_queue: None,
+	});
+	let (node2, _) = build_test_full_node(config2);
+
+	async_std::task::spawn(async move {
+		while let Some(request) = inbound_rx.next().await {
+			let _ = request.pending_response.send(config::OutgoingResponse {
+				result: Ok(request.payload),
+				reputation_changes: Vec::new(),
+				sent_feedback: None,
+			});
+		}
+	});
+
+	// requests are sent without knowing the concrete type of the network service.
+	let network: Arc<dyn NetworkRequest + Send + Sync> = node2;
+	let response = async_std::task::block_on(network.request(
+		node1.local_peer_id().clone(),
+		ECHO_PROTOCOL_NAME,
+		b"hello".to_vec(),
+		IfDisconnected::TryConnect,
+	));
+
+	assert_eq!(response.unwrap(), b"hello".to_vec());
+}
+
 #[test]
 #[should_panic(expected = "don't match the transport")]
 fn ensure_listen_addresses_consistent_with_transport_memory() {
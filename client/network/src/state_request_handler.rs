@@ -31,12 +31,15 @@ use futures::{
 };
 use log::{debug, trace};
 use lru::LruCache;
+use prometheus_endpoint::{
+	register, Counter, Histogram, HistogramOpts, PrometheusError, Registry, U64,
+};
 use prost::Message;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use std::{
 	hash::{Hash, Hasher},
 	sync::Arc,
-	time::Duration,
+	time::{Duration, Instant},
 };
 
 const LOG_TARGET: &str = "sync";
@@ -86,6 +89,41 @@ impl<B: BlockT> Hash for SeenRequestsKey<B> {
 	}
 }
 
+/// Prometheus metrics of the [`StateRequestHandler`].
+struct Metrics {
+	requests: Counter<U64>,
+	served_bytes: Counter<U64>,
+	generation_time: Histogram,
+}
+
+impl Metrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			requests: register(
+				Counter::new("sync_state_requests_served", "Number of state requests served")?,
+				registry,
+			)?,
+			served_bytes: register(
+				Counter::new(
+					"sync_state_requests_served_bytes",
+					"Number of bytes served in response to state requests",
+				)?,
+				registry,
+			)?,
+			generation_time: register(
+				Histogram::with_opts(
+					HistogramOpts::new(
+						"sync_state_response_generation_time",
+						"Time taken to generate the responses to state requests, in seconds",
+					)
+					.buckets(vec![0.001, 0.005, 0.025, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0]),
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
 /// The value of [`StateRequestHandler::seen_requests`].
 enum SeenRequestsValue {
 	/// First time we have seen the request.
@@ -102,6 +140,8 @@ pub struct StateRequestHandler<B: BlockT> {
 	///
 	/// This is used to check if a peer is spamming us with the same request.
 	seen_requests: LruCache<SeenRequestsKey<B>, SeenRequestsValue>,
+	/// Prometheus metrics.
+	metrics: Option<Metrics>,
 }
 
 impl<B: BlockT> StateRequestHandler<B> {
@@ -110,6 +150,7 @@ impl<B: BlockT> StateRequestHandler<B> {
 		protocol_id: &ProtocolId,
 		client: Arc<dyn Client<B>>,
 		num_peer_hint: usize,
+		metrics_registry: Option<&Registry>,
 	) -> (Self, ProtocolConfig) {
 		// Reserve enough request slots for one request per peer when we are at the maximum
 		// number of peers.
@@ -120,7 +161,15 @@ impl<B: BlockT> StateRequestHandler<B> {
 
 		let seen_requests = LruCache::new(num_peer_hint * 2);
 
-		(Self { client, request_receiver, seen_requests }, protocol_config)
+		let metrics = metrics_registry.and_then(|registry| {
+			Metrics::register(registry)
+				.map_err(
+					|e| log::warn!(target: LOG_TARGET, "Failed to register state request metrics: {}", e),
+				)
+				.ok()
+		});
+
+		(Self { client, request_receiver, seen_requests, metrics }, protocol_config)
 	}
 
 	/// Run [`StateRequestHandler`].
@@ -176,40 +225,8 @@ impl<B: BlockT> StateRequestHandler<B> {
 		);
 
 		let result = if reputation_changes.is_empty() {
-			let mut response = StateResponse::default();
-
-			if !request.no_proof {
-				let (proof, count) = self.client.read_proof_collection(
-					&BlockId::hash(block),
-					&request.start,
-					MAX_RESPONSE_BYTES,
-				)?;
-				response.proof = proof.encode();
-				if count == 0 {
-					response.complete = true;
-				}
-			} else {
-				let entries = self.client.storage_collection(
-					&BlockId::hash(block),
-					&request.start,
-					MAX_RESPONSE_BYTES,
-				)?;
-				response.entries =
-					entries.into_iter().map(|(key, value)| StateEntry { key, value }).collect();
-				if response.entries.is_empty() {
-					response.complete = true;
-				}
-			}
+			let data = self.response(block, &request)?;
 
-			trace!(
-				target: LOG_TARGET,
-				"StateResponse contains {} keys, {}, proof nodes, complete={}, from {:?} to {:?}",
-				response.entries.len(),
-				response.proof.len(),
-				response.complete,
-				response.entries.first().map(|e| sp_core::hexdisplay::HexDisplay::from(&e.key)),
-				response.entries.last().map(|e| sp_core::hexdisplay::HexDisplay::from(&e.key)),
-			);
 			if let Some(value) = self.seen_requests.get_mut(&key) {
 				// If this is the first time we have processed this request, we need to change
 				// it to `Fulfilled`.
@@ -218,8 +235,10 @@ impl<B: BlockT> StateRequestHandler<B> {
 				}
 			}
 
-			let mut data = Vec::with_capacity(response.encoded_len());
-			response.encode(&mut data)?;
+			if let Some(metrics) = &self.metrics {
+				metrics.served_bytes.inc_by(data.len() as u64);
+			}
+
 			Ok(data)
 		} else {
 			Err(())
@@ -229,6 +248,64 @@ impl<B: BlockT> StateRequestHandler<B> {
 			.send(OutgoingResponse { result, reputation_changes, sent_feedback: None })
 			.map_err(|_| HandleRequestError::SendResponse)
 	}
+
+	/// The encoded response to the given request.
+	///
+	/// The range is read from the state lazily, starting at the requested key, and reading stops
+	/// once `MAX_RESPONSE_BYTES` have been collected. Requests are handled one at a time and
+	/// nothing is kept once a response is sent, so the memory used to serve state requests is
+	/// bounded by the size of a single response.
+	fn response(
+		&self,
+		block: B::Hash,
+		request: &StateRequest,
+	) -> Result<Vec<u8>, HandleRequestError> {
+		let started = Instant::now();
+		let mut response = StateResponse::default();
+
+		if !request.no_proof {
+			let (proof, count) = self.client.read_proof_collection(
+				&BlockId::hash(block),
+				&request.start,
+				MAX_RESPONSE_BYTES,
+			)?;
+			response.proof = proof.encode();
+			if count == 0 {
+				response.complete = true;
+			}
+		} else {
+			let entries = self.client.storage_collection(
+				&BlockId::hash(block),
+				&request.start,
+				MAX_RESPONSE_BYTES,
+			)?;
+			response.entries =
+				entries.into_iter().map(|(key, value)| StateEntry { key, value }).collect();
+			if response.entries.is_empty() {
+				response.complete = true;
+			}
+		}
+
+		trace!(
+			target: LOG_TARGET,
+			"StateResponse contains {} keys, {}, proof nodes, complete={}, from {:?} to {:?}",
+			response.entries.len(),
+			response.proof.len(),
+			response.complete,
+			response.entries.first().map(|e| sp_core::hexdisplay::HexDisplay::from(&e.key)),
+			response.entries.last().map(|e| sp_core::hexdisplay::HexDisplay::from(&e.key)),
+		);
+
+		let mut data = Vec::with_capacity(response.encoded_len());
+		response.encode(&mut data)?;
+
+		if let Some(metrics) = &self.metrics {
+			metrics.requests.inc();
+			metrics.generation_time.observe(started.elapsed().as_secs_f64());
+		}
+
+		Ok(data)
+	}
 }
 
 #[derive(derive_more::Display, derive_more::From)]
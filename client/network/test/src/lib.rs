@@ -794,15 +794,20 @@ where
 		let protocol_id = ProtocolId::from("test-protocol-name");
 
 		let block_request_protocol_config = {
-			let (handler, protocol_config) =
-				BlockRequestHandler::new(&protocol_id, client.clone(), 50);
+			let (handler, protocol_config) = BlockRequestHandler::new(
+				&protocol_id,
+				client.clone(),
+				50,
+				Default::default(),
+				None,
+			);
 			self.spawn_task(handler.run().boxed());
 			protocol_config
 		};
 
 		let state_request_protocol_config = {
 			let (handler, protocol_config) =
-				StateRequestHandler::new(&protocol_id, client.clone(), 50);
+				StateRequestHandler::new(&protocol_id, client.clone(), 50, None);
 			self.spawn_task(handler.run().boxed());
 			protocol_config
 		};
@@ -831,6 +836,7 @@ where
 				.unwrap_or_else(|| Box::new(DefaultBlockAnnounceValidator)),
 			metrics_registry: None,
 			block_request_protocol_config,
+			block_request_reciprocity: Default::default(),
 			state_request_protocol_config,
 			light_client_request_protocol_config,
 			warp_sync: None,
@@ -921,6 +927,7 @@ where
 			block_announce_validator: Box::new(DefaultBlockAnnounceValidator),
 			metrics_registry: None,
 			block_request_protocol_config,
+			block_request_reciprocity: Default::default(),
 			state_request_protocol_config,
 			light_client_request_protocol_config,
 			warp_sync: None,
@@ -33,10 +33,11 @@ use fnv::FnvHashMap;
 use futures::{channel::mpsc, future, prelude::*};
 use hyper::{client, Body, Client as HyperClient};
 use hyper_rustls::HttpsConnector;
-use log::error;
+use log::{debug, error};
 use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver, TracingUnboundedSender};
 use sp_core::offchain::{HttpError, HttpRequestId, HttpRequestStatus, Timestamp};
 use std::{
+	collections::HashSet,
 	convert::TryFrom,
 	fmt,
 	io::Read as _,
@@ -47,11 +48,25 @@ use std::{
 
 /// Wrapper struct used for keeping the hyper_rustls client running.
 #[derive(Clone)]
-pub struct SharedClient(Arc<HyperClient<HttpsConnector<client::HttpConnector>, Body>>);
+pub struct SharedClient {
+	client: Arc<HyperClient<HttpsConnector<client::HttpConnector>, Body>>,
+	/// Hosts the requests may target, any host if `None`.
+	allowed_hosts: Option<Arc<HashSet<String>>>,
+}
 
 impl SharedClient {
 	pub fn new() -> Self {
-		Self(Arc::new(HyperClient::builder().build(HttpsConnector::with_native_roots())))
+		Self {
+			client: Arc::new(HyperClient::builder().build(HttpsConnector::with_native_roots())),
+			allowed_hosts: None,
+		}
+	}
+
+	/// Only allow requests to the given hosts, compared case-insensitively.
+	pub fn with_allowed_hosts(mut self, hosts: impl IntoIterator<Item = String>) -> Self {
+		let hosts = hosts.into_iter().map(|host| host.to_ascii_lowercase()).collect();
+		self.allowed_hosts = Some(Arc::new(hosts));
+		self
 	}
 }
 
@@ -67,10 +82,11 @@ pub fn http(shared_client: SharedClient) -> (HttpApi, HttpWorker) {
 		// writing runtime code with hardcoded IDs.
 		next_id: HttpRequestId(rand::random::<u16>() % 2000),
 		requests: FnvHashMap::default(),
+		allowed_hosts: shared_client.allowed_hosts,
 	};
 
 	let engine =
-		HttpWorker { to_api, from_api, http_client: shared_client.0, requests: Vec::new() };
+		HttpWorker { to_api, from_api, http_client: shared_client.client, requests: Vec::new() };
 
 	(api, engine)
 }
@@ -89,6 +105,8 @@ pub struct HttpApi {
 	next_id: HttpRequestId,
 	/// List of HTTP requests in preparation or in progress.
 	requests: FnvHashMap<HttpRequestId, HttpApiRequest>,
+	/// Hosts the requests may target, any host if `None`.
+	allowed_hosts: Option<Arc<HashSet<String>>>,
 }
 
 /// One active request within `HttpApi`.
@@ -138,6 +156,14 @@ impl HttpApi {
 		*request.method_mut() = hyper::Method::from_bytes(method.as_bytes()).map_err(|_| ())?;
 		*request.uri_mut() = hyper::Uri::from_maybe_shared(uri.to_owned()).map_err(|_| ())?;
 
+		if let Some(ref allowed_hosts) = self.allowed_hosts {
+			let host = request.uri().host().map(|host| host.to_ascii_lowercase());
+			if !host.map_or(false, |host| allowed_hosts.contains(&host)) {
+				debug!("Refusing offchain worker HTTP request to {}, the host is not allowed", uri);
+				return Err(())
+			}
+		}
+
 		let new_id = self.next_id;
 		debug_assert!(!self.requests.contains_key(&new_id));
 		match self.next_id.0.checked_add(1) {
@@ -781,6 +807,17 @@ mod tests {
 		};
 	}
 
+	#[test]
+	fn request_start_only_allowed_hosts() {
+		let (mut api, _) =
+			http(SHARED_CLIENT.clone().with_allowed_hosts(vec!["Example.com".into()]));
+
+		assert!(api.request_start("GET", "https://example.com/price").is_ok());
+		assert!(api.request_start("GET", "https://EXAMPLE.com:8080").is_ok());
+		assert!(api.request_start("GET", "https://api.example.com").is_err());
+		assert!(api.request_start("GET", "http://127.0.0.1").is_err());
+	}
+
 	#[test]
 	fn request_add_header_invalid_call() {
 		let (mut api, addr) = build_api_server!();
@@ -32,10 +32,21 @@
 //! 1. Challenge period for incorrect computations
 //! 2. Majority voting for results
 //! 3. etc
+//!
+//! The offchain workers of each block run in their own thread of a bounded pool, so the workers
+//! of several blocks may run concurrently. When all threads are busy and as many workers are
+//! already waiting for one, the oldest waiting workers are dropped rather than piling up, the
+//! workers of the newest blocks being the most relevant.
 
 #![warn(missing_docs)]
 
-use std::{collections::HashSet, fmt, marker::PhantomData, sync::Arc};
+use std::{
+	collections::{HashSet, VecDeque},
+	fmt,
+	marker::PhantomData,
+	sync::Arc,
+	time::Instant,
+};
 
 use futures::{
 	future::{ready, Future},
@@ -43,6 +54,7 @@ use futures::{
 };
 use log::{debug, warn};
 use parking_lot::Mutex;
+use prometheus_endpoint::Registry;
 use sc_network::{ExHashT, NetworkService, NetworkStateInfo, PeerId};
 use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_core::{offchain, traits::SpawnNamed, ExecutionContext};
@@ -53,6 +65,7 @@ use sp_runtime::{
 use threadpool::ThreadPool;
 
 mod api;
+mod metrics;
 
 pub use api::Db as OffchainDb;
 pub use sp_offchain::{OffchainWorkerApi, STORAGE_PREFIX};
@@ -86,23 +99,52 @@ pub struct OffchainWorkers<Client, Block: traits::Block> {
 	client: Arc<Client>,
 	_block: PhantomData<Block>,
 	thread_pool: Mutex<ThreadPool>,
+	/// The workers waiting for a thread of the pool, oldest first.
+	pending_workers: Arc<Mutex<VecDeque<Box<dyn FnOnce() + Send>>>>,
+	max_concurrent_workers: usize,
 	shared_client: api::SharedClient,
+	metrics: metrics::MetricsLink,
 }
 
 impl<Client, Block: traits::Block> OffchainWorkers<Client, Block> {
 	/// Creates new `OffchainWorkers`.
 	pub fn new(client: Arc<Client>) -> Self {
 		let shared_client = api::SharedClient::new();
+		let max_concurrent_workers = num_cpus::get();
 		Self {
 			client,
 			_block: PhantomData,
 			thread_pool: Mutex::new(ThreadPool::with_name(
 				"offchain-worker".into(),
-				num_cpus::get(),
+				max_concurrent_workers,
 			)),
+			pending_workers: Default::default(),
+			max_concurrent_workers,
 			shared_client,
+			metrics: Default::default(),
 		}
 	}
+
+	/// Run the offchain workers of at most `max` blocks concurrently.
+	///
+	/// Defaults to the number of CPUs.
+	pub fn with_max_concurrent_workers(mut self, max: usize) -> Self {
+		self.max_concurrent_workers = max.max(1);
+		self.thread_pool.get_mut().set_num_threads(self.max_concurrent_workers);
+		self
+	}
+
+	/// Report the offchain workers metrics to the given registry.
+	pub fn with_prometheus_registry(mut self, registry: &Registry) -> Self {
+		self.metrics = metrics::MetricsLink::new(registry);
+		self
+	}
+
+	/// Only allow the HTTP requests of the offchain workers to target the given hosts.
+	pub fn with_http_allowed_hosts(mut self, hosts: impl IntoIterator<Item = String>) -> Self {
+		self.shared_client = self.shared_client.with_allowed_hosts(hosts);
+		self
+	}
 }
 
 impl<Client, Block: traits::Block> fmt::Debug for OffchainWorkers<Client, Block> {
@@ -180,8 +222,57 @@ where
 	///
 	/// Note that we should avoid that if we switch to future-based runtime in the future,
 	/// alternatively:
+	///
+	/// If the workers of as many blocks as there are threads are already waiting to run, the
+	/// oldest of them is dropped.
 	fn spawn_worker(&self, f: impl FnOnce() -> () + Send + 'static) {
-		self.thread_pool.lock().execute(f);
+		{
+			let mut pending = self.pending_workers.lock();
+			if pending.len() >= self.max_concurrent_workers {
+				warn!(
+					"Dropping stale offchain workers, {} blocks are already waiting for theirs to run",
+					pending.len(),
+				);
+				pending.pop_front();
+				self.metrics.report(|metrics| metrics.skipped_workers.inc());
+			}
+			pending.push_back(Box::new(f));
+		}
+
+		// Every job runs the oldest pending worker, a job whose worker was dropped has none left.
+		let pending = self.pending_workers.clone();
+		let metrics = self.metrics.clone();
+		self.thread_pool.lock().execute(move || {
+			let f = match pending.lock().pop_front() {
+				Some(f) => f,
+				None => return,
+			};
+			let _running = RunningWorker::new(metrics);
+			f();
+		});
+	}
+}
+
+/// Reports an offchain worker as running until dropped, even if the worker panics.
+struct RunningWorker {
+	metrics: metrics::MetricsLink,
+	start: Instant,
+}
+
+impl RunningWorker {
+	fn new(metrics: metrics::MetricsLink) -> Self {
+		metrics.report(|metrics| metrics.running_workers.inc());
+		Self { metrics, start: Instant::now() }
+	}
+}
+
+impl Drop for RunningWorker {
+	fn drop(&mut self) {
+		let start = self.start;
+		self.metrics.report(|metrics| {
+			metrics.running_workers.dec();
+			metrics.worker_duration.observe(start.elapsed().as_secs_f64());
+		});
 	}
 }
 
@@ -300,6 +391,36 @@ mod tests {
 		assert_eq!(pool.0.ready().next().unwrap().is_propagable(), false);
 	}
 
+	#[test]
+	fn spawn_worker_drops_stale_workers_when_too_many_are_pending() {
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let offchain = OffchainWorkers::new(client).with_max_concurrent_workers(1);
+
+		let (release, wait) = std::sync::mpsc::channel::<()>();
+		let wait = Arc::new(Mutex::new(wait));
+		let (started, is_started) = std::sync::mpsc::channel();
+		let blocking_worker = |id: u32| {
+			let wait = wait.clone();
+			let started = started.clone();
+			move || {
+				started.send(id).unwrap();
+				wait.lock().recv().unwrap();
+			}
+		};
+
+		offchain.spawn_worker(blocking_worker(1));
+		assert_eq!(is_started.recv().unwrap(), 1);
+		// the first worker is running, the second one waits for it and is replaced by the third.
+		offchain.spawn_worker(blocking_worker(2));
+		offchain.spawn_worker(blocking_worker(3));
+
+		release.send(()).unwrap();
+		assert_eq!(is_started.recv().unwrap(), 3);
+		release.send(()).unwrap();
+		offchain.thread_pool.lock().join();
+		assert!(is_started.try_recv().is_err());
+	}
+
 	#[test]
 	fn offchain_index_set_and_clear_works() {
 		use sp_core::offchain::OffchainStorage;
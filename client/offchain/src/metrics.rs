@@ -0,0 +1,82 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Offchain workers Prometheus metrics.
+
+use prometheus_endpoint::{
+	register, Counter, Gauge, Histogram, HistogramOpts, PrometheusError, Registry, U64,
+};
+
+/// Optional shareable link to the offchain workers metrics.
+#[derive(Clone, Default)]
+pub(crate) struct MetricsLink(Option<Metrics>);
+
+impl MetricsLink {
+	pub fn new(registry: &Registry) -> Self {
+		Self(
+			Metrics::register(registry)
+				.map_err(|err| {
+					log::warn!("Failed to register offchain workers prometheus metrics: {}", err)
+				})
+				.ok(),
+		)
+	}
+
+	pub fn report(&self, do_this: impl FnOnce(&Metrics)) {
+		if let Some(metrics) = self.0.as_ref() {
+			do_this(metrics)
+		}
+	}
+}
+
+/// Offchain workers metrics.
+#[derive(Clone)]
+pub(crate) struct Metrics {
+	pub worker_duration: Histogram,
+	pub running_workers: Gauge<U64>,
+	pub skipped_workers: Counter<U64>,
+}
+
+impl Metrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			worker_duration: register(
+				Histogram::with_opts(
+					HistogramOpts::new(
+						"offchain_worker_duration_seconds",
+						"Time taken by the offchain workers of a block",
+					)
+					.buckets(vec![0.01, 0.05, 0.1, 0.5, 1.0, 2.0, 6.0, 12.0, 30.0, 60.0, 120.0]),
+				)?,
+				registry,
+			)?,
+			running_workers: register(
+				Gauge::new("offchain_workers_running", "Number of offchain workers running")?,
+				registry,
+			)?,
+			skipped_workers: register(
+				Counter::new(
+					"offchain_workers_skipped_total",
+					"Number of blocks whose offchain workers were dropped while waiting to run, \
+					 because the workers of too many newer blocks were pending",
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
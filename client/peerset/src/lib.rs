@@ -48,8 +48,10 @@ use wasm_timer::Delay;
 
 pub use libp2p::PeerId;
 
-/// We don't accept nodes whose reputation is under this value.
+/// Default value of [`ReputationConfig::banned_threshold`].
 const BANNED_THRESHOLD: i32 = 82 * (i32::MIN / 100);
+/// Default value of [`ReputationConfig::decay_divisor`].
+const REPUTATION_DECAY_DIVISOR: i32 = 50;
 /// Reputation change for a node when we get disconnected from it.
 const DISCONNECT_REPUTATION_CHANGE: i32 = -256;
 /// Amount of time between the moment we disconnect from a node and the moment we remove it from
@@ -207,6 +209,49 @@ impl From<u64> for IncomingIndex {
 pub struct PeersetConfig {
 	/// List of sets of nodes the peerset manages.
 	pub sets: Vec<SetConfig>,
+
+	/// Configuration of the reputation of nodes, shared by all sets.
+	pub reputation: ReputationConfig,
+}
+
+/// Configuration of the reputation of nodes.
+#[derive(Debug, Clone)]
+pub struct ReputationConfig {
+	/// Every second, the reputation of each node moves towards zero by `1 / decay_divisor` of
+	/// its value.
+	///
+	/// With the default of `50`, it takes 34.3 seconds to reduce a reputation by half.
+	pub decay_divisor: i32,
+
+	/// Nodes whose reputation falls under this value are disconnected and not accepted.
+	pub banned_threshold: i32,
+
+	/// Minimum amount of time during which a node is not accepted after its reputation fell
+	/// under [`ReputationConfig::banned_threshold`], even if its reputation recovers in the
+	/// meantime.
+	///
+	/// With the default of zero, nodes are accepted again as soon as their reputation is back
+	/// above the threshold.
+	pub ban_duration: Duration,
+}
+
+impl Default for ReputationConfig {
+	fn default() -> Self {
+		Self {
+			decay_divisor: REPUTATION_DECAY_DIVISOR,
+			banned_threshold: BANNED_THRESHOLD,
+			ban_duration: Duration::from_secs(0),
+		}
+	}
+}
+
+impl ReputationConfig {
+	/// Returns whether a node with the given reputation, banned until `banned_until` if at all,
+	/// must not be connected to.
+	fn is_banned(&self, reputation: i32, banned_until: Option<&Instant>) -> bool {
+		reputation < self.banned_threshold ||
+			banned_until.map_or(false, |until| *until > Instant::now())
+	}
 }
 
 /// Configuration for a single set of nodes.
@@ -259,6 +304,10 @@ pub struct Peerset {
 	/// Next time to do a periodic call to `alloc_slots` with all sets. This is done once per
 	/// second, to match the period of the reputation updates.
 	next_periodic_alloc_slots: Delay,
+	/// Configuration of the reputation of nodes.
+	reputation_config: ReputationConfig,
+	/// Nodes that are banned for [`ReputationConfig::ban_duration`], and until when.
+	banned_until: HashMap<PeerId, Instant>,
 }
 
 impl Peerset {
@@ -286,6 +335,8 @@ impl Peerset {
 				created: now,
 				latest_time_update: now,
 				next_periodic_alloc_slots: Delay::new(Duration::new(0, 0)),
+				reputation_config: config.reputation,
+				banned_until: HashMap::new(),
 			}
 		};
 
@@ -428,7 +479,7 @@ impl Peerset {
 
 		let mut reputation = self.data.peer_reputation(peer_id);
 		reputation.add_reputation(change.value);
-		if reputation.reputation() >= BANNED_THRESHOLD {
+		if reputation.reputation() >= self.reputation_config.banned_threshold {
 			trace!(target: "peerset", "Report {}: {:+} to {}. Reason: {}",
 				peer_id, change.value, reputation.reputation(), change.reason
 			);
@@ -441,6 +492,11 @@ impl Peerset {
 
 		drop(reputation);
 
+		if self.reputation_config.ban_duration > Duration::from_secs(0) {
+			self.banned_until
+				.insert(peer_id, Instant::now() + self.reputation_config.ban_duration);
+		}
+
 		for set_index in 0..self.data.num_sets() {
 			if let peersstate::Peer::Connected(peer) = self.data.peer(set_index, &peer_id) {
 				let peer = peer.disconnect();
@@ -468,16 +524,18 @@ impl Peerset {
 			elapsed_now.as_secs() - elapsed_latest.as_secs()
 		};
 
+		self.banned_until.retain(|_, until| *until > now);
+
 		// For each elapsed second, move the node reputation towards zero.
 		// If we multiply each second the reputation by `k` (where `k` is between 0 and 1), it
 		// takes `ln(0.5) / ln(k)` seconds to reduce the reputation by half. Use this formula to
 		// empirically determine a value of `k` that looks correct.
 		for _ in 0..secs_diff {
 			for peer_id in self.data.peers().cloned().collect::<Vec<_>>() {
-				// We use `k = 0.98`, so we divide by `50`. With that value, it takes 34.3 seconds
-				// to reduce the reputation by half.
-				fn reput_tick(reput: i32) -> i32 {
-					let mut diff = reput / 50;
+				// By default we use `k = 0.98`, so we divide by `50`. With that value, it takes
+				// 34.3 seconds to reduce the reputation by half.
+				fn reput_tick(reput: i32, decay_divisor: i32) -> i32 {
+					let mut diff = reput / decay_divisor.max(1);
 					if diff == 0 && reput < 0 {
 						diff = -1;
 					} else if diff == 0 && reput > 0 {
@@ -489,7 +547,7 @@ impl Peerset {
 				let mut peer_reputation = self.data.peer_reputation(peer_id);
 
 				let before = peer_reputation.reputation();
-				let after = reput_tick(before);
+				let after = reput_tick(before, self.reputation_config.decay_divisor);
 				trace!(target: "peerset", "Fleeting {}: {} -> {}", peer_id, before, after);
 				peer_reputation.set_reputation(after);
 
@@ -534,8 +592,11 @@ impl Peerset {
 			// This is a rather opinionated behaviour, and it wouldn't be fundamentally wrong to
 			// remove that check. If necessary, the peerset should be refactored to give more
 			// control over what happens in that situation.
-			if entry.reputation() < BANNED_THRESHOLD {
-				break
+			if self
+				.reputation_config
+				.is_banned(entry.reputation(), self.banned_until.get(reserved_node))
+			{
+				continue
 			}
 
 			match entry.try_outgoing() {
@@ -565,14 +626,20 @@ impl Peerset {
 		// Try to grab the next node to attempt to connect to.
 		// Since `highest_not_connected_peer` is rather expensive to call, check beforehand
 		// whether we have an available slot.
+		let now = Instant::now();
 		while self.data.has_free_outgoing_slot(set_id.0) {
-			let next = match self.data.highest_not_connected_peer(set_id.0) {
+			// Nodes still banned for `ban_duration` are skipped, whatever their reputation.
+			let banned_until = &self.banned_until;
+			let next = match self.data.highest_not_connected_peer_filtered(set_id.0, |peer_id| {
+				banned_until.get(peer_id).map_or(true, |until| *until <= now)
+			}) {
 				Some(n) => n,
 				None => break,
 			};
 
-			// Don't connect to nodes with an abysmal reputation.
-			if next.reputation() < BANNED_THRESHOLD {
+			// Don't connect to nodes with an abysmal reputation. The candidates are sorted by
+			// reputation, so none of the following ones would be connected to either.
+			if self.reputation_config.is_banned(next.reputation(), None) {
 				break
 			}
 
@@ -621,7 +688,10 @@ impl Peerset {
 			peersstate::Peer::Unknown(entry) => entry.discover(),
 		};
 
-		if not_connected.reputation() < BANNED_THRESHOLD {
+		if self
+			.reputation_config
+			.is_banned(not_connected.reputation(), self.banned_until.get(&peer_id))
+		{
 			self.message_queue.push_back(Message::Reject(index));
 			return
 		}
@@ -700,6 +770,16 @@ impl Peerset {
 		})
 	}
 
+	/// Returns the reputation of the given peer, or `None` if the peer is unknown.
+	pub fn peer_reputation(&mut self, peer_id: &PeerId) -> Option<i32> {
+		if !self.data.peers().any(|p| p == peer_id) {
+			return None
+		}
+
+		self.update_time();
+		Some(self.data.peer_reputation(*peer_id).reputation())
+	}
+
 	/// Returns the number of peers that we have discovered.
 	pub fn num_discovered_peers(&self) -> usize {
 		self.data.peers().len()
@@ -763,8 +843,8 @@ pub enum DropReason {
 #[cfg(test)]
 mod tests {
 	use super::{
-		IncomingIndex, Message, Peerset, PeersetConfig, ReputationChange, SetConfig, SetId,
-		BANNED_THRESHOLD,
+		IncomingIndex, Message, Peerset, PeersetConfig, ReputationChange, ReputationConfig,
+		SetConfig, SetId, BANNED_THRESHOLD,
 	};
 	use futures::prelude::*;
 	use libp2p::PeerId;
@@ -798,6 +878,7 @@ mod tests {
 				reserved_nodes: Default::default(),
 				reserved_only: true,
 			}],
+			reputation: Default::default(),
 		};
 
 		let (peerset, handle) = Peerset::from_config(config);
@@ -831,6 +912,7 @@ mod tests {
 				reserved_nodes: Default::default(),
 				reserved_only: false,
 			}],
+			reputation: Default::default(),
 		};
 
 		let (mut peerset, _handle) = Peerset::from_config(config);
@@ -862,6 +944,7 @@ mod tests {
 				reserved_nodes: Default::default(),
 				reserved_only: true,
 			}],
+			reputation: Default::default(),
 		};
 
 		let (mut peerset, _) = Peerset::from_config(config);
@@ -883,6 +966,7 @@ mod tests {
 				reserved_nodes: Default::default(),
 				reserved_only: false,
 			}],
+			reputation: Default::default(),
 		};
 
 		let (mut peerset, _handle) = Peerset::from_config(config);
@@ -909,6 +993,7 @@ mod tests {
 				reserved_nodes: Default::default(),
 				reserved_only: false,
 			}],
+			reputation: Default::default(),
 		});
 
 		// We ban a node by setting its reputation under the threshold.
@@ -952,6 +1037,7 @@ mod tests {
 				reserved_nodes: Default::default(),
 				reserved_only: false,
 			}],
+			reputation: Default::default(),
 		});
 
 		// We ban a node by setting its reputation under the threshold.
@@ -982,4 +1068,117 @@ mod tests {
 
 		futures::executor::block_on(fut);
 	}
+
+	#[test]
+	fn test_peerset_ban_duration() {
+		let (mut peerset, handle) = Peerset::from_config(PeersetConfig {
+			sets: vec![SetConfig {
+				in_peers: 25,
+				out_peers: 25,
+				bootnodes: vec![],
+				reserved_nodes: Default::default(),
+				reserved_only: false,
+			}],
+			reputation: ReputationConfig {
+				ban_duration: Duration::from_secs(3600),
+				..Default::default()
+			},
+		});
+
+		// Ban the node, then immediately restore its reputation.
+		let peer_id = PeerId::random();
+		handle.report_peer(peer_id.clone(), ReputationChange::new(BANNED_THRESHOLD - 1, ""));
+		handle.report_peer(peer_id.clone(), ReputationChange::new(-(BANNED_THRESHOLD - 1), ""));
+
+		let fut = futures::future::poll_fn(move |cx| {
+			// We need one polling for the messages to be processed.
+			assert_eq!(Stream::poll_next(Pin::new(&mut peerset), cx), Poll::Pending);
+			assert!(peerset.peer_reputation(&peer_id).unwrap() > BANNED_THRESHOLD);
+
+			// The node is still refused until the ban expires.
+			peerset.incoming(SetId::from(0), peer_id, IncomingIndex(1));
+			if let Poll::Ready(msg) = Stream::poll_next(Pin::new(&mut peerset), cx) {
+				assert_eq!(msg.unwrap(), Message::Reject(IncomingIndex(1)));
+			} else {
+				panic!()
+			}
+
+			Poll::Ready(())
+		});
+
+		futures::executor::block_on(fut);
+	}
+
+	#[test]
+	fn test_banned_reserved_node_does_not_prevent_others() {
+		let (mut peerset, handle) = Peerset::from_config(PeersetConfig {
+			sets: vec![SetConfig {
+				in_peers: 25,
+				out_peers: 25,
+				bootnodes: vec![],
+				reserved_nodes: Default::default(),
+				reserved_only: true,
+			}],
+			reputation: Default::default(),
+		});
+
+		let banned = PeerId::random();
+		let reserved = PeerId::random();
+		handle.report_peer(banned, ReputationChange::new(BANNED_THRESHOLD - 1, ""));
+		handle.add_reserved_peer(SetId::from(0), banned);
+		handle.add_reserved_peer(SetId::from(0), reserved);
+
+		let fut = futures::future::poll_fn(move |cx| {
+			let mut messages = Vec::new();
+			while let Poll::Ready(msg) = Stream::poll_next(Pin::new(&mut peerset), cx) {
+				messages.push(msg.unwrap());
+			}
+			assert_eq!(
+				messages,
+				vec![Message::Connect { set_id: SetId::from(0), peer_id: reserved }]
+			);
+
+			Poll::Ready(())
+		});
+
+		futures::executor::block_on(fut);
+	}
+
+	#[test]
+	fn test_peer_banned_for_duration_does_not_prevent_others() {
+		let (mut peerset, handle) = Peerset::from_config(PeersetConfig {
+			sets: vec![SetConfig {
+				in_peers: 25,
+				out_peers: 25,
+				bootnodes: vec![],
+				reserved_nodes: Default::default(),
+				reserved_only: false,
+			}],
+			reputation: ReputationConfig {
+				ban_duration: Duration::from_secs(3600),
+				..Default::default()
+			},
+		});
+
+		// Ban the node, then restore its reputation above the one of the other node.
+		let banned = PeerId::random();
+		let other = PeerId::random();
+		handle.report_peer(banned, ReputationChange::new(BANNED_THRESHOLD - 1, ""));
+		handle.report_peer(banned, ReputationChange::new(-(BANNED_THRESHOLD - 1) + 1000, ""));
+		handle.add_to_peers_set(SetId::from(0), banned);
+		handle.add_to_peers_set(SetId::from(0), other);
+
+		let fut = futures::future::poll_fn(move |cx| {
+			let mut messages = Vec::new();
+			while let Poll::Ready(msg) = Stream::poll_next(Pin::new(&mut peerset), cx) {
+				messages.push(msg.unwrap());
+			}
+			assert!(peerset.peer_reputation(&banned).unwrap() > 0);
+			assert_eq!(messages, vec![Message::Connect { set_id: SetId::from(0), peer_id: other }]);
+
+			Poll::Ready(())
+		});
+
+		futures::executor::block_on(fut);
+	}
 }
@@ -235,6 +235,16 @@ impl PeersState {
 	///
 	/// `set` must be within range of the sets passed to [`PeersState::new`].
 	pub fn highest_not_connected_peer(&mut self, set: usize) -> Option<NotConnectedPeer> {
+		self.highest_not_connected_peer_filtered(set, |_| true)
+	}
+
+	/// Same as [`PeersState::highest_not_connected_peer`], among the nodes for which `filter`
+	/// returns `true`.
+	pub fn highest_not_connected_peer_filtered(
+		&mut self,
+		set: usize,
+		filter: impl Fn(&PeerId) -> bool,
+	) -> Option<NotConnectedPeer> {
 		// The code below will panic anyway if this happens to be false, but this earlier assert
 		// makes it explicit what is wrong.
 		assert!(set < self.sets.len());
@@ -248,6 +258,7 @@ impl PeersState {
 				MembershipState::Out => false,
 				MembershipState::NotConnected { .. } => true,
 			})
+			.filter(|(peer_id, _)| filter(peer_id))
 			.fold(None::<(&PeerId, &mut Node)>, |mut cur_node, to_try| {
 				if let Some(cur_node) = cur_node.take() {
 					if cur_node.1.reputation >= to_try.1.reputation {
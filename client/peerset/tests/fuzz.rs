@@ -70,6 +70,7 @@ fn test_once() {
 			out_peers: Uniform::new_inclusive(0, 25).sample(&mut rng),
 			reserved_only: Uniform::new_inclusive(0, 10).sample(&mut rng) == 0,
 		}],
+		reputation: Default::default(),
 	});
 
 	futures::executor::block_on(futures::future::poll_fn(move |cx| {
@@ -18,7 +18,7 @@
 
 //! Authoring RPC module errors.
 
-use crate::errors;
+use crate::errors::{self, Module};
 use jsonrpc_core as rpc;
 use sp_runtime::transaction_validity::InvalidTransaction;
 
@@ -58,13 +58,16 @@ pub enum Error {
 	/// Invalid session keys encoding.
 	#[error("Session keys are not encoded correctly")]
 	InvalidSessionKeys,
+	/// The session keys to keep are missing when rotating only some key types.
+	#[error("The current session keys are required to rotate only some key types")]
+	MissingSessionKeys,
 	/// Call to an unsafe RPC was denied.
 	#[error(transparent)]
 	UnsafeRpcCalled(#[from] crate::policy::UnsafeRpcError),
 }
 
 /// Base code for all authorship errors.
-const BASE_ERROR: i64 = 1000;
+const BASE_ERROR: i64 = Module::Author.base_code();
 /// Extrinsic has an invalid format.
 const BAD_FORMAT: i64 = BASE_ERROR + 1;
 /// Error during transaction verification in runtime.
@@ -89,83 +92,97 @@ const UNSUPPORTED_KEY_TYPE: i64 = POOL_INVALID_TX + 7;
 /// The transaction was not included to the pool since it is unactionable,
 /// it is not propagable and the local node does not author blocks.
 const POOL_UNACTIONABLE: i64 = POOL_INVALID_TX + 8;
+/// The session keys are not encoded correctly.
+const INVALID_SESSION_KEYS: i64 = POOL_INVALID_TX + 9;
+/// The current session keys are required to rotate only some key types.
+const MISSING_SESSION_KEYS: i64 = POOL_INVALID_TX + 10;
+/// Key type ID has an unknown format.
+const BAD_KEY_TYPE: i64 = POOL_INVALID_TX + 11;
 
 impl From<Error> for rpc::Error {
 	fn from(e: Error) -> Self {
 		use sc_transaction_pool_api::error::Error as PoolError;
 
+		let author = Module::Author;
 		match e {
-			Error::BadFormat(e) => rpc::Error {
-				code: rpc::ErrorCode::ServerError(BAD_FORMAT),
-				message: format!("Extrinsic has invalid format: {}", e).into(),
-				data: None,
-			},
-			Error::Verification(e) => rpc::Error {
-				code: rpc::ErrorCode::ServerError(VERIFICATION_ERROR),
-				message: format!("Verification Error: {}", e).into(),
-				data: Some(format!("{:?}", e).into()),
-			},
-			Error::Pool(PoolError::InvalidTransaction(InvalidTransaction::Custom(e))) => rpc::Error {
-				code: rpc::ErrorCode::ServerError(POOL_INVALID_TX),
-				message: "Invalid Transaction".into(),
-				data: Some(format!("Custom error: {}", e).into()),
-			},
+			Error::BadFormat(e) => author
+				.error(BAD_FORMAT, "badFormat", format!("Extrinsic has invalid format: {}", e))
+				.into(),
+			Error::Verification(e) => author
+				.error(VERIFICATION_ERROR, "verification", format!("Verification Error: {}", e))
+				.details(format!("{:?}", e))
+				.into(),
+			Error::Pool(PoolError::InvalidTransaction(InvalidTransaction::Custom(e))) => author
+				.error(POOL_INVALID_TX, "invalidTransaction", "Invalid Transaction")
+				.details(format!("Custom error: {}", e))
+				.into(),
 			Error::Pool(PoolError::InvalidTransaction(e)) => {
 				let msg: &str = e.into();
-				rpc::Error {
-					code: rpc::ErrorCode::ServerError(POOL_INVALID_TX),
-					message: "Invalid Transaction".into(),
-					data: Some(msg.into()),
-				}
-			},
-			Error::Pool(PoolError::UnknownTransaction(e)) => rpc::Error {
-				code: rpc::ErrorCode::ServerError(POOL_UNKNOWN_VALIDITY),
-				message: "Unknown Transaction Validity".into(),
-				data: serde_json::to_value(e).ok(),
-			},
-			Error::Pool(PoolError::TemporarilyBanned) => rpc::Error {
-				code: rpc::ErrorCode::ServerError(POOL_TEMPORARILY_BANNED),
-				message: "Transaction is temporarily banned".into(),
-				data: None,
+				author
+					.error(POOL_INVALID_TX, "invalidTransaction", "Invalid Transaction")
+					.details(msg)
+					.into()
 			},
-			Error::Pool(PoolError::AlreadyImported(hash)) => rpc::Error {
-				code: rpc::ErrorCode::ServerError(POOL_ALREADY_IMPORTED),
-				message: "Transaction Already Imported".into(),
-				data: Some(format!("{:?}", hash).into()),
-			},
-			Error::Pool(PoolError::TooLowPriority { old, new }) => rpc::Error {
-				code: rpc::ErrorCode::ServerError(POOL_TOO_LOW_PRIORITY),
-				message: format!("Priority is too low: ({} vs {})", old, new),
-				data: Some("The transaction has too low priority to replace another transaction already in the pool.".into()),
-			},
-			Error::Pool(PoolError::CycleDetected) => rpc::Error {
-				code: rpc::ErrorCode::ServerError(POOL_CYCLE_DETECTED),
-				message: "Cycle Detected".into(),
-				data: None,
-			},
-			Error::Pool(PoolError::ImmediatelyDropped) => rpc::Error {
-				code: rpc::ErrorCode::ServerError(POOL_IMMEDIATELY_DROPPED),
-				message: "Immediately Dropped".into(),
-				data: Some("The transaction couldn't enter the pool because of the limit".into()),
+			Error::Pool(PoolError::UnknownTransaction(e)) => {
+				let error = author
+					.error(POOL_UNKNOWN_VALIDITY, "unknownValidity", "Unknown Transaction Validity")
+					.retryable();
+				match serde_json::to_value(e) {
+					Ok(details) => error.details(details).into(),
+					Err(_) => error.into(),
+				}
 			},
-			Error::Pool(PoolError::Unactionable) => rpc::Error {
-				code: rpc::ErrorCode::ServerError(POOL_UNACTIONABLE),
-				message: "Unactionable".into(),
-				data: Some(
+			Error::Pool(PoolError::TemporarilyBanned) => author
+				.error(
+					POOL_TEMPORARILY_BANNED,
+					"temporarilyBanned",
+					"Transaction is temporarily banned",
+				)
+				.retryable()
+				.into(),
+			Error::Pool(PoolError::AlreadyImported(hash)) => author
+				.error(POOL_ALREADY_IMPORTED, "alreadyImported", "Transaction Already Imported")
+				.details(format!("{:?}", hash))
+				.into(),
+			Error::Pool(PoolError::TooLowPriority { old, new }) => author
+				.error(
+					POOL_TOO_LOW_PRIORITY,
+					"tooLowPriority",
+					format!("Priority is too low: ({} vs {})", old, new),
+				)
+				.details(
+					"The transaction has too low priority to replace another transaction already \
+					 in the pool.",
+				)
+				.into(),
+			Error::Pool(PoolError::CycleDetected) =>
+				author.error(POOL_CYCLE_DETECTED, "cycleDetected", "Cycle Detected").into(),
+			Error::Pool(PoolError::ImmediatelyDropped) => author
+				.error(POOL_IMMEDIATELY_DROPPED, "immediatelyDropped", "Immediately Dropped")
+				.retryable()
+				.details("The transaction couldn't enter the pool because of the limit")
+				.into(),
+			Error::Pool(PoolError::Unactionable) => author
+				.error(POOL_UNACTIONABLE, "unactionable", "Unactionable")
+				.details(
 					"The transaction is unactionable since it is not propagable and \
-					 the local node does not author blocks".into(),
-				),
-			},
-			Error::UnsupportedKeyType => rpc::Error {
-				code: rpc::ErrorCode::ServerError(UNSUPPORTED_KEY_TYPE),
-				message: "Unknown key type crypto" .into(),
-				data: Some(
-					"The crypto for the given key type is unknown, please add the public key to the \
-					request to insert the key successfully.".into()
-				),
-			},
+					 the local node does not author blocks",
+				)
+				.into(),
+			Error::UnsupportedKeyType => author
+				.error(UNSUPPORTED_KEY_TYPE, "unsupportedKeyType", "Unknown key type crypto")
+				.details(
+					"The crypto for the given key type is unknown, please add the public key to \
+					 the request to insert the key successfully.",
+				)
+				.into(),
+			e @ Error::InvalidSessionKeys =>
+				author.error(INVALID_SESSION_KEYS, "invalidSessionKeys", e.to_string()).into(),
+			e @ Error::MissingSessionKeys =>
+				author.error(MISSING_SESSION_KEYS, "missingSessionKeys", e.to_string()).into(),
+			e @ Error::BadKeyType => author.error(BAD_KEY_TYPE, "badKeyType", e.to_string()).into(),
 			Error::UnsafeRpcCalled(e) => e.into(),
-			e => errors::internal(e),
+			e => errors::internal(author, e),
 		}
 	}
 }
@@ -24,11 +24,23 @@ pub mod hash;
 use self::error::{FutureResult, Result};
 use jsonrpc_derive::rpc;
 use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId};
-use sc_transaction_pool_api::TransactionStatus;
+use sc_transaction_pool_api::{TransactionEvent, TransactionStatus};
+use serde::{Deserialize, Serialize};
 use sp_core::Bytes;
+use std::collections::BTreeMap;
 
 pub use self::gen_client::Client as AuthorClient;
 
+/// Session keys generated by `author_rotateKeysWithDetails`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RotatedSessionKeys {
+	/// The SCALE encoded session keys object of the runtime.
+	pub keys: Bytes,
+	/// The public key of each session key, by key type.
+	pub public_keys: BTreeMap<String, Bytes>,
+}
+
 /// Substrate authoring RPC API
 #[rpc]
 pub trait AuthorApi<Hash, BlockHash> {
@@ -39,21 +51,45 @@ pub trait AuthorApi<Hash, BlockHash> {
 	#[rpc(name = "author_submitExtrinsic")]
 	fn submit_extrinsic(&self, extrinsic: Bytes) -> FutureResult<Hash>;
 
+	/// Submit hex-encoded extrinsic as a transaction originating from the node itself.
+	///
+	/// The transaction is validated as `TransactionSource::Local` and is not propagated if the
+	/// pool keeps local transactions private.
+	#[rpc(name = "author_submitLocal")]
+	fn submit_local(&self, extrinsic: Bytes) -> FutureResult<Hash>;
+
 	/// Insert a key into the keystore.
 	#[rpc(name = "author_insertKey")]
 	fn insert_key(&self, key_type: String, suri: String, public: Bytes) -> Result<()>;
 
 	/// Generate new session keys and returns the corresponding public keys.
+	///
+	/// If `key_types` is given, only the keys of those types are generated, the other ones are
+	/// kept from `session_keys`, the SCALE encoded session keys object currently in use.
 	#[rpc(name = "author_rotateKeys")]
-	fn rotate_keys(&self) -> Result<Bytes>;
+	fn rotate_keys(
+		&self,
+		key_types: Option<Vec<String>>,
+		session_keys: Option<Bytes>,
+	) -> Result<Bytes>;
+
+	/// Same as `author_rotateKeys`, also returning the public key of each session key.
+	#[rpc(name = "author_rotateKeysWithDetails")]
+	fn rotate_keys_with_details(
+		&self,
+		key_types: Option<Vec<String>>,
+		session_keys: Option<Bytes>,
+	) -> Result<RotatedSessionKeys>;
 
 	/// Checks if the keystore has private keys for the given session public keys.
 	///
-	/// `session_keys` is the SCALE encoded session keys object from the runtime.
+	/// `session_keys` is the SCALE encoded session keys object from the runtime. If `key_types`
+	/// is given, only the keys of those types are checked.
 	///
 	/// Returns `true` iff all private keys could be found.
 	#[rpc(name = "author_hasSessionKeys")]
-	fn has_session_keys(&self, session_keys: Bytes) -> Result<bool>;
+	fn has_session_keys(&self, session_keys: Bytes, key_types: Option<Vec<String>>)
+		-> Result<bool>;
 
 	/// Checks if the keystore has private keys for the given public key and key type.
 	///
@@ -99,4 +135,23 @@ pub trait AuthorApi<Hash, BlockHash> {
 		metadata: Option<Self::Metadata>,
 		id: SubscriptionId,
 	) -> Result<bool>;
+
+	/// Subscribe to the events of all transactions in the pool.
+	///
+	/// See [`PoolEvent`](sc_transaction_pool_api::PoolEvent) for details on the reported events.
+	/// This is an unsafe method, denied unless the node allows unsafe RPC methods.
+	#[pubsub(subscription = "author_poolEvent", subscribe, name = "author_subscribePoolEvents")]
+	fn subscribe_pool_events(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<TransactionEvent<Hash, BlockHash>>,
+	);
+
+	/// Unsubscribe from the events of all transactions in the pool.
+	#[pubsub(subscription = "author_poolEvent", unsubscribe, name = "author_unsubscribePoolEvents")]
+	fn unsubscribe_pool_events(
+		&self,
+		metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> Result<bool>;
 }
@@ -18,7 +18,7 @@
 
 //! Error helpers for Chain RPC module.
 
-use crate::errors;
+use crate::errors::{self, Module};
 use jsonrpc_core as rpc;
 
 /// Chain RPC Result type.
@@ -39,17 +39,13 @@ pub enum Error {
 }
 
 /// Base error code for all chain errors.
-const BASE_ERROR: i64 = 3000;
+const BASE_ERROR: i64 = Module::Chain.base_code();
 
 impl From<Error> for rpc::Error {
 	fn from(e: Error) -> Self {
 		match e {
-			Error::Other(message) => rpc::Error {
-				code: rpc::ErrorCode::ServerError(BASE_ERROR + 1),
-				message,
-				data: None,
-			},
-			e => errors::internal(e),
+			Error::Other(message) => Module::Chain.error(BASE_ERROR + 1, "other", message).into(),
+			e => errors::internal(Module::Chain, e),
 		}
 	}
 }
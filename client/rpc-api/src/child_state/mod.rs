@@ -45,7 +45,7 @@ pub trait ChildStateApi<Hash> {
 	) -> FutureResult<Vec<StorageKey>>;
 
 	/// Returns the keys with prefix from a child storage with pagination support.
-	/// Up to `count` keys will be returned.
+	/// Up to `count` keys will be returned, `count` may not exceed 1000.
 	/// If `start_key` is passed, return next keys in storage in lexicographic order.
 	#[rpc(name = "childstate_getKeysPaged", alias("childstate_getKeysPagedAt"))]
 	fn storage_keys_paged(
@@ -85,6 +85,9 @@ pub trait ChildStateApi<Hash> {
 	) -> FutureResult<Option<Hash>>;
 
 	/// Returns the size of a child storage entry at a block's state.
+	///
+	/// If no entry is stored at `key`, the sum of the sizes of all the entries with `key` as
+	/// prefix is returned.
 	#[rpc(name = "childstate_getStorageSize")]
 	fn storage_size(
 		&self,
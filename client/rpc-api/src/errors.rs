@@ -16,13 +16,213 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+//! Error codes and machine-readable error data shared by the RPC modules.
+//!
+//! Every module owns a range of server error codes, see [`Module`]. Its errors carry an
+//! [`ErrorData`] as `data`, telling clients which module failed, why, and whether the request
+//! may succeed when retried.
+
+use jsonrpc_core as rpc;
 use log::warn;
+use serde::{Deserialize, Serialize};
+
+/// The number of error codes in the range of each module.
+const MODULE_RANGE: i64 = 1000;
+
+/// An RPC module owning a range of error codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Module {
+	/// The `author` RPC module, codes `1001..2000`.
+	Author,
+	/// The `system` RPC module, codes `2001..3000`.
+	System,
+	/// The `chain` RPC module, codes `3001..4000`.
+	Chain,
+	/// The `state` RPC module, codes `4001..5000`.
+	State,
+	/// The `offchain` RPC module, codes `5001..6000`.
+	Offchain,
+	/// The `grandpa` RPC module, codes `6001..7000`.
+	Grandpa,
+}
+
+impl Module {
+	const ALL: [Module; 6] = [
+		Module::Author,
+		Module::System,
+		Module::Chain,
+		Module::State,
+		Module::Offchain,
+		Module::Grandpa,
+	];
+
+	/// The base code of the module, its error codes are above it.
+	pub const fn base_code(self) -> i64 {
+		match self {
+			Module::Author => 1000,
+			Module::System => 2000,
+			Module::Chain => 3000,
+			Module::State => 4000,
+			Module::Offchain => 5000,
+			Module::Grandpa => 6000,
+		}
+	}
+
+	/// The name of the module, as reported in [`ErrorData::module`].
+	pub fn name(self) -> &'static str {
+		match self {
+			Module::Author => "author",
+			Module::System => "system",
+			Module::Chain => "chain",
+			Module::State => "state",
+			Module::Offchain => "offchain",
+			Module::Grandpa => "grandpa",
+		}
+	}
+
+	/// The module owning the given server error code, if any.
+	pub fn of_code(code: i64) -> Option<Self> {
+		Self::ALL.iter().copied().find(|module| module.contains(code))
+	}
+
+	/// Whether the given code is in the range of this module.
+	pub fn contains(self, code: i64) -> bool {
+		code > self.base_code() && code < self.base_code() + MODULE_RANGE
+	}
+
+	/// Create an error of this module with the given code and `reason`.
+	///
+	/// `reason` is a short camel case identifier of the error, stable across releases.
+	pub fn error(self, code: i64, reason: &str, message: impl Into<String>) -> ModuleError {
+		debug_assert!(self.contains(code), "{} is not a code of the {:?} module", code, self);
+		ModuleError {
+			code,
+			message: message.into(),
+			data: ErrorData {
+				module: self.name().into(),
+				reason: reason.into(),
+				retryable: false,
+				details: None,
+			},
+		}
+	}
+}
+
+/// Machine-readable `data` of the errors returned by the RPC modules.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorData {
+	/// The name of the module which returned the error.
+	pub module: String,
+	/// A short identifier of the error within the module.
+	pub reason: String,
+	/// Whether the same request may succeed when retried later.
+	pub retryable: bool,
+	/// Free-form details about the error.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub details: Option<serde_json::Value>,
+}
 
-pub fn internal<E: ::std::fmt::Debug>(e: E) -> jsonrpc_core::Error {
+impl ErrorData {
+	/// The error data of the given error, if it was returned by one of the modules.
+	pub fn of(error: &rpc::Error) -> Option<Self> {
+		error.data.clone().and_then(|data| serde_json::from_value(data).ok())
+	}
+}
+
+/// An error of an RPC module, see [`Module::error`].
+#[derive(Debug)]
+pub struct ModuleError {
+	code: i64,
+	message: String,
+	data: ErrorData,
+}
+
+impl ModuleError {
+	/// Mark the error as retryable.
+	pub fn retryable(mut self) -> Self {
+		self.data.retryable = true;
+		self
+	}
+
+	/// Attach free-form details to the error.
+	pub fn details(mut self, details: impl Into<serde_json::Value>) -> Self {
+		self.data.details = Some(details.into());
+		self
+	}
+}
+
+impl From<ModuleError> for rpc::Error {
+	fn from(e: ModuleError) -> Self {
+		rpc::Error {
+			code: rpc::ErrorCode::ServerError(e.code),
+			message: e.message,
+			data: serde_json::to_value(e.data).ok(),
+		}
+	}
+}
+
+/// An unexpected error of the given module, e.g. a client error.
+///
+/// Those are reported as internal errors and considered retryable since they don't depend on
+/// the request itself.
+pub fn internal<E: ::std::fmt::Debug>(module: Module, e: E) -> rpc::Error {
 	warn!("Unknown error: {:?}", e);
-	jsonrpc_core::Error {
-		code: jsonrpc_core::ErrorCode::InternalError,
+	let data = ErrorData {
+		module: module.name().into(),
+		reason: "internal".into(),
+		retryable: true,
+		details: Some(format!("{:?}", e).into()),
+	};
+	rpc::Error {
+		code: rpc::ErrorCode::InternalError,
 		message: "Unknown error occurred".into(),
-		data: Some(format!("{:?}", e).into()),
+		data: serde_json::to_value(data).ok(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn module_ranges_do_not_overlap() {
+		for module in Module::ALL.iter().copied() {
+			assert!(!module.contains(module.base_code()));
+			assert_eq!(Module::of_code(module.base_code() + 1), Some(module));
+			assert_eq!(Module::of_code(module.base_code() + MODULE_RANGE - 1), Some(module));
+		}
+		assert_eq!(Module::of_code(1), None);
+	}
+
+	#[test]
+	fn module_errors_carry_error_data() {
+		let error: rpc::Error =
+			Module::State.error(4002, "invalidCount", "count exceeds maximum value").into();
+
+		assert_eq!(error.code, rpc::ErrorCode::ServerError(4002));
+		assert_eq!(
+			error.data,
+			Some(serde_json::json!({
+				"module": "state",
+				"reason": "invalidCount",
+				"retryable": false,
+			})),
+		);
+
+		let error: rpc::Error = Module::Author
+			.error(1011, "unknownValidity", "Unknown")
+			.retryable()
+			.details(42)
+			.into();
+		assert_eq!(
+			ErrorData::of(&error),
+			Some(ErrorData {
+				module: "author".into(),
+				reason: "unknownValidity".into(),
+				retryable: true,
+				details: Some(42.into()),
+			}),
+		);
 	}
 }
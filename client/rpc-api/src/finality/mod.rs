@@ -0,0 +1,87 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Substrate finality API.
+//!
+//! Streams the justifications of finalized blocks, regardless of the finality gadget that
+//! produced them.
+
+use jsonrpc_core::Result as RpcResult;
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId};
+use serde::{Deserialize, Serialize};
+use sp_core::Bytes;
+
+pub use self::gen_client::Client as FinalityClient;
+
+/// A justification of a finalized block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinalityProof<Hash> {
+	/// Hash of the finalized block.
+	pub block_hash: Hash,
+	/// Id of the consensus engine the justification belongs to.
+	pub engine_id: Bytes,
+	/// The encoded justification.
+	pub justification: Bytes,
+}
+
+/// Substrate finality RPC API
+#[rpc]
+pub trait FinalityApi<Hash> {
+	/// RPC metadata
+	type Metadata;
+
+	/// Subscribe to the justifications of finalized blocks.
+	///
+	/// A notification is sent for every justification of every finalized block, blocks
+	/// finalized without a justification are skipped.
+	#[pubsub(subscription = "finality_proofs", subscribe, name = "finality_subscribeProofs")]
+	fn subscribe_proofs(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<FinalityProof<Hash>>,
+	);
+
+	/// Unsubscribe from justifications of finalized blocks.
+	#[pubsub(subscription = "finality_proofs", unsubscribe, name = "finality_unsubscribeProofs")]
+	fn unsubscribe_proofs(
+		&self,
+		metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> RpcResult<bool>;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn should_serialize_finality_proof() {
+		let proof = FinalityProof {
+			block_hash: 5u64,
+			engine_id: Bytes(b"FRNK".to_vec()),
+			justification: Bytes(vec![1, 2, 3]),
+		};
+
+		assert_eq!(
+			serde_json::to_string(&proof).unwrap(),
+			r#"{"blockHash":5,"engineId":"0x46524e4b","justification":"0x010203"}"#,
+		);
+	}
+}
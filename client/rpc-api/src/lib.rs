@@ -22,19 +22,20 @@
 
 #![warn(missing_docs)]
 
-mod errors;
 mod helpers;
 mod metadata;
 mod policy;
 
 pub use helpers::Receiver;
 pub use jsonrpc_core::IoHandlerExtension as RpcExtension;
-pub use metadata::Metadata;
+pub use metadata::{Metadata, SubscriptionGuard, SubscriptionsMetadata};
 pub use policy::{DenyUnsafe, UnsafeRpcError};
 
 pub mod author;
 pub mod chain;
 pub mod child_state;
+pub mod errors;
+pub mod finality;
 pub mod offchain;
 pub mod state;
 pub mod system;
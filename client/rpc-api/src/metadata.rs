@@ -17,7 +17,10 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 //! RPC Metadata
-use std::sync::Arc;
+use std::sync::{
+	atomic::{AtomicUsize, Ordering},
+	Arc,
+};
 
 use futures::channel::mpsc;
 use jsonrpc_pubsub::{PubSubMetadata, Session};
@@ -30,6 +33,7 @@ use jsonrpc_pubsub::{PubSubMetadata, Session};
 #[derive(Default, Clone)]
 pub struct Metadata {
 	session: Option<Arc<Session>>,
+	subscriptions: Arc<AtomicUsize>,
 }
 
 impl jsonrpc_core::Metadata for Metadata {}
@@ -39,10 +43,29 @@ impl PubSubMetadata for Metadata {
 	}
 }
 
+impl SubscriptionsMetadata for Metadata {
+	fn open_subscriptions(&self) -> Option<usize> {
+		self.session.as_ref().map(|_| self.subscriptions.load(Ordering::SeqCst))
+	}
+}
+
 impl Metadata {
 	/// Create new `Metadata` with session (Pub/Sub) support.
 	pub fn new(transport: mpsc::UnboundedSender<String>) -> Self {
-		Metadata { session: Some(Arc::new(Session::new(transport))) }
+		Metadata {
+			session: Some(Arc::new(Session::new(transport))),
+			subscriptions: Default::default(),
+		}
+	}
+
+	/// Account a subscription of the connection as open until the returned guard is dropped.
+	///
+	/// The guard is meant to be moved into the future feeding the sink of the subscription, so
+	/// that the subscription is released however it ends, unsubscribed by the client or closed by
+	/// the server.
+	pub fn open_subscription(&self) -> SubscriptionGuard {
+		self.subscriptions.fetch_add(1, Ordering::SeqCst);
+		SubscriptionGuard(self.subscriptions.clone())
 	}
 
 	/// Create new `Metadata` for tests.
@@ -58,3 +81,41 @@ impl From<mpsc::UnboundedSender<String>> for Metadata {
 		Self::new(sender)
 	}
 }
+
+/// Metadata keeping track of the subscriptions open on its connection.
+pub trait SubscriptionsMetadata: PubSubMetadata {
+	/// Number of subscriptions open on the connection, `None` if the transport has no session.
+	fn open_subscriptions(&self) -> Option<usize>;
+}
+
+/// Keeps a subscription accounted as open by [`Metadata::open_subscription`] until dropped.
+#[must_use]
+pub struct SubscriptionGuard(Arc<AtomicUsize>);
+
+impl Drop for SubscriptionGuard {
+	fn drop(&mut self) {
+		self.0.fetch_sub(1, Ordering::SeqCst);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn subscriptions_are_open_until_the_guard_is_dropped() {
+		assert_eq!(Metadata::default().open_subscriptions(), None);
+
+		let (_rx, meta) = Metadata::new_test();
+		assert_eq!(meta.open_subscriptions(), Some(0));
+
+		let first = meta.open_subscription();
+		let second = meta.clone().open_subscription();
+		assert_eq!(meta.open_subscriptions(), Some(2));
+
+		drop(first);
+		assert_eq!(meta.open_subscriptions(), Some(1));
+		drop(second);
+		assert_eq!(meta.open_subscriptions(), Some(0));
+	}
+}
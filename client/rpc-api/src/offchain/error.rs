@@ -18,6 +18,7 @@
 
 //! Offchain RPC errors.
 
+use crate::errors::Module;
 use jsonrpc_core as rpc;
 
 /// Offchain RPC Result type.
@@ -29,22 +30,44 @@ pub enum Error {
 	/// Unavailable storage kind error.
 	#[error("This storage kind is not available yet.")]
 	UnavailableStorageKind,
+	/// The storage can't enumerate its keys.
+	#[error("This storage doesn't support enumerating its keys.")]
+	UnsupportedEnumeration,
+	/// Provided count exceeds maximum value.
+	#[error("count exceeds maximum value. value: {}, max: {}", .value, .max)]
+	InvalidCount {
+		/// Provided value
+		value: u32,
+		/// Maximum allowed value
+		max: u32,
+	},
 	/// Call to an unsafe RPC was denied.
 	#[error(transparent)]
 	UnsafeRpcCalled(#[from] crate::policy::UnsafeRpcError),
 }
 
 /// Base error code for all offchain errors.
-const BASE_ERROR: i64 = 5000;
+const BASE_ERROR: i64 = Module::Offchain.base_code();
 
 impl From<Error> for rpc::Error {
 	fn from(e: Error) -> Self {
 		match e {
-			Error::UnavailableStorageKind => rpc::Error {
-				code: rpc::ErrorCode::ServerError(BASE_ERROR + 1),
-				message: "This storage kind is not available yet".into(),
-				data: None,
-			},
+			Error::UnavailableStorageKind => Module::Offchain
+				.error(
+					BASE_ERROR + 1,
+					"unavailableStorageKind",
+					"This storage kind is not available yet",
+				)
+				.into(),
+			Error::UnsupportedEnumeration => Module::Offchain
+				.error(
+					BASE_ERROR + 2,
+					"unsupportedEnumeration",
+					"This storage doesn't support enumerating its keys",
+				)
+				.into(),
+			e @ Error::InvalidCount { .. } =>
+				Module::Offchain.error(BASE_ERROR + 3, "invalidCount", e.to_string()).into(),
 			Error::UnsafeRpcCalled(e) => e.into(),
 		}
 	}
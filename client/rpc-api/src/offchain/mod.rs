@@ -36,4 +36,16 @@ pub trait OffchainApi {
 	/// Get offchain local storage under given key and prefix.
 	#[rpc(name = "offchain_localStorageGet")]
 	fn get_local_storage(&self, kind: StorageKind, key: Bytes) -> Result<Option<Bytes>>;
+
+	/// Get offchain local storage entries whose key starts with the given prefix.
+	///
+	/// Returns up to `count` entries ordered by key, following `start_key` if it is given.
+	#[rpc(name = "offchain_localStorageGetPaged")]
+	fn get_local_storage_paged(
+		&self,
+		kind: StorageKind,
+		prefix: Bytes,
+		count: u32,
+		start_key: Option<Bytes>,
+	) -> Result<Vec<(Bytes, Bytes)>>;
 }
@@ -18,7 +18,7 @@
 
 //! State RPC errors.
 
-use crate::errors;
+use crate::errors::{self, Module};
 use jsonrpc_core as rpc;
 
 /// State RPC Result type.
@@ -57,22 +57,16 @@ pub enum Error {
 }
 
 /// Base code for all state errors.
-const BASE_ERROR: i64 = 4000;
+const BASE_ERROR: i64 = Module::State.base_code();
 
 impl From<Error> for rpc::Error {
 	fn from(e: Error) -> Self {
 		match e {
-			Error::InvalidBlockRange { .. } => rpc::Error {
-				code: rpc::ErrorCode::ServerError(BASE_ERROR + 1),
-				message: format!("{}", e),
-				data: None,
-			},
-			Error::InvalidCount { .. } => rpc::Error {
-				code: rpc::ErrorCode::ServerError(BASE_ERROR + 2),
-				message: format!("{}", e),
-				data: None,
-			},
-			e => errors::internal(e),
+			Error::InvalidBlockRange { .. } =>
+				Module::State.error(BASE_ERROR + 1, "invalidBlockRange", e.to_string()).into(),
+			Error::InvalidCount { .. } =>
+				Module::State.error(BASE_ERROR + 2, "invalidCount", e.to_string()).into(),
+			e => errors::internal(Module::State, e),
 		}
 	}
 }
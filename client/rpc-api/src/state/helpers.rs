@@ -30,3 +30,13 @@ pub struct ReadProof<Hash> {
 	/// A proof used to prove that storage entries are included in the storage trie
 	pub proof: Vec<Bytes>,
 }
+
+/// Location of an event returned by `state_queryEventsByTopic`.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventLocation<Hash> {
+	/// Hash of the block which deposited the event
+	pub block: Hash,
+	/// Index of the event among the events of the block
+	pub index: u32,
+}
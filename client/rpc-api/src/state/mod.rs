@@ -31,7 +31,10 @@ use sp_core::{
 };
 use sp_version::RuntimeVersion;
 
-pub use self::{gen_client::Client as StateClient, helpers::ReadProof};
+pub use self::{
+	gen_client::Client as StateClient,
+	helpers::{EventLocation, ReadProof},
+};
 
 /// Substrate state API
 #[rpc]
@@ -118,6 +121,19 @@ pub trait StateApi<Hash> {
 		hash: Option<Hash>,
 	) -> FutureResult<ReadProof<Hash>>;
 
+	/// Query the events deposited under `topic` in the blocks from `from` to `to` (the best block
+	/// if `None`), both included.
+	///
+	/// The events are looked up in the topic index of the `System` pallet, only the events
+	/// deposited with topics are found.
+	#[rpc(name = "state_queryEventsByTopic")]
+	fn query_events_by_topic(
+		&self,
+		topic: Hash,
+		from: Hash,
+		to: Option<Hash>,
+	) -> FutureResult<Vec<EventLocation<Hash>>>;
+
 	/// New runtime version subscription
 	#[pubsub(
 		subscription = "state_runtimeVersion",
@@ -294,6 +310,8 @@ pub trait StateApi<Hash> {
 	/// an empty string is specified, events will be filtered by method (so non-method events will
 	/// **not** show up).
 	///
+	/// Omitting `storage_keys` or `methods` is equivalent to specifying an empty string.
+	///
 	/// Additionally you would want to track the extrinsic index, which is under the
 	/// `:extrinsic_index` key. The key for this would be the aforementioned string as bytes
 	/// in hex: `3a65787472696e7369635f696e646578`.
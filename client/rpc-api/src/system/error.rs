@@ -18,7 +18,7 @@
 
 //! System RPC module errors.
 
-use crate::system::helpers::Health;
+use crate::{errors::Module, system::helpers::Health};
 use jsonrpc_core as rpc;
 
 /// System RPC Result type.
@@ -33,24 +33,35 @@ pub enum Error {
 	/// Peer argument is malformatted.
 	#[error("{0}")]
 	MalformattedPeerArg(String),
+	/// The runtime version at the best block could not be retrieved.
+	#[error("Runtime version is unavailable: {0}")]
+	RuntimeVersionUnavailable(String),
+	/// The protocol argument doesn't match a registered notifications protocol.
+	#[error("{0}")]
+	UnknownProtocol(String),
 }
 
 /// Base code for all system errors.
-const BASE_ERROR: i64 = 2000;
+const BASE_ERROR: i64 = Module::System.base_code();
 
 impl From<Error> for rpc::Error {
 	fn from(e: Error) -> Self {
+		let system = Module::System;
 		match e {
-			Error::NotHealthy(ref h) => rpc::Error {
-				code: rpc::ErrorCode::ServerError(BASE_ERROR + 1),
-				message: format!("{}", e),
-				data: serde_json::to_value(h).ok(),
-			},
-			Error::MalformattedPeerArg(ref e) => rpc::Error {
-				code: rpc::ErrorCode::ServerError(BASE_ERROR + 2),
-				message: e.clone(),
-				data: None,
+			Error::NotHealthy(ref h) => {
+				let error = system.error(BASE_ERROR + 1, "notHealthy", e.to_string()).retryable();
+				match serde_json::to_value(h) {
+					Ok(details) => error.details(details).into(),
+					Err(_) => error.into(),
+				}
 			},
+			Error::MalformattedPeerArg(e) =>
+				system.error(BASE_ERROR + 2, "malformattedPeerArg", e).into(),
+			Error::RuntimeVersionUnavailable(_) => system
+				.error(BASE_ERROR + 3, "runtimeVersionUnavailable", e.to_string())
+				.retryable()
+				.into(),
+			Error::UnknownProtocol(e) => system.error(BASE_ERROR + 4, "unknownProtocol", e).into(),
 		}
 	}
 }
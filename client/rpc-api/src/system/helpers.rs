@@ -18,8 +18,9 @@
 
 //! Substrate system API helpers.
 
-use sc_chain_spec::{ChainType, Properties};
+use sc_chain_spec::{ChainType, GenesisHashes, Properties};
 use serde::{Deserialize, Serialize};
+use sp_version::RuntimeVersion;
 use std::fmt;
 
 /// Running node's static details.
@@ -35,6 +36,8 @@ pub struct SystemInfo {
 	pub properties: Properties,
 	/// The type of this chain.
 	pub chain_type: ChainType,
+	/// Hashes of the genesis state of the chain, if they could be determined.
+	pub genesis_hashes: Option<GenesisHashes>,
 }
 
 /// Health struct returned by the RPC
@@ -71,6 +74,36 @@ pub struct PeerInfo<Hash, Number> {
 	pub best_number: Number,
 }
 
+/// Detailed information about a connected peer, as seen by the peer set manager.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerDetails<Hash, Number> {
+	/// Peer ID
+	pub peer_id: String,
+	/// Reputation of the peer. Reputations are global and not specific to a protocol.
+	pub reputation: i32,
+	/// Roles, if the peer is connected on the block announces protocol.
+	pub roles: Option<String>,
+	/// Peer best block hash, if the peer is connected on the block announces protocol.
+	pub best_hash: Option<Hash>,
+	/// Peer best block number, if the peer is connected on the block announces protocol.
+	pub best_number: Option<Number>,
+	/// What the node is currently syncing from the peer.
+	pub sync_state: Option<String>,
+	/// Notifications protocols the peer is connected on.
+	pub protocols: Vec<PeerProtocol>,
+}
+
+/// State of a peer on a notifications protocol.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerProtocol {
+	/// Name of the notifications protocol.
+	pub name: String,
+	/// Whether the peer is a reserved peer of the protocol.
+	pub reserved: bool,
+}
+
 /// The role the node is running as
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub enum NodeRole {
@@ -94,6 +127,18 @@ pub struct SyncState<Number> {
 	#[serde(default = "Default::default", skip_serializing_if = "Option::is_none")]
 	pub highest_block: Option<Number>,
 }
+
+/// Version details of the node and of the runtime at its best block.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionDetails {
+	/// Implementation version of the node.
+	pub node_version: String,
+	/// Version of the runtime at the best block.
+	pub runtime_version: RuntimeVersion,
+	/// Names of the optional features the runtime was compiled with, e.g. `try-runtime`.
+	pub runtime_features: Vec<String>,
+}
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -125,6 +170,23 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn should_serialize_peer_details() {
+		assert_eq!(
+			::serde_json::to_string(&PeerDetails {
+				peer_id: "2".into(),
+				reputation: -10,
+				roles: Some("a".into()),
+				best_hash: Some(5u32),
+				best_number: Some(6u32),
+				sync_state: Some("Available".into()),
+				protocols: vec![PeerProtocol { name: "/foo/1".into(), reserved: true }],
+			})
+			.unwrap(),
+			r#"{"peerId":"2","reputation":-10,"roles":"a","bestHash":5,"bestNumber":6,"syncState":"Available","protocols":[{"name":"/foo/1","reserved":true}]}"#,
+		);
+	}
+
 	#[test]
 	fn should_serialize_sync_state() {
 		assert_eq!(
@@ -29,7 +29,10 @@ use self::error::Result as SystemResult;
 
 pub use self::{
 	gen_client::Client as SystemClient,
-	helpers::{Health, NodeRole, PeerInfo, SyncState, SystemInfo},
+	helpers::{
+		Health, NodeRole, PeerDetails, PeerInfo, PeerProtocol, SyncState, SystemInfo,
+		VersionDetails,
+	},
 };
 
 /// Substrate system RPC API
@@ -55,6 +58,13 @@ pub trait SystemApi<Hash, Number> {
 	#[rpc(name = "system_properties")]
 	fn system_properties(&self) -> SystemResult<sc_chain_spec::Properties>;
 
+	/// Get the genesis state root and the hash of the genesis runtime of the chain.
+	///
+	/// These can be compared with the hashes recorded by `build-spec --raw`, to check that the
+	/// node runs the intended chain spec.
+	#[rpc(name = "system_chainSpecHashes")]
+	fn system_chain_spec_hashes(&self) -> SystemResult<Option<sc_chain_spec::GenesisHashes>>;
+
 	/// Return health status of the node.
 	///
 	/// Node is considered healthy if it is:
@@ -78,6 +88,23 @@ pub trait SystemApi<Hash, Number> {
 	#[rpc(name = "system_peers", returns = "Vec<PeerInfo<Hash, Number>>")]
 	fn system_peers(&self) -> BoxFuture<jsonrpc_core::Result<Vec<PeerInfo<Hash, Number>>>>;
 
+	/// Returns currently connected peers, along with their reputation and the notifications
+	/// protocols they are connected on.
+	#[rpc(name = "system_peersDetailed", returns = "Vec<PeerDetails<Hash, Number>>")]
+	fn system_peers_detailed(
+		&self,
+	) -> BoxFuture<jsonrpc_core::Result<Vec<PeerDetails<Hash, Number>>>>;
+
+	/// Returns the reputation of the given base58-encoded PeerId, or `null` if the node doesn't
+	/// know the peer.
+	///
+	/// Peers whose reputation falls under the ban threshold are disconnected and refused.
+	#[rpc(name = "system_peerReputation", returns = "Option<i32>")]
+	fn system_peer_reputation(
+		&self,
+		peer_id: String,
+	) -> BoxFuture<jsonrpc_core::Result<Option<i32>>>;
+
 	/// Returns current state of the network.
 	///
 	/// **Warning**: This API is not stable. Please do not programmatically interpret its output,
@@ -92,15 +119,26 @@ pub trait SystemApi<Hash, Number> {
 	///
 	/// `/ip4/198.51.100.19/tcp/30333/p2p/QmSk5HQbn6LhUwDiNMseVUjuRYhEtYj4aUZ6WfWoGURpdV`
 	/// is an example of a valid, passing multiaddr with PeerId attached.
+	///
+	/// If `protocol` is given, the peer is reserved on that notifications protocol (e.g. the
+	/// GRANDPA or transactions protocol) instead of the default syncing one.
 	#[rpc(name = "system_addReservedPeer", returns = "()")]
-	fn system_add_reserved_peer(&self, peer: String) -> BoxFuture<Result<(), jsonrpc_core::Error>>;
+	fn system_add_reserved_peer(
+		&self,
+		peer: String,
+		protocol: Option<String>,
+	) -> BoxFuture<Result<(), jsonrpc_core::Error>>;
 
 	/// Remove a reserved peer. Returns the empty string or an error. The string
 	/// should encode only the PeerId e.g. `QmSk5HQbn6LhUwDiNMseVUjuRYhEtYj4aUZ6WfWoGURpdV`.
+	///
+	/// If `protocol` is given, the peer is removed from the reserved peers of that notifications
+	/// protocol instead of the default syncing one.
 	#[rpc(name = "system_removeReservedPeer", returns = "()")]
 	fn system_remove_reserved_peer(
 		&self,
 		peer_id: String,
+		protocol: Option<String>,
 	) -> BoxFuture<Result<(), jsonrpc_core::Error>>;
 
 	/// Returns the list of reserved peers
@@ -116,6 +154,14 @@ pub trait SystemApi<Hash, Number> {
 	#[rpc(name = "system_syncState", returns = "SyncState<Number>")]
 	fn system_sync_state(&self) -> Receiver<SyncState<Number>>;
 
+	/// Returns the version of the node and of the runtime at the best block, along with the
+	/// optional features the runtime was compiled with.
+	///
+	/// This allows to verify that a production runtime doesn't carry testing features, like
+	/// `try-runtime`.
+	#[rpc(name = "system_versionDetails", returns = "VersionDetails")]
+	fn system_version_details(&self) -> BoxFuture<jsonrpc_core::Result<VersionDetails>>;
+
 	/// Adds the supplied directives to the current log filter
 	///
 	/// The syntax is identical to the CLI `<target>=<level>`:
@@ -20,12 +20,13 @@
 
 #![warn(missing_docs)]
 
+mod limits;
 mod middleware;
 
 use jsonrpc_core::{IoHandlerExtension, MetaIoHandler};
 use log::error;
 use prometheus_endpoint::{register, Counter, PrometheusError, Registry, U64};
-use pubsub::PubSubMetadata;
+use sc_rpc_api::SubscriptionsMetadata;
 use std::io;
 
 const MEGABYTE: usize = 1024 * 1024;
@@ -39,10 +40,13 @@ const WS_MAX_CONNECTIONS: usize = 100;
 /// The RPC IoHandler containing all requested APIs.
 pub type RpcHandler<T> = pubsub::PubSubHandler<T, RpcMiddleware>;
 
+pub use limits::{
+	RpcLimits, RATE_LIMITED_ERROR, RESPONSE_TOO_LARGE_ERROR, TOO_MANY_SUBSCRIPTIONS_ERROR,
+};
 pub use middleware::{method_names, RpcMetrics, RpcMiddleware};
 
 /// Construct rpc `IoHandler`
-pub fn rpc_handler<M: PubSubMetadata>(
+pub fn rpc_handler<M: SubscriptionsMetadata>(
 	extension: impl IoHandlerExtension<M>,
 	rpc_middleware: RpcMiddleware,
 ) -> RpcHandler<M> {
@@ -125,7 +129,7 @@ impl ws::SessionStats for ServerMetrics {
 }
 
 /// Start HTTP server listening on given address.
-pub fn start_http<M: pubsub::PubSubMetadata + Default + Unpin>(
+pub fn start_http<M: SubscriptionsMetadata + Default + Unpin>(
 	addr: &std::net::SocketAddr,
 	cors: Option<&Vec<String>>,
 	io: RpcHandler<M>,
@@ -148,7 +152,7 @@ pub fn start_http<M: pubsub::PubSubMetadata + Default + Unpin>(
 }
 
 /// Start IPC server listening on given path.
-pub fn start_ipc<M: pubsub::PubSubMetadata + Default>(
+pub fn start_ipc<M: SubscriptionsMetadata + Default>(
 	addr: &str,
 	io: RpcHandler<M>,
 	server_metrics: ServerMetrics,
@@ -165,7 +169,7 @@ pub fn start_ipc<M: pubsub::PubSubMetadata + Default>(
 
 /// Start WS server listening on given address.
 pub fn start_ws<
-	M: pubsub::PubSubMetadata + From<futures::channel::mpsc::UnboundedSender<String>>,
+	M: SubscriptionsMetadata + From<futures::channel::mpsc::UnboundedSender<String>>,
 >(
 	addr: &std::net::SocketAddr,
 	max_connections: Option<usize>,
@@ -0,0 +1,251 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Per-connection resource limits of the RPC servers.
+
+use std::{
+	collections::HashMap,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+use jsonrpc_core::{Error, ErrorCode, Output};
+use parking_lot::Mutex;
+use pubsub::Session;
+
+/// Error code returned when a connection exceeds its call rate.
+pub const RATE_LIMITED_ERROR: i64 = -32097;
+/// Error code returned when a connection exceeds its number of subscriptions.
+pub const TOO_MANY_SUBSCRIPTIONS_ERROR: i64 = -32098;
+/// Error code returned when a response exceeds the maximum response size.
+pub const RESPONSE_TOO_LARGE_ERROR: i64 = -32099;
+
+/// Resource limits applied to each persistent (WS or IPC) RPC connection.
+///
+/// HTTP requests are not tied to a connection and are only subject to `max_response_size`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RpcLimits {
+	/// Maximum number of calls a connection may make per second. `None` if unlimited.
+	pub max_calls_per_second: Option<u32>,
+	/// Maximum number of subscriptions a connection may have open at the same time, as
+	/// accounted by [`sc_rpc_api::Metadata::open_subscription`]. `None` if unlimited.
+	pub max_subscriptions_per_connection: Option<u32>,
+	/// Maximum size in bytes of the response to a single call. `None` if unlimited.
+	pub max_response_size: Option<usize>,
+}
+
+/// How a call affects the subscriptions of a connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SubscriptionCall {
+	/// The call opens a subscription.
+	Subscribe,
+	/// The call closes a subscription.
+	Unsubscribe,
+	/// The call is not related to subscriptions.
+	None,
+}
+
+impl SubscriptionCall {
+	/// Classify the given method, following the naming convention of the Substrate RPC APIs,
+	/// e.g. `chain_subscribeNewHeads`, `author_submitAndWatchExtrinsic` or
+	/// `chainHead_unstable_follow` and their `unsubscribe`, `unwatch` and `unfollow`
+	/// counterparts.
+	pub(crate) fn of(method: &str) -> Self {
+		let parts = || method.split('_');
+		if parts().any(|part| {
+			part.starts_with("unsubscribe") || part.starts_with("unwatch") || part == "unfollow"
+		}) {
+			SubscriptionCall::Unsubscribe
+		} else if parts().any(|part| {
+			part.starts_with("subscribe") || part.contains("AndWatch") || part == "follow"
+		}) {
+			SubscriptionCall::Subscribe
+		} else {
+			SubscriptionCall::None
+		}
+	}
+}
+
+/// The usage of a single connection.
+#[derive(Debug, Default)]
+struct ConnectionUsage {
+	/// Start of the current rate limiting window.
+	window_start: Option<Instant>,
+	/// Number of calls made in the current window.
+	calls: u32,
+}
+
+/// Tracks the usage of all connections of a server against the [`RpcLimits`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ConnectionLimiter {
+	limits: RpcLimits,
+	/// Usage of each connection, keyed by the address of its session.
+	connections: Arc<Mutex<HashMap<usize, ConnectionUsage>>>,
+}
+
+impl ConnectionLimiter {
+	/// Create a new limiter enforcing the given `limits`.
+	pub(crate) fn new(limits: RpcLimits) -> Self {
+		Self { limits, connections: Default::default() }
+	}
+
+	/// Account a call of `session`, which has `open_subscriptions` subscriptions open, returning
+	/// an error if the call exceeds the limits.
+	pub(crate) fn start_call(
+		&self,
+		session: &Arc<Session>,
+		subscription_call: SubscriptionCall,
+		open_subscriptions: usize,
+	) -> Result<(), Error> {
+		if self.limits.max_calls_per_second.is_none() &&
+			self.limits.max_subscriptions_per_connection.is_none()
+		{
+			return Ok(())
+		}
+
+		let key = Arc::as_ptr(session) as usize;
+		let mut connections = self.connections.lock();
+		let usage = connections.entry(key).or_insert_with(|| {
+			// Forget the usage once the connection is closed.
+			let connections = Arc::downgrade(&self.connections);
+			session.on_drop(move || {
+				if let Some(connections) = connections.upgrade() {
+					connections.lock().remove(&key);
+				}
+			});
+			Default::default()
+		});
+
+		if let Some(max_calls) = self.limits.max_calls_per_second {
+			let now = Instant::now();
+			match usage.window_start {
+				Some(start) if now.duration_since(start) < Duration::from_secs(1) => (),
+				_ => {
+					usage.window_start = Some(now);
+					usage.calls = 0;
+				},
+			}
+
+			if usage.calls >= max_calls {
+				return Err(error(RATE_LIMITED_ERROR, "Too many calls, try again later"))
+			}
+			usage.calls += 1;
+		}
+
+		if let (SubscriptionCall::Subscribe, Some(max_subscriptions)) =
+			(subscription_call, self.limits.max_subscriptions_per_connection)
+		{
+			if open_subscriptions >= max_subscriptions as usize {
+				return Err(error(TOO_MANY_SUBSCRIPTIONS_ERROR, "Too many subscriptions"))
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Returns an error if the serialized `output` exceeds the maximum response size.
+	pub(crate) fn check_response_size(&self, output: &Output) -> Result<(), Error> {
+		let max_size = match self.limits.max_response_size {
+			Some(max_size) => max_size,
+			None => return Ok(()),
+		};
+
+		let size = serde_json::to_vec(output)
+			.expect("Serialization of RPC output is infallible; qed")
+			.len();
+		if size > max_size {
+			Err(error(RESPONSE_TOO_LARGE_ERROR, "Response is too large"))
+		} else {
+			Ok(())
+		}
+	}
+}
+
+fn error(code: i64, message: &str) -> Error {
+	Error { code: ErrorCode::ServerError(code), message: message.into(), data: None }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::channel::mpsc;
+
+	fn session() -> Arc<Session> {
+		let (tx, _rx) = mpsc::unbounded();
+		Arc::new(Session::new(tx))
+	}
+
+	#[test]
+	fn subscription_calls_are_classified() {
+		assert_eq!(SubscriptionCall::of("chain_subscribeNewHeads"), SubscriptionCall::Subscribe);
+		assert_eq!(SubscriptionCall::of("subscribe_newHead"), SubscriptionCall::Subscribe);
+		assert_eq!(
+			SubscriptionCall::of("author_submitAndWatchExtrinsic"),
+			SubscriptionCall::Subscribe,
+		);
+		assert_eq!(SubscriptionCall::of("chainHead_unstable_follow"), SubscriptionCall::Subscribe);
+		assert_eq!(
+			SubscriptionCall::of("chain_unsubscribeNewHeads"),
+			SubscriptionCall::Unsubscribe,
+		);
+		assert_eq!(SubscriptionCall::of("author_unwatchExtrinsic"), SubscriptionCall::Unsubscribe);
+		assert_eq!(
+			SubscriptionCall::of("chainHead_unstable_unfollow"),
+			SubscriptionCall::Unsubscribe,
+		);
+		assert_eq!(SubscriptionCall::of("chain_getBlock"), SubscriptionCall::None);
+	}
+
+	#[test]
+	fn calls_per_second_are_limited_per_connection() {
+		let limiter = ConnectionLimiter::new(RpcLimits {
+			max_calls_per_second: Some(2),
+			..Default::default()
+		});
+		let (first, second) = (session(), session());
+
+		assert!(limiter.start_call(&first, SubscriptionCall::None, 0).is_ok());
+		assert!(limiter.start_call(&first, SubscriptionCall::None, 0).is_ok());
+		assert_eq!(
+			limiter.start_call(&first, SubscriptionCall::None, 0).unwrap_err().code,
+			ErrorCode::ServerError(RATE_LIMITED_ERROR),
+		);
+		assert!(limiter.start_call(&second, SubscriptionCall::None, 0).is_ok());
+	}
+
+	#[test]
+	fn subscriptions_are_limited_per_connection() {
+		let limiter = ConnectionLimiter::new(RpcLimits {
+			max_subscriptions_per_connection: Some(1),
+			..Default::default()
+		});
+		let session = session();
+
+		assert!(limiter.start_call(&session, SubscriptionCall::Subscribe, 0).is_ok());
+		assert_eq!(
+			limiter.start_call(&session, SubscriptionCall::Subscribe, 1).unwrap_err().code,
+			ErrorCode::ServerError(TOO_MANY_SUBSCRIPTIONS_ERROR),
+		);
+		assert!(limiter.start_call(&session, SubscriptionCall::None, 1).is_ok());
+		assert!(limiter.start_call(&session, SubscriptionCall::Unsubscribe, 1).is_ok());
+
+		// the usage is forgotten once the connection is closed.
+		drop(session);
+		assert!(limiter.connections.lock().is_empty());
+	}
+}
@@ -20,15 +20,22 @@
 
 use std::collections::HashSet;
 
-use jsonrpc_core::{FutureOutput, FutureResponse, Metadata, Middleware as RequestMiddleware};
+use jsonrpc_core::{FutureOutput, FutureResponse, Middleware as RequestMiddleware};
 use prometheus_endpoint::{
-	register, CounterVec, HistogramOpts, HistogramVec, Opts, PrometheusError, Registry, U64,
+	exponential_buckets, register, CounterVec, Gauge, GaugeVec, HistogramOpts, HistogramVec, Opts,
+	PrometheusError, Registry, U64,
 };
 
-use futures::{future::Either, Future, FutureExt};
-use pubsub::PubSubMetadata;
+use futures::{
+	future::{self, Either},
+	Future, FutureExt,
+};
+use sc_rpc_api::SubscriptionsMetadata;
 
-use crate::RpcHandler;
+use crate::{
+	limits::{ConnectionLimiter, SubscriptionCall},
+	RpcHandler, RpcLimits,
+};
 
 /// Metrics for RPC middleware
 #[derive(Debug, Clone)]
@@ -38,6 +45,7 @@ pub struct RpcMetrics {
 	calls_time: HistogramVec,
 	calls_started: CounterVec<U64>,
 	calls_finished: CounterVec<U64>,
+	calls_in_flight: GaugeVec<U64>,
 }
 
 impl RpcMetrics {
@@ -70,7 +78,9 @@ impl RpcMetrics {
 						HistogramOpts::new(
 							"rpc_calls_time",
 							"Total time [μs] of processed RPC calls",
-						),
+						)
+						// From 10μs to ~2.6s.
+						.buckets(exponential_buckets(10.0, 4.0, 10)?),
 						&["protocol", "method"],
 					)?,
 					r,
@@ -95,6 +105,16 @@ impl RpcMetrics {
 					)?,
 					r,
 				)?,
+				calls_in_flight: register(
+					GaugeVec::new(
+						Opts::new(
+							"rpc_calls_in_flight",
+							"Number of RPC calls (unique un-batched requests) being processed",
+						),
+						&["protocol", "method"],
+					)?,
+					r,
+				)?,
 			}))
 		} else {
 			Ok(None)
@@ -106,7 +126,7 @@ impl RpcMetrics {
 pub fn method_names<F, M, E>(gen_handler: F) -> Result<HashSet<String>, E>
 where
 	F: FnOnce(RpcMiddleware) -> Result<RpcHandler<M>, E>,
-	M: PubSubMetadata,
+	M: SubscriptionsMetadata,
 {
 	let io = gen_handler(RpcMiddleware::new(None, HashSet::new(), "dummy"))?;
 	Ok(io.iter().map(|x| x.0.clone()).collect())
@@ -117,6 +137,7 @@ pub struct RpcMiddleware {
 	metrics: Option<RpcMetrics>,
 	known_rpc_method_names: HashSet<String>,
 	transport_label: String,
+	limiter: ConnectionLimiter,
 }
 
 impl RpcMiddleware {
@@ -129,11 +150,22 @@ impl RpcMiddleware {
 		known_rpc_method_names: HashSet<String>,
 		transport_label: &str,
 	) -> Self {
-		RpcMiddleware { metrics, known_rpc_method_names, transport_label: transport_label.into() }
+		RpcMiddleware {
+			metrics,
+			known_rpc_method_names,
+			transport_label: transport_label.into(),
+			limiter: Default::default(),
+		}
+	}
+
+	/// Enforce the given resource `limits` on the calls handled by this middleware.
+	pub fn with_limits(mut self, limits: RpcLimits) -> Self {
+		self.limiter = ConnectionLimiter::new(limits);
+		self
 	}
 }
 
-impl<M: Metadata> RequestMiddleware<M> for RpcMiddleware {
+impl<M: SubscriptionsMetadata> RequestMiddleware<M> for RpcMiddleware {
 	type Future = FutureResponse;
 	type CallFuture = FutureOutput;
 
@@ -186,10 +218,46 @@ impl<M: Metadata> RequestMiddleware<M> for RpcMiddleware {
 				.with_label_values(&[transport_label.as_str(), name.as_str()])
 				.inc();
 		}
+
+		let limiter = self.limiter.clone();
+		let session = meta.session();
+		let open_subscriptions = meta.open_subscriptions().unwrap_or_default();
+		let (subscription_call, failure) = match call {
+			jsonrpc_core::Call::MethodCall(ref method_call) => (
+				SubscriptionCall::of(&method_call.method),
+				Some((method_call.id.clone(), method_call.jsonrpc)),
+			),
+			_ => (SubscriptionCall::None, None),
+		};
+		if let (Some(session), Some((id, jsonrpc))) = (&session, &failure) {
+			if let Err(e) = limiter.start_call(session, subscription_call, open_subscriptions) {
+				log::debug!(target: "rpc", "[{}] {} call rejected: {}", transport_label, name, e);
+				return Either::Left(
+					future::ready(Some(jsonrpc_core::Output::from(Err(e), id.clone(), *jsonrpc)))
+						.boxed(),
+				)
+			}
+		}
+
+		let in_flight = metrics.as_ref().map(|metrics| {
+			InFlightCall::new(
+				metrics
+					.calls_in_flight
+					.with_label_values(&[transport_label.as_str(), name.as_str()]),
+			)
+		});
 		let r = next(call, meta);
 		Either::Left(
 			async move {
-				let r = r.await;
+				let mut r = r.await;
+				drop(in_flight);
+				if let Some((id, jsonrpc)) = failure {
+					let size_error =
+						r.as_ref().and_then(|output| limiter.check_response_size(output).err());
+					if let Some(e) = size_error {
+						r = Some(jsonrpc_core::Output::from(Err(e), id, jsonrpc));
+					}
+				}
 				let micros = start.elapsed().as_micros();
 				if let Some(ref metrics) = metrics {
 					metrics
@@ -201,7 +269,7 @@ impl<M: Metadata> RequestMiddleware<M> for RpcMiddleware {
 						.with_label_values(&[
 							transport_label.as_str(),
 							name.as_str(),
-							if is_success(&r) { "true" } else { "false" },
+							if is_success(&r) { "false" } else { "true" },
 						])
 						.inc();
 				}
@@ -219,6 +287,22 @@ impl<M: Metadata> RequestMiddleware<M> for RpcMiddleware {
 	}
 }
 
+/// Counts a call as in flight until it is dropped, including when the call is cancelled.
+struct InFlightCall(Gauge<U64>);
+
+impl InFlightCall {
+	fn new(gauge: Gauge<U64>) -> Self {
+		gauge.inc();
+		InFlightCall(gauge)
+	}
+}
+
+impl Drop for InFlightCall {
+	fn drop(&mut self) {
+		self.0.dec();
+	}
+}
+
 fn call_name<'a>(call: &'a jsonrpc_core::Call, known_methods: &HashSet<String>) -> &'a str {
 	// To prevent bloating metric with all invalid method names we filter them out here.
 	let only_known = |method: &'a String| {
@@ -242,3 +326,63 @@ fn is_success(output: &Option<jsonrpc_core::Output>) -> bool {
 		_ => false,
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::executor::block_on;
+	use jsonrpc_core::{MetaIoHandler, Metadata, Value};
+	use pubsub::{PubSubMetadata, Session};
+	use std::sync::Arc;
+
+	#[derive(Clone, Default)]
+	struct Meta;
+
+	impl Metadata for Meta {}
+
+	impl PubSubMetadata for Meta {
+		fn session(&self) -> Option<Arc<Session>> {
+			None
+		}
+	}
+
+	impl SubscriptionsMetadata for Meta {
+		fn open_subscriptions(&self) -> Option<usize> {
+			None
+		}
+	}
+
+	#[test]
+	fn calls_are_recorded_per_method() {
+		let registry = Registry::new();
+		let metrics = RpcMetrics::new(Some(&registry)).unwrap().unwrap();
+		let known_methods = ["test_ok", "test_err"].iter().map(|m| m.to_string()).collect();
+		let mut io = MetaIoHandler::with_middleware(RpcMiddleware::new(
+			Some(metrics.clone()),
+			known_methods,
+			"test",
+		));
+		io.add_sync_method("test_ok", |_| Ok(Value::Bool(true)));
+		io.add_sync_method("test_err", |_| Err(jsonrpc_core::Error::internal_error()));
+
+		for method in &["test_ok", "test_ok", "test_err", "test_unknown"] {
+			let request = format!(r#"{{"jsonrpc":"2.0","method":"{}","id":1}}"#, method);
+			block_on(io.handle_request(&request, Meta)).unwrap();
+		}
+
+		let finished = |method: &str, is_error: &str| {
+			metrics.calls_finished.with_label_values(&["test", method, is_error]).get()
+		};
+		assert_eq!(finished("test_ok", "false"), 2);
+		assert_eq!(finished("test_ok", "true"), 0);
+		assert_eq!(finished("test_err", "true"), 1);
+		assert_eq!(finished("invalid method", "true"), 1);
+		assert_eq!(
+			metrics.calls_time.with_label_values(&["test", "test_ok"]).get_sample_count(),
+			2
+		);
+		for method in &["test_ok", "test_err", "invalid method"] {
+			assert_eq!(metrics.calls_in_flight.with_label_values(&["test", method]).get(), 0);
+		}
+	}
+}
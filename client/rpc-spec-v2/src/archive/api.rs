@@ -0,0 +1,63 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! API trait of the archive.
+
+use crate::error::Result;
+use jsonrpc_derive::rpc;
+use sp_core::{storage::StorageKey, Bytes};
+
+pub use self::gen_client::Client as ArchiveClient;
+
+/// Substrate archive API
+#[rpc]
+pub trait ArchiveApi<Number, Hash> {
+	/// Get the hash of the finalized block at the given height.
+	///
+	/// Returns `None` if no block was finalized at this height yet.
+	#[rpc(name = "archive_unstable_hashByHeight")]
+	fn hash_by_height(&self, height: Number) -> Result<Option<Hash>>;
+
+	/// Retrieve the body of a finalized block.
+	///
+	/// The body is reported as a list of hex encoded extrinsics, or `None` if the body is not
+	/// available.
+	#[rpc(name = "archive_unstable_body")]
+	fn body(&self, hash: Hash) -> Result<Option<Vec<String>>>;
+
+	/// Retrieve the hex encoded header of a finalized block.
+	#[rpc(name = "archive_unstable_header")]
+	fn header(&self, hash: Hash) -> Result<Option<String>>;
+
+	/// Retrieve a storage value of a finalized block.
+	///
+	/// If `child_key` is given, the value is read from the default child trie with this key.
+	#[rpc(name = "archive_unstable_storage")]
+	fn storage(
+		&self,
+		hash: Hash,
+		key: StorageKey,
+		child_key: Option<StorageKey>,
+	) -> Result<Option<String>>;
+
+	/// Call a runtime function on top of a finalized block.
+	///
+	/// The output of the call is returned hex encoded.
+	#[rpc(name = "archive_unstable_call")]
+	fn call(&self, hash: Hash, function: String, call_parameters: Bytes) -> Result<String>;
+}
@@ -0,0 +1,154 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Implementation of the archive API.
+
+use crate::{
+	archive::api::ArchiveApi,
+	error::{Error, Result},
+};
+use codec::Encode;
+use sc_client_api::{Backend, BlockBackend, CallExecutor, ExecutorProvider, StorageProvider};
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	bytes::to_hex,
+	storage::{ChildInfo, StorageKey},
+	Bytes,
+};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, NumberFor},
+};
+use std::{marker::PhantomData, sync::Arc};
+
+/// An API for the finalized history of the chain following the new JSON-RPC specification.
+pub struct Archive<BE, Block: BlockT, Client> {
+	/// Substrate client.
+	client: Arc<Client>,
+	_phantom: PhantomData<(BE, Block)>,
+}
+
+impl<BE, Block: BlockT, Client> Archive<BE, Block, Client> {
+	/// Create a new [`Archive`].
+	pub fn new(client: Arc<Client>) -> Self {
+		Self { client, _phantom: PhantomData }
+	}
+}
+
+impl<BE, Block, Client> Archive<BE, Block, Client>
+where
+	Block: BlockT + 'static,
+	Client: HeaderBackend<Block> + 'static,
+{
+	/// Ensure that the given block is finalized, returning its id.
+	fn ensure_finalized(&self, hash: Block::Hash) -> Result<BlockId<Block>> {
+		let number = self.client.number(hash).map_err(client_err)?.ok_or(Error::InvalidBlock)?;
+		if number > self.client.info().finalized_number {
+			return Err(Error::InvalidBlock)
+		}
+
+		// A block below the finalized block is only finalized if it is part of the canonical chain.
+		match self.client.hash(number).map_err(client_err)? {
+			Some(canonical) if canonical == hash => Ok(BlockId::Hash(hash)),
+			_ => Err(Error::InvalidBlock),
+		}
+	}
+}
+
+impl<BE, Block, Client> ArchiveApi<NumberFor<Block>, Block::Hash> for Archive<BE, Block, Client>
+where
+	Block: BlockT + 'static,
+	BE: Backend<Block> + 'static,
+	Client: BlockBackend<Block>
+		+ HeaderBackend<Block>
+		+ ExecutorProvider<Block>
+		+ StorageProvider<Block, BE>
+		+ Send
+		+ Sync
+		+ 'static,
+{
+	fn hash_by_height(&self, height: NumberFor<Block>) -> Result<Option<Block::Hash>> {
+		if height > self.client.info().finalized_number {
+			return Ok(None)
+		}
+
+		self.client.hash(height).map_err(client_err)
+	}
+
+	fn body(&self, hash: Block::Hash) -> Result<Option<Vec<String>>> {
+		let id = self.ensure_finalized(hash)?;
+
+		self.client
+			.block_body(&id)
+			.map(|body| {
+				body.map(|body| {
+					body.iter().map(|extrinsic| to_hex(&extrinsic.encode(), false)).collect()
+				})
+			})
+			.map_err(client_err)
+	}
+
+	fn header(&self, hash: Block::Hash) -> Result<Option<String>> {
+		let id = self.ensure_finalized(hash)?;
+
+		self.client
+			.header(id)
+			.map(|header| header.map(|header| to_hex(&header.encode(), false)))
+			.map_err(client_err)
+	}
+
+	fn storage(
+		&self,
+		hash: Block::Hash,
+		key: StorageKey,
+		child_key: Option<StorageKey>,
+	) -> Result<Option<String>> {
+		let id = self.ensure_finalized(hash)?;
+
+		let value = match child_key {
+			Some(child_key) =>
+				self.client
+					.child_storage(&id, &ChildInfo::new_default_from_vec(child_key.0), &key),
+			None => self.client.storage(&id, &key),
+		};
+
+		value
+			.map(|value| value.map(|value| to_hex(&value.0, false)))
+			.map_err(client_err)
+	}
+
+	fn call(&self, hash: Block::Hash, function: String, call_parameters: Bytes) -> Result<String> {
+		let id = self.ensure_finalized(hash)?;
+
+		self.client
+			.executor()
+			.call(
+				&id,
+				&function,
+				&call_parameters,
+				self.client.execution_extensions().strategies().other,
+				None,
+			)
+			.map(|output| to_hex(&output, false))
+			.map_err(|e| Error::Call(e.to_string()))
+	}
+}
+
+fn client_err(err: sp_blockchain::Error) -> Error {
+	Error::Client(Box::new(err))
+}
@@ -0,0 +1,32 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The `archive` methods allow to query the finalized history of the chain.
+//!
+//! Contrary to the `chainHead` methods, these methods only serve finalized blocks. Their results
+//! therefore do not depend on the node answering the request, which allows to load-balance them
+//! across archive nodes.
+
+pub mod api;
+pub mod archive;
+
+#[cfg(test)]
+mod tests;
+
+pub use api::ArchiveApi;
+pub use archive::Archive;
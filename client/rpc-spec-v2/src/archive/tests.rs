@@ -0,0 +1,80 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::error::Error;
+use assert_matches::assert_matches;
+use codec::Decode;
+use futures::executor;
+use sc_block_builder::BlockBuilderProvider;
+use sp_consensus::BlockOrigin;
+use sp_core::{bytes::from_hex, storage::StorageKey, Bytes};
+use sp_runtime::generic::BlockId;
+use sp_version::RuntimeVersion;
+use std::sync::Arc;
+use substrate_test_runtime_client::{
+	prelude::*,
+	runtime::{Block, H256},
+	Backend, Client,
+};
+
+type Api = Archive<Backend, Block, Client<Backend>>;
+
+fn setup_api() -> (Arc<Client<Backend>>, Api) {
+	let client = Arc::new(TestClientBuilder::new().build());
+	let api = Archive::new(client.clone());
+	(client, api)
+}
+
+#[test]
+fn only_finalized_blocks_are_served() {
+	let (mut client, api) = setup_api();
+	let genesis = client.genesis_hash();
+
+	let block = client.new_block(Default::default()).unwrap().build().unwrap().block;
+	let block_hash = block.header.hash();
+	executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
+
+	assert_eq!(api.hash_by_height(0).unwrap(), Some(genesis));
+	assert_eq!(api.hash_by_height(1).unwrap(), None);
+	assert_matches!(api.header(block_hash), Err(Error::InvalidBlock));
+	assert_matches!(api.body(block_hash), Err(Error::InvalidBlock));
+	assert_matches!(api.header(H256::from_low_u64_be(5)), Err(Error::InvalidBlock));
+
+	client.finalize_block(BlockId::Hash(block_hash), None).unwrap();
+
+	assert_eq!(api.hash_by_height(1).unwrap(), Some(block_hash));
+	assert_matches!(api.header(block_hash), Ok(Some(_)));
+	assert_eq!(api.body(block_hash).unwrap(), Some(vec![]));
+}
+
+#[test]
+fn storage_and_call_of_finalized_block() {
+	let (client, api) = setup_api();
+	let genesis = client.genesis_hash();
+
+	assert_matches!(api.storage(genesis, StorageKey(b":code".to_vec()), None), Ok(Some(_)));
+	assert_eq!(api.storage(genesis, StorageKey(b":unknown".to_vec()), None).unwrap(), None);
+
+	let output = api.call(genesis, "Core_version".into(), Bytes(vec![])).unwrap();
+	let output = RuntimeVersion::decode(&mut &from_hex(&output).unwrap()[..]).unwrap();
+	let runtime_version = client.runtime_version_at(&BlockId::Hash(genesis)).unwrap();
+	assert_eq!(output.spec_version, runtime_version.spec_version);
+
+	assert_matches!(api.call(genesis, "Core_unknown".into(), Bytes(vec![])), Err(Error::Call(_)));
+}
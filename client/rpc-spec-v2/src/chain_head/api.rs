@@ -0,0 +1,148 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! API trait of the chain head.
+
+use crate::{
+	chain_head::event::{ChainHeadEvent, FollowEvent},
+	error::Result,
+};
+use jsonrpc_core::Result as RpcResult;
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId};
+use sp_core::{storage::StorageKey, Bytes};
+
+pub use self::gen_client::Client as ChainHeadClient;
+
+/// Substrate chain head API
+#[rpc]
+pub trait ChainHeadApi<Hash> {
+	/// RPC metadata
+	type Metadata;
+
+	/// Track the state of the head of the chain: the finalized, non-finalized, and best blocks.
+	///
+	/// If `runtime_updates` is `true`, the runtime of the finalized block and any change of the
+	/// runtime by a new block are reported as well.
+	#[pubsub(
+		subscription = "chainHead_unstable_followEvent",
+		subscribe,
+		name = "chainHead_unstable_follow"
+	)]
+	fn follow(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<FollowEvent<Hash>>,
+		runtime_updates: bool,
+	);
+
+	/// Stop following the head of the chain, unpinning all blocks of the subscription.
+	#[pubsub(
+		subscription = "chainHead_unstable_followEvent",
+		unsubscribe,
+		name = "chainHead_unstable_unfollow"
+	)]
+	fn unfollow(&self, metadata: Option<Self::Metadata>, id: SubscriptionId) -> RpcResult<bool>;
+
+	/// Retrieve the body of a block pinned by the given follow subscription.
+	///
+	/// The body is reported as a list of hex encoded extrinsics.
+	#[pubsub(
+		subscription = "chainHead_unstable_bodyEvent",
+		subscribe,
+		name = "chainHead_unstable_body"
+	)]
+	fn body(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<ChainHeadEvent<Vec<String>>>,
+		follow_subscription: String,
+		hash: Hash,
+	);
+
+	/// Stop a `chainHead_unstable_body` operation.
+	#[pubsub(
+		subscription = "chainHead_unstable_bodyEvent",
+		unsubscribe,
+		name = "chainHead_unstable_stopBody"
+	)]
+	fn stop_body(&self, metadata: Option<Self::Metadata>, id: SubscriptionId) -> RpcResult<bool>;
+
+	/// Retrieve the hex encoded header of a block pinned by the given follow subscription.
+	#[rpc(name = "chainHead_unstable_header")]
+	fn header(&self, follow_subscription: String, hash: Hash) -> Result<Option<String>>;
+
+	/// Retrieve a storage value of a block pinned by the given follow subscription.
+	///
+	/// If `child_key` is given, the value is read from the default child trie with this key.
+	#[pubsub(
+		subscription = "chainHead_unstable_storageEvent",
+		subscribe,
+		name = "chainHead_unstable_storage"
+	)]
+	fn storage(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<ChainHeadEvent<Option<String>>>,
+		follow_subscription: String,
+		hash: Hash,
+		key: StorageKey,
+		child_key: Option<StorageKey>,
+	);
+
+	/// Stop a `chainHead_unstable_storage` operation.
+	#[pubsub(
+		subscription = "chainHead_unstable_storageEvent",
+		unsubscribe,
+		name = "chainHead_unstable_stopStorage"
+	)]
+	fn stop_storage(&self, metadata: Option<Self::Metadata>, id: SubscriptionId)
+		-> RpcResult<bool>;
+
+	/// Call a runtime function on top of a block pinned by the given follow subscription.
+	///
+	/// The output of the call is reported hex encoded.
+	#[pubsub(
+		subscription = "chainHead_unstable_callEvent",
+		subscribe,
+		name = "chainHead_unstable_call"
+	)]
+	fn call(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<ChainHeadEvent<String>>,
+		follow_subscription: String,
+		hash: Hash,
+		function: String,
+		call_parameters: Bytes,
+	);
+
+	/// Stop a `chainHead_unstable_call` operation.
+	#[pubsub(
+		subscription = "chainHead_unstable_callEvent",
+		unsubscribe,
+		name = "chainHead_unstable_stopCall"
+	)]
+	fn stop_call(&self, metadata: Option<Self::Metadata>, id: SubscriptionId) -> RpcResult<bool>;
+
+	/// Unpin a block reported by the given follow subscription.
+	///
+	/// Once unpinned, the block can no longer be queried through this subscription.
+	#[rpc(name = "chainHead_unstable_unpin")]
+	fn unpin(&self, follow_subscription: String, hash: Hash) -> Result<()>;
+}
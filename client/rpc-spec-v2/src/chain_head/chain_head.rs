@@ -0,0 +1,584 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Implementation of the chain head API.
+
+use crate::{
+	chain_head::{
+		api::ChainHeadApi,
+		event::{
+			BestBlockChanged, ChainHeadEvent, ChainHeadResult, ErrorEvent, Finalized, FollowEvent,
+			Initialized, NewBlock, RuntimeEvent, RuntimeVersionEvent,
+		},
+		subscription::{SubscriptionState, Subscriptions},
+	},
+	error::{Error, Result},
+};
+use codec::Encode;
+use futures::{future, stream, FutureExt, SinkExt, StreamExt};
+use jsonrpc_core::Result as RpcResult;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use log::warn;
+use sc_client_api::{
+	Backend, BlockBackend, BlockImportNotification, BlockchainEvents, CallExecutor,
+	ExecutorProvider, FinalityNotification, StorageProvider,
+};
+use sc_rpc_api::SubscriptionGuard;
+use serde::Serialize;
+use sp_api::CallApiAt;
+use sp_blockchain::{Backend as BlockchainBackend, HeaderBackend, HeaderMetadata};
+use sp_core::{
+	bytes::to_hex,
+	storage::{ChildInfo, StorageKey},
+	Bytes,
+};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Header},
+};
+use std::{
+	collections::{HashMap, HashSet},
+	sync::Arc,
+};
+
+/// An API for the chain head following the new JSON-RPC specification.
+pub struct ChainHead<BE: Backend<Block>, Block: BlockT, Client> {
+	/// Substrate client.
+	client: Arc<Client>,
+	/// Backend of the chain.
+	backend: Arc<BE>,
+	/// Executor of the RPC subscriptions.
+	executor: SubscriptionManager,
+	/// The active follow subscriptions.
+	subscriptions: Arc<Subscriptions<Block, BE>>,
+}
+
+impl<BE: Backend<Block>, Block: BlockT, Client> ChainHead<BE, Block, Client> {
+	/// Create a new [`ChainHead`].
+	pub fn new(client: Arc<Client>, backend: Arc<BE>, executor: SubscriptionManager) -> Self {
+		Self { client, backend, executor, subscriptions: Arc::new(Default::default()) }
+	}
+}
+
+impl<BE, Block, Client> ChainHead<BE, Block, Client>
+where
+	Block: BlockT + 'static,
+	BE: Backend<Block> + 'static,
+{
+	/// Ensure that the given block is pinned by the given follow subscription.
+	fn ensure_pinned(&self, follow_subscription: String, hash: &Block::Hash) -> Result<()> {
+		let state = self
+			.subscriptions
+			.get(&SubscriptionId::String(follow_subscription))
+			.ok_or(Error::InvalidSubscription)?;
+		if state.contains(hash) {
+			Ok(())
+		} else {
+			Err(Error::InvalidBlock)
+		}
+	}
+
+	/// Run an operation of the given follow subscription on the given block.
+	///
+	/// The `operation` is executed in the background and its event is reported to the
+	/// `subscriber`.
+	fn run_operation<T, F>(
+		&self,
+		metadata: &sc_rpc_api::Metadata,
+		subscriber: Subscriber<ChainHeadEvent<T>>,
+		follow_subscription: String,
+		hash: Block::Hash,
+		operation: F,
+	) where
+		T: Serialize + Send + Unpin + 'static,
+		F: FnOnce() -> ChainHeadEvent<T> + Send + 'static,
+	{
+		let subscription = metadata.open_subscription();
+		let state = match self.subscriptions.get(&SubscriptionId::String(follow_subscription)) {
+			Some(state) => state,
+			None =>
+				return send_event(&self.executor, subscriber, subscription, || {
+					ChainHeadEvent::Disjoint
+				}),
+		};
+
+		if !state.contains(&hash) {
+			let _ = subscriber.reject(Error::InvalidBlock.into());
+			return
+		}
+
+		match state.start_operation() {
+			Some(guard) => send_event(&self.executor, subscriber, subscription, move || {
+				let event = operation();
+				drop(guard);
+				event
+			}),
+			None => send_event(&self.executor, subscriber, subscription, || {
+				ChainHeadEvent::Inaccessible(ErrorEvent {
+					error: "Maximum number of ongoing operations reached".into(),
+				})
+			}),
+		}
+	}
+}
+
+impl<BE, Block, Client> ChainHeadApi<Block::Hash> for ChainHead<BE, Block, Client>
+where
+	Block: BlockT + 'static,
+	Block::Header: Unpin,
+	Block::Hash: Unpin,
+	BE: Backend<Block> + 'static,
+	Client: BlockBackend<Block>
+		+ HeaderBackend<Block>
+		+ HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ BlockchainEvents<Block>
+		+ CallApiAt<Block>
+		+ ExecutorProvider<Block>
+		+ StorageProvider<Block, BE>
+		+ Send
+		+ Sync
+		+ 'static,
+{
+	type Metadata = sc_rpc_api::Metadata;
+
+	fn follow(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<FollowEvent<Block::Hash>>,
+		runtime_updates: bool,
+	) {
+		let state = Arc::new(SubscriptionState::new(self.backend.clone(), runtime_updates));
+		let follow_state = state.clone();
+		let client = self.client.clone();
+		let backend = self.backend.clone();
+		let subscriptions = self.subscriptions.clone();
+		let subscription = metadata.open_subscription();
+
+		let id = self.executor.add(subscriber, move |sink| {
+			// Subscribe to the notifications before reporting the initial blocks, to not miss any
+			// block imported in the meantime.
+			let imports = client.import_notification_stream().map(Notification::Import);
+			let finality = client.finality_notification_stream().map(Notification::Finality);
+
+			let mut follower = ChainHeadFollower::new(client, follow_state.clone());
+			let initial_events = follower.initial_events(&*backend);
+			let events = stream::select(imports, finality)
+				.flat_map(move |notification| stream::iter(follower.handle(notification)));
+
+			stream::iter(initial_events)
+				.chain(events)
+				// No events are reported after the subscription was stopped.
+				.scan(false, |stopped, event| {
+					if *stopped {
+						return future::ready(None)
+					}
+					*stopped = matches!(event, FollowEvent::Stop);
+					future::ready(Some(Ok::<_, ()>(Ok(event))))
+				})
+				.forward(sink.sink_map_err(|e| warn!("Error sending notifications: {:?}", e)))
+				.map(move |_| {
+					subscriptions.remove_state(&follow_state);
+					drop(subscription);
+				})
+		});
+
+		self.subscriptions.insert(id, state);
+	}
+
+	fn unfollow(&self, _metadata: Option<Self::Metadata>, id: SubscriptionId) -> RpcResult<bool> {
+		self.subscriptions.remove(&id);
+		Ok(self.executor.cancel(id))
+	}
+
+	fn body(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<ChainHeadEvent<Vec<String>>>,
+		follow_subscription: String,
+		hash: Block::Hash,
+	) {
+		let client = self.client.clone();
+		let operation = move || match client.block_body(&BlockId::Hash(hash)) {
+			Ok(Some(body)) => ChainHeadEvent::Done(ChainHeadResult {
+				result: body.iter().map(|extrinsic| to_hex(&extrinsic.encode(), false)).collect(),
+			}),
+			Ok(None) => ChainHeadEvent::Inaccessible(ErrorEvent {
+				error: "Block body is not available".into(),
+			}),
+			Err(e) => ChainHeadEvent::Inaccessible(ErrorEvent { error: e.to_string() }),
+		};
+		self.run_operation(&metadata, subscriber, follow_subscription, hash, operation)
+	}
+
+	fn stop_body(&self, _metadata: Option<Self::Metadata>, id: SubscriptionId) -> RpcResult<bool> {
+		Ok(self.executor.cancel(id))
+	}
+
+	fn header(&self, follow_subscription: String, hash: Block::Hash) -> Result<Option<String>> {
+		self.ensure_pinned(follow_subscription, &hash)?;
+
+		self.client
+			.header(BlockId::Hash(hash))
+			.map(|header| header.map(|header| to_hex(&header.encode(), false)))
+			.map_err(client_err)
+	}
+
+	fn storage(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<ChainHeadEvent<Option<String>>>,
+		follow_subscription: String,
+		hash: Block::Hash,
+		key: StorageKey,
+		child_key: Option<StorageKey>,
+	) {
+		let client = self.client.clone();
+		self.run_operation(&metadata, subscriber, follow_subscription, hash, move || {
+			let id = BlockId::Hash(hash);
+			let value = match child_key {
+				Some(child_key) =>
+					client.child_storage(&id, &ChildInfo::new_default_from_vec(child_key.0), &key),
+				None => client.storage(&id, &key),
+			};
+
+			match value {
+				Ok(value) => ChainHeadEvent::Done(ChainHeadResult {
+					result: value.map(|value| to_hex(&value.0, false)),
+				}),
+				Err(e) => ChainHeadEvent::Inaccessible(ErrorEvent { error: e.to_string() }),
+			}
+		})
+	}
+
+	fn stop_storage(
+		&self,
+		_metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> RpcResult<bool> {
+		Ok(self.executor.cancel(id))
+	}
+
+	fn call(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<ChainHeadEvent<String>>,
+		follow_subscription: String,
+		hash: Block::Hash,
+		function: String,
+		call_parameters: Bytes,
+	) {
+		let client = self.client.clone();
+		self.run_operation(&metadata, subscriber, follow_subscription, hash, move || {
+			client
+				.executor()
+				.call(
+					&BlockId::Hash(hash),
+					&function,
+					&call_parameters,
+					client.execution_extensions().strategies().other,
+					None,
+				)
+				.map(|output| {
+					ChainHeadEvent::Done(ChainHeadResult { result: to_hex(&output, false) })
+				})
+				.unwrap_or_else(|e| ChainHeadEvent::Error(ErrorEvent { error: e.to_string() }))
+		})
+	}
+
+	fn stop_call(&self, _metadata: Option<Self::Metadata>, id: SubscriptionId) -> RpcResult<bool> {
+		Ok(self.executor.cancel(id))
+	}
+
+	fn unpin(&self, follow_subscription: String, hash: Block::Hash) -> Result<()> {
+		let state = self
+			.subscriptions
+			.get(&SubscriptionId::String(follow_subscription))
+			.ok_or(Error::InvalidSubscription)?;
+		if state.unpin(&hash) {
+			Ok(())
+		} else {
+			Err(Error::InvalidBlock)
+		}
+	}
+}
+
+/// Report the single event returned by `event` to the `subscriber`.
+///
+/// The event is generated in the background.
+fn send_event<T, F>(
+	executor: &SubscriptionManager,
+	subscriber: Subscriber<T>,
+	subscription: SubscriptionGuard,
+	event: F,
+) where
+	T: Serialize + Send + Unpin + 'static,
+	F: FnOnce() -> T + Send + 'static,
+{
+	executor.add(subscriber, move |sink| {
+		stream::once(async move { Ok::<_, ()>(Ok(event())) })
+			.forward(sink.sink_map_err(|e| warn!("Error sending notifications: {:?}", e)))
+			.map(move |_| drop(subscription))
+	});
+}
+
+/// A notification of the client relevant for a follow subscription.
+enum Notification<Block: BlockT> {
+	/// A block was imported.
+	Import(BlockImportNotification<Block>),
+	/// A block was finalized.
+	Finality(FinalityNotification<Block>),
+}
+
+/// Generates the events of a follow subscription.
+struct ChainHeadFollower<Block: BlockT, BE: Backend<Block>, Client> {
+	/// Substrate client.
+	client: Arc<Client>,
+	/// The state of the subscription.
+	state: Arc<SubscriptionState<Block, BE>>,
+	/// The last block reported as finalized.
+	finalized: Block::Hash,
+	/// The reported blocks that are descendants of the finalized block, with their parent.
+	non_finalized: HashMap<Block::Hash, Block::Hash>,
+}
+
+impl<Block, BE, Client> ChainHeadFollower<Block, BE, Client>
+where
+	Block: BlockT + 'static,
+	BE: Backend<Block> + 'static,
+	Client: HeaderBackend<Block>
+		+ HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ CallApiAt<Block>
+		+ 'static,
+{
+	/// Create a new follower for the given subscription.
+	fn new(client: Arc<Client>, state: Arc<SubscriptionState<Block, BE>>) -> Self {
+		let finalized = client.info().finalized_hash;
+		Self { client, state, finalized, non_finalized: Default::default() }
+	}
+
+	/// Returns the initial events of the subscription: the finalized block, all its descendants and
+	/// the best block.
+	fn initial_events(&mut self, backend: &BE) -> Vec<FollowEvent<Block::Hash>> {
+		self.try_initial_events(backend).unwrap_or_else(|e| {
+			warn!("Failed to generate the initial events of the follow subscription: {:?}", e);
+			vec![FollowEvent::Stop]
+		})
+	}
+
+	fn try_initial_events(
+		&mut self,
+		backend: &BE,
+	) -> sp_blockchain::Result<Vec<FollowEvent<Block::Hash>>> {
+		let info = self.client.info();
+		self.finalized = info.finalized_hash;
+
+		if !self.state.pin(self.finalized) {
+			return Ok(vec![FollowEvent::Stop])
+		}
+		let mut events = vec![FollowEvent::Initialized(Initialized {
+			finalized_block_hash: self.finalized,
+			finalized_block_runtime: self.runtime_event(&self.finalized, None),
+		})];
+
+		for leaf in backend.blockchain().leaves()? {
+			let route = sp_blockchain::tree_route(&*self.client, self.finalized, leaf)?;
+			// Ignore stale forks, which are not descendants of the finalized block.
+			if route.common_block().hash != self.finalized {
+				continue
+			}
+
+			let mut parent = self.finalized;
+			for block in route.enacted() {
+				if !self.non_finalized.contains_key(&block.hash) {
+					let event = self.new_block(block.hash, parent);
+					let stop = matches!(event, FollowEvent::Stop);
+					events.push(event);
+					if stop {
+						return Ok(events)
+					}
+				}
+				parent = block.hash;
+			}
+		}
+
+		events.push(FollowEvent::BestBlockChanged(BestBlockChanged {
+			best_block_hash: info.best_hash,
+		}));
+
+		Ok(events)
+	}
+
+	/// Returns the events for the given notification.
+	fn handle(&mut self, notification: Notification<Block>) -> Vec<FollowEvent<Block::Hash>> {
+		match notification {
+			Notification::Import(notification) => self.handle_import(notification),
+			Notification::Finality(notification) =>
+				self.handle_finality(notification).unwrap_or_else(|e| {
+					warn!("Failed to handle finality notification: {:?}", e);
+					vec![FollowEvent::Stop]
+				}),
+		}
+	}
+
+	fn handle_import(
+		&mut self,
+		notification: BlockImportNotification<Block>,
+	) -> Vec<FollowEvent<Block::Hash>> {
+		let hash = notification.hash;
+		let parent = *notification.header.parent_hash();
+		let mut events = Vec::new();
+
+		if !self.non_finalized.contains_key(&hash) {
+			// Blocks that don't descend from the finalized block are not part of the followed
+			// chain.
+			if parent != self.finalized && !self.non_finalized.contains_key(&parent) {
+				return events
+			}
+
+			let event = self.new_block(hash, parent);
+			let stop = matches!(event, FollowEvent::Stop);
+			events.push(event);
+			if stop {
+				return events
+			}
+		}
+
+		if notification.is_new_best {
+			events.push(FollowEvent::BestBlockChanged(BestBlockChanged { best_block_hash: hash }));
+		}
+
+		events
+	}
+
+	fn handle_finality(
+		&mut self,
+		notification: FinalityNotification<Block>,
+	) -> sp_blockchain::Result<Vec<FollowEvent<Block::Hash>>> {
+		let hash = notification.hash;
+		if hash == self.finalized {
+			return Ok(Vec::new())
+		}
+
+		let route = sp_blockchain::tree_route(&*self.client, self.finalized, hash)?;
+		// An ancestor of the finalized block, which was already reported.
+		if route.enacted().is_empty() {
+			return Ok(Vec::new())
+		}
+		// Finalized blocks can't be reverted, the subscriber can't follow the chain anymore.
+		if !route.retracted().is_empty() {
+			return Ok(vec![FollowEvent::Stop])
+		}
+
+		// Report the finalized blocks which weren't reported yet, e.g. because they were imported
+		// while the notifications of the subscription were lagging behind.
+		let mut events = Vec::new();
+		let mut parent = self.finalized;
+		for block in route.enacted() {
+			if !self.non_finalized.contains_key(&block.hash) {
+				let event = self.new_block(block.hash, parent);
+				let stop = matches!(event, FollowEvent::Stop);
+				events.push(event);
+				if stop {
+					return Ok(events)
+				}
+			}
+			parent = block.hash;
+		}
+
+		let finalized_block_hashes =
+			route.enacted().iter().map(|block| block.hash).collect::<Vec<_>>();
+		// The blocks which don't descend from the newly finalized block branch off the previously
+		// finalized block or the other newly finalized blocks, and are pruned.
+		let finalized = finalized_block_hashes.iter().collect::<HashSet<_>>();
+		let mut pruned_block_hashes = Vec::new();
+		for (block, parent) in &self.non_finalized {
+			if finalized.contains(block) {
+				continue
+			}
+			let mut ancestor = parent;
+			while !finalized.contains(ancestor) {
+				match self.non_finalized.get(ancestor) {
+					Some(parent) => ancestor = parent,
+					// The previously finalized block.
+					None => break,
+				}
+			}
+			if *ancestor != hash {
+				pruned_block_hashes.push(*block);
+			}
+		}
+
+		for block in finalized_block_hashes.iter().chain(pruned_block_hashes.iter()) {
+			self.non_finalized.remove(block);
+		}
+		self.finalized = hash;
+
+		events.push(FollowEvent::Finalized(Finalized {
+			finalized_block_hashes,
+			pruned_block_hashes,
+		}));
+		Ok(events)
+	}
+
+	/// Pin and report a new block.
+	///
+	/// Returns [`FollowEvent::Stop`] if the subscription can not pin any more blocks.
+	fn new_block(&mut self, hash: Block::Hash, parent: Block::Hash) -> FollowEvent<Block::Hash> {
+		if !self.state.pin(hash) {
+			return FollowEvent::Stop
+		}
+		self.non_finalized.insert(hash, parent);
+
+		FollowEvent::NewBlock(NewBlock {
+			block_hash: hash,
+			parent_block_hash: parent,
+			new_runtime: self.runtime_event(&hash, Some(&parent)),
+		})
+	}
+
+	/// Returns the runtime of the given block, if runtime updates are enabled.
+	///
+	/// If a `parent` is given, the runtime is only returned if it differs from the runtime of the
+	/// parent.
+	fn runtime_event(
+		&self,
+		hash: &Block::Hash,
+		parent: Option<&Block::Hash>,
+	) -> Option<RuntimeEvent> {
+		if !self.state.runtime_updates() {
+			return None
+		}
+
+		let version = self.client.runtime_version_at(&BlockId::Hash(*hash));
+		if let (Ok(version), Some(parent)) = (&version, parent) {
+			match self.client.runtime_version_at(&BlockId::Hash(*parent)) {
+				Ok(parent_version) if parent_version == *version => return None,
+				_ => {},
+			}
+		}
+
+		Some(match version {
+			Ok(spec) => RuntimeEvent::Valid(RuntimeVersionEvent { spec }),
+			Err(e) => RuntimeEvent::Invalid(ErrorEvent { error: e.to_string() }),
+		})
+	}
+}
+
+fn client_err(err: sp_blockchain::Error) -> Error {
+	Error::Client(Box::new(err))
+}
@@ -0,0 +1,138 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The events reported by the chain head.
+
+use serde::{Deserialize, Serialize};
+use sp_version::RuntimeVersion;
+
+/// The runtime of a block, reported if the follow subscription was started with
+/// `runtime_updates` enabled.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "type")]
+pub enum RuntimeEvent {
+	/// The runtime of the block is valid.
+	Valid(RuntimeVersionEvent),
+	/// The runtime of the block could not be obtained.
+	Invalid(ErrorEvent),
+}
+
+/// The version of a valid runtime.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeVersionEvent {
+	/// The runtime version.
+	pub spec: RuntimeVersion,
+}
+
+/// The first event of a follow subscription, reporting the current finalized block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Initialized<Hash> {
+	/// The hash of the finalized block.
+	pub finalized_block_hash: Hash,
+	/// The runtime of the finalized block, if runtime updates are enabled.
+	#[serde(skip_serializing_if = "Option::is_none", default)]
+	pub finalized_block_runtime: Option<RuntimeEvent>,
+}
+
+/// A new non-finalized block was added to the chain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewBlock<Hash> {
+	/// The hash of the new block.
+	pub block_hash: Hash,
+	/// The hash of the parent of the new block.
+	pub parent_block_hash: Hash,
+	/// The runtime of the new block, if runtime updates are enabled and it differs from the
+	/// runtime of the parent.
+	#[serde(skip_serializing_if = "Option::is_none", default)]
+	pub new_runtime: Option<RuntimeEvent>,
+}
+
+/// The best block of the chain changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BestBlockChanged<Hash> {
+	/// The hash of the new best block.
+	pub best_block_hash: Hash,
+}
+
+/// One or more blocks were finalized.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Finalized<Hash> {
+	/// The hashes of the newly finalized blocks, ordered by ascending block number.
+	pub finalized_block_hashes: Vec<Hash>,
+	/// The hashes of the reported blocks that are no longer descendants of the finalized block.
+	///
+	/// These blocks stay pinned until they are unpinned.
+	pub pruned_block_hashes: Vec<Hash>,
+}
+
+/// An event of a follow subscription.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "event")]
+pub enum FollowEvent<Hash> {
+	/// The first event of the subscription.
+	Initialized(Initialized<Hash>),
+	/// A new non-finalized block was added.
+	NewBlock(NewBlock<Hash>),
+	/// The best block changed.
+	BestBlockChanged(BestBlockChanged<Hash>),
+	/// One or more blocks were finalized.
+	Finalized(Finalized<Hash>),
+	/// The subscription was stopped by the node, no further events are reported.
+	///
+	/// This happens for example if the subscription pinned too many blocks.
+	Stop,
+}
+
+/// The successful result of an operation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainHeadResult<T> {
+	/// The result of the operation.
+	pub result: T,
+}
+
+/// The error of a failed operation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorEvent {
+	/// Reason of the error.
+	pub error: String,
+}
+
+/// The event of an operation (`body`, `storage` or `call`) of a follow subscription.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "event")]
+pub enum ChainHeadEvent<T> {
+	/// The operation succeeded.
+	Done(ChainHeadResult<T>),
+	/// The information could not be retrieved, e.g. because it was pruned or because the
+	/// follow subscription has too many ongoing operations. Retrying may succeed.
+	Inaccessible(ErrorEvent),
+	/// The operation failed and retrying it will not succeed.
+	Error(ErrorEvent),
+	/// The follow subscription does not exist or was stopped.
+	Disjoint,
+}
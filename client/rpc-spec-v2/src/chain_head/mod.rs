@@ -0,0 +1,46 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The `chainHead` methods allow to follow the head of the chain.
+//!
+//! A client starts by calling `chainHead_unstable_follow`, which reports the finalized block and
+//! all its descendants, followed by notifications about new, best and finalized blocks. Every
+//! block reported by the subscription is pinned for this subscription and can be queried through
+//! the other methods of this group until the client unpins it through `chainHead_unstable_unpin`.
+//! The state of the pinned blocks is kept by the backend, which doesn't prune it in the meantime.
+//!
+//! The resources used by each follow subscription are bounded: a subscription may pin at most
+//! [`MAX_PINNED_BLOCKS`] blocks, otherwise it is stopped, and at most [`MAX_ONGOING_OPERATIONS`]
+//! operations may run at the same time.
+
+pub mod api;
+pub mod chain_head;
+pub mod event;
+
+mod subscription;
+
+#[cfg(test)]
+mod tests;
+
+pub use api::ChainHeadApi;
+pub use chain_head::ChainHead;
+pub use event::{
+	BestBlockChanged, ChainHeadEvent, ChainHeadResult, ErrorEvent, Finalized, FollowEvent,
+	Initialized, NewBlock, RuntimeEvent, RuntimeVersionEvent,
+};
+pub use subscription::{MAX_ONGOING_OPERATIONS, MAX_PINNED_BLOCKS};
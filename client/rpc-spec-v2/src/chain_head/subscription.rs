@@ -0,0 +1,172 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Bookkeeping of the follow subscriptions and their pinned blocks.
+
+use jsonrpc_pubsub::SubscriptionId;
+use log::warn;
+use parking_lot::{Mutex, RwLock};
+use sc_client_api::Backend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::{
+	collections::HashMap,
+	sync::{
+		atomic::{AtomicBool, AtomicUsize, Ordering},
+		Arc,
+	},
+};
+
+/// The maximum number of blocks a follow subscription can pin.
+///
+/// A subscription that would pin more blocks is stopped.
+pub const MAX_PINNED_BLOCKS: usize = 512;
+
+/// The maximum number of operations that can run at the same time for a follow subscription.
+pub const MAX_ONGOING_OPERATIONS: usize = 16;
+
+/// The state of a single follow subscription.
+pub(crate) struct SubscriptionState<Block: BlockT, BE: Backend<Block>> {
+	/// Backend of the chain.
+	backend: Arc<BE>,
+	/// Whether the runtime of the blocks should be reported.
+	runtime_updates: bool,
+	/// The blocks pinned by the subscription, with their state which keeps them pinned in the
+	/// backend until it is dropped.
+	pinned: Mutex<HashMap<Block::Hash, BE::State>>,
+	/// The number of operations currently running.
+	ongoing_operations: AtomicUsize,
+	/// Whether the subscription was stopped.
+	stopped: AtomicBool,
+}
+
+impl<Block: BlockT, BE: Backend<Block>> SubscriptionState<Block, BE> {
+	/// Create a new subscription state.
+	pub fn new(backend: Arc<BE>, runtime_updates: bool) -> Self {
+		Self {
+			backend,
+			runtime_updates,
+			pinned: Default::default(),
+			ongoing_operations: AtomicUsize::new(0),
+			stopped: AtomicBool::new(false),
+		}
+	}
+
+	/// Returns whether the runtime of the blocks should be reported.
+	pub fn runtime_updates(&self) -> bool {
+		self.runtime_updates
+	}
+
+	/// Pin the given block, so that its state is not pruned by the backend until it is unpinned.
+	///
+	/// Returns `false` if the subscription already pins [`MAX_PINNED_BLOCKS`] blocks or if the
+	/// state of the block is not available.
+	pub fn pin(&self, hash: Block::Hash) -> bool {
+		let mut pinned = self.pinned.lock();
+		if pinned.contains_key(&hash) {
+			return true
+		}
+		if pinned.len() >= MAX_PINNED_BLOCKS {
+			return false
+		}
+
+		match self.backend.state_at(BlockId::Hash(hash)) {
+			Ok(state) => {
+				pinned.insert(hash, state);
+				true
+			},
+			Err(e) => {
+				warn!("Failed to pin the state of block {:?}: {:?}", hash, e);
+				false
+			},
+		}
+	}
+
+	/// Unpin the given block.
+	///
+	/// Returns `false` if the block was not pinned.
+	pub fn unpin(&self, hash: &Block::Hash) -> bool {
+		self.pinned.lock().remove(hash).is_some()
+	}
+
+	/// Returns whether the given block is pinned.
+	pub fn contains(&self, hash: &Block::Hash) -> bool {
+		self.pinned.lock().contains_key(hash)
+	}
+
+	/// Start an operation, which ends when the returned guard is dropped.
+	///
+	/// Returns `None` if [`MAX_ONGOING_OPERATIONS`] operations are already running.
+	pub fn start_operation(self: &Arc<Self>) -> Option<OperationGuard<Block, BE>> {
+		self.ongoing_operations
+			.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |ongoing| {
+				(ongoing < MAX_ONGOING_OPERATIONS).then(|| ongoing + 1)
+			})
+			.ok()
+			.map(|_| OperationGuard(self.clone()))
+	}
+}
+
+/// Guard of a running operation, the operation ends when the guard is dropped.
+pub(crate) struct OperationGuard<Block: BlockT, BE: Backend<Block>>(
+	Arc<SubscriptionState<Block, BE>>,
+);
+
+impl<Block: BlockT, BE: Backend<Block>> Drop for OperationGuard<Block, BE> {
+	fn drop(&mut self) {
+		self.0.ongoing_operations.fetch_sub(1, Ordering::SeqCst);
+	}
+}
+
+/// All active follow subscriptions.
+pub(crate) struct Subscriptions<Block: BlockT, BE: Backend<Block>> {
+	subscriptions: RwLock<HashMap<SubscriptionId, Arc<SubscriptionState<Block, BE>>>>,
+}
+
+impl<Block: BlockT, BE: Backend<Block>> Default for Subscriptions<Block, BE> {
+	fn default() -> Self {
+		Self { subscriptions: Default::default() }
+	}
+}
+
+impl<Block: BlockT, BE: Backend<Block>> Subscriptions<Block, BE> {
+	/// Register the state of the subscription with the given id.
+	///
+	/// Does nothing if the subscription was already stopped.
+	pub fn insert(&self, id: SubscriptionId, state: Arc<SubscriptionState<Block, BE>>) {
+		let mut subscriptions = self.subscriptions.write();
+		if !state.stopped.load(Ordering::SeqCst) {
+			subscriptions.insert(id, state);
+		}
+	}
+
+	/// Returns the state of the subscription with the given id.
+	pub fn get(&self, id: &SubscriptionId) -> Option<Arc<SubscriptionState<Block, BE>>> {
+		self.subscriptions.read().get(id).cloned()
+	}
+
+	/// Remove the subscription with the given id.
+	pub fn remove(&self, id: &SubscriptionId) {
+		self.subscriptions.write().remove(id);
+	}
+
+	/// Stop and remove the subscription with the given state.
+	pub fn remove_state(&self, state: &Arc<SubscriptionState<Block, BE>>) {
+		state.stopped.store(true, Ordering::SeqCst);
+		self.subscriptions.write().retain(|_, s| !Arc::ptr_eq(s, state));
+	}
+}
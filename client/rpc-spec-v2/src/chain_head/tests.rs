@@ -0,0 +1,411 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{subscription::SubscriptionState, *};
+use crate::error;
+use assert_matches::assert_matches;
+use codec::{Decode, Encode};
+use futures::{executor, Stream, StreamExt};
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use sc_block_builder::BlockBuilderProvider;
+use sc_client_api::Backend as _;
+use sc_rpc::testing::TaskExecutor;
+use sc_rpc_api::SubscriptionsMetadata;
+use serde::de::DeserializeOwned;
+use sp_consensus::BlockOrigin;
+use sp_core::{
+	bytes::{from_hex, to_hex},
+	storage::StorageKey,
+	Bytes,
+};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use sp_version::RuntimeVersion;
+use std::sync::Arc;
+use substrate_test_runtime_client::{
+	prelude::*,
+	runtime::{self, Block, H256},
+	Backend, Client,
+};
+
+type Api = ChainHead<Backend, Block, Client<Backend>>;
+
+fn setup_api() -> (Arc<Client<Backend>>, Api) {
+	let (client, backend) = TestClientBuilder::new().build_with_backend();
+	let client = Arc::new(client);
+	let api =
+		ChainHead::new(client.clone(), backend, SubscriptionManager::new(Arc::new(TaskExecutor)));
+	(client, api)
+}
+
+/// Returns the result of the next notification of the subscription.
+fn next_event<T: DeserializeOwned>(transport: &mut (impl Stream<Item = String> + Unpin)) -> T {
+	let notification = executor::block_on(transport.next()).expect("Subscription is alive; qed");
+	let notification: serde_json::Value = serde_json::from_str(&notification).unwrap();
+	serde_json::from_value(notification["params"]["result"].clone()).unwrap()
+}
+
+/// Start a follow subscription and return its id and transport.
+fn follow(api: &Api, runtime_updates: bool) -> (String, impl Stream<Item = String> + Unpin) {
+	let (subscriber, id, transport) = Subscriber::new_test("test");
+	api.follow(Default::default(), subscriber, runtime_updates);
+	let id = executor::block_on(id).unwrap().unwrap();
+	(follow_id(&id), transport)
+}
+
+/// The id of a follow subscription, as passed to the methods operating on the subscription.
+fn follow_id(id: &SubscriptionId) -> String {
+	match id {
+		SubscriptionId::String(id) => id.clone(),
+		id => panic!("Unexpected subscription id {:?}", id),
+	}
+}
+
+#[test]
+fn follow_reports_new_and_finalized_blocks() {
+	let (mut client, api) = setup_api();
+	let genesis = client.genesis_hash();
+	let (_, mut transport) = follow(&api, false);
+
+	assert_eq!(
+		next_event::<FollowEvent<H256>>(&mut transport),
+		FollowEvent::Initialized(Initialized {
+			finalized_block_hash: genesis,
+			finalized_block_runtime: None,
+		}),
+	);
+	assert_eq!(
+		next_event::<FollowEvent<H256>>(&mut transport),
+		FollowEvent::BestBlockChanged(BestBlockChanged { best_block_hash: genesis }),
+	);
+
+	let block = client.new_block(Default::default()).unwrap().build().unwrap().block;
+	let block_hash = block.hash();
+	executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
+
+	assert_eq!(
+		next_event::<FollowEvent<H256>>(&mut transport),
+		FollowEvent::NewBlock(NewBlock {
+			block_hash,
+			parent_block_hash: genesis,
+			new_runtime: None,
+		}),
+	);
+	assert_eq!(
+		next_event::<FollowEvent<H256>>(&mut transport),
+		FollowEvent::BestBlockChanged(BestBlockChanged { best_block_hash: block_hash }),
+	);
+
+	client.finalize_block(BlockId::Hash(block_hash), None).unwrap();
+
+	assert_eq!(
+		next_event::<FollowEvent<H256>>(&mut transport),
+		FollowEvent::Finalized(Finalized {
+			finalized_block_hashes: vec![block_hash],
+			pruned_block_hashes: vec![],
+		}),
+	);
+}
+
+#[test]
+fn follow_reports_finalized_blocks_without_import_notification() {
+	let (mut client, api) = setup_api();
+	let genesis = client.genesis_hash();
+	let (_, mut transport) = follow(&api, false);
+
+	assert_matches!(next_event::<FollowEvent<H256>>(&mut transport), FollowEvent::Initialized(_));
+	assert_matches!(
+		next_event::<FollowEvent<H256>>(&mut transport),
+		FollowEvent::BestBlockChanged(_)
+	);
+
+	// No import notification is sent for blocks imported from a file.
+	let block = client.new_block(Default::default()).unwrap().build().unwrap().block;
+	let block_hash = block.hash();
+	executor::block_on(client.import(BlockOrigin::File, block)).unwrap();
+	client.finalize_block(BlockId::Hash(block_hash), None).unwrap();
+
+	assert_eq!(
+		next_event::<FollowEvent<H256>>(&mut transport),
+		FollowEvent::NewBlock(NewBlock {
+			block_hash,
+			parent_block_hash: genesis,
+			new_runtime: None,
+		}),
+	);
+	assert_eq!(
+		next_event::<FollowEvent<H256>>(&mut transport),
+		FollowEvent::Finalized(Finalized {
+			finalized_block_hashes: vec![block_hash],
+			pruned_block_hashes: vec![],
+		}),
+	);
+}
+
+#[test]
+fn follow_reports_pruned_forks() {
+	let (mut client, api) = setup_api();
+	let genesis = client.genesis_hash();
+	let (_, mut transport) = follow(&api, false);
+
+	assert_matches!(next_event::<FollowEvent<H256>>(&mut transport), FollowEvent::Initialized(_));
+	assert_matches!(
+		next_event::<FollowEvent<H256>>(&mut transport),
+		FollowEvent::BestBlockChanged(_)
+	);
+
+	// genesis -> a1 -> a2
+	//         -> b1
+	let a1 = client.new_block(Default::default()).unwrap().build().unwrap().block;
+	let a1_hash = a1.hash();
+	executor::block_on(client.import(BlockOrigin::Own, a1)).unwrap();
+	let a2 = client.new_block(Default::default()).unwrap().build().unwrap().block;
+	let a2_hash = a2.hash();
+	executor::block_on(client.import(BlockOrigin::Own, a2)).unwrap();
+	let mut builder =
+		client.new_block_at(&BlockId::Hash(genesis), Default::default(), false).unwrap();
+	builder
+		.push_transfer(runtime::Transfer {
+			from: AccountKeyring::Alice.into(),
+			to: AccountKeyring::Ferdie.into(),
+			amount: 41,
+			nonce: 0,
+		})
+		.unwrap();
+	let b1 = builder.build().unwrap().block;
+	let b1_hash = b1.hash();
+	executor::block_on(client.import(BlockOrigin::Own, b1)).unwrap();
+
+	for &(block_hash, parent_block_hash) in &[(a1_hash, genesis), (a2_hash, a1_hash)] {
+		assert_eq!(
+			next_event::<FollowEvent<H256>>(&mut transport),
+			FollowEvent::NewBlock(NewBlock { block_hash, parent_block_hash, new_runtime: None }),
+		);
+		assert_eq!(
+			next_event::<FollowEvent<H256>>(&mut transport),
+			FollowEvent::BestBlockChanged(BestBlockChanged { best_block_hash: block_hash }),
+		);
+	}
+	assert_eq!(
+		next_event::<FollowEvent<H256>>(&mut transport),
+		FollowEvent::NewBlock(NewBlock {
+			block_hash: b1_hash,
+			parent_block_hash: genesis,
+			new_runtime: None,
+		}),
+	);
+
+	client.finalize_block(BlockId::Hash(a2_hash), None).unwrap();
+
+	assert_eq!(
+		next_event::<FollowEvent<H256>>(&mut transport),
+		FollowEvent::Finalized(Finalized {
+			finalized_block_hashes: vec![a1_hash, a2_hash],
+			pruned_block_hashes: vec![b1_hash],
+		}),
+	);
+}
+
+#[test]
+fn follow_reports_runtime_of_finalized_block() {
+	let (client, api) = setup_api();
+	let (_, mut transport) = follow(&api, true);
+
+	let runtime_version = client.runtime_version_at(&BlockId::Hash(client.genesis_hash())).unwrap();
+	assert_matches!(
+		next_event::<FollowEvent<H256>>(&mut transport),
+		FollowEvent::Initialized(Initialized {
+			finalized_block_runtime: Some(RuntimeEvent::Valid(RuntimeVersionEvent { spec })),
+			..
+		}) if spec == runtime_version
+	);
+}
+
+#[test]
+fn header_and_unpin_require_pinned_block() {
+	let (client, api) = setup_api();
+	let genesis = client.genesis_hash();
+	let (id, _transport) = follow(&api, false);
+
+	let header = client.header(&BlockId::Hash(genesis)).unwrap().unwrap();
+	assert_eq!(api.header(id.clone(), genesis).unwrap(), Some(to_hex(&header.encode(), false)));
+	assert_matches!(
+		api.header(id.clone(), H256::from_low_u64_be(5)),
+		Err(error::Error::InvalidBlock)
+	);
+	assert_matches!(api.header("invalid".into(), genesis), Err(error::Error::InvalidSubscription));
+
+	api.unpin(id.clone(), genesis).unwrap();
+	assert_matches!(api.header(id.clone(), genesis), Err(error::Error::InvalidBlock));
+	assert_matches!(api.unpin(id, genesis), Err(error::Error::InvalidBlock));
+}
+
+#[test]
+fn operations_report_their_result() {
+	let (client, api) = setup_api();
+	let genesis = client.genesis_hash();
+	let (id, _transport) = follow(&api, false);
+
+	let (subscriber, _, mut transport) = Subscriber::new_test("test");
+	api.body(Default::default(), subscriber, id.clone(), genesis);
+	assert_eq!(
+		next_event::<ChainHeadEvent<Vec<String>>>(&mut transport),
+		ChainHeadEvent::Done(ChainHeadResult { result: vec![] }),
+	);
+
+	let (subscriber, _, mut transport) = Subscriber::new_test("test");
+	api.storage(
+		Default::default(),
+		subscriber,
+		id.clone(),
+		genesis,
+		StorageKey(b":code".to_vec()),
+		None,
+	);
+	assert_matches!(
+		next_event::<ChainHeadEvent<Option<String>>>(&mut transport),
+		ChainHeadEvent::Done(ChainHeadResult { result: Some(_) })
+	);
+
+	let (subscriber, _, mut transport) = Subscriber::new_test("test");
+	api.call(
+		Default::default(),
+		subscriber,
+		id.clone(),
+		genesis,
+		"Core_version".into(),
+		Bytes(vec![]),
+	);
+	let output = assert_matches!(
+		next_event::<ChainHeadEvent<String>>(&mut transport),
+		ChainHeadEvent::Done(ChainHeadResult { result }) => result
+	);
+	let output = RuntimeVersion::decode(&mut &from_hex(&output).unwrap()[..]).unwrap();
+	let runtime_version = client.runtime_version_at(&BlockId::Hash(genesis)).unwrap();
+	assert_eq!(output.spec_version, runtime_version.spec_version);
+
+	// Blocks that are not pinned can not be queried.
+	let (subscriber, rejected, _) = Subscriber::<ChainHeadEvent<Vec<String>>>::new_test("test");
+	api.body(Default::default(), subscriber, id, H256::from_low_u64_be(5));
+	assert_matches!(executor::block_on(rejected), Ok(Err(_)));
+
+	// Operations of unknown follow subscriptions are disjoint.
+	let (subscriber, _, mut transport) = Subscriber::new_test("test");
+	api.body(Default::default(), subscriber, "invalid".into(), genesis);
+	assert_eq!(next_event::<ChainHeadEvent<Vec<String>>>(&mut transport), ChainHeadEvent::Disjoint,);
+}
+
+/// Wait for the connection of `meta` to have `expected` subscriptions open.
+fn wait_open_subscriptions(meta: &sc_rpc_api::Metadata, expected: usize) {
+	for _ in 0..100 {
+		if meta.open_subscriptions() == Some(expected) {
+			return
+		}
+		std::thread::sleep(std::time::Duration::from_millis(10));
+	}
+	panic!("{:?} subscriptions open, expected {}", meta.open_subscriptions(), expected);
+}
+
+#[test]
+fn subscriptions_closed_by_the_server_are_released() {
+	let (client, api) = setup_api();
+	let genesis = client.genesis_hash();
+	let (tx, _rx) = futures::channel::mpsc::unbounded();
+	let meta = sc_rpc_api::Metadata::new(tx);
+
+	let (subscriber, id, _follow_transport) = Subscriber::new_test("test");
+	api.follow(meta.clone(), subscriber, false);
+	let id = executor::block_on(id).unwrap().unwrap();
+	assert_eq!(meta.open_subscriptions(), Some(1));
+
+	// The operation is closed by the server once its event is reported.
+	let (subscriber, _, mut transport) = Subscriber::new_test("test");
+	api.body(meta.clone(), subscriber, follow_id(&id), genesis);
+	assert_matches!(
+		next_event::<ChainHeadEvent<Vec<String>>>(&mut transport),
+		ChainHeadEvent::Done(_)
+	);
+	wait_open_subscriptions(&meta, 1);
+
+	assert!(api.unfollow(None, id).unwrap());
+	wait_open_subscriptions(&meta, 0);
+}
+
+#[test]
+fn pinned_blocks_are_not_pruned() {
+	let (client, backend) = TestClientBuilder::with_pruning_window(1).build_with_backend();
+	let mut client = Arc::new(client);
+	let api = ChainHead::new(
+		client.clone(),
+		backend.clone(),
+		SubscriptionManager::new(Arc::new(TaskExecutor)),
+	);
+	let (id, mut transport) = follow(&api, false);
+	let _initialized = next_event::<FollowEvent<H256>>(&mut transport);
+	let _best_block = next_event::<FollowEvent<H256>>(&mut transport);
+
+	let mut import_and_finalize = || {
+		let block = client.new_block(Default::default()).unwrap().build().unwrap().block;
+		let hash = block.hash();
+		executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
+		let _new_block = next_event::<FollowEvent<H256>>(&mut transport);
+		let _best_block = next_event::<FollowEvent<H256>>(&mut transport);
+		client.finalize_block(BlockId::Hash(hash), None).unwrap();
+		let _finalized = next_event::<FollowEvent<H256>>(&mut transport);
+		hash
+	};
+
+	let first = import_and_finalize();
+	import_and_finalize();
+	import_and_finalize();
+	assert!(backend.state_at(BlockId::Hash(first)).is_ok());
+
+	// Once unpinned, the state of the block is pruned.
+	api.unpin(id, first).unwrap();
+	import_and_finalize();
+	assert!(backend.state_at(BlockId::Hash(first)).is_err());
+}
+
+#[test]
+fn subscription_resources_are_bounded() {
+	let (mut client, backend) = TestClientBuilder::new().build_with_backend();
+	let mut hashes = vec![client.genesis_hash()];
+	for _ in 0..MAX_PINNED_BLOCKS {
+		let block = client.new_block(Default::default()).unwrap().build().unwrap().block;
+		hashes.push(block.hash());
+		executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
+	}
+	let state = Arc::new(SubscriptionState::<Block, _>::new(backend, false));
+
+	for hash in &hashes[..MAX_PINNED_BLOCKS] {
+		assert!(state.pin(*hash));
+	}
+	assert!(!state.pin(hashes[MAX_PINNED_BLOCKS]));
+	// Pinning an already pinned block does not require more resources.
+	assert!(state.pin(hashes[0]));
+	assert!(state.unpin(&hashes[0]));
+	assert!(state.pin(hashes[MAX_PINNED_BLOCKS]));
+	// Blocks whose state is not available can not be pinned.
+	assert!(!state.pin(H256::from_low_u64_be(5)));
+
+	let guards = (0..MAX_ONGOING_OPERATIONS)
+		.map(|_| state.start_operation().unwrap())
+		.collect::<Vec<_>>();
+	assert!(state.start_operation().is_none());
+	drop(guards);
+	assert!(state.start_operation().is_some());
+}
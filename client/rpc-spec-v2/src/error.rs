@@ -0,0 +1,79 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Error helpers shared by the method groups.
+
+use jsonrpc_core as rpc;
+
+/// Result type of the methods.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Errors of the methods.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// The provided block hash is not pinned by the follow subscription of `chainHead`, or is
+	/// unknown or not finalized for `archive`.
+	#[error("Invalid block hash")]
+	InvalidBlock,
+	/// The follow subscription does not exist or was stopped.
+	#[error("Invalid follow subscription")]
+	InvalidSubscription,
+	/// Calling the runtime function failed.
+	#[error("Runtime call failed: {}", .0)]
+	Call(String),
+	/// Client error.
+	#[error("Client error: {}", .0)]
+	Client(#[from] Box<dyn std::error::Error + Send>),
+}
+
+/// Error code of [`Error::InvalidBlock`], as defined by the specification.
+const INVALID_BLOCK_ERROR: i64 = -32801;
+/// Error code of [`Error::InvalidSubscription`], as defined by the specification.
+const INVALID_SUBSCRIPTION_ERROR: i64 = -32802;
+/// Error code of [`Error::Call`].
+const CALL_ERROR: i64 = -32803;
+
+impl From<Error> for rpc::Error {
+	fn from(e: Error) -> Self {
+		match e {
+			Error::InvalidBlock => rpc::Error {
+				code: rpc::ErrorCode::ServerError(INVALID_BLOCK_ERROR),
+				message: e.to_string(),
+				data: None,
+			},
+			Error::InvalidSubscription => rpc::Error {
+				code: rpc::ErrorCode::ServerError(INVALID_SUBSCRIPTION_ERROR),
+				message: e.to_string(),
+				data: None,
+			},
+			Error::Call(_) => rpc::Error {
+				code: rpc::ErrorCode::ServerError(CALL_ERROR),
+				message: e.to_string(),
+				data: None,
+			},
+			e => {
+				log::warn!("Unknown error: {:?}", e);
+				rpc::Error {
+					code: rpc::ErrorCode::InternalError,
+					message: "Unknown error occurred".into(),
+					data: Some(format!("{:?}", e).into()),
+				}
+			},
+		}
+	}
+}
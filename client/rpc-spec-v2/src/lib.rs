@@ -0,0 +1,28 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Substrate RPC interfaces and implementation following the new JSON-RPC specification.
+//!
+//! The methods of this crate are grouped by function, where each group has its own prefix, e.g.
+//! `chainHead` or `archive`. Methods which are not yet stabilized contain `unstable` in their name.
+
+#![warn(missing_docs)]
+
+pub mod archive;
+pub mod chain_head;
+pub mod error;
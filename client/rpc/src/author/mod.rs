@@ -22,7 +22,10 @@
 mod tests;
 
 use log::warn;
-use std::{convert::TryInto, sync::Arc};
+use std::{
+	convert::{TryFrom, TryInto},
+	sync::Arc,
+};
 
 use sp_blockchain::HeaderBackend;
 
@@ -34,11 +37,11 @@ use futures::{
 use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
 use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::{
-	error::IntoPoolError, BlockHash, InPoolTransaction, TransactionFor, TransactionPool,
-	TransactionSource, TransactionStatus, TxHash,
+	error::IntoPoolError, BlockHash, InPoolTransaction, TransactionEvent, TransactionFor,
+	TransactionPool, TransactionSource, TransactionStatus, TxHash,
 };
 use sp_api::ProvideRuntimeApi;
-use sp_core::Bytes;
+use sp_core::{crypto::KeyTypeId, Bytes};
 use sp_keystore::{SyncCryptoStore, SyncCryptoStorePtr};
 use sp_runtime::{generic, traits::Block as BlockT};
 use sp_session::SessionKeys;
@@ -74,13 +77,128 @@ impl<P, Client> Author<P, Client> {
 	}
 }
 
-/// Currently we treat all RPC transactions as externals.
-///
-/// Possibly in the future we could allow opt-in for special treatment
-/// of such transactions, so that the block authors can inject
-/// some unique transactions via RPC and have them included in the pool.
+/// We treat RPC transactions as externals, unless they are explicitly submitted as local.
 const TX_SOURCE: TransactionSource = TransactionSource::External;
 
+impl<P, Client> Author<P, Client>
+where
+	P: TransactionPool + Sync + Send + 'static,
+	Client: HeaderBackend<P::Block> + Send + Sync + 'static,
+{
+	/// Submit the extrinsic to the pool at the best block.
+	fn submit(&self, source: TransactionSource, ext: Bytes) -> FutureResult<TxHash<P>> {
+		let xt = match Decode::decode(&mut &ext[..]) {
+			Ok(xt) => xt,
+			Err(err) => return async move { Err(err.into()) }.boxed(),
+		};
+		let best_block_hash = self.client.info().best_hash;
+
+		self.pool
+			.submit_one(&generic::BlockId::hash(best_block_hash), source, xt)
+			.map_err(|e| {
+				e.into_pool_error()
+					.map(Into::into)
+					.unwrap_or_else(|e| error::Error::Verification(Box::new(e)).into())
+			})
+			.boxed()
+	}
+}
+
+impl<P, Client> Author<P, Client>
+where
+	P: TransactionPool + Sync + Send + 'static,
+	Client: HeaderBackend<P::Block> + ProvideRuntimeApi<P::Block> + Send + Sync + 'static,
+	Client::Api: SessionKeys<P::Block>,
+{
+	/// Decode the given session keys at the best block into their public keys and key types.
+	fn decode_session_keys(&self, session_keys: Vec<u8>) -> Result<Vec<(Vec<u8>, KeyTypeId)>> {
+		let best_block_hash = self.client.info().best_hash;
+		self.client
+			.runtime_api()
+			.decode_session_keys(&generic::BlockId::Hash(best_block_hash), session_keys)
+			.map_err(|e| Error::Client(Box::new(e)))?
+			.ok_or_else(|| Error::InvalidSessionKeys)
+	}
+
+	/// Generate new session keys of the given key types, keeping the other ones from
+	/// `session_keys`.
+	///
+	/// Returns the encoded session keys along with their public keys and key types.
+	fn generate_session_keys(
+		&self,
+		key_types: Option<Vec<String>>,
+		session_keys: Option<Bytes>,
+	) -> Result<(Bytes, Vec<(Vec<u8>, KeyTypeId)>)> {
+		let key_types = match key_types {
+			Some(key_types) => key_types
+				.iter()
+				.map(|key_type| KeyTypeId::try_from(key_type.as_str()))
+				.collect::<std::result::Result<Vec<_>, _>>()
+				.map_err(|_| Error::BadKeyType)?,
+			None => {
+				let best_block_hash = self.client.info().best_hash;
+				let new_keys = self
+					.client
+					.runtime_api()
+					.generate_session_keys(&generic::BlockId::Hash(best_block_hash), None)
+					.map_err(|e| Error::Client(Box::new(e)))?;
+				let new_public_keys = self.decode_session_keys(new_keys.clone())?;
+				return Ok((new_keys.into(), new_public_keys))
+			},
+		};
+
+		// The runtime generates the keys of every key type, only the selected ones are generated
+		// here, with the crypto of the current keys.
+		let public_keys = self
+			.decode_session_keys(session_keys.ok_or(Error::MissingSessionKeys)?.0)?
+			.into_iter()
+			.map(|(public, key_type)| {
+				if key_types.contains(&key_type) {
+					Ok((self.generate_key_like(key_type, &public)?, key_type))
+				} else {
+					Ok((public, key_type))
+				}
+			})
+			.collect::<Result<Vec<_>>>()?;
+		// The session keys object is the concatenation of its fixed size public keys.
+		let keys = public_keys.iter().flat_map(|(public, _)| public.clone()).collect::<Vec<_>>();
+		if self.decode_session_keys(keys.clone())? != public_keys {
+			return Err(Error::InvalidSessionKeys)
+		}
+
+		Ok((keys.into(), public_keys))
+	}
+
+	/// Generate a new key of `key_type` in the keystore, of the same crypto as its `current`
+	/// public key.
+	fn generate_key_like(&self, key_type: KeyTypeId, current: &[u8]) -> Result<Vec<u8>> {
+		let keystore = &*self.keystore;
+		let generated = if SyncCryptoStore::sr25519_public_keys(keystore, key_type)
+			.iter()
+			.any(|public| AsRef::<[u8]>::as_ref(public) == current)
+		{
+			SyncCryptoStore::sr25519_generate_new(keystore, key_type, None)
+				.map(|public| public.0.to_vec())
+		} else if SyncCryptoStore::ed25519_public_keys(keystore, key_type)
+			.iter()
+			.any(|public| AsRef::<[u8]>::as_ref(public) == current)
+		{
+			SyncCryptoStore::ed25519_generate_new(keystore, key_type, None)
+				.map(|public| public.0.to_vec())
+		} else if SyncCryptoStore::ecdsa_public_keys(keystore, key_type)
+			.iter()
+			.any(|public| AsRef::<[u8]>::as_ref(public) == current)
+		{
+			SyncCryptoStore::ecdsa_generate_new(keystore, key_type, None)
+				.map(|public| public.0.to_vec())
+		} else {
+			// The crypto of the key type is only known from the current key in the keystore.
+			return Err(Error::UnsupportedKeyType)
+		};
+		generated.map_err(|_| Error::KeyStoreUnavailable)
+	}
+}
+
 impl<P, Client> AuthorApi<TxHash<P>, BlockHash<P>> for Author<P, Client>
 where
 	P: TransactionPool + Sync + Send + 'static,
@@ -100,27 +218,47 @@ where
 		Ok(())
 	}
 
-	fn rotate_keys(&self) -> Result<Bytes> {
+	fn rotate_keys(
+		&self,
+		key_types: Option<Vec<String>>,
+		session_keys: Option<Bytes>,
+	) -> Result<Bytes> {
 		self.deny_unsafe.check_if_safe()?;
 
-		let best_block_hash = self.client.info().best_hash;
-		self.client
-			.runtime_api()
-			.generate_session_keys(&generic::BlockId::Hash(best_block_hash), None)
-			.map(Into::into)
-			.map_err(|e| Error::Client(Box::new(e)))
+		self.generate_session_keys(key_types, session_keys).map(|(keys, _)| keys)
 	}
 
-	fn has_session_keys(&self, session_keys: Bytes) -> Result<bool> {
+	fn rotate_keys_with_details(
+		&self,
+		key_types: Option<Vec<String>>,
+		session_keys: Option<Bytes>,
+	) -> Result<RotatedSessionKeys> {
 		self.deny_unsafe.check_if_safe()?;
 
-		let best_block_hash = self.client.info().best_hash;
-		let keys = self
-			.client
-			.runtime_api()
-			.decode_session_keys(&generic::BlockId::Hash(best_block_hash), session_keys.to_vec())
-			.map_err(|e| Error::Client(Box::new(e)))?
-			.ok_or_else(|| Error::InvalidSessionKeys)?;
+		let (keys, public_keys) = self.generate_session_keys(key_types, session_keys)?;
+		let public_keys = public_keys
+			.into_iter()
+			.map(|(public, key_type)| (String::from_utf8_lossy(&key_type.0).into(), public.into()))
+			.collect();
+		Ok(RotatedSessionKeys { keys, public_keys })
+	}
+
+	fn has_session_keys(
+		&self,
+		session_keys: Bytes,
+		key_types: Option<Vec<String>>,
+	) -> Result<bool> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let mut keys = self.decode_session_keys(session_keys.to_vec())?;
+		if let Some(key_types) = key_types {
+			let key_types = key_types
+				.iter()
+				.map(|key_type| KeyTypeId::try_from(key_type.as_str()))
+				.collect::<std::result::Result<Vec<_>, _>>()
+				.map_err(|_| Error::BadKeyType)?;
+			keys.retain(|(_, key_type)| key_types.contains(key_type));
+		}
 
 		Ok(SyncCryptoStore::has_keys(&*self.keystore, &keys))
 	}
@@ -133,20 +271,15 @@ where
 	}
 
 	fn submit_extrinsic(&self, ext: Bytes) -> FutureResult<TxHash<P>> {
-		let xt = match Decode::decode(&mut &ext[..]) {
-			Ok(xt) => xt,
-			Err(err) => return async move { Err(err.into()) }.boxed(),
-		};
-		let best_block_hash = self.client.info().best_hash;
+		self.submit(TX_SOURCE, ext)
+	}
 
-		self.pool
-			.submit_one(&generic::BlockId::hash(best_block_hash), TX_SOURCE, xt)
-			.map_err(|e| {
-				e.into_pool_error()
-					.map(Into::into)
-					.unwrap_or_else(|e| error::Error::Verification(Box::new(e)).into())
-			})
-			.boxed()
+	fn submit_local(&self, ext: Bytes) -> FutureResult<TxHash<P>> {
+		if let Err(err) = self.deny_unsafe.check_if_safe() {
+			return async move { Err(err.into()) }.boxed()
+		}
+
+		self.submit(TransactionSource::Local, ext)
 	}
 
 	fn pending_extrinsics(&self) -> Result<Vec<Bytes>> {
@@ -180,7 +313,7 @@ where
 
 	fn watch_extrinsic(
 		&self,
-		_metadata: Self::Metadata,
+		metadata: Self::Metadata,
 		subscriber: Subscriber<TransactionStatus<TxHash<P>, BlockHash<P>>>,
 		xt: Bytes,
 	) {
@@ -206,6 +339,7 @@ where
 			});
 
 		let subscriptions = self.subscriptions.clone();
+		let subscription = metadata.open_subscription();
 
 		let future = async move {
 			let tx_stream = match submit.await {
@@ -223,7 +357,7 @@ where
 				tx_stream
 					.map(|v| Ok(Ok(v)))
 					.forward(sink.sink_map_err(|e| warn!("Error sending notifications: {:?}", e)))
-					.map(drop)
+					.map(move |_| drop(subscription))
 			});
 		};
 
@@ -240,4 +374,32 @@ where
 	) -> Result<bool> {
 		Ok(self.subscriptions.cancel(id))
 	}
+
+	fn subscribe_pool_events(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<TransactionEvent<TxHash<P>, BlockHash<P>>>,
+	) {
+		if let Err(err) = self.deny_unsafe.check_if_safe() {
+			let _ = subscriber.reject(err.into());
+			return
+		}
+
+		let stream = self.pool.event_stream();
+		let subscription = metadata.open_subscription();
+		self.subscriptions.add(subscriber, |sink| {
+			stream
+				.map(|event| Ok(Ok(event)))
+				.forward(sink.sink_map_err(|e| warn!("Error sending notifications: {:?}", e)))
+				.map(move |_| drop(subscription))
+		});
+	}
+
+	fn unsubscribe_pool_events(
+		&self,
+		_metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> Result<bool> {
+		Ok(self.subscriptions.cancel(id))
+	}
 }
@@ -104,6 +104,20 @@ fn submit_rich_transaction_should_not_cause_error() {
 	assert!(executor::block_on(AuthorApi::submit_extrinsic(&p, xt.into())).is_err());
 }
 
+#[test]
+fn submit_local_transaction_should_use_local_source() {
+	let setup = TestSetup::default();
+	let p = setup.author();
+	let xt = uxt(AccountKeyring::Alice, 0).encode();
+	let h: H256 = blake2_256(&xt).into();
+
+	assert_matches!(
+		executor::block_on(AuthorApi::submit_local(&p, xt.into())),
+		Ok(h2) if h == h2
+	);
+	assert_eq!(setup.pool.ready_transaction(&h).unwrap().source(), TransactionSource::Local);
+}
+
 #[test]
 fn should_watch_extrinsic() {
 	// given
@@ -157,6 +171,54 @@ fn should_watch_extrinsic() {
 	assert_eq!(res, expected);
 }
 
+#[test]
+fn should_subscribe_to_pool_events() {
+	// given
+	let setup = TestSetup::default();
+	let p = setup.author();
+
+	let (subscriber, id_rx, data) = jsonrpc_pubsub::typed::Subscriber::new_test("test");
+	p.subscribe_pool_events(Default::default(), subscriber);
+
+	let id = match executor::block_on(id_rx).unwrap().unwrap() {
+		SubscriptionId::String(id) => id,
+		_ => unreachable!(),
+	};
+
+	// when
+	let xt = uxt(AccountKeyring::Alice, 0);
+	executor::block_on(AuthorApi::submit_extrinsic(&p, xt.encode().into())).unwrap();
+
+	// then
+	let h = blake2_256(&xt.encode());
+	let (res, data) = executor::block_on(data.into_future());
+	let expected = Some(format!(
+		r#"{{"jsonrpc":"2.0","method":"test","params":{{"result":{{"hash":"0x{}","event":"imported"}},"subscription":"{}"}}}}"#,
+		HexDisplay::from(&h),
+		id,
+	));
+	assert_eq!(res, expected);
+
+	let res = executor::block_on(data.into_future()).0;
+	let expected = Some(format!(
+		r#"{{"jsonrpc":"2.0","method":"test","params":{{"result":{{"hash":"0x{}","event":"ready"}},"subscription":"{}"}}}}"#,
+		HexDisplay::from(&h),
+		id,
+	));
+	assert_eq!(res, expected);
+}
+
+#[test]
+fn should_not_subscribe_to_pool_events_if_unsafe_is_denied() {
+	let setup = TestSetup::default();
+	let p = Author { deny_unsafe: DenyUnsafe::Yes, ..setup.author() };
+
+	let (subscriber, id_rx, _data) = jsonrpc_pubsub::typed::Subscriber::new_test("test");
+	p.subscribe_pool_events(Default::default(), subscriber);
+
+	assert!(executor::block_on(id_rx).unwrap().is_err());
+}
+
 #[test]
 fn should_return_watch_validation_error() {
 	// given
@@ -239,7 +301,7 @@ fn should_rotate_keys() {
 	let setup = TestSetup::default();
 	let p = setup.author();
 
-	let new_public_keys = p.rotate_keys().expect("Rotates the keys");
+	let new_public_keys = p.rotate_keys(None, None).expect("Rotates the keys");
 
 	let session_keys =
 		SessionKeys::decode(&mut &new_public_keys[..]).expect("SessionKeys decode successfully");
@@ -259,9 +321,9 @@ fn test_has_session_keys() {
 	let p = setup.author();
 
 	let non_existent_public_keys =
-		TestSetup::default().author().rotate_keys().expect("Rotates the keys");
+		TestSetup::default().author().rotate_keys(None, None).expect("Rotates the keys");
 
-	let public_keys = p.rotate_keys().expect("Rotates the keys");
+	let public_keys = p.rotate_keys(None, None).expect("Rotates the keys");
 	let test_vectors = vec![
 		(public_keys, Ok(true)),
 		(vec![1, 2, 3].into(), Err(Error::InvalidSessionKeys)),
@@ -271,11 +333,56 @@ fn test_has_session_keys() {
 	for (keys, result) in test_vectors {
 		assert_eq!(
 			result.map_err(|e| mem::discriminant(&e)),
-			p.has_session_keys(keys).map_err(|e| mem::discriminant(&e)),
+			p.has_session_keys(keys, None).map_err(|e| mem::discriminant(&e)),
 		);
 	}
 }
 
+#[test]
+fn should_rotate_only_the_given_key_types() {
+	let setup = TestSetup::default();
+	let p = setup.author();
+
+	let keys = p.rotate_keys(None, None).expect("Rotates the keys");
+	let current = SessionKeys::decode(&mut &keys[..]).unwrap();
+
+	assert_matches!(p.rotate_keys(Some(vec!["sr25".into()]), None), Err(Error::MissingSessionKeys));
+	let rotated = p
+		.rotate_keys_with_details(Some(vec!["sr25".into()]), Some(keys.clone()))
+		.expect("Rotates the sr25519 key");
+	let new = SessionKeys::decode(&mut &rotated.keys[..]).unwrap();
+
+	assert_eq!(new.ed25519, current.ed25519);
+	assert_ne!(new.sr25519, current.sr25519);
+	assert_eq!(rotated.public_keys["ed25"], current.ed25519.to_raw_vec().into());
+	assert_eq!(rotated.public_keys["sr25"], new.sr25519.to_raw_vec().into());
+	assert_matches!(p.has_session_keys(rotated.keys, None), Ok(true));
+	// No key of the other key types is generated.
+	assert_eq!(SyncCryptoStore::keys(&*setup.keystore, ED25519).unwrap().len(), 1);
+	assert_eq!(SyncCryptoStore::keys(&*setup.keystore, SR25519).unwrap().len(), 2);
+}
+
+#[test]
+fn should_check_session_keys_of_the_given_key_types() {
+	let setup = TestSetup::default();
+	let p = setup.author();
+
+	let keys = p.rotate_keys(None, None).expect("Rotates the keys");
+	let mut session_keys = SessionKeys::decode(&mut &keys[..]).unwrap();
+	session_keys.ed25519 = ed25519::Pair::generate().0.public().into();
+	let partially_known: Bytes = session_keys.encode().into();
+
+	assert_matches!(p.has_session_keys(partially_known.clone(), None), Ok(false));
+	assert_matches!(
+		p.has_session_keys(partially_known.clone(), Some(vec!["sr25".into()])),
+		Ok(true)
+	);
+	assert_matches!(
+		p.has_session_keys(partially_known, Some(vec!["invalid".into()])),
+		Err(Error::BadKeyType)
+	);
+}
+
 #[test]
 fn test_has_key() {
 	let setup = TestSetup::default();
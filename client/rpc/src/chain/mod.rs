@@ -37,6 +37,7 @@ use sc_client_api::{
 	light::{Fetcher, RemoteBlockchain},
 	BlockchainEvents,
 };
+use sc_rpc_api::SubscriptionGuard;
 use sp_rpc::{list::ListOrValue, number::NumberOrHex};
 use sp_runtime::{
 	generic::{BlockId, SignedBlock},
@@ -110,13 +111,14 @@ where
 	/// All new head subscription
 	fn subscribe_all_heads(
 		&self,
-		_metadata: crate::Metadata,
+		metadata: crate::Metadata,
 		subscriber: Subscriber<Block::Header>,
 	) {
 		subscribe_headers(
 			self.client(),
 			self.subscriptions(),
 			subscriber,
+			metadata.open_subscription(),
 			|| self.client().info().best_hash,
 			|| {
 				self.client()
@@ -138,13 +140,14 @@ where
 	/// New best head subscription
 	fn subscribe_new_heads(
 		&self,
-		_metadata: crate::Metadata,
+		metadata: crate::Metadata,
 		subscriber: Subscriber<Block::Header>,
 	) {
 		subscribe_headers(
 			self.client(),
 			self.subscriptions(),
 			subscriber,
+			metadata.open_subscription(),
 			|| self.client().info().best_hash,
 			|| {
 				self.client()
@@ -167,13 +170,14 @@ where
 	/// Finalized head subscription
 	fn subscribe_finalized_heads(
 		&self,
-		_metadata: crate::Metadata,
+		metadata: crate::Metadata,
 		subscriber: Subscriber<Block::Header>,
 	) {
 		subscribe_headers(
 			self.client(),
 			self.subscriptions(),
 			subscriber,
+			metadata.open_subscription(),
 			|| self.client().info().finalized_hash,
 			|| {
 				self.client()
@@ -317,6 +321,7 @@ fn subscribe_headers<Block, Client, F, G, S>(
 	client: &Arc<Client>,
 	subscriptions: &SubscriptionManager,
 	subscriber: Subscriber<Block::Header>,
+	subscription: SubscriptionGuard,
 	best_block_hash: G,
 	stream: F,
 ) where
@@ -346,7 +351,7 @@ fn subscribe_headers<Block, Client, F, G, S>(
 			.chain(stream)
 			.forward(sink.sink_map_err(|e| warn!("Error sending notifications: {:?}", e)))
 			// we ignore the resulting Stream (if the first stream is over we are unsubscribed)
-			.map(|_| ())
+			.map(move |_| drop(subscription))
 	});
 }
 
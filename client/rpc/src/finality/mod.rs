@@ -0,0 +1,103 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Substrate finality API.
+
+#[cfg(test)]
+mod tests;
+
+use futures::{stream, FutureExt, SinkExt, StreamExt};
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use log::warn;
+use rpc::Result as RpcResult;
+use sc_client_api::{BlockBackend, BlockchainEvents};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::{marker::PhantomData, sync::Arc};
+
+pub use sc_rpc_api::finality::*;
+
+/// Finality API, streaming the justifications of finalized blocks.
+pub struct Finality<Block, Client> {
+	client: Arc<Client>,
+	subscriptions: SubscriptionManager,
+	_phantom: PhantomData<Block>,
+}
+
+impl<Block, Client> Finality<Block, Client> {
+	/// Create new instance of Finality API.
+	pub fn new(client: Arc<Client>, subscriptions: SubscriptionManager) -> Self {
+		Self { client, subscriptions, _phantom: PhantomData }
+	}
+}
+
+impl<Block, Client> FinalityApi<Block::Hash> for Finality<Block, Client>
+where
+	Block: BlockT + 'static,
+	Block::Hash: Unpin,
+	Client: BlockBackend<Block> + BlockchainEvents<Block> + Send + Sync + 'static,
+{
+	type Metadata = crate::Metadata;
+
+	fn subscribe_proofs(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<FinalityProof<Block::Hash>>,
+	) {
+		// the client owns the notification sinks, a strong reference would keep it alive.
+		let client = Arc::downgrade(&self.client);
+		let stream = self.client.finality_notification_stream().flat_map(move |notification| {
+			let justifications = match client
+				.upgrade()
+				.map(|client| client.justifications(&BlockId::Hash(notification.hash)))
+				.transpose()
+			{
+				Ok(justifications) => justifications.flatten(),
+				Err(e) => {
+					warn!("Failed to get justifications of block {}: {:?}", notification.hash, e);
+					None
+				},
+			};
+
+			stream::iter(justifications.into_iter().flatten().map(
+				move |(engine_id, justification)| {
+					Ok::<_, ()>(Ok(FinalityProof {
+						block_hash: notification.hash,
+						engine_id: engine_id.to_vec().into(),
+						justification: justification.into(),
+					}))
+				},
+			))
+		});
+
+		let subscription = metadata.open_subscription();
+		self.subscriptions.add(subscriber, |sink| {
+			stream
+				.forward(sink.sink_map_err(|e| warn!("Error sending notifications: {:?}", e)))
+				// we ignore the resulting Stream (if the first stream is over we are unsubscribed)
+				.map(move |_| drop(subscription))
+		});
+	}
+
+	fn unsubscribe_proofs(
+		&self,
+		_metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> RpcResult<bool> {
+		Ok(self.subscriptions.cancel(id))
+	}
+}
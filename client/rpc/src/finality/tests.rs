@@ -0,0 +1,57 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::testing::TaskExecutor;
+use futures::executor;
+use sc_block_builder::BlockBuilderProvider;
+use sp_consensus::BlockOrigin;
+use substrate_test_runtime_client::{prelude::*, runtime::Block};
+
+#[test]
+fn should_notify_about_justifications_of_finalized_blocks() {
+	let (subscriber, id, mut transport) = Subscriber::new_test("test");
+
+	{
+		let mut client = Arc::new(substrate_test_runtime_client::new());
+		let api = Finality::<Block, _>::new(
+			client.clone(),
+			SubscriptionManager::new(Arc::new(TaskExecutor)),
+		);
+
+		api.subscribe_proofs(Default::default(), subscriber);
+
+		// assert id assigned
+		assert!(matches!(executor::block_on(id), Ok(Ok(SubscriptionId::String(_)))));
+
+		// finalized without a justification, nothing is sent.
+		let block = client.new_block(Default::default()).unwrap().build().unwrap().block;
+		executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
+		client.finalize_block(BlockId::number(1), None).unwrap();
+
+		let block = client.new_block(Default::default()).unwrap().build().unwrap().block;
+		executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
+		client
+			.finalize_block(BlockId::number(2), Some((*b"TEST", vec![1, 2, 3])))
+			.unwrap();
+	}
+
+	let notification = executor::block_on(transport.next()).unwrap();
+	assert!(notification.contains(r#""engineId":"0x54455354","justification":"0x010203""#));
+	assert!(executor::block_on(transport.next()).is_none());
+}
@@ -32,6 +32,9 @@ use sp_core::{
 };
 use std::sync::Arc;
 
+/// Maximum number of entries returned by `offchain_localStorageGetPaged`.
+const LOCAL_STORAGE_PAGED_MAX_COUNT: u32 = 1000;
+
 /// Offchain API
 #[derive(Debug)]
 pub struct Offchain<T: OffchainStorage> {
@@ -70,4 +73,35 @@ impl<T: OffchainStorage + 'static> OffchainApi for Offchain<T> {
 		};
 		Ok(self.storage.read().get(prefix, &*key).map(Into::into))
 	}
+
+	/// Get offchain local storage entries whose key starts with the given prefix.
+	fn get_local_storage_paged(
+		&self,
+		kind: StorageKind,
+		prefix: Bytes,
+		count: u32,
+		start_key: Option<Bytes>,
+	) -> Result<Vec<(Bytes, Bytes)>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		if count > LOCAL_STORAGE_PAGED_MAX_COUNT {
+			return Err(Error::InvalidCount { value: count, max: LOCAL_STORAGE_PAGED_MAX_COUNT })
+		}
+		let storage_prefix = match kind {
+			StorageKind::PERSISTENT => sp_offchain::STORAGE_PREFIX,
+			StorageKind::LOCAL => return Err(Error::UnavailableStorageKind),
+		};
+		let entries = self
+			.storage
+			.read()
+			.entries_with_prefix(
+				storage_prefix,
+				&*prefix,
+				start_key.as_ref().map(|key| &key[..]),
+				count as usize,
+			)
+			.ok_or(Error::UnsupportedEnumeration)?;
+
+		Ok(entries.into_iter().map(|(key, value)| (key.into(), value.into())).collect())
+	}
 }
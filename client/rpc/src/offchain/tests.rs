@@ -53,3 +53,45 @@ fn offchain_calls_considered_unsafe() {
 		Err(Error::UnsafeRpcCalled(_))
 	);
 }
+
+#[test]
+fn local_storage_paged_should_work() {
+	let storage = InMemOffchainStorage::default();
+	let offchain = Offchain::new(storage, DenyUnsafe::No);
+	for key in &[b"key3", b"key1", b"key2"] {
+		offchain
+			.set_local_storage(StorageKind::PERSISTENT, Bytes(key.to_vec()), Bytes(b"v".to_vec()))
+			.unwrap();
+	}
+	offchain
+		.set_local_storage(StorageKind::PERSISTENT, Bytes(b"other".to_vec()), Bytes(b"v".to_vec()))
+		.unwrap();
+
+	let keys =
+		|entries: Vec<(Bytes, Bytes)>| entries.into_iter().map(|(k, _)| k.0).collect::<Vec<_>>();
+	assert_eq!(
+		keys(
+			offchain
+				.get_local_storage_paged(StorageKind::PERSISTENT, Bytes(b"key".to_vec()), 2, None)
+				.unwrap()
+		),
+		vec![b"key1".to_vec(), b"key2".to_vec()],
+	);
+	assert_eq!(
+		keys(
+			offchain
+				.get_local_storage_paged(
+					StorageKind::PERSISTENT,
+					Bytes(b"key".to_vec()),
+					2,
+					Some(Bytes(b"key2".to_vec())),
+				)
+				.unwrap()
+		),
+		vec![b"key3".to_vec()],
+	);
+	assert_matches!(
+		offchain.get_local_storage_paged(StorageKind::PERSISTENT, Bytes(vec![]), 1001, None),
+		Err(Error::InvalidCount { value: 1001, max: 1000 })
+	);
+}
@@ -143,6 +143,15 @@ where
 		keys: Vec<StorageKey>,
 	) -> FutureResult<ReadProof<Block::Hash>>;
 
+	/// Query the events deposited under `topic` in the blocks from `from` to `to` (the best block
+	/// if `None`), both included.
+	fn query_events_by_topic(
+		&self,
+		from: Block::Hash,
+		to: Option<Block::Hash>,
+		topic: Block::Hash,
+	) -> FutureResult<Vec<EventLocation<Block::Hash>>>;
+
 	/// New runtime version subscription
 	fn subscribe_runtime_version(
 		&self,
@@ -367,6 +376,19 @@ where
 		self.backend.read_proof(block, keys)
 	}
 
+	fn query_events_by_topic(
+		&self,
+		topic: Block::Hash,
+		from: Block::Hash,
+		to: Option<Block::Hash>,
+	) -> FutureResult<Vec<EventLocation<Block::Hash>>> {
+		if let Err(err) = self.deny_unsafe.check_if_safe() {
+			return async move { Err(err.into()) }.boxed()
+		}
+
+		self.backend.query_events_by_topic(from, to, topic)
+	}
+
 	fn subscribe_storage(
 		&self,
 		meta: Self::Metadata,
@@ -482,6 +504,9 @@ where
 	) -> FutureResult<Option<Block::Hash>>;
 
 	/// Returns the size of a child storage entry at a block's state.
+	///
+	/// If data is available at `key`, it is returned. Else, the sum of values who's key has `key`
+	/// prefix is returned, if supported by the backend.
 	fn storage_size(
 		&self,
 		block: Option<Block::Hash>,
@@ -550,6 +575,12 @@ where
 		start_key: Option<StorageKey>,
 		block: Option<Block::Hash>,
 	) -> FutureResult<Vec<StorageKey>> {
+		if count > STORAGE_KEYS_PAGED_MAX_COUNT {
+			return async move {
+				Err(Error::InvalidCount { value: count, max: STORAGE_KEYS_PAGED_MAX_COUNT })
+			}
+			.boxed()
+		}
 		self.backend.storage_keys_paged(block, storage_key, prefix, count, start_key)
 	}
 
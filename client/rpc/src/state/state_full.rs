@@ -32,12 +32,15 @@ use std::{
 	sync::Arc,
 };
 
-use sc_rpc_api::state::ReadProof;
+use codec::{Decode, Encode};
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use sc_rpc_api::state::{EventLocation, ReadProof};
 use sp_blockchain::{
 	CachedHeaderMetadata, Error as ClientError, HeaderBackend, HeaderMetadata,
 	Result as ClientResult,
 };
 use sp_core::{
+	hashing::{blake2_128, twox_128},
 	storage::{
 		ChildInfo, ChildType, PrefixedStorageKey, StorageChangeSet, StorageData, StorageKey,
 	},
@@ -468,11 +471,52 @@ where
 		async move { r }.boxed()
 	}
 
+	fn query_events_by_topic(
+		&self,
+		from: Block::Hash,
+		to: Option<Block::Hash>,
+		topic: Block::Hash,
+	) -> FutureResult<Vec<EventLocation<Block::Hash>>> {
+		let call_fn = move || {
+			let range = self.split_query_storage_range(from, to)?;
+			// the `System` pallet may be named differently by the runtime.
+			let metadata = self
+				.client
+				.runtime_api()
+				.metadata(&BlockId::Hash(from))
+				.map_err(|e| Error::Client(Box::new(e)))?;
+			let prefix = event_topics_prefix(&metadata).map_err(client_err)?;
+			let key = event_topic_key(&prefix, &topic);
+			let mut events = Vec::new();
+			for block in range.hashes {
+				// the index only holds the topics of the events of the last block, the events
+				// deposited by `block` are read from its state.
+				let indexed =
+					match self.client.storage(&BlockId::Hash(block), &key).map_err(client_err)? {
+						Some(data) => <Vec<(NumberFor<Block>, u32)>>::decode(&mut &data.0[..])
+							.map_err(|e| {
+								client_err(ClientError::Storage(format!(
+									"Failed to decode the event topic index: {}",
+									e
+								)))
+							})?,
+						None => continue,
+					};
+				events.extend(indexed.into_iter().map(|(_, index)| EventLocation { block, index }));
+			}
+			Ok(events)
+		};
+
+		let r = call_fn();
+		async move { r }.boxed()
+	}
+
 	fn subscribe_runtime_version(
 		&self,
-		_meta: crate::Metadata,
+		meta: crate::Metadata,
 		subscriber: Subscriber<RuntimeVersion>,
 	) {
+		let subscription = meta.open_subscription();
 		self.subscriptions.add(subscriber, |sink| {
 			let version = self
 				.block_or_best(None)
@@ -507,7 +551,7 @@ where
 				.chain(stream)
 				.forward(sink.sink_map_err(|e| warn!("Error sending notifications: {:?}", e)))
 				// we ignore the resulting Stream (if the first stream is over we are unsubscribed)
-				.map(|_| ())
+				.map(move |_| drop(subscription))
 		});
 	}
 
@@ -521,7 +565,7 @@ where
 
 	fn subscribe_storage(
 		&self,
-		_meta: crate::Metadata,
+		meta: crate::Metadata,
 		subscriber: Subscriber<StorageChangeSet<Block::Hash>>,
 		keys: Option<Vec<StorageKey>>,
 	) {
@@ -553,6 +597,7 @@ where
 			.unwrap_or_default(),
 		);
 
+		let subscription = meta.open_subscription();
 		self.subscriptions.add(subscriber, |sink| {
 			let stream = stream.map(|(block, changes)| {
 				Ok(Ok::<_, rpc::Error>(StorageChangeSet {
@@ -568,7 +613,7 @@ where
 				.chain(stream)
 				.forward(sink.sink_map_err(|e| warn!("Error sending notifications: {:?}", e)))
 				// we ignore the resulting Stream (if the first stream is over we are unsubscribed)
-				.map(|_| ())
+				.map(move |_| drop(subscription))
 		});
 	}
 
@@ -766,6 +811,39 @@ where
 
 		async move { r }.boxed()
 	}
+
+	fn storage_size(
+		&self,
+		block: Option<Block::Hash>,
+		storage_key: PrefixedStorageKey,
+		key: StorageKey,
+	) -> FutureResult<Option<u64>> {
+		let r = self
+			.block_or_best(block)
+			.and_then(|block| {
+				let child_info = match ChildType::from_prefixed_key(&storage_key) {
+					Some((ChildType::ParentKeyId, storage_key)) =>
+						ChildInfo::new_default(storage_key),
+					None => return Err(sp_blockchain::Error::InvalidChildStorageKey),
+				};
+				let block = BlockId::Hash(block);
+				if let Some(d) = self.client.child_storage(&block, &child_info, &key)? {
+					return Ok(Some(d.0.len() as u64))
+				}
+
+				let mut item_sum = 0;
+				for key in self.client.child_storage_keys(&block, &child_info, &key)? {
+					item_sum += self
+						.client
+						.child_storage(&block, &child_info, &key)?
+						.map_or(0, |d| d.0.len() as u64);
+				}
+				Ok(if item_sum > 0 { Some(item_sum) } else { None })
+			})
+			.map_err(client_err);
+
+		async move { r }.boxed()
+	}
 }
 
 /// Splits passed range into two subranges where:
@@ -792,6 +870,35 @@ pub(crate) fn split_range(
 	(range1, range2)
 }
 
+/// Storage prefix of the pallet declaring the `EventTopics` map, i.e. of the `System` pallet, as
+/// found in the runtime `metadata`.
+pub(crate) fn event_topics_prefix(metadata: &[u8]) -> ClientResult<String> {
+	let metadata = RuntimeMetadataPrefixed::decode(&mut &metadata[..]).map_err(|e| {
+		ClientError::Storage(format!("Failed to decode the runtime metadata: {}", e))
+	})?;
+	let pallets = match metadata.1 {
+		RuntimeMetadata::V14(metadata) => metadata.pallets,
+		_ => return Err(ClientError::Storage("Unsupported runtime metadata version".into())),
+	};
+	pallets
+		.into_iter()
+		.filter_map(|pallet| pallet.storage)
+		.find(|storage| storage.entries.iter().any(|entry| entry.name == "EventTopics"))
+		.map(|storage| storage.prefix)
+		.ok_or_else(|| ClientError::Storage("The runtime doesn't index events by topic".into()))
+}
+
+/// Storage key of the events deposited under `topic`, in the `EventTopics` map of the `System`
+/// pallet, whose storage prefix is `prefix`.
+pub(crate) fn event_topic_key<Hash: Encode>(prefix: &str, topic: &Hash) -> StorageKey {
+	let topic = topic.encode();
+	let mut key = twox_128(prefix.as_bytes()).to_vec();
+	key.extend(&twox_128(b"EventTopics"));
+	key.extend(&blake2_128(&topic));
+	key.extend(topic);
+	StorageKey(key)
+}
+
 fn invalid_block_range<B: BlockT>(
 	from: &CachedHeaderMetadata<B>,
 	to: &CachedHeaderMetadata<B>,
@@ -41,7 +41,7 @@ use sc_client_api::{
 	},
 	BlockchainEvents,
 };
-use sc_rpc_api::state::ReadProof;
+use sc_rpc_api::state::{EventLocation, ReadProof};
 use sp_blockchain::{Error as ClientError, HeaderBackend};
 use sp_core::{
 	storage::{PrefixedStorageKey, StorageChangeSet, StorageData, StorageKey},
@@ -289,7 +289,7 @@ where
 
 	fn subscribe_storage(
 		&self,
-		_meta: crate::Metadata,
+		meta: crate::Metadata,
 		subscriber: Subscriber<StorageChangeSet<Block::Hash>>,
 		keys: Option<Vec<StorageKey>>,
 	) {
@@ -303,6 +303,7 @@ where
 
 		let keys = keys.iter().cloned().collect::<HashSet<_>>();
 		let keys_to_check = keys.iter().map(|k| k.0.clone()).collect::<HashSet<_>>();
+		let subscription = meta.open_subscription();
 		let subscription_id = self.subscriptions.add(subscriber, move |sink| {
 			let fetcher = self.fetcher.clone();
 			let remote_blockchain = self.remote_blockchain.clone();
@@ -352,7 +353,7 @@ where
 				.map_ok(Ok)
 				.forward(sink.sink_map_err(|e| warn!("Error sending notifications: {:?}", e)))
 				// we ignore the resulting Stream (if the first stream is over we are unsubscribed)
-				.map(|_| ())
+				.map(move |_| drop(subscription))
 		});
 
 		// remember keys associated with this subscription
@@ -401,9 +402,10 @@ where
 
 	fn subscribe_runtime_version(
 		&self,
-		_meta: crate::Metadata,
+		meta: crate::Metadata,
 		subscriber: Subscriber<RuntimeVersion>,
 	) {
+		let subscription = meta.open_subscription();
 		self.subscriptions.add(subscriber, move |sink| {
 			let fetcher = self.fetcher.clone();
 			let remote_blockchain = self.remote_blockchain.clone();
@@ -432,7 +434,7 @@ where
 				.map_ok(Ok)
 				.forward(sink.sink_map_err(|e| warn!("Error sending notifications: {:?}", e)))
 				// we ignore the resulting Stream (if the first stream is over we are unsubscribed)
-				.map(|_| ())
+				.map(move |_| drop(subscription))
 		});
 	}
 
@@ -444,6 +446,15 @@ where
 		Ok(self.subscriptions.cancel(id))
 	}
 
+	fn query_events_by_topic(
+		&self,
+		_from: Block::Hash,
+		_to: Option<Block::Hash>,
+		_topic: Block::Hash,
+	) -> FutureResult<Vec<EventLocation<Block::Hash>>> {
+		async move { Err(client_err(ClientError::NotAvailableOnLightClient)) }.boxed()
+	}
+
 	fn trace_block(
 		&self,
 		_block: Block::Hash,
@@ -17,9 +17,13 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use self::error::Error;
-use super::{state_full::split_range, *};
+use super::{
+	state_full::{event_topic_key, split_range},
+	*,
+};
 use crate::testing::TaskExecutor;
 use assert_matches::assert_matches;
+use codec::Encode;
 use futures::{executor, StreamExt};
 use sc_block_builder::BlockBuilderProvider;
 use sc_rpc_api::DenyUnsafe;
@@ -236,6 +240,53 @@ fn should_return_child_storage_entries() {
 		executor::block_on(child.storage_size(child_key.clone(), keys[0].clone(), None)),
 		Ok(Some(1))
 	);
+	assert_matches!(
+		executor::block_on(child.storage_size(child_key, StorageKey(b"key".to_vec()), None)),
+		Ok(Some(3))
+	);
+}
+
+#[test]
+fn should_return_child_storage_keys_paged() {
+	let child_info = ChildInfo::new_default(STORAGE_KEY);
+	let client = Arc::new(
+		substrate_test_runtime_client::TestClientBuilder::new()
+			.add_child_storage(&child_info, "key1", vec![42_u8])
+			.add_child_storage(&child_info, "key2", vec![43_u8, 44])
+			.add_child_storage(&child_info, "other", vec![45_u8])
+			.build(),
+	);
+	let (_client, child) =
+		new_full(client, SubscriptionManager::new(Arc::new(TaskExecutor)), DenyUnsafe::No, None);
+	let child_key = prefixed_storage_key();
+	let prefix = Some(StorageKey(b"key".to_vec()));
+
+	assert_eq!(
+		executor::block_on(child.storage_keys_paged(
+			child_key.clone(),
+			prefix.clone(),
+			1,
+			None,
+			None
+		))
+		.unwrap(),
+		vec![StorageKey(b"key1".to_vec())],
+	);
+	assert_eq!(
+		executor::block_on(child.storage_keys_paged(
+			child_key.clone(),
+			prefix.clone(),
+			10,
+			Some(StorageKey(b"key1".to_vec())),
+			None
+		))
+		.unwrap(),
+		vec![StorageKey(b"key2".to_vec())],
+	);
+	assert_matches!(
+		executor::block_on(child.storage_keys_paged(child_key, prefix, 1001, None, None)),
+		Err(Error::InvalidCount { value: 1001, max: 1000 })
+	);
 }
 
 #[test]
@@ -530,6 +581,87 @@ fn should_query_storage() {
 	);
 }
 
+#[test]
+fn should_query_events_by_topic() {
+	let mut client = Arc::new(substrate_test_runtime_client::new());
+	let (api, _child) = new_full(
+		client.clone(),
+		SubscriptionManager::new(Arc::new(TaskExecutor)),
+		DenyUnsafe::No,
+		None,
+	);
+
+	let topic = H256::repeat_byte(42);
+	let mut add_block = |indexed: Option<Vec<(u64, u32)>>| {
+		let mut builder = client.new_block(Default::default()).unwrap();
+		builder
+			.push_storage_change(event_topic_key("System", &topic).0, indexed.map(|i| i.encode()))
+			.unwrap();
+		let block = builder.build().unwrap().block;
+		let hash = block.header.hash();
+		executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
+		hash
+	};
+	let block1_hash = add_block(Some(vec![(1, 0), (1, 2)]));
+	let block2_hash = add_block(None);
+	let block3_hash = add_block(Some(vec![(3, 1)]));
+	let genesis_hash = client.genesis_hash();
+
+	let result = api.query_events_by_topic(topic, genesis_hash, None);
+	assert_eq!(
+		executor::block_on(result).unwrap(),
+		vec![
+			EventLocation { block: block1_hash, index: 0 },
+			EventLocation { block: block1_hash, index: 2 },
+			EventLocation { block: block3_hash, index: 1 },
+		],
+	);
+
+	let result = api.query_events_by_topic(topic, block2_hash, Some(block2_hash));
+	assert!(executor::block_on(result).unwrap().is_empty());
+
+	let result = api.query_events_by_topic(H256::repeat_byte(7), genesis_hash, None);
+	assert!(executor::block_on(result).unwrap().is_empty());
+
+	// the range is scanned, the RPC is unsafe.
+	let (api, _child) = new_full(
+		client.clone(),
+		SubscriptionManager::new(Arc::new(TaskExecutor)),
+		DenyUnsafe::Yes,
+		None,
+	);
+	let result = api.query_events_by_topic(topic, genesis_hash, None);
+	assert_matches!(executor::block_on(result), Err(Error::UnsafeRpcCalled(_)));
+}
+
+#[test]
+fn should_trace_block_without_filters() {
+	let mut client = Arc::new(substrate_test_runtime_client::new());
+	let (api, _child) = new_full(
+		client.clone(),
+		SubscriptionManager::new(Arc::new(TaskExecutor)),
+		DenyUnsafe::No,
+		None,
+	);
+
+	let mut builder = client.new_block(Default::default()).unwrap();
+	builder.push_storage_change(vec![1], Some(vec![2])).unwrap();
+	let block = builder.build().unwrap().block;
+	let hash = block.header.hash();
+	executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
+
+	let trace = match executor::block_on(api.trace_block(hash, None, None, None)).unwrap() {
+		sp_rpc::tracing::TraceBlockResponse::BlockTrace(trace) => trace,
+		other => panic!("Unexpected response: {:?}", other),
+	};
+	// Omitted filters do not drop any event.
+	assert_eq!(trace.storage_keys, "");
+	assert_eq!(trace.methods, "");
+	assert!(trace.events.iter().any(|event| {
+		event.data.string_values.get("method").map(|m| m == "Put").unwrap_or(false)
+	}));
+}
+
 #[test]
 fn should_split_ranges() {
 	assert_eq!(split_range(1, None), (0..1, None));
@@ -550,11 +682,11 @@ fn should_return_runtime_version() {
 	);
 
 	let result = "{\"specName\":\"test\",\"implName\":\"parity-test\",\"authoringVersion\":1,\
-		\"specVersion\":2,\"implVersion\":2,\"apis\":[[\"0xdf6acb689907609b\",3],\
+		\"specVersion\":2,\"implVersion\":2,\"apis\":[[\"0xdf6acb689907609b\",4],\
 		[\"0x37e397fc7c91f5e4\",1],[\"0xd2bc9897eed08f15\",3],[\"0x40fe3ad401f8959a\",5],\
 		[\"0xc6e9a76309f39b09\",1],[\"0xdd718d5cc53262d4\",1],[\"0xcbca25e39f142387\",2],\
 		[\"0xf78b278be53f454c\",2],[\"0xab3c0572291feb8b\",1],[\"0xbc9d89904f5b923f\",1]],\
-		\"transactionVersion\":1}";
+		\"transactionVersion\":1,\"features\":0}";
 
 	let runtime_version = executor::block_on(api.runtime_version(None.into())).unwrap();
 	let serialized = serde_json::to_string(&runtime_version).unwrap();
@@ -24,10 +24,14 @@ use sc_rpc_api::{DenyUnsafe, Receiver};
 use sc_tracing::logging;
 use sc_utils::mpsc::TracingUnboundedSender;
 use sp_runtime::traits::{self, Header as HeaderT};
+use sp_version::RuntimeVersion;
 
 pub use self::{
 	gen_client::Client as SystemClient,
-	helpers::{Health, NodeRole, PeerInfo, SyncState, SystemInfo},
+	helpers::{
+		Health, NodeRole, PeerDetails, PeerInfo, PeerProtocol, SyncState, SystemInfo,
+		VersionDetails,
+	},
 };
 pub use sc_rpc_api::system::*;
 
@@ -61,18 +65,28 @@ pub enum Request<B: traits::Block> {
 	LocalListenAddresses(oneshot::Sender<Vec<String>>),
 	/// Must return information about the peers we are connected to.
 	Peers(oneshot::Sender<Vec<PeerInfo<B::Hash, <B::Header as HeaderT>::Number>>>),
+	/// Must return detailed information about the peers we are connected to.
+	PeersDetailed(oneshot::Sender<Vec<PeerDetails<B::Hash, <B::Header as HeaderT>::Number>>>),
+	/// Must return the reputation of the given peer, or any potential parse error.
+	PeerReputation(String, oneshot::Sender<Result<Option<i32>>>),
 	/// Must return the state of the network.
 	NetworkState(oneshot::Sender<rpc::Value>),
 	/// Must return any potential parse error.
 	NetworkAddReservedPeer(String, oneshot::Sender<Result<()>>),
 	/// Must return any potential parse error.
 	NetworkRemoveReservedPeer(String, oneshot::Sender<Result<()>>),
+	/// Must return any potential parse or unknown protocol error.
+	NetworkAddSetReservedPeer(String, String, oneshot::Sender<Result<()>>),
+	/// Must return any potential parse or unknown protocol error.
+	NetworkRemoveSetReservedPeer(String, String, oneshot::Sender<Result<()>>),
 	/// Must return the list of reserved peers
 	NetworkReservedPeers(oneshot::Sender<Vec<String>>),
 	/// Must return the node role.
 	NodeRoles(oneshot::Sender<Vec<NodeRole>>),
 	/// Must return the state of the node syncing.
 	SyncState(oneshot::Sender<SyncState<<B::Header as HeaderT>::Number>>),
+	/// Must return the runtime version at the best block.
+	RuntimeVersion(oneshot::Sender<Result<RuntimeVersion>>),
 }
 
 impl<B: traits::Block> System<B> {
@@ -110,6 +124,10 @@ impl<B: traits::Block> SystemApi<B::Hash, <B::Header as HeaderT>::Number> for Sy
 		Ok(self.info.properties.clone())
 	}
 
+	fn system_chain_spec_hashes(&self) -> Result<Option<sc_chain_spec::GenesisHashes>> {
+		Ok(self.info.genesis_hashes.clone())
+	}
+
 	fn system_health(&self) -> Receiver<Health> {
 		let (tx, rx) = oneshot::channel();
 		let _ = self.send_back.unbounded_send(Request::Health(tx));
@@ -139,6 +157,32 @@ impl<B: traits::Block> SystemApi<B::Hash, <B::Header as HeaderT>::Number> for Sy
 		async move { rx.await.map_err(|_| rpc::Error::internal_error()) }.boxed()
 	}
 
+	fn system_peers_detailed(
+		&self,
+	) -> rpc::BoxFuture<rpc::Result<Vec<PeerDetails<B::Hash, <B::Header as HeaderT>::Number>>>> {
+		bail_if_unsafe!(self.deny_unsafe);
+
+		let (tx, rx) = oneshot::channel();
+		let _ = self.send_back.unbounded_send(Request::PeersDetailed(tx));
+
+		async move { rx.await.map_err(|_| rpc::Error::internal_error()) }.boxed()
+	}
+
+	fn system_peer_reputation(&self, peer_id: String) -> rpc::BoxFuture<rpc::Result<Option<i32>>> {
+		bail_if_unsafe!(self.deny_unsafe);
+
+		let (tx, rx) = oneshot::channel();
+		let _ = self.send_back.unbounded_send(Request::PeerReputation(peer_id, tx));
+		async move {
+			match rx.await {
+				Ok(Ok(reputation)) => Ok(reputation),
+				Ok(Err(e)) => Err(rpc::Error::from(e)),
+				Err(_) => Err(rpc::Error::internal_error()),
+			}
+		}
+		.boxed()
+	}
+
 	fn system_network_state(&self) -> rpc::BoxFuture<rpc::Result<rpc::Value>> {
 		bail_if_unsafe!(self.deny_unsafe);
 
@@ -148,11 +192,19 @@ impl<B: traits::Block> SystemApi<B::Hash, <B::Header as HeaderT>::Number> for Sy
 		async move { rx.await.map_err(|_| rpc::Error::internal_error()) }.boxed()
 	}
 
-	fn system_add_reserved_peer(&self, peer: String) -> rpc::BoxFuture<rpc::Result<()>> {
+	fn system_add_reserved_peer(
+		&self,
+		peer: String,
+		protocol: Option<String>,
+	) -> rpc::BoxFuture<rpc::Result<()>> {
 		bail_if_unsafe!(self.deny_unsafe);
 
 		let (tx, rx) = oneshot::channel();
-		let _ = self.send_back.unbounded_send(Request::NetworkAddReservedPeer(peer, tx));
+		let request = match protocol {
+			Some(protocol) => Request::NetworkAddSetReservedPeer(protocol, peer, tx),
+			None => Request::NetworkAddReservedPeer(peer, tx),
+		};
+		let _ = self.send_back.unbounded_send(request);
 		async move {
 			match rx.await {
 				Ok(Ok(())) => Ok(()),
@@ -163,11 +215,19 @@ impl<B: traits::Block> SystemApi<B::Hash, <B::Header as HeaderT>::Number> for Sy
 		.boxed()
 	}
 
-	fn system_remove_reserved_peer(&self, peer: String) -> rpc::BoxFuture<rpc::Result<()>> {
+	fn system_remove_reserved_peer(
+		&self,
+		peer: String,
+		protocol: Option<String>,
+	) -> rpc::BoxFuture<rpc::Result<()>> {
 		bail_if_unsafe!(self.deny_unsafe);
 
 		let (tx, rx) = oneshot::channel();
-		let _ = self.send_back.unbounded_send(Request::NetworkRemoveReservedPeer(peer, tx));
+		let request = match protocol {
+			Some(protocol) => Request::NetworkRemoveSetReservedPeer(protocol, peer, tx),
+			None => Request::NetworkRemoveReservedPeer(peer, tx),
+		};
+		let _ = self.send_back.unbounded_send(request);
 		async move {
 			match rx.await {
 				Ok(Ok(())) => Ok(()),
@@ -196,6 +256,24 @@ impl<B: traits::Block> SystemApi<B::Hash, <B::Header as HeaderT>::Number> for Sy
 		Receiver(rx)
 	}
 
+	fn system_version_details(&self) -> rpc::BoxFuture<rpc::Result<VersionDetails>> {
+		let (tx, rx) = oneshot::channel();
+		let _ = self.send_back.unbounded_send(Request::RuntimeVersion(tx));
+		let node_version = self.info.impl_version.clone();
+		async move {
+			match rx.await {
+				Ok(Ok(runtime_version)) => Ok(VersionDetails {
+					node_version,
+					runtime_features: runtime_version.features.names(),
+					runtime_version,
+				}),
+				Ok(Err(e)) => Err(rpc::Error::from(e)),
+				Err(_) => Err(rpc::Error::internal_error()),
+			}
+		}
+		.boxed()
+	}
+
 	fn system_add_log_filter(&self, directives: String) -> rpc::Result<()> {
 		self.deny_unsafe.check_if_safe()?;
 		logging::add_directives(&directives);
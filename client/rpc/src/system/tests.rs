@@ -20,8 +20,10 @@ use super::*;
 
 use assert_matches::assert_matches;
 use futures::{executor, prelude::*};
+use sc_chain_spec::GenesisHashes;
 use sc_network::{self, config::Role, PeerId};
 use sc_utils::mpsc::tracing_unbounded;
+use sp_core::H256;
 use std::{
 	env,
 	io::{BufRead, BufReader, Write},
@@ -43,6 +45,16 @@ impl Default for Status {
 	}
 }
 
+const GRANDPA: &str = "/paritytech/grandpa/1";
+
+fn runtime_version() -> RuntimeVersion {
+	RuntimeVersion {
+		spec_name: "test".into(),
+		features: sp_version::RuntimeFeatures::TRY_RUNTIME,
+		..Default::default()
+	}
+}
+
 fn api<T: Into<Option<Status>>>(sync: T) -> System<Block> {
 	let status = sync.into().unwrap_or_default();
 	let should_have_peers = !status.is_dev;
@@ -79,6 +91,29 @@ fn api<T: Into<Option<Status>>>(sync: T) -> System<Block> {
 					}
 					let _ = sender.send(peers);
 				},
+				Request::PeersDetailed(sender) => {
+					let mut peers = vec![];
+					for _peer in 0..status.peers {
+						peers.push(PeerDetails {
+							peer_id: status.peer_id.to_base58(),
+							reputation: 0,
+							roles: Some(format!("{}", Role::Full)),
+							best_hash: Some(Default::default()),
+							best_number: Some(1),
+							sync_state: Some("Available".into()),
+							protocols: vec![PeerProtocol { name: GRANDPA.into(), reserved: true }],
+						});
+					}
+					let _ = sender.send(peers);
+				},
+				Request::PeerReputation(peer, sender) => {
+					let _ = match peer.parse::<PeerId>() {
+						Ok(peer_id) if peer_id == status.peer_id => sender.send(Ok(Some(-100))),
+						Ok(_) => sender.send(Ok(None)),
+						Err(s) =>
+							sender.send(Err(error::Error::MalformattedPeerArg(s.to_string()))),
+					};
+				},
 				Request::NetworkState(sender) => {
 					let _ = sender.send(
 						serde_json::to_value(&sc_network::network_state::NetworkState {
@@ -106,6 +141,22 @@ fn api<T: Into<Option<Status>>>(sync: T) -> System<Block> {
 							sender.send(Err(error::Error::MalformattedPeerArg(s.to_string()))),
 					};
 				},
+				Request::NetworkAddSetReservedPeer(protocol, peer, sender) => {
+					let _ = match sc_network::config::parse_str_addr(&peer) {
+						Ok(_) if protocol == GRANDPA => sender.send(Ok(())),
+						Ok(_) => sender.send(Err(error::Error::UnknownProtocol(protocol))),
+						Err(s) =>
+							sender.send(Err(error::Error::MalformattedPeerArg(s.to_string()))),
+					};
+				},
+				Request::NetworkRemoveSetReservedPeer(protocol, peer, sender) => {
+					let _ = match peer.parse::<PeerId>() {
+						Ok(_) if protocol == GRANDPA => sender.send(Ok(())),
+						Ok(_) => sender.send(Err(error::Error::UnknownProtocol(protocol))),
+						Err(s) =>
+							sender.send(Err(error::Error::MalformattedPeerArg(s.to_string()))),
+					};
+				},
 				Request::NetworkReservedPeers(sender) => {
 					let _ = sender
 						.send(vec!["QmSk5HQbn6LhUwDiNMseVUjuRYhEtYj4aUZ6WfWoGURpdV".to_string()]);
@@ -120,6 +171,9 @@ fn api<T: Into<Option<Status>>>(sync: T) -> System<Block> {
 						highest_block: Some(3),
 					});
 				},
+				Request::RuntimeVersion(sender) => {
+					let _ = sender.send(Ok(runtime_version()));
+				},
 			};
 
 			future::ready(())
@@ -132,6 +186,10 @@ fn api<T: Into<Option<Status>>>(sync: T) -> System<Block> {
 			chain_name: "testchain".into(),
 			properties: Default::default(),
 			chain_type: Default::default(),
+			genesis_hashes: Some(GenesisHashes {
+				state_root: H256::repeat_byte(1),
+				runtime_hash: Some(H256::repeat_byte(2)),
+			}),
 		},
 		tx,
 		sc_rpc_api::DenyUnsafe::No,
@@ -167,6 +225,17 @@ fn system_type_works() {
 	assert_eq!(api(None).system_type().unwrap(), Default::default());
 }
 
+#[test]
+fn system_chain_spec_hashes_works() {
+	assert_eq!(
+		api(None).system_chain_spec_hashes().unwrap(),
+		Some(GenesisHashes {
+			state_root: H256::repeat_byte(1),
+			runtime_hash: Some(H256::repeat_byte(2)),
+		}),
+	);
+}
+
 #[test]
 fn system_health() {
 	assert_matches!(
@@ -237,6 +306,40 @@ fn system_peers() {
 	);
 }
 
+#[test]
+fn system_peers_detailed() {
+	let peer_id = PeerId::random();
+	let req =
+		api(Status { peer_id, peers: 1, is_syncing: false, is_dev: true }).system_peers_detailed();
+	let res = executor::block_on(req).unwrap();
+
+	assert_eq!(
+		res,
+		vec![PeerDetails {
+			peer_id: peer_id.to_base58(),
+			reputation: 0,
+			roles: Some("FULL".into()),
+			best_hash: Some(Default::default()),
+			best_number: Some(1u64),
+			sync_state: Some("Available".into()),
+			protocols: vec![PeerProtocol { name: GRANDPA.into(), reserved: true }],
+		}]
+	);
+}
+
+#[test]
+fn system_peer_reputation() {
+	let peer_id = PeerId::random();
+	let api = || api(Status { peer_id, peers: 1, is_syncing: false, is_dev: true });
+
+	let known = executor::block_on(api().system_peer_reputation(peer_id.to_base58()));
+	let unknown = executor::block_on(api().system_peer_reputation(PeerId::random().to_base58()));
+	let malformed = executor::block_on(api().system_peer_reputation("foo".into()));
+	assert_eq!(known, Ok(Some(-100)));
+	assert_eq!(unknown, Ok(None));
+	assert!(malformed.is_err());
+}
+
 #[test]
 fn system_network_state() {
 	let req = api(None).system_network_state();
@@ -268,16 +371,33 @@ fn system_sync_state() {
 	);
 }
 
+#[test]
+fn system_version_details() {
+	assert_eq!(
+		executor::block_on(api(None).system_version_details()).unwrap(),
+		VersionDetails {
+			node_version: "0.2.0".into(),
+			runtime_version: runtime_version(),
+			runtime_features: vec!["try-runtime".into()],
+		}
+	);
+}
+
 #[test]
 fn system_network_add_reserved() {
 	let good_peer_id =
 		"/ip4/198.51.100.19/tcp/30333/p2p/QmSk5HQbn6LhUwDiNMseVUjuRYhEtYj4aUZ6WfWoGURpdV";
 	let bad_peer_id = "/ip4/198.51.100.19/tcp/30333";
 
-	let good_fut = api(None).system_add_reserved_peer(good_peer_id.into());
-	let bad_fut = api(None).system_add_reserved_peer(bad_peer_id.into());
+	let good_fut = api(None).system_add_reserved_peer(good_peer_id.into(), None);
+	let bad_fut = api(None).system_add_reserved_peer(bad_peer_id.into(), None);
 	assert_eq!(executor::block_on(good_fut), Ok(()));
 	assert!(executor::block_on(bad_fut).is_err());
+
+	let good_fut = api(None).system_add_reserved_peer(good_peer_id.into(), Some(GRANDPA.into()));
+	let bad_fut = api(None).system_add_reserved_peer(good_peer_id.into(), Some("/foo/1".into()));
+	assert_eq!(executor::block_on(good_fut), Ok(()));
+	assert_eq!(executor::block_on(bad_fut).unwrap_err().code, rpc::ErrorCode::ServerError(2004),);
 }
 
 #[test]
@@ -286,8 +406,13 @@ fn system_network_remove_reserved() {
 	let bad_peer_id =
 		"/ip4/198.51.100.19/tcp/30333/p2p/QmSk5HQbn6LhUwDiNMseVUjuRYhEtYj4aUZ6WfWoGURpdV";
 
-	let good_fut = api(None).system_remove_reserved_peer(good_peer_id.into());
-	let bad_fut = api(None).system_remove_reserved_peer(bad_peer_id.into());
+	let good_fut = api(None).system_remove_reserved_peer(good_peer_id.into(), None);
+	let bad_fut = api(None).system_remove_reserved_peer(bad_peer_id.into(), None);
+	assert_eq!(executor::block_on(good_fut), Ok(()));
+	assert!(executor::block_on(bad_fut).is_err());
+
+	let good_fut = api(None).system_remove_reserved_peer(good_peer_id.into(), Some(GRANDPA.into()));
+	let bad_fut = api(None).system_remove_reserved_peer(bad_peer_id.into(), Some(GRANDPA.into()));
 	assert_eq!(executor::block_on(good_fut), Ok(()));
 	assert!(executor::block_on(bad_fut).is_err());
 }
@@ -28,7 +28,7 @@ use futures::{channel::oneshot, future::ready, FutureExt, StreamExt};
 use jsonrpc_pubsub::manager::SubscriptionManager;
 use log::info;
 use prometheus_endpoint::Registry;
-use sc_chain_spec::get_extension;
+use sc_chain_spec::{get_extension, GenesisHashes};
 use sc_client_api::{
 	execution_extensions::ExecutionExtensions, light::RemoteBlockchain,
 	proof_provider::ProofProvider, BadBlocks, BlockBackend, BlockchainEvents, ExecutorProvider,
@@ -37,9 +37,9 @@ use sc_client_api::{
 use sc_client_db::{Backend, DatabaseSettings};
 use sc_consensus::import_queue::ImportQueue;
 use sc_executor::RuntimeVersionOf;
-use sc_keystore::LocalKeystore;
+use sc_keystore::{LocalKeystore, RemoteKeystore};
 use sc_network::{
-	block_request_handler::{self, BlockRequestHandler},
+	block_request_handler::{self, BlockRequestHandler, Reciprocity},
 	config::{OnDemand, Role, SyncMode},
 	light_client_requests::{self, handler::LightClientRequestHandler},
 	state_request_handler::{self, StateRequestHandler},
@@ -54,11 +54,17 @@ use sp_blockchain::{HeaderBackend, HeaderMetadata};
 use sp_consensus::block_validation::{
 	BlockAnnounceValidator, Chain, DefaultBlockAnnounceValidator,
 };
-use sp_core::traits::{CodeExecutor, SpawnNamed};
+use sp_core::{
+	storage::{well_known_keys, StorageKey},
+	traits::{CodeExecutor, SpawnNamed},
+	H256,
+};
 use sp_keystore::{CryptoStore, SyncCryptoStore, SyncCryptoStorePtr};
 use sp_runtime::{
 	generic::BlockId,
-	traits::{Block as BlockT, BlockIdTo, HashFor, Zero},
+	traits::{
+		BlakeTwo256, Block as BlockT, BlockIdTo, Hash as HashT, HashFor, Header as HeaderT, Zero,
+	},
 	BuildStorage,
 };
 use std::{str::FromStr, sync::Arc, time::SystemTime};
@@ -213,6 +219,20 @@ impl KeystoreContainer {
 		Ok(Self { remote: Default::default(), local: keystore })
 	}
 
+	/// Construct KeystoreContainer from the node configuration.
+	///
+	/// Signing requests are forwarded to the remote keystore of `config.keystore_remote`, if
+	/// any, instead of the local keystore.
+	pub fn from_config(config: &Configuration) -> Result<Self, Error> {
+		let mut container = Self::new(&config.keystore)?;
+		if let Some(uri) = &config.keystore_remote {
+			let remote = RemoteKeystore::open(uri, config.prometheus_registry())?;
+			container.set_remote_keystore(Arc::new(remote));
+		}
+
+		Ok(container)
+	}
+
 	/// Set the remote keystore.
 	/// Should be called right away at startup and not at runtime:
 	/// even though this overrides any previously set remote store, it
@@ -282,7 +302,7 @@ where
 	TExec: CodeExecutor + RuntimeVersionOf + Clone,
 	TBl::Hash: FromStr,
 {
-	let keystore_container = KeystoreContainer::new(&config.keystore)?;
+	let keystore_container = KeystoreContainer::from_config(config)?;
 
 	let task_manager = {
 		let registry = config.prometheus_config.as_ref().map(|cfg| &cfg.registry);
@@ -302,6 +322,7 @@ where
 		let db_config = sc_client_db::DatabaseSettings {
 			state_cache_size: config.state_cache_size,
 			state_cache_child_ratio: config.state_cache_child_ratio.map(|v| (v, 100)),
+			trie_cache_size: config.trie_cache_size,
 			state_pruning: config.state_pruning.clone(),
 			source: config.database.clone(),
 			keep_blocks: config.keep_blocks.clone(),
@@ -350,6 +371,7 @@ where
 					sc_network::config::SyncMode::Fast { .. } | sc_network::config::SyncMode::Warp
 				),
 				wasm_runtime_substitutes,
+				stale_import_limit: config.stale_import_limit,
 			},
 		)?;
 
@@ -369,7 +391,7 @@ where
 	TBl: BlockT,
 	TExec: CodeExecutor + RuntimeVersionOf + Clone,
 {
-	let keystore_container = KeystoreContainer::new(&config.keystore)?;
+	let keystore_container = KeystoreContainer::from_config(config)?;
 	let task_manager = {
 		let registry = config.prometheus_config.as_ref().map(|cfg| &cfg.registry);
 		TaskManager::new(config.tokio_handle.clone(), registry)?
@@ -379,6 +401,7 @@ where
 		let db_settings = sc_client_db::DatabaseSettings {
 			state_cache_size: config.state_cache_size,
 			state_cache_child_ratio: config.state_cache_child_ratio.map(|v| (v, 100)),
+			trie_cache_size: config.trie_cache_size,
 			state_pruning: config.state_pruning.clone(),
 			source: config.database.clone(),
 			keep_blocks: config.keep_blocks.clone(),
@@ -503,7 +526,17 @@ where
 	TCl: Send + Sync + ProvideRuntimeApi<TBl> + BlockchainEvents<TBl> + 'static,
 	<TCl as ProvideRuntimeApi<TBl>>::Api: sc_offchain::OffchainWorkerApi<TBl>,
 {
-	let offchain_workers = Some(Arc::new(sc_offchain::OffchainWorkers::new(client.clone())));
+	let mut offchain_workers = sc_offchain::OffchainWorkers::new(client.clone());
+	if let Some(hosts) = config.offchain_worker.http_allowed_hosts.clone() {
+		offchain_workers = offchain_workers.with_http_allowed_hosts(hosts);
+	}
+	if let Some(max) = config.offchain_worker.max_concurrent_workers {
+		offchain_workers = offchain_workers.with_max_concurrent_workers(max);
+	}
+	if let Some(registry) = config.prometheus_registry() {
+		offchain_workers = offchain_workers.with_prometheus_registry(registry);
+	}
+	let offchain_workers = Some(Arc::new(offchain_workers));
 
 	// Inform the offchain worker about new imported blocks
 	if let Some(offchain) = offchain_workers.clone() {
@@ -620,6 +653,8 @@ where
 	);
 
 	// RPC
+	let genesis_hashes =
+		genesis_hashes::<TBl, TBackend, _>(&*client, config.chain_spec.genesis_hashes());
 	let gen_handler = |deny_unsafe: sc_rpc::DenyUnsafe,
 	                   rpc_middleware: sc_rpc_server::RpcMiddleware| {
 		gen_handler(
@@ -633,8 +668,9 @@ where
 			on_demand.clone(),
 			remote_blockchain.clone(),
 			&*rpc_extensions_builder,
-			backend.offchain_storage(),
+			backend.clone(),
 			system_rpc_tx.clone(),
+			genesis_hashes.clone(),
 		)
 	};
 	let rpc_metrics = sc_rpc_server::RpcMetrics::new(config.prometheus_registry())?;
@@ -721,6 +757,42 @@ fn init_telemetry<TBl: BlockT, TCl: BlockBackend<TBl>>(
 	Ok(telemetry.handle())
 }
 
+/// The hashes of the genesis state of the chain of `client`.
+///
+/// The state root is the one of the genesis header, and the runtime hash the one `recorded` in
+/// the chain spec if it describes the same genesis. Otherwise the runtime is read from the genesis
+/// state, which may have been pruned. The genesis storage is never built again.
+fn genesis_hashes<TBl, TBackend, TCl>(
+	client: &TCl,
+	recorded: Option<GenesisHashes>,
+) -> Option<GenesisHashes>
+where
+	TBl: BlockT,
+	TBackend: sc_client_api::backend::Backend<TBl>,
+	TCl: HeaderBackend<TBl> + StorageProvider<TBl, TBackend>,
+{
+	let genesis = BlockId::Number(Zero::zero());
+	let header = client.header(genesis).ok().flatten()?;
+	let state_root = header.state_root().as_ref();
+	// The hashes are only defined for chains using 256 bit hashes.
+	if state_root.len() != H256::len_bytes() {
+		return None
+	}
+	let state_root = H256::from_slice(state_root);
+
+	match recorded {
+		Some(hashes) if hashes.state_root == state_root => Some(hashes),
+		_ => {
+			let runtime_hash = client
+				.storage(&genesis, &StorageKey(well_known_keys::CODE.to_vec()))
+				.ok()
+				.flatten()
+				.map(|code| <BlakeTwo256 as HashT>::hash(&code.0));
+			Some(GenesisHashes { state_root, runtime_hash })
+		},
+	}
+}
+
 fn gen_handler<TBl, TBackend, TExPool, TRpc, TCl>(
 	deny_unsafe: sc_rpc::DenyUnsafe,
 	rpc_middleware: sc_rpc_server::RpcMiddleware,
@@ -732,8 +804,9 @@ fn gen_handler<TBl, TBackend, TExPool, TRpc, TCl>(
 	on_demand: Option<Arc<OnDemand<TBl>>>,
 	remote_blockchain: Option<Arc<dyn RemoteBlockchain<TBl>>>,
 	rpc_extensions_builder: &(dyn RpcExtensionBuilder<Output = TRpc> + Send),
-	offchain_storage: Option<<TBackend as sc_client_api::backend::Backend<TBl>>::OffchainStorage>,
+	backend: Arc<TBackend>,
 	system_rpc_tx: TracingUnboundedSender<sc_rpc::system::Request<TBl>>,
+	genesis_hashes: Option<GenesisHashes>,
 ) -> Result<sc_rpc_server::RpcHandler<sc_rpc::Metadata>, Error>
 where
 	TBl: BlockT,
@@ -756,7 +829,7 @@ where
 	TBl::Hash: Unpin,
 	TBl::Header: Unpin,
 {
-	use sc_rpc::{author, chain, offchain, state, system};
+	use sc_rpc::{author, chain, finality, offchain, state, system};
 
 	let system_info = sc_rpc::system::SystemInfo {
 		chain_name: config.chain_spec.name().into(),
@@ -764,12 +837,13 @@ where
 		impl_version: config.impl_version.clone(),
 		properties: config.chain_spec.properties(),
 		chain_type: config.chain_spec.chain_type(),
+		genesis_hashes,
 	};
 
 	let task_executor = sc_rpc::SubscriptionTaskExecutor::new(spawn_handle);
 	let subscriptions = SubscriptionManager::new(Arc::new(task_executor.clone()));
 
-	let (chain, state, child_state) =
+	let (chain, state, child_state, chain_head, archive, finality) =
 		if let (Some(remote_blockchain), Some(on_demand)) = (remote_blockchain, on_demand) {
 			// Light clients
 			let chain = sc_rpc::chain::new_light(
@@ -785,7 +859,7 @@ where
 				on_demand,
 				deny_unsafe,
 			);
-			(chain, state, child_state)
+			(chain, state, child_state, None, None, None)
 		} else {
 			// Full nodes
 			let chain = sc_rpc::chain::new_full(client.clone(), subscriptions.clone());
@@ -795,14 +869,21 @@ where
 				deny_unsafe,
 				config.rpc_max_payload,
 			);
-			(chain, state, child_state)
+			let chain_head = sc_rpc_spec_v2::chain_head::ChainHead::new(
+				client.clone(),
+				backend.clone(),
+				subscriptions.clone(),
+			);
+			let archive = sc_rpc_spec_v2::archive::Archive::new(client.clone());
+			let finality = finality::Finality::<TBl, _>::new(client.clone(), subscriptions.clone());
+			(chain, state, child_state, Some(chain_head), Some(archive), Some(finality))
 		};
 
 	let author =
 		sc_rpc::author::Author::new(client, transaction_pool, subscriptions, keystore, deny_unsafe);
 	let system = system::System::new(system_info, system_rpc_tx, deny_unsafe);
 
-	let maybe_offchain_rpc = offchain_storage.map(|storage| {
+	let maybe_offchain_rpc = backend.offchain_storage().map(|storage| {
 		let offchain = sc_rpc::offchain::Offchain::new(storage, deny_unsafe);
 		offchain::OffchainApi::to_delegate(offchain)
 	});
@@ -812,6 +893,9 @@ where
 			state::StateApi::to_delegate(state),
 			state::ChildStateApi::to_delegate(child_state),
 			chain::ChainApi::to_delegate(chain),
+			chain_head.map(sc_rpc_spec_v2::chain_head::ChainHeadApi::to_delegate),
+			archive.map(sc_rpc_spec_v2::archive::ArchiveApi::to_delegate),
+			finality.map(finality::FinalityApi::to_delegate),
 			maybe_offchain_rpc,
 			author::AuthorApi::to_delegate(author),
 			system::SystemApi::to_delegate(system),
@@ -838,7 +922,7 @@ pub struct BuildNetworkParams<'a, TBl: BlockT, TExPool, TImpQu, TCl> {
 	/// A block announce validator builder.
 	pub block_announce_validator_builder:
 		Option<Box<dyn FnOnce(Arc<TCl>) -> Box<dyn BlockAnnounceValidator<TBl> + Send> + Send>>,
-	/// An optional warp sync provider.
+	/// An optional warp sync provider, required when syncing with `SyncMode::Warp`.
 	pub warp_sync: Option<Arc<dyn WarpSyncProvider<TBl>>>,
 }
 
@@ -863,6 +947,7 @@ where
 		+ ProofProvider<TBl>
 		+ HeaderBackend<TBl>
 		+ BlockchainEvents<TBl>
+		+ CallApiAt<TBl>
 		+ 'static,
 	TExPool: MaintainedTransactionPool<Block = TBl, Hash = <TBl as BlockT>::Hash> + 'static,
 	TImpQu: ImportQueue<TBl> + 'static,
@@ -878,6 +963,10 @@ where
 		warp_sync,
 	} = params;
 
+	if warp_sync.is_none() && matches!(config.network.sync_mode, SyncMode::Warp) {
+		return Err("Warp sync enabled, but no warp sync provider configured.".into())
+	}
+
 	let transaction_pool_adapter = Arc::new(TransactionPoolAdapter {
 		imports_external_transactions: !matches!(config.role, Role::Light),
 		pool: transaction_pool,
@@ -892,6 +981,7 @@ where
 		Box::new(DefaultBlockAnnounceValidator)
 	};
 
+	let block_request_reciprocity = Reciprocity::default();
 	let block_request_protocol_config = {
 		if matches!(config.role, Role::Light) {
 			// Allow outgoing requests but deny incoming requests.
@@ -903,6 +993,8 @@ where
 				client.clone(),
 				config.network.default_peers_set.in_peers as usize +
 					config.network.default_peers_set.out_peers as usize,
+				block_request_reciprocity.clone(),
+				config.prometheus_config.as_ref().map(|config| &config.registry),
 			);
 			spawn_handle.spawn("block_request_handler", handler.run());
 			protocol_config
@@ -920,6 +1012,7 @@ where
 				client.clone(),
 				config.network.default_peers_set.in_peers as usize +
 					config.network.default_peers_set.out_peers as usize,
+				config.prometheus_config.as_ref().map(|config| &config.registry),
 			);
 			spawn_handle.spawn("state_request_handler", handler.run());
 			protocol_config
@@ -976,6 +1069,7 @@ where
 		block_announce_validator,
 		metrics_registry: config.prometheus_config.as_ref().map(|config| config.registry.clone()),
 		block_request_protocol_config,
+		block_request_reciprocity,
 		state_request_protocol_config,
 		warp_sync: warp_sync_params,
 		light_client_request_protocol_config,
@@ -73,7 +73,7 @@ use sp_runtime::{
 	generic::{BlockId, DigestItem, SignedBlock},
 	traits::{
 		Block as BlockT, DigestFor, HashFor, Header as HeaderT, NumberFor, One,
-		SaturatedConversion, Zero,
+		SaturatedConversion, Saturating, Zero,
 	},
 	BuildStorage, Justification, Justifications,
 };
@@ -199,6 +199,9 @@ pub struct ClientConfig<Block: BlockT> {
 	/// Map of WASM runtime substitute starting at the child of the given block until the runtime
 	/// version doesn't match anymore.
 	pub wasm_runtime_substitutes: HashMap<Block::Hash, Vec<u8>>,
+	/// Unknown blocks more than this number of blocks below the last finalized block are rejected
+	/// before verification. `None` disables the check.
+	pub stale_import_limit: Option<u32>,
 }
 
 impl<Block: BlockT> Default for ClientConfig<Block> {
@@ -209,6 +212,7 @@ impl<Block: BlockT> Default for ClientConfig<Block> {
 			wasm_runtime_overrides: None,
 			no_genesis: false,
 			wasm_runtime_substitutes: HashMap::new(),
+			stale_import_limit: None,
 		}
 	}
 }
@@ -1953,6 +1957,21 @@ where
 			BlockStatus::KnownBad => return Ok(ImportResult::KnownBad),
 		}
 
+		// Blocks that far below the finalized block can't become part of the best chain, reject
+		// them before spending any time on their verification. Imported state is not affected.
+		if let Some(limit) = self.config.stale_import_limit {
+			let finalized_number = self.backend.blockchain().info().finalized_number;
+			if !allow_missing_parent && number.saturating_add(limit.into()) < finalized_number {
+				trace!(
+					"Rejecting stale block #{} {:?}, finalized block is #{}",
+					number,
+					hash,
+					finalized_number,
+				);
+				return Ok(ImportResult::Stale)
+			}
+		}
+
 		match self
 			.block_status(&BlockId::Hash(parent_hash))
 			.map_err(|e| ConsensusError::ClientImport(e.to_string()))?
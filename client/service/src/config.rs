@@ -19,7 +19,10 @@
 //! Service configuration.
 
 pub use sc_client_api::execution_extensions::{ExecutionStrategies, ExecutionStrategy};
-pub use sc_client_db::{Database, DatabaseSource, KeepBlocks, PruningMode, TransactionStorageMode};
+pub use sc_client_db::{
+	migrate_rocksdb_to_parity_db, Database, DatabaseSource, DatabaseType, KeepBlocks, PruningMode,
+	TransactionStorageMode,
+};
 pub use sc_executor::WasmExecutionMethod;
 pub use sc_network::{
 	config::{
@@ -29,6 +32,7 @@ pub use sc_network::{
 	},
 	Multiaddr,
 };
+pub use sc_rpc_server::RpcLimits;
 
 use prometheus_endpoint::Registry;
 use sc_chain_spec::ChainSpec;
@@ -67,6 +71,8 @@ pub struct Configuration {
 	pub state_cache_size: usize,
 	/// Size in percent of cache size dedicated to child tries
 	pub state_cache_child_ratio: Option<usize>,
+	/// Size of the trie node cache in Bytes, `0` disables it.
+	pub trie_cache_size: usize,
 	/// State pruning settings.
 	pub state_pruning: PruningMode,
 	/// Number of blocks to keep in the db.
@@ -81,6 +87,9 @@ pub struct Configuration {
 	/// over on-chain runtimes when the spec version matches. Set to `None` to
 	/// disable overrides (default).
 	pub wasm_runtime_overrides: Option<PathBuf>,
+	/// Unknown blocks more than this number of blocks below the last finalized block are rejected
+	/// before verification. `None` disables the check.
+	pub stale_import_limit: Option<u32>,
 	/// Execution strategies.
 	pub execution_strategies: ExecutionStrategies,
 	/// RPC over HTTP binding address. `None` if disabled.
@@ -97,6 +106,8 @@ pub struct Configuration {
 	pub rpc_methods: RpcMethods,
 	/// Maximum payload of rpc request/responses.
 	pub rpc_max_payload: Option<usize>,
+	/// Resource limits of each RPC connection.
+	pub rpc_limits: RpcLimits,
 	/// Prometheus endpoint configuration. `None` if disabled.
 	pub prometheus_config: Option<PrometheusConfig>,
 	/// Telemetry service URL. `None` if disabled.
@@ -173,6 +184,11 @@ pub struct OffchainWorkerConfig {
 	pub enabled: bool,
 	/// allow writes from the runtime to the offchain worker database.
 	pub indexing_enabled: bool,
+	/// Hosts the HTTP requests of offchain workers may target, any host if `None`.
+	pub http_allowed_hosts: Option<Vec<String>>,
+	/// Maximum number of blocks whose offchain workers run concurrently, the number of CPUs if
+	/// `None`.
+	pub max_concurrent_workers: Option<usize>,
 }
 
 /// Configuration of the Prometheus endpoint.
@@ -62,8 +62,8 @@ pub use config::{
 	TransactionStorageMode,
 };
 pub use sc_chain_spec::{
-	ChainSpec, ChainType, Extension as ChainSpecExtension, GenericChainSpec, NoExtension,
-	Properties, RuntimeGenesis,
+	reverse_storage, ChainSpec, ChainType, Extension as ChainSpecExtension, GenericChainSpec,
+	GenesisHashes, NoExtension, Properties, RuntimeGenesis, StorageNames,
 };
 use sc_client_api::{blockchain::HeaderBackend, BlockchainEvents};
 pub use sc_consensus::ImportQueue;
@@ -137,7 +137,7 @@ pub struct PartialComponents<Client, Backend, SelectChain, ImportQueue, Transact
 /// The `status_sink` contain a list of senders to send a periodic network status to.
 async fn build_network_future<
 	B: BlockT,
-	C: BlockchainEvents<B> + HeaderBackend<B>,
+	C: BlockchainEvents<B> + HeaderBackend<B> + sp_api::CallApiAt<B>,
 	H: sc_network::ExHashT,
 >(
 	role: Role,
@@ -233,6 +233,32 @@ async fn build_network_future<
 							}
 						).collect());
 					}
+					sc_rpc::system::Request::PeersDetailed(sender) => {
+						let _ = sender.send(network.peers_details().into_iter().map(|(peer_id, p)|
+							sc_rpc::system::PeerDetails {
+								peer_id: peer_id.to_base58(),
+								reputation: p.reputation,
+								roles: p.info.as_ref().map(|info| format!("{:?}", info.roles)),
+								best_hash: p.info.as_ref().map(|info| info.best_hash),
+								best_number: p.info.as_ref().map(|info| info.best_number),
+								sync_state: p.sync_state,
+								protocols: p.protocols.into_iter().map(|protocol|
+									sc_rpc::system::PeerProtocol {
+										name: protocol.name.into_owned(),
+										reserved: protocol.reserved,
+									}
+								).collect(),
+							}
+						).collect());
+					}
+					sc_rpc::system::Request::PeerReputation(peer_id, sender) => {
+						let _ = match peer_id.parse::<PeerId>() {
+							Ok(peer_id) => sender.send(Ok(network.peer_reputation(&peer_id))),
+							Err(e) => sender.send(Err(sc_rpc::system::error::Error::MalformattedPeerArg(
+								e.to_string(),
+							))),
+						};
+					}
 					sc_rpc::system::Request::NetworkState(sender) => {
 						if let Some(network_state) = serde_json::to_value(&network.network_state()).ok() {
 							let _ = sender.send(network_state);
@@ -254,6 +280,33 @@ async fn build_network_future<
 							))),
 						};
 					}
+					sc_rpc::system::Request::NetworkAddSetReservedPeer(protocol, peer_addr, sender) => {
+						let x = if network.is_notifications_protocol(&protocol) {
+							network.add_set_reserved_peer(protocol.into(), peer_addr)
+								.map_err(sc_rpc::system::error::Error::MalformattedPeerArg)
+						} else {
+							Err(sc_rpc::system::error::Error::UnknownProtocol(
+								format!("Unknown notifications protocol: {}", protocol),
+							))
+						};
+						let _ = sender.send(x);
+					}
+					sc_rpc::system::Request::NetworkRemoveSetReservedPeer(protocol, peer_id, sender) => {
+						let _ = match peer_id.parse::<PeerId>() {
+							Ok(_) if !network.is_notifications_protocol(&protocol) => sender.send(Err(
+								sc_rpc::system::error::Error::UnknownProtocol(
+									format!("Unknown notifications protocol: {}", protocol),
+								),
+							)),
+							Ok(peer_id) => {
+								network.remove_set_reserved_peer(protocol.into(), peer_id);
+								sender.send(Ok(()))
+							}
+							Err(e) => sender.send(Err(sc_rpc::system::error::Error::MalformattedPeerArg(
+								e.to_string(),
+							))),
+						};
+					}
 					sc_rpc::system::Request::NetworkReservedPeers(sender) => {
 						let reserved_peers = network.reserved_peers();
 						let reserved_peers = reserved_peers
@@ -282,6 +335,17 @@ async fn build_network_future<
 							highest_block: network.best_seen_block(),
 						});
 					}
+					sc_rpc::system::Request::RuntimeVersion(sender) => {
+						let best_hash = client.info().best_hash;
+						let version = sp_api::CallApiAt::runtime_version_at(
+							&*client,
+							&BlockId::Hash(best_hash),
+						)
+						.map_err(|e| sc_rpc::system::error::Error::RuntimeVersionUnavailable(
+							e.to_string(),
+						));
+						let _ = sender.send(version);
+					}
 				}
 			}
 
@@ -385,7 +449,8 @@ fn start_rpc_servers<
 							rpc_metrics.clone(),
 							rpc_method_names.clone(),
 							"ipc",
-						),
+						)
+						.with_limits(config.rpc_limits),
 					)?,
 					server_metrics.clone(),
 				)
@@ -402,7 +467,8 @@ fn start_rpc_servers<
 						rpc_metrics.clone(),
 						rpc_method_names.clone(),
 						"http",
-					),
+					)
+					.with_limits(config.rpc_limits),
 				)?,
 				config.rpc_max_payload,
 				config.tokio_handle.clone(),
@@ -421,7 +487,8 @@ fn start_rpc_servers<
 						rpc_metrics.clone(),
 						rpc_method_names.clone(),
 						"ws",
-					),
+					)
+					.with_limits(config.rpc_limits),
 				)?,
 				config.rpc_max_payload,
 				server_metrics.clone(),
@@ -43,6 +43,8 @@ struct PrometheusMetrics {
 	database_cache: Gauge<U64>,
 	state_cache: Gauge<U64>,
 	state_db: GaugeVec<U64>,
+	trie_cache: Gauge<U64>,
+	trie_cache_lookups: GaugeVec<U64>,
 }
 
 impl PrometheusMetrics {
@@ -120,6 +122,17 @@ impl PrometheusMetrics {
 				)?,
 				registry,
 			)?,
+			trie_cache: register(
+				Gauge::new("trie_cache_bytes", "Trie node cache size in bytes")?,
+				registry,
+			)?,
+			trie_cache_lookups: register(
+				GaugeVec::new(
+					Opts::new("trie_cache_lookups", "Number of trie node cache lookups"),
+					&["result"],
+				)?,
+				registry,
+			)?,
 		})
 	}
 }
@@ -261,6 +274,16 @@ impl MetricsService {
 					.state_db
 					.with_label_values(&["pinned"])
 					.set(info.memory.state_db.pinned.as_bytes() as u64);
+
+				metrics.trie_cache.set(info.memory.trie_cache.as_bytes() as u64);
+				metrics
+					.trie_cache_lookups
+					.with_label_values(&["hit"])
+					.set(info.io.trie_cache_hits);
+				metrics
+					.trie_cache_lookups
+					.with_label_values(&["miss"])
+					.set(info.io.trie_cache_misses);
 			}
 		}
 
@@ -1437,6 +1437,7 @@ fn doesnt_import_blocks_that_revert_finality() {
 			DatabaseSettings {
 				state_cache_size: 1 << 20,
 				state_cache_child_ratio: None,
+				trie_cache_size: 1 << 20,
 				state_pruning: PruningMode::ArchiveAll,
 				keep_blocks: KeepBlocks::All,
 				transaction_storage: TransactionStorageMode::BlockBody,
@@ -1640,6 +1641,39 @@ fn respects_block_rules() {
 	run_test(false, &mut known_bad, &mut fork_rules);
 }
 
+#[test]
+fn rejects_stale_blocks_without_marking_them_bad() {
+	let mut client = TestClientBuilder::new().set_stale_import_limit(1).build();
+
+	let mut fork = client.new_block_at(&BlockId::Number(0), Default::default(), false).unwrap();
+	fork.push_storage_change(vec![0], Some(vec![1])).unwrap();
+	let fork = fork.build().unwrap().block;
+	for _ in 0..3 {
+		let block = client.new_block(Default::default()).unwrap().build().unwrap().block;
+		block_on(client.import_as_final(BlockOrigin::Own, block)).unwrap();
+	}
+
+	let params = BlockCheckParams {
+		hash: fork.hash(),
+		number: 1,
+		parent_hash: fork.header().parent_hash().clone(),
+		allow_missing_state: false,
+		allow_missing_parent: false,
+		import_existing: false,
+	};
+	assert_eq!(block_on(client.check_block(params.clone())).unwrap(), ImportResult::Stale);
+
+	// blocks closer to the finalized block are still checked.
+	let block = client.new_block(Default::default()).unwrap().build().unwrap().block;
+	let params = BlockCheckParams {
+		hash: block.hash(),
+		number: 4,
+		parent_hash: block.header().parent_hash().clone(),
+		..params
+	};
+	assert_eq!(block_on(client.check_block(params)).unwrap(), ImportResult::imported(false));
+}
+
 #[test]
 fn returns_status_for_pruned_blocks() {
 	sp_tracing::try_init_simple();
@@ -1652,6 +1686,7 @@ fn returns_status_for_pruned_blocks() {
 			DatabaseSettings {
 				state_cache_size: 1 << 20,
 				state_cache_child_ratio: None,
+				trie_cache_size: 1 << 20,
 				state_pruning: PruningMode::keep_blocks(1),
 				keep_blocks: KeepBlocks::All,
 				transaction_storage: TransactionStorageMode::BlockBody,
@@ -40,6 +40,13 @@
 //! # Pruning.
 //! See `RefWindow` for pruning algorithm details. `StateDb` prunes on each canonicalization until
 //! pruning constraints are satisfied.
+//!
+//! # Changing the pruning mode.
+//! The size of the pruning window may change across restarts, as long as it is not shrunk below
+//! the number of non-canonical blocks. Switching between `Constrained` and `ArchiveCanonical` is
+//! supported as well, the pruning journal is discarded when pruning is disabled, the blocks of the
+//! window are then kept forever. `ArchiveAll` doesn't maintain the non-canonical overlay and can't
+//! be switched to or from.
 
 mod noncanonical;
 mod pruning;
@@ -47,7 +54,7 @@ mod pruning;
 mod test;
 
 use codec::Codec;
-use log::trace;
+use log::{info, trace};
 use noncanonical::NonCanonicalOverlay;
 use parity_util_mem::{malloc_size, MallocSizeOf};
 use parking_lot::RwLock;
@@ -238,6 +245,8 @@ struct StateDbSync<BlockHash: Hash, Key: Hash> {
 	non_canonical: NonCanonicalOverlay<BlockHash, Key>,
 	pruning: Option<RefWindow<BlockHash, Key>>,
 	pinned: HashMap<BlockHash, u32>,
+	/// Meta changes migrating the database to `mode`, written along the next commit.
+	pending_migration: Option<ChangeSet<Vec<u8>>>,
 }
 
 impl<BlockHash: Hash + MallocSizeOf, Key: Hash + MallocSizeOf> StateDbSync<BlockHash, Key> {
@@ -249,7 +258,7 @@ impl<BlockHash: Hash + MallocSizeOf, Key: Hash + MallocSizeOf> StateDbSync<Block
 		trace!(target: "state-db", "StateDb settings: {:?}. Ref-counting: {}", mode, ref_counting);
 
 		// Check that settings match
-		Self::check_meta(&mode, db)?;
+		let db_mode = Self::check_meta(&mode, db)?;
 
 		let non_canonical: NonCanonicalOverlay<BlockHash, Key> = NonCanonicalOverlay::new(db)?;
 		let pruning: Option<RefWindow<BlockHash, Key>> = match mode {
@@ -258,19 +267,77 @@ impl<BlockHash: Hash + MallocSizeOf, Key: Hash + MallocSizeOf> StateDbSync<Block
 			PruningMode::ArchiveAll | PruningMode::ArchiveCanonical => None,
 		};
 
-		Ok(StateDbSync { mode, non_canonical, pruning, pinned: Default::default() })
+		if let (
+			Some(pruning),
+			PruningMode::Constrained(Constraints { max_blocks: Some(max_blocks), .. }),
+		) = (&pruning, &mode)
+		{
+			// the blocks which are about to be finalized must stay in the window.
+			let finality_lag = non_canonical.levels_count();
+			let max_blocks = *max_blocks as u64;
+			if max_blocks < pruning.window_size() && max_blocks < finality_lag {
+				return Err(Error::InvalidPruningMode(format!(
+					"a pruning window of at least {} blocks, the number of non-canonical blocks",
+					finality_lag,
+				)))
+			}
+		}
+
+		let pending_migration = match db_mode {
+			Some(db_mode) if db_mode.as_slice() != mode.id() => {
+				info!(
+					target: "state-db",
+					"Switching state pruning mode from {} to {}",
+					String::from_utf8_lossy(&db_mode),
+					String::from_utf8_lossy(mode.id()),
+				);
+				let mut commit = CommitSet::default();
+				if db_mode.as_slice() == PRUNING_MODE_CONSTRAINED {
+					// Keys of the journal may be inserted again while the window is not
+					// maintained, pruning them later would delete live nodes.
+					RefWindow::<BlockHash, Key>::new(db, ref_counting)?
+						.discard_journal(&mut commit);
+				}
+				commit.meta.inserted.push((to_meta_key(PRUNING_MODE, &()), mode.id().into()));
+				Some(commit.meta)
+			},
+			_ => None,
+		};
+
+		Ok(StateDbSync {
+			mode,
+			non_canonical,
+			pruning,
+			pinned: Default::default(),
+			pending_migration,
+		})
 	}
 
-	fn check_meta<D: MetaDb>(mode: &PruningMode, db: &D) -> Result<(), Error<D::Error>> {
+	/// Check that the database may be opened with `mode`, returning the mode it was created with.
+	fn check_meta<D: MetaDb>(
+		mode: &PruningMode,
+		db: &D,
+	) -> Result<Option<Vec<u8>>, Error<D::Error>> {
 		let db_mode = db.get_meta(&to_meta_key(PRUNING_MODE, &())).map_err(Error::Db)?;
 		trace!(target: "state-db",
 			"DB pruning mode: {:?}",
 			db_mode.as_ref().map(|v| std::str::from_utf8(&v))
 		);
 		match &db_mode {
-			Some(v) if v.as_slice() == mode.id() => Ok(()),
+			Some(v) if v.as_slice() == mode.id() => Ok(db_mode),
+			// the non-canonical overlay is maintained in both modes.
+			Some(v) if v.as_slice() != PRUNING_MODE_ARCHIVE && *mode != PruningMode::ArchiveAll =>
+				Ok(db_mode),
 			Some(v) => Err(Error::InvalidPruningMode(String::from_utf8_lossy(v).into())),
-			None => Ok(()),
+			None => Ok(None),
+		}
+	}
+
+	/// Add the pending migration, if any, to `commit`.
+	fn apply_migration(&mut self, commit: &mut CommitSet<Key>) {
+		if let Some(migration) = self.pending_migration.take() {
+			commit.meta.inserted.extend(migration.inserted);
+			commit.meta.deleted.extend(migration.deleted);
 		}
 	}
 
@@ -297,6 +364,7 @@ impl<BlockHash: Hash + MallocSizeOf, Key: Hash + MallocSizeOf> StateDbSync<Block
 				let commit = self.non_canonical.insert(hash, number, parent_hash, changeset);
 				commit.map(|mut c| {
 					c.meta.inserted.extend(meta.inserted);
+					self.apply_migration(&mut c);
 					c
 				})
 			},
@@ -322,6 +390,7 @@ impl<BlockHash: Hash + MallocSizeOf, Key: Hash + MallocSizeOf> StateDbSync<Block
 			pruning.note_canonical(&hash, &mut commit);
 		}
 		self.prune(&mut commit);
+		self.apply_migration(&mut commit);
 		Ok(commit)
 	}
 
@@ -709,4 +778,59 @@ mod tests {
 		let state_db: Result<StateDb<H256, H256>, _> = StateDb::new(new_mode, false, &db);
 		assert!(state_db.is_err());
 	}
+
+	#[test]
+	fn pruning_window_may_change_but_not_below_non_canonical_blocks() {
+		let (mut db, _) = make_test_db(PruningMode::keep_blocks(2));
+
+		// block 4 is not canonicalized yet.
+		let state_db: Result<StateDb<H256, H256>, _> =
+			StateDb::new(PruningMode::keep_blocks(0), false, &db);
+		assert!(state_db.is_err());
+
+		let state_db: StateDb<H256, H256> =
+			StateDb::new(PruningMode::keep_blocks(1), false, &db).unwrap();
+		db.commit(&state_db.canonicalize_block::<io::Error>(&H256::from_low_u64_be(4)).unwrap());
+		state_db.apply_pending();
+		assert!(db.data_eq(&make_db(&[21, 3, 4, 922, 94])));
+	}
+
+	#[test]
+	fn switching_to_canonical_archive_discards_pruning_journal() {
+		fn insert_and_canonicalize(
+			db: &mut TestDb,
+			state_db: &StateDb<H256, H256>,
+			n: u64,
+			inserted: &[u64],
+			deleted: &[u64],
+		) {
+			let hash = H256::from_low_u64_be(n);
+			let parent = H256::from_low_u64_be(n.saturating_sub(1));
+			db.commit(
+				&state_db
+					.insert_block::<io::Error>(&hash, n, &parent, make_changeset(inserted, deleted))
+					.unwrap(),
+			);
+			state_db.apply_pending();
+			db.commit(&state_db.canonicalize_block::<io::Error>(&hash).unwrap());
+			state_db.apply_pending();
+		}
+
+		let mut db = make_db(&[1]);
+		let state_db = StateDb::new(PruningMode::keep_blocks(1), false, &db).unwrap();
+		insert_and_canonicalize(&mut db, &state_db, 0, &[], &[1]);
+
+		let state_db = StateDb::new(PruningMode::ArchiveCanonical, false, &db).unwrap();
+		insert_and_canonicalize(&mut db, &state_db, 1, &[2], &[]);
+		assert!(db.meta.keys().all(|key| !key.ends_with(b"pruning_journal")));
+
+		// the discarded window of block 0 is never pruned.
+		let state_db = StateDb::new(PruningMode::keep_blocks(0), false, &db).unwrap();
+		insert_and_canonicalize(&mut db, &state_db, 2, &[3], &[2]);
+		assert!(db.data_eq(&make_db(&[1, 3])));
+
+		let state_db: Result<StateDb<H256, H256>, _> =
+			StateDb::new(PruningMode::ArchiveAll, false, &db);
+		assert!(state_db.is_err());
+	}
 }
@@ -365,6 +365,12 @@ impl<BlockHash: Hash, Key: Hash> NonCanonicalOverlay<BlockHash, Key> {
 		}
 	}
 
+	/// Number of levels of non-canonical blocks, i.e. how far the best block is ahead of the
+	/// last canonicalized one.
+	pub fn levels_count(&self) -> u64 {
+		(self.levels.len() - self.pending_canonicalizations.len()) as u64
+	}
+
 	pub fn last_canonicalized_hash(&self) -> Option<BlockHash> {
 		self.last_canonicalized.as_ref().map(|&(ref h, _)| h.clone())
 	}
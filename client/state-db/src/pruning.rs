@@ -161,6 +161,16 @@ impl<BlockHash: Hash, Key: Hash> RefWindow<BlockHash, Key> {
 		self.death_rows.iter().skip(self.pending_prunings).any(|r| r.hash == *hash)
 	}
 
+	/// Drop the whole pruning journal without pruning the blocks in the window. Adds changes to
+	/// `commit`.
+	pub fn discard_journal(&self, commit: &mut CommitSet<Key>) {
+		commit
+			.meta
+			.deleted
+			.extend(self.death_rows.iter().map(|row| row.journal_key.clone()));
+		commit.meta.deleted.push(to_meta_key(LAST_PRUNED, &()));
+	}
+
 	/// Prune next block. Expects at least one block in the window. Adds changes to `commit`.
 	pub fn prune_one(&mut self, commit: &mut CommitSet<Key>) {
 		if let Some(pruned) = self.death_rows.get(self.pending_prunings) {
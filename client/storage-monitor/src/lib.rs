@@ -0,0 +1,213 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Storage monitor.
+//!
+//! Polls the space available on the filesystem of the database and stops the node once it
+//! drops below a threshold, before the database gets corrupted by a full disk. The monitor runs
+//! as an essential task, the node shuts down gracefully when it ends.
+
+use futures::FutureExt;
+use log::{debug, error, info, warn};
+use sc_client_db::DatabaseSource;
+use sc_telemetry::{telemetry, TelemetryHandle, SUBSTRATE_INFO};
+use sp_core::traits::SpawnEssentialNamed;
+use std::{
+	io,
+	path::{Path, PathBuf},
+	time::Duration,
+};
+use structopt::StructOpt;
+
+const LOG_TARGET: &str = "storage-monitor";
+
+/// Number of bytes in a MiB.
+const MIB: u64 = 1024 * 1024;
+
+/// Error of the storage monitor.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// Reading the available space failed.
+	#[error("Failed to read the available storage space: {0}")]
+	Io(#[from] io::Error),
+	/// The available space is already below the threshold.
+	#[error("Available storage space ({available} MiB) is below the threshold ({threshold} MiB)")]
+	StorageOutOfSpace {
+		/// Available space in MiB.
+		available: u64,
+		/// Threshold in MiB.
+		threshold: u64,
+	},
+}
+
+/// Parameters of the storage monitor.
+#[derive(Debug, StructOpt, Clone)]
+pub struct StorageMonitorParams {
+	/// Required available space on the database storage, in MiB.
+	///
+	/// The node is stopped when less space is available. `0` disables the storage monitor.
+	#[structopt(long = "db-storage-threshold", value_name = "MiB", default_value = "1024")]
+	pub threshold: u64,
+
+	/// How often the available space on the database storage is checked, in seconds.
+	#[structopt(long = "db-storage-polling-period", value_name = "SECONDS", default_value = "5")]
+	pub polling_period: u32,
+}
+
+/// Periodically checks the space available to the database.
+pub struct StorageMonitorService {
+	path: PathBuf,
+	threshold: u64,
+	polling_period: Duration,
+	telemetry: Option<TelemetryHandle>,
+	low_space_reported: bool,
+}
+
+impl StorageMonitorService {
+	/// Spawn the storage monitor of the given database as an essential task.
+	///
+	/// Nothing is spawned if the monitor is disabled or the database is not stored on disk. Fails
+	/// if the available space is already below the threshold.
+	pub fn try_spawn(
+		params: StorageMonitorParams,
+		database: DatabaseSource,
+		spawner: &impl SpawnEssentialNamed,
+		telemetry: Option<TelemetryHandle>,
+	) -> Result<(), Error> {
+		let path = match database.path() {
+			Some(path) if params.threshold > 0 => path.to_path_buf(),
+			Some(_) => {
+				info!(target: LOG_TARGET, "Storage monitor is disabled");
+				return Ok(())
+			},
+			None => {
+				debug!(target: LOG_TARGET, "Database is not stored on disk, not monitoring it");
+				return Ok(())
+			},
+		};
+
+		let mut service = StorageMonitorService {
+			path,
+			threshold: params.threshold,
+			polling_period: Duration::from_secs(params.polling_period.max(1) as u64),
+			telemetry,
+			low_space_reported: false,
+		};
+		let available = service.check()?;
+		info!(
+			target: LOG_TARGET,
+			"Monitoring storage of {} ({} MiB available, threshold {} MiB)",
+			service.path.display(),
+			available,
+			service.threshold,
+		);
+
+		spawner.spawn_essential("storage-monitor", service.run().boxed());
+		Ok(())
+	}
+
+	/// Poll the available space until it gets below the threshold.
+	async fn run(mut self) {
+		loop {
+			futures_timer::Delay::new(self.polling_period).await;
+			match self.check() {
+				Ok(_) => {},
+				Err(Error::Io(e)) => {
+					debug!(target: LOG_TARGET, "Failed to read the available storage space: {}", e)
+				},
+				Err(e) => {
+					error!(target: LOG_TARGET, "{}, stopping the node", e);
+					return
+				},
+			}
+		}
+	}
+
+	/// Check the available space in MiB, warning when it gets close to the threshold.
+	fn check(&mut self) -> Result<u64, Error> {
+		let available = available_space(&self.path)? / MIB;
+		let low_space = available < self.threshold.saturating_mul(2);
+		if low_space && !self.low_space_reported {
+			warn!(
+				target: LOG_TARGET,
+				"⚠️  Only {} MiB of storage available for the database, the node stops below {} MiB",
+				available,
+				self.threshold,
+			);
+			telemetry!(
+				self.telemetry;
+				SUBSTRATE_INFO;
+				"storage.space_low";
+				"available" => available,
+				"threshold" => self.threshold,
+			);
+		}
+		self.low_space_reported = low_space;
+
+		if available < self.threshold {
+			return Err(Error::StorageOutOfSpace { available, threshold: self.threshold })
+		}
+		Ok(available)
+	}
+}
+
+/// Available space on the filesystem of `path`, or of its closest existing ancestor since the
+/// database may not be created yet.
+fn available_space(path: &Path) -> io::Result<u64> {
+	let existing = path.ancestors().find(|path| path.exists()).unwrap_or(path);
+	fs2::available_space(existing)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn service(path: &Path, threshold: u64) -> StorageMonitorService {
+		StorageMonitorService {
+			path: path.join("chains").join("db"),
+			threshold,
+			polling_period: Duration::from_secs(1),
+			telemetry: None,
+			low_space_reported: false,
+		}
+	}
+
+	#[test]
+	fn check_fails_below_threshold() {
+		let dir = tempfile::tempdir().unwrap();
+
+		let available = service(dir.path(), 1).check().unwrap();
+		assert!(matches!(
+			service(dir.path(), available + 1024).check(),
+			Err(Error::StorageOutOfSpace { threshold, .. }) if threshold == available + 1024,
+		));
+	}
+
+	#[test]
+	fn low_space_is_reported_once() {
+		let dir = tempfile::tempdir().unwrap();
+		let available = service(dir.path(), 1).check().unwrap();
+
+		let mut service = service(dir.path(), available / 2 + 1024);
+		let _ = service.check();
+		assert!(service.low_space_reported);
+		service.threshold = 1;
+		let _ = service.check();
+		assert!(!service.low_space_reported);
+	}
+}
@@ -38,6 +38,25 @@
 //!
 //! If the [`LightSyncStateExtension`] is not added as an extension to the chain spec,
 //! the [`SyncStateRpcHandler`] will fail at instantiation.
+//!
+//! The handler depends on the BABE and GRANDPA state of the node, it is therefore not registered
+//! by `sc-service` but has to be added to the RPC extensions of the node, next to the BABE and
+//! GRANDPA RPC handlers:
+//!
+//! ```ignore
+//! io.extend_with(sc_sync_state_rpc::SyncStateRpcApi::to_delegate(
+//! 	sc_sync_state_rpc::SyncStateRpcHandler::new(
+//! 		chain_spec,
+//! 		client,
+//! 		shared_authority_set,
+//! 		shared_epoch_changes,
+//! 		deny_unsafe,
+//! 	)?,
+//! ));
+//! ```
+//!
+//! The `sync_state_genSyncSpec` RPC then returns the chain spec of the node with the light sync
+//! state of the latest finalized block, allowing light clients to start syncing from there.
 
 #![deny(unused_crate_dependencies)]
 
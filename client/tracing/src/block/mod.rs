@@ -175,7 +175,7 @@ impl Subscriber for BlockSubscriber {
 /// Records spans & events for the supplied targets (eg. "pallet,frame,state") and
 /// only records events with the specified hex encoded storage key prefixes.
 /// Note: if `targets` or `storage_keys` is an empty string then nothing is
-/// filtered out.
+/// filtered out. Omitting `storage_keys` or `methods` does not filter out anything either.
 pub struct BlockExecutor<Block: BlockT, Client> {
 	client: Arc<Client>,
 	block: Block::Hash,
@@ -277,13 +277,13 @@ where
 				self.storage_keys
 					.as_ref()
 					.map(|keys| event_values_filter(e, "key", keys))
-					.unwrap_or(false)
+					.unwrap_or(true)
 			})
 			.filter(|e| {
 				self.methods
 					.as_ref()
 					.map(|methods| event_values_filter(e, "method", methods))
-					.unwrap_or(false)
+					.unwrap_or(true)
 			})
 			.map(|s| s.into())
 			.collect();
@@ -359,3 +359,36 @@ fn block_id_as_string<T: BlockT>(block_id: BlockId<T>) -> String {
 		BlockId::Number(n) => HexDisplay::from(&n.encode()).to_string(),
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn storage_event(method: &str, key: &str) -> TraceEvent {
+		let mut values = Values::default();
+		values.string_values.insert("method".to_owned(), method.to_owned());
+		values.string_values.insert("key".to_owned(), key.to_owned());
+		TraceEvent {
+			name: "event".to_owned(),
+			target: "state".to_owned(),
+			level: Level::TRACE,
+			values,
+			parent_id: None,
+		}
+	}
+
+	#[test]
+	fn event_values_filter_matches_by_prefix() {
+		let event = storage_event("Put", "26aa394eea5630e07c48ae0c9558cef7");
+
+		assert!(event_values_filter(&event, "key", ""));
+		assert!(event_values_filter(&event, "key", "26aa394e"));
+		assert!(event_values_filter(&event, "key", "f0c365c3,26aa394e"));
+		assert!(!event_values_filter(&event, "key", "f0c365c3"));
+
+		assert!(event_values_filter(&event, "method", "Put"));
+		assert!(!event_values_filter(&event, "method", "Get"));
+		// Events without the value are filtered out.
+		assert!(!event_values_filter(&event, "child_info", ""));
+	}
+}
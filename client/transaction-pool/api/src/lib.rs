@@ -27,6 +27,7 @@ pub use sp_runtime::transaction_validity::{
 	TransactionLongevity, TransactionPriority, TransactionSource, TransactionTag,
 };
 use sp_runtime::{
+	codec::Encode,
 	generic::BlockId,
 	traits::{Block as BlockT, Member, NumberFor},
 };
@@ -129,6 +130,54 @@ pub enum TransactionStatus<Hash, BlockHash> {
 pub type TransactionStatusStream<Hash, BlockHash> =
 	dyn Stream<Item = TransactionStatus<Hash, BlockHash>> + Send;
 
+/// An event of a transaction in the pool, reported by the pool-wide event stream.
+///
+/// Unlike [`TransactionStatus`], which is reported for a single watched transaction, the events
+/// are reported for all transactions passing through the pool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionEvent<Hash, BlockHash> {
+	/// The hash of the transaction.
+	pub hash: Hash,
+	/// The event of the transaction.
+	pub event: PoolEvent<Hash, BlockHash>,
+}
+
+/// The kind of a [`TransactionEvent`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PoolEvent<Hash, BlockHash> {
+	/// Transaction was imported to the pool, either to the future or the ready queue.
+	Imported,
+	/// Transaction is part of the ready queue.
+	Ready,
+	/// The transaction has been broadcast to the given peers.
+	Broadcast(Vec<String>),
+	/// Transaction has been included in block with given hash.
+	InBlock(BlockHash),
+	/// Transaction has been dropped from the pool.
+	Dropped {
+		/// Why the transaction was dropped.
+		reason: DropReason<Hash>,
+	},
+	/// Transaction is no longer valid in the current state.
+	Invalid,
+}
+
+/// The reason a transaction was dropped from the pool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DropReason<Hash> {
+	/// Transaction has been dropped because of the limit.
+	Limit,
+	/// Transaction has been replaced by another transaction that provides the same tags.
+	Usurped(Hash),
+}
+
+/// The pool-wide stream of transaction events.
+pub type PoolEventStream<Hash, BlockHash> =
+	futures::channel::mpsc::Receiver<TransactionEvent<Hash, BlockHash>>;
+
 /// The import notification event stream.
 pub type ImportNotificationStream<H> = futures::channel::mpsc::Receiver<H>;
 
@@ -170,6 +219,8 @@ pub trait InPoolTransaction {
 	fn provides(&self) -> &[TransactionTag];
 	/// Return a flag indicating if the transaction should be propagated to other peers.
 	fn is_propagable(&self) -> bool;
+	/// Get the source the transaction was submitted from.
+	fn source(&self) -> TransactionSource;
 }
 
 /// Transaction pool interface.
@@ -243,6 +294,12 @@ pub trait TransactionPool: Send + Sync {
 	/// Return an event stream of transactions imported to the pool.
 	fn import_notification_stream(&self) -> ImportNotificationStream<TxHash<Self>>;
 
+	// *** RPC / indexing
+	/// Return a stream of the events of all transactions in the pool.
+	///
+	/// Events are dropped if the stream is not consumed fast enough.
+	fn event_stream(&self) -> PoolEventStream<TxHash<Self>, BlockHash<Self>>;
+
 	// *** networking
 	/// Notify the pool about transactions broadcast.
 	fn on_broadcasted(&self, propagations: HashMap<TxHash<Self>, Vec<String>>);
@@ -252,6 +309,30 @@ pub trait TransactionPool: Send + Sync {
 
 	/// Return specific ready transaction by hash, if there is one.
 	fn ready_transaction(&self, hash: &TxHash<Self>) -> Option<Arc<Self::InPoolTransaction>>;
+
+	/// Returns the lowest priority a new transaction of `len` bytes needs to be included in the
+	/// next block, given the ready transactions competing for `max_block_length` bytes.
+	///
+	/// Returns `0` if the ready queue is not congested, i.e. if the transaction would fit in the
+	/// next block regardless of its priority.
+	fn inclusion_priority(&self, len: usize, max_block_length: usize) -> TransactionPriority {
+		let mut ready = self
+			.ready()
+			.map(|tx| (*tx.priority(), tx.data().encoded_size()))
+			.collect::<Vec<_>>();
+		ready.sort_by(|a, b| b.0.cmp(&a.0));
+
+		let mut total_length = len;
+		for (priority, length) in ready {
+			total_length = total_length.saturating_add(length);
+			if total_length > max_block_length {
+				// transactions with equal priority are ordered by their arrival, so the new
+				// transaction has to outbid the first one that does not fit anymore.
+				return priority.saturating_add(1)
+			}
+		}
+		0
+	}
 }
 
 /// Events that the transaction pool listens for.
@@ -32,6 +32,7 @@ use sp_runtime::{
 		TransactionLongevity as Longevity, TransactionPriority as Priority,
 		TransactionSource as Source, TransactionTag as Tag,
 	},
+	Percent,
 };
 
 use super::{
@@ -57,6 +58,8 @@ pub enum Imported<Hash, Ex> {
 	Future {
 		/// Hash of transaction that was successfully imported.
 		hash: Hash,
+		/// Transactions removed from the Future queue (replaced).
+		removed: Vec<Arc<Transaction<Hash, Ex>>>,
 	},
 }
 
@@ -143,6 +146,10 @@ impl<Hash, Extrinsic> InPoolTransaction for Transaction<Hash, Extrinsic> {
 	fn is_propagable(&self) -> bool {
 		self.propagate
 	}
+
+	fn source(&self) -> Source {
+		self.source
+	}
 }
 
 impl<Hash: Clone, Extrinsic: Clone> Transaction<Hash, Extrinsic> {
@@ -210,6 +217,9 @@ const RECENTLY_PRUNED_TAGS: usize = 2;
 #[derive(Debug, parity_util_mem::MallocSizeOf)]
 pub struct BasePool<Hash: hash::Hash + Eq, Ex> {
 	reject_future_transactions: bool,
+	future_sender_limit: Option<SenderLimit>,
+	#[ignore_malloc_size_of = "Primitive type"]
+	future_replacement_bump: Percent,
 	future: FutureTransactions<Hash, Ex>,
 	ready: ReadyTransactions<Hash, Ex>,
 	/// Store recently pruned tags (for last two invocations).
@@ -222,15 +232,25 @@ pub struct BasePool<Hash: hash::Hash + Eq, Ex> {
 
 impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> Default for BasePool<Hash, Ex> {
 	fn default() -> Self {
-		Self::new(false)
+		Self::new(false, None, Percent::zero())
 	}
 }
 
 impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash, Ex> {
-	/// Create new pool given reject_future_transactions flag.
-	pub fn new(reject_future_transactions: bool) -> Self {
+	/// Create new pool given reject_future_transactions flag and the future queue policies.
+	///
+	/// `future_sender_limit` bounds the number of future transactions of a single sender and
+	/// `future_replacement_bump` is the minimal priority increase required for a future
+	/// transaction to replace another one providing the same tags.
+	pub fn new(
+		reject_future_transactions: bool,
+		future_sender_limit: Option<SenderLimit>,
+		future_replacement_bump: Percent,
+	) -> Self {
 		Self {
 			reject_future_transactions,
+			future_sender_limit,
+			future_replacement_bump,
 			future: Default::default(),
 			ready: Default::default(),
 			recently_pruned: Default::default(),
@@ -287,13 +307,71 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			}
 
 			let hash = tx.transaction.hash.clone();
+			let removed = self.replace_future(&tx.transaction)?;
 			self.future.import(tx);
-			return Ok(Imported::Future { hash })
+			return Ok(Imported::Future { hash, removed })
 		}
 
 		self.import_to_ready(tx)
 	}
 
+	/// Makes room in the Future queue for the given transaction.
+	///
+	/// Future transactions providing exactly the same tags as the new one (i.e. having the same
+	/// nonce) are replaced if the priority of the new transaction is higher by at least
+	/// `future_replacement_bump`. The number of future transactions of the sender is then
+	/// checked against `future_sender_limit`, not counting the replaced ones.
+	///
+	/// Returns the removed transactions, the queue is left untouched in case of an error.
+	fn replace_future(
+		&mut self,
+		tx: &Transaction<Hash, Ex>,
+	) -> error::Result<Vec<Arc<Transaction<Hash, Ex>>>> {
+		let replaced = self
+			.future
+			.all()
+			.filter(|old| !tx.provides.is_empty() && old.provides == tx.provides)
+			.map(|old| (old.hash.clone(), old.priority))
+			.collect::<Vec<_>>();
+
+		if !replaced.is_empty() {
+			let old_priority = replaced
+				.iter()
+				.fold(0 as Priority, |total, (_, priority)| total.saturating_add(*priority));
+			let required_priority =
+				old_priority.saturating_add(self.future_replacement_bump * old_priority);
+
+			// bail - the transaction doesn't bump the priority enough to replace the old ones
+			if tx.priority <= old_priority || tx.priority < required_priority {
+				return Err(error::Error::TooLowPriority { old: old_priority, new: tx.priority })
+			}
+		}
+
+		if let Some(ref limit) = self.future_sender_limit {
+			if let Some(sender) = limit.sender(tx) {
+				let pending = self
+					.future
+					.all()
+					.filter(|old| limit.sender(old) == Some(sender))
+					.filter(|old| !replaced.iter().any(|(hash, _)| *hash == old.hash))
+					.count();
+
+				if pending >= limit.count {
+					debug!(
+						target: "txpool",
+						"[{:?}] Sender already has {} future transactions",
+						tx.hash,
+						pending,
+					);
+					return Err(error::Error::ImmediatelyDropped)
+				}
+			}
+		}
+
+		let hashes = replaced.into_iter().map(|(hash, _)| hash).collect::<Vec<_>>();
+		Ok(self.future.remove(&hashes))
+	}
+
 	/// Imports transaction to ready queue.
 	///
 	/// NOTE the transaction has to have all requirements satisfied.
@@ -514,6 +592,30 @@ impl Limit {
 	}
 }
 
+/// Limit of future transactions of a single sender.
+///
+/// The pool doesn't know the senders of transactions, so they are derived from the tags:
+/// transactions whose first provided tags are equal once the trailing `nonce_bytes` are
+/// stripped are considered to come from the same sender. This matches the `(AccountId, Index)`
+/// tags provided by FRAME's `CheckNonce` signed extension.
+#[derive(Debug, Clone, parity_util_mem::MallocSizeOf)]
+pub struct SenderLimit {
+	/// Maximal number of future transactions of a single sender.
+	pub count: usize,
+	/// Number of trailing bytes of the provided tag encoding the nonce.
+	pub nonce_bytes: usize,
+}
+
+impl SenderLimit {
+	/// Returns the sender of given transaction or `None` if it can't be determined.
+	fn sender<'a, Hash, Ex>(&self, tx: &'a Transaction<Hash, Ex>) -> Option<&'a [u8]> {
+		tx.provides
+			.first()
+			.filter(|tag| tag.len() > self.nonce_bytes)
+			.map(|tag| &tag[..tag.len() - self.nonce_bytes])
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1024,6 +1126,76 @@ source: TransactionSource::External, requires: [03, 02], provides: [04], data: [
 		assert_eq!(pool.future.len(), 0);
 	}
 
+	#[test]
+	fn should_replace_future_transaction_only_with_bumped_priority() {
+		// given
+		let mut pool = BasePool::new(false, None, Percent::from_percent(10));
+		pool.import(Transaction {
+			data: vec![1u8],
+			hash: 1,
+			priority: 100u64,
+			requires: vec![vec![0]],
+			provides: vec![vec![1]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+
+		// when
+		let err = pool
+			.import(Transaction {
+				data: vec![2u8],
+				hash: 2,
+				priority: 109u64,
+				requires: vec![vec![0]],
+				provides: vec![vec![1]],
+				..DEFAULT_TX.clone()
+			})
+			.unwrap_err();
+		let res = pool
+			.import(Transaction {
+				data: vec![3u8],
+				hash: 3,
+				priority: 110u64,
+				requires: vec![vec![0]],
+				provides: vec![vec![1]],
+				..DEFAULT_TX.clone()
+			})
+			.unwrap();
+
+		// then
+		assert!(matches!(err, error::Error::TooLowPriority { old: 100, new: 109 }));
+		assert_eq!(res.hash(), &3);
+		assert!(matches!(res, Imported::Future { ref removed, .. } if removed[0].hash == 1));
+		assert_eq!(pool.futures().map(|tx| tx.hash).collect::<Vec<_>>(), vec![3]);
+	}
+
+	#[test]
+	fn should_limit_future_transactions_per_sender() {
+		// given
+		let limit = SenderLimit { count: 2, nonce_bytes: 1 };
+		let mut pool = BasePool::new(false, Some(limit), Percent::zero());
+		let future = |sender: u8, nonce: u8| Transaction {
+			data: vec![sender, nonce],
+			hash: (sender as u64) << 8 | nonce as u64,
+			requires: vec![vec![sender, nonce - 1]],
+			provides: vec![vec![sender, nonce]],
+			..DEFAULT_TX.clone()
+		};
+
+		// when
+		pool.import(future(1, 2)).unwrap();
+		pool.import(future(1, 4)).unwrap();
+		let err = pool.import(future(1, 6)).unwrap_err();
+		pool.import(future(2, 2)).unwrap();
+		// replacing a transaction of the sender doesn't count towards the limit
+		let res = pool.import(Transaction { priority: 10u64, hash: 1000, ..future(1, 4) });
+
+		// then
+		assert!(matches!(err, error::Error::ImmediatelyDropped));
+		assert!(res.is_ok());
+		assert_eq!(pool.future.len(), 3);
+	}
+
 	#[test]
 	fn should_accept_future_transactions_when_explicitly_asked_to() {
 		// given
@@ -18,8 +18,11 @@
 
 use std::{collections::HashMap, fmt::Debug, hash};
 
+use futures::channel::mpsc::{channel, Sender};
 use linked_hash_map::LinkedHashMap;
-use log::{debug, trace};
+use log::{debug, trace, warn};
+use retain_mut::RetainMut;
+use sc_transaction_pool_api::{DropReason, PoolEvent, PoolEventStream, TransactionEvent};
 use serde::Serialize;
 use sp_runtime::traits;
 
@@ -29,6 +32,7 @@ use super::{watcher, BlockHash, ChainApi, ExtrinsicHash};
 pub struct Listener<H: hash::Hash + Eq, C: ChainApi> {
 	watchers: HashMap<H, watcher::Sender<H, ExtrinsicHash<C>>>,
 	finality_watchers: LinkedHashMap<ExtrinsicHash<C>, Vec<H>>,
+	event_sinks: Vec<Sender<TransactionEvent<H, BlockHash<C>>>>,
 }
 
 /// Maximum number of blocks awaiting finality at any time.
@@ -36,7 +40,11 @@ const MAX_FINALITY_WATCHERS: usize = 512;
 
 impl<H: hash::Hash + Eq + Debug, C: ChainApi> Default for Listener<H, C> {
 	fn default() -> Self {
-		Self { watchers: Default::default(), finality_watchers: Default::default() }
+		Self {
+			watchers: Default::default(),
+			finality_watchers: Default::default(),
+			event_sinks: Default::default(),
+		}
 	}
 }
 
@@ -57,6 +65,38 @@ impl<H: hash::Hash + traits::Member + Serialize, C: ChainApi> Listener<H, C> {
 		}
 	}
 
+	/// Report the event of the transaction to the pool-wide event streams.
+	fn notify(&mut self, hash: &H, event: PoolEvent<H, BlockHash<C>>) {
+		if self.event_sinks.is_empty() {
+			return
+		}
+
+		let event = TransactionEvent { hash: hash.clone(), event };
+		self.event_sinks.retain_mut(|sink| match sink.try_send(event.clone()) {
+			Ok(()) => true,
+			Err(e) =>
+				if e.is_full() {
+					warn!(
+						target: "txpool",
+						"[{:?}] Trying to notify a pool event but the channel is full",
+						hash,
+					);
+					true
+				} else {
+					false
+				},
+		});
+	}
+
+	/// Creates a new stream of the events of all transactions.
+	pub fn create_event_stream(&mut self) -> PoolEventStream<H, BlockHash<C>> {
+		const CHANNEL_BUFFER_SIZE: usize = 1024;
+
+		let (sink, stream) = channel(CHANNEL_BUFFER_SIZE);
+		self.event_sinks.push(sink);
+		stream
+	}
+
 	/// Creates a new watcher for given verified extrinsic.
 	///
 	/// The watcher can be used to subscribe to life-cycle events of that extrinsic.
@@ -68,14 +108,23 @@ impl<H: hash::Hash + traits::Member + Serialize, C: ChainApi> Listener<H, C> {
 	/// Notify the listeners about extrinsic broadcast.
 	pub fn broadcasted(&mut self, hash: &H, peers: Vec<String>) {
 		trace!(target: "txpool", "[{:?}] Broadcasted", hash);
+		self.notify(hash, PoolEvent::Broadcast(peers.clone()));
 		self.fire(hash, |watcher| watcher.broadcast(peers));
 	}
 
+	/// New transaction was imported to the pool.
+	pub fn imported(&mut self, tx: &H) {
+		trace!(target: "txpool", "[{:?}] Imported", tx);
+		self.notify(tx, PoolEvent::Imported);
+	}
+
 	/// New transaction was added to the ready pool or promoted from the future pool.
 	pub fn ready(&mut self, tx: &H, old: Option<&H>) {
 		trace!(target: "txpool", "[{:?}] Ready (replaced with {:?})", tx, old);
+		self.notify(tx, PoolEvent::Ready);
 		self.fire(tx, |watcher| watcher.ready());
 		if let Some(old) = old {
+			self.notify(old, PoolEvent::Dropped { reason: DropReason::Usurped(tx.clone()) });
 			self.fire(old, |watcher| watcher.usurped(tx.clone()));
 		}
 	}
@@ -89,6 +138,11 @@ impl<H: hash::Hash + traits::Member + Serialize, C: ChainApi> Listener<H, C> {
 	/// Transaction was dropped from the pool because of the limit.
 	pub fn dropped(&mut self, tx: &H, by: Option<&H>) {
 		trace!(target: "txpool", "[{:?}] Dropped (replaced with {:?})", tx, by);
+		let reason = match by {
+			Some(t) => DropReason::Usurped(t.clone()),
+			None => DropReason::Limit,
+		};
+		self.notify(tx, PoolEvent::Dropped { reason });
 		self.fire(tx, |watcher| match by {
 			Some(t) => watcher.usurped(t.clone()),
 			None => watcher.dropped(),
@@ -98,12 +152,14 @@ impl<H: hash::Hash + traits::Member + Serialize, C: ChainApi> Listener<H, C> {
 	/// Transaction was removed as invalid.
 	pub fn invalid(&mut self, tx: &H) {
 		debug!(target: "txpool", "[{:?}] Extrinsic invalid", tx);
+		self.notify(tx, PoolEvent::Invalid);
 		self.fire(tx, |watcher| watcher.invalid());
 	}
 
 	/// Transaction was pruned from the pool.
 	pub fn pruned(&mut self, block_hash: BlockHash<C>, tx: &H) {
 		debug!(target: "txpool", "[{:?}] Pruned at {:?}", tx, block_hash);
+		self.notify(tx, PoolEvent::InBlock(block_hash));
 		self.fire(tx, |s| s.in_block(block_hash));
 		self.finality_watchers.entry(block_hash).or_insert(vec![]).push(tx.clone());
 
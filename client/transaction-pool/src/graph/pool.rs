@@ -26,6 +26,7 @@ use sp_runtime::{
 	transaction_validity::{
 		TransactionSource, TransactionTag as Tag, TransactionValidity, TransactionValidityError,
 	},
+	Percent,
 };
 use std::time::Instant;
 
@@ -106,8 +107,16 @@ pub struct Options {
 	pub ready: base::Limit,
 	/// Future queue limits.
 	pub future: base::Limit,
+	/// Future queue limit of a single sender.
+	pub future_per_sender: Option<base::SenderLimit>,
+	/// Minimal priority increase for a future transaction to replace another one with the same
+	/// nonce.
+	pub future_replacement_bump: Percent,
 	/// Reject future transactions.
 	pub reject_future_transactions: bool,
+	/// Never propagate transactions submitted from `TransactionSource::Local`, so that they are
+	/// only included in blocks authored by this node.
+	pub private_local_transactions: bool,
 }
 
 impl Default for Options {
@@ -115,7 +124,10 @@ impl Default for Options {
 		Self {
 			ready: base::Limit { count: 8192, total_bytes: 20 * 1024 * 1024 },
 			future: base::Limit { count: 512, total_bytes: 1 * 1024 * 1024 },
+			future_per_sender: Some(base::SenderLimit { count: 64, nonce_bytes: 4 }),
+			future_replacement_bump: Percent::from_percent(10),
 			reject_future_transactions: false,
+			private_local_transactions: false,
 		}
 	}
 }
@@ -448,7 +460,7 @@ mod tests {
 	use codec::Encode;
 	use futures::executor::block_on;
 	use parking_lot::Mutex;
-	use sc_transaction_pool_api::TransactionStatus;
+	use sc_transaction_pool_api::{PoolEvent, TransactionStatus};
 	use sp_runtime::{
 		traits::Hash,
 		transaction_validity::{InvalidTransaction, TransactionSource, ValidTransaction},
@@ -661,6 +673,33 @@ mod tests {
 		assert_matches!(res.unwrap_err(), error::Error::Unactionable);
 	}
 
+	#[test]
+	fn should_not_propagate_private_local_transactions() {
+		// given
+		let options = Options { private_local_transactions: true, ..Default::default() };
+		let pool = Pool::new(options, true.into(), TestApi::default().into());
+		let transfer = |nonce| {
+			uxt(Transfer {
+				from: AccountId::from_h256(H256::from_low_u64_be(1)),
+				to: AccountId::from_h256(H256::from_low_u64_be(2)),
+				amount: 5,
+				nonce,
+			})
+		};
+
+		// when
+		let local =
+			block_on(pool.submit_one(&BlockId::Number(0), TransactionSource::Local, transfer(0)))
+				.unwrap();
+		let external = block_on(pool.submit_one(&BlockId::Number(0), SOURCE, transfer(1))).unwrap();
+
+		// then
+		let ready = |hash| pool.validated_pool().ready_by_hash(&hash).unwrap();
+		assert_eq!(ready(local).source, TransactionSource::Local);
+		assert!(!ready(local).propagate);
+		assert!(ready(external).propagate);
+	}
+
 	#[test]
 	fn should_notify_about_pool_events() {
 		let (stream, hash0, hash1) = {
@@ -993,6 +1032,33 @@ mod tests {
 			assert_eq!(stream.next(), Some(TransactionStatus::Ready));
 		}
 
+		#[test]
+		fn should_report_imported_once_when_promoted() {
+			// given
+			let pool = pool();
+			let mut events = pool.validated_pool().event_stream();
+			let xt = uxt(Transfer {
+				from: AccountId::from_h256(H256::from_low_u64_be(1)),
+				to: AccountId::from_h256(H256::from_low_u64_be(2)),
+				amount: 5,
+				nonce: 1,
+			});
+			let hash = block_on(pool.submit_one(&BlockId::Number(0), SOURCE, xt)).unwrap();
+			assert_eq!(pool.validated_pool().status().future, 1);
+
+			// when
+			block_on(pool.prune_tags(&BlockId::Number(1), vec![vec![0u8]], vec![])).unwrap();
+			assert_eq!(pool.validated_pool().status().ready, 1);
+
+			// then
+			let mut received = vec![];
+			while let Ok(Some(event)) = events.try_next() {
+				assert_eq!(event.hash, hash);
+				received.push(event.event);
+			}
+			assert_eq!(received, vec![PoolEvent::Imported, PoolEvent::Ready]);
+		}
+
 		#[test]
 		fn should_trigger_invalid_and_ban() {
 			// given
@@ -25,7 +25,7 @@ use std::{
 use futures::channel::mpsc::{channel, Sender};
 use parking_lot::{Mutex, RwLock};
 use retain_mut::RetainMut;
-use sc_transaction_pool_api::{error, PoolStatus};
+use sc_transaction_pool_api::{error, PoolEventStream, PoolStatus};
 use serde::Serialize;
 use sp_runtime::{
 	generic::BlockId,
@@ -124,7 +124,11 @@ where
 impl<B: ChainApi> ValidatedPool<B> {
 	/// Create a new transaction pool.
 	pub fn new(options: Options, is_validator: IsValidator, api: Arc<B>) -> Self {
-		let base_pool = base::BasePool::new(options.reject_future_transactions);
+		let base_pool = base::BasePool::new(
+			options.reject_future_transactions,
+			options.future_per_sender.clone(),
+			options.future_replacement_bump,
+		);
 		Self {
 			is_validator,
 			options,
@@ -192,10 +196,21 @@ impl<B: ChainApi> ValidatedPool<B> {
 			.collect()
 	}
 
+	/// Prevent local transactions from being propagated if the pool keeps them private.
+	fn apply_propagation_policy(
+		&self,
+		tx: &mut base::Transaction<ExtrinsicHash<B>, ExtrinsicFor<B>>,
+	) {
+		if tx.source == TransactionSource::Local && self.options.private_local_transactions {
+			tx.propagate = false;
+		}
+	}
+
 	/// Submit single pre-validated transaction to the pool.
 	fn submit_one(&self, tx: ValidatedTransactionFor<B>) -> Result<ExtrinsicHash<B>, B::Error> {
 		match tx {
-			ValidatedTransaction::Valid(tx) => {
+			ValidatedTransaction::Valid(mut tx) => {
+				self.apply_propagation_policy(&mut tx);
 				if !tx.propagate && !(self.is_validator.0)() {
 					return Err(error::Error::Unactionable.into())
 				}
@@ -222,6 +237,7 @@ impl<B: ChainApi> ValidatedPool<B> {
 				}
 
 				let mut listener = self.listener.write();
+				listener.imported(imported.hash());
 				fire_events(&mut *listener, &imported);
 				Ok(*imported.hash())
 			},
@@ -344,7 +360,7 @@ impl<B: ChainApi> ValidatedPool<B> {
 				for removed_tx in removed {
 					let removed_hash = removed_tx.hash;
 					let updated_transaction = updated_transactions.remove(&removed_hash);
-					let tx_to_resubmit = if let Some(updated_tx) = updated_transaction {
+					let mut tx_to_resubmit = if let Some(updated_tx) = updated_transaction {
 						updated_tx
 					} else {
 						// in most cases we'll end up in successful `try_unwrap`, but if not
@@ -356,6 +372,10 @@ impl<B: ChainApi> ValidatedPool<B> {
 						ValidatedTransaction::Valid(transaction)
 					};
 
+					if let ValidatedTransaction::Valid(ref mut tx) = tx_to_resubmit {
+						self.apply_propagation_policy(tx);
+					}
+
 					initial_statuses.insert(removed_hash, Status::Ready);
 					txs_to_resubmit.push((removed_hash, tx_to_resubmit));
 				}
@@ -386,8 +406,11 @@ impl<B: ChainApi> ValidatedPool<B> {
 										final_statuses.insert(tx.hash, Status::Dropped);
 									}
 								},
-								base::Imported::Future { .. } => {
+								base::Imported::Future { removed, .. } => {
 									final_statuses.insert(hash, Status::Future);
+									for tx in removed {
+										final_statuses.insert(tx.hash, Status::Dropped);
+									}
 								},
 							},
 							Err(err) => {
@@ -592,6 +615,11 @@ impl<B: ChainApi> ValidatedPool<B> {
 		stream
 	}
 
+	/// Get a stream of the events of all transactions in the pool.
+	pub fn event_stream(&self) -> PoolEventStream<ExtrinsicHash<B>, BlockHash<B>> {
+		self.listener.write().create_event_stream()
+	}
+
 	/// Invoked when extrinsics are broadcasted.
 	pub fn on_broadcasted(&self, propagated: HashMap<ExtrinsicHash<B>, Vec<String>>) {
 		let mut listener = self.listener.write();
@@ -673,6 +701,9 @@ where
 			removed.into_iter().for_each(|r| listener.dropped(&r.hash, Some(hash)));
 			promoted.into_iter().for_each(|p| listener.ready(p, None));
 		},
-		base::Imported::Future { ref hash } => listener.future(hash),
+		base::Imported::Future { ref hash, ref removed } => {
+			listener.future(hash);
+			removed.into_iter().for_each(|r| listener.dropped(&r.hash, Some(hash)));
+		},
 	}
 }
@@ -55,8 +55,9 @@ use std::{
 
 use graph::{ExtrinsicHash, IsValidator};
 use sc_transaction_pool_api::{
-	ChainEvent, ImportNotificationStream, MaintainedTransactionPool, PoolFuture, PoolStatus,
-	TransactionFor, TransactionPool, TransactionSource, TransactionStatusStreamFor, TxHash,
+	BlockHash, ChainEvent, ImportNotificationStream, MaintainedTransactionPool, PoolEventStream,
+	PoolFuture, PoolStatus, TransactionFor, TransactionPool, TransactionSource,
+	TransactionStatusStreamFor, TxHash,
 };
 use sp_core::traits::SpawnEssentialNamed;
 use sp_runtime::{
@@ -318,6 +319,10 @@ where
 		self.pool.validated_pool().import_notification_stream()
 	}
 
+	fn event_stream(&self) -> PoolEventStream<TxHash<Self>, BlockHash<Self>> {
+		self.pool.validated_pool().event_stream()
+	}
+
 	fn hash_of(&self, xt: &TransactionFor<Self>) -> TxHash<Self> {
 		self.pool.hash_of(xt)
 	}
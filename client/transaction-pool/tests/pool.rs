@@ -27,7 +27,8 @@ use sc_block_builder::BlockBuilderProvider;
 use sc_client_api::client::BlockchainEvents;
 use sc_transaction_pool::{test_helpers::*, *};
 use sc_transaction_pool_api::{
-	ChainEvent, MaintainedTransactionPool, TransactionPool, TransactionStatus,
+	ChainEvent, MaintainedTransactionPool, PoolEvent, TransactionEvent, TransactionPool,
+	TransactionStatus,
 };
 use sp_consensus::BlockOrigin;
 use sp_runtime::{
@@ -408,6 +409,59 @@ fn should_push_watchers_during_maintenance() {
 	);
 }
 
+#[test]
+fn should_report_events_of_all_transactions() {
+	// given
+	let (pool, _guard, _notifier) = maintained_pool();
+	let mut events = pool.event_stream();
+
+	// when
+	let tx0 = uxt(Alice, 209);
+	let tx1 = uxt(Alice, 211);
+	block_on(pool.submit_one(&BlockId::number(0), SOURCE, tx0.clone())).unwrap();
+	block_on(pool.submit_one(&BlockId::number(0), SOURCE, tx1.clone())).unwrap();
+	let (hash0, hash1) = (pool.hash_of(&tx0), pool.hash_of(&tx1));
+	pool.on_broadcasted(vec![(hash0, vec!["peer".into()])].into_iter().collect());
+	pool.remove_invalid(&[hash0]);
+
+	// then
+	let event = |hash, event| TransactionEvent { hash, event };
+	let mut received = vec![];
+	while let Ok(Some(event)) = events.try_next() {
+		received.push(event);
+	}
+	assert_eq!(
+		received,
+		vec![
+			event(hash0, PoolEvent::Imported),
+			event(hash0, PoolEvent::Ready),
+			event(hash1, PoolEvent::Imported),
+			event(hash1, PoolEvent::Ready),
+			event(hash0, PoolEvent::Broadcast(vec!["peer".into()])),
+			event(hash0, PoolEvent::Invalid),
+			// `tx1` depends on `tx0`, so it is removed along with it.
+			event(hash1, PoolEvent::Invalid),
+		],
+	);
+}
+
+#[test]
+fn inclusion_priority_should_reflect_congestion() {
+	// given
+	let (pool, _guard, _notifier) = maintained_pool();
+	let len = uxt(Alice, 209).encoded_size();
+	assert_eq!(pool.inclusion_priority(len, len), 0);
+
+	// when
+	block_on(pool.submit_one(&BlockId::number(0), SOURCE, uxt(Alice, 209))).unwrap();
+	block_on(pool.submit_one(&BlockId::number(0), SOURCE, uxt(Alice, 210))).unwrap();
+
+	// then
+	assert_eq!(pool.inclusion_priority(len, 3 * len), 0);
+	// the test api assigns every transaction a priority of `1`.
+	assert_eq!(pool.inclusion_priority(len, 2 * len), 2);
+}
+
 #[test]
 fn can_track_heap_size() {
 	let (pool, _guard, _notifier) = maintained_pool();
@@ -26,7 +26,7 @@ use frame_benchmarking::{
 };
 use frame_support::{
 	dispatch::UnfilteredDispatchable,
-	traits::{EnsureOrigin, Get},
+	traits::Get,
 };
 use frame_system::RawOrigin as SystemOrigin;
 use sp_runtime::traits::Bounded;
@@ -151,10 +151,15 @@ fn assert_event<T: Config<I>, I: 'static>(generic_event: <T as Config<I>>::Event
 
 benchmarks_instance_pallet! {
 	create {
-		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = Default::default();
+		let origin = T::CreateOrigin::successful_origin(&asset_id);
+		let caller = T::CreateOrigin::ensure_origin(origin.clone(), &asset_id).unwrap();
+		whitelist_account!(caller);
 		let caller_lookup = T::Lookup::unlookup(caller.clone());
 		T::Currency::make_free_balance_be(&caller, DepositBalanceOf::<T, I>::max_value());
-	}: _(SystemOrigin::Signed(caller.clone()), Default::default(), caller_lookup, 1u32.into())
+		let call =
+			Call::<T, I>::create { id: asset_id, admin: caller_lookup, min_balance: 1u32.into() };
+	}: { call.dispatch_bypass_filter(origin)? }
 	verify {
 		assert_last_event::<T, I>(Event::Created(Default::default(), caller.clone(), caller).into());
 	}
@@ -329,7 +334,7 @@ benchmarks_instance_pallet! {
 
 		create_default_asset::<T, I>(true);
 
-		let origin = T::ForceOrigin::successful_origin();
+		let origin = T::ForceOrigin::successful_origin(&Default::default());
 		let call = Call::<T, I>::force_set_metadata {
 			id: Default::default(),
 			name: name.clone(),
@@ -350,7 +355,7 @@ benchmarks_instance_pallet! {
 		let origin = SystemOrigin::Signed(caller.clone()).into();
 		Assets::<T, I>::set_metadata(origin, Default::default(), dummy.clone(), dummy, 12)?;
 
-		let origin = T::ForceOrigin::successful_origin();
+		let origin = T::ForceOrigin::successful_origin(&Default::default());
 		let call = Call::<T, I>::force_clear_metadata { id: Default::default() };
 	}: { call.dispatch_bypass_filter(origin)? }
 	verify {
@@ -360,7 +365,7 @@ benchmarks_instance_pallet! {
 	force_asset_status {
 		let (caller, caller_lookup) = create_default_asset::<T, I>(true);
 
-		let origin = T::ForceOrigin::successful_origin();
+		let origin = T::ForceOrigin::successful_origin(&Default::default());
 		let call = Call::<T, I>::force_asset_status {
 			id: Default::default(),
 			owner: caller_lookup.clone(),
@@ -146,7 +146,7 @@ use frame_support::{
 	traits::{
 		tokens::{fungibles, DepositConsequence, WithdrawConsequence},
 		BalanceStatus::Reserved,
-		Currency, ReservableCurrency, StoredMap,
+		Currency, EnsureOriginWithArg, ReservableCurrency, StoredMap,
 	},
 };
 use frame_system::Config as SystemConfig;
@@ -187,9 +187,17 @@ pub mod pallet {
 		/// The currency mechanism.
 		type Currency: ReservableCurrency<Self::AccountId>;
 
+		/// Standard asset creation is only allowed if the origin attempting it and the asset ID
+		/// are in this set.
+		type CreateOrigin: EnsureOriginWithArg<
+			Self::Origin,
+			Self::AssetId,
+			Success = Self::AccountId,
+		>;
+
 		/// The origin which may forcibly create or destroy an asset or otherwise alter privileged
-		/// attributes.
-		type ForceOrigin: EnsureOrigin<Self::Origin>;
+		/// attributes of the given asset.
+		type ForceOrigin: EnsureOriginWithArg<Self::Origin, Self::AssetId>;
 
 		/// The basic amount of funds that must be reserved for an asset.
 		#[pallet::constant]
@@ -356,7 +364,7 @@ pub mod pallet {
 		///
 		/// This new asset class has no assets initially and its owner is the origin.
 		///
-		/// The origin must be Signed and the sender must have sufficient funds free.
+		/// The origin must conform to the configured `CreateOrigin` and have sufficient funds free.
 		///
 		/// Funds of sender are reserved by `AssetDeposit`.
 		///
@@ -378,7 +386,7 @@ pub mod pallet {
 			admin: <T::Lookup as StaticLookup>::Source,
 			min_balance: T::Balance,
 		) -> DispatchResult {
-			let owner = ensure_signed(origin)?;
+			let owner = T::CreateOrigin::ensure_origin(origin, &id)?;
 			let admin = T::Lookup::lookup(admin)?;
 
 			ensure!(!Asset::<T, I>::contains_key(id), Error::<T, I>::InUse);
@@ -435,31 +443,10 @@ pub mod pallet {
 			is_sufficient: bool,
 			#[pallet::compact] min_balance: T::Balance,
 		) -> DispatchResult {
-			T::ForceOrigin::ensure_origin(origin)?;
+			T::ForceOrigin::ensure_origin(origin, &id)?;
 			let owner = T::Lookup::lookup(owner)?;
 
-			ensure!(!Asset::<T, I>::contains_key(id), Error::<T, I>::InUse);
-			ensure!(!min_balance.is_zero(), Error::<T, I>::MinBalanceZero);
-
-			Asset::<T, I>::insert(
-				id,
-				AssetDetails {
-					owner: owner.clone(),
-					issuer: owner.clone(),
-					admin: owner.clone(),
-					freezer: owner.clone(),
-					supply: Zero::zero(),
-					deposit: Zero::zero(),
-					min_balance,
-					is_sufficient,
-					accounts: 0,
-					sufficients: 0,
-					approvals: 0,
-					is_frozen: false,
-				},
-			);
-			Self::deposit_event(Event::ForceCreated(id, owner));
-			Ok(())
+			Self::do_force_create(id, owner, is_sufficient, min_balance)
 		}
 
 		/// Destroy a class of fungible assets.
@@ -490,7 +477,7 @@ pub mod pallet {
 			#[pallet::compact] id: T::AssetId,
 			witness: DestroyWitness,
 		) -> DispatchResultWithPostInfo {
-			let maybe_check_owner = match T::ForceOrigin::try_origin(origin) {
+			let maybe_check_owner = match T::ForceOrigin::try_origin(origin, &id) {
 				Ok(_) => None,
 				Err(origin) => Some(ensure_signed(origin)?),
 			};
@@ -988,7 +975,7 @@ pub mod pallet {
 			decimals: u8,
 			is_frozen: bool,
 		) -> DispatchResult {
-			T::ForceOrigin::ensure_origin(origin)?;
+			T::ForceOrigin::ensure_origin(origin, &id)?;
 
 			let bounded_name: BoundedVec<u8, T::StringLimit> =
 				name.clone().try_into().map_err(|_| Error::<T, I>::BadMetadata)?;
@@ -1028,7 +1015,7 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			#[pallet::compact] id: T::AssetId,
 		) -> DispatchResult {
-			T::ForceOrigin::ensure_origin(origin)?;
+			T::ForceOrigin::ensure_origin(origin, &id)?;
 
 			let d = Asset::<T, I>::get(id).ok_or(Error::<T, I>::Unknown)?;
 			Metadata::<T, I>::try_mutate_exists(id, |metadata| {
@@ -1073,7 +1060,7 @@ pub mod pallet {
 			is_sufficient: bool,
 			is_frozen: bool,
 		) -> DispatchResult {
-			T::ForceOrigin::ensure_origin(origin)?;
+			T::ForceOrigin::ensure_origin(origin, &id)?;
 
 			Asset::<T, I>::try_mutate(id, |maybe_asset| {
 				let mut asset = maybe_asset.take().ok_or(Error::<T, I>::Unknown)?;
@@ -1205,13 +1192,13 @@ pub mod pallet {
 			delegate: <T::Lookup as StaticLookup>::Source,
 		) -> DispatchResult {
 			let mut d = Asset::<T, I>::get(id).ok_or(Error::<T, I>::Unknown)?;
-			T::ForceOrigin::try_origin(origin)
-				.map(|_| ())
-				.or_else(|origin| -> DispatchResult {
+			T::ForceOrigin::try_origin(origin, &id).map(|_| ()).or_else(
+				|origin| -> DispatchResult {
 					let origin = ensure_signed(origin)?;
 					ensure!(&origin == &d.admin, Error::<T, I>::NoPermission);
 					Ok(())
-				})?;
+				},
+			)?;
 
 			let owner = T::Lookup::lookup(owner)?;
 			let delegate = T::Lookup::lookup(delegate)?;
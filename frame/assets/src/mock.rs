@@ -20,7 +20,7 @@
 use super::*;
 use crate as pallet_assets;
 
-use frame_support::{construct_runtime, parameter_types};
+use frame_support::{construct_runtime, parameter_types, traits::AsEnsureOriginWithArg};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -100,7 +100,8 @@ impl Config for Test {
 	type Balance = u64;
 	type AssetId = u32;
 	type Currency = Balances;
-	type ForceOrigin = frame_system::EnsureRoot<u64>;
+	type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<u64>>;
+	type ForceOrigin = AsEnsureOriginWithArg<frame_system::EnsureRoot<u64>>;
 	type AssetDeposit = AssetDeposit;
 	type MetadataDepositBase = MetadataDepositBase;
 	type MetadataDepositPerByte = MetadataDepositPerByte;
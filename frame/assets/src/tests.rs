@@ -649,6 +649,22 @@ fn imbalances_should_work() {
 	});
 }
 
+#[test]
+fn fungibles_create_should_work() {
+	use frame_support::traits::tokens::fungibles::{Create, Inspect, Mutate};
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(<Assets as Create<u64>>::create(0, 1, true, 5));
+		assert_eq!(Asset::<Test>::get(0).unwrap().admin, 1);
+		assert_eq!(<Assets as Inspect<u64>>::minimum_balance(0), 5);
+		assert_noop!(<Assets as Create<u64>>::create(0, 2, true, 5), Error::<Test>::InUse);
+		assert_noop!(<Assets as Create<u64>>::create(1, 2, true, 0), Error::<Test>::MinBalanceZero);
+
+		assert_ok!(<Assets as Mutate<u64>>::mint_into(0, &2, 100));
+		assert_eq!(Assets::balance(0, 2), 100);
+	});
+}
+
 #[test]
 fn force_metadata_should_work() {
 	new_test_ext().execute_with(|| {
@@ -784,3 +800,17 @@ fn balance_conversion_should_work() {
 		);
 	});
 }
+
+#[test]
+fn ensure_asset_owner_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, true, 1));
+		assert_eq!(EnsureAssetOwner::<Test>::try_origin(Origin::signed(1), &0).ok(), Some(1));
+		assert!(EnsureAssetOwner::<Test>::try_origin(Origin::signed(2), &0).is_err());
+		assert!(EnsureAssetOwner::<Test>::try_origin(Origin::signed(1), &1).is_err());
+		assert!(EnsureAssetOwner::<Test>::try_origin(Origin::root(), &0).is_err());
+
+		assert_ok!(Assets::transfer_ownership(Origin::signed(1), 0, 2));
+		assert_eq!(EnsureAssetOwner::<Test>::try_origin(Origin::signed(2), &0).ok(), Some(2));
+	});
+}
@@ -233,3 +233,24 @@ where
 			.saturating_mul_int(balance))
 	}
 }
+
+/// Ensures the origin is signed by the owner of the given asset.
+pub struct EnsureAssetOwner<T, I = ()>(PhantomData<(T, I)>);
+impl<T: Config<I>, I: 'static> EnsureOriginWithArg<T::Origin, T::AssetId>
+	for EnsureAssetOwner<T, I>
+{
+	type Success = T::AccountId;
+	fn try_origin(o: T::Origin, id: &T::AssetId) -> Result<T::AccountId, T::Origin> {
+		let owner = Asset::<T, I>::get(id).map(|details| details.owner);
+		o.into().and_then(|o| match o {
+			frame_system::RawOrigin::Signed(who) if owner.as_ref() == Some(&who) => Ok(who),
+			r => Err(T::Origin::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin(id: &T::AssetId) -> T::Origin {
+		let owner = Asset::<T, I>::get(id).map(|details| details.owner).unwrap_or_default();
+		T::Origin::from(frame_system::RawOrigin::Signed(owner))
+	}
+}
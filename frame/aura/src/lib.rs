@@ -79,6 +79,13 @@ pub mod pallet {
 		/// Blocks authored by a disabled validator will lead to a panic as part of this module's
 		/// initialization.
 		type DisabledValidators: DisabledValidators;
+
+		/// The maximum drift tolerated between the timestamp inherent and the window of the
+		/// current slot, blocks with a timestamp further away are rejected.
+		///
+		/// With a drift of zero the timestamp must fall within the current slot.
+		#[pallet::constant]
+		type MaxTimestampDrift: Get<Self::Moment>;
 	}
 
 	#[pallet::pallet]
@@ -120,6 +127,13 @@ pub mod pallet {
 	pub(super) type Authorities<T: Config> =
 		StorageValue<_, WeakBoundedVec<T::AuthorityId, T::MaxAuthorities>, ValueQuery>;
 
+	/// The authority set queued for the next session.
+	///
+	/// Only set by session changes, see [`Pallet::next_authorities`].
+	#[pallet::storage]
+	pub(super) type NextAuthorities<T: Config> =
+		StorageValue<_, WeakBoundedVec<T::AuthorityId, T::MaxAuthorities>, OptionQuery>;
+
 	/// The current slot of this block.
 	///
 	/// This will be set in `on_initialize`.
@@ -158,6 +172,13 @@ impl<T: Config> Pallet<T> {
 		<frame_system::Pallet<T>>::deposit_log(log.into());
 	}
 
+	/// The authority set that will become active at the next session change.
+	///
+	/// Falls back to the current authority set if no session change queued one yet.
+	pub fn next_authorities() -> WeakBoundedVec<T::AuthorityId, T::MaxAuthorities> {
+		<NextAuthorities<T>>::get().unwrap_or_else(Self::authorities)
+	}
+
 	fn initialize_authorities(authorities: &[T::AuthorityId]) {
 		if !authorities.is_empty() {
 			assert!(<Authorities<T>>::get().is_empty(), "Authorities are already initialized!");
@@ -203,7 +224,7 @@ impl<T: Config> OneSessionHandler<T::AccountId> for Pallet<T> {
 		Self::initialize_authorities(&authorities);
 	}
 
-	fn on_new_session<'a, I: 'a>(changed: bool, validators: I, _queued_validators: I)
+	fn on_new_session<'a, I: 'a>(changed: bool, validators: I, queued_validators: I)
 	where
 		I: Iterator<Item = (&'a T::AccountId, T::AuthorityId)>,
 	{
@@ -219,6 +240,12 @@ impl<T: Config> OneSessionHandler<T::AccountId> for Pallet<T> {
 				Self::change_authorities(bounded);
 			}
 		}
+
+		let queued_authorities = queued_validators.map(|(_, k)| k).collect::<Vec<_>>();
+		<NextAuthorities<T>>::put(<WeakBoundedVec<_, T::MaxAuthorities>>::force_from(
+			queued_authorities,
+			Some("AuRa queued session"),
+		));
 	}
 
 	fn on_disabled(i: usize) {
@@ -281,12 +308,17 @@ impl<T: Config> OnTimestampSet<T::Moment> for Pallet<T> {
 		let slot_duration = Self::slot_duration();
 		assert!(!slot_duration.is_zero(), "Aura slot duration cannot be zero.");
 
-		let timestamp_slot = moment / slot_duration;
-		let timestamp_slot = Slot::from(timestamp_slot.saturated_into::<u64>());
+		// the timestamp may fall outside of the window of the current slot by at most
+		// `MaxTimestampDrift` on either side.
+		let current_slot: T::Moment = (*CurrentSlot::<T>::get()).saturated_into();
+		let slot_start = current_slot.saturating_mul(slot_duration);
+		let slot_end = slot_start.saturating_add(slot_duration);
+		let max_drift = T::MaxTimestampDrift::get();
 
 		assert!(
-			CurrentSlot::<T>::get() == timestamp_slot,
-			"Timestamp slot must match `CurrentSlot`"
+			moment.saturating_add(max_drift) >= slot_start &&
+				moment < slot_end.saturating_add(max_drift),
+			"Timestamp must be within the window of `CurrentSlot`"
 		);
 	}
 }
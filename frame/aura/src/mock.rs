@@ -89,6 +89,7 @@ impl pallet_timestamp::Config for Test {
 
 parameter_types! {
 	pub const MaxAuthorities: u32 = 10;
+	pub const MaxTimestampDrift: u64 = 1;
 }
 
 thread_local! {
@@ -118,6 +119,7 @@ impl pallet_aura::Config for Test {
 	type AuthorityId = AuthorityId;
 	type DisabledValidators = MockDisabledValidators;
 	type MaxAuthorities = MaxAuthorities;
+	type MaxTimestampDrift = MaxTimestampDrift;
 }
 
 pub fn new_test_ext(authorities: Vec<u64>) -> sp_io::TestExternalities {
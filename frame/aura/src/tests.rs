@@ -19,12 +19,15 @@
 
 #![cfg(test)]
 
-use crate::mock::{new_test_ext, Aura, MockDisabledValidators, System};
+use crate::{
+	mock::{new_test_ext, Aura, MockDisabledValidators, System},
+	CurrentSlot,
+};
 use codec::Encode;
-use frame_support::traits::OnInitialize;
+use frame_support::traits::{OnInitialize, OnTimestampSet, OneSessionHandler};
 use frame_system::InitKind;
-use sp_consensus_aura::{Slot, AURA_ENGINE_ID};
-use sp_runtime::{Digest, DigestItem};
+use sp_consensus_aura::{ed25519::AuthorityId, Slot, AURA_ENGINE_ID};
+use sp_runtime::{testing::UintAuthorityId, Digest, DigestItem};
 
 #[test]
 fn initial_values() {
@@ -34,6 +37,25 @@ fn initial_values() {
 	});
 }
 
+#[test]
+fn next_authorities_follow_queued_validators() {
+	new_test_ext(vec![0, 1, 2, 3]).execute_with(|| {
+		// without a queued set the current one is reported.
+		assert_eq!(Aura::next_authorities(), Aura::authorities());
+
+		let key = |a: u64| UintAuthorityId(a).to_public_key::<AuthorityId>();
+		let validators = vec![(1u64, key(0)), (2, key(1))];
+		let queued = vec![(1u64, key(0)), (2, key(4))];
+		fn to_item((a, k): &(u64, AuthorityId)) -> (&u64, AuthorityId) {
+			(a, k.clone())
+		}
+		Aura::on_new_session(false, validators.iter().map(to_item), queued.iter().map(to_item));
+
+		assert_eq!(Aura::authorities().len(), 4);
+		assert_eq!(Aura::next_authorities().into_inner(), vec![key(0), key(4)]);
+	});
+}
+
 #[test]
 #[should_panic(
 	expected = "Validator with index 1 is disabled and should not be attempting to author blocks."
@@ -54,3 +76,33 @@ fn disabled_validators_cannot_author_blocks() {
 		Aura::on_initialize(42);
 	});
 }
+
+#[test]
+fn timestamp_within_drift_of_slot_window_is_accepted() {
+	new_test_ext(vec![0, 1, 2, 3]).execute_with(|| {
+		// the window of slot 5 is [10, 12), extended by a drift of 1 on each side.
+		CurrentSlot::<crate::mock::Test>::put(Slot::from(5));
+
+		for moment in 9..13 {
+			Aura::on_timestamp_set(moment);
+		}
+	});
+}
+
+#[test]
+#[should_panic(expected = "Timestamp must be within the window of `CurrentSlot`")]
+fn timestamp_too_early_for_slot_is_rejected() {
+	new_test_ext(vec![0, 1, 2, 3]).execute_with(|| {
+		CurrentSlot::<crate::mock::Test>::put(Slot::from(5));
+		Aura::on_timestamp_set(8);
+	});
+}
+
+#[test]
+#[should_panic(expected = "Timestamp must be within the window of `CurrentSlot`")]
+fn timestamp_too_late_for_slot_is_rejected() {
+	new_test_ext(vec![0, 1, 2, 3]).execute_with(|| {
+		CurrentSlot::<crate::mock::Test>::put(Slot::from(5));
+		Aura::on_timestamp_set(13);
+	});
+}
@@ -226,6 +226,7 @@ mod tests {
 	impl pallet_session::historical::Config for Test {
 		type FullIdentification = ();
 		type FullIdentificationOf = ();
+		type LegacyFullIdentification = ();
 	}
 
 	pub type BlockNumber = u64;
@@ -132,6 +132,13 @@ pub mod pallet {
 		#[pallet::constant]
 		type ExpectedBlockTime: Get<Self::Moment>;
 
+		/// The maximum drift tolerated between the timestamp inherent and the window of the
+		/// current slot, blocks with a timestamp further away are rejected.
+		///
+		/// With a drift of zero the timestamp must fall within the current slot.
+		#[pallet::constant]
+		type MaxTimestampDrift: Get<Self::Moment>;
+
 		/// BABE requires some logic to be triggered on every block to query for whether an epoch
 		/// has ended and to perform the transition to the next epoch.
 		///
@@ -179,6 +186,8 @@ pub mod pallet {
 		InvalidKeyOwnershipProof,
 		/// A given equivocation report is valid but already previously reported.
 		DuplicateOffenceReport,
+		/// The planned epoch configuration is invalid.
+		InvalidConfiguration,
 	}
 
 	/// Current epoch index.
@@ -387,12 +396,18 @@ pub mod pallet {
 		/// the next call to `enact_epoch_change`. The config will be activated one epoch after.
 		/// Multiple calls to this method will replace any existing planned config change that had
 		/// not been enacted yet.
+		///
+		/// The value of `c` must be in `(0, 1]`.
 		#[pallet::weight(<T as Config>::WeightInfo::plan_config_change())]
 		pub fn plan_config_change(
 			origin: OriginFor<T>,
 			config: NextConfigDescriptor,
 		) -> DispatchResult {
 			ensure_root(origin)?;
+			ensure!(
+				BabeEpochConfiguration::from(config.clone()).is_valid(),
+				Error::<T>::InvalidConfiguration,
+			);
 			PendingEpochConfigChange::<T>::put(config);
 			Ok(())
 		}
@@ -818,12 +833,17 @@ impl<T: Config> OnTimestampSet<T::Moment> for Pallet<T> {
 		let slot_duration = Self::slot_duration();
 		assert!(!slot_duration.is_zero(), "Babe slot duration cannot be zero.");
 
-		let timestamp_slot = moment / slot_duration;
-		let timestamp_slot = Slot::from(timestamp_slot.saturated_into::<u64>());
+		// the timestamp may fall outside of the window of the current slot by at most
+		// `MaxTimestampDrift` on either side.
+		let current_slot: T::Moment = (*CurrentSlot::<T>::get()).saturated_into();
+		let slot_start = current_slot.saturating_mul(slot_duration);
+		let slot_end = slot_start.saturating_add(slot_duration);
+		let max_drift = T::MaxTimestampDrift::get();
 
 		assert!(
-			CurrentSlot::<T>::get() == timestamp_slot,
-			"Timestamp slot must match `CurrentSlot`"
+			moment.saturating_add(max_drift) >= slot_start &&
+				moment < slot_end.saturating_add(max_drift),
+			"Timestamp must be within the window of `CurrentSlot`"
 		);
 	}
 }
@@ -20,7 +20,7 @@
 use super::{Call, *};
 use frame_support::{
 	assert_err, assert_noop, assert_ok,
-	traits::{Currency, EstimateNextSessionRotation, OnFinalize},
+	traits::{Currency, EstimateNextSessionRotation, OnFinalize, OnTimestampSet},
 	weights::{GetDispatchInfo, Pays},
 };
 use mock::*;
@@ -314,6 +314,26 @@ fn only_root_can_enact_config_change() {
 	});
 }
 
+#[test]
+fn invalid_config_change_is_rejected() {
+	new_test_ext(1).execute_with(|| {
+		for c in [(0, 4), (5, 4), (1, 0)] {
+			assert_noop!(
+				Babe::plan_config_change(
+					Origin::root(),
+					NextConfigDescriptor::V1 { c, allowed_slots: AllowedSlots::PrimarySlots },
+				),
+				Error::<Test>::InvalidConfiguration,
+			);
+		}
+
+		assert_ok!(Babe::plan_config_change(
+			Origin::root(),
+			NextConfigDescriptor::V1 { c: (4, 4), allowed_slots: AllowedSlots::PrimarySlots },
+		));
+	});
+}
+
 #[test]
 fn can_fetch_current_and_next_epoch_data() {
 	new_test_ext(5).execute_with(|| {
@@ -903,3 +923,33 @@ fn add_epoch_configurations_migration_works() {
 		assert_eq!(PendingEpochConfigChange::<Test>::get(), Some(next_config_descriptor));
 	});
 }
+
+#[test]
+fn timestamp_within_drift_of_slot_window_is_accepted() {
+	new_test_ext(1).execute_with(|| {
+		// the window of slot 5 is [10, 12), extended by a drift of 1 on each side.
+		CurrentSlot::<Test>::put(Slot::from(5));
+
+		for moment in 9..13 {
+			Babe::on_timestamp_set(moment);
+		}
+	});
+}
+
+#[test]
+#[should_panic(expected = "Timestamp must be within the window of `CurrentSlot`")]
+fn timestamp_too_early_for_slot_is_rejected() {
+	new_test_ext(1).execute_with(|| {
+		CurrentSlot::<Test>::put(Slot::from(5));
+		Babe::on_timestamp_set(8);
+	});
+}
+
+#[test]
+#[should_panic(expected = "Timestamp must be within the window of `CurrentSlot`")]
+fn timestamp_too_late_for_slot_is_rejected() {
+	new_test_ext(1).execute_with(|| {
+		CurrentSlot::<Test>::put(Slot::from(5));
+		Babe::on_timestamp_set(13);
+	});
+}
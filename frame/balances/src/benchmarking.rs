@@ -215,6 +215,25 @@ benchmarks_instance_pallet! {
 		assert!(Balances::<T, I>::reserved_balance(&user).is_zero());
 		assert_eq!(Balances::<T, I>::free_balance(&user), balance);
 	}
+
+	upgrade_accounts {
+		let caller: T::AccountId = whitelisted_caller();
+		let u in 1 .. 1_000;
+		let who = (0 .. u).map(|i| -> T::AccountId {
+			let user = account("old_user", i, SEED);
+			let balance = T::ExistentialDeposit::get().saturating_mul(ED_MULTIPLIER.into());
+			let _ = <Balances<T, I> as Currency<_>>::make_free_balance_be(&user, balance);
+			// Give the account a lock, so that the upgrade has to re-derive the frozen balances.
+			<Balances<T, I> as LockableCurrency<_>>::set_lock(*b"old_lock", &user, balance, WithdrawReasons::all());
+			AccountVersions::<T, I>::remove(&user);
+			user
+		}).collect::<Vec<_>>();
+	}: _(RawOrigin::Signed(caller), who.clone())
+	verify {
+		for i in who {
+			assert_eq!(Balances::<T, I>::account_version(&i), AccountDataVersion::LATEST);
+		}
+	}
 }
 
 impl_benchmark_test_suite!(
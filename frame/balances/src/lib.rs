@@ -184,7 +184,7 @@ use sp_runtime::{
 		AtLeast32BitUnsigned, Bounded, CheckedAdd, CheckedSub, MaybeSerializeDeserialize,
 		Saturating, StaticLookup, Zero,
 	},
-	ArithmeticError, DispatchError, RuntimeDebug,
+	ArithmeticError, DispatchError, Perbill, RuntimeDebug,
 };
 use sp_std::{cmp, fmt::Debug, mem, ops::BitOr, prelude::*, result};
 pub use weights::WeightInfo;
@@ -274,6 +274,7 @@ pub mod pallet {
 		/// - Base Weight: 73.64 µs, worst case scenario (account created, account removed)
 		/// - DB Weight: 1 Read and 1 Write to destination account
 		/// - Origin account is already in memory, so no DB operations for them.
+		/// NOTE: The weight of removing the origin account is refunded if it stays alive.
 		/// # </weight>
 		#[pallet::weight(T::WeightInfo::transfer())]
 		pub fn transfer(
@@ -289,6 +290,11 @@ pub mod pallet {
 				value,
 				ExistenceRequirement::AllowDeath,
 			)?;
+
+			// The origin account was not removed, which costs as much as `transfer_keep_alive`.
+			if !<Self as Currency<_>>::total_balance(&transactor).is_zero() {
+				return Ok(Some(T::WeightInfo::transfer_keep_alive()).into())
+			}
 			Ok(().into())
 		}
 
@@ -309,6 +315,7 @@ pub mod pallet {
 		///     - Creating: 27.56 µs
 		///     - Killing: 35.11 µs
 		/// - DB Weight: 1 Read, 1 Write to `who`
+		/// NOTE: Weight annotation is the more expensive of both cases, we refund otherwise.
 		/// # </weight>
 		#[pallet::weight(
 			T::WeightInfo::set_balance_creating() // Creates a new account.
@@ -347,7 +354,13 @@ pub mod pallet {
 				(account.free, account.reserved)
 			})?;
 			Self::deposit_event(Event::BalanceSet(who, free, reserved));
-			Ok(().into())
+
+			let actual_weight = if wipeout {
+				T::WeightInfo::set_balance_killing()
+			} else {
+				T::WeightInfo::set_balance_creating()
+			};
+			Ok(Some(actual_weight).into())
 		}
 
 		/// Exactly as `transfer`, except the origin must be root and the source account may be
@@ -435,6 +448,34 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Upgrade the data of the specified accounts to the latest layout.
+		///
+		/// Accounts are otherwise upgraded lazily when they are next mutated, so this allows the
+		/// upgrade of a large number of accounts to be spread over many blocks.
+		///
+		/// The dispatch origin of this call must be Signed, by any account.
+		///
+		/// - `who`: The accounts to be upgraded.
+		///
+		/// The transaction fee is waived if at least 90% of the given accounts needed an upgrade.
+		#[pallet::weight(T::WeightInfo::upgrade_accounts(who.len() as u32))]
+		pub fn upgrade_accounts(
+			origin: OriginFor<T>,
+			who: Vec<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			if who.is_empty() {
+				return Ok(Pays::Yes.into())
+			}
+			let upgraded = who.iter().filter(|who| Self::ensure_upgraded(who)).count() as u32;
+			let proportion_upgraded = Perbill::from_rational(upgraded, who.len() as u32);
+			if proportion_upgraded >= Perbill::from_percent(90) {
+				Ok(Pays::No.into())
+			} else {
+				Ok(Pays::Yes.into())
+			}
+		}
+
 		/// Unreserve some balance from a user by force.
 		///
 		/// Can only be called by ROOT.
@@ -473,6 +514,11 @@ pub mod pallet {
 		/// Final argument indicates the destination balance type.
 		/// \[from, to, balance, destination_status\]
 		ReserveRepatriated(T::AccountId, T::AccountId, T::Balance, Status),
+		/// Dust removed from an account was credited to a beneficiary instead of being lost.
+		/// \[beneficiary, amount\]
+		DustDeposited(T::AccountId, T::Balance),
+		/// An account's data was upgraded to the latest layout. \[who\]
+		Upgraded(T::AccountId),
 	}
 
 	/// Old name generated by `decl_event`.
@@ -543,6 +589,14 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// The layout version of each account's data.
+	///
+	/// Accounts without an entry were written before the version was tracked, and are treated
+	/// as `AccountDataVersion::V0`.
+	#[pallet::storage]
+	pub type AccountVersions<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, AccountDataVersion>;
+
 	/// Storage version of the pallet.
 	///
 	/// This is set to v2.0.0 for new networks.
@@ -593,6 +647,7 @@ pub mod pallet {
 			for &(ref who, free) in self.balances.iter() {
 				assert!(T::AccountStore::insert(who, AccountData { free, ..Default::default() })
 					.is_ok());
+				AccountVersions::<T, I>::insert(who, AccountDataVersion::LATEST);
 			}
 		}
 	}
@@ -669,6 +724,37 @@ pub struct ReserveData<ReserveIdentifier, Balance> {
 	pub amount: Balance,
 }
 
+/// The layout version of an account's `AccountData`, as recorded in `AccountVersions`.
+///
+/// The version is kept out of `AccountData` itself, so that its encoding does not change. Accounts
+/// are upgraded to the latest version lazily, the first time they are mutated, or explicitly
+/// through `upgrade_accounts`.
+#[derive(
+	Encode,
+	Decode,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	RuntimeDebug,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub enum AccountDataVersion {
+	/// Accounts written before the version was tracked. The frozen balances may be out of sync
+	/// with the account's `Locks`.
+	V0,
+	/// The frozen balances are guaranteed to be derived from the account's `Locks`.
+	V1,
+}
+
+impl AccountDataVersion {
+	/// The version all accounts are upgraded to.
+	pub const LATEST: Self = Self::V1;
+}
+
 /// All balance information for an account.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 pub struct AccountData<Balance> {
@@ -742,6 +828,28 @@ impl<T: Config<I>, I: 'static> Drop for DustCleaner<T, I> {
 	}
 }
 
+/// A `DustRemoval` handler which credits all removed dust to the account returned by
+/// `Beneficiary`, rather than letting it reduce the total issuance.
+///
+/// If the dust cannot be deposited (e.g. because the beneficiary does not exist and the dust is
+/// below the existential deposit), it is burned as it would be without a handler.
+pub struct DustToBeneficiary<T, I, Beneficiary>(sp_std::marker::PhantomData<(T, I, Beneficiary)>);
+
+impl<T: Config<I>, I: 'static, Beneficiary: Get<T::AccountId>> OnUnbalanced<NegativeImbalance<T, I>>
+	for DustToBeneficiary<T, I, Beneficiary>
+{
+	fn on_nonzero_unbalanced(dust: NegativeImbalance<T, I>) {
+		let beneficiary = Beneficiary::get();
+		let credit = <Pallet<T, I> as Currency<_>>::deposit_creating(&beneficiary, dust.peek());
+		let deposited = credit.peek();
+		// Whatever could not be credited is burned when the remaining imbalance is dropped.
+		drop(dust.offset(credit));
+		if !deposited.is_zero() {
+			Pallet::<T, I>::deposit_event(Event::DustDeposited(beneficiary, deposited));
+		}
+	}
+}
+
 impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// Get the free balance of an account.
 	pub fn free_balance(who: impl sp_std::borrow::Borrow<T::AccountId>) -> T::Balance {
@@ -770,6 +878,59 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		T::AccountStore::get(&who)
 	}
 
+	/// Get the layout version of the data of `who`.
+	pub fn account_version(who: &T::AccountId) -> AccountDataVersion {
+		AccountVersions::<T, I>::get(who).unwrap_or(AccountDataVersion::V0)
+	}
+
+	/// Upgrade the data of `who` to the latest layout, if it is not already.
+	///
+	/// Returns `true` if an upgrade was performed. Accounts that do not exist are left untouched.
+	pub fn ensure_upgraded(who: &T::AccountId) -> bool {
+		if Self::account_version(who) >= AccountDataVersion::LATEST {
+			return false
+		}
+		let res = T::AccountStore::try_mutate_exists(who, |maybe_account| -> DispatchResult {
+			match maybe_account {
+				Some(account) => {
+					Self::upgrade_account_data(who, account);
+					Ok(())
+				},
+				None => Err(DispatchError::CannotLookup),
+			}
+		});
+		if res.is_err() {
+			return false
+		}
+		AccountVersions::<T, I>::insert(who, AccountDataVersion::LATEST);
+		Self::deposit_event(Event::Upgraded(who.clone()));
+		true
+	}
+
+	/// Bring `account`, the data of `who`, from `AccountDataVersion::V0` to the latest layout
+	/// in place. The caller is responsible for recording the new version.
+	fn upgrade_account_data(who: &T::AccountId, account: &mut AccountData<T::Balance>) {
+		// V0 -> V1: re-derive the frozen balances from the locks.
+		let (misc_frozen, fee_frozen) = Self::frozen_balances(&Self::locks(who));
+		account.misc_frozen = misc_frozen;
+		account.fee_frozen = fee_frozen;
+	}
+
+	/// The `(misc_frozen, fee_frozen)` balances implied by `locks`.
+	fn frozen_balances(locks: &[BalanceLock<T::Balance>]) -> (T::Balance, T::Balance) {
+		let mut misc_frozen = T::Balance::zero();
+		let mut fee_frozen = T::Balance::zero();
+		for l in locks.iter() {
+			if l.reasons == Reasons::All || l.reasons == Reasons::Misc {
+				misc_frozen = misc_frozen.max(l.amount);
+			}
+			if l.reasons == Reasons::All || l.reasons == Reasons::Fee {
+				fee_frozen = fee_frozen.max(l.amount);
+			}
+		}
+		(misc_frozen, fee_frozen)
+	}
+
 	/// Handles any steps needed after mutating an account.
 	///
 	/// This includes DustRemoval unbalancing, in the case than the `new` account's total balance
@@ -924,14 +1085,28 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		let result = T::AccountStore::try_mutate_exists(who, |maybe_account| {
 			let is_new = maybe_account.is_none();
 			let mut account = maybe_account.take().unwrap_or_default();
+			let upgraded = !is_new && Self::account_version(who) < AccountDataVersion::LATEST;
+			if upgraded {
+				Self::upgrade_account_data(who, &mut account);
+			}
 			f(&mut account, is_new).map(move |result| {
 				let maybe_endowed = if is_new { Some(account.free) } else { None };
 				let maybe_account_maybe_dust = Self::post_mutation(who, account);
 				*maybe_account = maybe_account_maybe_dust.0;
-				(maybe_endowed, maybe_account_maybe_dust.1, result)
+				let exists = maybe_account.is_some();
+				(exists, upgraded, maybe_endowed, maybe_account_maybe_dust.1, result)
 			})
 		});
-		result.map(|(maybe_endowed, maybe_dust, result)| {
+		result.map(|(exists, upgraded, maybe_endowed, maybe_dust, result)| {
+			let is_new = maybe_endowed.is_some();
+			if exists && (is_new || upgraded) {
+				AccountVersions::<T, I>::insert(who, AccountDataVersion::LATEST);
+			} else if !exists && !is_new {
+				AccountVersions::<T, I>::remove(who);
+			}
+			if upgraded {
+				Self::deposit_event(Event::Upgraded(who.clone()));
+			}
 			if let Some(endowed) = maybe_endowed {
 				Self::deposit_event(Event::Endowed(who.clone(), endowed));
 			}
@@ -955,17 +1130,10 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			);
 		}
 		// No way this can fail since we do not alter the existential balances.
+		let (misc_frozen, fee_frozen) = Self::frozen_balances(locks);
 		let res = Self::mutate_account(who, |b| {
-			b.misc_frozen = Zero::zero();
-			b.fee_frozen = Zero::zero();
-			for l in locks.iter() {
-				if l.reasons == Reasons::All || l.reasons == Reasons::Misc {
-					b.misc_frozen = b.misc_frozen.max(l.amount);
-				}
-				if l.reasons == Reasons::All || l.reasons == Reasons::Fee {
-					b.fee_frozen = b.fee_frozen.max(l.amount);
-				}
-			}
+			b.misc_frozen = misc_frozen;
+			b.fee_frozen = fee_frozen;
 		});
 		debug_assert!(res.is_ok());
 
@@ -1519,8 +1687,11 @@ where
 			},
 		)?;
 
-		// Emit transfer event.
-		Self::deposit_event(Event::Transfer(transactor.clone(), dest.clone(), value));
+		// Emit transfer event, indexed under the topics of both accounts.
+		system::Pallet::<T>::deposit_event_for_accounts(
+			&[transactor, dest],
+			<T as Config<I>>::Event::from(Event::Transfer(transactor.clone(), dest.clone(), value)),
+		);
 
 		Ok(())
 	}
@@ -613,6 +613,27 @@ macro_rules! decl_tests {
 			});
 		}
 
+		#[test]
+		fn transfer_event_is_indexed_under_both_accounts() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				System::set_block_number(1);
+				assert_ok!(Balances::transfer(Some(1).into(), 2, 5));
+
+				let event_index = System::event_count() - 1;
+				for who in &[1, 2] {
+					assert_eq!(
+						System::event_topics(System::account_topic(who)),
+						vec![(1, event_index)],
+					);
+				}
+				assert!(System::event_topics(System::account_topic(&3)).is_empty());
+				assert_eq!(
+					events().last(),
+					Some(&Event::Balances(crate::Event::Transfer(1, 2, 5))),
+				);
+			});
+		}
+
 		#[test]
 		fn transfer_keep_alive_works() {
 			<$ext_builder>::default().existential_deposit(1).build().execute_with(|| {
@@ -626,6 +647,35 @@ macro_rules! decl_tests {
 			});
 		}
 
+		#[test]
+		fn transfer_refunds_weight_if_origin_is_kept_alive() {
+			<$ext_builder>::default().existential_deposit(1).build().execute_with(|| {
+				let weight = <$test as Config>::WeightInfo::transfer_keep_alive();
+				let _ = Balances::deposit_creating(&1, 100);
+
+				let post_info = Balances::transfer(Some(1).into(), 2, 50).unwrap();
+				assert_eq!(post_info.actual_weight, Some(weight));
+
+				// Transferring the whole balance removes the origin account.
+				let post_info = Balances::transfer(Some(1).into(), 2, 50).unwrap();
+				assert_eq!(post_info.actual_weight, None);
+			});
+		}
+
+		#[test]
+		fn set_balance_is_weighed_by_outcome() {
+			<$ext_builder>::default().existential_deposit(1).build().execute_with(|| {
+				let creating = <$test as Config>::WeightInfo::set_balance_creating();
+				let killing = <$test as Config>::WeightInfo::set_balance_killing();
+
+				let post_info = Balances::set_balance(RawOrigin::Root.into(), 1, 100, 0).unwrap();
+				assert_eq!(post_info.actual_weight, Some(creating));
+
+				let post_info = Balances::set_balance(RawOrigin::Root.into(), 1, 0, 0).unwrap();
+				assert_eq!(post_info.actual_weight, Some(killing));
+			});
+		}
+
 		#[test]
 		#[should_panic = "the balance of any account should always be at least the existential deposit."]
 		fn cannot_set_genesis_value_below_ed() {
@@ -1221,5 +1271,62 @@ macro_rules! decl_tests {
 				assert_eq!(Balances::free_balance(&3), 25);
 			});
 		}
+
+		#[test]
+		fn account_version_is_tracked() {
+			<$ext_builder>::default().existential_deposit(2).build().execute_with(|| {
+				// Accounts without a recorded version predate it.
+				assert_eq!(Balances::account_version(&1), AccountDataVersion::V0);
+
+				// New accounts are created at the latest version.
+				let _ = Balances::deposit_creating(&1, 10);
+				assert_eq!(Balances::account_version(&1), AccountDataVersion::LATEST);
+
+				// Reaped accounts no longer have a version.
+				assert_ok!(Balances::transfer(Some(1).into(), 2, 10));
+				assert!(!AccountVersions::<$test>::contains_key(&1));
+				assert_eq!(Balances::account_version(&2), AccountDataVersion::LATEST);
+
+				// Accounts which do not exist are not upgraded.
+				assert!(!Balances::ensure_upgraded(&3));
+				assert!(!AccountVersions::<$test>::contains_key(&3));
+			});
+		}
+
+		#[test]
+		fn old_account_data_is_upgraded() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				Balances::set_lock(ID_1, &1, 9, WithdrawReasons::all());
+				Balances::set_lock(ID_1, &2, 5, WithdrawReasons::TRANSACTION_PAYMENT);
+				// Simulate accounts written by an old version, with stale frozen balances.
+				for who in [1, 2] {
+					assert_ok!(<$test as Config>::AccountStore::mutate(&who, |a| {
+						a.misc_frozen = 0;
+						a.fee_frozen = 0;
+					}));
+					AccountVersions::<$test>::remove(&who);
+				}
+				System::reset_events();
+
+				// Any mutation lazily upgrades the account first.
+				let _ = Balances::deposit_creating(&1, 1);
+				assert_eq!(Balances::account_version(&1), AccountDataVersion::LATEST);
+				assert_eq!(Balances::account(&1).misc_frozen, 9);
+				assert_eq!(Balances::account(&1).fee_frozen, 9);
+				assert_eq!(events(), [Event::Balances(crate::Event::Upgraded(1))]);
+
+				// Accounts can be upgraded explicitly, for free if most of them needed it.
+				let info = Balances::upgrade_accounts(Some(3).into(), vec![2]).unwrap();
+				assert_eq!(info.pays_fee, frame_support::weights::Pays::No);
+				assert_eq!(Balances::account_version(&2), AccountDataVersion::LATEST);
+				assert_eq!(Balances::account(&2).misc_frozen, 0);
+				assert_eq!(Balances::account(&2).fee_frozen, 5);
+				assert_eq!(events(), [Event::Balances(crate::Event::Upgraded(2))]);
+
+				let info = Balances::upgrade_accounts(Some(3).into(), vec![1, 2]).unwrap();
+				assert_eq!(info.pays_fee, frame_support::weights::Pays::Yes);
+				assert!(events().is_empty());
+			});
+		}
 	}
 }
@@ -243,3 +243,21 @@ fn repatriating_reserved_balance_dust_removal_should_work() {
 		System::assert_last_event(Event::Balances(crate::Event::DustLost(2, 50)));
 	});
 }
+
+#[test]
+fn dust_to_beneficiary_should_work() {
+	parameter_types! {
+		pub const DustBeneficiary: u64 = 1;
+	}
+	type Handler = crate::DustToBeneficiary<Test, (), DustBeneficiary>;
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		assert_ok!(Balances::set_balance(RawOrigin::Root.into(), 1, 1000, 0));
+		let issuance = Balances::total_issuance();
+
+		Handler::on_unbalanced(NegativeImbalance::new(50));
+		assert_eq!(Balances::free_balance(&1), 1050);
+		assert_eq!(Balances::total_issuance(), issuance);
+		System::assert_last_event(Event::Balances(crate::Event::DustDeposited(1, 50)));
+	});
+}
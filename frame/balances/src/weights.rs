@@ -52,97 +52,154 @@ pub trait WeightInfo {
 	fn force_transfer() -> Weight;
 	fn transfer_all() -> Weight;
 	fn force_unreserve() -> Weight;
+	fn upgrade_accounts(u: u32, ) -> Weight;
 }
 
 /// Weights for pallet_balances using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// The event topic and account version accesses are added by hand to the reference measurement.
 	// Storage: System Account (r:1 w:1)
+	// Storage: System EventTopics (r:2 w:2)
+	// Storage: Balances AccountVersions (r:1 w:2)
 	fn transfer() -> Weight {
 		(70_952_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
 	}
+	// The event topic and account version accesses are added by hand to the reference measurement.
 	// Storage: System Account (r:1 w:1)
+	// Storage: System EventTopics (r:2 w:2)
+	// Storage: Balances AccountVersions (r:1 w:1)
 	fn transfer_keep_alive() -> Weight {
 		(54_410_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
+	// The account version accesses are added by hand to the reference measurement.
 	// Storage: System Account (r:1 w:1)
+	// Storage: Balances AccountVersions (r:1 w:0)
 	fn set_balance_creating() -> Weight {
 		(29_176_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// The account version accesses are added by hand to the reference measurement.
 	// Storage: System Account (r:1 w:1)
+	// Storage: Balances AccountVersions (r:1 w:1)
 	fn set_balance_killing() -> Weight {
 		(35_214_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
+	// The event topic and account version accesses are added by hand to the reference measurement.
 	// Storage: System Account (r:2 w:2)
+	// Storage: System EventTopics (r:2 w:2)
+	// Storage: Balances AccountVersions (r:1 w:2)
 	fn force_transfer() -> Weight {
 		(71_780_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
 	}
+	// The event topic and account version accesses are added by hand to the reference measurement.
 	// Storage: System Account (r:1 w:1)
+	// Storage: System EventTopics (r:2 w:2)
+	// Storage: Balances AccountVersions (r:1 w:2)
 	fn transfer_all() -> Weight {
 		(66_475_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
 	}
+	// The account version accesses are added by hand to the reference measurement.
 	// Storage: System Account (r:1 w:1)
+	// Storage: Balances AccountVersions (r:1 w:0)
 	fn force_unreserve() -> Weight {
 		(27_766_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// Storage: Balances AccountVersions (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:0)
+	fn upgrade_accounts(u: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 531_000
+			.saturating_add((44_700_000 as Weight).saturating_mul(u as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(u as Weight)))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
+	// The event topic and account version accesses are added by hand to the reference measurement.
 	// Storage: System Account (r:1 w:1)
+	// Storage: System EventTopics (r:2 w:2)
+	// Storage: Balances AccountVersions (r:1 w:2)
 	fn transfer() -> Weight {
 		(70_952_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
 	}
+	// The event topic and account version accesses are added by hand to the reference measurement.
 	// Storage: System Account (r:1 w:1)
+	// Storage: System EventTopics (r:2 w:2)
+	// Storage: Balances AccountVersions (r:1 w:1)
 	fn transfer_keep_alive() -> Weight {
 		(54_410_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
+	// The account version accesses are added by hand to the reference measurement.
 	// Storage: System Account (r:1 w:1)
+	// Storage: Balances AccountVersions (r:1 w:0)
 	fn set_balance_creating() -> Weight {
 		(29_176_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	// The account version accesses are added by hand to the reference measurement.
 	// Storage: System Account (r:1 w:1)
+	// Storage: Balances AccountVersions (r:1 w:1)
 	fn set_balance_killing() -> Weight {
 		(35_214_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
+	// The event topic and account version accesses are added by hand to the reference measurement.
 	// Storage: System Account (r:2 w:2)
+	// Storage: System EventTopics (r:2 w:2)
+	// Storage: Balances AccountVersions (r:1 w:2)
 	fn force_transfer() -> Weight {
 		(71_780_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
 	}
+	// The event topic and account version accesses are added by hand to the reference measurement.
 	// Storage: System Account (r:1 w:1)
+	// Storage: System EventTopics (r:2 w:2)
+	// Storage: Balances AccountVersions (r:1 w:2)
 	fn transfer_all() -> Weight {
 		(66_475_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
 	}
+	// The account version accesses are added by hand to the reference measurement.
 	// Storage: System Account (r:1 w:1)
+	// Storage: Balances AccountVersions (r:1 w:0)
 	fn force_unreserve() -> Weight {
 		(27_766_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	// Storage: Balances AccountVersions (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:0)
+	fn upgrade_accounts(u: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 531_000
+			.saturating_add((44_700_000 as Weight).saturating_mul(u as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(u as Weight)))
+	}
 }
@@ -25,7 +25,14 @@ mod analysis;
 mod tests;
 #[cfg(test)]
 mod tests_instance;
+#[cfg(test)]
+mod tests_v2;
 mod utils;
+pub mod v2;
+
+// Allows the `#[benchmarks]` attribute to be tested in this crate.
+#[cfg(test)]
+extern crate self as frame_benchmarking;
 
 #[cfg(feature = "std")]
 pub use analysis::{Analysis, AnalysisChoice, BenchmarkSelector, RegressionModel};
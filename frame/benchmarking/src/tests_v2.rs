@@ -0,0 +1,198 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the `#[benchmarks]` attribute.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::parameter_types;
+use sp_runtime::{
+	testing::{Header, H256},
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage,
+};
+use sp_std::prelude::*;
+
+#[frame_support::pallet]
+mod pallet_test {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type MaxValue: Get<u32>;
+	}
+
+	#[pallet::storage]
+	pub(crate) type Value<T: Config> = StorageValue<_, u32, OptionQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::weight(0)]
+		pub fn set_value(origin: OriginFor<T>, n: u32) -> DispatchResult {
+			let _sender = frame_system::ensure_signed(origin)?;
+			Value::<T>::put(n);
+			Ok(())
+		}
+
+		#[pallet::weight(0)]
+		pub fn dummy(origin: OriginFor<T>, _n: u32) -> DispatchResult {
+			let _sender = frame_system::ensure_none(origin)?;
+			Ok(())
+		}
+	}
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		TestPallet: pallet_test::{Pallet, Call, Storage},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Call = Call;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const MaxValue: u32 = 100;
+}
+
+impl pallet_test::Config for Test {
+	type MaxValue = MaxValue;
+}
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	GenesisConfig::default().build_storage().unwrap().into()
+}
+
+#[crate::v2::benchmarks(where crate::tests_v2::Origin: From<RawOrigin<T::AccountId>>)]
+mod benchmarks {
+	use super::{new_test_ext, pallet_test::Value, Test};
+	use crate::{v2::*, BenchmarkParameter, BenchmarkingSetup};
+	use frame_support::{assert_err, assert_ok, ensure, traits::Get};
+	use frame_system::RawOrigin;
+	use sp_std::prelude::*;
+
+	// Additional used internally by the benchmark macro.
+	use super::pallet_test::{Call, Config, Pallet};
+
+	#[benchmark]
+	fn set_value(b: Linear<1, { T::MaxValue::get() }>) {
+		let caller = account::<T::AccountId>("caller", 0, 0);
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), b);
+		assert_eq!(Value::<T>::get(), Some(b));
+	}
+
+	#[benchmark]
+	fn other_name(b: Linear<1, 1000>) {
+		#[extrinsic_call]
+		dummy(RawOrigin::None, b);
+	}
+
+	#[benchmark]
+	fn sort_vector(x: Linear<1, 10000>, y: Linear<0, 10>) {
+		let (mut m, len) = ((0..x + y).rev().collect::<Vec<u32>>(), x + y);
+		#[block]
+		{
+			m.sort();
+		}
+		ensure!(m[0] == 0 && m.len() as u32 == len, "You forgot to sort!");
+	}
+
+	#[benchmark(extra)]
+	fn bad_verify(x: Linear<1, 10000>) {
+		let mut m = (0..x).rev().collect::<Vec<u32>>();
+		#[block]
+		{}
+		ensure!(m[0] == 0, "You forgot to sort!");
+		m.clear();
+	}
+
+	#[benchmark]
+	fn no_components() {
+		let caller = account::<T::AccountId>("caller", 0, 0);
+		#[extrinsic_call]
+		set_value(RawOrigin::Signed(caller), 0);
+	}
+
+	#[test]
+	fn components_are_declared_by_ranges() {
+		let components =
+			|selected| <SelectedBenchmark as BenchmarkingSetup<Test>>::components(&selected);
+		assert_eq!(components(SelectedBenchmark::set_value), vec![(BenchmarkParameter::b, 1, 100)]);
+		assert_eq!(
+			components(SelectedBenchmark::sort_vector),
+			vec![(BenchmarkParameter::x, 1, 10000), (BenchmarkParameter::y, 0, 10)],
+		);
+		assert!(components(SelectedBenchmark::no_components).is_empty());
+	}
+
+	#[test]
+	fn extra_benchmarks_are_marked() {
+		let benchmarks = <Pallet<Test> as crate::Benchmarking>::benchmarks(false);
+		assert!(benchmarks.iter().any(|info| info.name == b"set_value".to_vec()));
+		assert!(!benchmarks.iter().any(|info| info.name == b"bad_verify".to_vec()));
+		let benchmarks = <Pallet<Test> as crate::Benchmarking>::benchmarks(true);
+		assert!(benchmarks.iter().any(|info| info.name == b"bad_verify".to_vec()));
+	}
+
+	#[test]
+	fn benchmarks_generate_unit_tests() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Pallet::<Test>::test_benchmark_set_value());
+			assert_ok!(Pallet::<Test>::test_benchmark_other_name());
+			assert_ok!(Pallet::<Test>::test_benchmark_sort_vector());
+			assert_err!(Pallet::<Test>::test_benchmark_bad_verify(), "You forgot to sort!");
+			assert_ok!(Pallet::<Test>::test_benchmark_no_components());
+		});
+	}
+}
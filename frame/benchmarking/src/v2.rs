@@ -0,0 +1,79 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Attribute syntax to define benchmarks.
+//!
+//! The benchmarks of a pallet are defined by the functions annotated with `#[benchmark]` in a
+//! module annotated with `#[benchmarks]`. Unlike the [`benchmarks!`](crate::benchmarks) macro,
+//! whose rules match the setup code token by token, the functions are parsed as Rust items by the
+//! attribute macro: their statements are split around the benchmarked section and emitted
+//! unchanged.
+//!
+//! - The components of a benchmark are the arguments of its function, their range being given by
+//!   their type: `x: Linear<0, 100>` is a component varying from 0 to 100 included. The bounds can
+//!   be any constant expression, e.g. `Linear<1, { T::MaxItems::get() }>`.
+//! - The statements before the benchmarked section are the setup of the benchmark.
+//! - The benchmarked section is either a call to a dispatchable of the pallet annotated with
+//!   `#[extrinsic_call]`, `_` standing for the dispatchable named after the benchmark, or a block
+//!   annotated with `#[block]`.
+//! - The statements after the benchmarked section verify the outcome of the benchmark.
+//! - `#[benchmark(extra)]` and `#[benchmark(skip_meta)]` mark the benchmark as with the `#[extra]`
+//!   and `#[skip_meta]` attributes of `benchmarks!`.
+//!
+//! The `#[benchmarks]` attribute takes the `where` clause required by the benchmarks, preceded
+//! by `instance` for an instantiable pallet. The module must import the `Pallet`, `Config` and
+//! `Call` of the pallet, the benchmarks being generic over `T: Config`.
+//!
+//! The results of running the benchmarks are analyzed as the ones of `benchmarks!`: the weight
+//! of each component is computed by linear regression over the measurements.
+//!
+//! ```ignore
+//! use frame_benchmarking::v2::*;
+//!
+//! #[benchmarks(where T::AccountId: From<u64>)]
+//! mod benchmarks {
+//! 	use super::{Call, Config, Pallet, Value};
+//! 	use frame_system::RawOrigin;
+//!
+//! 	#[benchmark]
+//! 	fn set_value(b: Linear<1, 1000>) {
+//! 		let caller: T::AccountId = whitelisted_caller();
+//! 		#[extrinsic_call]
+//! 		_(RawOrigin::Signed(caller), b);
+//! 		assert_eq!(Value::<T>::get(), Some(b));
+//! 	}
+//!
+//! 	#[benchmark(extra)]
+//! 	fn sort_vector(x: Linear<1, 10_000>) {
+//! 		let mut m = (0..x).rev().collect::<Vec<u32>>();
+//! 		#[block]
+//! 		{
+//! 			m.sort();
+//! 		}
+//! 	}
+//!
+//! 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+//! }
+//! ```
+
+pub use crate::{
+	account, impl_benchmark_test_suite, whitelisted_caller, BenchmarkError, BenchmarkResult,
+};
+pub use frame_support::benchmarks;
+
+/// A component of a benchmark varying linearly from `A` to `B` included.
+pub struct Linear<const A: u32, const B: u32>;
@@ -37,7 +37,7 @@ use sp_std::{marker::PhantomData, mem, prelude::*};
 /// When fields are added to the [`ContractInfo`] that can change during execution this
 /// variable needs to be set to true. This will also force changes to the
 /// `in_memory_changes_not_discarded` test.
-const CONTRACT_INFO_CAN_CHANGE: bool = false;
+const CONTRACT_INFO_CAN_CHANGE: bool = true;
 
 pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 pub type MomentOf<T> = <<T as Config>::Time as Time>::Moment;
@@ -108,6 +108,18 @@ pub trait Ext: sealing::Sealed {
 		allows_reentry: bool,
 	) -> Result<ExecReturnValue, ExecError>;
 
+	/// Execute the code identified by `code_hash` in the context of the current contract.
+	///
+	/// The executed code has access to the storage and balance of the current contract.
+	/// `caller`, `address` and `value_transferred` return the same values as for the
+	/// current frame. No balance is transferred. Reentry into the current contract is denied
+	/// while the code executes.
+	fn delegate_call(
+		&mut self,
+		code_hash: CodeHash<Self::T>,
+		input_data: Vec<u8>,
+	) -> Result<ExecReturnValue, ExecError>;
+
 	/// Instantiate a contract from the given code.
 	///
 	/// Returns the original code size of the called contract.
@@ -135,6 +147,18 @@ pub trait Ext: sealing::Sealed {
 	/// call stack.
 	fn terminate(&mut self, beneficiary: &AccountIdOf<Self::T>) -> Result<(), DispatchError>;
 
+	/// Replace the code of the current contract by the code identified by `hash`.
+	///
+	/// The new code is used for all calls to the contract made after this function returns.
+	/// Executions of the contract which are already on the call stack keep running the
+	/// code they were started with.
+	///
+	/// # Note
+	///
+	/// The storage of the contract is kept as-is. It is the responsibility of the contract
+	/// author to make sure that the new code is able to interpret (or migrate) it.
+	fn set_code_hash(&mut self, hash: CodeHash<Self::T>) -> Result<(), DispatchError>;
+
 	/// Transfer some amount of funds into the specified account.
 	fn transfer(&mut self, to: &AccountIdOf<Self::T>, value: BalanceOf<Self::T>) -> DispatchResult;
 
@@ -357,6 +381,18 @@ pub struct Frame<T: Config> {
 	nested_meter: GasMeter<T>,
 	/// If `false` the contract enabled its defense against reentrance attacks.
 	allows_reentry: bool,
+	/// The caller of the frame which executed the delegate call that created this frame.
+	///
+	/// `None` if this frame was not created by a delegate call.
+	delegate_caller: Option<T::AccountId>,
+}
+
+/// Parameter passed in when creating a new `Frame` for a delegate call.
+struct DelegatedCall<T: Config, E> {
+	/// The executable which is run instead of the code of the contract.
+	executable: E,
+	/// The caller of the frame which executes the delegate call.
+	caller: T::AccountId,
 }
 
 /// Parameter passed in when creating a new `Frame`.
@@ -368,6 +404,8 @@ enum FrameArgs<'a, T: Config, E> {
 		dest: T::AccountId,
 		/// If `None` the contract info needs to be reloaded from storage.
 		cached_info: Option<ContractInfo<T>>,
+		/// If `Some` the frame executes the given code instead of the code of `dest`.
+		delegated_call: Option<DelegatedCall<T, E>>,
 	},
 	Instantiate {
 		/// The contract or signed origin which instantiates the new contract.
@@ -482,7 +520,7 @@ where
 		debug_message: Option<&'a mut Vec<u8>>,
 	) -> Result<ExecReturnValue, ExecError> {
 		let (mut stack, executable) = Self::new(
-			FrameArgs::Call { dest, cached_info: None },
+			FrameArgs::Call { dest, cached_info: None, delegated_call: None },
 			origin,
 			gas_meter,
 			schedule,
@@ -566,17 +604,23 @@ where
 		gas_limit: Weight,
 		schedule: &Schedule<T>,
 	) -> Result<(Frame<T>, E), ExecError> {
-		let (account_id, contract_info, executable, entry_point) = match frame_args {
-			FrameArgs::Call { dest, cached_info } => {
+		let (account_id, contract_info, executable, delegate_caller, entry_point) = match frame_args
+		{
+			FrameArgs::Call { dest, cached_info, delegated_call } => {
 				let contract = if let Some(contract) = cached_info {
 					contract
 				} else {
 					<ContractInfoOf<T>>::get(&dest).ok_or(<Error<T>>::ContractNotFound)?
 				};
 
-				let executable = E::from_storage(contract.code_hash, schedule, gas_meter)?;
+				let (executable, delegate_caller) =
+					if let Some(DelegatedCall { executable, caller }) = delegated_call {
+						(executable, Some(caller))
+					} else {
+						(E::from_storage(contract.code_hash, schedule, gas_meter)?, None)
+					};
 
-				(dest, contract, executable, ExportedFunction::Call)
+				(dest, contract, executable, delegate_caller, ExportedFunction::Call)
 			},
 			FrameArgs::Instantiate { sender, trie_seed, executable, salt } => {
 				let account_id =
@@ -587,7 +631,7 @@ where
 					trie_id,
 					executable.code_hash().clone(),
 				)?;
-				(account_id, contract, executable, ExportedFunction::Constructor)
+				(account_id, contract, executable, None, ExportedFunction::Constructor)
 			},
 		};
 
@@ -598,6 +642,7 @@ where
 			entry_point,
 			nested_meter: gas_meter.nested(gas_limit)?,
 			allows_reentry: true,
+			delegate_caller,
 		};
 
 		Ok((frame, executable))
@@ -795,6 +840,11 @@ where
 			return Err(<Error<T>>::NewContractNotFunded.into())
 		}
 
+		// The value of a delegate call was already transferred to the contract by its caller.
+		if frame.delegate_caller.is_some() {
+			return Ok(())
+		}
+
 		Self::transfer(self.caller_is_origin(), false, self.caller(), &frame.account_id, value)
 	}
 
@@ -888,8 +938,11 @@ where
 					CachedContract::Cached(contract) => Some(contract.clone()),
 					_ => None,
 				});
-			let executable =
-				self.push_frame(FrameArgs::Call { dest: to, cached_info }, value, gas_limit)?;
+			let executable = self.push_frame(
+				FrameArgs::Call { dest: to, cached_info, delegated_call: None },
+				value,
+				gas_limit,
+			)?;
 			self.run(executable, input_data)
 		};
 
@@ -902,6 +955,44 @@ where
 		result
 	}
 
+	fn delegate_call(
+		&mut self,
+		code_hash: CodeHash<Self::T>,
+		input_data: Vec<u8>,
+	) -> Result<ExecReturnValue, ExecError> {
+		let executable = E::from_storage(code_hash, &self.schedule, self.gas_meter())?;
+		let caller = self.caller().clone();
+		let top_frame = self.top_frame_mut();
+		let contract_info = top_frame.contract_info().clone();
+		let account_id = top_frame.account_id.clone();
+		let value = top_frame.value_transferred;
+
+		// The delegated code acts on behalf of the current contract, which must not be
+		// reentered until it returns. The delegated frame itself may still deny or allow
+		// reentry for the calls it makes.
+		top_frame.allows_reentry = false;
+
+		let try_call = || {
+			let executable = self.push_frame(
+				FrameArgs::Call {
+					dest: account_id,
+					cached_info: Some(contract_info),
+					delegated_call: Some(DelegatedCall { executable, caller }),
+				},
+				value,
+				0,
+			)?;
+			self.run(executable, input_data)
+		};
+
+		// We need to make sure to reset `allows_reentry` even on failure.
+		let result = try_call();
+
+		self.top_frame_mut().allows_reentry = true;
+
+		result
+	}
+
 	fn instantiate(
 		&mut self,
 		gas_limit: Weight,
@@ -949,6 +1040,19 @@ where
 		Ok(())
 	}
 
+	fn set_code_hash(&mut self, hash: CodeHash<Self::T>) -> Result<(), DispatchError> {
+		E::add_user(hash, self.gas_meter())?;
+		let frame = self.top_frame_mut();
+		let prev_hash = mem::replace(&mut frame.contract_info().code_hash, hash);
+		E::remove_user(prev_hash, &mut frame.nested_meter)?;
+		Contracts::<T>::deposit_event(Event::ContractCodeUpdated(
+			frame.account_id.clone(),
+			hash,
+			prev_hash,
+		));
+		Ok(())
+	}
+
 	fn transfer(&mut self, to: &T::AccountId, value: BalanceOf<T>) -> DispatchResult {
 		Self::transfer(true, false, &self.top_frame().account_id, to, value)
 	}
@@ -967,7 +1071,11 @@ where
 	}
 
 	fn caller(&self) -> &T::AccountId {
-		self.frames().nth(1).map(|f| &f.account_id).unwrap_or(&self.origin)
+		if let Some(caller) = &self.top_frame().delegate_caller {
+			caller
+		} else {
+			self.frames().nth(1).map(|f| &f.account_id).unwrap_or(&self.origin)
+		}
 	}
 
 	fn balance(&self) -> BalanceOf<T> {
@@ -1035,7 +1143,15 @@ where
 	fn call_runtime(&self, call: <Self::T as Config>::Call) -> DispatchResultWithPostInfo {
 		let mut origin: T::Origin = RawOrigin::Signed(self.address().clone()).into();
 		origin.add_filter(T::CallFilter::contains);
-		call.dispatch(origin)
+		let result = call.dispatch(origin);
+		deposit_event::<T>(
+			vec![],
+			Event::RuntimeCallDispatched(
+				self.address().clone(),
+				result.map(|_| ()).map_err(|e| e.error),
+			),
+		);
+		result
 	}
 
 	fn ecdsa_recover(&self, signature: &[u8; 65], message_hash: &[u8; 32]) -> Result<[u8; 33], ()> {
@@ -1078,7 +1194,7 @@ mod tests {
 		storage::Storage,
 		tests::{
 			test_utils::{get_balance, place_contract, set_balance},
-			Call, Event as MetaEvent, ExtBuilder, Test, TestFilter, ALICE, BOB, CHARLIE,
+			Call, Event as MetaEvent, ExtBuilder, Test, TestFilter, ALICE, BOB, CHARLIE, DJANGO,
 		},
 		Error, Weight,
 	};
@@ -1157,15 +1273,15 @@ mod tests {
 			})
 		}
 
-		fn increment_refcount(code_hash: CodeHash<Test>) {
+		fn increment_refcount(code_hash: CodeHash<Test>) -> Result<(), DispatchError> {
 			LOADER.with(|loader| {
 				let mut loader = loader.borrow_mut();
 				loader
 					.map
-					.entry(code_hash)
-					.and_modify(|executable| executable.refcount += 1)
-					.or_insert_with(|| panic!("code_hash does not exist"));
-			});
+					.get_mut(&code_hash)
+					.map(|executable| executable.refcount += 1)
+					.ok_or(Error::<Test>::CodeNotFound.into())
+			})
 		}
 
 		fn decrement_refcount(code_hash: CodeHash<Test>) {
@@ -1209,8 +1325,7 @@ mod tests {
 			code_hash: CodeHash<Test>,
 			_: &mut GasMeter<Test>,
 		) -> Result<(), DispatchError> {
-			MockLoader::increment_refcount(code_hash);
-			Ok(())
+			MockLoader::increment_refcount(code_hash)
 		}
 
 		fn remove_user(
@@ -1228,7 +1343,7 @@ mod tests {
 			input_data: Vec<u8>,
 		) -> ExecResult {
 			if let &Constructor = function {
-				MockLoader::increment_refcount(self.code_hash);
+				MockLoader::increment_refcount(self.code_hash).expect("code_hash does not exist");
 			}
 			if function == &self.func_type {
 				(self.func)(MockCtx { ext, input_data }, &self)
@@ -1839,10 +1954,6 @@ mod tests {
 
 	#[test]
 	fn in_memory_changes_not_discarded() {
-		// Remove this assert and fill out the "DO" stubs once fields are added to the
-		// contract info that can be modified during exection.
-		assert!(!CONTRACT_INFO_CAN_CHANGE);
-
 		// Call stack: BOB -> CHARLIE (trap) -> BOB' (success)
 		// This tests verfies some edge case of the contract info cache:
 		// We change some value in our contract info before calling into a contract
@@ -1851,11 +1962,11 @@ mod tests {
 		// The trap of CHARLIE reverts the storage changes to BOB. When the root BOB regains
 		// control it reloads its contract info from storage. We check that changes that
 		// are made before calling into CHARLIE are not discarded.
-		let code_bob = MockLoader::insert(Call, |ctx, _| {
+		let code_bob_new = MockLoader::insert(Call, |_, _| exec_success());
+		let code_bob = MockLoader::insert(Call, move |ctx, _| {
 			if ctx.input_data[0] == 0 {
-				// DO: modify medata (ContractInfo) of own contract through ctx.ext functions
+				ctx.ext.set_code_hash(code_bob_new).unwrap();
 				assert_eq!(ctx.ext.call(0, CHARLIE, 0, vec![], true), exec_trapped());
-				// DO: check that the value is not discarded (query via ctx.ext)
 			}
 			exec_success()
 		});
@@ -1880,6 +1991,7 @@ mod tests {
 				None,
 			);
 			assert_matches!(result, Ok(_));
+			assert_eq!(Storage::<Test>::code_hash(&BOB), Some(code_bob_new));
 		});
 	}
 
@@ -2057,6 +2169,185 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn delegate_call_runs_in_context_of_caller() {
+		thread_local! {
+			static WITNESSED: RefCell<Option<(AccountIdOf<Test>, AccountIdOf<Test>, u64)>> =
+				RefCell::new(None);
+		}
+
+		let delegated_ch = MockLoader::insert(Call, |ctx, _| {
+			WITNESSED.with(|witnessed| {
+				*witnessed.borrow_mut() = Some((
+					ctx.ext.caller().clone(),
+					ctx.ext.address().clone(),
+					ctx.ext.value_transferred(),
+				))
+			});
+			ctx.ext.set_storage([1; 32], Some(ctx.input_data)).unwrap();
+			exec_success()
+		});
+		let bob_ch = MockLoader::insert(Call, move |ctx, _| {
+			assert_matches!(ctx.ext.delegate_call(delegated_ch, vec![42]), Ok(_));
+			exec_success()
+		});
+
+		ExtBuilder::default().build().execute_with(|| {
+			let schedule = <Test as Config>::Schedule::get();
+			set_balance(&ALICE, 100);
+			place_contract(&BOB, bob_ch);
+			let bob_balance = get_balance(&BOB);
+
+			let result = MockStack::run_call(
+				ALICE,
+				BOB,
+				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&schedule,
+				55,
+				vec![],
+				None,
+			);
+
+			assert_matches!(result, Ok(_));
+			// The value is only transferred once by the outer call.
+			assert_eq!(get_balance(&ALICE), 45);
+			assert_eq!(get_balance(&BOB), bob_balance + 55);
+			let trie_id = ContractInfoOf::<Test>::get(&BOB).unwrap().trie_id;
+			assert_eq!(Storage::<Test>::read(&trie_id, &[1; 32]), Some(vec![42]));
+		});
+
+		WITNESSED.with(|witnessed| assert_eq!(*witnessed.borrow(), Some((ALICE, BOB, 55))));
+	}
+
+	#[test]
+	fn delegate_call_fails_for_unknown_code() {
+		let bob_ch = MockLoader::insert(Call, |ctx, _| {
+			let unknown = <Test as frame_system::Config>::Hash::repeat_byte(0xff);
+			assert_err!(
+				ctx.ext.delegate_call(unknown, vec![]).map_err(|e| e.error),
+				<Error<Test>>::CodeNotFound,
+			);
+			exec_success()
+		});
+
+		ExtBuilder::default().build().execute_with(|| {
+			let schedule = <Test as Config>::Schedule::get();
+			place_contract(&BOB, bob_ch);
+
+			let result = MockStack::run_call(
+				ALICE,
+				BOB,
+				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&schedule,
+				0,
+				vec![],
+				None,
+			);
+			assert_matches!(result, Ok(_));
+		});
+	}
+
+	#[test]
+	fn terminate_in_delegate_call_fails() {
+		let delegated_ch = MockLoader::insert(Call, |ctx, _| {
+			assert_err!(ctx.ext.terminate(&CHARLIE), <Error<Test>>::TerminatedWhileReentrant,);
+			exec_success()
+		});
+		let bob_ch =
+			MockLoader::insert(Call, move |ctx, _| ctx.ext.delegate_call(delegated_ch, vec![]));
+
+		ExtBuilder::default().build().execute_with(|| {
+			let schedule = <Test as Config>::Schedule::get();
+			place_contract(&BOB, bob_ch);
+
+			let result = MockStack::run_call(
+				ALICE,
+				BOB,
+				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&schedule,
+				0,
+				vec![],
+				None,
+			);
+			assert_matches!(result, Ok(_));
+			assert!(ContractInfoOf::<Test>::contains_key(&BOB));
+		});
+	}
+
+	#[test]
+	fn delegate_call_keeps_reentry_denial() {
+		// the delegated code allows reentry for its own call.
+		let delegated_ch =
+			MockLoader::insert(Call, |ctx, _| ctx.ext.call(0, CHARLIE, 0, vec![], true));
+		let code_bob = MockLoader::insert(Call, move |ctx, _| {
+			if ctx.input_data[0] == 0 {
+				ctx.ext.delegate_call(delegated_ch, vec![])
+			} else {
+				exec_success()
+			}
+		});
+
+		// call BOB with input set to '1'
+		let code_charlie =
+			MockLoader::insert(Call, |ctx, _| ctx.ext.call(0, BOB, 0, vec![1], true));
+
+		ExtBuilder::default().build().execute_with(|| {
+			let schedule = <Test as Config>::Schedule::get();
+			place_contract(&BOB, code_bob);
+			place_contract(&CHARLIE, code_charlie);
+
+			// BOB -> BOB' (delegated) -> CHARLIE -> BOB fails as BOB denies reentry while
+			// delegating.
+			assert_err!(
+				MockStack::run_call(
+					ALICE,
+					BOB,
+					&mut GasMeter::<Test>::new(GAS_LIMIT),
+					&schedule,
+					0,
+					vec![0],
+					None,
+				)
+				.map_err(|e| e.error),
+				<Error<Test>>::ReentranceDenied,
+			);
+		});
+	}
+
+	#[test]
+	fn set_code_hash_works() {
+		let new_ch = MockLoader::insert(Call, |_, _| exec_success());
+		let bob_ch = MockLoader::insert(Call, move |ctx, _| {
+			let unknown = <Test as frame_system::Config>::Hash::repeat_byte(0xff);
+			assert_err!(ctx.ext.set_code_hash(unknown), <Error<Test>>::CodeNotFound);
+			ctx.ext.set_code_hash(new_ch)?;
+			exec_success()
+		});
+
+		ExtBuilder::default().build().execute_with(|| {
+			let schedule = <Test as Config>::Schedule::get();
+			place_contract(&BOB, bob_ch);
+			System::reset_events();
+
+			let result = MockStack::run_call(
+				ALICE,
+				BOB,
+				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&schedule,
+				0,
+				vec![],
+				None,
+			);
+
+			assert_matches!(result, Ok(_));
+			assert_eq!(Storage::<Test>::code_hash(&BOB), Some(new_ch));
+			assert_eq!(MockExecutable::from_storage_noinstr(new_ch).unwrap().refcount(), 2);
+			// The old code lost its only user and was removed.
+			assert!(MockExecutable::from_storage_noinstr(bob_ch).is_err());
+			assert_eq!(&events(), &[Event::ContractCodeUpdated(BOB, new_ch, bob_ch)]);
+		});
+	}
+
 	#[test]
 	fn call_runtime_works() {
 		let code_hash = MockLoader::insert(Call, |ctx, _| {
@@ -2079,11 +2370,21 @@ mod tests {
 			let remark_hash = <Test as frame_system::Config>::Hashing::hash(b"Hello World");
 			assert_eq!(
 				System::events(),
-				vec![EventRecord {
-					phase: Phase::Initialization,
-					event: MetaEvent::System(frame_system::Event::Remarked(BOB, remark_hash)),
-					topics: vec![],
-				},]
+				vec![
+					EventRecord {
+						phase: Phase::Initialization,
+						event: MetaEvent::System(frame_system::Event::Remarked(BOB, remark_hash)),
+						topics: vec![],
+					},
+					EventRecord {
+						phase: Phase::Initialization,
+						event: MetaEvent::Contracts(crate::Event::RuntimeCallDispatched(
+							BOB,
+							Ok(())
+						)),
+						topics: vec![],
+					},
+				]
 			);
 		});
 	}
@@ -2134,6 +2435,14 @@ mod tests {
 			assert_eq!(
 				System::events(),
 				vec![
+					EventRecord {
+						phase: Phase::Initialization,
+						event: MetaEvent::Contracts(crate::Event::RuntimeCallDispatched(
+							BOB,
+							Err(BadOrigin.into())
+						)),
+						topics: vec![],
+					},
 					EventRecord {
 						phase: Phase::Initialization,
 						event: MetaEvent::System(frame_system::Event::Remarked(BOB, remark_hash)),
@@ -2152,6 +2461,14 @@ mod tests {
 						),),
 						topics: vec![],
 					},
+					EventRecord {
+						phase: Phase::Initialization,
+						event: MetaEvent::Contracts(crate::Event::RuntimeCallDispatched(
+							BOB,
+							Ok(())
+						)),
+						topics: vec![],
+					},
 				]
 			);
 		});
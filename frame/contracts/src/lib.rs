@@ -430,6 +430,35 @@ pub mod pallet {
 		///
 		/// This happens when the last contract that uses this code hash was removed.
 		CodeRemoved(T::Hash),
+
+		/// A contract dispatched a call of the runtime.
+		/// \[contract, result\]
+		///
+		/// # Params
+		///
+		/// - `contract`: The contract that dispatched the call as its origin.
+		/// - `result`: The result of the dispatched call.
+		///
+		/// # Note
+		///
+		/// The only way for a contract to dispatch a call and emit this event is by calling
+		/// `seal_call_runtime`.
+		RuntimeCallDispatched(T::AccountId, DispatchResult),
+
+		/// A contract's code was updated.
+		/// \[contract, new_code_hash, old_code_hash\]
+		///
+		/// # Params
+		///
+		/// - `contract`: The contract whose code was updated.
+		/// - `new_code_hash`: The code hash the contract uses from now on.
+		/// - `old_code_hash`: The code hash the contract used before.
+		///
+		/// # Note
+		///
+		/// The only way for a contract to update its code and emit this event is by calling
+		/// `seal_set_code_hash`.
+		ContractCodeUpdated(T::AccountId, T::Hash, T::Hash),
 	}
 
 	#[pallet::error]
@@ -505,6 +534,8 @@ pub mod pallet {
 		DebugMessageInvalidUTF8,
 		/// A call tried to invoke a contract that is flagged as non-reentrant.
 		ReentranceDenied,
+		/// Invalid combination of flags supplied to `seal_call` or `seal_delegate_call`.
+		InvalidCallFlags,
 	}
 
 	/// A mapping from an original code hash to the original code, untouched by instrumentation.
@@ -346,6 +346,9 @@ pub struct HostFnWeights<T: Config> {
 	/// Weight per output byte received through `seal_call`.
 	pub call_per_output_byte: Weight,
 
+	/// Weight of calling `seal_delegate_call`.
+	pub delegate_call: Weight,
+
 	/// Weight of calling `seal_instantiate`.
 	pub instantiate: Weight,
 
@@ -385,6 +388,9 @@ pub struct HostFnWeights<T: Config> {
 	/// Weight of calling `seal_ecdsa_recover`.
 	pub ecdsa_recover: Weight,
 
+	/// Weight of calling `seal_set_code_hash`.
+	pub set_code_hash: Weight,
+
 	/// The type parameter is used in the default implementation.
 	#[codec(skip)]
 	pub _phantom: PhantomData<T>,
@@ -605,6 +611,7 @@ impl<T: Config> Default for HostFnWeights<T> {
 				0,
 				1
 			),
+			delegate_call: cost_batched!(seal_delegate_call),
 			instantiate: cost_batched!(seal_instantiate),
 			instantiate_per_input_byte: cost_byte_batched_args!(
 				seal_instantiate_per_input_output_salt_kb,
@@ -633,6 +640,7 @@ impl<T: Config> Default for HostFnWeights<T> {
 			hash_blake2_128: cost_batched!(seal_hash_blake2_128),
 			hash_blake2_128_per_byte: cost_byte_batched!(seal_hash_blake2_128_per_kb),
 			ecdsa_recover: cost_batched!(seal_ecdsa_recover),
+			set_code_hash: cost_batched!(seal_set_code_hash),
 			_phantom: PhantomData,
 		}
 	}
@@ -474,7 +474,7 @@ fn instantiate_and_call_and_deposit_event() {
 						addr.clone(),
 						subsistence * 100
 					)),
-					topics: vec![],
+					topics: vec![System::account_topic(&ALICE), System::account_topic(&addr)],
 				},
 				EventRecord {
 					phase: Phase::Initialization,
@@ -760,7 +760,7 @@ fn self_destruct_works() {
 						DJANGO,
 						100_000,
 					)),
-					topics: vec![],
+					topics: vec![System::account_topic(&addr), System::account_topic(&DJANGO)],
 				},
 				EventRecord {
 					phase: Phase::Initialization,
@@ -82,6 +82,7 @@ pub trait WeightInfo {
 	fn seal_transfer(r: u32, ) -> Weight;
 	fn seal_call(r: u32, ) -> Weight;
 	fn seal_call_per_transfer_input_output_kb(t: u32, i: u32, o: u32, ) -> Weight;
+	fn seal_delegate_call(r: u32, ) -> Weight;
 	fn seal_instantiate(r: u32, ) -> Weight;
 	fn seal_instantiate_per_input_output_salt_kb(i: u32, o: u32, s: u32, ) -> Weight;
 	fn seal_hash_sha2_256(r: u32, ) -> Weight;
@@ -93,6 +94,7 @@ pub trait WeightInfo {
 	fn seal_hash_blake2_128(r: u32, ) -> Weight;
 	fn seal_hash_blake2_128_per_kb(n: u32, ) -> Weight;
 	fn seal_ecdsa_recover(r: u32, ) -> Weight;
+	fn seal_set_code_hash(r: u32, ) -> Weight;
 	fn instr_i64const(r: u32, ) -> Weight;
 	fn instr_i64load(r: u32, ) -> Weight;
 	fn instr_i64store(r: u32, ) -> Weight;
@@ -534,6 +536,17 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(101 as Weight))
 			.saturating_add(T::DbWeight::get().writes((101 as Weight).saturating_mul(t as Weight)))
 	}
+	// Not measured on the reference hardware yet, priced like `seal_call`.
+	// Storage: Contracts ContractInfoOf (r:1 w:1)
+	// Storage: Contracts CodeStorage (r:101 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	fn seal_delegate_call(r: u32, ) -> Weight {
+		(0 as Weight)
+			.saturating_add((39_806_777_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((100 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 	// Storage: Contracts ContractInfoOf (r:1 w:1)
 	// Storage: Contracts CodeStorage (r:1 w:0)
 	// Storage: Timestamp Now (r:1 w:0)
@@ -654,6 +667,18 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(3 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// Not measured on the reference hardware yet, priced like `seal_call`.
+	// Storage: Contracts ContractInfoOf (r:1 w:1)
+	// Storage: Contracts CodeStorage (r:101 w:101)
+	// Storage: Timestamp Now (r:1 w:0)
+	fn seal_set_code_hash(r: u32, ) -> Weight {
+		(0 as Weight)
+			.saturating_add((39_806_777_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((100 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((100 as Weight).saturating_mul(r as Weight)))
+	}
 	fn instr_i64const(r: u32, ) -> Weight {
 		(45_937_000 as Weight)
 			// Standard Error: 10_000
@@ -1296,6 +1321,17 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(101 as Weight))
 			.saturating_add(RocksDbWeight::get().writes((101 as Weight).saturating_mul(t as Weight)))
 	}
+	// Not measured on the reference hardware yet, priced like `seal_call`.
+	// Storage: Contracts ContractInfoOf (r:1 w:1)
+	// Storage: Contracts CodeStorage (r:101 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	fn seal_delegate_call(r: u32, ) -> Weight {
+		(0 as Weight)
+			.saturating_add((39_806_777_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((100 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 	// Storage: Contracts ContractInfoOf (r:1 w:1)
 	// Storage: Contracts CodeStorage (r:1 w:0)
 	// Storage: Timestamp Now (r:1 w:0)
@@ -1416,6 +1452,18 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	// Not measured on the reference hardware yet, priced like `seal_call`.
+	// Storage: Contracts ContractInfoOf (r:1 w:1)
+	// Storage: Contracts CodeStorage (r:101 w:101)
+	// Storage: Timestamp Now (r:1 w:0)
+	fn seal_set_code_hash(r: u32, ) -> Weight {
+		(0 as Weight)
+			.saturating_add((39_806_777_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((100 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((100 as Weight).saturating_mul(r as Weight)))
+	}
 	fn instr_i64const(r: u32, ) -> Weight {
 		(45_937_000 as Weight)
 			// Standard Error: 10_000
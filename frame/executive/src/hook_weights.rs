@@ -0,0 +1,187 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Detection of block hooks exceeding their declared weight.
+//!
+//! Pallets declare the weight of their `on_initialize` and `on_finalize` hooks as the return value
+//! of `on_initialize`, and the weight consumed by `on_idle` as its return value. When the runtime
+//! is built natively with the `try-runtime` feature, the [`Executive`] measures the execution time
+//! of the hooks with a host timer and reports the hooks which took longer than the weight they
+//! declared, along with the size of the storage proof they recorded if a proof is being recorded.
+//! Nothing is measured otherwise, in particular not by nodes importing blocks.
+//!
+//! Overruns are logged, and the last one can be inspected with [`take_last_overrun`]. Test
+//! runtimes can turn overruns into panics with [`set_strict`].
+//!
+//! [`Executive`]: crate::Executive
+
+use frame_support::weights::Weight;
+
+/// Block hooks which took longer than the weight they declared.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HookOverrun {
+	/// The hooks which were executed.
+	pub hooks: &'static str,
+	/// The weight declared by the hooks.
+	pub declared: Weight,
+	/// The weight actually consumed by the hooks, derived from their execution time.
+	pub measured: Weight,
+	/// The size of the storage proof recorded by the hooks, `None` if no proof was recorded.
+	pub proof_size: Option<u32>,
+}
+
+#[cfg(all(feature = "std", feature = "try-runtime"))]
+std::thread_local! {
+	static STRICT: std::cell::Cell<bool> = std::cell::Cell::new(false);
+	static LAST_OVERRUN: std::cell::Cell<Option<HookOverrun>> = std::cell::Cell::new(None);
+	static ON_INITIALIZE: std::cell::Cell<Option<(Weight, Weight, Option<u32>)>> =
+		std::cell::Cell::new(None);
+}
+
+/// Panic instead of logging when hooks executed by the current thread exceed their declared
+/// weight.
+///
+/// This is meant for test runtimes, the measured weight depends on the machine executing the
+/// hooks.
+#[cfg(all(feature = "std", feature = "try-runtime"))]
+pub fn set_strict(strict: bool) {
+	STRICT.with(|s| s.set(strict));
+}
+
+/// Take the last overrun of hooks executed by the current thread.
+#[cfg(all(feature = "std", feature = "try-runtime"))]
+pub fn take_last_overrun() -> Option<HookOverrun> {
+	LAST_OVERRUN.with(|o| o.take())
+}
+
+/// The size of the storage proof recorded so far, if a proof is being recorded.
+#[cfg(all(feature = "std", feature = "try-runtime"))]
+fn recorded_proof_size() -> Option<u32> {
+	sp_externalities::with_externalities(|ext| ext.proof_size()).flatten()
+}
+
+/// Measures the weight consumed by hooks, from their execution time, and the size of the storage
+/// proof they recorded.
+pub(crate) struct Timer {
+	#[cfg(all(feature = "std", feature = "try-runtime"))]
+	started: std::time::Instant,
+	#[cfg(all(feature = "std", feature = "try-runtime"))]
+	started_proof_size: Option<u32>,
+}
+
+impl Timer {
+	/// Start measuring.
+	pub(crate) fn start() -> Self {
+		Timer {
+			#[cfg(all(feature = "std", feature = "try-runtime"))]
+			started: std::time::Instant::now(),
+			#[cfg(all(feature = "std", feature = "try-runtime"))]
+			started_proof_size: recorded_proof_size(),
+		}
+	}
+
+	/// The weight consumed since the timer was started.
+	///
+	/// Always zero when built without `std` and `try-runtime`.
+	pub(crate) fn elapsed(&self) -> Weight {
+		#[cfg(all(feature = "std", feature = "try-runtime"))]
+		{
+			use frame_support::weights::constants::WEIGHT_PER_NANOS;
+
+			let nanos = self.started.elapsed().as_nanos().min(Weight::max_value() as u128);
+			(nanos as Weight).saturating_mul(WEIGHT_PER_NANOS)
+		}
+		#[cfg(not(all(feature = "std", feature = "try-runtime")))]
+		{
+			0
+		}
+	}
+
+	/// The size of the storage proof recorded since the timer was started.
+	///
+	/// Always `None` when built without `std` and `try-runtime`, or when no proof is being
+	/// recorded.
+	pub(crate) fn proof_size(&self) -> Option<u32> {
+		#[cfg(all(feature = "std", feature = "try-runtime"))]
+		{
+			Some(recorded_proof_size()?.saturating_sub(self.started_proof_size.unwrap_or(0)))
+		}
+		#[cfg(not(all(feature = "std", feature = "try-runtime")))]
+		{
+			None
+		}
+	}
+}
+
+/// Note the weight declared and consumed by `on_initialize`, to be checked together with
+/// `on_finalize` by [`check_on_finalize`].
+pub(crate) fn note_on_initialize(declared: Weight, timer: &Timer) {
+	#[cfg(all(feature = "std", feature = "try-runtime"))]
+	ON_INITIALIZE.with(|i| i.set(Some((declared, timer.elapsed(), timer.proof_size()))));
+	#[cfg(not(all(feature = "std", feature = "try-runtime")))]
+	let _ = (declared, timer);
+}
+
+/// Check the weight consumed by `on_initialize` and `on_finalize` against the weight declared by
+/// `on_initialize`.
+///
+/// Nothing is checked if `on_initialize` wasn't executed by the current thread, e.g. when the
+/// block was initialized by another runtime call.
+pub(crate) fn check_on_finalize(timer: &Timer) {
+	#[cfg(all(feature = "std", feature = "try-runtime"))]
+	if let Some((declared, on_initialize, on_initialize_proof_size)) =
+		ON_INITIALIZE.with(|i| i.take())
+	{
+		let proof_size = match (on_initialize_proof_size, timer.proof_size()) {
+			(None, None) => None,
+			(on_initialize, on_finalize) =>
+				Some(on_initialize.unwrap_or(0).saturating_add(on_finalize.unwrap_or(0))),
+		};
+		report(
+			"on_initialize and on_finalize",
+			declared,
+			on_initialize.saturating_add(timer.elapsed()),
+			proof_size,
+		);
+	}
+	#[cfg(not(all(feature = "std", feature = "try-runtime")))]
+	let _ = timer;
+}
+
+/// Report the given hooks if they consumed more weight than they declared.
+pub(crate) fn check(hooks: &'static str, declared: Weight, timer: &Timer) {
+	report(hooks, declared, timer.elapsed(), timer.proof_size())
+}
+
+fn report(hooks: &'static str, declared: Weight, measured: Weight, proof_size: Option<u32>) {
+	#[cfg(all(feature = "std", feature = "try-runtime"))]
+	if measured > declared {
+		let overrun = HookOverrun { hooks, declared, measured, proof_size };
+		if STRICT.with(|s| s.get()) {
+			panic!("Block hooks exceeded their declared weight: {:?}", overrun);
+		}
+
+		frame_support::log::warn!(
+			target: "runtime::executive",
+			"Block hooks exceeded their declared weight: {:?}",
+			overrun,
+		);
+		LAST_OVERRUN.with(|o| o.set(Some(overrun)));
+	}
+	#[cfg(not(all(feature = "std", feature = "try-runtime")))]
+	let _ = (hooks, declared, measured, proof_size);
+}
@@ -137,6 +137,8 @@ use sp_runtime::{
 };
 use sp_std::{marker::PhantomData, prelude::*};
 
+pub mod hook_weights;
+
 pub type CheckedOf<E, C> = <E as Checkable<C>>::Checked;
 pub type CallOf<E, C> = <CheckedOf<E, C> as Applyable>::Call;
 pub type OriginOf<E, C> = <CallOf<E, C> as Dispatchable>::Origin;
@@ -222,6 +224,8 @@ where
 
 	/// Execute all `OnRuntimeUpgrade` of this runtime, including the pre and post migration checks.
 	///
+	/// Returns the error of the first failing check, if any.
+	///
 	/// This should only be used for testing.
 	#[cfg(feature = "try-runtime")]
 	pub fn try_runtime_upgrade() -> Result<frame_support::weights::Weight, &'static str> {
@@ -229,7 +233,7 @@ where
 			(frame_system::Pallet::<System>, COnRuntimeUpgrade, AllPallets)
 			as
 			OnRuntimeUpgrade
-		>::pre_upgrade().unwrap();
+		>::pre_upgrade()?;
 
 		let weight = Self::execute_on_runtime_upgrade();
 
@@ -237,7 +241,7 @@ where
 			(frame_system::Pallet::<System>, COnRuntimeUpgrade, AllPallets)
 			as
 			OnRuntimeUpgrade
-		>::post_upgrade().unwrap();
+		>::post_upgrade()?;
 
 		Ok(weight)
 	}
@@ -275,12 +279,16 @@ where
 			digest,
 			frame_system::InitKind::Full,
 		);
-		weight = weight.saturating_add(<frame_system::Pallet<System> as OnInitialize<
-			System::BlockNumber,
-		>>::on_initialize(*block_number));
-		weight = weight.saturating_add(
-			<AllPallets as OnInitialize<System::BlockNumber>>::on_initialize(*block_number),
-		);
+		let timer = hook_weights::Timer::start();
+		let hooks_weight =
+			<frame_system::Pallet<System> as OnInitialize<System::BlockNumber>>::on_initialize(
+				*block_number,
+			)
+			.saturating_add(<AllPallets as OnInitialize<System::BlockNumber>>::on_initialize(
+				*block_number,
+			));
+		hook_weights::note_on_initialize(hooks_weight, &timer);
+		weight = weight.saturating_add(hooks_weight);
 		weight = weight.saturating_add(
 			<System::BlockWeights as frame_support::traits::Get<_>>::get().base_block,
 		);
@@ -351,6 +359,34 @@ where
 		}
 	}
 
+	/// Execute all transitions for `block` like [`Self::execute_block`], without checking the state
+	/// root of its header, and return the consumed weight.
+	///
+	/// This should only be used for testing.
+	#[cfg(feature = "try-runtime")]
+	pub fn execute_block_no_check(block: Block) -> frame_support::weights::Weight {
+		Self::initialize_block(block.header());
+		Self::initial_checks(&block);
+
+		let (header, extrinsics) = block.deconstruct();
+		Self::execute_extrinsics_with_book_keeping(extrinsics, *header.number());
+		let weight = <frame_system::Pallet<System>>::block_weight().total();
+
+		// The checks of `final_checks`, except the one of the state root.
+		let new_header = <frame_system::Pallet<System>>::finalize();
+		let items_zip = header.digest().logs().iter().zip(new_header.digest().logs().iter());
+		for (header_item, computed_item) in items_zip {
+			header_item.check_equal(&computed_item);
+			assert!(header_item == computed_item, "Digest item must match that calculated.");
+		}
+		assert!(
+			header.extrinsics_root() == new_header.extrinsics_root(),
+			"Transaction trie root must be valid.",
+		);
+
+		weight
+	}
+
 	/// Execute given extrinsics and take care of post-extrinsics book-keeping.
 	fn execute_extrinsics_with_book_keeping(
 		extrinsics: Vec<Block::Extrinsic>,
@@ -388,6 +424,7 @@ where
 		let mut remaining_weight = max_weight.saturating_sub(weight.total());
 
 		if remaining_weight > 0 {
+			let timer = hook_weights::Timer::start();
 			let mut used_weight =
 				<frame_system::Pallet<System> as OnIdle<System::BlockNumber>>::on_idle(
 					block_number,
@@ -399,16 +436,19 @@ where
 				remaining_weight,
 			)
 			.saturating_add(used_weight);
+			hook_weights::check("on_idle", used_weight, &timer);
 			<frame_system::Pallet<System>>::register_extra_weight_unchecked(
 				used_weight,
 				DispatchClass::Mandatory,
 			);
 		}
 
+		let timer = hook_weights::Timer::start();
 		<frame_system::Pallet<System> as OnFinalize<System::BlockNumber>>::on_finalize(
 			block_number,
 		);
 		<AllPallets as OnFinalize<System::BlockNumber>>::on_finalize(block_number);
+		hook_weights::check_on_finalize(&timer);
 	}
 
 	/// Apply extrinsic outside of the block execution function.
@@ -858,7 +898,7 @@ mod tests {
 					parent_hash: [69u8; 32].into(),
 					number: 1,
 					state_root: hex!(
-						"1039e1a4bd0cf5deefe65f313577e70169c41c7773d6acf31ca8d671397559f5"
+						"da9e8ef1eed4f93d0a475f8f8d4ace6925d47658c5f5d84cfd4f315d419c60c7"
 					)
 					.into(),
 					extrinsics_root: hex!(
@@ -1327,6 +1367,48 @@ mod tests {
 		});
 	}
 
+	#[test]
+	#[cfg(feature = "try-runtime")]
+	fn hook_weight_overruns_are_reported() {
+		new_test_ext(1).execute_with(|| {
+			Executive::initialize_block(&Header::new(
+				1,
+				H256::default(),
+				H256::default(),
+				[69u8; 32].into(),
+				Digest::default(),
+			));
+			// The custom pallet declares 175 picoseconds for its hooks, which is exceeded by
+			// printing to stdout.
+			Executive::finalize_block();
+
+			let overrun = hook_weights::take_last_overrun().unwrap();
+			assert_eq!(overrun.hooks, "on_initialize and on_finalize");
+			assert_eq!(overrun.declared, 175);
+			assert!(overrun.measured > overrun.declared);
+			// The test externalities don't record a storage proof.
+			assert_eq!(overrun.proof_size, None);
+			assert_eq!(hook_weights::take_last_overrun(), None);
+		});
+	}
+
+	#[test]
+	#[cfg(feature = "try-runtime")]
+	#[should_panic(expected = "Block hooks exceeded their declared weight")]
+	fn hook_weight_overruns_panic_in_strict_mode() {
+		hook_weights::set_strict(true);
+		new_test_ext(1).execute_with(|| {
+			Executive::initialize_block(&Header::new(
+				1,
+				H256::default(),
+				H256::default(),
+				[69u8; 32].into(),
+				Digest::default(),
+			));
+			Executive::finalize_block();
+		});
+	}
+
 	#[test]
 	fn offchain_worker_works_as_expected() {
 		new_test_ext(1).execute_with(|| {
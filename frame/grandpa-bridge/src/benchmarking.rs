@@ -0,0 +1,118 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! GRANDPA Bridge pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_finality_grandpa::{RoundNumber, KEY_TYPE};
+
+/// The maximum number of authorities in the authority set of the bridged chain.
+const MAX_AUTHORITIES: u32 = 1024;
+
+const ROUND: RoundNumber = 1;
+
+/// Generate the keys of `n` authorities in the keystore.
+fn authority_keys(n: u32) -> Vec<sp_core::ed25519::Public> {
+	(0..n).map(|_| sp_io::crypto::ed25519_generate(KEY_TYPE, None)).collect()
+}
+
+fn authority_set(keys: &[sp_core::ed25519::Public]) -> AuthoritySet {
+	AuthoritySet { authorities: keys.iter().map(|key| ((*key).into(), 1)).collect(), set_id: 0 }
+}
+
+fn header<T: Config>(number: u32, parent_hash: BridgedHash<T>) -> T::BridgedHeader {
+	T::BridgedHeader::new(
+		number.into(),
+		Default::default(),
+		Default::default(),
+		parent_hash,
+		Default::default(),
+	)
+}
+
+/// An encoded justification of `header` with a precommit of each of the given authorities.
+fn justification<T: Config>(
+	header: &T::BridgedHeader,
+	keys: &[sp_core::ed25519::Public],
+) -> Vec<u8> {
+	let precommit =
+		grandpa::Precommit { target_hash: header.hash(), target_number: *header.number() };
+	let message = grandpa::Message::Precommit(precommit.clone());
+	let payload = sp_finality_grandpa::localized_payload(ROUND, 0, &message);
+	let precommits = keys
+		.iter()
+		.map(|key| grandpa::SignedPrecommit {
+			precommit: precommit.clone(),
+			signature: sp_io::crypto::ed25519_sign(KEY_TYPE, key, &payload)
+				.expect("the key was generated in the keystore; qed")
+				.into(),
+			id: (*key).into(),
+		})
+		.collect();
+
+	GrandpaJustification::<T::BridgedHeader> {
+		round: ROUND,
+		commit: grandpa::Commit {
+			target_hash: header.hash(),
+			target_number: *header.number(),
+			precommits,
+		},
+		votes_ancestries: vec![],
+	}
+	.encode()
+}
+
+benchmarks! {
+	initialize {
+		let a in 1 .. MAX_AUTHORITIES;
+
+		let data = InitializationData {
+			header: header::<T>(0, Default::default()),
+			authority_set: authority_set(&authority_keys(a)),
+		};
+	}: _(RawOrigin::Root, data)
+	verify {
+		assert!(Pallet::<T>::best_finalized().is_some());
+	}
+
+	// Every authority signs the justification.
+	submit_finality_proof {
+		let p in 1 .. MAX_AUTHORITIES;
+
+		let keys = authority_keys(p);
+		let genesis = header::<T>(0, Default::default());
+		Pallet::<T>::initialize_bridge(InitializationData {
+			header: genesis.clone(),
+			authority_set: authority_set(&keys),
+		})?;
+		// Importing the header prunes the oldest imported header.
+		ImportedHashes::<T>::insert(ImportedHashesPointer::<T>::get(), genesis.hash());
+
+		let header = header::<T>(1, genesis.hash());
+		let justification = justification::<T>(&header, &keys);
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), Box::new(header.clone()), justification)
+	verify {
+		assert_eq!(Pallet::<T>::best_finalized_hash(), Some(header.hash()));
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_benchmark_ext(), crate::mock::Test);
@@ -0,0 +1,444 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # GRANDPA Bridge Module
+//!
+//! An on-chain light client of another chain finalized by GRANDPA.
+//!
+//! ## Overview
+//!
+//! The module tracks the finalized headers of the bridged chain. It is initialized with a
+//! finalized header of the bridged chain and the authority set which finalizes its descendants,
+//! either at genesis or by the root origin through `initialize`.
+//!
+//! Afterwards anyone may submit a header of the bridged chain together with a GRANDPA
+//! justification through `submit_finality_proof`. The header is imported if it is a descendant
+//! of the best finalized header, i.e. has a higher number, and if the justification proves that
+//! the current authority set finalized it. Verifying the justification also checks that the
+//! precommits of the authorities are routed to the header through the headers included in the
+//! justification.
+//!
+//! Authority set changes are tracked through the GRANDPA digests of the imported headers: a
+//! scheduled change is enacted once the header it was scheduled for is imported. As that header
+//! is the last one finalized by the old authority set, relayers must submit every header which
+//! signals or enacts an authority set change. Forced changes are not supported.
+//!
+//! Only the last `HeadersToKeep` imported headers are kept, older headers are pruned.
+//!
+//! Downstream pallets, e.g. ones verifying messages sent by the bridged chain, can access the
+//! imported headers and their state roots through the [`HeaderChain`] trait.
+//!
+//! ## Related Modules
+//!
+//! * [`System`](../frame_system/index.html)
+//! * [`Support`](../frame_support/index.html)
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use codec::{Decode, Encode};
+use frame_support::{dispatch::DispatchResult, ensure, traits::Get};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_finality_grandpa::{
+	justification::GrandpaJustification, AuthorityList, ConsensusLog, ScheduledChange, SetId,
+	GRANDPA_ENGINE_ID,
+};
+use sp_runtime::{
+	generic::OpaqueDigestItemId,
+	traits::{Header as HeaderT, Saturating, Zero},
+	RuntimeDebug, SaturatedConversion,
+};
+use sp_std::prelude::*;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+const LOG_TARGET: &str = "runtime::grandpa-bridge";
+
+/// The hash of a header of the bridged chain.
+pub type BridgedHash<T> = <<T as Config>::BridgedHeader as HeaderT>::Hash;
+/// The number of a header of the bridged chain.
+pub type BridgedBlockNumber<T> = <<T as Config>::BridgedHeader as HeaderT>::Number;
+
+/// An authority set of the bridged chain.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AuthoritySet {
+	/// The authorities of the set and their weights.
+	pub authorities: AuthorityList,
+	/// The id of the set.
+	pub set_id: SetId,
+}
+
+/// The data the bridge is initialized with.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct InitializationData<Header> {
+	/// A finalized header of the bridged chain.
+	pub header: Header,
+	/// The authority set which finalizes the descendants of `header`.
+	pub authority_set: AuthoritySet,
+}
+
+/// An authority set change which is enacted once the header it is scheduled for is imported.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PendingAuthoritySetChange<Number> {
+	/// The authorities of the next set.
+	pub next_authorities: AuthorityList,
+	/// The number of the last header finalized by the current authority set.
+	pub enacted_at: Number,
+}
+
+/// Access to the finalized headers of a bridged chain.
+pub trait HeaderChain<Header: HeaderT> {
+	/// Returns the finalized header with the given hash, if it is known and was not pruned yet.
+	fn finalized_header(hash: &Header::Hash) -> Option<Header>;
+
+	/// Returns the state root of the finalized header with the given hash, if it is known and
+	/// was not pruned yet.
+	fn finalized_state_root(hash: &Header::Hash) -> Option<Header::Hash> {
+		Self::finalized_header(hash).map(|header| *header.state_root())
+	}
+}
+
+impl<Header: HeaderT> HeaderChain<Header> for () {
+	fn finalized_header(_hash: &Header::Hash) -> Option<Header> {
+		None
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize};
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The block number of the bridged chain.
+		type BridgedBlockNumber: Parameter
+			+ Member
+			+ MaybeSerializeDeserialize
+			+ AtLeast32BitUnsigned
+			+ Copy
+			+ grandpa::BlockNumberOps;
+
+		/// The hash of a header of the bridged chain.
+		type BridgedHash: Parameter
+			+ Member
+			+ MaybeSerializeDeserialize
+			+ Ord
+			+ Default
+			+ Copy
+			+ sp_std::hash::Hash
+			+ AsRef<[u8]>
+			+ AsMut<[u8]>;
+
+		/// The header of the bridged chain.
+		type BridgedHeader: HeaderT<Number = Self::BridgedBlockNumber, Hash = Self::BridgedHash>
+			+ Parameter
+			+ MaybeSerializeDeserialize;
+
+		/// The number of imported headers which are kept before they are pruned.
+		///
+		/// At least the best finalized header is always kept.
+		#[pallet::constant]
+		type HeadersToKeep: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// The hash of the best finalized header, `None` until the bridge is initialized.
+	#[pallet::storage]
+	#[pallet::getter(fn best_finalized_hash)]
+	pub type BestFinalized<T: Config> = StorageValue<_, BridgedHash<T>>;
+
+	/// The imported headers which were not pruned yet.
+	#[pallet::storage]
+	pub type ImportedHeaders<T: Config> = StorageMap<_, Identity, BridgedHash<T>, T::BridgedHeader>;
+
+	/// The hashes of the imported headers, used as a ring buffer to prune the oldest header.
+	#[pallet::storage]
+	pub type ImportedHashes<T: Config> = StorageMap<_, Identity, u32, BridgedHash<T>>;
+
+	/// The index in `ImportedHashes` at which the hash of the next imported header is stored.
+	#[pallet::storage]
+	pub type ImportedHashesPointer<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The authority set which finalizes the headers after the best finalized header.
+	#[pallet::storage]
+	#[pallet::getter(fn current_authority_set)]
+	pub type CurrentAuthoritySet<T: Config> = StorageValue<_, AuthoritySet, ValueQuery>;
+
+	/// The authority set change which was scheduled by an imported header, if any.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_change)]
+	pub type PendingChange<T: Config> =
+		StorageValue<_, PendingAuthoritySetChange<BridgedBlockNumber<T>>>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A finalized header was imported. \[hash, number\]
+		HeaderImported(BridgedHash<T>, BridgedBlockNumber<T>),
+		/// An authority set change was scheduled. \[enacted_at\]
+		AuthoritySetChangeScheduled(BridgedBlockNumber<T>),
+		/// An authority set change was enacted. \[set_id\]
+		AuthoritySetChanged(SetId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The bridge was already initialized.
+		AlreadyInitialized,
+		/// The bridge was not initialized yet.
+		NotInitialized,
+		/// The header is not higher than the best finalized header.
+		OldHeader,
+		/// The header is higher than the header a pending authority set change is enacted at.
+		UnenactedAuthoritySetChange,
+		/// The header schedules an authority set change while another one is pending.
+		AuthoritySetChangeAlreadyPending,
+		/// The header schedules a forced authority set change, which is not supported.
+		UnsupportedForcedChange,
+		/// The authority set is empty or all of its authorities have a weight of zero.
+		InvalidAuthoritySet,
+		/// The justification does not prove the finality of the header.
+		InvalidJustification,
+	}
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The data to initialize the bridge with, if any.
+		pub initialization_data: Option<InitializationData<T::BridgedHeader>>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { initialization_data: None }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			if let Some(ref data) = self.initialization_data {
+				Pallet::<T>::initialize_bridge(data.clone())
+					.expect("invalid initialization data of the grandpa bridge in genesis");
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Initialize the bridge with a finalized header and the authority set which finalizes
+		/// its descendants.
+		///
+		/// The dispatch origin of this call must be _Root_.
+		#[pallet::weight(T::WeightInfo::initialize(data.authority_set.authorities.len() as u32))]
+		pub fn initialize(
+			origin: OriginFor<T>,
+			data: InitializationData<T::BridgedHeader>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			Self::initialize_bridge(data)
+		}
+
+		/// Import a finalized header of the bridged chain, proven by an encoded GRANDPA
+		/// justification of the current authority set.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// Emits `HeaderImported`, as well as `AuthoritySetChangeScheduled` and
+		/// `AuthoritySetChanged` if the header schedules or enacts an authority set change.
+		#[pallet::weight(T::WeightInfo::submit_finality_proof(
+			max_precommits::<T>(justification.len())
+		))]
+		pub fn submit_finality_proof(
+			origin: OriginFor<T>,
+			header: Box<T::BridgedHeader>,
+			justification: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let best_hash = BestFinalized::<T>::get().ok_or(Error::<T>::NotInitialized)?;
+			let best_number = ImportedHeaders::<T>::get(best_hash)
+				.map(|header| *header.number())
+				.unwrap_or_else(Zero::zero);
+			let (hash, number) = (header.hash(), *header.number());
+			ensure!(number > best_number, Error::<T>::OldHeader);
+
+			let pending_change = PendingChange::<T>::get();
+			if let Some(ref change) = pending_change {
+				ensure!(number <= change.enacted_at, Error::<T>::UnenactedAuthoritySetChange);
+			}
+			ensure!(find_forced_change(&*header).is_none(), Error::<T>::UnsupportedForcedChange);
+
+			let authority_set = CurrentAuthoritySet::<T>::get();
+			let voters =
+				grandpa::voter_set::VoterSet::new(authority_set.authorities.iter().cloned())
+					.ok_or(Error::<T>::InvalidAuthoritySet)?;
+			let justification =
+				GrandpaJustification::<T::BridgedHeader>::decode_and_verify_finalizes(
+					&justification,
+					(hash, number),
+					authority_set.set_id,
+					&voters,
+				)
+				.map_err(|e| {
+					log::debug!(
+						target: LOG_TARGET,
+						"Justification of header {:?} is invalid: {:?}",
+						hash,
+						e,
+					);
+					Error::<T>::InvalidJustification
+				})?;
+
+			let mut authority_set = authority_set;
+			let mut pending_change = pending_change;
+			if pending_change.as_ref().map_or(false, |change| change.enacted_at == number) {
+				let change = pending_change.take().expect("checked above; qed");
+				authority_set = Self::enact_change(authority_set, change.next_authorities);
+			}
+			if let Some(change) = find_scheduled_change(&*header) {
+				ensure!(pending_change.is_none(), Error::<T>::AuthoritySetChangeAlreadyPending);
+				if change.delay.is_zero() {
+					authority_set = Self::enact_change(authority_set, change.next_authorities);
+				} else {
+					let enacted_at = number.saturating_add(change.delay);
+					Self::deposit_event(Event::AuthoritySetChangeScheduled(enacted_at));
+					pending_change = Some(PendingAuthoritySetChange {
+						next_authorities: change.next_authorities,
+						enacted_at,
+					});
+				}
+			}
+
+			CurrentAuthoritySet::<T>::put(authority_set);
+			PendingChange::<T>::set(pending_change);
+			Self::import_header(*header);
+
+			let precommits = justification.commit.precommits.len() as u32;
+			Ok(Some(T::WeightInfo::submit_finality_proof(precommits)).into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns the best finalized header, `None` if the bridge was not initialized yet.
+	pub fn best_finalized() -> Option<T::BridgedHeader> {
+		BestFinalized::<T>::get().and_then(ImportedHeaders::<T>::get)
+	}
+
+	fn initialize_bridge(data: InitializationData<T::BridgedHeader>) -> DispatchResult {
+		ensure!(BestFinalized::<T>::get().is_none(), Error::<T>::AlreadyInitialized);
+		ensure!(
+			grandpa::voter_set::VoterSet::new(data.authority_set.authorities.iter().cloned())
+				.is_some(),
+			Error::<T>::InvalidAuthoritySet,
+		);
+
+		CurrentAuthoritySet::<T>::put(data.authority_set);
+		PendingChange::<T>::kill();
+		Self::import_header(data.header);
+		Ok(())
+	}
+
+	fn enact_change(current: AuthoritySet, next_authorities: AuthorityList) -> AuthoritySet {
+		let set_id = current.set_id + 1;
+		Self::deposit_event(Event::AuthoritySetChanged(set_id));
+		AuthoritySet { authorities: next_authorities, set_id }
+	}
+
+	/// Import the header as the best finalized header and prune the oldest imported header
+	/// once more than `HeadersToKeep` are stored.
+	fn import_header(header: T::BridgedHeader) {
+		let (hash, number) = (header.hash(), *header.number());
+		let index = ImportedHashesPointer::<T>::get();
+		if let Some(pruned) = ImportedHashes::<T>::get(index) {
+			ImportedHeaders::<T>::remove(pruned);
+		}
+		ImportedHashes::<T>::insert(index, hash);
+		ImportedHashesPointer::<T>::put((index + 1) % T::HeadersToKeep::get().max(1));
+
+		ImportedHeaders::<T>::insert(hash, header);
+		BestFinalized::<T>::put(hash);
+		Self::deposit_event(Event::HeaderImported(hash, number));
+	}
+}
+
+impl<T: Config> HeaderChain<T::BridgedHeader> for Pallet<T> {
+	fn finalized_header(hash: &BridgedHash<T>) -> Option<T::BridgedHeader> {
+		ImportedHeaders::<T>::get(hash)
+	}
+}
+
+/// An upper bound of the number of precommits in an encoded justification of the given length.
+///
+/// Each signed precommit encodes at least its target hash, a signature and an authority id.
+fn max_precommits<T: Config>(justification_len: usize) -> u32 {
+	const SIGNATURE_LEN: usize = 64;
+	const AUTHORITY_ID_LEN: usize = 32;
+
+	let hash_len = BridgedHash::<T>::default().as_ref().len();
+	(justification_len / (hash_len + SIGNATURE_LEN + AUTHORITY_ID_LEN)).saturated_into()
+}
+
+/// Returns the authority set change scheduled by the header, if any.
+fn find_scheduled_change<H: HeaderT>(header: &H) -> Option<ScheduledChange<H::Number>> {
+	let id = OpaqueDigestItemId::Consensus(&GRANDPA_ENGINE_ID);
+
+	let filter_log = |log: ConsensusLog<H::Number>| match log {
+		ConsensusLog::ScheduledChange(change) => Some(change),
+		_ => None,
+	};
+
+	// find the first consensus digest with the right ID which converts to
+	// the right kind of consensus log.
+	header.digest().convert_first(|l| l.try_to(id).and_then(filter_log))
+}
+
+/// Returns the forced authority set change scheduled by the header, if any.
+fn find_forced_change<H: HeaderT>(header: &H) -> Option<(H::Number, ScheduledChange<H::Number>)> {
+	let id = OpaqueDigestItemId::Consensus(&GRANDPA_ENGINE_ID);
+
+	let filter_log = |log: ConsensusLog<H::Number>| match log {
+		ConsensusLog::ForcedChange(delay, change) => Some((delay, change)),
+		_ => None,
+	};
+
+	header.digest().convert_first(|l| l.try_to(id).and_then(filter_log))
+}
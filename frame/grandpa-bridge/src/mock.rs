@@ -0,0 +1,180 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test environment for GRANDPA Bridge pallet.
+
+use super::*;
+use crate as pallet_grandpa_bridge;
+
+use frame_support::{parameter_types, traits::GenesisBuild};
+use sp_core::H256;
+use sp_finality_grandpa::{AuthorityId, RoundNumber};
+use sp_keyring::Ed25519Keyring;
+#[cfg(feature = "runtime-benchmarks")]
+use sp_keystore::{testing::KeyStore, KeystoreExt};
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	DigestItem,
+};
+#[cfg(feature = "runtime-benchmarks")]
+use std::sync::Arc;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Bridge: pallet_grandpa_bridge::{Pallet, Call, Config<T>, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const HeadersToKeep: u32 = 3;
+}
+
+impl Config for Test {
+	type Event = Event;
+	type BridgedBlockNumber = u64;
+	type BridgedHash = H256;
+	type BridgedHeader = Header;
+	type HeadersToKeep = HeadersToKeep;
+	type WeightInfo = ();
+}
+
+/// The round all test justifications are made in.
+const ROUND: RoundNumber = 1;
+
+/// The authorities of the initial authority set.
+pub const INITIAL_AUTHORITIES: [Ed25519Keyring; 3] =
+	[Ed25519Keyring::Alice, Ed25519Keyring::Bob, Ed25519Keyring::Charlie];
+
+/// The authority set with the given authorities, all of equal weight.
+pub fn authority_set(authorities: &[Ed25519Keyring], set_id: SetId) -> AuthoritySet {
+	AuthoritySet {
+		authorities: authorities.iter().map(|k| (AuthorityId::from(k.public()), 1)).collect(),
+		set_id,
+	}
+}
+
+/// The genesis header of the bridged chain.
+pub fn genesis() -> Header {
+	Header::new_from_number(0)
+}
+
+/// A child of `parent` with the given digest items.
+pub fn child(parent: &Header, digest: Vec<DigestItem<H256>>) -> Header {
+	let mut header = Header::new_from_number(parent.number + 1);
+	header.parent_hash = parent.hash();
+	header.digest.logs = digest;
+	header
+}
+
+/// A GRANDPA digest item of the given consensus log.
+pub fn grandpa_log(log: ConsensusLog<u64>) -> DigestItem<H256> {
+	DigestItem::Consensus(GRANDPA_ENGINE_ID, log.encode())
+}
+
+/// An encoded justification of `header` by the given authorities of the set with `set_id`.
+pub fn justification(header: &Header, set_id: SetId, authorities: &[Ed25519Keyring]) -> Vec<u8> {
+	let precommit = grandpa::Precommit { target_hash: header.hash(), target_number: header.number };
+	let message = grandpa::Message::Precommit(precommit.clone());
+	let precommits = authorities
+		.iter()
+		.map(|keyring| grandpa::SignedPrecommit {
+			precommit: precommit.clone(),
+			signature: keyring
+				.sign(&sp_finality_grandpa::localized_payload(ROUND, set_id, &message))
+				.into(),
+			id: keyring.public().into(),
+		})
+		.collect();
+
+	GrandpaJustification::<Header> {
+		round: ROUND,
+		commit: grandpa::Commit {
+			target_hash: header.hash(),
+			target_number: header.number,
+			precommits,
+		},
+		votes_ancestries: vec![],
+	}
+	.encode()
+}
+
+pub fn new_test_ext(
+	initialization_data: Option<InitializationData<Header>>,
+) -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_grandpa_bridge::GenesisConfig::<Test> { initialization_data }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// A test environment with a keystore, in which the benchmarks generate the authority keys.
+#[cfg(feature = "runtime-benchmarks")]
+pub fn new_benchmark_ext() -> sp_io::TestExternalities {
+	let mut ext = new_test_ext(None);
+	ext.register_extension(KeystoreExt(Arc::new(KeyStore::new())));
+	ext
+}
+
+/// A test environment with the bridge initialized with the genesis header and the
+/// `INITIAL_AUTHORITIES`.
+pub fn new_initialized_test_ext() -> sp_io::TestExternalities {
+	new_test_ext(Some(InitializationData {
+		header: genesis(),
+		authority_set: authority_set(&INITIAL_AUTHORITIES, 0),
+	}))
+}
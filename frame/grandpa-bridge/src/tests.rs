@@ -0,0 +1,276 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for GRANDPA Bridge pallet.
+
+use super::*;
+use crate::mock::*;
+use frame_support::{
+	assert_noop, assert_ok, dispatch::DispatchResultWithPostInfo, weights::GetDispatchInfo,
+};
+use sp_core::H256;
+use sp_finality_grandpa::AuthorityId;
+use sp_keyring::Ed25519Keyring;
+use sp_runtime::{testing::Header, traits::BadOrigin, DigestItem};
+
+fn submit(
+	header: &Header,
+	set_id: SetId,
+	authorities: &[Ed25519Keyring],
+) -> DispatchResultWithPostInfo {
+	Bridge::submit_finality_proof(
+		Origin::signed(1),
+		Box::new(header.clone()),
+		justification(header, set_id, authorities),
+	)
+}
+
+fn scheduled_change(authorities: &[Ed25519Keyring], delay: u64) -> DigestItem<H256> {
+	grandpa_log(ConsensusLog::ScheduledChange(ScheduledChange {
+		next_authorities: authority_set(authorities, 0).authorities,
+		delay,
+	}))
+}
+
+#[test]
+fn genesis_initializes_bridge() {
+	new_initialized_test_ext().execute_with(|| {
+		assert_eq!(Bridge::best_finalized(), Some(genesis()));
+		assert_eq!(Bridge::current_authority_set(), authority_set(&INITIAL_AUTHORITIES, 0));
+		assert_eq!(Bridge::pending_change(), None);
+	});
+}
+
+#[test]
+fn initialize_works() {
+	new_test_ext(None).execute_with(|| {
+		let data = InitializationData {
+			header: genesis(),
+			authority_set: authority_set(&INITIAL_AUTHORITIES, 7),
+		};
+
+		assert_noop!(Bridge::initialize(Origin::signed(1), data.clone()), BadOrigin);
+		assert_noop!(
+			Bridge::initialize(
+				Origin::root(),
+				InitializationData { authority_set: Default::default(), ..data.clone() }
+			),
+			Error::<Test>::InvalidAuthoritySet
+		);
+
+		assert_ok!(Bridge::initialize(Origin::root(), data.clone()));
+		assert_eq!(Bridge::best_finalized(), Some(genesis()));
+		assert_eq!(Bridge::current_authority_set(), authority_set(&INITIAL_AUTHORITIES, 7));
+		System::assert_last_event(crate::Event::HeaderImported(genesis().hash(), 0).into());
+
+		assert_noop!(Bridge::initialize(Origin::root(), data), Error::<Test>::AlreadyInitialized);
+	});
+}
+
+#[test]
+fn submit_finality_proof_imports_header() {
+	new_initialized_test_ext().execute_with(|| {
+		let header = child(&genesis(), vec![]);
+
+		assert_ok!(submit(&header, 0, &INITIAL_AUTHORITIES));
+
+		assert_eq!(Bridge::best_finalized(), Some(header.clone()));
+		assert_eq!(
+			<Bridge as HeaderChain<Header>>::finalized_state_root(&header.hash()),
+			Some(header.state_root)
+		);
+		System::assert_last_event(crate::Event::HeaderImported(header.hash(), 1).into());
+	});
+}
+
+#[test]
+fn submit_finality_proof_is_weighed_by_the_number_of_precommits() {
+	new_initialized_test_ext().execute_with(|| {
+		let header = child(&genesis(), vec![]);
+		let justification = justification(&header, 0, &INITIAL_AUTHORITIES);
+		let call = crate::Call::<Test>::submit_finality_proof {
+			header: Box::new(header.clone()),
+			justification: justification.clone(),
+		};
+		let max_weight = call.get_dispatch_info().weight;
+		assert!(max_weight >= <() as WeightInfo>::submit_finality_proof(3));
+
+		let info =
+			Bridge::submit_finality_proof(Origin::signed(1), Box::new(header), justification)
+				.unwrap();
+		assert_eq!(info.actual_weight, Some(<() as WeightInfo>::submit_finality_proof(3)));
+	});
+}
+
+#[test]
+fn submit_finality_proof_requires_initialization() {
+	new_test_ext(None).execute_with(|| {
+		assert_noop!(
+			submit(&child(&genesis(), vec![]), 0, &INITIAL_AUTHORITIES),
+			Error::<Test>::NotInitialized
+		);
+	});
+}
+
+#[test]
+fn submit_finality_proof_rejects_old_headers() {
+	new_initialized_test_ext().execute_with(|| {
+		let header = child(&genesis(), vec![]);
+		assert_ok!(submit(&header, 0, &INITIAL_AUTHORITIES));
+
+		assert_noop!(submit(&header, 0, &INITIAL_AUTHORITIES), Error::<Test>::OldHeader);
+		assert_noop!(submit(&genesis(), 0, &INITIAL_AUTHORITIES), Error::<Test>::OldHeader);
+	});
+}
+
+#[test]
+fn submit_finality_proof_rejects_invalid_justifications() {
+	new_initialized_test_ext().execute_with(|| {
+		let header = child(&genesis(), vec![]);
+
+		// signed for another authority set.
+		assert_noop!(submit(&header, 1, &INITIAL_AUTHORITIES), Error::<Test>::InvalidJustification);
+		// not signed by a supermajority.
+		assert_noop!(
+			submit(&header, 0, &INITIAL_AUTHORITIES[..2]),
+			Error::<Test>::InvalidJustification
+		);
+		// signed by authorities outside of the set.
+		assert_noop!(
+			submit(
+				&header,
+				0,
+				&[Ed25519Keyring::Dave, Ed25519Keyring::Eve, Ed25519Keyring::Ferdie]
+			),
+			Error::<Test>::InvalidJustification
+		);
+		// justifying another header.
+		assert_noop!(
+			Bridge::submit_finality_proof(
+				Origin::signed(1),
+				Box::new(header.clone()),
+				justification(&child(&header, vec![]), 0, &INITIAL_AUTHORITIES),
+			),
+			Error::<Test>::InvalidJustification
+		);
+	});
+}
+
+#[test]
+fn scheduled_change_without_delay_is_enacted_immediately() {
+	new_initialized_test_ext().execute_with(|| {
+		let next_authorities = [Ed25519Keyring::Dave, Ed25519Keyring::Eve, Ed25519Keyring::Ferdie];
+		let header = child(&genesis(), vec![scheduled_change(&next_authorities, 0)]);
+
+		assert_ok!(submit(&header, 0, &INITIAL_AUTHORITIES));
+		assert_eq!(Bridge::current_authority_set(), authority_set(&next_authorities, 1));
+		assert_eq!(Bridge::pending_change(), None);
+		System::assert_has_event(crate::Event::AuthoritySetChanged(1).into());
+
+		// the next header is finalized by the new authority set.
+		let next = child(&header, vec![]);
+		assert_noop!(submit(&next, 0, &INITIAL_AUTHORITIES), Error::<Test>::InvalidJustification);
+		assert_ok!(submit(&next, 1, &next_authorities));
+	});
+}
+
+#[test]
+fn scheduled_change_is_enacted_after_delay() {
+	new_initialized_test_ext().execute_with(|| {
+		let next_authorities = [Ed25519Keyring::Dave, Ed25519Keyring::Eve, Ed25519Keyring::Ferdie];
+		let signal = child(&genesis(), vec![scheduled_change(&next_authorities, 2)]);
+		let intermediate = child(&signal, vec![]);
+		let enacting = child(&intermediate, vec![]);
+		let next = child(&enacting, vec![]);
+
+		assert_ok!(submit(&signal, 0, &INITIAL_AUTHORITIES));
+		System::assert_last_event(crate::Event::HeaderImported(signal.hash(), 1).into());
+		System::assert_has_event(crate::Event::AuthoritySetChangeScheduled(3).into());
+		assert_eq!(Bridge::current_authority_set(), authority_set(&INITIAL_AUTHORITIES, 0));
+		assert_eq!(
+			Bridge::pending_change(),
+			Some(PendingAuthoritySetChange {
+				next_authorities: authority_set(&next_authorities, 0).authorities,
+				enacted_at: 3,
+			})
+		);
+
+		// the header enacting the change must not be skipped.
+		assert_noop!(
+			submit(&next, 1, &next_authorities),
+			Error::<Test>::UnenactedAuthoritySetChange
+		);
+
+		// another change can't be scheduled while one is pending.
+		let conflicting = child(&signal, vec![scheduled_change(&INITIAL_AUTHORITIES, 1)]);
+		assert_noop!(
+			submit(&conflicting, 0, &INITIAL_AUTHORITIES),
+			Error::<Test>::AuthoritySetChangeAlreadyPending
+		);
+
+		assert_ok!(submit(&enacting, 0, &INITIAL_AUTHORITIES));
+		assert_eq!(Bridge::current_authority_set(), authority_set(&next_authorities, 1));
+		assert_eq!(Bridge::pending_change(), None);
+		System::assert_has_event(crate::Event::AuthoritySetChanged(1).into());
+
+		assert_ok!(submit(&next, 1, &next_authorities));
+	});
+}
+
+#[test]
+fn forced_change_is_rejected() {
+	new_initialized_test_ext().execute_with(|| {
+		let header = child(
+			&genesis(),
+			vec![grandpa_log(ConsensusLog::ForcedChange(
+				0,
+				ScheduledChange {
+					next_authorities: vec![(AuthorityId::from(Ed25519Keyring::Dave.public()), 1)],
+					delay: 0,
+				},
+			))],
+		);
+
+		assert_noop!(
+			submit(&header, 0, &INITIAL_AUTHORITIES),
+			Error::<Test>::UnsupportedForcedChange
+		);
+	});
+}
+
+#[test]
+fn old_headers_are_pruned() {
+	new_initialized_test_ext().execute_with(|| {
+		let mut headers = vec![genesis()];
+		for _ in 0..4 {
+			let header = child(headers.last().unwrap(), vec![]);
+			assert_ok!(submit(&header, 0, &INITIAL_AUTHORITIES));
+			headers.push(header);
+		}
+
+		// only the last `HeadersToKeep` headers are kept.
+		for (number, header) in headers.iter().enumerate() {
+			assert_eq!(
+				<Bridge as HeaderChain<Header>>::finalized_header(&header.hash()).is_some(),
+				number >= 2,
+				"unexpected pruning of header {}",
+				number,
+			);
+		}
+		assert_eq!(Bridge::best_finalized(), headers.last().cloned());
+	});
+}
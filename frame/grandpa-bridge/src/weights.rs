@@ -0,0 +1,111 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Autogenerated weights for pallet_grandpa_bridge
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2026-10-17, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+
+// Executed Command:
+// ./target/release/substrate
+// benchmark
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_grandpa_bridge
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --output=./frame/grandpa-bridge/src/weights.rs
+// --template=./.maintain/frame-weight-template.hbs
+
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_grandpa_bridge.
+pub trait WeightInfo {
+	fn initialize(a: u32, ) -> Weight;
+	fn submit_finality_proof(p: u32, ) -> Weight;
+}
+
+/// Weights for pallet_grandpa_bridge using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: GrandpaBridge BestFinalized (r:1 w:1)
+	// Storage: GrandpaBridge ImportedHashesPointer (r:1 w:1)
+	// Storage: GrandpaBridge ImportedHashes (r:1 w:1)
+	// Storage: GrandpaBridge CurrentAuthoritySet (r:0 w:1)
+	// Storage: GrandpaBridge PendingChange (r:0 w:1)
+	// Storage: GrandpaBridge ImportedHeaders (r:0 w:1)
+	fn initialize(a: u32, ) -> Weight {
+		(29_767_000 as Weight)
+			// Standard Error: 11_000
+			.saturating_add((950_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: GrandpaBridge BestFinalized (r:1 w:1)
+	// Storage: GrandpaBridge ImportedHeaders (r:1 w:2)
+	// Storage: GrandpaBridge PendingChange (r:1 w:1)
+	// Storage: GrandpaBridge CurrentAuthoritySet (r:1 w:1)
+	// Storage: GrandpaBridge ImportedHashesPointer (r:1 w:1)
+	// Storage: GrandpaBridge ImportedHashes (r:1 w:1)
+	fn submit_finality_proof(p: u32, ) -> Weight {
+		(7_594_672_000 as Weight)
+			// Standard Error: 1_524_000
+			.saturating_add((69_617_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: GrandpaBridge BestFinalized (r:1 w:1)
+	// Storage: GrandpaBridge ImportedHashesPointer (r:1 w:1)
+	// Storage: GrandpaBridge ImportedHashes (r:1 w:1)
+	// Storage: GrandpaBridge CurrentAuthoritySet (r:0 w:1)
+	// Storage: GrandpaBridge PendingChange (r:0 w:1)
+	// Storage: GrandpaBridge ImportedHeaders (r:0 w:1)
+	fn initialize(a: u32, ) -> Weight {
+		(29_767_000 as Weight)
+			// Standard Error: 11_000
+			.saturating_add((950_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	// Storage: GrandpaBridge BestFinalized (r:1 w:1)
+	// Storage: GrandpaBridge ImportedHeaders (r:1 w:2)
+	// Storage: GrandpaBridge PendingChange (r:1 w:1)
+	// Storage: GrandpaBridge CurrentAuthoritySet (r:1 w:1)
+	// Storage: GrandpaBridge ImportedHashesPointer (r:1 w:1)
+	// Storage: GrandpaBridge ImportedHashes (r:1 w:1)
+	fn submit_finality_proof(p: u32, ) -> Weight {
+		(7_594_672_000 as Weight)
+			// Standard Error: 1_524_000
+			.saturating_add((69_617_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+}
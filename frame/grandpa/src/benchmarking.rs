@@ -20,9 +20,14 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use super::{Pallet as Grandpa, *};
-use frame_benchmarking::benchmarks;
+use frame_benchmarking::{account, benchmarks};
 use frame_system::RawOrigin;
 use sp_core::H256;
+use sp_runtime::traits::TrailingZeroInput;
+
+const SEED: u32 = 0;
+
+const MAX_REPORTERS: u32 = 100;
 
 benchmarks! {
 	check_equivocation_proof {
@@ -70,6 +75,19 @@ benchmarks! {
 	verify {
 		assert!(Grandpa::<T>::stalled().is_some());
 	}
+
+	reward_reporters {
+		let r in 1 .. MAX_REPORTERS;
+
+		let reporters: Vec<T::AccountId> =
+			(0..r).map(|i| account("reporter", i, SEED)).collect();
+		let offender = T::KeyOwnerIdentification::decode(&mut TrailingZeroInput::new(&[]))
+			.map_err(|_| "failed to decode the offender")?;
+
+		T::HandleEquivocationReward::setup_benchmark(&reporters);
+	}: {
+		T::HandleEquivocationReward::reward_reporters(&reporters, &offender);
+	}
 }
 
 #[cfg(test)]
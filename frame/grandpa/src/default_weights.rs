@@ -51,4 +51,14 @@ impl crate::WeightInfo for () {
 	fn note_stalled() -> Weight {
 		(3 * WEIGHT_PER_MICROS).saturating_add(DbWeight::get().writes(1))
 	}
+
+	fn reward_reporters(reporters: u32) -> Weight {
+		let reporters = reporters as u64;
+
+		// a transfer from the pot for each reporter, the worst case being that
+		// the reporter account is created by the transfer.
+		(45 * WEIGHT_PER_MICROS)
+			.saturating_mul(reporters)
+			.saturating_add(DbWeight::get().reads_writes(2, 2).saturating_mul(reporters))
+	}
 }
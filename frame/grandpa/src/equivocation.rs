@@ -38,7 +38,10 @@
 use sp_std::prelude::*;
 
 use codec::{self as codec, Decode, Encode};
-use frame_support::traits::{Get, KeyOwnerProofSystem};
+use frame_support::{
+	traits::{Currency, ExistenceRequirement, Get, KeyOwnerProofSystem},
+	weights::Weight,
+};
 use sp_finality_grandpa::{EquivocationProof, RoundNumber, SetId};
 use sp_runtime::{
 	transaction_validity::{
@@ -52,7 +55,7 @@ use sp_staking::{
 	SessionIndex,
 };
 
-use super::{Call, Config, Pallet};
+use super::{Call, Config, Pallet, WeightInfo};
 
 /// A trait with utility methods for handling equivocation reports in GRANDPA.
 /// The offence type is generic, and the trait provides , reporting an offence
@@ -118,6 +121,76 @@ impl<T: Config> HandleEquivocation<T> for () {
 	}
 }
 
+/// A hook to reward the reporters of valid equivocation reports.
+///
+/// Offence handlers like the staking pallet already reward reporters with a fraction of the
+/// slashed funds once the slash is applied, this hook allows runtimes to reward them as soon as
+/// the report is accepted.
+pub trait HandleEquivocationReward<AccountId, Offender> {
+	/// Reward the `reporters` of a valid equivocation report against `offender`.
+	fn reward_reporters(reporters: &[AccountId], offender: &Offender);
+
+	/// The weight of rewarding the given number of `reporters`, charged on top of the report
+	/// itself.
+	fn weight(reporters: u32) -> Weight;
+
+	/// Set up the state needed to reward the `reporters` in benchmarks, e.g. fund the reward pot.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn setup_benchmark(_reporters: &[AccountId]) {}
+}
+
+impl<AccountId, Offender> HandleEquivocationReward<AccountId, Offender> for () {
+	fn reward_reporters(_reporters: &[AccountId], _offender: &Offender) {}
+
+	fn weight(_reporters: u32) -> Weight {
+		0
+	}
+}
+
+/// Rewards the reporters of valid equivocation reports with `Reward`, transferred from the `Pot`
+/// account, e.g. the account receiving the slashed funds.
+///
+/// Reporters are not rewarded once the pot is depleted.
+pub struct RewardFromPot<T, C, Pot, Reward>(sp_std::marker::PhantomData<(T, C, Pot, Reward)>);
+
+impl<T, C, Pot, Reward, Offender> HandleEquivocationReward<T::AccountId, Offender>
+	for RewardFromPot<T, C, Pot, Reward>
+where
+	T: Config,
+	C: Currency<T::AccountId>,
+	Pot: Get<T::AccountId>,
+	Reward: Get<C::Balance>,
+{
+	fn reward_reporters(reporters: &[T::AccountId], _offender: &Offender) {
+		let pot = Pot::get();
+		for reporter in reporters {
+			if C::transfer(&pot, reporter, Reward::get(), ExistenceRequirement::KeepAlive).is_err()
+			{
+				log::warn!(
+					target: "runtime::afg",
+					"Equivocation reward pot is depleted, reporter {:?} is not rewarded.",
+					reporter,
+				);
+			}
+		}
+	}
+
+	fn weight(reporters: u32) -> Weight {
+		T::WeightInfo::reward_reporters(reporters)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn setup_benchmark(reporters: &[T::AccountId]) {
+		use sp_runtime::traits::Saturating;
+
+		// enough funds to reward every reporter without reaping the pot.
+		let funds = Reward::get()
+			.saturating_mul((reporters.len() as u32).into())
+			.saturating_add(C::minimum_balance());
+		C::make_free_balance_be(&Pot::get(), funds);
+	}
+}
+
 /// Generic equivocation handler. This type implements `HandleEquivocation`
 /// using existing subsystems that are part of frame (type bounds described
 /// below) and will dispatch to them directly, it's only purpose is to wire all
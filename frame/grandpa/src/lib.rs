@@ -62,7 +62,7 @@ mod tests;
 
 pub use equivocation::{
 	EquivocationHandler, GrandpaEquivocationOffence, GrandpaOffence, GrandpaTimeSlot,
-	HandleEquivocation,
+	HandleEquivocation, HandleEquivocationReward, RewardFromPot,
 };
 
 pub use pallet::*;
@@ -117,6 +117,13 @@ pub mod pallet {
 		/// definition.
 		type HandleEquivocation: HandleEquivocation<Self>;
 
+		/// Rewards the reporters of valid equivocation reports, in addition to any reward
+		/// given by the offence handler.
+		type HandleEquivocationReward: HandleEquivocationReward<
+			Self::AccountId,
+			Self::KeyOwnerIdentification,
+		>;
+
 		/// Weights for this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -189,7 +196,10 @@ pub mod pallet {
 		/// equivocation proof and validate the given key ownership proof
 		/// against the extracted offender. If both are valid, the offence
 		/// will be reported.
-		#[pallet::weight(T::WeightInfo::report_equivocation(key_owner_proof.validator_count()))]
+		#[pallet::weight(
+			T::WeightInfo::report_equivocation(key_owner_proof.validator_count())
+				.saturating_add(T::HandleEquivocationReward::weight(1))
+		)]
 		pub fn report_equivocation(
 			origin: OriginFor<T>,
 			equivocation_proof: Box<EquivocationProof<T::Hash, T::BlockNumber>>,
@@ -209,7 +219,10 @@ pub mod pallet {
 		/// block authors will call it (validated in `ValidateUnsigned`), as such
 		/// if the block author is defined it will be defined as the equivocation
 		/// reporter.
-		#[pallet::weight(T::WeightInfo::report_equivocation(key_owner_proof.validator_count()))]
+		#[pallet::weight(
+			T::WeightInfo::report_equivocation(key_owner_proof.validator_count())
+				.saturating_add(T::HandleEquivocationReward::weight(1))
+		)]
 		pub fn report_equivocation_unsigned(
 			origin: OriginFor<T>,
 			equivocation_proof: Box<EquivocationProof<T::Hash, T::BlockNumber>>,
@@ -353,6 +366,7 @@ pub mod pallet {
 pub trait WeightInfo {
 	fn report_equivocation(validator_count: u32) -> Weight;
 	fn note_stalled() -> Weight;
+	fn reward_reporters(reporters: u32) -> Weight;
 }
 
 /// A stored pending change.
@@ -550,18 +564,21 @@ impl<T: Config> Pallet<T> {
 		}
 
 		// report to the offences module rewarding the sender.
+		let reporters: Vec<_> = reporter.into_iter().collect();
 		T::HandleEquivocation::report_offence(
-			reporter.into_iter().collect(),
+			reporters.clone(),
 			<T::HandleEquivocation as HandleEquivocation<T>>::Offence::new(
 				session_index,
 				validator_count,
-				offender,
+				offender.clone(),
 				set_id,
 				round,
 			),
 		)
 		.map_err(|_| Error::<T>::DuplicateOffenceReport)?;
 
+		T::HandleEquivocationReward::reward_reporters(&reporters, &offender);
+
 		// waive the fee since the report is valid and beneficial
 		Ok(Pays::No.into())
 	}
@@ -882,3 +882,42 @@ fn valid_equivocation_reports_dont_pay_fees() {
 		assert_eq!(post_info.pays_fee, Pays::Yes);
 	})
 }
+
+#[test]
+fn valid_equivocation_reports_reward_reporters() {
+	let authorities = test_authorities();
+
+	new_test_ext_raw_authorities(authorities).execute_with(|| {
+		start_era(1);
+
+		let authorities = Grandpa::grandpa_authorities();
+		let set_id = Grandpa::current_set_id();
+		let reporter = 1;
+		let reporter_balance = Balances::free_balance(reporter);
+
+		let report = |equivocation_key: &AuthorityId| {
+			let equivocation_keyring = extract_keyring(equivocation_key);
+			let equivocation_proof = generate_equivocation_proof(
+				set_id,
+				(1, H256::random(), 10, &equivocation_keyring),
+				(1, H256::random(), 10, &equivocation_keyring),
+			);
+			let key_owner_proof =
+				Historical::prove((sp_finality_grandpa::KEY_TYPE, equivocation_key)).unwrap();
+
+			Grandpa::report_equivocation(
+				Origin::signed(reporter),
+				Box::new(equivocation_proof),
+				key_owner_proof,
+			)
+		};
+
+		assert_ok!(report(&authorities[0].0));
+		assert_eq!(Balances::free_balance(reporter), reporter_balance + 100);
+		assert_eq!(Balances::free_balance(EquivocationRewardPot::get()), 50);
+
+		// the pot is depleted, the report is still accepted.
+		assert_ok!(report(&authorities[2].0));
+		assert_eq!(Balances::free_balance(reporter), reporter_balance + 100);
+	})
+}
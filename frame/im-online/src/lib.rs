@@ -412,6 +412,12 @@ pub mod pallet {
 	pub(crate) type Keys<T: Config> =
 		StorageValue<_, WeakBoundedVec<T::AuthorityId, T::MaxKeys>, ValueQuery>;
 
+	/// The indexes of the validators disabled during the current session, which are not
+	/// expected to be online.
+	#[pallet::storage]
+	pub(crate) type DisabledValidators<T: Config> =
+		StorageValue<_, WeakBoundedVec<AuthIndex, T::MaxKeys>, ValueQuery>;
+
 	/// For each session index, we keep a mapping of 'SessionIndex` and `AuthIndex` to
 	/// `WrapperOpaque<BoundedOpaqueNetworkState>`.
 	#[pallet::storage]
@@ -888,10 +894,14 @@ impl<T: Config> OneSessionHandler<T::AccountId> for Pallet<T> {
 		let session_index = T::ValidatorSet::session_index();
 		let keys = Keys::<T>::get();
 		let current_validators = T::ValidatorSet::validators();
+		// validators disabled during the session, e.g. the ones which took themselves offline
+		// for maintenance, are not reported.
+		let disabled = DisabledValidators::<T>::take();
 
 		let offenders = current_validators
 			.into_iter()
 			.enumerate()
+			.filter(|(index, _)| disabled.binary_search(&(*index as u32)).is_err())
 			.filter(|(index, id)| !Self::is_online_aux(*index as u32, id))
 			.filter_map(|(_, id)| {
 				<T::ValidatorSet as ValidatorSetWithIdentification<T::AccountId>>::IdentificationOf::convert(
@@ -919,8 +929,13 @@ impl<T: Config> OneSessionHandler<T::AccountId> for Pallet<T> {
 		}
 	}
 
-	fn on_disabled(_i: usize) {
-		// ignore
+	fn on_disabled(i: usize) {
+		DisabledValidators::<T>::mutate(|disabled| {
+			if let Err(index) = disabled.binary_search(&(i as u32)) {
+				// the validators can't outnumber the keys.
+				let _ = disabled.try_insert(index, i as u32);
+			}
+		});
 	}
 }
 
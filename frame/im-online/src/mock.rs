@@ -167,6 +167,7 @@ impl pallet_session::Config for Runtime {
 impl pallet_session::historical::Config for Runtime {
 	type FullIdentification = u64;
 	type FullIdentificationOf = ConvertInto;
+	type LegacyFullIdentification = ();
 }
 
 parameter_types! {
@@ -109,6 +109,42 @@ fn should_report_offline_validators() {
 	});
 }
 
+#[test]
+fn should_not_report_disabled_validators() {
+	new_test_ext().execute_with(|| {
+		// given
+		System::set_block_number(1);
+		advance_session();
+		VALIDATORS.with(|l| *l.borrow_mut() = Some(vec![1, 2, 3, 4, 5, 6]));
+		advance_session();
+		advance_session();
+		OFFENCES.with(|l| l.replace(vec![]));
+
+		// when
+		assert!(!Session::disable_index(4));
+		advance_session();
+
+		// then
+		let offences = OFFENCES.with(|l| l.replace(vec![]));
+		assert_eq!(
+			offences,
+			vec![(
+				vec![],
+				UnresponsivenessOffence {
+					session_index: 3,
+					validator_set_count: 6,
+					offenders: vec![(1, 1), (2, 2), (3, 3), (4, 4), (6, 6)],
+				}
+			)]
+		);
+
+		// the validator is reported again once it is enabled by the new session.
+		advance_session();
+		let offences = OFFENCES.with(|l| l.replace(vec![]));
+		assert_eq!(offences[0].1.offenders.len(), 6);
+	});
+}
+
 fn heartbeat(
 	block_number: u64,
 	session_index: u32,
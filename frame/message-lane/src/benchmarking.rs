@@ -0,0 +1,170 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Message lane pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+use sp_trie::{Layout, TrieDBMut, TrieMut};
+
+use crate::Pallet as MessageLane;
+
+const SEED: u32 = 0;
+
+/// The lane the benchmarks exchange messages through.
+const LANE: LaneId = *b"bnch";
+
+/// The maximal size of the storage proofs, in bytes.
+const MAX_PROOF_SIZE: u32 = 64 * 1024;
+
+/// The configuration of a runtime the pallet is benchmarked in.
+pub trait Config: crate::Config {
+	/// Register a finalized header of the bridged chain with the given state root, so that it
+	/// is returned by the `BridgedHeaderChain` of the runtime.
+	///
+	/// Returns the hash of the registered header.
+	fn import_bridged_header(state_root: BridgedHash<Self>) -> BridgedHash<Self>;
+}
+
+fn assert_last_event<T: Config>(generic_event: <T as crate::Config>::Event) {
+	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+// Register a finalized header of the bridged chain whose state consists of the given entries.
+//
+// Returns the hash of the header and the trie nodes proving all the entries.
+fn prove_bridged_state<T: Config>(
+	entries: Vec<(Vec<u8>, Vec<u8>)>,
+) -> (BridgedHash<T>, Vec<Vec<u8>>) {
+	let mut db = MemoryDB::<<T::BridgedHeader as HeaderT>::Hashing>::default();
+	let mut root = Default::default();
+	{
+		let mut trie =
+			TrieDBMut::<Layout<<T::BridgedHeader as HeaderT>::Hashing>>::new(&mut db, &mut root);
+		for (key, value) in entries {
+			trie.insert(&key, &value)
+				.expect("inserting into an in-memory trie succeeds; qed");
+		}
+	}
+
+	let proof = db.drain().into_iter().map(|(_, (node, _))| node).collect();
+	(T::import_bridged_header(root), proof)
+}
+
+// Send `n` messages through the benchmarked lane, whose delivery fees add up to the rewards of
+// their relayers.
+fn send_messages<T: Config>(n: MessageNonce) -> Result<(), &'static str> {
+	let sender: T::AccountId = account("sender", 0, SEED);
+	T::Currency::make_free_balance_be(&sender, BalanceOf::<T>::max_value() / 2u32.into());
+	for _ in 0..n {
+		MessageLane::<T>::send_message(
+			RawOrigin::Signed(sender.clone()).into(),
+			LANE,
+			vec![],
+			T::Currency::minimum_balance(),
+		)?;
+	}
+	Ok(())
+}
+
+benchmarks! {
+	send_message {
+		let p in 0 .. T::MaxMessagePayloadSize::get();
+
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+		let fee = T::Currency::minimum_balance();
+	}: _(RawOrigin::Signed(caller), LANE, vec![0; p as usize], fee)
+	verify {
+		assert_last_event::<T>(Event::MessageAccepted(LANE, 1).into());
+	}
+
+	// The payloads of the messages are empty, so that their dispatch, which is paid for through
+	// `dispatch_weight`, is not measured. The proof is padded with an unread entry of `p` bytes.
+	receive_messages_proof {
+		let m in 1 .. T::MaxUnconfirmedMessages::get() as u32;
+		let p in 0 .. MAX_PROOF_SIZE;
+
+		let caller: T::AccountId = whitelisted_caller();
+		let mut entries: Vec<_> = (1..=m as MessageNonce).map(|nonce| (
+			storage_key::<T>(b"OutboundMessages", &(LANE, nonce).encode()),
+			MessageData { payload: Vec::new(), fee: BalanceOf::<T>::zero() }.encode(),
+		)).collect();
+		entries.push((
+			storage_key::<T>(b"OutboundLanes", &LANE.encode()),
+			OutboundLaneData::default().encode(),
+		));
+		entries.push((b"padding".to_vec(), vec![0; p as usize]));
+		let (bridged_header_hash, storage_proof) = prove_bridged_state::<T>(entries);
+		let proof = MessagesProof {
+			bridged_header_hash,
+			storage_proof,
+			lane: LANE,
+			nonces_start: 1,
+			nonces_end: m as MessageNonce,
+		};
+	}: _(RawOrigin::Signed(caller), proof, Weight::max_value())
+	verify {
+		assert_eq!(MessageLane::<T>::inbound_lane(LANE).last_delivered_nonce, m as MessageNonce);
+	}
+
+	// Every relayer delivered a distinct range of the `m` messages.
+	receive_messages_delivery_proof {
+		let m in 1 .. T::MaxUnconfirmedMessages::get() as u32;
+		let r in 1 .. T::MaxUnrewardedRelayerEntries::get();
+
+		send_messages::<T>(m as MessageNonce)?;
+		let caller: T::AccountId = whitelisted_caller();
+		let relayers = (0..r).map(|i| UnrewardedRelayer {
+			relayer: account("relayer", i, SEED),
+			begin: (i * m / r) as MessageNonce + 1,
+			end: ((i + 1) * m / r) as MessageNonce,
+		}).collect();
+		let inbound_lane = InboundLaneData::<T::AccountId> {
+			relayers,
+			last_delivered_nonce: m as MessageNonce,
+		};
+		let (bridged_header_hash, storage_proof) = prove_bridged_state::<T>(vec![(
+			storage_key::<T>(b"InboundLanes", &LANE.encode()),
+			inbound_lane.encode(),
+		)]);
+		let proof = MessagesDeliveryProof { bridged_header_hash, storage_proof, lane: LANE };
+	}: _(RawOrigin::Signed(caller), proof)
+	verify {
+		assert_last_event::<T>(Event::MessagesDelivered(LANE, 1, m as MessageNonce).into());
+	}
+
+	claim_rewards {
+		let caller: T::AccountId = whitelisted_caller();
+		let reward = T::Currency::minimum_balance();
+		T::Currency::make_free_balance_be(
+			&MessageLane::<T>::account_id(),
+			reward.saturating_add(T::Currency::minimum_balance()),
+		);
+		RelayerRewards::<T>::insert(&caller, reward);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_last_event::<T>(Event::RewardPaid(caller, reward).into());
+	}
+}
+
+impl_benchmark_test_suite!(MessageLane, crate::mock::new_test_ext(), crate::mock::Test);
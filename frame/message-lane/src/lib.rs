@@ -0,0 +1,625 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Message Lane Module
+//!
+//! Exchanges queued messages with a bridged chain running the same module.
+//!
+//! ## Overview
+//!
+//! Messages are exchanged through lanes. Every lane consists of an outbound lane, queueing the
+//! messages sent to the bridged chain, and an inbound lane, tracking the messages delivered from
+//! the bridged chain. The messages of a lane are identified by consecutive nonces, starting
+//! from 1, and are delivered in order.
+//!
+//! A message is sent through `send_message` together with a delivery fee, which is held by the
+//! module until the delivery is confirmed.
+//!
+//! Relayers deliver messages through `receive_messages_proof`, which checks a storage proof of
+//! the queued messages against the state root of a finalized header of the bridged chain, as
+//! provided by the [`HeaderChain`] of the bridged chain (e.g. the GRANDPA bridge module). The
+//! delivered messages are dispatched through the [`MessageDispatch`] hook and the relayer is
+//! recorded in the inbound lane as the one to be rewarded for their delivery.
+//!
+//! The delivery is confirmed on the source chain through `receive_messages_delivery_proof`,
+//! which checks a storage proof of the inbound lane on the bridged chain. The fees of the
+//! confirmed messages are credited to the relayers which delivered them and can be claimed
+//! through `claim_rewards`. The confirmed messages are pruned from the outbound lane. The
+//! relayers recorded in an inbound lane are pruned once the bridged chain confirmed their
+//! deliveries, which is learned from the outbound lane state proven with the next delivery.
+//!
+//! The storage proofs are checked against the storage keys of this module on the bridged chain,
+//! which is expected to be included in its runtime under the name `BridgedPalletName`. The
+//! account ids of the relayers are expected to be the same on both chains.
+//!
+//! ## Related Modules
+//!
+//! * [`GRANDPA Bridge`](../pallet_grandpa_bridge/index.html)
+//! * [`System`](../frame_system/index.html)
+//! * [`Support`](../frame_support/index.html)
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::{DispatchError, DispatchResult},
+	ensure,
+	storage::{with_transaction, TransactionOutcome},
+	traits::{Currency, ExistenceRequirement, Get},
+	weights::Weight,
+	Blake2_128Concat, PalletId, StorageHasher,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AccountIdConversion, Header as HeaderT, Saturating, Zero},
+	RuntimeDebug,
+};
+use sp_std::prelude::*;
+use sp_trie::{read_trie_value, Layout, MemoryDB, StorageProof};
+
+pub use pallet::*;
+pub use pallet_grandpa_bridge::HeaderChain;
+pub use weights::WeightInfo;
+
+const LOG_TARGET: &str = "runtime::message-lane";
+
+/// The identifier of a lane.
+pub type LaneId = [u8; 4];
+
+/// The nonce of a message, unique within its lane.
+pub type MessageNonce = u64;
+
+/// The hash of a header of the bridged chain.
+pub type BridgedHash<T> = <<T as Config>::BridgedHeader as HeaderT>::Hash;
+
+type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// A message queued in an outbound lane.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct MessageData<Balance> {
+	/// The encoded payload, dispatched on the bridged chain.
+	pub payload: Vec<u8>,
+	/// The fee paid to the relayer delivering the message.
+	pub fee: Balance,
+}
+
+/// The state of an outbound lane.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct OutboundLaneData {
+	/// The nonce of the oldest message which was not pruned yet.
+	pub oldest_unpruned_nonce: MessageNonce,
+	/// The nonce of the latest message whose delivery was confirmed.
+	pub latest_received_nonce: MessageNonce,
+	/// The nonce of the latest message sent through the lane.
+	pub latest_generated_nonce: MessageNonce,
+}
+
+impl Default for OutboundLaneData {
+	fn default() -> Self {
+		Self { oldest_unpruned_nonce: 1, latest_received_nonce: 0, latest_generated_nonce: 0 }
+	}
+}
+
+/// A relayer which delivered a range of messages and was not rewarded yet.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct UnrewardedRelayer<AccountId> {
+	/// The account of the relayer.
+	pub relayer: AccountId,
+	/// The nonce of the first message delivered by the relayer.
+	pub begin: MessageNonce,
+	/// The nonce of the last message delivered by the relayer.
+	pub end: MessageNonce,
+}
+
+/// The state of an inbound lane.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct InboundLaneData<AccountId> {
+	/// The relayers whose deliveries were not confirmed by the bridged chain yet.
+	pub relayers: Vec<UnrewardedRelayer<AccountId>>,
+	/// The nonce of the latest message delivered through the lane.
+	pub last_delivered_nonce: MessageNonce,
+}
+
+impl<AccountId> Default for InboundLaneData<AccountId> {
+	fn default() -> Self {
+		Self { relayers: Vec::new(), last_delivered_nonce: 0 }
+	}
+}
+
+/// A proof of the messages queued in an outbound lane of the bridged chain.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct MessagesProof<BridgedHash> {
+	/// The hash of the finalized header of the bridged chain the proof is made at.
+	pub bridged_header_hash: BridgedHash,
+	/// The trie nodes proving the messages and, optionally, the outbound lane state.
+	pub storage_proof: Vec<Vec<u8>>,
+	/// The lane of the messages.
+	pub lane: LaneId,
+	/// The nonce of the first proven message.
+	pub nonces_start: MessageNonce,
+	/// The nonce of the last proven message.
+	pub nonces_end: MessageNonce,
+}
+
+/// A proof of the state of an inbound lane of the bridged chain.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct MessagesDeliveryProof<BridgedHash> {
+	/// The hash of the finalized header of the bridged chain the proof is made at.
+	pub bridged_header_hash: BridgedHash,
+	/// The trie nodes proving the inbound lane state.
+	pub storage_proof: Vec<Vec<u8>>,
+	/// The proven lane.
+	pub lane: LaneId,
+}
+
+/// Dispatches the payloads of the messages delivered from the bridged chain.
+pub trait MessageDispatch {
+	/// The payload of a message.
+	type Payload: Decode;
+
+	/// The weight of dispatching the given payload.
+	fn dispatch_weight(payload: &Self::Payload) -> Weight;
+
+	/// Dispatch the payload of the message with the given nonce.
+	///
+	/// The storage changes are reverted if an error is returned.
+	fn dispatch(lane: LaneId, nonce: MessageNonce, payload: Self::Payload) -> DispatchResult;
+}
+
+impl MessageDispatch for () {
+	type Payload = ();
+
+	fn dispatch_weight(_payload: &()) -> Weight {
+		0
+	}
+
+	fn dispatch(_lane: LaneId, _nonce: MessageNonce, _payload: ()) -> DispatchResult {
+		Ok(())
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{pallet_prelude::*, transactional};
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency the delivery fees are paid in.
+		type Currency: Currency<Self::AccountId>;
+
+		/// The module id, used for deriving the account holding the delivery fees.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The header of the bridged chain.
+		type BridgedHeader: HeaderT;
+
+		/// The finalized headers of the bridged chain the storage proofs are checked against.
+		type BridgedHeaderChain: HeaderChain<Self::BridgedHeader>;
+
+		/// The name of this module in the runtime of the bridged chain.
+		type BridgedPalletName: Get<&'static str>;
+
+		/// Dispatches the payloads of the delivered messages.
+		type MessageDispatch: MessageDispatch;
+
+		/// The maximal size of the payload of a sent message.
+		#[pallet::constant]
+		type MaxMessagePayloadSize: Get<u32>;
+
+		/// The maximal number of sent messages whose delivery was not confirmed yet, per lane.
+		#[pallet::constant]
+		type MaxUnconfirmedMessages: Get<MessageNonce>;
+
+		/// The maximal number of relayer entries whose deliveries were not confirmed yet, per
+		/// lane.
+		#[pallet::constant]
+		type MaxUnrewardedRelayerEntries: Get<u32>;
+
+		/// The maximal number of confirmed messages pruned by a single delivery confirmation.
+		#[pallet::constant]
+		type MaxMessagesToPruneAtOnce: Get<MessageNonce>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// The state of the outbound lanes.
+	#[pallet::storage]
+	pub type OutboundLanes<T: Config> =
+		StorageMap<_, Blake2_128Concat, LaneId, OutboundLaneData, ValueQuery>;
+
+	/// The messages queued in the outbound lanes which were not pruned yet.
+	#[pallet::storage]
+	pub type OutboundMessages<T: Config> =
+		StorageMap<_, Blake2_128Concat, (LaneId, MessageNonce), MessageData<BalanceOf<T>>>;
+
+	/// The state of the inbound lanes.
+	#[pallet::storage]
+	pub type InboundLanes<T: Config> =
+		StorageMap<_, Blake2_128Concat, LaneId, InboundLaneData<T::AccountId>, ValueQuery>;
+
+	/// The rewards credited to relayers which were not claimed yet.
+	#[pallet::storage]
+	#[pallet::getter(fn relayer_reward)]
+	pub type RelayerRewards<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A message was queued in an outbound lane. \[lane, nonce\]
+		MessageAccepted(LaneId, MessageNonce),
+		/// A message delivered from the bridged chain was dispatched. \[lane, nonce, result\]
+		MessageDispatched(LaneId, MessageNonce, DispatchResult),
+		/// The delivery of a range of sent messages was confirmed. \[lane, begin, end\]
+		MessagesDelivered(LaneId, MessageNonce, MessageNonce),
+		/// A relayer claimed their rewards. \[relayer, reward\]
+		RewardPaid(T::AccountId, BalanceOf<T>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The payload of the message is too large.
+		MessageTooLarge,
+		/// The lane has too many messages whose delivery was not confirmed yet.
+		TooManyUnconfirmedMessages,
+		/// The header of the bridged chain is unknown or was pruned.
+		UnknownBridgedHeader,
+		/// The storage proof is invalid.
+		InvalidStorageProof,
+		/// A proven message or lane state could not be decoded.
+		InvalidProvenData,
+		/// A message is missing from the proof.
+		MissingMessage,
+		/// The messages don't follow the last delivered message of the lane.
+		UnexpectedMessageNonces,
+		/// The declared dispatch weight is lower than the weight of dispatching the messages.
+		InsufficientDispatchWeight,
+		/// The lane has too many relayer entries whose deliveries were not confirmed yet.
+		TooManyUnrewardedRelayers,
+		/// The proven inbound lane state confirms messages which were never sent.
+		InvalidMessagesDeliveryProof,
+		/// The proof doesn't confirm the delivery of any new messages.
+		NothingToConfirm,
+		/// The relayer has no rewards to claim.
+		NoRewards,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Queue a message to the bridged chain in the given lane.
+		///
+		/// The dispatch origin of this call must be _Signed_. The delivery fee is transferred
+		/// from the sender to the module and paid to the relayer delivering the message.
+		///
+		/// Emits `MessageAccepted`.
+		#[pallet::weight(T::WeightInfo::send_message(payload.len() as u32))]
+		pub fn send_message(
+			origin: OriginFor<T>,
+			lane: LaneId,
+			payload: Vec<u8>,
+			delivery_fee: BalanceOf<T>,
+		) -> DispatchResult {
+			let submitter = ensure_signed(origin)?;
+			ensure!(
+				payload.len() <= T::MaxMessagePayloadSize::get() as usize,
+				Error::<T>::MessageTooLarge
+			);
+
+			let mut lane_data = OutboundLanes::<T>::get(&lane);
+			let unconfirmed =
+				lane_data.latest_generated_nonce.saturating_sub(lane_data.latest_received_nonce);
+			ensure!(
+				unconfirmed < T::MaxUnconfirmedMessages::get(),
+				Error::<T>::TooManyUnconfirmedMessages
+			);
+
+			T::Currency::transfer(
+				&submitter,
+				&Self::account_id(),
+				delivery_fee,
+				ExistenceRequirement::AllowDeath,
+			)?;
+
+			let nonce = lane_data.latest_generated_nonce + 1;
+			lane_data.latest_generated_nonce = nonce;
+			OutboundLanes::<T>::insert(&lane, lane_data);
+			OutboundMessages::<T>::insert(
+				(lane, nonce),
+				MessageData { payload, fee: delivery_fee },
+			);
+
+			Self::deposit_event(Event::MessageAccepted(lane, nonce));
+			Ok(())
+		}
+
+		/// Deliver messages queued in an outbound lane of the bridged chain.
+		///
+		/// The dispatch origin of this call must be _Signed_ and is rewarded for the delivery
+		/// once it is confirmed on the bridged chain. `dispatch_weight` must cover the weight of
+		/// dispatching all the delivered messages.
+		///
+		/// Emits `MessageDispatched` for every delivered message.
+		#[pallet::weight(receive_messages_proof_weight::<T>(proof, *dispatch_weight))]
+		pub fn receive_messages_proof(
+			origin: OriginFor<T>,
+			proof: MessagesProof<BridgedHash<T>>,
+			dispatch_weight: Weight,
+		) -> DispatchResult {
+			let relayer = ensure_signed(origin)?;
+			let MessagesProof {
+				bridged_header_hash,
+				storage_proof,
+				lane,
+				nonces_start,
+				nonces_end,
+			} = proof;
+
+			let mut lane_data = InboundLanes::<T>::get(&lane);
+			ensure!(
+				nonces_start == lane_data.last_delivered_nonce + 1 && nonces_start <= nonces_end,
+				Error::<T>::UnexpectedMessageNonces
+			);
+
+			let checker = StorageProofChecker::<T>::new(&bridged_header_hash, storage_proof)?;
+
+			// prune the relayers whose deliveries were confirmed by the bridged chain.
+			let outbound_lane: Option<OutboundLaneData> =
+				checker.read(&storage_key::<T>(b"OutboundLanes", &lane.encode()))?;
+			if let Some(outbound_lane) = outbound_lane {
+				lane_data
+					.relayers
+					.retain(|entry| entry.end > outbound_lane.latest_received_nonce);
+			}
+
+			let mut messages = Vec::new();
+			for nonce in nonces_start..=nonces_end {
+				// the fee is only relevant to the bridged chain, so only the payload is decoded.
+				let payload: Vec<u8> = checker
+					.read(&storage_key::<T>(b"OutboundMessages", &(lane, nonce).encode()))?
+					.ok_or(Error::<T>::MissingMessage)?;
+				messages.push((
+					nonce,
+					<T::MessageDispatch as MessageDispatch>::Payload::decode(&mut &payload[..]),
+				));
+			}
+
+			let required_weight = messages.iter().fold(0 as Weight, |total, (_, payload)| {
+				let weight = payload.as_ref().map_or(0, T::MessageDispatch::dispatch_weight);
+				total.saturating_add(weight)
+			});
+			ensure!(required_weight <= dispatch_weight, Error::<T>::InsufficientDispatchWeight);
+
+			match lane_data.relayers.last_mut() {
+				Some(entry) if entry.relayer == relayer => entry.end = nonces_end,
+				_ => {
+					ensure!(
+						lane_data.relayers.len() < T::MaxUnrewardedRelayerEntries::get() as usize,
+						Error::<T>::TooManyUnrewardedRelayers
+					);
+					lane_data.relayers.push(UnrewardedRelayer {
+						relayer,
+						begin: nonces_start,
+						end: nonces_end,
+					});
+				},
+			}
+			lane_data.last_delivered_nonce = nonces_end;
+			InboundLanes::<T>::insert(&lane, lane_data);
+
+			for (nonce, payload) in messages {
+				let result = match payload {
+					Ok(payload) => with_transaction(|| {
+						let result = T::MessageDispatch::dispatch(lane, nonce, payload);
+						if result.is_ok() {
+							TransactionOutcome::Commit(result)
+						} else {
+							TransactionOutcome::Rollback(result)
+						}
+					}),
+					Err(_) => Err(DispatchError::Other("Failed to decode message payload")),
+				};
+				Self::deposit_event(Event::MessageDispatched(lane, nonce, result));
+			}
+
+			Ok(())
+		}
+
+		/// Confirm the delivery of messages sent through an outbound lane.
+		///
+		/// The dispatch origin of this call must be _Signed_. The fees of the confirmed
+		/// messages are credited to the relayers which delivered them.
+		///
+		/// Emits `MessagesDelivered`.
+		#[pallet::weight(receive_messages_delivery_proof_weight::<T>())]
+		pub fn receive_messages_delivery_proof(
+			origin: OriginFor<T>,
+			proof: MessagesDeliveryProof<BridgedHash<T>>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let MessagesDeliveryProof { bridged_header_hash, storage_proof, lane } = proof;
+
+			let checker = StorageProofChecker::<T>::new(&bridged_header_hash, storage_proof)?;
+			let inbound_lane: InboundLaneData<T::AccountId> = checker
+				.read(&storage_key::<T>(b"InboundLanes", &lane.encode()))?
+				.ok_or(Error::<T>::InvalidMessagesDeliveryProof)?;
+
+			let mut lane_data = OutboundLanes::<T>::get(&lane);
+			ensure!(
+				inbound_lane.last_delivered_nonce <= lane_data.latest_generated_nonce,
+				Error::<T>::InvalidMessagesDeliveryProof
+			);
+			ensure!(
+				inbound_lane.last_delivered_nonce > lane_data.latest_received_nonce,
+				Error::<T>::NothingToConfirm
+			);
+
+			let begin = lane_data.latest_received_nonce + 1;
+			let end = inbound_lane.last_delivered_nonce;
+			for entry in inbound_lane.relayers {
+				let reward = (entry.begin.max(begin)..=entry.end.min(end))
+					.filter_map(|nonce| OutboundMessages::<T>::get((lane, nonce)))
+					.fold(BalanceOf::<T>::zero(), |total, message| {
+						total.saturating_add(message.fee)
+					});
+				if !reward.is_zero() {
+					RelayerRewards::<T>::mutate(&entry.relayer, |total| {
+						*total = total.saturating_add(reward)
+					});
+				}
+			}
+			lane_data.latest_received_nonce = end;
+
+			let mut pruned = 0;
+			while lane_data.oldest_unpruned_nonce <= end &&
+				pruned < T::MaxMessagesToPruneAtOnce::get()
+			{
+				OutboundMessages::<T>::remove((lane, lane_data.oldest_unpruned_nonce));
+				lane_data.oldest_unpruned_nonce += 1;
+				pruned += 1;
+			}
+			OutboundLanes::<T>::insert(&lane, lane_data);
+
+			Self::deposit_event(Event::MessagesDelivered(lane, begin, end));
+			Ok(())
+		}
+
+		/// Claim the rewards credited to the relayer for delivering messages.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// Emits `RewardPaid`.
+		#[pallet::weight(T::WeightInfo::claim_rewards())]
+		#[transactional]
+		pub fn claim_rewards(origin: OriginFor<T>) -> DispatchResult {
+			let relayer = ensure_signed(origin)?;
+			let reward = RelayerRewards::<T>::take(&relayer);
+			ensure!(!reward.is_zero(), Error::<T>::NoRewards);
+
+			T::Currency::transfer(
+				&Self::account_id(),
+				&relayer,
+				reward,
+				ExistenceRequirement::AllowDeath,
+			)?;
+
+			Self::deposit_event(Event::RewardPaid(relayer, reward));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The account holding the delivery fees.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// Returns the state of the given outbound lane.
+	pub fn outbound_lane(lane: LaneId) -> OutboundLaneData {
+		OutboundLanes::<T>::get(lane)
+	}
+
+	/// Returns the state of the given inbound lane.
+	pub fn inbound_lane(lane: LaneId) -> InboundLaneData<T::AccountId> {
+		InboundLanes::<T>::get(lane)
+	}
+}
+
+/// The storage key of an entry of a map of this module on the bridged chain.
+///
+/// All maps of the module use the `Blake2_128Concat` hasher.
+pub fn storage_key<T: Config>(storage_name: &[u8], encoded_key: &[u8]) -> Vec<u8> {
+	let prefix = frame_support::storage::storage_prefix(
+		T::BridgedPalletName::get().as_bytes(),
+		storage_name,
+	);
+	let mut key = prefix.to_vec();
+	key.extend(Blake2_128Concat::hash(encoded_key));
+	key
+}
+
+/// Reads storage values of the bridged chain from a storage proof.
+struct StorageProofChecker<T: Config> {
+	root: BridgedHash<T>,
+	db: MemoryDB<<T::BridgedHeader as HeaderT>::Hashing>,
+}
+
+impl<T: Config> StorageProofChecker<T> {
+	/// Create a checker of a proof made at the finalized header of the bridged chain with the
+	/// given hash.
+	fn new(header_hash: &BridgedHash<T>, proof: Vec<Vec<u8>>) -> Result<Self, Error<T>> {
+		let root = T::BridgedHeaderChain::finalized_state_root(header_hash)
+			.ok_or(Error::<T>::UnknownBridgedHeader)?;
+		Ok(Self { root, db: StorageProof::new(proof).into_memory_db() })
+	}
+
+	/// Read and decode the value stored under the given key, `None` if no value is stored.
+	fn read<V: Decode>(&self, key: &[u8]) -> Result<Option<V>, Error<T>> {
+		let value = read_trie_value::<Layout<<T::BridgedHeader as HeaderT>::Hashing>, _>(
+			&self.db, &self.root, key,
+		)
+		.map_err(|e| {
+			log::debug!(target: LOG_TARGET, "Invalid storage proof: {:?}", e);
+			Error::<T>::InvalidStorageProof
+		})?;
+
+		value
+			.map(|value| V::decode(&mut &value[..]).map_err(|_| Error::<T>::InvalidProvenData))
+			.transpose()
+	}
+}
+
+/// The weight of `receive_messages_proof`.
+fn receive_messages_proof_weight<T: Config>(
+	proof: &MessagesProof<BridgedHash<T>>,
+	dispatch_weight: Weight,
+) -> Weight {
+	let messages = proof.nonces_end.saturating_sub(proof.nonces_start).saturating_add(1);
+	let proof_size = proof.storage_proof.iter().fold(0usize, |total, node| total + node.len());
+
+	T::WeightInfo::receive_messages_proof(
+		messages.min(u32::MAX as MessageNonce) as u32,
+		proof_size.min(u32::MAX as usize) as u32,
+	)
+	.saturating_add(dispatch_weight)
+}
+
+/// The weight of `receive_messages_delivery_proof`, assuming all unconfirmed messages of the
+/// lane are confirmed by distinct relayers.
+fn receive_messages_delivery_proof_weight<T: Config>() -> Weight {
+	let messages = T::MaxUnconfirmedMessages::get().min(u32::MAX as MessageNonce) as u32;
+	T::WeightInfo::receive_messages_delivery_proof(messages, T::MaxUnrewardedRelayerEntries::get())
+}
@@ -0,0 +1,241 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test environment for Message Lane pallet.
+
+use super::*;
+use crate as pallet_message_lane;
+
+use frame_support::{parameter_types, traits::GenesisBuild};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use sp_trie::{trie_types::TrieDBMut, TrieMut};
+use std::{cell::RefCell, collections::HashMap};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Messages: pallet_message_lane::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+thread_local! {
+	static BRIDGED_HEADERS: RefCell<HashMap<H256, Header>> = RefCell::new(HashMap::new());
+}
+
+/// The finalized headers of the bridged chain, as registered through `import_bridged_header`.
+pub struct TestHeaderChain;
+impl HeaderChain<Header> for TestHeaderChain {
+	fn finalized_header(hash: &H256) -> Option<Header> {
+		BRIDGED_HEADERS.with(|headers| headers.borrow().get(hash).cloned())
+	}
+}
+
+/// A payload dispatched with the given weight, which succeeds if the flag is set.
+pub type TestPayload = (Weight, bool);
+
+pub struct TestMessageDispatch;
+impl MessageDispatch for TestMessageDispatch {
+	type Payload = TestPayload;
+
+	fn dispatch_weight(payload: &TestPayload) -> Weight {
+		payload.0
+	}
+
+	fn dispatch(_lane: LaneId, _nonce: MessageNonce, payload: TestPayload) -> DispatchResult {
+		if payload.1 {
+			Ok(())
+		} else {
+			Err(DispatchError::Other("Test dispatch failure"))
+		}
+	}
+}
+
+parameter_types! {
+	pub const MessageLanePalletId: PalletId = PalletId(*b"py/msgln");
+	pub const BridgedPalletName: &'static str = "Messages";
+	pub const MaxMessagePayloadSize: u32 = 64;
+	pub const MaxUnconfirmedMessages: MessageNonce = 4;
+	pub const MaxUnrewardedRelayerEntries: u32 = 2;
+	pub const MaxMessagesToPruneAtOnce: MessageNonce = 2;
+}
+
+impl Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type PalletId = MessageLanePalletId;
+	type BridgedHeader = Header;
+	type BridgedHeaderChain = TestHeaderChain;
+	type BridgedPalletName = BridgedPalletName;
+	type MessageDispatch = TestMessageDispatch;
+	type MaxMessagePayloadSize = MaxMessagePayloadSize;
+	type MaxUnconfirmedMessages = MaxUnconfirmedMessages;
+	type MaxUnrewardedRelayerEntries = MaxUnrewardedRelayerEntries;
+	type MaxMessagesToPruneAtOnce = MaxMessagesToPruneAtOnce;
+	type WeightInfo = ();
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::benchmarking::Config for Test {
+	fn import_bridged_header(state_root: H256) -> H256 {
+		register_bridged_header(state_root)
+	}
+}
+
+/// The lane all tests exchange messages through.
+pub const LANE: LaneId = *b"test";
+
+/// The relayer delivering messages.
+pub const RELAYER: u64 = 10;
+
+/// Register a finalized header of the bridged chain with the given state root.
+fn register_bridged_header(state_root: H256) -> H256 {
+	let mut header = Header::new_from_number(BRIDGED_HEADERS.with(|h| h.borrow().len() as u64));
+	header.state_root = state_root;
+	let hash = header.hash();
+	BRIDGED_HEADERS.with(|headers| headers.borrow_mut().insert(hash, header));
+	hash
+}
+
+/// Register a finalized header of the bridged chain whose state consists of the given entries.
+///
+/// Returns the hash of the header and the trie nodes proving all the entries.
+pub fn import_bridged_header(entries: Vec<(Vec<u8>, Vec<u8>)>) -> (H256, Vec<Vec<u8>>) {
+	let mut db = MemoryDB::<BlakeTwo256>::default();
+	let mut root = H256::default();
+	{
+		let mut trie = TrieDBMut::<BlakeTwo256>::new(&mut db, &mut root);
+		for (key, value) in entries {
+			trie.insert(&key, &value).unwrap();
+		}
+	}
+
+	let hash = register_bridged_header(root);
+	let proof = db.drain().into_iter().map(|(_, (node, _))| node).collect();
+	(hash, proof)
+}
+
+/// The storage entry of a message queued in the outbound lane of the bridged chain.
+pub fn bridged_message(nonce: MessageNonce, payload: TestPayload) -> (Vec<u8>, Vec<u8>) {
+	(
+		storage_key::<Test>(b"OutboundMessages", &(LANE, nonce).encode()),
+		MessageData { payload: payload.encode(), fee: 1u64 }.encode(),
+	)
+}
+
+/// The storage entry of the outbound lane state of the bridged chain.
+pub fn bridged_outbound_lane(latest_received_nonce: MessageNonce) -> (Vec<u8>, Vec<u8>) {
+	let lane = OutboundLaneData { latest_received_nonce, ..Default::default() };
+	(storage_key::<Test>(b"OutboundLanes", &LANE.encode()), lane.encode())
+}
+
+/// The storage entry of the inbound lane state of the bridged chain.
+pub fn bridged_inbound_lane(
+	relayers: Vec<(u64, MessageNonce, MessageNonce)>,
+	last_delivered_nonce: MessageNonce,
+) -> (Vec<u8>, Vec<u8>) {
+	let lane = InboundLaneData {
+		relayers: relayers
+			.into_iter()
+			.map(|(relayer, begin, end)| UnrewardedRelayer { relayer, begin, end })
+			.collect(),
+		last_delivered_nonce,
+	};
+	(storage_key::<Test>(b"InboundLanes", &LANE.encode()), lane.encode())
+}
+
+/// A proof of the messages with the given nonces queued in the bridged chain's state.
+pub fn messages_proof(
+	entries: Vec<(Vec<u8>, Vec<u8>)>,
+	nonces_start: MessageNonce,
+	nonces_end: MessageNonce,
+) -> MessagesProof<H256> {
+	let (bridged_header_hash, storage_proof) = import_bridged_header(entries);
+	MessagesProof { bridged_header_hash, storage_proof, lane: LANE, nonces_start, nonces_end }
+}
+
+/// A proof of the inbound lane state in the bridged chain's state.
+pub fn delivery_proof(entries: Vec<(Vec<u8>, Vec<u8>)>) -> MessagesDeliveryProof<H256> {
+	let (bridged_header_hash, storage_proof) = import_bridged_header(entries);
+	MessagesDeliveryProof { bridged_header_hash, storage_proof, lane: LANE }
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 100), (2, 100)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,210 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the Message Lane pallet.
+
+use super::*;
+use crate::mock::*;
+
+use frame_support::{assert_noop, assert_ok};
+use sp_core::H256;
+
+fn send(sender: u64, fee: u64) -> DispatchResult {
+	Messages::send_message(Origin::signed(sender), LANE, vec![42; 8], fee)
+}
+
+#[test]
+fn send_message_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(send(1, 10));
+		assert_ok!(send(2, 20));
+
+		System::assert_last_event(crate::Event::MessageAccepted(LANE, 2).into());
+		assert_eq!(
+			OutboundMessages::<Test>::get((LANE, 1)),
+			Some(MessageData { payload: vec![42; 8], fee: 10 }),
+		);
+		assert_eq!(Messages::outbound_lane(LANE).latest_generated_nonce, 2);
+		assert_eq!(Balances::free_balance(1), 90);
+		assert_eq!(Balances::free_balance(Messages::account_id()), 30);
+	});
+}
+
+#[test]
+fn send_message_respects_limits() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Messages::send_message(Origin::signed(1), LANE, vec![0; 65], 1),
+			Error::<Test>::MessageTooLarge,
+		);
+		assert_noop!(send(3, 1), pallet_balances::Error::<Test>::InsufficientBalance,);
+
+		for _ in 0..MaxUnconfirmedMessages::get() {
+			assert_ok!(send(1, 1));
+		}
+		assert_noop!(send(1, 1), Error::<Test>::TooManyUnconfirmedMessages);
+	});
+}
+
+#[test]
+fn receive_messages_proof_dispatches_messages() {
+	new_test_ext().execute_with(|| {
+		let proof = messages_proof(
+			vec![bridged_message(1, (100, true)), bridged_message(2, (100, false))],
+			1,
+			2,
+		);
+		assert_ok!(Messages::receive_messages_proof(Origin::signed(RELAYER), proof, 200));
+
+		System::assert_has_event(crate::Event::MessageDispatched(LANE, 1, Ok(())).into());
+		// The message of `DispatchError::Other` is not part of the encoded event.
+		System::assert_last_event(
+			crate::Event::MessageDispatched(LANE, 2, Err(DispatchError::Other(""))).into(),
+		);
+		assert_eq!(
+			Messages::inbound_lane(LANE),
+			InboundLaneData {
+				relayers: vec![UnrewardedRelayer { relayer: RELAYER, begin: 1, end: 2 }],
+				last_delivered_nonce: 2,
+			},
+		);
+
+		// the relayer's entry is extended by the next delivery.
+		let proof = messages_proof(vec![bridged_message(3, (0, true))], 3, 3);
+		assert_ok!(Messages::receive_messages_proof(Origin::signed(RELAYER), proof, 0));
+		assert_eq!(
+			Messages::inbound_lane(LANE).relayers,
+			vec![UnrewardedRelayer { relayer: RELAYER, begin: 1, end: 3 }],
+		);
+	});
+}
+
+#[test]
+fn receive_messages_proof_rejects_invalid_proofs() {
+	new_test_ext().execute_with(|| {
+		let proof = messages_proof(vec![bridged_message(2, (0, true))], 2, 2);
+		assert_noop!(
+			Messages::receive_messages_proof(Origin::signed(RELAYER), proof, 0),
+			Error::<Test>::UnexpectedMessageNonces,
+		);
+
+		let proof = messages_proof(vec![bridged_message(1, (0, true))], 1, 2);
+		assert_noop!(
+			Messages::receive_messages_proof(Origin::signed(RELAYER), proof, 0),
+			Error::<Test>::MissingMessage,
+		);
+
+		let proof = messages_proof(vec![bridged_message(1, (100, true))], 1, 1);
+		assert_noop!(
+			Messages::receive_messages_proof(Origin::signed(RELAYER), proof, 99),
+			Error::<Test>::InsufficientDispatchWeight,
+		);
+
+		let mut proof = messages_proof(vec![bridged_message(1, (0, true))], 1, 1);
+		proof.bridged_header_hash = H256::repeat_byte(1);
+		assert_noop!(
+			Messages::receive_messages_proof(Origin::signed(RELAYER), proof, 0),
+			Error::<Test>::UnknownBridgedHeader,
+		);
+
+		let mut proof = messages_proof(vec![bridged_message(1, (0, true))], 1, 1);
+		proof.storage_proof.clear();
+		assert_noop!(
+			Messages::receive_messages_proof(Origin::signed(RELAYER), proof, 0),
+			Error::<Test>::InvalidStorageProof,
+		);
+	});
+}
+
+#[test]
+fn unrewarded_relayers_are_limited_and_pruned() {
+	new_test_ext().execute_with(|| {
+		for (relayer, nonce) in [(1, 1), (2, 2)] {
+			let proof = messages_proof(vec![bridged_message(nonce, (0, true))], nonce, nonce);
+			assert_ok!(Messages::receive_messages_proof(Origin::signed(relayer), proof, 0));
+		}
+
+		let proof = messages_proof(vec![bridged_message(3, (0, true))], 3, 3);
+		assert_noop!(
+			Messages::receive_messages_proof(Origin::signed(3), proof, 0),
+			Error::<Test>::TooManyUnrewardedRelayers,
+		);
+
+		// the bridged chain confirmed the delivery of the first message.
+		let proof =
+			messages_proof(vec![bridged_message(3, (0, true)), bridged_outbound_lane(1)], 3, 3);
+		assert_ok!(Messages::receive_messages_proof(Origin::signed(3), proof, 0));
+		assert_eq!(
+			Messages::inbound_lane(LANE).relayers,
+			vec![
+				UnrewardedRelayer { relayer: 2, begin: 2, end: 2 },
+				UnrewardedRelayer { relayer: 3, begin: 3, end: 3 },
+			],
+		);
+	});
+}
+
+#[test]
+fn receive_messages_delivery_proof_rewards_relayers() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(send(1, 10));
+		assert_ok!(send(1, 20));
+		assert_ok!(send(2, 30));
+
+		let proof = delivery_proof(vec![bridged_inbound_lane(vec![(10, 1, 1), (11, 2, 3)], 3)]);
+		assert_ok!(Messages::receive_messages_delivery_proof(Origin::signed(RELAYER), proof));
+
+		System::assert_last_event(crate::Event::MessagesDelivered(LANE, 1, 3).into());
+		assert_eq!(Messages::relayer_reward(10), 10);
+		assert_eq!(Messages::relayer_reward(11), 50);
+		// only `MaxMessagesToPruneAtOnce` messages are pruned.
+		assert_eq!(Messages::outbound_lane(LANE).oldest_unpruned_nonce, 3);
+		assert_eq!(OutboundMessages::<Test>::get((LANE, 2)), None);
+		assert!(OutboundMessages::<Test>::get((LANE, 3)).is_some());
+
+		assert_ok!(Messages::claim_rewards(Origin::signed(11)));
+		System::assert_last_event(crate::Event::RewardPaid(11, 50).into());
+		assert_eq!(Balances::free_balance(11), 50);
+		assert_eq!(Balances::free_balance(Messages::account_id()), 10);
+		assert_noop!(Messages::claim_rewards(Origin::signed(11)), Error::<Test>::NoRewards);
+	});
+}
+
+#[test]
+fn receive_messages_delivery_proof_rejects_invalid_proofs() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(send(1, 10));
+
+		let proof = delivery_proof(vec![bridged_inbound_lane(vec![(10, 1, 2)], 2)]);
+		assert_noop!(
+			Messages::receive_messages_delivery_proof(Origin::signed(RELAYER), proof),
+			Error::<Test>::InvalidMessagesDeliveryProof,
+		);
+
+		let proof = delivery_proof(vec![bridged_outbound_lane(0)]);
+		assert_noop!(
+			Messages::receive_messages_delivery_proof(Origin::signed(RELAYER), proof),
+			Error::<Test>::InvalidMessagesDeliveryProof,
+		);
+
+		let proof = delivery_proof(vec![bridged_inbound_lane(vec![], 0)]);
+		assert_noop!(
+			Messages::receive_messages_delivery_proof(Origin::signed(RELAYER), proof),
+			Error::<Test>::NothingToConfirm,
+		);
+	});
+}
@@ -0,0 +1,155 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Autogenerated weights for pallet_message_lane
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2026-10-17, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+
+// Executed Command:
+// ./target/release/substrate
+// benchmark
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_message_lane
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --output=./frame/message-lane/src/weights.rs
+// --template=./.maintain/frame-weight-template.hbs
+
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_message_lane.
+pub trait WeightInfo {
+	fn send_message(p: u32, ) -> Weight;
+	fn receive_messages_proof(m: u32, p: u32, ) -> Weight;
+	fn receive_messages_delivery_proof(m: u32, r: u32, ) -> Weight;
+	fn claim_rewards() -> Weight;
+}
+
+/// Weights for pallet_message_lane using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: MessageLane OutboundLanes (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Balances AccountVersions (r:1 w:1)
+	// Storage: System EventTopics (r:2 w:2)
+	// Storage: MessageLane OutboundMessages (r:0 w:1)
+	fn send_message(p: u32, ) -> Weight {
+		(140_968_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((1_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: MessageLane InboundLanes (r:1 w:1)
+	// Storage: GrandpaBridge ImportedHeaders (r:1 w:0)
+	fn receive_messages_proof(m: u32, p: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 251_000
+			.saturating_add((20_276_000 as Weight).saturating_mul(m as Weight))
+			// Standard Error: 0
+			.saturating_add((7_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: GrandpaBridge ImportedHeaders (r:1 w:0)
+	// Storage: MessageLane OutboundLanes (r:1 w:1)
+	// Storage: MessageLane OutboundMessages (r:1 w:1)
+	// Storage: MessageLane RelayerRewards (r:1 w:1)
+	fn receive_messages_delivery_proof(m: u32, r: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 127_000
+			.saturating_add((7_267_000 as Weight).saturating_mul(m as Weight))
+			// Standard Error: 1_468_000
+			.saturating_add((6_473_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(m as Weight)))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+	}
+	// Storage: MessageLane RelayerRewards (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Balances AccountVersions (r:1 w:1)
+	// Storage: System EventTopics (r:2 w:2)
+	fn claim_rewards() -> Weight {
+		(153_730_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: MessageLane OutboundLanes (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Balances AccountVersions (r:1 w:1)
+	// Storage: System EventTopics (r:2 w:2)
+	// Storage: MessageLane OutboundMessages (r:0 w:1)
+	fn send_message(p: u32, ) -> Weight {
+		(140_968_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((1_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	// Storage: MessageLane InboundLanes (r:1 w:1)
+	// Storage: GrandpaBridge ImportedHeaders (r:1 w:0)
+	fn receive_messages_proof(m: u32, p: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 251_000
+			.saturating_add((20_276_000 as Weight).saturating_mul(m as Weight))
+			// Standard Error: 0
+			.saturating_add((7_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: GrandpaBridge ImportedHeaders (r:1 w:0)
+	// Storage: MessageLane OutboundLanes (r:1 w:1)
+	// Storage: MessageLane OutboundMessages (r:1 w:1)
+	// Storage: MessageLane RelayerRewards (r:1 w:1)
+	fn receive_messages_delivery_proof(m: u32, r: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 127_000
+			.saturating_add((7_267_000 as Weight).saturating_mul(m as Weight))
+			// Standard Error: 1_468_000
+			.saturating_add((6_473_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(m as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+	}
+	// Storage: MessageLane RelayerRewards (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Balances AccountVersions (r:1 w:1)
+	// Storage: System EventTopics (r:2 w:2)
+	fn claim_rewards() -> Weight {
+		(153_730_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+}
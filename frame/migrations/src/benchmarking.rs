@@ -0,0 +1,47 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Migrations pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_system::RawOrigin;
+
+benchmarks! {
+	force_set_cursor {
+		let cursor = MigrationCursor::Active(0, Some(vec![0u8; 32]));
+	}: _(RawOrigin::Root, Some(cursor.clone()))
+	verify {
+		assert_eq!(Cursor::<T>::get(), Some(cursor));
+	}
+
+	clear_historic {
+		let n in 0 .. 256;
+
+		let ids = (0..n).map(|i| i.encode()).collect::<Vec<_>>();
+		for id in &ids {
+			Historic::<T>::insert(id, ());
+		}
+	}: _(RawOrigin::Root, ids.clone())
+	verify {
+		assert!(ids.iter().all(|id| !Historic::<T>::contains_key(id)));
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,411 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Migrations Module
+//!
+//! A module for executing storage migrations which span multiple blocks.
+//!
+//! ## Overview
+//!
+//! The runtime registers a list of [`SteppedMigration`]s through `Config::Migrations`. On a
+//! runtime upgrade the module starts executing them in order. In `on_initialize` of every block,
+//! the current migration is stepped with at most `MaxServiceWeight`; a migration which is not
+//! completed returns a cursor, which is stored and passed to its next step in a later block.
+//!
+//! Completed migrations are remembered by their identifier and are skipped by later upgrades.
+//!
+//! While migrations are ongoing, [`PauseDuringMigrations`] can be used as the base call filter of
+//! the system pallet to stop the inclusion of transactions, since they would otherwise operate on
+//! partially migrated storage.
+//!
+//! If a migration fails, `Config::FailedMigrationHandler` decides whether the chain stays stuck
+//! (with transactions paused), resumes normal operation or continues with the next migration.
+//! A stuck chain can be recovered by the root origin through `force_set_cursor`.
+//!
+//! ## Related Modules
+//!
+//! * [`System`](../frame_system/index.html)
+//! * [`Support`](../frame_support/index.html)
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use codec::{Decode, Encode, FullCodec};
+use frame_support::{
+	traits::{Contains, Get},
+	weights::{DispatchClass, GetDispatchInfo, Weight},
+};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::{marker::PhantomData, prelude::*};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+pub(crate) const LOG_TARGET: &'static str = "runtime::migrations";
+
+/// An error which occurred while stepping a migration.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum SteppedMigrationError {
+	/// The weight limit is too low for the migration to make any progress.
+	InsufficientWeight,
+	/// The migration failed and cannot be continued.
+	Failed,
+}
+
+/// A migration which is executed in steps, possibly across multiple blocks.
+pub trait SteppedMigration {
+	/// The cursor from which the migration is resumed at its next step.
+	type Cursor: FullCodec;
+
+	/// The unique identifier of the migration.
+	///
+	/// Completed migrations are remembered by their identifier and never executed again.
+	fn id() -> Vec<u8>;
+
+	/// Execute as much of the migration as `limit` allows, starting from `cursor`.
+	///
+	/// `cursor` is `None` when the migration is started. Returns the cursor to resume from at the
+	/// next step, or `None` if the migration is completed, along with the consumed weight.
+	fn step(
+		cursor: Option<Self::Cursor>,
+		limit: Weight,
+	) -> Result<(Option<Self::Cursor>, Weight), SteppedMigrationError>;
+
+	/// Same as [`Self::step`], but with an encoded cursor.
+	fn step_encoded(
+		cursor: Option<Vec<u8>>,
+		limit: Weight,
+	) -> Result<(Option<Vec<u8>>, Weight), SteppedMigrationError> {
+		let cursor = match cursor {
+			Some(cursor) => Some(
+				Self::Cursor::decode(&mut &cursor[..])
+					.map_err(|_| SteppedMigrationError::Failed)?,
+			),
+			None => None,
+		};
+		Self::step(cursor, limit).map(|(cursor, weight)| (cursor.map(|c| c.encode()), weight))
+	}
+}
+
+/// An ordered list of [`SteppedMigration`]s, with their cursors in encoded form.
+pub trait SteppedMigrations {
+	/// The identifier of the `n`th migration, or `None` if there are less than `n + 1`.
+	fn nth_id(n: u32) -> Option<Vec<u8>>;
+
+	/// Step the `n`th migration, or return `None` if there are less than `n + 1`.
+	fn nth_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		limit: Weight,
+	) -> Option<Result<(Option<Vec<u8>>, Weight), SteppedMigrationError>>;
+
+	/// The number of migrations.
+	fn len() -> u32;
+}
+
+type StepFn =
+	fn(Option<Vec<u8>>, Weight) -> Result<(Option<Vec<u8>>, Weight), SteppedMigrationError>;
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+#[tuple_types_custom_trait_bound(SteppedMigration)]
+impl SteppedMigrations for Tuple {
+	fn nth_id(n: u32) -> Option<Vec<u8>> {
+		let ids: &[fn() -> Vec<u8>] = &[for_tuples!( #( Tuple::id ),* )];
+		ids.get(n as usize).map(|id| id())
+	}
+
+	fn nth_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		limit: Weight,
+	) -> Option<Result<(Option<Vec<u8>>, Weight), SteppedMigrationError>> {
+		let steps: &[StepFn] = &[for_tuples!( #( Tuple::step_encoded ),* )];
+		steps.get(n as usize).map(|step| step(cursor, limit))
+	}
+
+	fn len() -> u32 {
+		let ids: &[fn() -> Vec<u8>] = &[for_tuples!( #( Tuple::id ),* )];
+		ids.len() as u32
+	}
+}
+
+/// The progress of the ongoing migrations.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum MigrationCursor {
+	/// The migration with the given index is executed next, resuming from the encoded cursor.
+	Active(u32, Option<Vec<u8>>),
+	/// A migration failed and the remaining migrations are not executed.
+	Stuck,
+}
+
+/// How to proceed after a migration failed.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum FailedMigrationHandling {
+	/// Stay stuck, with transactions paused, until the cursor is changed by `force_set_cursor`.
+	KeepStuck,
+	/// Abort the remaining migrations and resume normal operation.
+	ForceUnstuck,
+	/// Ignore the failure and continue with the next migration.
+	Ignore,
+}
+
+/// Decides how to proceed after a migration failed.
+pub trait FailedMigrationHandler {
+	/// The migration with the given index failed.
+	fn failed(migration: u32) -> FailedMigrationHandling;
+}
+
+/// Keeps the chain stuck on failures.
+impl FailedMigrationHandler for () {
+	fn failed(_: u32) -> FailedMigrationHandling {
+		FailedMigrationHandling::KeepStuck
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The migrations to execute, in order.
+		type Migrations: SteppedMigrations;
+
+		/// The maximum weight spent on migrations in a single block.
+		#[pallet::constant]
+		type MaxServiceWeight: Get<Weight>;
+
+		/// Decides how to proceed after a migration failed.
+		type FailedMigrationHandler: FailedMigrationHandler;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// The progress of the ongoing migrations, if any.
+	#[pallet::storage]
+	#[pallet::getter(fn cursor)]
+	pub type Cursor<T: Config> = StorageValue<_, MigrationCursor>;
+
+	/// The identifiers of the completed migrations.
+	#[pallet::storage]
+	pub type Historic<T: Config> = StorageMap<_, Twox64Concat, Vec<u8>, ()>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event {
+		/// A runtime upgrade started the execution of migrations. \[migrations\]
+		UpgradeStarted(u32),
+		/// All migrations have been executed.
+		UpgradeCompleted,
+		/// A migration failed and the remaining migrations are not executed.
+		UpgradeFailed,
+		/// A migration progressed, but is not completed yet. \[index\]
+		MigrationAdvanced(u32),
+		/// A migration was completed. \[index\]
+		MigrationCompleted(u32),
+		/// A migration was skipped, since it has been completed before. \[index\]
+		MigrationSkipped(u32),
+		/// A migration failed. \[index\]
+		MigrationFailed(u32),
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Cursor::<T>::exists() {
+				log::error!(
+					target: LOG_TARGET,
+					"runtime upgraded while migrations are ongoing; keeping the current cursor",
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let migrations = T::Migrations::len();
+			if migrations > 0 {
+				Cursor::<T>::put(MigrationCursor::Active(0, None));
+				Self::deposit_event(Event::UpgradeStarted(migrations));
+			}
+			T::DbWeight::get().reads_writes(1, 1)
+		}
+
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			Self::progress_migrations(T::MaxServiceWeight::get())
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Overwrite the migration cursor, or remove it if `cursor` is `None`.
+		///
+		/// Removing the cursor aborts the ongoing migrations and resumes normal operation.
+		///
+		/// The dispatch origin of this call must be _Root_.
+		#[pallet::weight(T::WeightInfo::force_set_cursor())]
+		pub fn force_set_cursor(
+			origin: OriginFor<T>,
+			cursor: Option<MigrationCursor>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			Cursor::<T>::set(cursor);
+			Ok(())
+		}
+
+		/// Forget that the migrations with the given identifiers have been completed, so that
+		/// they are executed again.
+		///
+		/// The dispatch origin of this call must be _Root_.
+		#[pallet::weight(T::WeightInfo::clear_historic(ids.len() as u32))]
+		pub fn clear_historic(origin: OriginFor<T>, ids: Vec<Vec<u8>>) -> DispatchResult {
+			ensure_root(origin)?;
+			for id in ids {
+				Historic::<T>::remove(id);
+			}
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Whether migrations are ongoing, including when a migration failed and the chain is stuck.
+	pub fn ongoing() -> bool {
+		Cursor::<T>::exists()
+	}
+
+	/// Progress the ongoing migrations, spending at most `limit` weight.
+	///
+	/// Returns the consumed weight.
+	fn progress_migrations(limit: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		let mut weight = db_weight.reads(1);
+		let (mut index, mut inner) = match Cursor::<T>::get() {
+			Some(MigrationCursor::Active(index, inner)) => (index, inner),
+			_ => return weight,
+		};
+
+		let mut first_step = true;
+		loop {
+			weight = weight.saturating_add(db_weight.reads(1));
+			let id = match T::Migrations::nth_id(index) {
+				Some(id) => id,
+				None => {
+					Cursor::<T>::kill();
+					Self::deposit_event(Event::UpgradeCompleted);
+					return weight.saturating_add(db_weight.writes(1))
+				},
+			};
+
+			if inner.is_none() && Historic::<T>::contains_key(&id) {
+				Self::deposit_event(Event::MigrationSkipped(index));
+				index += 1;
+				continue
+			}
+
+			let remaining = limit.saturating_sub(weight);
+			let result = T::Migrations::nth_step(index, inner.clone(), remaining)
+				.unwrap_or(Err(SteppedMigrationError::Failed));
+			match result {
+				Ok((Some(cursor), consumed)) => {
+					Cursor::<T>::put(MigrationCursor::Active(index, Some(cursor)));
+					Self::deposit_event(Event::MigrationAdvanced(index));
+					return weight.saturating_add(consumed).saturating_add(db_weight.writes(1))
+				},
+				Ok((None, consumed)) => {
+					weight = weight.saturating_add(consumed).saturating_add(db_weight.writes(1));
+					Historic::<T>::insert(&id, ());
+					Self::deposit_event(Event::MigrationCompleted(index));
+					index += 1;
+					inner = None;
+				},
+				// Resume in the next block, which offers the full `limit` again.
+				Err(SteppedMigrationError::InsufficientWeight) if !first_step => {
+					Cursor::<T>::put(MigrationCursor::Active(index, inner));
+					return weight.saturating_add(db_weight.writes(1))
+				},
+				Err(_) => {
+					log::error!(target: LOG_TARGET, "migration {} failed", index);
+					weight = weight.saturating_add(db_weight.writes(1));
+					if !Self::on_migration_failed(index) {
+						return weight
+					}
+					// The failed migration is ignored, carry on with the next one.
+					index += 1;
+					inner = None;
+				},
+			}
+			first_step = false;
+		}
+	}
+
+	/// Update the cursor as decided by the `FailedMigrationHandler`.
+	///
+	/// Returns whether the remaining migrations should still be progressed.
+	fn on_migration_failed(index: u32) -> bool {
+		Self::deposit_event(Event::MigrationFailed(index));
+		match T::FailedMigrationHandler::failed(index) {
+			FailedMigrationHandling::KeepStuck => {
+				Cursor::<T>::put(MigrationCursor::Stuck);
+				Self::deposit_event(Event::UpgradeFailed);
+				false
+			},
+			FailedMigrationHandling::ForceUnstuck => {
+				Cursor::<T>::kill();
+				Self::deposit_event(Event::UpgradeFailed);
+				false
+			},
+			FailedMigrationHandling::Ignore => true,
+		}
+	}
+}
+
+/// A call filter which pauses transactions while migrations are ongoing.
+///
+/// Intended as `frame_system::Config::BaseCallFilter`. Calls not contained in `Base` are always
+/// filtered. While migrations are ongoing, only mandatory calls (i.e. inherents) and calls
+/// contained in `Allowed` are accepted.
+pub struct PauseDuringMigrations<T, Base, Allowed>(PhantomData<(T, Base, Allowed)>);
+
+impl<T, Base, Allowed, Call> Contains<Call> for PauseDuringMigrations<T, Base, Allowed>
+where
+	T: Config,
+	Base: Contains<Call>,
+	Allowed: Contains<Call>,
+	Call: GetDispatchInfo,
+{
+	fn contains(call: &Call) -> bool {
+		Base::contains(call) &&
+			(!Pallet::<T>::ongoing() ||
+				call.get_dispatch_info().class == DispatchClass::Mandatory ||
+				Allowed::contains(call))
+	}
+}
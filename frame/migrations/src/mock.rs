@@ -0,0 +1,166 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test environment for Migrations pallet.
+
+use super::*;
+use crate as pallet_migrations;
+
+use frame_support::{
+	parameter_types,
+	traits::{Everything, Hooks, Nothing},
+};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Migrations: pallet_migrations::{Pallet, Call, Storage, Event},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+impl frame_system::Config for Test {
+	type BaseCallFilter = PauseDuringMigrations<Test, Everything, Nothing>;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+/// The weight consumed by every step of a mocked migration.
+pub const STEP_WEIGHT: Weight = 10;
+
+parameter_types! {
+	/// The number of steps needed by each mocked migration, or `None` if it fails.
+	pub static MigrationSteps: Vec<Option<u32>> = vec![Some(2), Some(1)];
+	pub static FailureHandling: FailedMigrationHandling = FailedMigrationHandling::KeepStuck;
+	pub const MaxServiceWeight: Weight = 100;
+}
+
+/// Execute one step of the `index`th mocked migration, as configured by `MigrationSteps`.
+fn mock_step(
+	index: usize,
+	cursor: Option<u32>,
+	limit: Weight,
+) -> Result<(Option<u32>, Weight), SteppedMigrationError> {
+	if limit < STEP_WEIGHT {
+		return Err(SteppedMigrationError::InsufficientWeight)
+	}
+	let steps = MigrationSteps::get()[index].ok_or(SteppedMigrationError::Failed)?;
+	let done = cursor.unwrap_or(0) + 1;
+	Ok((if done < steps { Some(done) } else { None }, STEP_WEIGHT))
+}
+
+pub struct MigrationA;
+impl SteppedMigration for MigrationA {
+	type Cursor = u32;
+
+	fn id() -> Vec<u8> {
+		b"A".to_vec()
+	}
+
+	fn step(
+		cursor: Option<u32>,
+		limit: Weight,
+	) -> Result<(Option<u32>, Weight), SteppedMigrationError> {
+		mock_step(0, cursor, limit)
+	}
+}
+
+pub struct MigrationB;
+impl SteppedMigration for MigrationB {
+	type Cursor = u32;
+
+	fn id() -> Vec<u8> {
+		b"B".to_vec()
+	}
+
+	fn step(
+		cursor: Option<u32>,
+		limit: Weight,
+	) -> Result<(Option<u32>, Weight), SteppedMigrationError> {
+		mock_step(1, cursor, limit)
+	}
+}
+
+pub struct MockFailedMigrationHandler;
+impl FailedMigrationHandler for MockFailedMigrationHandler {
+	fn failed(_: u32) -> FailedMigrationHandling {
+		FailureHandling::get()
+	}
+}
+
+impl Config for Test {
+	type Event = Event;
+	type Migrations = (MigrationA, MigrationB);
+	type MaxServiceWeight = MaxServiceWeight;
+	type FailedMigrationHandler = MockFailedMigrationHandler;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Run `on_initialize` of the migrations pallet for the next block.
+pub fn next_block() {
+	let n = System::block_number() + 1;
+	System::set_block_number(n);
+	Migrations::on_initialize(n);
+}
+
+pub fn migration_events() -> Vec<pallet_migrations::Event> {
+	System::events()
+		.into_iter()
+		.filter_map(|r| if let Event::Migrations(inner) = r.event { Some(inner) } else { None })
+		.collect()
+}
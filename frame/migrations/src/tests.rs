@@ -0,0 +1,181 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for Migrations pallet.
+
+use super::{Event, *};
+use crate::mock::{Call, *};
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use sp_runtime::traits::BadOrigin;
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark { remark: vec![] })
+}
+
+fn call_allowed() -> bool {
+	<Test as frame_system::Config>::BaseCallFilter::contains(&remark())
+}
+
+#[test]
+fn migrations_are_executed_across_blocks() {
+	new_test_ext().execute_with(|| {
+		assert!(!Migrations::ongoing());
+		Migrations::on_runtime_upgrade();
+		assert_eq!(Migrations::cursor(), Some(MigrationCursor::Active(0, None)));
+
+		// the first migration needs two steps.
+		next_block();
+		assert_eq!(Migrations::cursor(), Some(MigrationCursor::Active(0, Some(1u32.encode()))));
+		assert!(!Historic::<Test>::contains_key(b"A".to_vec()));
+
+		// the second one is completed in the same block as the first one.
+		next_block();
+		assert_eq!(Migrations::cursor(), None);
+		assert!(Historic::<Test>::contains_key(b"A".to_vec()));
+		assert!(Historic::<Test>::contains_key(b"B".to_vec()));
+		assert_eq!(
+			migration_events(),
+			vec![
+				Event::UpgradeStarted(2),
+				Event::MigrationAdvanced(0),
+				Event::MigrationCompleted(0),
+				Event::MigrationCompleted(1),
+				Event::UpgradeCompleted,
+			]
+		);
+
+		// nothing happens in later blocks.
+		next_block();
+		assert_eq!(migration_events().len(), 5);
+	});
+}
+
+#[test]
+fn on_initialize_respects_max_service_weight() {
+	new_test_ext().execute_with(|| {
+		MigrationSteps::set(vec![Some(1), Some(1)]);
+		Migrations::on_runtime_upgrade();
+
+		assert_eq!(Migrations::on_initialize(2), 2 * STEP_WEIGHT);
+		assert_eq!(Migrations::cursor(), None);
+
+		// only enough weight for a single step.
+		assert_ok!(Migrations::clear_historic(Origin::root(), vec![b"A".to_vec(), b"B".to_vec()]));
+		Migrations::on_runtime_upgrade();
+		assert_eq!(Migrations::progress_migrations(STEP_WEIGHT), STEP_WEIGHT);
+		assert_eq!(Migrations::cursor(), Some(MigrationCursor::Active(1, None)));
+	});
+}
+
+#[test]
+fn transactions_are_paused_during_migrations() {
+	new_test_ext().execute_with(|| {
+		assert!(call_allowed());
+
+		Migrations::on_runtime_upgrade();
+		assert!(!call_allowed());
+
+		next_block();
+		assert!(!call_allowed());
+
+		next_block();
+		assert!(call_allowed());
+	});
+}
+
+#[test]
+fn failed_migration_keeps_chain_stuck() {
+	new_test_ext().execute_with(|| {
+		MigrationSteps::set(vec![None, Some(1)]);
+		Migrations::on_runtime_upgrade();
+
+		next_block();
+		assert_eq!(Migrations::cursor(), Some(MigrationCursor::Stuck));
+		assert!(!Historic::<Test>::contains_key(b"B".to_vec()));
+		assert!(!call_allowed());
+
+		// stuck migrations are not progressed.
+		next_block();
+		assert_eq!(Migrations::cursor(), Some(MigrationCursor::Stuck));
+		assert_eq!(
+			migration_events(),
+			vec![Event::UpgradeStarted(2), Event::MigrationFailed(0), Event::UpgradeFailed]
+		);
+
+		// governance can abort the migrations.
+		assert_noop!(Migrations::force_set_cursor(Origin::signed(1), None), BadOrigin);
+		assert_ok!(Migrations::force_set_cursor(Origin::root(), None));
+		assert!(call_allowed());
+	});
+}
+
+#[test]
+fn failed_migration_can_be_ignored() {
+	new_test_ext().execute_with(|| {
+		MigrationSteps::set(vec![None, Some(1)]);
+		FailureHandling::set(FailedMigrationHandling::Ignore);
+		Migrations::on_runtime_upgrade();
+
+		next_block();
+		assert_eq!(Migrations::cursor(), None);
+		assert!(!Historic::<Test>::contains_key(b"A".to_vec()));
+		assert!(Historic::<Test>::contains_key(b"B".to_vec()));
+		assert_eq!(
+			migration_events(),
+			vec![
+				Event::UpgradeStarted(2),
+				Event::MigrationFailed(0),
+				Event::MigrationCompleted(1),
+				Event::UpgradeCompleted,
+			]
+		);
+	});
+}
+
+#[test]
+fn failed_migration_can_force_unstuck() {
+	new_test_ext().execute_with(|| {
+		MigrationSteps::set(vec![None, Some(1)]);
+		FailureHandling::set(FailedMigrationHandling::ForceUnstuck);
+		Migrations::on_runtime_upgrade();
+
+		next_block();
+		assert_eq!(Migrations::cursor(), None);
+		assert!(!Historic::<Test>::contains_key(b"B".to_vec()));
+		assert!(call_allowed());
+	});
+}
+
+#[test]
+fn completed_migrations_are_skipped() {
+	new_test_ext().execute_with(|| {
+		Historic::<Test>::insert(b"A".to_vec(), ());
+		Migrations::on_runtime_upgrade();
+
+		next_block();
+		assert_eq!(Migrations::cursor(), None);
+		assert_eq!(
+			migration_events(),
+			vec![
+				Event::UpgradeStarted(2),
+				Event::MigrationSkipped(0),
+				Event::MigrationCompleted(1),
+				Event::UpgradeCompleted,
+			]
+		);
+	});
+}
@@ -91,6 +91,7 @@ impl pallet_timestamp::Config for Test {
 impl pallet_session::historical::Config for Test {
 	type FullIdentification = pallet_staking::Exposure<AccountId, Balance>;
 	type FullIdentificationOf = pallet_staking::ExposureOf<Test>;
+	type LegacyFullIdentification = ();
 }
 
 sp_runtime::impl_opaque_keys! {
@@ -146,6 +147,7 @@ pallet_staking_reward_curve::build! {
 parameter_types! {
 	pub const RewardCurve: &'static sp_runtime::curve::PiecewiseLinear<'static> = &I_NPOS;
 	pub const MaxNominatorRewardedPerValidator: u32 = 64;
+	pub const MaxSelfDisablesPerEra: u32 = 2;
 	pub const MaxKeys: u32 = 10_000;
 	  pub const MaxPeerInHeartbeats: u32 = 10_000;
 	  pub const MaxPeerDataEncodingSize: u32 = 1_000;
@@ -172,9 +174,11 @@ impl pallet_staking::Config for Test {
 	type SlashCancelOrigin = frame_system::EnsureRoot<Self::AccountId>;
 	type BondingDuration = ();
 	type SessionInterface = Self;
+	type DisablingStrategy = ();
 	type EraPayout = pallet_staking::ConvertCurve<RewardCurve>;
 	type NextNewSession = Session;
 	type MaxNominatorRewardedPerValidator = MaxNominatorRewardedPerValidator;
+	type MaxSelfDisablesPerEra = MaxSelfDisablesPerEra;
 	type ElectionProvider = onchain::OnChainSequentialPhragmen<Self>;
 	type GenesisElectionProvider = Self::ElectionProvider;
 	type SortedListProvider = pallet_staking::UseNominatorsMap<Self>;
@@ -0,0 +1,39 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parameters pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::{EnsureOrigin, UnfilteredDispatchable};
+
+benchmarks! {
+	set_parameter {
+		let (key, value) = T::Key::benchmark_parameter();
+		// Replacing a value is the worst case, as the old value is read and emitted.
+		Parameters::<T>::insert(&key, &value);
+		let origin = T::AdminOrigin::successful_origin();
+		let call = Call::<T>::set_parameter { key: key.clone(), value: Some(value.clone()) };
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(Pallet::<T>::parameter(&key), Some(value));
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(vec![]), crate::mock::Test);
@@ -0,0 +1,187 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Parameters Module
+//!
+//! A module for storing runtime parameters which can be updated without a runtime upgrade.
+//!
+//! ## Overview
+//!
+//! The runtime defines the set of parameters through two types: a `Key` type (usually an enum
+//! with a variant per parameter) and a `Value` type (usually an enum with a variant per kind of
+//! value). The pairing of both is checked through [`ParameterKey::accepts`], so that a parameter
+//! can only ever hold a value of the kind it expects.
+//!
+//! The value of a parameter can be set or removed by `AdminOrigin` through `set_parameter`.
+//!
+//! Other pallets read parameters through the [`Dynamic`] adapter, which implements `Get<V>` and
+//! falls back to a default value as long as the parameter has not been set. This allows values
+//! such as deposits or periods, usually configured as constants, to be changed by governance.
+//!
+//! ## Related Modules
+//!
+//! * [`System`](../frame_system/index.html)
+//! * [`Support`](../frame_support/index.html)
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use frame_support::traits::Get;
+use sp_std::{convert::TryInto, marker::PhantomData, prelude::*};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// The keys of a set of runtime parameters, each of which accepts a specific kind of value.
+pub trait ParameterKey<Value> {
+	/// Returns `true` if `value` is of the kind held by the parameter `self`.
+	fn accepts(&self, value: &Value) -> bool;
+
+	/// A parameter and a value it accepts, used by the benchmarks.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn benchmark_parameter() -> (Self, Value)
+	where
+		Self: Sized;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::MaybeSerializeDeserialize;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The key identifying a parameter.
+		type Key: Parameter
+			+ Member
+			+ MaxEncodedLen
+			+ MaybeSerializeDeserialize
+			+ ParameterKey<Self::Value>;
+
+		/// The value of a parameter.
+		type Value: Parameter + Member + MaxEncodedLen + MaybeSerializeDeserialize;
+
+		/// The origin which may update the parameters.
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::generate_storage_info]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// The value of each parameter that has been set.
+	#[pallet::storage]
+	#[pallet::getter(fn parameter)]
+	pub type Parameters<T: Config> = StorageMap<_, Blake2_128Concat, T::Key, T::Value>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A parameter was set or removed. \[key, old_value, new_value\]
+		Updated(T::Key, Option<T::Value>, Option<T::Value>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The value is not of the kind held by the parameter.
+		InvalidValue,
+	}
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The initial value of the parameters.
+		pub parameters: Vec<(T::Key, T::Value)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { parameters: Default::default() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (key, value) in &self.parameters {
+				assert!(key.accepts(value), "invalid value for parameter {:?} in genesis", key);
+				Parameters::<T>::insert(key, value);
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the value of the parameter `key` to `value`, or remove it if `value` is `None`.
+		///
+		/// Once removed, readers of the parameter fall back to their default value.
+		///
+		/// The dispatch origin of this call must be `AdminOrigin`.
+		///
+		/// Emits `Updated`.
+		#[pallet::weight(T::WeightInfo::set_parameter())]
+		pub fn set_parameter(
+			origin: OriginFor<T>,
+			key: T::Key,
+			value: Option<T::Value>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			if let Some(ref value) = value {
+				ensure!(key.accepts(value), Error::<T>::InvalidValue);
+			}
+
+			let old_value = Parameters::<T>::mutate_exists(&key, |maybe_value| {
+				sp_std::mem::replace(maybe_value, value.clone())
+			});
+			Self::deposit_event(Event::Updated(key, old_value, value));
+			Ok(())
+		}
+	}
+}
+
+/// A `Get<V>` implementation returning the value of the parameter with key `Key`, or
+/// `DefaultValue` if the parameter has not been set or does not hold a value of type `V`.
+pub struct Dynamic<T, Key, DefaultValue>(PhantomData<(T, Key, DefaultValue)>);
+
+impl<T, Key, DefaultValue, V> Get<V> for Dynamic<T, Key, DefaultValue>
+where
+	T: Config,
+	T::Value: TryInto<V>,
+	Key: Get<T::Key>,
+	DefaultValue: Get<V>,
+{
+	fn get() -> V {
+		Parameters::<T>::get(Key::get())
+			.and_then(|value| value.try_into().ok())
+			.unwrap_or_else(DefaultValue::get)
+	}
+}
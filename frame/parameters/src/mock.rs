@@ -0,0 +1,172 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test environment for Parameters pallet.
+
+use super::*;
+use crate as pallet_parameters;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{parameter_types, traits::GenesisBuild};
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	RuntimeDebug,
+};
+use sp_std::convert::TryFrom;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Parameters: pallet_parameters::{Pallet, Call, Config<T>, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+#[derive(
+	Encode,
+	Decode,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	MaxEncodedLen,
+	TypeInfo,
+	serde::Serialize,
+	serde::Deserialize,
+)]
+pub enum Key {
+	Deposit,
+	Period,
+}
+
+#[derive(
+	Encode,
+	Decode,
+	Clone,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	MaxEncodedLen,
+	TypeInfo,
+	serde::Serialize,
+	serde::Deserialize,
+)]
+pub enum Value {
+	Balance(u128),
+	BlockNumber(u64),
+}
+
+impl ParameterKey<Value> for Key {
+	fn accepts(&self, value: &Value) -> bool {
+		match (self, value) {
+			(Key::Deposit, Value::Balance(_)) | (Key::Period, Value::BlockNumber(_)) => true,
+			_ => false,
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn benchmark_parameter() -> (Self, Value) {
+		(Key::Deposit, Value::Balance(1))
+	}
+}
+
+impl TryFrom<Value> for u128 {
+	type Error = ();
+	fn try_from(value: Value) -> Result<Self, ()> {
+		match value {
+			Value::Balance(b) => Ok(b),
+			_ => Err(()),
+		}
+	}
+}
+
+impl TryFrom<Value> for u64 {
+	type Error = ();
+	fn try_from(value: Value) -> Result<Self, ()> {
+		match value {
+			Value::BlockNumber(n) => Ok(n),
+			_ => Err(()),
+		}
+	}
+}
+
+impl Config for Test {
+	type Event = Event;
+	type Key = Key;
+	type Value = Value;
+	type AdminOrigin = frame_system::EnsureRoot<u64>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const DepositKey: Key = Key::Deposit;
+	pub const DefaultDeposit: u128 = 100;
+	pub const PeriodKey: Key = Key::Period;
+	pub const DefaultPeriod: u64 = 10;
+}
+
+pub type Deposit = Dynamic<Test, DepositKey, DefaultDeposit>;
+pub type Period = Dynamic<Test, PeriodKey, DefaultPeriod>;
+
+pub fn new_test_ext(parameters: Vec<(Key, Value)>) -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_parameters::GenesisConfig::<Test> { parameters }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,76 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for Parameters pallet.
+
+use super::*;
+use crate::mock::{Parameters, *};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn genesis_parameters_are_set() {
+	new_test_ext(vec![(Key::Period, Value::BlockNumber(20))]).execute_with(|| {
+		assert_eq!(Parameters::parameter(Key::Period), Some(Value::BlockNumber(20)));
+		assert_eq!(Parameters::parameter(Key::Deposit), None);
+		assert_eq!(<Period as Get<u64>>::get(), 20);
+		assert_eq!(<Deposit as Get<u128>>::get(), 100);
+	});
+}
+
+#[test]
+#[should_panic(expected = "invalid value for parameter Key::Period in genesis")]
+fn genesis_rejects_invalid_value() {
+	new_test_ext(vec![(Key::Period, Value::Balance(20))]);
+}
+
+#[test]
+fn set_parameter_works() {
+	new_test_ext(vec![]).execute_with(|| {
+		assert_eq!(<Deposit as Get<u128>>::get(), 100);
+
+		assert_ok!(Parameters::set_parameter(
+			Origin::root(),
+			Key::Deposit,
+			Some(Value::Balance(500))
+		));
+		assert_eq!(<Deposit as Get<u128>>::get(), 500);
+		System::assert_last_event(
+			crate::Event::Updated(Key::Deposit, None, Some(Value::Balance(500))).into(),
+		);
+
+		assert_ok!(Parameters::set_parameter(Origin::root(), Key::Deposit, None));
+		assert_eq!(<Deposit as Get<u128>>::get(), 100);
+		System::assert_last_event(
+			crate::Event::Updated(Key::Deposit, Some(Value::Balance(500)), None).into(),
+		);
+	});
+}
+
+#[test]
+fn set_parameter_checks_origin_and_value() {
+	new_test_ext(vec![]).execute_with(|| {
+		assert_noop!(
+			Parameters::set_parameter(Origin::signed(1), Key::Deposit, Some(Value::Balance(5))),
+			BadOrigin
+		);
+		assert_noop!(
+			Parameters::set_parameter(Origin::root(), Key::Deposit, Some(Value::BlockNumber(5))),
+			Error::<Test>::InvalidValue
+		);
+	});
+}
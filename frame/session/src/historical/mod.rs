@@ -60,9 +60,58 @@ pub trait Config: super::Config {
 	/// historical trie.
 	///
 	/// It must return the identification for the current session index.
+	///
+	/// Use [`ComposedIdentificationOf`] to combine multiple identification providers.
 	type FullIdentificationOf: Convert<Self::ValidatorId, Option<Self::FullIdentification>>;
+
+	/// The full identification committed to by the historical tries of the sessions before
+	/// [`Module::full_identification_since`].
+	///
+	/// Set this to `()` if the full identification never changed.
+	type LegacyFullIdentification: UpgradeFullIdentification<Self::FullIdentification>;
+}
+
+/// A full identification which was replaced by `FullIdentification`.
+pub trait UpgradeFullIdentification<FullIdentification> {
+	/// The replaced full identification.
+	type Legacy: Parameter;
+
+	/// Convert the replaced full identification, if possible.
+	fn upgrade(legacy: Self::Legacy) -> Option<FullIdentification>;
+}
+
+impl<FullIdentification: Parameter> UpgradeFullIdentification<FullIdentification> for () {
+	type Legacy = FullIdentification;
+
+	fn upgrade(legacy: Self::Legacy) -> Option<FullIdentification> {
+		Some(legacy)
+	}
+}
+
+/// A conversion from validator ID to the full identifications provided by both `A` and `B`.
+///
+/// Returns `None` if either of them fails to identify the validator. Nest it to combine more
+/// than two providers.
+pub struct ComposedIdentificationOf<A, B>(sp_std::marker::PhantomData<(A, B)>);
+
+impl<ValidatorId, IdA, IdB, A, B> Convert<ValidatorId, Option<(IdA, IdB)>>
+	for ComposedIdentificationOf<A, B>
+where
+	ValidatorId: Clone,
+	A: Convert<ValidatorId, Option<IdA>>,
+	B: Convert<ValidatorId, Option<IdB>>,
+{
+	fn convert(validator: ValidatorId) -> Option<(IdA, IdB)> {
+		Some((A::convert(validator.clone())?, B::convert(validator)?))
+	}
 }
 
+/// The full identification committed to by the historical tries of legacy sessions.
+pub type LegacyFullIdentificationOf<T> =
+	<<T as Config>::LegacyFullIdentification as UpgradeFullIdentification<
+		<T as Config>::FullIdentification,
+	>>::Legacy;
+
 decl_storage! {
 	trait Store for Module<T: Config> as Session {
 		/// Mapping from historical session indices to session-data root hash and validator count.
@@ -70,6 +119,10 @@ decl_storage! {
 			map hasher(twox_64_concat) SessionIndex => Option<(T::Hash, ValidatorCount)>;
 		/// The range of historical sessions we store. [first, last)
 		StoredRange: Option<(SessionIndex, SessionIndex)>;
+		/// The first session whose historical trie commits to the current `FullIdentification`.
+		///
+		/// The tries of all the sessions before commit to `LegacyFullIdentification`.
+		FullIdentificationSince get(fn full_identification_since): SessionIndex;
 		/// Deprecated.
 		CachedObsolete:
 			map hasher(twox_64_concat) SessionIndex
@@ -107,6 +160,24 @@ impl<T: Config> Module<T> {
 			}
 		})
 	}
+
+	/// Note that the `FullIdentification` has changed, so that the historical tries created so
+	/// far are checked against `LegacyFullIdentification` from now on.
+	///
+	/// Must be called by the runtime upgrade which changes the `FullIdentification`. Proofs of
+	/// the current session created after the upgrade can only be checked during it.
+	pub fn note_full_identification_upgrade() {
+		let since = match StoredRange::get() {
+			Some((_, end)) => end,
+			None => <SessionModule<T>>::current_index(),
+		};
+		FullIdentificationSince::put(since);
+	}
+
+	/// Whether the historical trie of `session` commits to the `LegacyFullIdentification`.
+	fn is_legacy(session: SessionIndex) -> bool {
+		session < Self::full_identification_since()
+	}
 }
 
 impl<T: Config> ValidatorSet<T::AccountId> for Module<T> {
@@ -200,7 +271,10 @@ where
 	}
 
 	fn end_session(end_index: SessionIndex) {
-		onchain::store_session_validator_set_to_offchain::<T>(end_index);
+		// the off-chain proofs of a legacy session would not match its historical trie.
+		if !Module::<T>::is_legacy(end_index) {
+			onchain::store_session_validator_set_to_offchain::<T>(end_index);
+		}
 		<I as SessionManager<_, _>>::end_session(end_index)
 	}
 }
@@ -216,9 +290,10 @@ pub struct ProvingTrie<T: Config> {
 }
 
 impl<T: Config> ProvingTrie<T> {
-	fn generate_for<I>(validators: I) -> Result<Self, &'static str>
+	fn generate_for<I, F>(validators: I) -> Result<Self, &'static str>
 	where
-		I: IntoIterator<Item = (T::ValidatorId, T::FullIdentification)>,
+		I: IntoIterator<Item = (T::ValidatorId, F)>,
+		F: Encode,
 	{
 		let mut db = MemoryDB::default();
 		let mut root = Default::default();
@@ -274,11 +349,7 @@ impl<T: Config> ProvingTrie<T> {
 				.and_then(|raw| u32::decode(&mut &*raw).ok())
 		})?;
 
-		val_idx.using_encoded(|s| {
-			trie.get_with(s, &mut recorder)
-				.ok()?
-				.and_then(|raw| <IdentificationTuple<T>>::decode(&mut &*raw).ok())
-		})?;
+		val_idx.using_encoded(|s| trie.get_with(s, &mut recorder).ok()?)?;
 
 		Some(recorder.drain().into_iter().map(|r| r.data).collect())
 	}
@@ -290,7 +361,7 @@ impl<T: Config> ProvingTrie<T> {
 
 	// Check a proof contained within the current memory-db. Returns `None` if the
 	// nodes within the current `MemoryDB` are insufficient to query the item.
-	fn query(&self, key_id: KeyTypeId, key_data: &[u8]) -> Option<IdentificationTuple<T>> {
+	fn query<F: Decode>(&self, key_id: KeyTypeId, key_data: &[u8]) -> Option<(T::ValidatorId, F)> {
 		let trie = TrieDB::new(&self.db, &self.root).ok()?;
 		let val_idx = (key_id, key_data)
 			.using_encoded(|s| trie.get(s))
@@ -300,7 +371,7 @@ impl<T: Config> ProvingTrie<T> {
 		val_idx
 			.using_encoded(|s| trie.get(s))
 			.ok()?
-			.and_then(|raw| <IdentificationTuple<T>>::decode(&mut &*raw).ok())
+			.and_then(|raw| <(T::ValidatorId, F)>::decode(&mut &*raw).ok())
 	}
 }
 
@@ -355,7 +426,13 @@ impl<T: Config, D: AsRef<[u8]>> frame_support::traits::KeyOwnerProofSystem<(KeyT
 			}
 
 			let trie = ProvingTrie::<T>::from_nodes(root, &proof.trie_nodes);
-			trie.query(id, data.as_ref())
+			if Self::is_legacy(proof.session) {
+				let (owner, legacy) =
+					trie.query::<LegacyFullIdentificationOf<T>>(id, data.as_ref())?;
+				T::LegacyFullIdentification::upgrade(legacy).map(|id| (owner, id))
+			} else {
+				trie.query(id, data.as_ref())
+			}
 		}
 	}
 }
@@ -423,6 +500,57 @@ pub(crate) mod tests {
 		});
 	}
 
+	#[test]
+	fn legacy_proofs_are_upgraded() {
+		new_test_ext().execute_with(|| {
+			set_next_validators(vec![1, 2]);
+			force_new_session();
+
+			System::set_block_number(1);
+			Session::on_initialize(1);
+
+			let encoded_key_1 = UintAuthorityId(1).encode();
+			let proof = Historical::prove((DUMMY, &encoded_key_1[..])).unwrap();
+
+			force_new_session();
+			System::set_block_number(2);
+			Session::on_initialize(2);
+
+			assert_eq!(
+				Historical::check_proof((DUMMY, &encoded_key_1[..]), proof.clone()),
+				Some((1, 1)),
+			);
+
+			Historical::note_full_identification_upgrade();
+			assert!(Historical::full_identification_since() > proof.session);
+
+			// the proof is still valid, its identification is upgraded.
+			assert_eq!(
+				Historical::check_proof((DUMMY, &encoded_key_1[..]), proof.clone()),
+				Some((1, 10)),
+			);
+		});
+	}
+
+	#[test]
+	fn composed_identification_of_works() {
+		use sp_runtime::traits::ConvertInto;
+
+		assert_eq!(
+			<ComposedIdentificationOf<ConvertInto, ConvertInto> as Convert<
+				u64,
+				Option<(u64, u64)>,
+			>>::convert(7),
+			Some((7u64, 7u64)),
+		);
+		assert_eq!(
+			<ComposedIdentificationOf<ConvertInto, ()> as Convert<_, Option<(u64, ())>>>::convert(
+				7u64
+			),
+			None,
+		);
+	}
+
 	#[test]
 	fn prune_up_to_works() {
 		new_test_ext().execute_with(|| {
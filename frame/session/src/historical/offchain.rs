@@ -25,6 +25,7 @@
 //! This is used in conjunction with [`ProvingTrie`](super::ProvingTrie) and
 //! the off-chain indexing API.
 
+use codec::Decode;
 use sp_runtime::{
 	offchain::storage::{MutateStorageError, StorageRetrievalError, StorageValueRef},
 	KeyTypeId,
@@ -33,18 +34,21 @@ use sp_session::MembershipProof;
 
 use super::{
 	super::{Pallet as SessionModule, SessionIndex},
-	Config, IdentificationTuple, ProvingTrie,
+	Config, LegacyFullIdentificationOf, Module, ProvingTrie,
 };
 
 use super::shared;
 use sp_std::prelude::*;
 
 /// A set of validators, which was used for a fixed session index.
-struct ValidatorSet<T: Config> {
-	validator_set: Vec<IdentificationTuple<T>>,
+///
+/// The validators are identified by `F`, which differs from the current `FullIdentification` for
+/// legacy sessions.
+struct ValidatorSet<T: Config, F = <T as Config>::FullIdentification> {
+	validator_set: Vec<(T::ValidatorId, F)>,
 }
 
-impl<T: Config> ValidatorSet<T> {
+impl<T: Config, F: Decode> ValidatorSet<T, F> {
 	/// Load the set of validators for a particular session index from the off-chain storage.
 	///
 	/// If none is found or decodable given `prefix` and `session`, it will return `None`.
@@ -52,7 +56,7 @@ impl<T: Config> ValidatorSet<T> {
 	pub fn load_from_offchain_db(session_index: SessionIndex) -> Option<Self> {
 		let derived_key = shared::derive_key(shared::PREFIX, session_index);
 		StorageValueRef::persistent(derived_key.as_ref())
-			.get::<Vec<(T::ValidatorId, T::FullIdentification)>>()
+			.get::<Vec<(T::ValidatorId, F)>>()
 			.ok()
 			.flatten()
 			.map(|validator_set| Self { validator_set })
@@ -66,8 +70,8 @@ impl<T: Config> ValidatorSet<T> {
 
 /// Implement conversion into iterator for usage
 /// with [ProvingTrie](super::ProvingTrie::generate_for).
-impl<T: Config> sp_std::iter::IntoIterator for ValidatorSet<T> {
-	type Item = (T::ValidatorId, T::FullIdentification);
+impl<T: Config, F> sp_std::iter::IntoIterator for ValidatorSet<T, F> {
+	type Item = (T::ValidatorId, F);
 	type IntoIter = sp_std::vec::IntoIter<Self::Item>;
 	fn into_iter(self) -> Self::IntoIter {
 		self.validator_set.into_iter()
@@ -83,9 +87,14 @@ pub fn prove_session_membership<T: Config, D: AsRef<[u8]>>(
 	session_index: SessionIndex,
 	session_key: (KeyTypeId, D),
 ) -> Option<MembershipProof> {
-	let validators = ValidatorSet::<T>::load_from_offchain_db(session_index)?;
-	let count = validators.len() as u32;
-	let trie = ProvingTrie::<T>::generate_for(validators.into_iter()).ok()?;
+	let (count, trie) = if Module::<T>::is_legacy(session_index) {
+		let validators =
+			ValidatorSet::<T, LegacyFullIdentificationOf<T>>::load_from_offchain_db(session_index)?;
+		(validators.len() as u32, ProvingTrie::<T>::generate_for(validators.into_iter()).ok()?)
+	} else {
+		let validators = ValidatorSet::<T>::load_from_offchain_db(session_index)?;
+		(validators.len() as u32, ProvingTrie::<T>::generate_for(validators.into_iter()).ok()?)
+	};
 
 	let (id, data) = session_key;
 	trie.prove(id, data.as_ref()).map(|trie_nodes| MembershipProof {
@@ -532,16 +532,28 @@ decl_module! {
 		/// - DbWrites: `origin account`, `NextKeys`
 		/// - DbReads per key id: `KeyOwner`
 		/// - DbWrites per key id: `KeyOwner`
+		/// NOTE: The `KeyOwner` writes of the keys which did not change are refunded.
 		/// # </weight>
 		#[weight = T::WeightInfo::set_keys()]
-		pub fn set_keys(origin, keys: T::Keys, proof: Vec<u8>) -> dispatch::DispatchResult {
+		pub fn set_keys(
+			origin,
+			keys: T::Keys,
+			proof: Vec<u8>,
+		) -> dispatch::DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 
 			ensure!(keys.ownership_proof_is_valid(&proof), Error::<T>::InvalidProof);
 
-			Self::do_set_keys(&who, keys)?;
+			let key_owner_writes = Self::do_set_keys(&who, keys)?;
 
-			Ok(())
+			// The weight accounts for one `KeyOwner` write per key id. Replacing a key writes
+			// more than that, in which case the full weight is charged.
+			let key_ids = T::Keys::key_ids().len() as u32;
+			let actual_weight = key_ids.checked_sub(key_owner_writes).map(|skipped| {
+				let refund = T::DbWeight::get().writes(skipped as Weight);
+				T::WeightInfo::set_keys().saturating_sub(refund)
+			});
+			Ok(actual_weight.into())
 		}
 
 		/// Removes any session key(s) of the function caller.
@@ -702,6 +714,29 @@ impl<T: Config> Module<T> {
 			.ok_or(())
 	}
 
+	/// Re-enable the validator of index `i`, if it was disabled.
+	///
+	/// Returns `true` if the validator was disabled.
+	pub fn enable_index(i: usize) -> bool {
+		DisabledValidators::mutate(|disabled| {
+			if let Ok(index) = disabled.binary_search(&(i as u32)) {
+				disabled.remove(index);
+				true
+			} else {
+				false
+			}
+		})
+	}
+
+	/// Re-enable the validator identified by `c`, if it was disabled. (If using with the staking
+	/// module, this would be their *stash* account.)
+	///
+	/// Returns `Ok(true)` if the validator was disabled, and `Err(())` if it is not part of the
+	/// current validator set.
+	pub fn enable(c: &T::ValidatorId) -> sp_std::result::Result<bool, ()> {
+		Self::validators().iter().position(|i| i == c).map(Self::enable_index).ok_or(())
+	}
+
 	/// Upgrade the key type from some old type to a new type. Supports adding
 	/// and removing key types.
 	///
@@ -756,31 +791,35 @@ impl<T: Config> Module<T> {
 	///
 	/// This ensures that the reference counter in system is incremented appropriately and as such
 	/// must accept an account ID, rather than a validator ID.
-	fn do_set_keys(account: &T::AccountId, keys: T::Keys) -> dispatch::DispatchResult {
+	///
+	/// Returns the number of `KeyOwner` entries written.
+	fn do_set_keys(account: &T::AccountId, keys: T::Keys) -> Result<u32, DispatchError> {
 		let who = T::ValidatorIdOf::convert(account.clone())
 			.ok_or(Error::<T>::NoAssociatedValidatorId)?;
 
 		ensure!(frame_system::Pallet::<T>::can_inc_consumer(&account), Error::<T>::NoAccount);
-		let old_keys = Self::inner_set_keys(&who, keys)?;
+		let (old_keys, key_owner_writes) = Self::inner_set_keys(&who, keys)?;
 		if old_keys.is_none() {
 			let assertion = frame_system::Pallet::<T>::inc_consumers(&account).is_ok();
 			debug_assert!(assertion, "can_inc_consumer() returned true; no change since; qed");
 		}
 
-		Ok(())
+		Ok(key_owner_writes)
 	}
 
 	/// Perform the set_key operation, checking for duplicates. Does not set `Changed`.
 	///
-	/// The old keys for this validator are returned, or `None` if there were none.
+	/// The old keys for this validator are returned, or `None` if there were none, along with the
+	/// number of `KeyOwner` entries written.
 	///
 	/// This does not ensure that the reference counter in system is incremented appropriately, it
 	/// must be done by the caller or the keys will be leaked in storage.
 	fn inner_set_keys(
 		who: &T::ValidatorId,
 		keys: T::Keys,
-	) -> Result<Option<T::Keys>, DispatchError> {
+	) -> Result<(Option<T::Keys>, u32), DispatchError> {
 		let old_keys = Self::load_keys(who);
+		let mut key_owner_writes = 0;
 
 		for id in T::Keys::key_ids() {
 			let key = keys.get_raw(*id);
@@ -801,13 +840,15 @@ impl<T: Config> Module<T> {
 				}
 
 				Self::clear_key_owner(*id, old);
+				key_owner_writes += 1;
 			}
 
 			Self::put_key_owner(*id, key, who);
+			key_owner_writes += 1;
 		}
 
 		Self::put_keys(who, &keys);
-		Ok(old_keys)
+		Ok((old_keys, key_owner_writes))
 	}
 
 	fn do_purge_keys(account: &T::AccountId) -> DispatchResult {
@@ -836,6 +877,22 @@ impl<T: Config> Module<T> {
 		<NextKeys<T>>::insert(v, keys);
 	}
 
+	/// The raw session keys registered by the given validator, by key type.
+	///
+	/// Those are the keys used from the next session on, `None` if no keys were set.
+	pub fn next_keys_raw(v: &T::ValidatorId) -> Option<Vec<(KeyTypeId, Vec<u8>)>> {
+		Self::load_keys(v).map(|keys| {
+			T::Keys::key_ids().iter().map(|id| (*id, keys.get_raw(*id).to_vec())).collect()
+		})
+	}
+
+	/// Whether the given validator is disabled in the current session.
+	pub fn is_validator_disabled(v: &T::ValidatorId) -> bool {
+		Self::validators().iter().position(|i| i == v).map_or(false, |i| {
+			<Self as frame_support::traits::DisabledValidators>::is_disabled(i as u32)
+		})
+	}
+
 	/// Query the owner of a session key by returning the owner's validator ID.
 	pub fn key_owner(id: KeyTypeId, key_data: &[u8]) -> Option<T::ValidatorId> {
 		<KeyOwner<T>>::get((id, key_data))
@@ -286,8 +286,22 @@ impl Config for Test {
 	type WeightInfo = ();
 }
 
+/// Upgrades the full identifications of legacy sessions by scaling them by ten.
+#[cfg(feature = "historical")]
+pub struct TestLegacyIdentification;
+
+#[cfg(feature = "historical")]
+impl crate::historical::UpgradeFullIdentification<u64> for TestLegacyIdentification {
+	type Legacy = u64;
+
+	fn upgrade(legacy: u64) -> Option<u64> {
+		legacy.checked_mul(10)
+	}
+}
+
 #[cfg(feature = "historical")]
 impl crate::historical::Config for Test {
 	type FullIdentification = u64;
 	type FullIdentificationOf = sp_runtime::traits::ConvertInto;
+	type LegacyFullIdentification = TestLegacyIdentification;
 }
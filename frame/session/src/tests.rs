@@ -19,7 +19,7 @@
 
 use super::*;
 use codec::Decode;
-use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize, weights::RuntimeDbWeight};
 use mock::{
 	authorities, before_session_end_called, force_new_session, new_test_ext,
 	reset_before_session_end_called, session_changed, set_next_validators, set_session_length,
@@ -198,6 +198,25 @@ fn duplicates_are_not_allowed() {
 	});
 }
 
+#[test]
+fn set_keys_refunds_unchanged_keys() {
+	new_test_ext().execute_with(|| {
+		let weight = <Test as Config>::WeightInfo::set_keys();
+		let write = <<Test as frame_system::Config>::DbWeight as Get<RuntimeDbWeight>>::get()
+			.writes(1);
+
+		// The key of 1 is set at genesis, setting it again writes no `KeyOwner` entry.
+		let post_info =
+			Session::set_keys(Origin::signed(1), UintAuthorityId(1).into(), vec![]).unwrap();
+		assert_eq!(post_info.actual_weight, Some(weight - write));
+
+		// Replacing the key clears the old `KeyOwner` entry, so the full weight is charged.
+		let post_info =
+			Session::set_keys(Origin::signed(1), UintAuthorityId(10).into(), vec![]).unwrap();
+		assert_eq!(post_info.actual_weight, None);
+	});
+}
+
 #[test]
 fn session_changed_flag_works() {
 	reset_before_session_end_called();
@@ -352,6 +371,35 @@ fn return_true_if_more_than_third_is_disabled() {
 	});
 }
 
+#[test]
+fn enable_index_reverts_disable_index() {
+	new_test_ext().execute_with(|| {
+		Session::disable_index(1);
+		assert_eq!(Session::disabled_validators(), vec![1]);
+
+		assert!(Session::enable_index(1));
+		assert!(Session::disabled_validators().is_empty());
+		assert!(!Session::enable_index(1));
+	});
+}
+
+#[test]
+fn next_keys_and_disabled_status_by_validator() {
+	new_test_ext().execute_with(|| {
+		let id = DUMMY;
+		assert_eq!(
+			Session::next_keys_raw(&2),
+			Some(vec![(id, UintAuthorityId(2).get_raw(id).to_vec())]),
+		);
+		assert_eq!(Session::next_keys_raw(&4), None);
+
+		Session::disable_index(1);
+		assert!(Session::is_validator_disabled(&2));
+		assert!(!Session::is_validator_disabled(&1));
+		assert!(!Session::is_validator_disabled(&4));
+	});
+}
+
 #[test]
 fn upgrade_keys() {
 	use frame_support::storage;
@@ -880,6 +880,31 @@ benchmarks! {
 	verify {
 		assert!(!T::SortedListProvider::contains(&stash));
 	}
+
+	go_offline {
+		// the validator must be part of the current session, which must have enough validators
+		// for one of them to go offline without reaching the disabling threshold.
+		let stash = T::SessionInterface::validators().into_iter().next()
+			.ok_or("no validator in the current session")?;
+		let controller = Bonded::<T>::get(&stash).ok_or("validator is not bonded")?;
+		whitelist_account!(controller);
+	}: _(RawOrigin::Signed(controller))
+	verify {
+		assert!(SelfDisabledValidators::<T>::get().contains(&stash));
+		assert_eq!(SelfDisablesInEra::<T>::get(&stash), 1);
+	}
+
+	go_online {
+		let stash = T::SessionInterface::validators().into_iter().next()
+			.ok_or("no validator in the current session")?;
+		let controller = Bonded::<T>::get(&stash).ok_or("validator is not bonded")?;
+		Staking::<T>::go_offline(RawOrigin::Signed(controller.clone()).into())?;
+		whitelist_account!(controller);
+	}: _(RawOrigin::Signed(controller))
+	verify {
+		assert!(!SelfDisabledValidators::<T>::get().contains(&stash));
+		assert!(!T::SessionInterface::is_disabled(&stash));
+	}
 }
 
 #[cfg(test)]
@@ -997,7 +1022,7 @@ mod tests {
 
 impl_benchmark_test_suite!(
 	Staking,
-	crate::mock::ExtBuilder::default().has_stakers(true),
+	crate::mock::ExtBuilder::default().has_stakers(true).validator_count(3),
 	crate::mock::Test,
 	exec_name = build_and_execute
 );
@@ -136,6 +136,11 @@
 //!
 //! An account can step back via the [`chill`](Call::chill) call.
 //!
+//! An active validator that needs to go offline for maintenance can instead leave the current
+//! session via the [`go_offline`](Call::go_offline) call, without being reported for an offence.
+//! It is enabled again at the start of the next session, or via the [`go_online`](Call::go_online)
+//! call.
+//!
 //! ### Session managing
 //!
 //! The pallet implement the trait `SessionManager`. Which is the only API to query new validator
@@ -299,10 +304,10 @@ use scale_info::TypeInfo;
 use sp_runtime::{
 	curve::PiecewiseLinear,
 	traits::{AtLeast32BitUnsigned, Convert, Saturating, Zero},
-	Perbill, RuntimeDebug,
+	KeyTypeId, Perbill, RuntimeDebug,
 };
 use sp_staking::{
-	offence::{Offence, OffenceError, ReportOffence},
+	offence::{Offence, OffenceError, OffenceSeverity, ReportOffence},
 	SessionIndex,
 };
 use sp_std::{collections::btree_map::BTreeMap, convert::From, prelude::*};
@@ -596,6 +601,30 @@ pub struct Exposure<AccountId, Balance: HasCompact> {
 	pub others: Vec<IndividualExposure<AccountId, Balance>>,
 }
 
+/// Why a stash is, or isn't, part of the active validator set, see
+/// [`Pallet::validator_diagnostics`].
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+pub struct ValidatorDiagnostics<Balance> {
+	/// The session keys set for the next session, by key type. `None` if no keys were set.
+	pub session_keys: Option<Vec<(KeyTypeId, Vec<u8>)>>,
+	/// Whether the stash is bonded.
+	pub bonded: bool,
+	/// The active bonded balance of the stash.
+	pub active_bond: Balance,
+	/// The minimum active bond required to validate.
+	pub min_validator_bond: Balance,
+	/// Whether the stash declared its intention to validate.
+	pub intends_to_validate: bool,
+	/// Whether the stash was elected in the last election, i.e. for the planned era.
+	pub elected: bool,
+	/// Whether the stash is part of the validator set of the current session.
+	pub active: bool,
+	/// Whether the stash is disabled in the current session.
+	pub disabled: bool,
+	/// Whether the stash is part of the validator set queued for the next session.
+	pub queued: bool,
+}
+
 /// A pending slash record. The value of the slash has been computed but not applied yet,
 /// rather deferred for several eras.
 #[derive(Encode, Decode, Default, RuntimeDebug, TypeInfo)]
@@ -622,8 +651,16 @@ pub trait SessionInterface<AccountId>: frame_system::Config {
 	/// This allows preventing a situation where there is too many validators
 	/// disabled and block production stalls.
 	fn disable_validator(validator: &AccountId) -> Result<bool, ()>;
+	/// Re-enable a given validator by stash ID, if it was disabled.
+	fn enable_validator(validator: &AccountId);
 	/// Get the validators from session.
 	fn validators() -> Vec<AccountId>;
+	/// Get the validators queued for the next session.
+	fn queued_validators() -> Vec<AccountId>;
+	/// Whether the given validator is disabled in the current session.
+	fn is_disabled(validator: &AccountId) -> bool;
+	/// Get the raw session keys set by the given validator, by key type.
+	fn session_keys(validator: &AccountId) -> Option<Vec<(KeyTypeId, Vec<u8>)>>;
 	/// Prune historical session tries up to but not including the given index.
 	fn prune_historical_up_to(up_to: SessionIndex);
 }
@@ -646,15 +683,94 @@ where
 		<pallet_session::Pallet<T>>::disable(validator)
 	}
 
+	fn enable_validator(validator: &<T as frame_system::Config>::AccountId) {
+		let _ = <pallet_session::Pallet<T>>::enable(validator);
+	}
+
 	fn validators() -> Vec<<T as frame_system::Config>::AccountId> {
 		<pallet_session::Pallet<T>>::validators()
 	}
 
+	fn queued_validators() -> Vec<<T as frame_system::Config>::AccountId> {
+		<pallet_session::Pallet<T>>::queued_keys().into_iter().map(|(v, _)| v).collect()
+	}
+
+	fn is_disabled(validator: &<T as frame_system::Config>::AccountId) -> bool {
+		<pallet_session::Pallet<T>>::is_validator_disabled(validator)
+	}
+
+	fn session_keys(
+		validator: &<T as frame_system::Config>::AccountId,
+	) -> Option<Vec<(KeyTypeId, Vec<u8>)>> {
+		<pallet_session::Pallet<T>>::next_keys_raw(validator)
+	}
+
 	fn prune_historical_up_to(up_to: SessionIndex) {
 		<pallet_session::historical::Pallet<T>>::prune_up_to(up_to);
 	}
 }
 
+/// For how long an offending validator is disabled.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum DisablingDecision {
+	/// The offender is not disabled.
+	Keep,
+	/// The offender is disabled until the end of the current session.
+	ForSession,
+	/// The offender is disabled until the end of the current era.
+	ForEra,
+}
+
+/// Decides whether and for how long a validator is disabled following an offence.
+pub trait DisablingStrategy<T: Config> {
+	/// Decide how to disable `offender_stash`, which committed an offence of the given `severity`.
+	///
+	/// `disabled_count` is the number of validators currently disabled because of offences.
+	fn decision(
+		offender_stash: &T::AccountId,
+		severity: OffenceSeverity,
+		disabled_count: u32,
+	) -> DisablingDecision;
+}
+
+/// Disables every offender for the rest of the era, regardless of the offence.
+impl<T: Config> DisablingStrategy<T> for () {
+	fn decision(_: &T::AccountId, _: OffenceSeverity, _: u32) -> DisablingDecision {
+		DisablingDecision::ForEra
+	}
+}
+
+/// A [`DisablingStrategy`] differentiating offences by their severity.
+///
+/// Offences at least as severe as `HighSeverity` (e.g. equivocations) disable the offender for the
+/// rest of the era. Less severe offences (e.g. being offline) only disable the offender for the
+/// rest of the session, and only while less than `LowSeverityCap` of the validator set is
+/// disabled.
+pub struct SeverityBasedDisabling<HighSeverity, LowSeverityCap>(
+	sp_std::marker::PhantomData<(HighSeverity, LowSeverityCap)>,
+);
+
+impl<T: Config, HighSeverity: Get<Perbill>, LowSeverityCap: Get<Perbill>> DisablingStrategy<T>
+	for SeverityBasedDisabling<HighSeverity, LowSeverityCap>
+{
+	fn decision(
+		_: &T::AccountId,
+		severity: OffenceSeverity,
+		disabled_count: u32,
+	) -> DisablingDecision {
+		if severity >= OffenceSeverity(HighSeverity::get()) {
+			return DisablingDecision::ForEra
+		}
+
+		let validator_count = T::SessionInterface::validators().len() as u32;
+		if disabled_count < LowSeverityCap::get() * validator_count {
+			DisablingDecision::ForSession
+		} else {
+			DisablingDecision::Keep
+		}
+	}
+}
+
 /// Handler for determining how much of a balance should be paid out on the current era.
 pub trait EraPayout<Balance> {
 	/// Determine the payout for this era.
@@ -36,15 +36,16 @@ use sp_runtime::{
 	Perbill,
 };
 use sp_staking::{
-	offence::{OffenceDetails, OnOffenceHandler},
+	offence::{OffenceDetails, OffenceSeverity, OnOffenceHandler},
 	SessionIndex,
 };
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
 use crate::{
-	log, slashing, weights::WeightInfo, ActiveEraInfo, BalanceOf, EraIndex, EraPayout, Exposure,
-	ExposureOf, Forcing, IndividualExposure, Nominations, PositiveImbalanceOf, RewardDestination,
-	SessionInterface, StakingLedger, ValidatorPrefs,
+	log, slashing, weights::WeightInfo, ActiveEraInfo, BalanceOf, DisablingDecision,
+	DisablingStrategy, EraIndex, EraPayout, Exposure, ExposureOf, Forcing, IndividualExposure,
+	Nominations, PositiveImbalanceOf, RewardDestination, SessionInterface, StakingLedger,
+	ValidatorDiagnostics, ValidatorPrefs,
 };
 
 use super::{pallet::*, STAKING_ID};
@@ -64,6 +65,25 @@ impl<T: Config> Pallet<T> {
 		T::CurrencyToVote::to_vote(Self::slashable_balance_of(stash), issuance)
 	}
 
+	/// Diagnose why the given stash is, or isn't, part of the active validator set.
+	///
+	/// Aggregates its session keys, bond, election and session status in a single answer, meant
+	/// to be queried off-chain.
+	pub fn validator_diagnostics(stash: &T::AccountId) -> ValidatorDiagnostics<BalanceOf<T>> {
+		ValidatorDiagnostics {
+			session_keys: T::SessionInterface::session_keys(stash),
+			bonded: <Bonded<T>>::contains_key(stash),
+			active_bond: Self::slashable_balance_of(stash),
+			min_validator_bond: MinValidatorBond::<T>::get(),
+			intends_to_validate: Validators::<T>::contains_key(stash),
+			elected: Self::current_era()
+				.map_or(false, |era| ErasStakers::<T>::contains_key(era, stash)),
+			active: T::SessionInterface::validators().contains(stash),
+			disabled: T::SessionInterface::is_disabled(stash),
+			queued: T::SessionInterface::queued_validators().contains(stash),
+		}
+	}
+
 	/// Returns a closure around `slashable_balance_of_vote_weight` that can be passed around.
 	///
 	/// This prevents call sites from repeatedly requesting `total_issuance` from backend. But it is
@@ -300,7 +320,68 @@ impl<T: Config> Pallet<T> {
 				// pallet.
 				frame_support::print("Warning: A session appears to have been skipped.");
 				Self::start_era(start_session);
+			} else {
+				Self::enable_session_disabled();
 			}
+		} else {
+			Self::enable_session_disabled();
+		}
+	}
+
+	/// Re-enable the validators which were only disabled for the past session.
+	fn enable_session_disabled() {
+		for stash in SessionDisabledValidators::<T>::take() {
+			T::SessionInterface::enable_validator(&stash);
+		}
+		for stash in SelfDisabledValidators::<T>::take() {
+			Self::enable_self_disabled(&stash);
+		}
+	}
+
+	/// Re-enable a validator which went offline with `go_offline`, unless it is also disabled
+	/// because of an offence.
+	pub(crate) fn enable_self_disabled(stash: &T::AccountId) {
+		if !SessionDisabledValidators::<T>::get().contains(stash) &&
+			!EraDisabledValidators::<T>::get().contains(stash)
+		{
+			T::SessionInterface::enable_validator(stash);
+		}
+	}
+
+	/// Disable the validator `stash` following an offence of the given `severity`, as decided by
+	/// `T::DisablingStrategy`.
+	///
+	/// A new era is forced if the session pallet reports that too many validators are disabled.
+	pub(crate) fn disable_offender(stash: &T::AccountId, severity: OffenceSeverity) {
+		let session_disabled = SessionDisabledValidators::<T>::get();
+		let era_disabled = EraDisabledValidators::<T>::get();
+		let disabled_count = (session_disabled.len() + era_disabled.len()) as u32;
+
+		match T::DisablingStrategy::decision(stash, severity, disabled_count) {
+			DisablingDecision::Keep => return,
+			DisablingDecision::ForSession => {
+				if era_disabled.contains(stash) {
+					return
+				}
+				if !session_disabled.contains(stash) {
+					SessionDisabledValidators::<T>::append(stash);
+				}
+			},
+			DisablingDecision::ForEra => {
+				if session_disabled.contains(stash) {
+					SessionDisabledValidators::<T>::mutate(|disabled| {
+						disabled.retain(|s| s != stash)
+					});
+				}
+				if !era_disabled.contains(stash) {
+					EraDisabledValidators::<T>::append(stash);
+				}
+			},
+		}
+
+		let maybe_new_era = T::SessionInterface::disable_validator(stash);
+		if maybe_new_era.unwrap_or(false) {
+			Self::ensure_new_era()
 		}
 	}
 
@@ -356,6 +437,13 @@ impl<T: Config> Pallet<T> {
 		});
 
 		Self::apply_unapplied_slashes(active_era);
+
+		SessionDisabledValidators::<T>::kill();
+		EraDisabledValidators::<T>::kill();
+		for stash in SelfDisabledValidators::<T>::take() {
+			T::SessionInterface::enable_validator(&stash);
+		}
+		SelfDisablesInEra::<T>::remove_all(None);
 	}
 
 	/// Compute payout for era.
@@ -547,10 +635,16 @@ impl<T: Config> Pallet<T> {
 	/// This is called:
 	/// - after a `withdraw_unbonded()` call that frees all of a stash's bonded balance.
 	/// - through `reap_stash()` if the balance has fallen to zero (through slashing).
-	pub(crate) fn kill_stash(stash: &T::AccountId, num_slashing_spans: u32) -> DispatchResult {
+	///
+	/// Returns the number of slashing spans removed, to refund the weight of the unused ones.
+	pub(crate) fn kill_stash(
+		stash: &T::AccountId,
+		num_slashing_spans: u32,
+	) -> Result<u32, DispatchError> {
 		let controller = <Bonded<T>>::get(stash).ok_or(Error::<T>::NotStash)?;
 
-		slashing::clear_stash_metadata::<T>(stash, num_slashing_spans)?;
+		let cleared_slashing_spans =
+			slashing::clear_stash_metadata::<T>(stash, num_slashing_spans)?;
 
 		<Bonded<T>>::remove(stash);
 		<Ledger<T>>::remove(&controller);
@@ -561,7 +655,7 @@ impl<T: Config> Pallet<T> {
 
 		frame_system::Pallet::<T>::dec_consumers(stash);
 
-		Ok(())
+		Ok(cleared_slashing_spans)
 	}
 
 	/// Clear all era information for given era.
@@ -24,6 +24,7 @@ use frame_support::{
 		Currency, CurrencyToVote, EnsureOrigin, EstimateNextNewSession, Get, LockIdentifier,
 		LockableCurrency, OnUnbalanced, UnixTime,
 	},
+	transactional,
 	weights::Weight,
 };
 use frame_system::{ensure_root, ensure_signed, offchain::SendTransactionTypes, pallet_prelude::*};
@@ -39,10 +40,10 @@ mod impls;
 pub use impls::*;
 
 use crate::{
-	log, migrations, slashing, weights::WeightInfo, ActiveEraInfo, BalanceOf, EraIndex, EraPayout,
-	EraRewardPoints, Exposure, Forcing, NegativeImbalanceOf, Nominations, PositiveImbalanceOf,
-	Releases, RewardDestination, SessionInterface, StakingLedger, UnappliedSlash, UnlockChunk,
-	ValidatorPrefs,
+	log, migrations, slashing, weights::WeightInfo, ActiveEraInfo, BalanceOf, DisablingStrategy,
+	EraIndex, EraPayout, EraRewardPoints, Exposure, Forcing, NegativeImbalanceOf, Nominations,
+	PositiveImbalanceOf, Releases, RewardDestination, SessionInterface, StakingLedger,
+	UnappliedSlash, UnlockChunk, ValidatorPrefs,
 };
 
 pub const MAX_UNLOCKING_CHUNKS: usize = 32;
@@ -126,6 +127,9 @@ pub mod pallet {
 		/// Interface for interacting with a session pallet.
 		type SessionInterface: SessionInterface<Self::AccountId>;
 
+		/// Decides whether and for how long offending validators are disabled.
+		type DisablingStrategy: DisablingStrategy<Self>;
+
 		/// The payout for validators and the system for the current era.
 		/// See [Era payout](./index.html#era-payout).
 		type EraPayout: EraPayout<BalanceOf<Self>>;
@@ -141,6 +145,11 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxNominatorRewardedPerValidator: Get<u32>;
 
+		/// The maximum number of times a validator can take itself offline with `go_offline`
+		/// during a single era.
+		#[pallet::constant]
+		type MaxSelfDisablesPerEra: Get<u32>;
+
 		/// Something that can provide a sorted list of voters in a somewhat sorted way. The
 		/// original use case for this was designed with [`pallet_bags_list::Pallet`] in mind. If
 		/// the bags-list is not desired, [`impls::UseNominatorsMap`] is likely the desired option.
@@ -430,6 +439,27 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(crate) type EarliestUnappliedSlash<T> = StorageValue<_, EraIndex>;
 
+	/// Validators disabled because of offences until the end of the current session.
+	#[pallet::storage]
+	pub(crate) type SessionDisabledValidators<T: Config> =
+		StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+	/// Validators disabled because of offences until the end of the current era.
+	#[pallet::storage]
+	pub(crate) type EraDisabledValidators<T: Config> =
+		StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+	/// Validators which took themselves offline with `go_offline` until the end of the current
+	/// session.
+	#[pallet::storage]
+	pub(crate) type SelfDisabledValidators<T: Config> =
+		StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+	/// The number of times each validator went offline with `go_offline` during the current era.
+	#[pallet::storage]
+	pub(crate) type SelfDisablesInEra<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
 	/// The last planned session scheduled by the session pallet.
 	///
 	/// This is basically in sync with the call to [`pallet_session::SessionManager::new_session`].
@@ -573,6 +603,10 @@ pub mod pallet {
 		Chilled(T::AccountId),
 		/// The stakers' rewards are getting paid. \[era_index, validator_stash\]
 		PayoutStarted(EraIndex, T::AccountId),
+		/// A validator took itself offline for the rest of the session. \[stash\]
+		ValidatorOffline(T::AccountId),
+		/// A validator which went offline is back online. \[stash\]
+		ValidatorOnline(T::AccountId),
 	}
 
 	#[pallet::error]
@@ -625,6 +659,16 @@ pub mod pallet {
 		/// There are too many validators in the system. Governance needs to adjust the staking
 		/// settings to keep things safe for the runtime.
 		TooManyValidators,
+		/// The stash is not part of the current session's validator set.
+		NotActiveValidator,
+		/// The validator is already disabled.
+		AlreadyOffline,
+		/// The validator did not go offline with `go_offline`.
+		NotOffline,
+		/// The validator went offline too many times during this era.
+		TooManySelfDisables,
+		/// Going offline would disable too many validators of the current session.
+		TooManyDisabled,
 	}
 
 	#[pallet::hooks]
@@ -876,7 +920,8 @@ pub mod pallet {
 		///
 		/// # <weight>
 		/// Complexity O(S) where S is the number of slashing spans to remove
-		/// NOTE: Weight annotation is the kill scenario, we refund otherwise.
+		/// NOTE: Weight annotation is the kill scenario with `num_slashing_spans`, we refund
+		/// otherwise.
 		/// # </weight>
 		#[pallet::weight(T::WeightInfo::withdraw_unbonded_kill(*num_slashing_spans))]
 		pub fn withdraw_unbonded(
@@ -896,11 +941,12 @@ pub mod pallet {
 				// This account must have called `unbond()` with some value that caused the active
 				// portion to fall below existential deposit + will have no more unlocking chunks
 				// left. We can now safely remove all staking-related information.
-				Self::kill_stash(&stash, num_slashing_spans)?;
+				let cleared_slashing_spans = Self::kill_stash(&stash, num_slashing_spans)?;
 				// Remove the lock.
 				T::Currency::remove_lock(STAKING_ID, &stash);
-				// This is worst case scenario, so we use the full weight and return None
-				None
+				// This is the worst case scenario, but only for the slashing spans actually
+				// removed.
+				Some(T::WeightInfo::withdraw_unbonded_kill(cleared_slashing_spans))
 			} else {
 				// This was the consequence of a partial unbond. just update the ledger and move on.
 				Self::update_ledger(&controller, &ledger);
@@ -1221,21 +1267,22 @@ pub mod pallet {
 		/// Reads: Bonded, Slashing Spans, Account, Locks
 		/// Writes: Bonded, Slashing Spans (if S > 0), Ledger, Payee, Validators, Nominators,
 		/// Account, Locks Writes Each: SpanSlash * S
+		/// NOTE: Weight annotation is for `num_slashing_spans`, the unused spans are refunded.
 		/// # </weight>
 		#[pallet::weight(T::WeightInfo::force_unstake(*num_slashing_spans))]
 		pub fn force_unstake(
 			origin: OriginFor<T>,
 			stash: T::AccountId,
 			num_slashing_spans: u32,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
 
 			// Remove all staking-related information.
-			Self::kill_stash(&stash, num_slashing_spans)?;
+			let cleared_slashing_spans = Self::kill_stash(&stash, num_slashing_spans)?;
 
 			// Remove the lock.
 			T::Currency::remove_lock(STAKING_ID, &stash);
-			Ok(())
+			Ok(Some(T::WeightInfo::force_unstake(cleared_slashing_spans)).into())
 		}
 
 		/// Force there to be a new era at the end of sessions indefinitely.
@@ -1423,18 +1470,19 @@ pub mod pallet {
 		/// - Writes: Bonded, Slashing Spans (if S > 0), Ledger, Payee, Validators, Nominators,
 		///   Stash Account, Locks
 		/// - Writes Each: SpanSlash * S
+		/// NOTE: Weight annotation is for `num_slashing_spans`, the unused spans are refunded.
 		/// # </weight>
 		#[pallet::weight(T::WeightInfo::reap_stash(*num_slashing_spans))]
 		pub fn reap_stash(
 			_origin: OriginFor<T>,
 			stash: T::AccountId,
 			num_slashing_spans: u32,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let at_minimum = T::Currency::total_balance(&stash) == T::Currency::minimum_balance();
 			ensure!(at_minimum, Error::<T>::FundedTarget);
-			Self::kill_stash(&stash, num_slashing_spans)?;
+			let cleared_slashing_spans = Self::kill_stash(&stash, num_slashing_spans)?;
 			T::Currency::remove_lock(STAKING_ID, &stash);
-			Ok(())
+			Ok(Some(T::WeightInfo::reap_stash(cleared_slashing_spans)).into())
 		}
 
 		/// Remove the given nominations from the calling validator.
@@ -1578,6 +1626,71 @@ pub mod pallet {
 			Self::chill_stash(&stash);
 			Ok(())
 		}
+
+		/// Take the validator offline for the rest of the current session.
+		///
+		/// The validator is disabled in the session pallet without any offence being reported,
+		/// and is enabled again at the start of the next session or with `go_online`. This can
+		/// only be done `MaxSelfDisablesPerEra` times per era, and is rejected if it would
+		/// disable too many validators of the current session.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
+		///
+		/// Emits `ValidatorOffline`.
+		#[pallet::weight(T::WeightInfo::go_offline())]
+		#[transactional]
+		pub fn go_offline(origin: OriginFor<T>) -> DispatchResult {
+			let controller = ensure_signed(origin)?;
+			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+			let stash = ledger.stash;
+
+			ensure!(
+				!SelfDisabledValidators::<T>::get().contains(&stash) &&
+					!SessionDisabledValidators::<T>::get().contains(&stash) &&
+					!EraDisabledValidators::<T>::get().contains(&stash),
+				Error::<T>::AlreadyOffline
+			);
+			let self_disables = SelfDisablesInEra::<T>::get(&stash);
+			ensure!(
+				self_disables < T::MaxSelfDisablesPerEra::get(),
+				Error::<T>::TooManySelfDisables
+			);
+
+			// unlike offences, going offline must never force a new era.
+			let threshold_reached = T::SessionInterface::disable_validator(&stash)
+				.map_err(|_| Error::<T>::NotActiveValidator)?;
+			ensure!(!threshold_reached, Error::<T>::TooManyDisabled);
+
+			SelfDisabledValidators::<T>::append(&stash);
+			SelfDisablesInEra::<T>::insert(&stash, self_disables + 1);
+			Self::deposit_event(Event::<T>::ValidatorOffline(stash));
+			Ok(())
+		}
+
+		/// Bring a validator which went offline with `go_offline` back online.
+		///
+		/// The validator stays disabled if it was also disabled because of an offence.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
+		///
+		/// Emits `ValidatorOnline`.
+		#[pallet::weight(T::WeightInfo::go_online())]
+		pub fn go_online(origin: OriginFor<T>) -> DispatchResult {
+			let controller = ensure_signed(origin)?;
+			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+			let stash = ledger.stash;
+
+			SelfDisabledValidators::<T>::try_mutate(|disabled| {
+				let position =
+					disabled.iter().position(|s| s == &stash).ok_or(Error::<T>::NotOffline)?;
+				disabled.remove(position);
+				Ok::<_, Error<T>>(())
+			})?;
+
+			Self::enable_self_disabled(&stash);
+			Self::deposit_event(Event::<T>::ValidatorOnline(stash));
+			Ok(())
+		}
 	}
 }
 
@@ -50,8 +50,8 @@
 //! Based on research at <https://w3f-research.readthedocs.io/en/latest/polkadot/slashing/npos.html>
 
 use crate::{
-	BalanceOf, Config, EraIndex, Error, Exposure, NegativeImbalanceOf, Pallet, Perbill,
-	SessionInterface, Store, UnappliedSlash,
+	BalanceOf, Config, EraIndex, Error, Exposure, NegativeImbalanceOf, Pallet, Perbill, Store,
+	UnappliedSlash,
 };
 use codec::{Decode, Encode};
 use frame_support::{
@@ -61,8 +61,9 @@ use frame_support::{
 use scale_info::TypeInfo;
 use sp_runtime::{
 	traits::{Saturating, Zero},
-	DispatchResult, RuntimeDebug,
+	DispatchError, RuntimeDebug,
 };
+use sp_staking::offence::OffenceSeverity;
 use sp_std::vec::Vec;
 
 /// The proportion of the slashing reward to be paid out on the first slashing detection.
@@ -279,11 +280,8 @@ pub(crate) fn compute_slash<T: Config>(
 			spans.end_span(now);
 			<Pallet<T>>::chill_stash(stash);
 
-			// make sure to disable validator till the end of this session
-			if T::SessionInterface::disable_validator(stash).unwrap_or(false) {
-				// force a new era, to select a new validator set
-				<Pallet<T>>::ensure_new_era()
-			}
+			// disable the validator for as long as the disabling strategy decides.
+			<Pallet<T>>::disable_offender(stash, OffenceSeverity(slash));
 		}
 	}
 
@@ -317,11 +315,8 @@ fn kick_out_if_recent<T: Config>(params: SlashParams<T>) {
 		spans.end_span(params.now);
 		<Pallet<T>>::chill_stash(params.stash);
 
-		// make sure to disable validator till the end of this session
-		if T::SessionInterface::disable_validator(params.stash).unwrap_or(false) {
-			// force a new era, to select a new validator set
-			<Pallet<T>>::ensure_new_era()
-		}
+		// disable the validator for as long as the disabling strategy decides.
+		<Pallet<T>>::disable_offender(params.stash, OffenceSeverity(params.slash));
 	}
 }
 
@@ -524,19 +519,19 @@ pub(crate) fn clear_era_metadata<T: Config>(obsolete_era: EraIndex) {
 }
 
 /// Clear slashing metadata for a dead account.
+///
+/// Returns the number of slashing spans cleared, at most `num_slashing_spans`.
 pub(crate) fn clear_stash_metadata<T: Config>(
 	stash: &T::AccountId,
 	num_slashing_spans: u32,
-) -> DispatchResult {
+) -> Result<u32, DispatchError> {
 	let spans = match <Pallet<T> as Store>::SlashingSpans::get(stash) {
-		None => return Ok(()),
+		None => return Ok(0),
 		Some(s) => s,
 	};
 
-	ensure!(
-		num_slashing_spans as usize >= spans.iter().count(),
-		Error::<T>::IncorrectSlashingSpans
-	);
+	let count = spans.iter().count();
+	ensure!(num_slashing_spans as usize >= count, Error::<T>::IncorrectSlashingSpans);
 
 	<Pallet<T> as Store>::SlashingSpans::remove(stash);
 
@@ -549,7 +544,7 @@ pub(crate) fn clear_stash_metadata<T: Config>(
 		<Pallet<T> as Store>::SpanSlash::remove(&(stash.clone(), span.index));
 	}
 
-	Ok(())
+	Ok(count as u32)
 }
 
 // apply the slash to a stash account, deducting any missing funds from the reward
@@ -68,6 +68,22 @@ fn force_unstake_works() {
 	});
 }
 
+#[test]
+fn force_unstake_refunds_unused_slashing_spans() {
+	ExtBuilder::default().build_and_execute(|| {
+		let weight = <Test as Config>::WeightInfo::force_unstake;
+		assert!(weight(2) < weight(10));
+		// Adds 2 slashing spans
+		add_slash(&11);
+		// The weight of the 8 spans declared but not removed is refunded.
+		let post_info = Staking::force_unstake(Origin::root(), 11, 10).unwrap();
+		assert_eq!(post_info.actual_weight, Some(weight(2)));
+		// Account 21 has no slashing span.
+		let post_info = Staking::force_unstake(Origin::root(), 21, 10).unwrap();
+		assert_eq!(post_info.actual_weight, Some(weight(0)));
+	});
+}
+
 #[test]
 fn kill_stash_works() {
 	ExtBuilder::default().build_and_execute(|| {
@@ -2198,6 +2214,154 @@ fn offence_deselects_validator_even_when_slash_is_zero() {
 	});
 }
 
+#[test]
+fn low_severity_offence_disables_for_session_only() {
+	ExtBuilder::default().build_and_execute(|| {
+		HighSeverityThreshold::set(Perbill::from_percent(10));
+		mock::start_active_era(1);
+		let index = Session::validators().iter().position(|v| *v == 11).unwrap() as u32;
+
+		on_offence_now(
+			&[OffenceDetails {
+				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				reporters: vec![],
+			}],
+			&[Perbill::from_percent(5)],
+		);
+
+		assert_eq!(SessionDisabledValidators::<Test>::get(), vec![11]);
+		assert!(EraDisabledValidators::<Test>::get().is_empty());
+		assert!(Session::disabled_validators().contains(&index));
+
+		// the validator is enabled again in the next session of the same era.
+		advance_session();
+		assert_eq!(active_era(), 1);
+		assert!(SessionDisabledValidators::<Test>::get().is_empty());
+		assert!(!Session::disabled_validators().contains(&index));
+	});
+}
+
+#[test]
+fn high_severity_offence_disables_for_era() {
+	ExtBuilder::default().build_and_execute(|| {
+		HighSeverityThreshold::set(Perbill::from_percent(10));
+		mock::start_active_era(1);
+		let index = Session::validators().iter().position(|v| *v == 11).unwrap() as u32;
+
+		on_offence_now(
+			&[OffenceDetails {
+				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				reporters: vec![],
+			}],
+			&[Perbill::from_percent(10)],
+		);
+
+		assert!(SessionDisabledValidators::<Test>::get().is_empty());
+		assert_eq!(EraDisabledValidators::<Test>::get(), vec![11]);
+		assert!(Session::disabled_validators().contains(&index));
+
+		// the validator stays disabled in the next session of the same era.
+		advance_session();
+		assert_eq!(active_era(), 1);
+		assert_eq!(EraDisabledValidators::<Test>::get(), vec![11]);
+		assert!(Session::disabled_validators().contains(&index));
+
+		// and is forgotten once a new era starts.
+		mock::start_active_era(2);
+		assert!(EraDisabledValidators::<Test>::get().is_empty());
+	});
+}
+
+#[test]
+fn low_severity_offences_respect_disabling_cap() {
+	ExtBuilder::default().build_and_execute(|| {
+		HighSeverityThreshold::set(Perbill::from_percent(10));
+		LowSeverityCap::set(Perbill::from_percent(50));
+		mock::start_active_era(1);
+		assert_eq!(Session::validators().len(), 2);
+
+		on_offence_now(
+			&[OffenceDetails {
+				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				reporters: vec![],
+			}],
+			&[Perbill::from_percent(5)],
+		);
+		on_offence_now(
+			&[OffenceDetails {
+				offender: (21, Staking::eras_stakers(active_era(), 21)),
+				reporters: vec![],
+			}],
+			&[Perbill::from_percent(5)],
+		);
+
+		// only half of the validator set may be disabled for low severity offences.
+		assert_eq!(SessionDisabledValidators::<Test>::get(), vec![11]);
+		assert!(!is_disabled(20));
+	});
+}
+
+#[test]
+fn go_offline_disables_for_session_without_slashing() {
+	ExtBuilder::default().validator_count(3).build_and_execute(|| {
+		mock::start_active_era(1);
+		let index = Session::validators().iter().position(|v| *v == 11).unwrap() as u32;
+		let balance = Balances::free_balance(11);
+
+		assert_ok!(Staking::go_offline(Origin::signed(10)));
+		assert_eq!(*staking_events().last().unwrap(), Event::ValidatorOffline(11));
+		assert!(Session::disabled_validators().contains(&index));
+		assert_noop!(Staking::go_offline(Origin::signed(10)), Error::<Test>::AlreadyOffline);
+
+		// no offence is recorded for going offline.
+		assert_eq!(Balances::free_balance(11), balance);
+		assert!(SessionDisabledValidators::<Test>::get().is_empty());
+
+		// the validator is enabled again in the next session of the same era.
+		advance_session();
+		assert_eq!(active_era(), 1);
+		assert!(!Session::disabled_validators().contains(&index));
+		assert_noop!(Staking::go_online(Origin::signed(10)), Error::<Test>::NotOffline);
+	});
+}
+
+#[test]
+fn go_online_re_enables_and_self_disables_are_limited_per_era() {
+	ExtBuilder::default().validator_count(3).build_and_execute(|| {
+		mock::start_active_era(1);
+		let index = Session::validators().iter().position(|v| *v == 11).unwrap() as u32;
+
+		for _ in 0..MaxSelfDisablesPerEra::get() {
+			assert_ok!(Staking::go_offline(Origin::signed(10)));
+			assert_ok!(Staking::go_online(Origin::signed(10)));
+			assert_eq!(*staking_events().last().unwrap(), Event::ValidatorOnline(11));
+			assert!(!Session::disabled_validators().contains(&index));
+		}
+		assert_noop!(Staking::go_offline(Origin::signed(10)), Error::<Test>::TooManySelfDisables);
+
+		// the limit is reset by the next era.
+		mock::start_active_era(2);
+		assert_ok!(Staking::go_offline(Origin::signed(10)));
+	});
+}
+
+#[test]
+fn go_offline_respects_disabled_validators_threshold() {
+	ExtBuilder::default().validator_count(3).build_and_execute(|| {
+		mock::start_active_era(1);
+		assert_eq!(Session::validators().len(), 3);
+
+		assert_ok!(Staking::go_offline(Origin::signed(10)));
+		// a second validator going offline would reach the threshold of the session pallet.
+		assert_noop!(Staking::go_offline(Origin::signed(20)), Error::<Test>::TooManyDisabled);
+		assert_eq!(Staking::force_era(), Forcing::NotForcing);
+
+		// nominators are not part of the validator set.
+		assert_noop!(Staking::go_offline(Origin::signed(100)), Error::<Test>::NotActiveValidator);
+		assert_noop!(Staking::go_offline(Origin::signed(11)), Error::<Test>::NotController);
+	});
+}
+
 #[test]
 fn slashing_performed_according_exposure() {
 	// This test checks that slashing is performed according the exposure (or more precisely,
@@ -4383,3 +4547,47 @@ mod sorted_list_provider {
 		});
 	}
 }
+
+#[test]
+fn validator_diagnostics_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		// an elected and active validator.
+		let diagnostics = Staking::validator_diagnostics(&11);
+		assert!(diagnostics.bonded && diagnostics.intends_to_validate);
+		assert!(diagnostics.elected && diagnostics.active && diagnostics.queued);
+		assert!(!diagnostics.disabled);
+		assert_eq!(diagnostics.active_bond, 1000);
+		assert_eq!(diagnostics.session_keys, None);
+
+		assert_ok!(Session::disable(&11));
+		assert!(Staking::validator_diagnostics(&11).disabled);
+
+		// a validator which lost the election.
+		assert_ok!(Session::set_keys(
+			Origin::signed(30),
+			SessionKeys { other: sp_runtime::testing::UintAuthorityId(31) },
+			vec![],
+		));
+		assert_eq!(
+			Staking::validator_diagnostics(&31),
+			ValidatorDiagnostics {
+				session_keys: Some(vec![(
+					sp_runtime::key_types::DUMMY,
+					31u64.to_le_bytes().to_vec()
+				)]),
+				bonded: true,
+				active_bond: 500,
+				min_validator_bond: ExistentialDeposit::get(),
+				intends_to_validate: true,
+				elected: false,
+				active: false,
+				disabled: false,
+				queued: false,
+			},
+		);
+
+		// an idle stash only has its bond.
+		let diagnostics = Staking::validator_diagnostics(&41);
+		assert!(diagnostics.bonded && !diagnostics.intends_to_validate && !diagnostics.elected);
+	});
+}
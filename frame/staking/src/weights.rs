@@ -73,6 +73,8 @@ pub trait WeightInfo {
 	fn get_npos_targets(v: u32, ) -> Weight;
 	fn set_staking_limits() -> Weight;
 	fn chill_other() -> Weight;
+	fn go_offline() -> Weight;
+	fn go_online() -> Weight;
 }
 
 /// Weights for pallet_staking using the Substrate node and recommended hardware.
@@ -442,6 +444,31 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(11 as Weight))
 			.saturating_add(T::DbWeight::get().writes(6 as Weight))
 	}
+	// Storage: Staking Ledger (r:1 w:0)
+	// Storage: Staking SelfDisabledValidators (r:1 w:1)
+	// Storage: Staking SessionDisabledValidators (r:1 w:0)
+	// Storage: Staking EraDisabledValidators (r:1 w:0)
+	// Storage: Staking SelfDisablesInEra (r:1 w:1)
+	// Storage: Session Validators (r:1 w:0)
+	// Storage: Session DisabledValidators (r:1 w:1)
+	// Storage: System Digest (r:1 w:1)
+	// Storage: ImOnline DisabledValidators (r:1 w:1)
+	fn go_offline() -> Weight {
+		(78_076_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Staking Ledger (r:1 w:0)
+	// Storage: Staking SelfDisabledValidators (r:1 w:1)
+	// Storage: Staking SessionDisabledValidators (r:1 w:0)
+	// Storage: Staking EraDisabledValidators (r:1 w:0)
+	// Storage: Session Validators (r:1 w:0)
+	// Storage: Session DisabledValidators (r:1 w:1)
+	fn go_online() -> Weight {
+		(37_137_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -810,4 +837,29 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
 	}
+	// Storage: Staking Ledger (r:1 w:0)
+	// Storage: Staking SelfDisabledValidators (r:1 w:1)
+	// Storage: Staking SessionDisabledValidators (r:1 w:0)
+	// Storage: Staking EraDisabledValidators (r:1 w:0)
+	// Storage: Staking SelfDisablesInEra (r:1 w:1)
+	// Storage: Session Validators (r:1 w:0)
+	// Storage: Session DisabledValidators (r:1 w:1)
+	// Storage: System Digest (r:1 w:1)
+	// Storage: ImOnline DisabledValidators (r:1 w:1)
+	fn go_offline() -> Weight {
+		(78_076_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Staking Ledger (r:1 w:0)
+	// Storage: Staking SelfDisabledValidators (r:1 w:1)
+	// Storage: Staking SessionDisabledValidators (r:1 w:0)
+	// Storage: Staking EraDisabledValidators (r:1 w:0)
+	// Storage: Session Validators (r:1 w:0)
+	// Storage: Session DisabledValidators (r:1 w:1)
+	fn go_online() -> Weight {
+		(37_137_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
 }
@@ -0,0 +1,408 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of the `benchmarks` attribute macro.
+//!
+//! Every `#[benchmark]` function of the annotated module is parsed and expanded into the
+//! `BenchmarkingSetup` implementation of the benchmark, its statements being emitted as they are.
+//! Only the selection of the benchmarks and their `Benchmarking` implementation, which depend on
+//! their names alone, are left to the macros of `frame-benchmarking`.
+
+use frame_support_procedural_tools::generate_crate_access_2018;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+	parse::{Parse, ParseStream},
+	spanned::Spanned,
+	Error, Expr, FnArg, GenericArgument, Ident, Item, ItemFn, ItemMod, Pat, PathArguments, Result,
+	ReturnType, Stmt, Token, Type, WhereClause,
+};
+
+mod keyword {
+	syn::custom_keyword!(instance);
+	syn::custom_keyword!(extra);
+	syn::custom_keyword!(skip_meta);
+}
+
+/// What the benchmarks of a module are expanded with.
+struct Context {
+	/// The path of `frame-benchmarking`.
+	crate_: Ident,
+	/// The instance of the pallet, if instantiable.
+	instance: Option<Ident>,
+	/// The predicates of the `where` clause of the benchmarks.
+	predicates: Option<TokenStream2>,
+}
+
+/// Arguments of the `benchmarks` attribute.
+struct BenchmarksAttr {
+	/// Whether the benchmarks are written for an instantiable pallet.
+	instance: bool,
+	where_clause: Option<WhereClause>,
+}
+
+impl Parse for BenchmarksAttr {
+	fn parse(input: ParseStream) -> Result<Self> {
+		let instance = input.parse::<Option<keyword::instance>>()?.is_some();
+		if instance && !input.is_empty() {
+			input.parse::<Token![,]>()?;
+		}
+		let where_clause = if input.is_empty() { None } else { Some(input.parse()?) };
+		Ok(BenchmarksAttr { instance, where_clause })
+	}
+}
+
+/// A component of a benchmark and its range, e.g. `x: Linear<1, 100>`.
+struct Component {
+	name: Ident,
+	from: TokenStream2,
+	to: TokenStream2,
+}
+
+/// The benchmarked section of a benchmark.
+enum BenchmarkCall {
+	/// A call to a dispatchable, `_` standing for the name of the benchmark.
+	Extrinsic { dispatchable: Option<Ident>, origin: Expr, args: Vec<Expr> },
+	/// An arbitrary block of code.
+	Block(syn::Block),
+}
+
+/// A benchmark defined by a `#[benchmark]` function.
+struct Benchmark {
+	name: Ident,
+	/// Either `extra` or `skip_meta`, if any.
+	tag: Option<Ident>,
+	components: Vec<Component>,
+	setup: Vec<Stmt>,
+	call: BenchmarkCall,
+	verify: Vec<Stmt>,
+}
+
+pub fn benchmarks(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
+	let BenchmarksAttr { instance, where_clause } = syn::parse(attr)?;
+	let ItemMod { attrs, vis, mod_token, ident, content, .. } = syn::parse(item)?;
+	let items = match content {
+		Some((_, items)) => items,
+		None =>
+			return Err(Error::new(
+				ident.span(),
+				"`#[benchmarks]` must be applied to an inline module",
+			)),
+	};
+
+	let mut benchmarks = Vec::new();
+	let mut other_items = Vec::new();
+	for item in items {
+		match item {
+			Item::Fn(item_fn)
+				if item_fn.attrs.iter().any(|attr| attr.path.is_ident("benchmark")) =>
+				benchmarks.push(Benchmark::from_item_fn(item_fn)?),
+			item => other_items.push(item),
+		}
+	}
+
+	let context = Context {
+		crate_: generate_crate_access_2018("frame-benchmarking")?,
+		instance: if instance { Some(format_ident!("I")) } else { None },
+		predicates: where_clause.map(|where_clause| where_clause.predicates.into_token_stream()),
+	};
+	let Context { crate_, predicates, .. } = &context;
+	let (instance_bound, instance) = match &context.instance {
+		Some(instance) => (quote!(#instance: 'static), quote!(#instance)),
+		None => (quote!(), quote!()),
+	};
+	let names = benchmarks.iter().map(|benchmark| &benchmark.name).collect::<Vec<_>>();
+	let tagged = |tag: &str| {
+		benchmarks
+			.iter()
+			.filter(|benchmark| benchmark.tag.as_ref().map_or(false, |t| t == tag))
+			.map(|benchmark| &benchmark.name)
+			.collect::<Vec<_>>()
+	};
+	let (extra, skip_meta) = (tagged("extra"), tagged("skip_meta"));
+	let benchmarks = benchmarks.iter().map(|benchmark| benchmark.expand(&context));
+
+	let output = quote! {
+		#(#attrs)*
+		#vis #mod_token #ident {
+			#(#other_items)*
+
+			#(#benchmarks)*
+
+			#crate_::selected_benchmark!(
+				{ #predicates }
+				{ #instance_bound }
+				#( { #instance } #names )*
+			);
+			#crate_::impl_benchmark!(
+				{ #predicates }
+				{ #instance_bound }
+				( #( { #instance } #names )* )
+				( #(#extra),* )
+				( #(#skip_meta),* )
+			);
+		}
+	};
+
+	Ok(output.into())
+}
+
+impl Benchmark {
+	fn from_item_fn(item_fn: ItemFn) -> Result<Self> {
+		let ItemFn { attrs, sig, block, .. } = item_fn;
+
+		let mut tag = None;
+		for attr in attrs {
+			if attr.path.is_ident("doc") {
+				continue
+			}
+			if !attr.path.is_ident("benchmark") {
+				return Err(Error::new(attr.span(), "unexpected attribute on a benchmark"))
+			}
+			if !attr.tokens.is_empty() {
+				tag = Some(attr.parse_args_with(parse_benchmark_tag)?);
+			}
+		}
+
+		if !sig.generics.params.is_empty() || sig.generics.where_clause.is_some() {
+			return Err(Error::new(
+				sig.generics.span(),
+				"benchmarks can't be generic, use the `where` clause of `#[benchmarks]` instead",
+			))
+		}
+		if let ReturnType::Type(_, ty) = &sig.output {
+			return Err(Error::new(ty.span(), "benchmarks can't return a value"))
+		}
+		let components =
+			sig.inputs.iter().map(Component::from_fn_arg).collect::<Result<Vec<_>>>()?;
+
+		let mut setup = Vec::new();
+		let mut call = None;
+		let mut verify = Vec::new();
+		for mut stmt in block.stmts {
+			match take_call(&mut stmt)? {
+				Some(_) if call.is_some() =>
+					return Err(Error::new(
+						stmt.span(),
+						"a benchmark must have a single `#[extrinsic_call]` or `#[block]`",
+					)),
+				Some(benchmark_call) => call = Some(benchmark_call),
+				None if call.is_some() => verify.push(stmt),
+				None => setup.push(stmt),
+			}
+		}
+		let call = call.ok_or_else(|| {
+			Error::new(
+				sig.ident.span(),
+				"a benchmark must have an `#[extrinsic_call]` or a `#[block]`",
+			)
+		})?;
+
+		Ok(Benchmark { name: sig.ident, tag, components, setup, call, verify })
+	}
+
+	/// Expand into the `BenchmarkingSetup` implementation of the benchmark, and its unit test.
+	fn expand(&self, context: &Context) -> TokenStream2 {
+		let Benchmark { name, components, setup, call, verify, .. } = self;
+		let Context { crate_, instance, predicates } = context;
+		let (impl_instance, instance_bound, instance) = match instance {
+			Some(instance) => (
+				quote!(<#instance>, #instance: 'static),
+				quote!(#instance: 'static),
+				quote!(, #instance),
+			),
+			None => (quote!(), quote!(), quote!()),
+		};
+		let params = components.iter().map(|component| &component.name).collect::<Vec<_>>();
+		let ranges = components.iter().map(|Component { from, to, .. }| quote!((#from), (#to)));
+
+		// The arguments of a dispatchable are evaluated during the setup, its call being decoded
+		// and dispatched in the benchmarked section, as done by `benchmarks!`.
+		let (call_setup, call) = match call {
+			BenchmarkCall::Extrinsic { dispatchable, origin, args } => {
+				let new_call =
+					format_ident!("new_call_variant_{}", dispatchable.as_ref().unwrap_or(name));
+				(
+					quote! {
+						let __call = Call::<T #instance>::#new_call(#(#args),*);
+						let __benchmarked_call_encoded =
+							#crate_::frame_support::codec::Encode::encode(&__call);
+					},
+					quote! {
+						let __call_decoded = <
+							Call<T #instance> as #crate_::frame_support::codec::Decode
+						>::decode(&mut &__benchmarked_call_encoded[..])
+							.expect("call is encoded above, encoding must be correct");
+						<
+							Call<T #instance> as #crate_::frame_support::traits::UnfilteredDispatchable
+						>::dispatch_bypass_filter(__call_decoded, #origin.into())?;
+					},
+				)
+			},
+			BenchmarkCall::Block(block) => (quote!(), quote!(#block;)),
+		};
+
+		quote! {
+			#[allow(non_camel_case_types)]
+			struct #name;
+
+			#[allow(unused_variables)]
+			impl<T: Config #impl_instance> #crate_::BenchmarkingSetup<T #instance> for #name
+			where #predicates
+			{
+				fn components(&self) -> #crate_::Vec<(#crate_::BenchmarkParameter, u32, u32)> {
+					#crate_::vec![ #( (#crate_::BenchmarkParameter::#params, #ranges) ),* ]
+				}
+
+				fn instance(
+					&self,
+					components: &[(#crate_::BenchmarkParameter, u32)],
+					verify: bool,
+				) -> Result<
+					#crate_::Box<dyn FnOnce() -> Result<(), #crate_::BenchmarkError>>,
+					#crate_::BenchmarkError,
+				> {
+					#(
+						let #params = components
+							.iter()
+							.find(|&c| c.0 == #crate_::BenchmarkParameter::#params)
+							.ok_or("Could not find component in benchmark preparation.")?
+							.1;
+					)*
+					#(#setup)*
+					#call_setup
+
+					Ok(#crate_::Box::new(move || -> Result<(), #crate_::BenchmarkError> {
+						#call
+						if verify {
+							#(#verify)*
+						}
+						Ok(())
+					}))
+				}
+			}
+
+			#[cfg(test)]
+			#crate_::impl_benchmark_test!({ #predicates } { #instance_bound } #name);
+		}
+	}
+}
+
+/// Parse the argument of `#[benchmark(..)]`.
+fn parse_benchmark_tag(input: ParseStream) -> Result<Ident> {
+	let lookahead = input.lookahead1();
+	if lookahead.peek(keyword::extra) || lookahead.peek(keyword::skip_meta) {
+		input.call(Ident::parse)
+	} else {
+		Err(lookahead.error())
+	}
+}
+
+impl Component {
+	fn from_fn_arg(arg: &FnArg) -> Result<Self> {
+		let invalid =
+			|| Error::new(arg.span(), "components must be declared as `name: Linear<FROM, TO>`");
+		let pat_type = match arg {
+			FnArg::Typed(pat_type) => pat_type,
+			FnArg::Receiver(_) => return Err(invalid()),
+		};
+		let name = match &*pat_type.pat {
+			Pat::Ident(pat) if pat.by_ref.is_none() && pat.subpat.is_none() => pat.ident.clone(),
+			_ => return Err(invalid()),
+		};
+		// The components are identified by the variants of `BenchmarkParameter`.
+		let is_parameter = {
+			let name = name.to_string();
+			name.len() == 1 && name.chars().all(|c| c.is_ascii_lowercase())
+		};
+		if !is_parameter {
+			return Err(Error::new(name.span(), "components must be named by a lowercase letter"))
+		}
+		let segment = match &*pat_type.ty {
+			Type::Path(ty) if ty.qself.is_none() =>
+				ty.path.segments.last().ok_or_else(invalid)?.clone(),
+			_ => return Err(invalid()),
+		};
+		let bounds = match segment.arguments {
+			PathArguments::AngleBracketed(args) if segment.ident == "Linear" => args.args,
+			_ => return Err(invalid()),
+		};
+		let mut bounds = bounds.into_iter().map(|bound| match bound {
+			GenericArgument::Const(expr) => Ok(expr.into_token_stream()),
+			// Constants given by name are parsed as types.
+			GenericArgument::Type(ty) => Ok(ty.into_token_stream()),
+			_ => Err(invalid()),
+		});
+		match (bounds.next(), bounds.next(), bounds.next()) {
+			(Some(from), Some(to), None) => Ok(Component { name, from: from?, to: to? }),
+			_ => Err(invalid()),
+		}
+	}
+}
+
+/// Remove the `#[extrinsic_call]` or `#[block]` attribute of a statement, returning the
+/// benchmarked section it marks.
+fn take_call(stmt: &mut Stmt) -> Result<Option<BenchmarkCall>> {
+	let expr = match stmt {
+		Stmt::Expr(expr) | Stmt::Semi(expr, _) => expr,
+		_ => return Ok(None),
+	};
+	let attrs = match expr {
+		Expr::Call(call) => &mut call.attrs,
+		Expr::Block(block) => &mut block.attrs,
+		Expr::MethodCall(call) => &mut call.attrs,
+		Expr::Macro(call) => &mut call.attrs,
+		_ => return Ok(None),
+	};
+	let marker = match attrs
+		.iter()
+		.position(|attr| attr.path.is_ident("extrinsic_call") || attr.path.is_ident("block"))
+	{
+		Some(index) => attrs.remove(index),
+		None => return Ok(None),
+	};
+	if !marker.tokens.is_empty() {
+		return Err(Error::new(marker.tokens.span(), "unexpected arguments"))
+	}
+
+	match expr {
+		Expr::Call(call) if marker.path.is_ident("extrinsic_call") => {
+			let dispatchable = match &*call.func {
+				Expr::Path(path) if path.path.get_ident().is_some() =>
+					path.path.get_ident().cloned(),
+				Expr::Verbatim(tokens) if tokens.to_string() == "_" => None,
+				func =>
+					return Err(Error::new(
+						func.span(),
+						"expected the name of a dispatchable of the pallet, or `_`",
+					)),
+			};
+			let mut args = call.args.clone().into_iter();
+			let origin = args.next().ok_or_else(|| {
+				Error::new(call.span(), "the first argument of the call must be its origin")
+			})?;
+			Ok(Some(BenchmarkCall::Extrinsic { dispatchable, origin, args: args.collect() }))
+		},
+		Expr::Block(block) if marker.path.is_ident("block") =>
+			Ok(Some(BenchmarkCall::Block(block.block.clone()))),
+		_ => Err(Error::new(
+			marker.span(),
+			"`#[extrinsic_call]` must mark a call `name(origin, args..)` and `#[block]` a block",
+		)),
+	}
+}
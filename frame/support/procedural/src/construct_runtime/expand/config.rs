@@ -88,6 +88,16 @@ pub fn expand_outer_config(
 				Ok(())
 			}
 		}
+
+		#[cfg(any(feature = "std", test))]
+		impl #scrate::traits::BuildGenesisConfig for GenesisConfig {
+			fn build(&self) -> std::result::Result<(), #scrate::sp_runtime::RuntimeString> {
+				let storage = #scrate::sp_runtime::BuildStorage::build_storage(self)
+					.map_err(#scrate::sp_runtime::RuntimeString::Owned)?;
+				#scrate::genesis_builder_helper::put_storage(storage);
+				Ok(())
+			}
+		}
 	}
 }
 
@@ -19,6 +19,7 @@
 
 #![recursion_limit = "512"]
 
+mod benchmark;
 mod clone_no_bound;
 mod construct_runtime;
 mod debug_no_bound;
@@ -354,6 +355,12 @@ pub fn pallet(attr: TokenStream, item: TokenStream) -> TokenStream {
 	pallet::pallet(attr, item)
 }
 
+/// Macro to define benchmarks in a module. Docs are at `frame_benchmarking::v2`.
+#[proc_macro_attribute]
+pub fn benchmarks(attr: TokenStream, item: TokenStream) -> TokenStream {
+	benchmark::benchmarks(attr, item).unwrap_or_else(|e| e.to_compile_error().into())
+}
+
 /// Execute the annotated function in a new storage transaction.
 ///
 /// The return type of the annotated function must be `Result`. All changes to storage performed
@@ -0,0 +1,128 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of the `GenesisBuilder` runtime api of `sp-genesis-builder` for a genesis
+//! config `GC` implementing [`BuildGenesisConfig`].
+//!
+//! ```ignore
+//! impl sp_genesis_builder::GenesisBuilder<Block> for Runtime {
+//! 	fn create_default_config() -> Vec<u8> {
+//! 		frame_support::genesis_builder_helper::create_default_config::<GenesisConfig>()
+//! 	}
+//!
+//! 	fn build_config(config: Vec<u8>) -> sp_genesis_builder::Result {
+//! 		frame_support::genesis_builder_helper::build_config::<GenesisConfig>(config)
+//! 	}
+//! }
+//! ```
+//!
+//! The helper doesn't require `std`, the config being serialized by `serde_json` without it, so
+//! the api is available in the wasm runtime as long as `GC` is.
+
+use crate::traits::BuildGenesisConfig;
+use serde::{de::DeserializeOwned, Serialize};
+use sp_runtime::format_runtime_string;
+use sp_std::vec::Vec;
+
+/// The JSON encoded default genesis config `GC` of the runtime.
+pub fn create_default_config<GC: BuildGenesisConfig + Serialize>() -> Vec<u8> {
+	serde_json::to_vec(&GC::default())
+		.expect("the default genesis config can always be serialized; qed")
+}
+
+/// Build the genesis storage from the JSON encoded genesis config `GC`, writing it to the current
+/// externalities.
+pub fn build_config<GC: BuildGenesisConfig + DeserializeOwned>(
+	json: Vec<u8>,
+) -> sp_genesis_builder::Result {
+	let config = serde_json::from_slice::<GC>(&json)
+		.map_err(|e| format_runtime_string!("Invalid JSON genesis config: {}", e))?;
+	config.build()
+}
+
+/// Write the given genesis `storage` to the current externalities.
+///
+/// It is used by the implementation of [`BuildGenesisConfig`] of the `GenesisConfig` generated by
+/// `construct_runtime!`, which builds its storage with `std`.
+#[cfg(feature = "std")]
+pub fn put_storage(storage: crate::sp_runtime::Storage) {
+	for (key, value) in storage.top {
+		sp_io::storage::set(&key, &value);
+	}
+	for (_, child) in storage.children_default {
+		let storage_key = child.child_info.storage_key();
+		for (key, value) in child.data {
+			sp_io::default_child_storage::set(storage_key, &key, &value);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sp_runtime::{BuildStorage, RuntimeString, Storage};
+	use serde::Deserialize;
+	use sp_core::storage::{ChildInfo, StorageChild};
+
+	#[derive(Default, Serialize, Deserialize)]
+	struct TestConfig {
+		value: u32,
+	}
+
+	impl BuildStorage for TestConfig {
+		fn assimilate_storage(&self, storage: &mut Storage) -> Result<(), String> {
+			storage.top.insert(b"value".to_vec(), self.value.to_le_bytes().to_vec());
+			storage.children_default.insert(
+				b"child".to_vec(),
+				StorageChild {
+					data: vec![(b"key".to_vec(), b"child".to_vec())].into_iter().collect(),
+					child_info: ChildInfo::new_default(b"child"),
+				},
+			);
+			Ok(())
+		}
+	}
+
+	impl BuildGenesisConfig for TestConfig {
+		fn build(&self) -> Result<(), RuntimeString> {
+			put_storage(self.build_storage().map_err(RuntimeString::Owned)?);
+			Ok(())
+		}
+	}
+
+	#[test]
+	fn default_config_is_built_into_externalities() {
+		let json = create_default_config::<TestConfig>();
+		assert_eq!(json, br#"{"value":0}"#.to_vec());
+
+		sp_io::TestExternalities::default().execute_with(|| {
+			build_config::<TestConfig>(br#"{"value":42}"#.to_vec()).unwrap();
+			assert_eq!(sp_io::storage::get(b"value"), Some(42u32.to_le_bytes().to_vec()));
+			assert_eq!(
+				sp_io::default_child_storage::get(b"child", b"key"),
+				Some(b"child".to_vec()),
+			);
+		});
+	}
+
+	#[test]
+	fn invalid_config_is_rejected() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			assert!(build_config::<TestConfig>(b"not json".to_vec()).is_err());
+		});
+	}
+}
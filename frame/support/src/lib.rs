@@ -60,6 +60,7 @@ pub mod event;
 pub mod inherent;
 #[macro_use]
 pub mod error;
+pub mod genesis_builder_helper;
 pub mod instances;
 pub mod migrations;
 pub mod traits;
@@ -702,6 +703,10 @@ pub use frame_support_procedural::DefaultNoBound;
 /// ```
 pub use frame_support_procedural::require_transactional;
 
+/// Define benchmarks in a module, see `frame_benchmarking::v2`.
+#[doc(hidden)]
+pub use frame_support_procedural::benchmarks;
+
 /// Return Err of the expression: `return Err($expression);`.
 ///
 /// Used as `fail!(expression)`.
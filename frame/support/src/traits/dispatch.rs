@@ -17,8 +17,12 @@
 
 //! Traits for dealing with dispatching calls and the origin from which they are dispatched.
 
-use crate::dispatch::DispatchResultWithPostInfo;
-use sp_runtime::traits::BadOrigin;
+use crate::{dispatch::DispatchResultWithPostInfo, traits::Get};
+use sp_runtime::{
+	traits::{BadOrigin, Morph},
+	Either,
+};
+use sp_std::marker::PhantomData;
 
 /// Some sort of check on the origin is performed by this object.
 pub trait EnsureOrigin<OuterOrigin> {
@@ -38,6 +42,117 @@ pub trait EnsureOrigin<OuterOrigin> {
 	fn successful_origin() -> OuterOrigin;
 }
 
+/// Some sort of check on the origin is performed by this object, given an argument such as the
+/// item the origin wants to act upon.
+pub trait EnsureOriginWithArg<OuterOrigin, Argument> {
+	/// A return type.
+	type Success;
+	/// Perform the origin check.
+	fn ensure_origin(o: OuterOrigin, a: &Argument) -> Result<Self::Success, BadOrigin> {
+		Self::try_origin(o, a).map_err(|_| BadOrigin)
+	}
+	/// Perform the origin check.
+	fn try_origin(o: OuterOrigin, a: &Argument) -> Result<Self::Success, OuterOrigin>;
+
+	/// Returns an outer origin capable of passing `try_origin` check for the argument `a`.
+	///
+	/// ** Should be used for benchmarking only!!! **
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin(a: &Argument) -> OuterOrigin;
+}
+
+/// An `EnsureOriginWithArg` implementation which ignores its argument and performs the origin
+/// check of an `EnsureOrigin` implementation.
+pub struct AsEnsureOriginWithArg<EO>(PhantomData<EO>);
+impl<O, A, EO: EnsureOrigin<O>> EnsureOriginWithArg<O, A> for AsEnsureOriginWithArg<EO> {
+	type Success = EO::Success;
+	fn try_origin(o: O, _: &A) -> Result<Self::Success, O> {
+		EO::try_origin(o)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin(_: &A) -> O {
+		EO::successful_origin()
+	}
+}
+
+/// A derivative `EnsureOrigin` implementation. It mutates the `Success` result of an `Original`
+/// implementation with a given `Mutator`.
+pub struct MapSuccess<Original, Mutator>(PhantomData<(Original, Mutator)>);
+impl<O, Original: EnsureOrigin<O>, Mutator: Morph<Original::Success>> EnsureOrigin<O>
+	for MapSuccess<Original, Mutator>
+{
+	type Success = Mutator::Outcome;
+	fn try_origin(o: O) -> Result<Mutator::Outcome, O> {
+		Original::try_origin(o).map(Mutator::morph)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		Original::successful_origin()
+	}
+}
+
+/// A derivative `EnsureOrigin` implementation. It passes whenever `Original` passes, but replaces
+/// its `Success` value with the value returned by `Success`.
+///
+/// `Value` is the type of the new `Success` value.
+pub struct EnsureWithSuccess<Original, Value, Success>(PhantomData<(Original, Value, Success)>);
+impl<O, Original: EnsureOrigin<O>, Value, Success: Get<Value>> EnsureOrigin<O>
+	for EnsureWithSuccess<Original, Value, Success>
+{
+	type Success = Value;
+	fn try_origin(o: O) -> Result<Value, O> {
+		Original::try_origin(o).map(|_| Success::get())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		Original::successful_origin()
+	}
+}
+
+/// "OR gate" implementation of `EnsureOrigin` allowing for different `Success` types for `L`
+/// and `R`, with them combined using an `Either` type.
+///
+/// Origin check will pass if `L` or `R` origin check passes. `L` is tested first.
+///
+/// Successful origin is derived from the left side.
+pub struct EitherOfDiverse<L, R>(PhantomData<(L, R)>);
+impl<O, L: EnsureOrigin<O>, R: EnsureOrigin<O>> EnsureOrigin<O> for EitherOfDiverse<L, R> {
+	type Success = Either<L::Success, R::Success>;
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		L::try_origin(o)
+			.map_or_else(|o| R::try_origin(o).map(Either::Right), |o| Ok(Either::Left(o)))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		L::successful_origin()
+	}
+}
+
+/// "OR gate" implementation of `EnsureOrigin`, `Success` type for both `L` and `R` must
+/// be equal.
+///
+/// Origin check will pass if `L` or `R` origin check passes. `L` is tested first.
+///
+/// Successful origin is derived from the left side.
+pub struct EitherOf<L, R>(PhantomData<(L, R)>);
+impl<O, L: EnsureOrigin<O>, R: EnsureOrigin<O, Success = L::Success>> EnsureOrigin<O>
+	for EitherOf<L, R>
+{
+	type Success = L::Success;
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		L::try_origin(o).or_else(|o| R::try_origin(o))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		L::successful_origin()
+	}
+}
+
 /// Type that can be dispatched with an origin but without checking the origin filter.
 ///
 /// Implemented for pallet dispatchable type by `decl_module` and for runtime dispatchable by
@@ -291,6 +291,16 @@ pub trait Hooks<BlockNumber> {
 	fn integrity_test() {}
 }
 
+/// A trait to build the genesis storage of a runtime from its genesis config.
+///
+/// Unlike [`GenesisBuild`], it is available without `std`, so that the genesis storage can be
+/// built by the wasm runtime, see `genesis_builder_helper`. It is implemented by the
+/// `GenesisConfig` generated by `construct_runtime!`.
+pub trait BuildGenesisConfig: Default {
+	/// Write the genesis storage to the current externalities.
+	fn build(&self) -> Result<(), sp_runtime::RuntimeString>;
+}
+
 /// A trait to define the build function of a genesis config, T and I are placeholder for pallet
 /// trait and pallet instance.
 #[cfg(feature = "std")]
@@ -66,6 +66,34 @@ impl<T> Contains<T> for Tuple {
 	}
 }
 
+/// A trait for querying whether a type can be said to "contain" a pair-value.
+pub trait ContainsPair<A, B> {
+	/// Return `true` if this "contains" the pair-value `(a, b)`.
+	fn contains(a: &A, b: &B) -> bool;
+}
+
+impl<A, B> ContainsPair<A, B> for Everything {
+	fn contains(_: &A, _: &B) -> bool {
+		true
+	}
+}
+
+impl<A, B> ContainsPair<A, B> for Nothing {
+	fn contains(_: &A, _: &B) -> bool {
+		false
+	}
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(0, 30)]
+impl<A, B> ContainsPair<A, B> for Tuple {
+	fn contains(a: &A, b: &B) -> bool {
+		for_tuples!( #(
+			if Tuple::contains(a, b) { return true }
+		)* );
+		false
+	}
+}
+
 /// Create a type which implements the `Contains` trait for a particular type with syntax similar
 /// to `matches!`.
 #[macro_export]
@@ -121,6 +149,22 @@ pub trait SortedMembers<T: Ord> {
 	}
 }
 
+/// A trait for a group whose members hold a rank.
+pub trait RankedMembers<AccountId> {
+	/// The rank of a member, higher ranks hold more privileges.
+	type Rank: Ord;
+
+	/// Get the rank of `who`, or `None` if `who` isn't a member.
+	fn rank_of(who: &AccountId) -> Option<Self::Rank>;
+
+	/// Make `who` a member of rank `rank`. It does not make sure any other state is correctly
+	/// maintained or generated.
+	///
+	/// **Should be used for benchmarking only!!!**
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_rank(who: &AccountId, rank: Self::Rank);
+}
+
 /// Adapter struct for turning an `OrderedMembership` impl into a `Contains` impl.
 pub struct AsContains<OM>(PhantomData<(OM,)>);
 impl<T: Ord + Eq, OM: SortedMembers<T>> Contains<T> for AsContains<OM> {
@@ -17,8 +17,10 @@
 
 //! Traits for managing information attached to pallets and their constituents.
 
+use super::{Contains, ContainsPair};
 use codec::{Decode, Encode};
 use sp_runtime::RuntimeDebug;
+use sp_std::{marker::PhantomData, prelude::*};
 
 /// Provides information about the pallet setup in the runtime.
 ///
@@ -68,6 +70,21 @@ pub trait GetCallMetadata {
 	fn get_call_metadata(&self) -> CallMetadata;
 }
 
+/// A [`Contains`] implementation for calls which matches a call by its pallet and function name.
+///
+/// `Names` is queried with the `(pallet_name, function_name)` pair of the call, as given by
+/// [`GetCallMetadata`], encoded as bytes. This allows the set of matched calls to be kept in
+/// storage, e.g. to pause or allow calls by their human-readable names.
+pub struct CallNameFilter<Names>(PhantomData<Names>);
+impl<Call: GetCallMetadata, Names: ContainsPair<Vec<u8>, Vec<u8>>> Contains<Call>
+	for CallNameFilter<Names>
+{
+	fn contains(call: &Call) -> bool {
+		let CallMetadata { pallet_name, function_name } = call.get_call_metadata();
+		Names::contains(&pallet_name.as_bytes().to_vec(), &function_name.as_bytes().to_vec())
+	}
+}
+
 /// The storage key postfix that is used to store the [`StorageVersion`] per pallet.
 ///
 /// The full storage key is built by using:
@@ -165,6 +182,35 @@ pub trait GetStorageVersion {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::traits::Everything;
+
+	struct Call(&'static str);
+	impl GetCallMetadata for Call {
+		fn get_module_names() -> &'static [&'static str] {
+			&["Balances"]
+		}
+		fn get_call_names(_: &str) -> &'static [&'static str] {
+			&["transfer", "transfer_all"]
+		}
+		fn get_call_metadata(&self) -> CallMetadata {
+			CallMetadata { pallet_name: "Balances", function_name: self.0 }
+		}
+	}
+
+	struct TransferAll;
+	impl ContainsPair<Vec<u8>, Vec<u8>> for TransferAll {
+		fn contains(pallet: &Vec<u8>, function: &Vec<u8>) -> bool {
+			pallet == b"Balances" && function == b"transfer_all"
+		}
+	}
+
+	#[test]
+	fn call_name_filter_works() {
+		assert!(CallNameFilter::<TransferAll>::contains(&Call("transfer_all")));
+		assert!(!CallNameFilter::<TransferAll>::contains(&Call("transfer")));
+		assert!(CallNameFilter::<(TransferAll, Everything)>::contains(&Call("transfer")));
+		assert!(!CallNameFilter::<()>::contains(&Call("transfer_all")));
+	}
 
 	#[test]
 	fn check_storage_version_ordering() {
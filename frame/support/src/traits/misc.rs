@@ -19,8 +19,11 @@
 
 use crate::{dispatch::Parameter, TypeInfo};
 use codec::{Decode, Encode, EncodeLike, Input, MaxEncodedLen};
-use sp_runtime::{traits::Block as BlockT, DispatchError};
-use sp_std::vec::Vec;
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, Block as BlockT, Convert, UniqueSaturatedInto},
+	DispatchError,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
 
 /// Anything that can have a `::len()` method.
 pub trait Len {
@@ -193,6 +196,64 @@ pub trait UnixTime {
 	fn now() -> core::time::Duration;
 }
 
+/// Converts a duration in milliseconds into the number of blocks expected to be produced during
+/// that time.
+///
+/// `ExpectedBlockTime` is the expected time between two blocks in milliseconds, usually the slot
+/// duration of the consensus engine. The number of blocks is rounded up, a non-zero duration never
+/// converts to zero blocks.
+pub struct MillisToBlocks<ExpectedBlockTime>(PhantomData<ExpectedBlockTime>);
+
+impl<ExpectedBlockTime: Get<u64>, BlockNumber: AtLeast32BitUnsigned> Convert<u64, BlockNumber>
+	for MillisToBlocks<ExpectedBlockTime>
+{
+	fn convert(millis: u64) -> BlockNumber {
+		let block_time = ExpectedBlockTime::get().max(1);
+		let blocks = millis / block_time + (millis % block_time != 0) as u64;
+		blocks.unique_saturated_into()
+	}
+}
+
+/// Converts a number of blocks into the duration in milliseconds they are expected to take.
+///
+/// `ExpectedBlockTime` is the expected time between two blocks in milliseconds, see
+/// [`MillisToBlocks`].
+pub struct BlocksToMillis<ExpectedBlockTime>(PhantomData<ExpectedBlockTime>);
+
+impl<ExpectedBlockTime: Get<u64>, BlockNumber: AtLeast32BitUnsigned> Convert<BlockNumber, u64>
+	for BlocksToMillis<ExpectedBlockTime>
+{
+	fn convert(blocks: BlockNumber) -> u64 {
+		let blocks: u64 = blocks.unique_saturated_into();
+		blocks.saturating_mul(ExpectedBlockTime::get())
+	}
+}
+
+/// A duration of `Millis` milliseconds, expressed as a number of blocks.
+///
+/// This allows to configure periods in time rather than in blocks, such that they don't need to be
+/// updated when the block time changes:
+///
+/// ```
+/// # use frame_support::{parameter_types, traits::{Get, MillisInBlocks}};
+/// parameter_types! {
+/// 	pub const ExpectedBlockTime: u64 = 6_000;
+/// 	pub const VotingDuration: u64 = 7 * 24 * 60 * 60 * 1_000;
+/// }
+///
+/// type VotingPeriod = MillisInBlocks<VotingDuration, ExpectedBlockTime>;
+/// assert_eq!(<VotingPeriod as Get<u32>>::get(), 100_800);
+/// ```
+pub struct MillisInBlocks<Millis, ExpectedBlockTime>(PhantomData<(Millis, ExpectedBlockTime)>);
+
+impl<Millis: Get<u64>, ExpectedBlockTime: Get<u64>, BlockNumber: AtLeast32BitUnsigned>
+	Get<BlockNumber> for MillisInBlocks<Millis, ExpectedBlockTime>
+{
+	fn get() -> BlockNumber {
+		MillisToBlocks::<ExpectedBlockTime>::convert(Millis::get())
+	}
+}
+
 /// Trait to be used when types are exactly same.
 ///
 /// This allow to convert back and forth from type, a reference and a mutable reference.
@@ -424,3 +485,27 @@ impl<T> From<T> for WrapperOpaque<T> {
 		Self(t)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	crate::parameter_types! {
+		const BlockTime: u64 = 6_000;
+	}
+
+	#[test]
+	fn millis_to_blocks_rounds_up() {
+		assert_eq!(<MillisToBlocks<BlockTime> as Convert<u64, u32>>::convert(0), 0);
+		assert_eq!(<MillisToBlocks<BlockTime> as Convert<u64, u32>>::convert(1), 1);
+		assert_eq!(<MillisToBlocks<BlockTime> as Convert<u64, u32>>::convert(12_000), 2);
+		assert_eq!(<MillisToBlocks<BlockTime> as Convert<u64, u32>>::convert(12_001), 3);
+		assert_eq!(<MillisToBlocks<BlockTime> as Convert<u64, u32>>::convert(u64::MAX), u32::MAX);
+	}
+
+	#[test]
+	fn blocks_to_millis_works() {
+		assert_eq!(<BlocksToMillis<BlockTime> as Convert<u32, u64>>::convert(10), 60_000);
+		assert_eq!(<BlocksToMillis<BlockTime> as Convert<u64, u64>>::convert(u64::MAX), u64::MAX);
+	}
+}
@@ -141,6 +141,17 @@ pub trait Mutate<AccountId>: Inspect<AccountId> {
 	}
 }
 
+/// Trait for providing the ability to create new fungible assets.
+pub trait Create<AccountId>: Inspect<AccountId> {
+	/// Create a new fungible asset.
+	fn create(
+		id: Self::AssetId,
+		admin: AccountId,
+		is_sufficient: bool,
+		min_balance: Self::Balance,
+	) -> DispatchResult;
+}
+
 /// Trait for providing a set of named fungible assets which can only be transferred.
 pub trait Transfer<AccountId>: Inspect<AccountId> {
 	/// Transfer funds from one account into another.
@@ -1507,6 +1507,45 @@ fn metadata() {
 	pretty_assertions::assert_eq!(actual_metadata.pallets[1], expected_metadata.pallets[1]);
 }
 
+#[test]
+fn metadata_types_are_registered() {
+	use frame_support::metadata::*;
+
+	let metadata = match Runtime::metadata().1 {
+		RuntimeMetadata::V14(metadata) => metadata,
+		_ => panic!("metadata has been bumped, test needs to be updated"),
+	};
+	let assert_registered = |id: u32| {
+		assert!(metadata.types.resolve(id).is_some(), "type {} is not in the registry", id)
+	};
+
+	assert_registered(metadata.ty.id());
+	assert_registered(metadata.extrinsic.ty.id());
+	for pallet in &metadata.pallets {
+		for entry in pallet.storage.iter().flat_map(|storage| &storage.entries) {
+			match &entry.ty {
+				StorageEntryType::Plain(value) => assert_registered(value.id()),
+				StorageEntryType::Map { key, value, .. } => {
+					assert_registered(key.id());
+					assert_registered(value.id());
+				},
+			}
+		}
+		pallet.calls.iter().for_each(|calls| assert_registered(calls.ty.id()));
+		pallet.event.iter().for_each(|event| assert_registered(event.ty.id()));
+		pallet.error.iter().for_each(|error| assert_registered(error.ty.id()));
+		pallet.constants.iter().for_each(|constant| assert_registered(constant.ty.id()));
+	}
+
+	// documentation of the calls is captured in their type.
+	let calls = metadata.pallets[1].calls.as_ref().expect("Example pallet has calls");
+	match metadata.types.resolve(calls.ty.id()).map(|ty| ty.type_def()) {
+		Some(scale_info::TypeDef::Variant(calls)) =>
+			assert!(calls.variants().iter().any(|call| !call.docs().is_empty())),
+		_ => panic!("calls are described by an enum"),
+	}
+}
+
 #[test]
 fn test_pallet_info_access() {
 	assert_eq!(<System as frame_support::traits::PalletInfoAccess>::name(), "System");
@@ -86,7 +86,7 @@ use frame_support::{
 	storage,
 	traits::{
 		Contains, EnsureOrigin, Get, HandleLifetime, OnKilledAccount, OnNewAccount, OriginTrait,
-		PalletInfo, SortedMembers, StoredMap,
+		PalletInfo, RankedMembers, SortedMembers, StoredMap,
 	},
 	weights::{
 		extract_actual_weight, DispatchClass, DispatchInfo, PerDispatchClass, RuntimeDbWeight,
@@ -938,6 +938,33 @@ impl<
 	}
 }
 
+/// Ensures the origin is signed by a member of `Members` whose rank is at least `MinRank`.
+pub struct EnsureRankedAtLeast<Members, MinRank, AccountId>(
+	sp_std::marker::PhantomData<(Members, MinRank, AccountId)>,
+);
+impl<
+		O: Into<Result<RawOrigin<AccountId>, O>> + From<RawOrigin<AccountId>>,
+		Members: RankedMembers<AccountId>,
+		MinRank: Get<Members::Rank>,
+		AccountId: Default,
+	> EnsureOrigin<O> for EnsureRankedAtLeast<Members, MinRank, AccountId>
+{
+	type Success = AccountId;
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().and_then(|o| match o {
+			RawOrigin::Signed(who) if Members::rank_of(&who) >= Some(MinRank::get()) => Ok(who),
+			r => Err(O::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		let who = AccountId::default();
+		Members::set_rank(&who, MinRank::get());
+		O::from(RawOrigin::Signed(who))
+	}
+}
+
 pub struct EnsureNone<AccountId>(sp_std::marker::PhantomData<AccountId>);
 impl<O: Into<Result<RawOrigin<AccountId>, O>> + From<RawOrigin<AccountId>>, AccountId>
 	EnsureOrigin<O> for EnsureNone<AccountId>
@@ -1292,6 +1319,21 @@ impl<T: Config> Pallet<T> {
 		Self::deposit_event_indexed(&[], event.into());
 	}
 
+	/// Deposits an event into this block's event record, indexed under the topics of the given
+	/// accounts (see [`Self::account_topic`]).
+	pub fn deposit_event_for_accounts(accounts: &[&T::AccountId], event: impl Into<T::Event>) {
+		let topics = accounts.iter().map(|who| Self::account_topic(who)).collect::<Vec<_>>();
+		Self::deposit_event_indexed(&topics, event.into());
+	}
+
+	/// The topic indexing the events concerning the account `who`.
+	///
+	/// This is the hash of the `(b"account", who)` tuple, so that clients can compute the topic of
+	/// an account on their own to look up its events.
+	pub fn account_topic(who: &T::AccountId) -> T::Hash {
+		T::Hashing::hash_of(&(b"account", who))
+	}
+
 	/// Deposits an event into this block's event record adding this event
 	/// to the corresponding topic indexes.
 	///
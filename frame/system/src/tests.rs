@@ -475,6 +475,93 @@ fn ensure_one_of_works() {
 	assert!(ensure_root_or_signed(RawOrigin::None).is_err());
 }
 
+#[test]
+fn origin_combinators_work() {
+	use frame_support::{
+		parameter_types,
+		traits::{EitherOf, EitherOfDiverse, EnsureWithSuccess, MapSuccess},
+	};
+	use sp_runtime::traits::Morph;
+
+	parameter_types! {
+		pub const Seven: u64 = 7;
+	}
+	pub struct Double;
+	impl Morph<u64> for Double {
+		type Outcome = u64;
+		fn morph(a: u64) -> u64 {
+			a * 2
+		}
+	}
+	type RootAsSeven = EnsureWithSuccess<EnsureRoot<u64>, u64, Seven>;
+
+	let diverse = |o: RawOrigin<u64>| {
+		EitherOfDiverse::<EnsureRoot<u64>, EnsureSigned<u64>>::try_origin(Origin::from(o))
+	};
+	assert_eq!(diverse(RawOrigin::Root).unwrap(), Either::Left(()));
+	assert_eq!(diverse(RawOrigin::Signed(3)).unwrap(), Either::Right(3));
+	assert!(diverse(RawOrigin::None).is_err());
+
+	let either =
+		|o: RawOrigin<u64>| EitherOf::<RootAsSeven, EnsureSigned<u64>>::try_origin(Origin::from(o));
+	assert_eq!(either(RawOrigin::Root).unwrap(), 7);
+	assert_eq!(either(RawOrigin::Signed(3)).unwrap(), 3);
+	assert!(either(RawOrigin::None).is_err());
+
+	let mapped =
+		|o: RawOrigin<u64>| MapSuccess::<EnsureSigned<u64>, Double>::try_origin(Origin::from(o));
+	assert_eq!(mapped(RawOrigin::Signed(3)).unwrap(), 6);
+	assert!(mapped(RawOrigin::Root).is_err());
+}
+
+#[test]
+fn ensure_ranked_at_least_works() {
+	use frame_support::{
+		parameter_types,
+		traits::{AsEnsureOriginWithArg, EnsureOriginWithArg, RankedMembers},
+	};
+
+	parameter_types! {
+		pub const Two: u16 = 2;
+	}
+	thread_local! {
+		// Account `n` has rank `n`, up to rank 3.
+		static RANKS: std::cell::RefCell<std::collections::BTreeMap<u64, u16>> =
+			std::cell::RefCell::new((0..=3).map(|n| (n, n as u16)).collect());
+	}
+	pub struct Ranks;
+	impl RankedMembers<u64> for Ranks {
+		type Rank = u16;
+		fn rank_of(who: &u64) -> Option<u16> {
+			RANKS.with(|ranks| ranks.borrow().get(who).cloned())
+		}
+		#[cfg(feature = "runtime-benchmarks")]
+		fn set_rank(who: &u64, rank: u16) {
+			RANKS.with(|ranks| ranks.borrow_mut().insert(*who, rank));
+		}
+	}
+	type Ranked = EnsureRankedAtLeast<Ranks, Two, u64>;
+
+	let ranked = |o: RawOrigin<u64>| Ranked::try_origin(Origin::from(o));
+	assert_eq!(ranked(RawOrigin::Signed(2)).unwrap(), 2);
+	assert_eq!(ranked(RawOrigin::Signed(3)).unwrap(), 3);
+	assert!(ranked(RawOrigin::Signed(1)).is_err());
+	assert!(ranked(RawOrigin::Signed(4)).is_err());
+	assert!(ranked(RawOrigin::Root).is_err());
+	#[cfg(feature = "runtime-benchmarks")]
+	assert!(Ranked::try_origin(<Ranked as EnsureOrigin<Origin>>::successful_origin()).is_ok());
+
+	// The argument is ignored.
+	let with_arg = |o: RawOrigin<u64>, item: u32| {
+		<AsEnsureOriginWithArg<Ranked> as EnsureOriginWithArg<Origin, u32>>::try_origin(
+			Origin::from(o),
+			&item,
+		)
+	};
+	assert_eq!(with_arg(RawOrigin::Signed(2), 0).unwrap(), 2);
+	assert!(with_arg(RawOrigin::Signed(1), 1).is_err());
+}
+
 #[test]
 fn extrinsics_root_is_calculated_correctly() {
 	new_test_ext().execute_with(|| {
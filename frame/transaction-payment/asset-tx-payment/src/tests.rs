@@ -0,0 +1,444 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate as pallet_asset_tx_payment;
+
+use frame_support::{
+	assert_ok, parameter_types,
+	traits::{fungibles::Mutate, AsEnsureOriginWithArg, Get},
+	weights::{
+		DispatchClass, DispatchInfo, Pays, PostDispatchInfo, Weight, WeightToFeeCoefficient,
+		WeightToFeeCoefficients, WeightToFeePolynomial,
+	},
+};
+use frame_system as system;
+use pallet_balances::Call as BalancesCall;
+use pallet_transaction_payment::CurrencyAdapter;
+use smallvec::smallvec;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, ConvertInto, IdentityLookup},
+	Perbill,
+};
+use std::cell::RefCell;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+type Balance = u64;
+type AccountId = u64;
+
+frame_support::construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		TransactionPayment: pallet_transaction_payment::{Pallet, Storage},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
+		AssetTxPayment: pallet_asset_tx_payment::{Pallet, Event<T>},
+	}
+);
+
+const CALL: &<Runtime as frame_system::Config>::Call =
+	&Call::Balances(BalancesCall::transfer { dest: 2, value: 69 });
+
+thread_local! {
+	static EXTRINSIC_BASE_WEIGHT: RefCell<u64> = RefCell::new(0);
+	static HANDLED_CREDIT: RefCell<u64> = RefCell::new(0);
+}
+
+pub struct BlockWeights;
+impl Get<frame_system::limits::BlockWeights> for BlockWeights {
+	fn get() -> frame_system::limits::BlockWeights {
+		frame_system::limits::BlockWeights::builder()
+			.base_block(0)
+			.for_class(DispatchClass::all(), |weights| {
+				weights.base_extrinsic = EXTRINSIC_BASE_WEIGHT.with(|v| *v.borrow()).into();
+			})
+			.for_class(DispatchClass::non_mandatory(), |weights| {
+				weights.max_total = 1024.into();
+			})
+			.build_or_panic()
+	}
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub static TransactionByteFee: u64 = 1;
+	pub static WeightToFee: u64 = 1;
+}
+
+impl frame_system::Config for Runtime {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = BlockWeights;
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 10;
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+
+impl WeightToFeePolynomial for WeightToFee {
+	type Balance = u64;
+
+	fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+		smallvec![WeightToFeeCoefficient {
+			degree: 1,
+			coeff_frac: Perbill::zero(),
+			coeff_integer: WEIGHT_TO_FEE.with(|v| *v.borrow()),
+			negative: false,
+		}]
+	}
+}
+
+impl pallet_transaction_payment::Config for Runtime {
+	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
+	type TransactionByteFee = TransactionByteFee;
+	type WeightToFee = WeightToFee;
+	type FeeMultiplierUpdate = ();
+}
+
+parameter_types! {
+	pub const AssetDeposit: u64 = 2;
+	pub const MetadataDeposit: u64 = 0;
+	pub const StringLimit: u32 = 20;
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<AccountId>>;
+	type ForceOrigin = AsEnsureOriginWithArg<frame_system::EnsureRoot<AccountId>>;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDeposit;
+	type MetadataDepositPerByte = MetadataDeposit;
+	type ApprovalDeposit = MetadataDeposit;
+	type StringLimit = StringLimit;
+	type Freezer = ();
+	type WeightInfo = ();
+	type Extra = ();
+}
+
+/// Keeps track of the asset fees handed over to the credit handler.
+pub struct CreditRecorder;
+impl HandleCredit<AccountId, Assets> for CreditRecorder {
+	fn handle_credit(credit: CreditOf<AccountId, Assets>) {
+		HANDLED_CREDIT.with(|c| *c.borrow_mut() += credit.peek());
+	}
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type Fungibles = Assets;
+	type OnChargeAssetTransaction = FungiblesAdapter<
+		pallet_assets::BalanceToAssetBalance<Balances, Runtime, ConvertInto>,
+		CreditRecorder,
+	>;
+}
+
+pub struct ExtBuilder {
+	balance_factor: u64,
+	base_weight: u64,
+	byte_fee: u64,
+	weight_to_fee: u64,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self { balance_factor: 1, base_weight: 0, byte_fee: 1, weight_to_fee: 1 }
+	}
+}
+
+impl ExtBuilder {
+	pub fn base_weight(mut self, base_weight: u64) -> Self {
+		self.base_weight = base_weight;
+		self
+	}
+	pub fn balance_factor(mut self, factor: u64) -> Self {
+		self.balance_factor = factor;
+		self
+	}
+	fn set_constants(&self) {
+		EXTRINSIC_BASE_WEIGHT.with(|v| *v.borrow_mut() = self.base_weight);
+		TRANSACTION_BYTE_FEE.with(|v| *v.borrow_mut() = self.byte_fee);
+		WEIGHT_TO_FEE.with(|v| *v.borrow_mut() = self.weight_to_fee);
+		HANDLED_CREDIT.with(|c| *c.borrow_mut() = 0);
+	}
+	pub fn build(self) -> sp_io::TestExternalities {
+		self.set_constants();
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: if self.balance_factor > 0 {
+				vec![
+					(1, 10 * self.balance_factor),
+					(2, 20 * self.balance_factor),
+					(3, 30 * self.balance_factor),
+					(4, 40 * self.balance_factor),
+					(5, 50 * self.balance_factor),
+					(6, 60 * self.balance_factor),
+				]
+			} else {
+				vec![]
+			},
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+		let mut ext: sp_io::TestExternalities = t.into();
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
+
+/// create a transaction info struct from weight. Handy to avoid building the whole struct.
+pub fn info_from_weight(w: Weight) -> DispatchInfo {
+	// pays_fee: Pays::Yes -- class: DispatchClass::Normal
+	DispatchInfo { weight: w, ..Default::default() }
+}
+
+fn post_info_from_weight(w: Weight) -> PostDispatchInfo {
+	PostDispatchInfo { actual_weight: Some(w), pays_fee: Default::default() }
+}
+
+fn default_post_info() -> PostDispatchInfo {
+	PostDispatchInfo { actual_weight: None, pays_fee: Default::default() }
+}
+
+/// Create the asset `asset_id`, valued twice as much as the native currency, and mint `balance`
+/// to `who`.
+fn create_asset(asset_id: u32, is_sufficient: bool, who: AccountId, balance: Balance) {
+	let min_balance = 2 * ExistentialDeposit::get();
+	assert_ok!(Assets::force_create(Origin::root(), asset_id, 42, is_sufficient, min_balance));
+	assert_ok!(Assets::mint_into(asset_id, &who, balance));
+}
+
+fn fee_paid_event(
+	who: AccountId,
+	actual_fee: Balance,
+	tip: Balance,
+	asset_id: Option<u32>,
+) -> Event {
+	Event::AssetTxPayment(pallet_asset_tx_payment::Event::AssetTxFeePaid(
+		who, actual_fee, tip, asset_id,
+	))
+}
+
+#[test]
+fn transaction_payment_in_native_possible() {
+	let balance_factor = 100;
+	ExtBuilder::default()
+		.balance_factor(balance_factor)
+		.base_weight(5)
+		.build()
+		.execute_with(|| {
+			let len = 10;
+			let pre = ChargeAssetTxPayment::<Runtime>::from(0, None)
+				.pre_dispatch(&1, CALL, &info_from_weight(5), len)
+				.unwrap();
+			let initial_balance = 10 * balance_factor;
+			assert_eq!(Balances::free_balance(1), initial_balance - 5 - 5 - 10);
+
+			assert_ok!(ChargeAssetTxPayment::<Runtime>::post_dispatch(
+				pre,
+				&info_from_weight(5),
+				&default_post_info(),
+				len,
+				&Ok(())
+			));
+			assert_eq!(Balances::free_balance(1), initial_balance - 5 - 5 - 10);
+			System::assert_last_event(fee_paid_event(1, 20, 0, None));
+
+			let pre = ChargeAssetTxPayment::<Runtime>::from(5 /* tipped */, None)
+				.pre_dispatch(&2, CALL, &info_from_weight(100), len)
+				.unwrap();
+			let initial_balance_for_2 = 20 * balance_factor;
+			assert_eq!(Balances::free_balance(2), initial_balance_for_2 - 5 - 10 - 100 - 5);
+
+			assert_ok!(ChargeAssetTxPayment::<Runtime>::post_dispatch(
+				pre,
+				&info_from_weight(100),
+				&post_info_from_weight(50),
+				len,
+				&Ok(())
+			));
+			assert_eq!(Balances::free_balance(2), initial_balance_for_2 - 5 - 10 - 50 - 5);
+			System::assert_last_event(fee_paid_event(2, 70, 5, None));
+		});
+}
+
+#[test]
+fn transaction_payment_in_asset_possible() {
+	ExtBuilder::default()
+		.balance_factor(100)
+		.base_weight(5)
+		.build()
+		.execute_with(|| {
+			// an asset worth twice as much as the native currency, held by an account without any
+			// native balance.
+			let asset_id = 1;
+			let caller = 7;
+			create_asset(asset_id, true, caller, 1000);
+			assert_eq!(Balances::free_balance(caller), 0);
+
+			let weight = 5;
+			let len = 10;
+			// fee = base_weight + weight + len = 20, converted to 40 units of the asset.
+			let fee = (5 + weight + len as u64) * 2;
+			let pre = ChargeAssetTxPayment::<Runtime>::from(0, Some(asset_id))
+				.pre_dispatch(&caller, CALL, &info_from_weight(weight), len)
+				.unwrap();
+			assert_eq!(Assets::balance(asset_id, caller), 1000 - fee);
+
+			assert_ok!(ChargeAssetTxPayment::<Runtime>::post_dispatch(
+				pre,
+				&info_from_weight(weight),
+				&default_post_info(),
+				len,
+				&Ok(())
+			));
+			assert_eq!(Assets::balance(asset_id, caller), 1000 - fee);
+			assert_eq!(HANDLED_CREDIT.with(|c| *c.borrow()), fee);
+			// the native balances are untouched.
+			assert_eq!(Balances::free_balance(caller), 0);
+			System::assert_last_event(fee_paid_event(caller, 20, 0, Some(asset_id)));
+		});
+}
+
+#[test]
+fn transaction_payment_in_asset_refunds_overpaid_fee() {
+	ExtBuilder::default()
+		.balance_factor(100)
+		.base_weight(5)
+		.build()
+		.execute_with(|| {
+			let asset_id = 1;
+			let caller = 2;
+			create_asset(asset_id, true, caller, 1000);
+
+			let len = 10;
+			// fee = base_weight + weight + len + tip = 120, converted to 240 units of the asset.
+			let pre = ChargeAssetTxPayment::<Runtime>::from(5 /* tipped */, Some(asset_id))
+				.pre_dispatch(&caller, CALL, &info_from_weight(100), len)
+				.unwrap();
+			assert_eq!(Assets::balance(asset_id, caller), 1000 - 240);
+
+			// only half of the weight was used, the actual fee is 70, converted to 140.
+			assert_ok!(ChargeAssetTxPayment::<Runtime>::post_dispatch(
+				pre,
+				&info_from_weight(100),
+				&post_info_from_weight(50),
+				len,
+				&Ok(())
+			));
+			assert_eq!(Assets::balance(asset_id, caller), 1000 - 140);
+			assert_eq!(HANDLED_CREDIT.with(|c| *c.borrow()), 140);
+			System::assert_last_event(fee_paid_event(caller, 70, 5, Some(asset_id)));
+		});
+}
+
+#[test]
+fn asset_transaction_payment_with_zero_fee_withdraws_nothing() {
+	ExtBuilder::default().balance_factor(100).build().execute_with(|| {
+		let asset_id = 1;
+		let caller = 2;
+		create_asset(asset_id, true, caller, 1000);
+		System::reset_events();
+
+		let info = DispatchInfo { pays_fee: Pays::No, ..info_from_weight(100) };
+		let pre = ChargeAssetTxPayment::<Runtime>::from(0, Some(asset_id))
+			.pre_dispatch(&caller, CALL, &info, 10)
+			.unwrap();
+		assert_eq!(Assets::balance(asset_id, caller), 1000);
+
+		assert_ok!(ChargeAssetTxPayment::<Runtime>::post_dispatch(
+			pre,
+			&info,
+			&default_post_info(),
+			10,
+			&Ok(())
+		));
+		assert_eq!(Assets::balance(asset_id, caller), 1000);
+		assert_eq!(HANDLED_CREDIT.with(|c| *c.borrow()), 0);
+		assert!(System::events().is_empty());
+	});
+}
+
+#[test]
+fn asset_payment_fails_when_unaffordable_or_insufficient() {
+	ExtBuilder::default()
+		.balance_factor(100)
+		.base_weight(5)
+		.build()
+		.execute_with(|| {
+			let len = 10;
+			// the converted fee of 40 exceeds the asset balance.
+			create_asset(1, true, 2, 30);
+			assert!(ChargeAssetTxPayment::<Runtime>::from(0, Some(1))
+				.pre_dispatch(&2, CALL, &info_from_weight(5), len)
+				.is_err());
+			assert_eq!(Assets::balance(1, 2), 30);
+
+			// assets that are not sufficient have no reliable value to convert the fee with.
+			create_asset(2, false, 2, 1000);
+			assert!(ChargeAssetTxPayment::<Runtime>::from(0, Some(2))
+				.pre_dispatch(&2, CALL, &info_from_weight(5), len)
+				.is_err());
+			assert_eq!(Assets::balance(2, 2), 1000);
+
+			// unknown assets can't be used either.
+			assert!(ChargeAssetTxPayment::<Runtime>::from(0, Some(3))
+				.pre_dispatch(&2, CALL, &info_from_weight(5), len)
+				.is_err());
+		});
+}
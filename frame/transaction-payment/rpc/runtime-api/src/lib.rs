@@ -20,15 +20,24 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Codec;
-use sp_runtime::traits::MaybeDisplay;
+use sp_runtime::{traits::MaybeDisplay, transaction_validity::TransactionPriority};
 
 pub use pallet_transaction_payment::{FeeDetails, InclusionFee, RuntimeDispatchInfo};
 
 sp_api::decl_runtime_apis! {
+	#[api_version(2)]
 	pub trait TransactionPaymentApi<Balance> where
 		Balance: Codec + MaybeDisplay,
 	{
 		fn query_info(uxt: Block::Extrinsic, len: u32) -> RuntimeDispatchInfo<Balance>;
 		fn query_fee_details(uxt: Block::Extrinsic, len: u32) -> FeeDetails<Balance>;
+		/// Query the minimum tip needed for `uxt` to be assigned at least `priority`.
+		fn query_tip_for_priority(
+			uxt: Block::Extrinsic,
+			len: u32,
+			priority: TransactionPriority,
+		) -> Balance;
+		/// The maximum length of the normal transactions in a block.
+		fn query_max_block_length() -> u32;
 	}
 }
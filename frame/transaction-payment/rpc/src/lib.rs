@@ -23,7 +23,8 @@ use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
 pub use pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi as TransactionPaymentRuntimeApi;
 use pallet_transaction_payment_rpc_runtime_api::{FeeDetails, InclusionFee, RuntimeDispatchInfo};
-use sp_api::ProvideRuntimeApi;
+use sc_transaction_pool_api::TransactionPool;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_core::Bytes;
 use sp_rpc::number::NumberOrHex;
@@ -43,18 +44,24 @@ pub trait TransactionPaymentApi<BlockHash, ResponseType> {
 		encoded_xt: Bytes,
 		at: Option<BlockHash>,
 	) -> Result<FeeDetails<NumberOrHex>>;
+	/// Estimate the tip the given transaction needs to be included in the next block, given the
+	/// ready transactions currently in the pool.
+	#[rpc(name = "payment_estimateTip")]
+	fn estimate_tip(&self, encoded_xt: Bytes, at: Option<BlockHash>) -> Result<NumberOrHex>;
 }
 
 /// A struct that implements the [`TransactionPaymentApi`].
-pub struct TransactionPayment<C, P> {
+pub struct TransactionPayment<C, Pool, P> {
 	client: Arc<C>,
+	pool: Arc<Pool>,
 	_marker: std::marker::PhantomData<P>,
 }
 
-impl<C, P> TransactionPayment<C, P> {
-	/// Create new `TransactionPayment` with the given reference to the client.
-	pub fn new(client: Arc<C>) -> Self {
-		Self { client, _marker: Default::default() }
+impl<C, Pool, P> TransactionPayment<C, Pool, P> {
+	/// Create new `TransactionPayment` with the given reference to the client and the transaction
+	/// pool.
+	pub fn new(client: Arc<C>, pool: Arc<Pool>) -> Self {
+		Self { client, pool, _marker: Default::default() }
 	}
 }
 
@@ -75,12 +82,14 @@ impl From<Error> for i64 {
 	}
 }
 
-impl<C, Block, Balance> TransactionPaymentApi<<Block as BlockT>::Hash, RuntimeDispatchInfo<Balance>>
-	for TransactionPayment<C, Block>
+impl<C, Pool, Block, Balance>
+	TransactionPaymentApi<<Block as BlockT>::Hash, RuntimeDispatchInfo<Balance>>
+	for TransactionPayment<C, Pool, Block>
 where
 	Block: BlockT,
 	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
 	C::Api: TransactionPaymentRuntimeApi<Block, Balance>,
+	Pool: 'static + TransactionPool<Block = Block>,
 	Balance: Codec + MaybeDisplay + Copy + TryInto<NumberOrHex>,
 {
 	fn query_info(
@@ -151,4 +160,49 @@ where
 			tip: Default::default(),
 		})
 	}
+
+	fn estimate_tip(
+		&self,
+		encoded_xt: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		let runtime_error = |e: String| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to estimate tip.".into(),
+			data: Some(e.into()),
+		};
+		let supported = api
+			.has_api_with::<dyn TransactionPaymentRuntimeApi<Block, Balance>, _>(&at, |v| v >= 2)
+			.map_err(|e| runtime_error(format!("{:?}", e)))?;
+		if !supported {
+			return Err(runtime_error("Tip estimation is not supported by the runtime.".into()))
+		}
+
+		let encoded_len = encoded_xt.len() as u32;
+
+		let uxt: Block::Extrinsic = Decode::decode(&mut &*encoded_xt).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::DecodeError.into()),
+			message: "Unable to estimate tip.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+
+		let max_block_length =
+			api.query_max_block_length(&at).map_err(|e| runtime_error(format!("{:?}", e)))?;
+		let priority =
+			self.pool.inclusion_priority(encoded_len as usize, max_block_length as usize);
+		let tip = api
+			.query_tip_for_priority(&at, uxt, encoded_len, priority)
+			.map_err(|e| runtime_error(format!("{:?}", e)))?;
+
+		tip.try_into().map_err(|_| RpcError {
+			code: ErrorCode::InvalidParams,
+			message: format!("{} doesn't fit in NumberOrHex representation", tip),
+			data: None,
+		})
+	}
 }
@@ -42,7 +42,9 @@
 //!   - The mapping between one unit of weight to one unit of fee via [`Config::WeightToFee`].
 //!   - A means of updating the fee for the next block, via defining a multiplier, based on the
 //!     final state of the chain at the end of the previous block. This can be configured via
-//!     [`Config::FeeMultiplierUpdate`]
+//!     [`Config::FeeMultiplierUpdate`], either with one of the provided [`MultiplierUpdate`]
+//!     implementations ([`TargetedFeeAdjustment`], [`ImmediateFeeAdjustment`],
+//!     [`ConstFeeMultiplier`]) or a custom one.
 //!   - How the fees are paid via [`Config::OnChargeTransaction`].
 
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -225,6 +227,73 @@ where
 	}
 }
 
+/// A multiplier which never changes: it is always `M`, whatever the fullness of the blocks.
+pub struct ConstFeeMultiplier<M: Get<Multiplier>>(sp_std::marker::PhantomData<M>);
+
+impl<M: Get<Multiplier>> MultiplierUpdate for ConstFeeMultiplier<M> {
+	fn min() -> Multiplier {
+		M::get()
+	}
+	fn target() -> Perquintill {
+		Default::default()
+	}
+	fn variability() -> Multiplier {
+		Default::default()
+	}
+}
+
+impl<M: Get<Multiplier>> Convert<Multiplier, Multiplier> for ConstFeeMultiplier<M> {
+	fn convert(_previous: Multiplier) -> Multiplier {
+		M::get()
+	}
+}
+
+/// A multiplier following the fullness of the previous block without any inertia.
+///
+/// The next multiplier is the ratio between the weight of the normal transactions of the previous
+/// block and the targeted weight `s' * m` (see [`TargetedFeeAdjustment`]), bounded below by `M`.
+/// A block filled at the target results in a multiplier of one, whatever the previous multiplier.
+pub struct ImmediateFeeAdjustment<T, S, M>(sp_std::marker::PhantomData<(T, S, M)>);
+
+impl<T, S, M> MultiplierUpdate for ImmediateFeeAdjustment<T, S, M>
+where
+	T: frame_system::Config,
+	S: Get<Perquintill>,
+	M: Get<Multiplier>,
+{
+	fn min() -> Multiplier {
+		M::get()
+	}
+	fn target() -> Perquintill {
+		S::get()
+	}
+	fn variability() -> Multiplier {
+		Default::default()
+	}
+}
+
+impl<T, S, M> Convert<Multiplier, Multiplier> for ImmediateFeeAdjustment<T, S, M>
+where
+	T: frame_system::Config,
+	S: Get<Perquintill>,
+	M: Get<Multiplier>,
+{
+	fn convert(_previous: Multiplier) -> Multiplier {
+		let weights = T::BlockWeights::get();
+		let normal_max_weight = weights
+			.get(DispatchClass::Normal)
+			.max_total
+			.unwrap_or_else(|| weights.max_block);
+		let current_block_weight = <frame_system::Pallet<T>>::block_weight();
+		let normal_block_weight =
+			*current_block_weight.get(DispatchClass::Normal).min(&normal_max_weight);
+
+		let target_weight = S::get() * normal_max_weight;
+		Multiplier::saturating_from_rational(normal_block_weight, target_weight.max(1))
+			.max(M::get())
+	}
+}
+
 /// Storage releases of the pallet.
 #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 enum Releases {
@@ -409,6 +478,32 @@ where
 		Self::compute_fee_details(len, &dispatch_info, 0u32.into())
 	}
 
+	/// Query the minimum tip a given `call` needs to be assigned at least `priority` by
+	/// [`ChargeTransactionPayment`].
+	///
+	/// Combined with the priority a transaction needs to get into the next block, as reported by
+	/// the transaction pool, this allows wallets to suggest a tip based on the current congestion.
+	pub fn query_tip_for_priority<Extrinsic: GetDispatchInfo>(
+		unchecked_extrinsic: Extrinsic,
+		len: u32,
+		priority: TransactionPriority,
+	) -> BalanceOf<T>
+	where
+		T::Call: Dispatchable<Info = DispatchInfo>,
+	{
+		let dispatch_info = <Extrinsic as GetDispatchInfo>::get_dispatch_info(&unchecked_extrinsic);
+		let coefficient = Self::priority_coefficient(len as usize, &dispatch_info).max(1);
+		// the smallest final fee that is assigned at least `priority`.
+		let required_fee = priority / coefficient + if priority % coefficient == 0 { 0 } else { 1 };
+		let fee = Self::compute_fee(len, &dispatch_info, Zero::zero());
+		required_fee.saturated_into::<BalanceOf<T>>().saturating_sub(fee)
+	}
+
+	/// The maximum length of the normal transactions in a block.
+	pub fn max_block_length() -> u32 {
+		*T::BlockLength::get().max.get(DispatchClass::Normal)
+	}
+
 	/// Compute the final fee value for a particular transaction.
 	pub fn compute_fee(len: u32, info: &DispatchInfoOf<T::Call>, tip: BalanceOf<T>) -> BalanceOf<T>
 	where
@@ -464,6 +559,18 @@ where
 		)
 	}
 
+	/// The factor between the final fee of a transaction and its priority.
+	///
+	/// See `ChargeTransactionPayment::get_priority` for the details.
+	fn priority_coefficient(len: usize, info: &DispatchInfoOf<T::Call>) -> u64
+	where
+		T::Call: Dispatchable<Info = DispatchInfo>,
+	{
+		let weight_saturation = T::BlockWeights::get().max_block / info.weight.max(1);
+		let len_saturation = Self::max_block_length() as u64 / (len as u64).max(1);
+		weight_saturation.min(len_saturation)
+	}
+
 	fn compute_fee_raw(
 		len: u32,
 		weight: Weight,
@@ -574,16 +681,13 @@ where
 	/// and the entire block weight `(1/1)`, its priority is `fee * min(1, 4) = fee * 1`. This means
 	///  that the transaction which consumes more resources (either length or weight) with the same
 	/// `fee` ends up having lower priority.
-	fn get_priority(
+	pub fn get_priority(
 		len: usize,
 		info: &DispatchInfoOf<T::Call>,
 		final_fee: BalanceOf<T>,
 	) -> TransactionPriority {
-		let weight_saturation = T::BlockWeights::get().max_block / info.weight.max(1);
-		let max_block_length = *T::BlockLength::get().max.get(DispatchClass::Normal);
-		let len_saturation = max_block_length as u64 / (len as u64).max(1);
 		let coefficient: BalanceOf<T> =
-			weight_saturation.min(len_saturation).saturated_into::<BalanceOf<T>>();
+			Pallet::<T>::priority_coefficient(len, info).saturated_into::<BalanceOf<T>>();
 		final_fee.saturating_mul(coefficient).saturated_into::<TransactionPriority>()
 	}
 }
@@ -1080,6 +1184,33 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn query_tip_for_priority_works() {
+		let info = ().get_dispatch_info();
+		let len = 10;
+		ExtBuilder::default()
+			.base_weight(5)
+			.balance_factor(100)
+			.build()
+			.execute_with(|| {
+				let priority = |tip| {
+					ChargeTransactionPayment::<Runtime>::from(tip)
+						.validate(&1, CALL, &info, len as usize)
+						.unwrap()
+						.priority
+				};
+				let untipped = priority(0);
+
+				// no tip is needed for a priority the transaction already has.
+				assert_eq!(TransactionPayment::query_tip_for_priority((), len, untipped), 0);
+
+				let target = untipped * 3 + 1;
+				let tip = TransactionPayment::query_tip_for_priority((), len, target);
+				assert!(priority(tip) >= target);
+				assert!(priority(tip - 1) < target);
+			});
+	}
+
 	#[test]
 	fn compute_fee_works_without_multiplier() {
 		ExtBuilder::default()
@@ -1370,4 +1501,32 @@ mod tests {
 				assert_eq!(refund_based_fee, actual_fee);
 			});
 	}
+
+	#[test]
+	fn const_and_immediate_multipliers_work() {
+		parameter_types! {
+			pub Target: Perquintill = Perquintill::from_percent(25);
+			pub MinMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 10);
+			pub ConstMultiplier: Multiplier = Multiplier::saturating_from_rational(3, 2);
+		}
+		type Immediate = ImmediateFeeAdjustment<Runtime, Target, MinMultiplier>;
+
+		ExtBuilder::default().build().execute_with(|| {
+			let previous = Multiplier::saturating_from_integer(7);
+
+			// the maximum weight of normal transactions is 1024, the target is 256.
+			System::set_block_consumed_resources(512, 0);
+			assert_eq!(
+				ConstFeeMultiplier::<ConstMultiplier>::convert(previous),
+				ConstMultiplier::get()
+			);
+			assert_eq!(Immediate::convert(previous), Multiplier::saturating_from_integer(2));
+
+			System::set_block_consumed_resources(256, 0);
+			assert_eq!(Immediate::convert(previous), Multiplier::one());
+
+			System::set_block_consumed_resources(0, 0);
+			assert_eq!(Immediate::convert(previous), MinMultiplier::get());
+		});
+	}
 }
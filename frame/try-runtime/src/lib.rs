@@ -33,5 +33,12 @@ sp_api::decl_runtime_apis! {
 		/// Returns the consumed weight of the migration in case of a successful one, combined with
 		/// the total allowed block weight of the runtime.
 		fn on_runtime_upgrade() -> Result<(Weight, Weight), sp_runtime::RuntimeString>;
+
+		/// Execute the given block, without checking that the resulting state root matches the one
+		/// of its header, and return the weight it consumed.
+		///
+		/// This allows to replay the blocks of a live chain with a modified runtime, the state roots
+		/// being compared by the caller.
+		fn execute_block_no_check(block: Block) -> Weight;
 	}
 }
@@ -26,7 +26,7 @@ use frame_benchmarking::{
 };
 use frame_support::{
 	dispatch::UnfilteredDispatchable,
-	traits::{EnsureOrigin, Get},
+	traits::Get,
 	BoundedVec,
 };
 use frame_system::RawOrigin as SystemOrigin;
@@ -137,10 +137,14 @@ fn assert_last_event<T: Config<I>, I: 'static>(generic_event: <T as Config<I>>::
 
 benchmarks_instance_pallet! {
 	create {
-		let caller: T::AccountId = whitelisted_caller();
+		let class = Default::default();
+		let origin = T::CreateOrigin::successful_origin(&class);
+		let caller = T::CreateOrigin::ensure_origin(origin.clone(), &class).unwrap();
+		whitelist_account!(caller);
 		let caller_lookup = T::Lookup::unlookup(caller.clone());
 		T::Currency::make_free_balance_be(&caller, DepositBalanceOf::<T, I>::max_value());
-	}: _(SystemOrigin::Signed(caller.clone()), Default::default(), caller_lookup)
+		let call = Call::<T, I>::create { class, admin: caller_lookup };
+	}: { call.dispatch_bypass_filter(origin)? }
 	verify {
 		assert_last_event::<T, I>(Event::Created(Default::default(), caller.clone(), caller).into());
 	}
@@ -285,7 +289,7 @@ benchmarks_instance_pallet! {
 
 	force_asset_status {
 		let (class, caller, caller_lookup) = create_class::<T, I>();
-		let origin = T::ForceOrigin::successful_origin();
+		let origin = T::ForceOrigin::successful_origin(&class);
 		let call = Call::<T, I>::force_asset_status {
 			class,
 			owner: caller_lookup.clone(),
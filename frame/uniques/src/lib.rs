@@ -41,7 +41,9 @@ mod types;
 pub use types::*;
 
 use codec::{Decode, Encode, HasCompact};
-use frame_support::traits::{BalanceStatus::Reserved, Currency, ReservableCurrency};
+use frame_support::traits::{
+	BalanceStatus::Reserved, Currency, EnsureOriginWithArg, ReservableCurrency,
+};
 use frame_system::Config as SystemConfig;
 use sp_runtime::{
 	traits::{Saturating, StaticLookup, Zero},
@@ -77,9 +79,17 @@ pub mod pallet {
 		/// The currency mechanism, used for paying for reserves.
 		type Currency: ReservableCurrency<Self::AccountId>;
 
+		/// Standard asset class creation is only allowed if the origin attempting it and the
+		/// asset class are in this set.
+		type CreateOrigin: EnsureOriginWithArg<
+			Self::Origin,
+			Self::ClassId,
+			Success = Self::AccountId,
+		>;
+
 		/// The origin which may forcibly create or destroy an asset or otherwise alter privileged
-		/// attributes.
-		type ForceOrigin: EnsureOrigin<Self::Origin>;
+		/// attributes of the given asset class.
+		type ForceOrigin: EnsureOriginWithArg<Self::Origin, Self::ClassId>;
 
 		/// The basic amount of funds that must be reserved for an asset class.
 		#[pallet::constant]
@@ -290,7 +300,7 @@ pub mod pallet {
 		///
 		/// This new asset class has no assets initially and its owner is the origin.
 		///
-		/// The origin must be Signed and the sender must have sufficient funds free.
+		/// The origin must conform to the configured `CreateOrigin` and have sufficient funds free.
 		///
 		/// `AssetDeposit` funds of sender are reserved.
 		///
@@ -308,7 +318,7 @@ pub mod pallet {
 			#[pallet::compact] class: T::ClassId,
 			admin: <T::Lookup as StaticLookup>::Source,
 		) -> DispatchResult {
-			let owner = ensure_signed(origin)?;
+			let owner = T::CreateOrigin::ensure_origin(origin, &class)?;
 			let admin = T::Lookup::lookup(admin)?;
 
 			Self::do_create_class(
@@ -344,7 +354,7 @@ pub mod pallet {
 			owner: <T::Lookup as StaticLookup>::Source,
 			free_holding: bool,
 		) -> DispatchResult {
-			T::ForceOrigin::ensure_origin(origin)?;
+			T::ForceOrigin::ensure_origin(origin, &class)?;
 			let owner = T::Lookup::lookup(owner)?;
 
 			Self::do_create_class(
@@ -382,7 +392,7 @@ pub mod pallet {
 			#[pallet::compact] class: T::ClassId,
 			witness: DestroyWitness,
 		) -> DispatchResult {
-			let maybe_check_owner = match T::ForceOrigin::try_origin(origin) {
+			let maybe_check_owner = match T::ForceOrigin::try_origin(origin, &class) {
 				Ok(_) => None,
 				Err(origin) => Some(ensure_signed(origin)?),
 			};
@@ -782,7 +792,7 @@ pub mod pallet {
 			#[pallet::compact] instance: T::InstanceId,
 			delegate: <T::Lookup as StaticLookup>::Source,
 		) -> DispatchResult {
-			let maybe_check: Option<T::AccountId> = T::ForceOrigin::try_origin(origin)
+			let maybe_check: Option<T::AccountId> = T::ForceOrigin::try_origin(origin, &class)
 				.map(|_| None)
 				.or_else(|origin| ensure_signed(origin).map(Some).map_err(DispatchError::from))?;
 
@@ -829,7 +839,7 @@ pub mod pallet {
 			#[pallet::compact] instance: T::InstanceId,
 			maybe_check_delegate: Option<<T::Lookup as StaticLookup>::Source>,
 		) -> DispatchResult {
-			let maybe_check: Option<T::AccountId> = T::ForceOrigin::try_origin(origin)
+			let maybe_check: Option<T::AccountId> = T::ForceOrigin::try_origin(origin, &class)
 				.map(|_| None)
 				.or_else(|origin| ensure_signed(origin).map(Some).map_err(DispatchError::from))?;
 
@@ -880,7 +890,7 @@ pub mod pallet {
 			free_holding: bool,
 			is_frozen: bool,
 		) -> DispatchResult {
-			T::ForceOrigin::ensure_origin(origin)?;
+			T::ForceOrigin::ensure_origin(origin, &class)?;
 
 			Class::<T, I>::try_mutate(class, |maybe_asset| {
 				let mut asset = maybe_asset.take().ok_or(Error::<T, I>::Unknown)?;
@@ -922,7 +932,7 @@ pub mod pallet {
 			key: BoundedVec<u8, T::KeyLimit>,
 			value: BoundedVec<u8, T::ValueLimit>,
 		) -> DispatchResult {
-			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin, &class)
 				.map(|_| None)
 				.or_else(|origin| ensure_signed(origin).map(Some))?;
 
@@ -986,7 +996,7 @@ pub mod pallet {
 			maybe_instance: Option<T::InstanceId>,
 			key: BoundedVec<u8, T::KeyLimit>,
 		) -> DispatchResult {
-			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin, &class)
 				.map(|_| None)
 				.or_else(|origin| ensure_signed(origin).map(Some))?;
 
@@ -1036,7 +1046,7 @@ pub mod pallet {
 			data: BoundedVec<u8, T::StringLimit>,
 			is_frozen: bool,
 		) -> DispatchResult {
-			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin, &class)
 				.map(|_| None)
 				.or_else(|origin| ensure_signed(origin).map(Some))?;
 
@@ -1095,7 +1105,7 @@ pub mod pallet {
 			#[pallet::compact] class: T::ClassId,
 			#[pallet::compact] instance: T::InstanceId,
 		) -> DispatchResult {
-			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin, &class)
 				.map(|_| None)
 				.or_else(|origin| ensure_signed(origin).map(Some))?;
 
@@ -1144,7 +1154,7 @@ pub mod pallet {
 			data: BoundedVec<u8, T::StringLimit>,
 			is_frozen: bool,
 		) -> DispatchResult {
-			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin, &class)
 				.map(|_| None)
 				.or_else(|origin| ensure_signed(origin).map(Some))?;
 
@@ -1198,7 +1208,7 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			#[pallet::compact] class: T::ClassId,
 		) -> DispatchResult {
-			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin, &class)
 				.map(|_| None)
 				.or_else(|origin| ensure_signed(origin).map(Some))?;
 
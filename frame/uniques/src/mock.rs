@@ -20,7 +20,7 @@
 use super::*;
 use crate as pallet_uniques;
 
-use frame_support::{construct_runtime, parameter_types};
+use frame_support::{construct_runtime, parameter_types, traits::AsEnsureOriginWithArg};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -104,7 +104,8 @@ impl Config for Test {
 	type ClassId = u32;
 	type InstanceId = u32;
 	type Currency = Balances;
-	type ForceOrigin = frame_system::EnsureRoot<u64>;
+	type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<u64>>;
+	type ForceOrigin = AsEnsureOriginWithArg<frame_system::EnsureRoot<u64>>;
 	type ClassDeposit = ClassDeposit;
 	type InstanceDeposit = InstanceDeposit;
 	type MetadataDepositBase = MetadataDepositBase;
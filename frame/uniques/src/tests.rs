@@ -581,3 +581,14 @@ fn cancel_approval_works_with_force() {
 		);
 	});
 }
+
+#[test]
+fn ensure_class_owner_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Uniques::force_create(Origin::root(), 0, 1, true));
+		assert_eq!(EnsureClassOwner::<Test>::try_origin(Origin::signed(1), &0).ok(), Some(1));
+		assert!(EnsureClassOwner::<Test>::try_origin(Origin::signed(2), &0).is_err());
+		assert!(EnsureClassOwner::<Test>::try_origin(Origin::signed(1), &1).is_err());
+		assert!(EnsureClassOwner::<Test>::try_origin(Origin::root(), &0).is_err());
+	});
+}
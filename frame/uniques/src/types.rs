@@ -120,3 +120,24 @@ pub struct InstanceMetadata<DepositBalance, StringLimit: Get<u32>> {
 	/// Whether the asset metadata may be changed by a non Force origin.
 	pub(super) is_frozen: bool,
 }
+
+/// Ensures the origin is signed by the owner of the given asset class.
+pub struct EnsureClassOwner<T, I = ()>(sp_std::marker::PhantomData<(T, I)>);
+impl<T: Config<I>, I: 'static> EnsureOriginWithArg<T::Origin, T::ClassId>
+	for EnsureClassOwner<T, I>
+{
+	type Success = T::AccountId;
+	fn try_origin(o: T::Origin, class: &T::ClassId) -> Result<T::AccountId, T::Origin> {
+		let owner = Class::<T, I>::get(class).map(|details| details.owner);
+		o.into().and_then(|o| match o {
+			frame_system::RawOrigin::Signed(who) if owner.as_ref() == Some(&who) => Ok(who),
+			r => Err(T::Origin::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin(class: &T::ClassId) -> T::Origin {
+		let owner = Class::<T, I>::get(class).map(|details| details.owner).unwrap_or_default();
+		T::Origin::from(frame_system::RawOrigin::Signed(owner))
+	}
+}
@@ -58,7 +58,7 @@ pub mod weights;
 
 use codec::{Decode, Encode};
 use frame_support::{
-	dispatch::PostDispatchInfo,
+	dispatch::{PostDispatchInfo, WithPostDispatchInfo},
 	traits::{IsSubType, OriginTrait, UnfilteredDispatchable},
 	transactional,
 	weights::{extract_actual_weight, GetDispatchInfo},
@@ -176,7 +176,11 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let is_root = ensure_root(origin.clone()).is_ok();
 			let calls_len = calls.len();
-			ensure!(calls_len <= Self::batched_calls_limit() as usize, Error::<T>::TooManyCalls);
+			// None of the calls is dispatched, so only the weight of this function is charged.
+			ensure!(
+				calls_len <= Self::batched_calls_limit() as usize,
+				Error::<T>::TooManyCalls.with_weight(T::WeightInfo::batch(0))
+			);
 
 			// Track the actual weight of each of the batch calls.
 			let mut weight: Weight = 0;
@@ -233,7 +237,9 @@ pub mod pallet {
 			call: Box<<T as Config>::Call>,
 		) -> DispatchResultWithPostInfo {
 			let mut origin = origin;
-			let who = ensure_signed(origin.clone())?;
+			// The call is not dispatched, so only the weight of this function is charged.
+			let who = ensure_signed(origin.clone())
+				.map_err(|e| e.with_weight(T::WeightInfo::as_derivative()))?;
 			let pseudonym = Self::derivative_account_id(who, index);
 			origin.set_caller_from(frame_system::RawOrigin::Signed(pseudonym));
 			let info = call.get_dispatch_info();
@@ -290,7 +296,11 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let is_root = ensure_root(origin.clone()).is_ok();
 			let calls_len = calls.len();
-			ensure!(calls_len <= Self::batched_calls_limit() as usize, Error::<T>::TooManyCalls);
+			// None of the calls is dispatched, so only the weight of this function is charged.
+			ensure!(
+				calls_len <= Self::batched_calls_limit() as usize,
+				Error::<T>::TooManyCalls.with_weight(T::WeightInfo::batch_all(0))
+			);
 
 			// Track the actual weight of each of the batch calls.
 			let mut weight: Weight = 0;
@@ -24,7 +24,7 @@ use super::*;
 use crate as utility;
 use frame_support::{
 	assert_err_ignore_postinfo, assert_noop, assert_ok,
-	dispatch::{DispatchError, DispatchErrorWithPostInfo, Dispatchable},
+	dispatch::{DispatchError, DispatchErrorWithPostInfo, Dispatchable, WithPostDispatchInfo},
 	parameter_types, storage,
 	traits::Contains,
 	weights::{Pays, Weight},
@@ -596,7 +596,13 @@ fn batch_all_does_not_nest() {
 fn batch_limit() {
 	new_test_ext().execute_with(|| {
 		let calls = vec![Call::System(SystemCall::remark { remark: vec![] }); 40_000];
-		assert_noop!(Utility::batch(Origin::signed(1), calls.clone()), Error::<Test>::TooManyCalls);
-		assert_noop!(Utility::batch_all(Origin::signed(1), calls), Error::<Test>::TooManyCalls);
+		assert_noop!(
+			Utility::batch(Origin::signed(1), calls.clone()),
+			Error::<Test>::TooManyCalls.with_weight(<() as WeightInfo>::batch(0))
+		);
+		assert_noop!(
+			Utility::batch_all(Origin::signed(1), calls),
+			Error::<Test>::TooManyCalls.with_weight(<() as WeightInfo>::batch_all(0))
+		);
 	});
 }
@@ -1114,6 +1114,27 @@ fn vesting_info_ending_block_as_balance_works() {
 	);
 }
 
+#[test]
+fn vesting_info_with_length_works() {
+	let schedule = VestingInfo::with_length::<Identity>(256u32, 16u32, 10u32);
+	assert_eq!(schedule, VestingInfo::new(256u32, 16u32, 10u32));
+	assert_eq!(schedule.ending_block_as_balance::<Identity>(), 10 + 16);
+
+	// A length of 0 vests everything in the block after it starts.
+	let schedule = VestingInfo::with_length::<Identity>(256u32, 0u32, 10u32);
+	assert_eq!(schedule.ending_block_as_balance::<Identity>(), 10 + 1);
+
+	// The length can be derived from a duration.
+	frame_support::parameter_types! {
+		const BlockTime: u64 = 6_000;
+	}
+	let one_day = <frame_support::traits::MillisToBlocks<BlockTime> as Convert<u64, u32>>::convert(
+		86_400_000,
+	);
+	let schedule = VestingInfo::with_length::<Identity>(14_400u32, one_day, 0u32);
+	assert_eq!(schedule.per_block(), 1);
+}
+
 #[test]
 fn per_block_works() {
 	let per_block_0 = VestingInfo::new(256u32, 0u32, 10u32);
@@ -44,6 +44,21 @@ where
 		VestingInfo { locked, per_block, starting_block }
 	}
 
+	/// Instantiate a new `VestingInfo` which is fully vested `length` blocks after
+	/// `starting_block`.
+	///
+	/// Schedules meant to last for a given time can compute `length` from their duration with
+	/// [`frame_support::traits::MillisToBlocks`].
+	pub fn with_length<BlockNumberToBalance: Convert<BlockNumber, Balance>>(
+		locked: Balance,
+		length: BlockNumber,
+		starting_block: BlockNumber,
+	) -> VestingInfo<Balance, BlockNumber> {
+		let length_as_balance = BlockNumberToBalance::convert(length);
+		let per_block = locked / length_as_balance.max(One::one());
+		Self::new(locked, per_block, starting_block)
+	}
+
 	/// Validate parameters for `VestingInfo`. Note that this does not check
 	/// against `MinVestedTransfer`.
 	pub fn is_valid(&self) -> bool {
@@ -45,9 +45,12 @@
 //!
 //! Substrate supports logging from the runtime in native and in wasm. For that purpose it provides
 //! the [`RuntimeLogger`](sp_runtime::runtime_logger::RuntimeLogger). This runtime logger is
-//! automatically enabled for each call into the runtime through the runtime api. As logging
-//! introduces extra code that isn't actually required for the logic of your runtime and also
-//! increases the final wasm blob size, it is recommended to disable the logging for on-chain
+//! automatically enabled for each call into the runtime through the runtime api. With the
+//! `log-target-filter` feature of `sp-runtime`, log messages are filtered by the log level the node
+//! configured for their target (e.g. `-lruntime::staking=debug`), before they are formatted inside
+//! the runtime. Such runtimes require a node providing the `logging::enabled` host function. As
+//! logging introduces extra code that isn't actually required for the logic of your runtime and
+//! also increases the final wasm blob size, it is recommended to disable the logging for on-chain
 //! wasm blobs. This can be done by enabling the `disable-logging` feature of this crate. Be aware
 //! that this feature instructs `log` and `tracing` to disable logging at compile time by setting
 //! the `max_level_off` feature for these crates. So, you should not enable this feature for a
@@ -269,6 +272,7 @@ pub use sp_api_proc_macro::decl_runtime_apis;
 ///     // Here we are exposing the runtime api versions.
 ///     apis: RUNTIME_API_VERSIONS,
 ///     transaction_version: 1,
+///     features: sp_version::runtime_features!(),
 /// };
 ///
 /// # fn main() {}
@@ -679,6 +683,7 @@ impl From<OldRuntimeVersion> for RuntimeVersion {
 			impl_version: x.impl_version,
 			apis: x.apis,
 			transaction_version: 1,
+			features: Default::default(),
 		}
 	}
 }
@@ -699,7 +704,7 @@ impl From<RuntimeVersion> for OldRuntimeVersion {
 decl_runtime_apis! {
 	/// The `Core` runtime api that every Substrate runtime needs to implement.
 	#[core_trait]
-	#[api_version(3)]
+	#[api_version(4)]
 	pub trait Core {
 		/// Returns the version of the runtime.
 		fn version() -> RuntimeVersion;
@@ -0,0 +1,81 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! BLS12-381 crypto types.
+
+use crate::{KeyTypeId, RuntimePublic};
+
+use sp_std::vec::Vec;
+
+pub use sp_core::bls381::*;
+
+mod app {
+	use sp_core::testing::BLS381;
+
+	crate::app_crypto!(super, BLS381);
+
+	impl crate::traits::BoundToRuntimeAppPublic for Public {
+		type Public = Self;
+	}
+}
+
+#[cfg(feature = "full_crypto")]
+pub use app::Pair as AppPair;
+pub use app::{Public as AppPublic, Signature as AppSignature};
+
+impl RuntimePublic for Public {
+	type Signature = Signature;
+
+	fn all(key_type: KeyTypeId) -> crate::Vec<Self> {
+		sp_io::bls::bls381_public_keys(key_type)
+	}
+
+	fn generate_pair(key_type: KeyTypeId, seed: Option<Vec<u8>>) -> Self {
+		sp_io::bls::bls381_generate(key_type, seed)
+	}
+
+	fn sign<M: AsRef<[u8]>>(&self, key_type: KeyTypeId, msg: &M) -> Option<Self::Signature> {
+		sp_io::bls::bls381_sign(key_type, self, msg.as_ref())
+	}
+
+	fn verify<M: AsRef<[u8]>>(&self, msg: &M, signature: &Self::Signature) -> bool {
+		sp_io::bls::bls381_verify(&signature, msg.as_ref(), self)
+	}
+
+	fn to_raw_vec(&self) -> Vec<u8> {
+		sp_core::crypto::Public::to_raw_vec(self)
+	}
+}
+
+/// Aggregate the given signatures into a single one.
+///
+/// Returns `None` if there is no signature or if any of them is invalid.
+pub fn aggregate(signatures: Vec<Signature>) -> Option<Signature> {
+	sp_io::bls::bls381_aggregate(signatures)
+}
+
+/// Verify a signature of `msg` aggregated from the signatures of all the given public keys.
+///
+/// Each of the public keys must have proven the possession of its secret key, otherwise the
+/// verification can be forged.
+pub fn verify_aggregate<M: AsRef<[u8]>>(
+	signature: &Signature,
+	msg: &M,
+	public_keys: Vec<Public>,
+) -> bool {
+	sp_io::bls::bls381_verify_aggregate(signature, msg.as_ref(), public_keys)
+}
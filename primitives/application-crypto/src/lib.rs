@@ -41,6 +41,8 @@ pub use serde;
 #[doc(hidden)]
 pub use sp_std::{convert::TryFrom, ops::Deref, vec::Vec};
 
+#[cfg(feature = "bls-experimental")]
+pub mod bls381;
 pub mod ecdsa;
 pub mod ed25519;
 pub mod sr25519;
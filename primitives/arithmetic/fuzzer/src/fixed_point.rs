@@ -28,7 +28,10 @@
 //! [here](https://docs.rs/honggfuzz/).
 
 use honggfuzz::fuzz;
-use sp_arithmetic::{traits::Saturating, FixedI64, FixedPointNumber};
+use sp_arithmetic::{
+	traits::{One, Saturating},
+	FixedI64, FixedPointNumber, Perbill,
+};
 
 fn main() {
 	loop {
@@ -79,6 +82,30 @@ fn main() {
 			let d = a.saturating_mul(xx).saturating_add(xx).into_inner() as i128 /
 				FixedI64::accuracy() as i128;
 			assert_eq!(b, d);
+
+			// Check `sqrt` is the largest number whose square is not above `x`.
+			let a = FixedI64::saturating_from_integer(x);
+			match a.sqrt() {
+				Some(root) => {
+					assert!(root.saturating_mul(root) <= a);
+					let next = root.saturating_add(FixedI64::from_inner(1));
+					assert!(next.saturating_mul(next) >= a);
+				},
+				None => assert!(x < 0),
+			}
+
+			// Check `into_clamped_perthing` is the inverse of the `PerThing` conversion.
+			if y != 0 {
+				let a = FixedI64::saturating_from_rational(x, y);
+				let p: Perbill = a.into_clamped_perthing();
+				if a.is_negative() {
+					assert_eq!(p, Perbill::zero());
+				} else if a > FixedI64::one() {
+					assert_eq!(p, Perbill::one());
+				} else {
+					assert_eq!(FixedI64::from(p), a);
+				}
+			}
 		});
 	}
 }
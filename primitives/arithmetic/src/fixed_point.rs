@@ -20,8 +20,8 @@
 use crate::{
 	helpers_128bit::multiply_by_rational,
 	traits::{
-		Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedNeg, CheckedSub, One,
-		SaturatedConversion, Saturating, UniqueSaturatedInto, Zero,
+		Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedNeg, CheckedSub, IntegerSquareRoot,
+		One, SaturatedConversion, Saturating, UniqueSaturatedInto, Zero,
 	},
 	PerThing,
 };
@@ -419,6 +419,64 @@ macro_rules! implement_fixed {
 			pub fn to_float(self) -> f64 {
 				self.0 as f64 / <Self as FixedPointNumber>::DIV as f64
 			}
+
+			/// Returns the square root, rounded down to the accuracy of the type.
+			///
+			/// Returns `None` if `self` is negative. Numbers whose inner value times `DIV` does
+			/// not fit in a `u128` lose the part of their root beyond `1 / sqrt(DIV)`.
+			pub fn sqrt(self) -> Option<Self> {
+				if self.is_negative() {
+					return None
+				}
+
+				let inner = self.0 as u128;
+				let div = <Self as FixedPointNumber>::DIV as u128;
+				let root = match inner.checked_mul(div) {
+					Some(scaled) => scaled.integer_sqrt(),
+					None => inner.integer_sqrt().saturating_mul(div.integer_sqrt()),
+				};
+				root.try_into().ok().map(Self)
+			}
+
+			/// Converts `self` into a `PerThing`, clamping it to `[0, 1]` first.
+			pub fn into_clamped_perthing<P: PerThing>(self) -> P {
+				if !self.is_positive() {
+					P::zero()
+				} else if self >= Self::one() {
+					P::one()
+				} else {
+					P::from_rational(self.0 as u128, <Self as FixedPointNumber>::DIV as u128)
+				}
+			}
+
+			/// Linear interpolation between `from` and `to`, `x` being the position on the way
+			/// from one to the other.
+			///
+			/// Saturates to `Self::min` or `Self::max` if the result does not fit.
+			pub fn saturating_interpolate<P: PerThing>(from: Self, to: Self, x: P) -> Self
+			where
+				P::Inner: FixedPointOperand,
+			{
+				let x = Self::from(x);
+				if to >= from {
+					from.saturating_add(to.saturating_sub(from).saturating_mul(x))
+				} else {
+					from.saturating_sub(from.saturating_sub(to).saturating_mul(x))
+				}
+			}
+
+			/// Evaluate the reciprocal curve `factor / (self + x_offset) + y_offset`.
+			///
+			/// Returns `None` if `self + x_offset` is zero or if any step overflows.
+			pub fn checked_reciprocal_curve(
+				self,
+				factor: Self,
+				x_offset: Self,
+				y_offset: Self,
+			) -> Option<Self> {
+				let denominator = self.checked_add(&x_offset)?;
+				factor.checked_div(&denominator)?.checked_add(&y_offset)
+			}
 		}
 
 		impl Saturating for $name {
@@ -1657,6 +1715,118 @@ macro_rules! implement_fixed {
 				}
 			}
 
+			#[test]
+			fn sqrt_works() {
+				assert_eq!($name::zero().sqrt(), Some($name::zero()));
+				assert_eq!($name::one().sqrt(), Some($name::one()));
+				assert_eq!($name::saturating_from_integer(4).sqrt(), Some(2.into()));
+				assert_eq!($name::saturating_from_rational(1, 4).sqrt(), Some((1, 2).into()));
+				assert_eq!(
+					$name::saturating_from_integer(2).sqrt().unwrap().into_inner(),
+					(2 * $name::accuracy() as u128 * $name::accuracy() as u128).integer_sqrt()
+						as <$name as FixedPointNumber>::Inner,
+				);
+
+				// the root of the largest numbers is still close to the exact one.
+				let root = $name::max_value().sqrt().unwrap();
+				let squared = root.saturating_mul(root);
+				assert!(squared <= $name::max_value());
+				assert!(
+					$name::max_value().saturating_sub(squared) <
+						$name::max_value() / 1_000_000.into()
+				);
+
+				if $name::SIGNED {
+					assert_eq!($name::saturating_from_integer(-4).sqrt(), None);
+					assert_eq!($name::from_inner(0.saturating_sub(1)).sqrt(), None);
+				}
+			}
+
+			#[test]
+			fn into_clamped_perthing_works() {
+				assert_eq!($name::zero().into_clamped_perthing::<Perbill>(), Perbill::zero());
+				assert_eq!($name::one().into_clamped_perthing::<Perbill>(), Perbill::one());
+				assert_eq!(
+					$name::saturating_from_integer(2).into_clamped_perthing::<Percent>(),
+					Percent::one(),
+				);
+				assert_eq!(
+					$name::saturating_from_rational(1, 3).into_clamped_perthing::<Perbill>(),
+					Perbill::from_rational(1u32, 3),
+				);
+				assert_eq!(
+					$name::saturating_from_rational(1, 10).into_clamped_perthing::<Permill>(),
+					Permill::from_percent(10),
+				);
+
+				if $name::SIGNED {
+					assert_eq!(
+						$name::saturating_from_integer(-1).into_clamped_perthing::<Perbill>(),
+						Perbill::zero(),
+					);
+				}
+			}
+
+			#[test]
+			fn saturating_interpolate_works() {
+				let a = $name::saturating_from_integer(1);
+				let b = $name::saturating_from_integer(3);
+
+				assert_eq!($name::saturating_interpolate(a, b, Perbill::zero()), a);
+				assert_eq!($name::saturating_interpolate(a, b, Perbill::one()), b);
+				assert_eq!(
+					$name::saturating_interpolate(a, b, Percent::from_percent(50)),
+					2.into()
+				);
+				assert_eq!(
+					$name::saturating_interpolate(b, a, Percent::from_percent(25)),
+					(5, 2).into()
+				);
+				assert_eq!(
+					$name::saturating_interpolate($name::zero(), max(), Perquintill::one()),
+					max()
+				);
+
+				if $name::SIGNED {
+					let c = $name::saturating_from_integer(-1);
+					assert_eq!(
+						$name::saturating_interpolate(c, a, Percent::from_percent(50)),
+						0.into()
+					);
+					assert_eq!(
+						$name::saturating_interpolate(a, c, Percent::from_percent(75)),
+						(-1, 2).into()
+					);
+				}
+			}
+
+			#[test]
+			fn checked_reciprocal_curve_works() {
+				let factor = $name::saturating_from_rational(1, 2);
+				let x_offset = $name::saturating_from_rational(1, 2);
+				let y_offset = $name::saturating_from_rational(1, 10);
+
+				// 0.5 / (0 + 0.5) + 0.1
+				assert_eq!(
+					$name::zero().checked_reciprocal_curve(factor, x_offset, y_offset),
+					Some((11, 10).into()),
+				);
+				// 0.5 / (1.5 + 0.5) + 0.1
+				assert_eq!(
+					$name::saturating_from_rational(3, 2)
+						.checked_reciprocal_curve(factor, x_offset, y_offset),
+					Some((35, 100).into()),
+				);
+				assert_eq!(
+					$name::zero().checked_reciprocal_curve(factor, $name::zero(), y_offset),
+					None
+				);
+				assert_eq!(
+					$name::zero().checked_reciprocal_curve(max(), $name::from_inner(1), y_offset),
+					None,
+				);
+			}
+
 			#[test]
 			fn perthing_into_works() {
 				let ten_percent_percent: $name = Percent::from_percent(10).into();
@@ -83,6 +83,7 @@ pub enum ConsensusLog<AuthorityId: Codec> {
 
 sp_api::decl_runtime_apis! {
 	/// API necessary for block authorship with aura.
+	#[api_version(2)]
 	pub trait AuraApi<AuthorityId: Codec> {
 		/// Returns the slot duration for Aura.
 		///
@@ -91,6 +92,9 @@ sp_api::decl_runtime_apis! {
 
 		// Return the current set of authorities.
 		fn authorities() -> Vec<AuthorityId>;
+
+		/// Return the set of authorities queued for the next session.
+		fn next_authorities() -> Vec<AuthorityId>;
 	}
 }
 
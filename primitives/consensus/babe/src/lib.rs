@@ -263,6 +263,13 @@ pub struct BabeEpochConfiguration {
 	pub allowed_slots: AllowedSlots,
 }
 
+impl BabeEpochConfiguration {
+	/// Whether `c` is a valid probability for the threshold calculation, i.e. is in `(0, 1]`.
+	pub fn is_valid(&self) -> bool {
+		self.c.0 > 0 && self.c.0 <= self.c.1
+	}
+}
+
 /// Verifies the equivocation proof by making sure that: both headers have
 /// different hashes, are targetting the same slot, and have valid signatures by
 /// the same authority.
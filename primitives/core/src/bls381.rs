@@ -0,0 +1,596 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// tag::description[]
+//! Simple BLS12-381 API.
+//!
+//! Public keys are points of G1 (48 bytes compressed) and signatures points of G2 (96 bytes
+//! compressed), following the proof of possession scheme of the IETF BLS signature draft.
+//! Signatures of the same message can be aggregated and verified at once against the
+//! corresponding public keys, which is only sound if each of those keys has proven the
+//! possession of its secret key. Such proofs are made by [`Pair::pop_prove`] and checked by
+//! [`Pair::pop_verify`], using a domain separation tag distinct from the one of the signatures,
+//! so a signature of a public key can't be passed off as a proof of possession.
+// end::description[]
+
+// `blst` is only built with `std`, the key pairs of `full_crypto` can't be provided without it.
+#[cfg(all(feature = "full_crypto", not(feature = "std")))]
+compile_error!("`bls-experimental` requires `std` when `full_crypto` is enabled");
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime_interface::pass_by::PassByInner;
+
+#[cfg(feature = "std")]
+use crate::crypto::Ss58Codec;
+use crate::crypto::{
+	CryptoType, CryptoTypeId, CryptoTypePublicPair, Derive, Public as TraitPublic, UncheckedFrom,
+};
+#[cfg(feature = "full_crypto")]
+use crate::crypto::{DeriveJunction, Pair as TraitPair, SecretStringError};
+#[cfg(feature = "std")]
+use bip39::{Language, Mnemonic, MnemonicType};
+#[cfg(feature = "full_crypto")]
+use blst::{
+	min_pk::{AggregateSignature, PublicKey, SecretKey, Signature as BlstSignature},
+	BLST_ERROR,
+};
+#[cfg(feature = "std")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "std")]
+use sp_std::convert::TryFrom;
+#[cfg(feature = "full_crypto")]
+use sp_std::vec::Vec;
+
+/// An identifier used to match public keys against BLS12-381 keys
+pub const CRYPTO_ID: CryptoTypeId = CryptoTypeId(*b"bls8");
+
+/// The domain separation tag of the signatures, as defined for the proof of possession scheme.
+#[cfg(feature = "full_crypto")]
+const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// The domain separation tag of the proofs of possession.
+#[cfg(feature = "full_crypto")]
+const POP_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Size of a compressed public key.
+pub const PUBLIC_KEY_SERIALIZED_SIZE: usize = 48;
+
+/// Size of a compressed signature.
+pub const SIGNATURE_SERIALIZED_SIZE: usize = 96;
+
+/// A secret seed, the input key material of the secret key.
+#[cfg(feature = "full_crypto")]
+type Seed = [u8; 32];
+
+/// A BLS12-381 compressed public key.
+#[derive(
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Encode,
+	Decode,
+	PassByInner,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub struct Public(pub [u8; PUBLIC_KEY_SERIALIZED_SIZE]);
+
+impl Public {
+	/// A new instance from the given 48-byte `data`.
+	///
+	/// NOTE: No checking goes on to ensure this is a real public key. Only use it if
+	/// you are certain that the array actually is a pubkey. GIGO!
+	pub fn from_raw(data: [u8; PUBLIC_KEY_SERIALIZED_SIZE]) -> Self {
+		Self(data)
+	}
+}
+
+impl TraitPublic for Public {
+	/// A new instance from the given slice that should be 48 bytes long.
+	///
+	/// NOTE: No checking goes on to ensure this is a real public key. Only use it if
+	/// you are certain that the array actually is a pubkey. GIGO!
+	fn from_slice(data: &[u8]) -> Self {
+		let mut r = [0u8; PUBLIC_KEY_SERIALIZED_SIZE];
+		r.copy_from_slice(data);
+		Self(r)
+	}
+
+	fn to_public_crypto_pair(&self) -> CryptoTypePublicPair {
+		CryptoTypePublicPair(CRYPTO_ID, self.to_raw_vec())
+	}
+}
+
+impl From<Public> for CryptoTypePublicPair {
+	fn from(key: Public) -> Self {
+		(&key).into()
+	}
+}
+
+impl From<&Public> for CryptoTypePublicPair {
+	fn from(key: &Public) -> Self {
+		CryptoTypePublicPair(CRYPTO_ID, key.to_raw_vec())
+	}
+}
+
+impl Derive for Public {}
+
+impl Default for Public {
+	fn default() -> Self {
+		Public([0u8; PUBLIC_KEY_SERIALIZED_SIZE])
+	}
+}
+
+impl AsRef<[u8]> for Public {
+	fn as_ref(&self) -> &[u8] {
+		&self.0[..]
+	}
+}
+
+impl AsMut<[u8]> for Public {
+	fn as_mut(&mut self) -> &mut [u8] {
+		&mut self.0[..]
+	}
+}
+
+impl sp_std::convert::TryFrom<&[u8]> for Public {
+	type Error = ();
+
+	fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+		if data.len() == PUBLIC_KEY_SERIALIZED_SIZE {
+			Ok(Self::from_slice(data))
+		} else {
+			Err(())
+		}
+	}
+}
+
+#[cfg(feature = "full_crypto")]
+impl From<Pair> for Public {
+	fn from(x: Pair) -> Self {
+		x.public()
+	}
+}
+
+impl UncheckedFrom<[u8; PUBLIC_KEY_SERIALIZED_SIZE]> for Public {
+	fn unchecked_from(x: [u8; PUBLIC_KEY_SERIALIZED_SIZE]) -> Self {
+		Public(x)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for Public {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "{}", self.to_ss58check())
+	}
+}
+
+impl sp_std::fmt::Debug for Public {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		let s = self.to_ss58check();
+		write!(f, "{} ({}...)", crate::hexdisplay::HexDisplay::from(&self.as_ref()), &s[0..8])
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+#[cfg(feature = "std")]
+impl Serialize for Public {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(&self.to_ss58check())
+	}
+}
+
+#[cfg(feature = "std")]
+impl<'de> Deserialize<'de> for Public {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		Public::from_ss58check(&String::deserialize(deserializer)?)
+			.map_err(|e| de::Error::custom(format!("{:?}", e)))
+	}
+}
+
+#[cfg(feature = "full_crypto")]
+impl sp_std::hash::Hash for Public {
+	fn hash<H: sp_std::hash::Hasher>(&self, state: &mut H) {
+		self.as_ref().hash(state);
+	}
+}
+
+/// A BLS12-381 compressed signature.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, PassByInner, TypeInfo)]
+pub struct Signature(pub [u8; SIGNATURE_SERIALIZED_SIZE]);
+
+impl sp_std::convert::TryFrom<&[u8]> for Signature {
+	type Error = ();
+
+	fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+		if data.len() == SIGNATURE_SERIALIZED_SIZE {
+			let mut inner = [0u8; SIGNATURE_SERIALIZED_SIZE];
+			inner.copy_from_slice(data);
+			Ok(Signature(inner))
+		} else {
+			Err(())
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl Serialize for Signature {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(&hex::encode(self))
+	}
+}
+
+#[cfg(feature = "std")]
+impl<'de> Deserialize<'de> for Signature {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let signature_hex = hex::decode(&String::deserialize(deserializer)?)
+			.map_err(|e| de::Error::custom(format!("{:?}", e)))?;
+		Signature::try_from(signature_hex.as_ref())
+			.map_err(|e| de::Error::custom(format!("{:?}", e)))
+	}
+}
+
+impl Default for Signature {
+	fn default() -> Self {
+		Signature([0u8; SIGNATURE_SERIALIZED_SIZE])
+	}
+}
+
+impl AsRef<[u8]> for Signature {
+	fn as_ref(&self) -> &[u8] {
+		&self.0[..]
+	}
+}
+
+impl AsMut<[u8]> for Signature {
+	fn as_mut(&mut self) -> &mut [u8] {
+		&mut self.0[..]
+	}
+}
+
+impl sp_std::fmt::Debug for Signature {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "{}", crate::hexdisplay::HexDisplay::from(&self.0))
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+#[cfg(feature = "full_crypto")]
+impl sp_std::hash::Hash for Signature {
+	fn hash<H: sp_std::hash::Hasher>(&self, state: &mut H) {
+		sp_std::hash::Hash::hash(&self.0[..], state);
+	}
+}
+
+impl Signature {
+	/// A new instance from the given 96-byte `data`.
+	///
+	/// NOTE: No checking goes on to ensure this is a real signature. Only use it if
+	/// you are certain that the array actually is a signature. GIGO!
+	pub fn from_raw(data: [u8; SIGNATURE_SERIALIZED_SIZE]) -> Signature {
+		Signature(data)
+	}
+
+	/// Aggregate the given signatures into a single one.
+	///
+	/// Returns `None` if there is no signature or if any of them is invalid.
+	#[cfg(feature = "full_crypto")]
+	pub fn aggregate(signatures: &[Signature]) -> Option<Signature> {
+		let signatures = signatures
+			.iter()
+			.map(|signature| BlstSignature::from_bytes(&signature.0).ok())
+			.collect::<Option<Vec<_>>>()?;
+		let signatures = signatures.iter().collect::<Vec<_>>();
+		AggregateSignature::aggregate(&signatures, true)
+			.ok()
+			.map(|aggregate| Signature(aggregate.to_signature().to_bytes()))
+	}
+}
+
+/// Derive a single hard junction.
+#[cfg(feature = "full_crypto")]
+fn derive_hard_junction(secret_seed: &Seed, cc: &[u8; 32]) -> Seed {
+	("BLS12381HDKD", secret_seed, cc).using_encoded(|data| {
+		let mut res = [0u8; 32];
+		res.copy_from_slice(blake2_rfc::blake2b::blake2b(32, &[], data).as_bytes());
+		res
+	})
+}
+
+/// An error when deriving a key.
+#[cfg(feature = "full_crypto")]
+pub enum DeriveError {
+	/// A soft key was found in the path (and is unsupported).
+	SoftKeyInPath,
+}
+
+/// A key pair.
+#[cfg(feature = "full_crypto")]
+#[derive(Clone)]
+pub struct Pair {
+	seed: Seed,
+	secret: SecretKey,
+	public: Public,
+}
+
+#[cfg(feature = "full_crypto")]
+impl TraitPair for Pair {
+	type Public = Public;
+	type Seed = Seed;
+	type Signature = Signature;
+	type DeriveError = DeriveError;
+
+	/// Generate new secure (random) key pair and provide the recovery phrase.
+	///
+	/// You can recover the same key later with `from_phrase`.
+	#[cfg(feature = "std")]
+	fn generate_with_phrase(password: Option<&str>) -> (Pair, String, Seed) {
+		let mnemonic = Mnemonic::new(MnemonicType::Words12, Language::English);
+		let phrase = mnemonic.phrase();
+		let (pair, seed) = Self::from_phrase(phrase, password)
+			.expect("All phrases generated by Mnemonic are valid; qed");
+		(pair, phrase.to_owned(), seed)
+	}
+
+	/// Generate key pair from given recovery phrase and password.
+	#[cfg(feature = "std")]
+	fn from_phrase(
+		phrase: &str,
+		password: Option<&str>,
+	) -> Result<(Pair, Seed), SecretStringError> {
+		let big_seed = substrate_bip39::seed_from_entropy(
+			Mnemonic::from_phrase(phrase, Language::English)
+				.map_err(|_| SecretStringError::InvalidPhrase)?
+				.entropy(),
+			password.unwrap_or(""),
+		)
+		.map_err(|_| SecretStringError::InvalidSeed)?;
+		let mut seed = Seed::default();
+		seed.copy_from_slice(&big_seed[0..32]);
+		Self::from_seed_slice(&big_seed[0..32]).map(|x| (x, seed))
+	}
+
+	/// Make a new key pair from secret seed material.
+	///
+	/// You should never need to use this; generate(), generate_with_phrase
+	fn from_seed(seed: &Seed) -> Pair {
+		Self::from_seed_slice(&seed[..]).expect("seed has valid length; qed")
+	}
+
+	/// Make a new key pair from secret seed material. The slice must be 32 bytes long or it
+	/// will return `None`.
+	///
+	/// You should never need to use this; generate(), generate_with_phrase
+	fn from_seed_slice(seed_slice: &[u8]) -> Result<Pair, SecretStringError> {
+		if seed_slice.len() != 32 {
+			return Err(SecretStringError::InvalidSeedLength)
+		}
+		let mut seed = Seed::default();
+		seed.copy_from_slice(seed_slice);
+		let secret = SecretKey::key_gen(&seed, &[]).map_err(|_| SecretStringError::InvalidSeed)?;
+		let public = Public(secret.sk_to_pk().to_bytes());
+		Ok(Pair { seed, secret, public })
+	}
+
+	/// Derive a child key from a series of given junctions.
+	fn derive<Iter: Iterator<Item = DeriveJunction>>(
+		&self,
+		path: Iter,
+		_seed: Option<Seed>,
+	) -> Result<(Pair, Option<Seed>), DeriveError> {
+		let mut acc = self.seed;
+		for j in path {
+			match j {
+				DeriveJunction::Soft(_cc) => return Err(DeriveError::SoftKeyInPath),
+				DeriveJunction::Hard(cc) => acc = derive_hard_junction(&acc, &cc),
+			}
+		}
+		Ok((Self::from_seed(&acc), Some(acc)))
+	}
+
+	/// Get the public key.
+	fn public(&self) -> Public {
+		self.public
+	}
+
+	/// Sign a message.
+	fn sign(&self, message: &[u8]) -> Signature {
+		Signature(self.secret.sign(message, DST, &[]).to_bytes())
+	}
+
+	/// Verify a signature on a message. Returns true if the signature is good.
+	fn verify<M: AsRef<[u8]>>(sig: &Self::Signature, message: M, pubkey: &Self::Public) -> bool {
+		Self::verify_weak(&sig.0[..], message, pubkey)
+	}
+
+	/// Verify a signature on a message. Returns true if the signature is good.
+	fn verify_weak<P: AsRef<[u8]>, M: AsRef<[u8]>>(sig: &[u8], message: M, pubkey: P) -> bool {
+		let (sig, pubkey) =
+			match (BlstSignature::from_bytes(sig), PublicKey::from_bytes(pubkey.as_ref())) {
+				(Ok(sig), Ok(pubkey)) => (sig, pubkey),
+				_ => return false,
+			};
+		sig.verify(true, message.as_ref(), DST, &[], &pubkey, true) == BLST_ERROR::BLST_SUCCESS
+	}
+
+	/// Return a vec filled with raw data.
+	fn to_raw_vec(&self) -> Vec<u8> {
+		self.seed().to_vec()
+	}
+}
+
+#[cfg(feature = "full_crypto")]
+impl Pair {
+	/// Get the seed for this key.
+	pub fn seed(&self) -> Seed {
+		self.seed
+	}
+
+	/// Prove the possession of the secret key, by signing the public key.
+	///
+	/// The proof must be checked with [`Pair::pop_verify`] before the public key is used in
+	/// [`Pair::verify_aggregate`].
+	pub fn pop_prove(&self) -> Signature {
+		Signature(self.secret.sign(&self.public.0, POP_DST, &[]).to_bytes())
+	}
+
+	/// Verify a proof of possession of the secret key of `pubkey`, made by [`Pair::pop_prove`].
+	pub fn pop_verify(proof: &Signature, pubkey: &Public) -> bool {
+		let (proof, key) =
+			match (BlstSignature::from_bytes(&proof.0), PublicKey::from_bytes(&pubkey.0)) {
+				(Ok(proof), Ok(key)) => (proof, key),
+				_ => return false,
+			};
+		proof.verify(true, &pubkey.0, POP_DST, &[], &key, true) == BLST_ERROR::BLST_SUCCESS
+	}
+
+	/// Verify a signature of `message` aggregated from the signatures of all the given public
+	/// keys.
+	///
+	/// Each of the public keys must have proven the possession of its secret key with
+	/// [`Pair::pop_verify`], otherwise the verification can be forged.
+	pub fn verify_aggregate<M: AsRef<[u8]>>(
+		sig: &Signature,
+		message: M,
+		pubkeys: &[Public],
+	) -> bool {
+		let sig = match BlstSignature::from_bytes(&sig.0) {
+			Ok(sig) => sig,
+			Err(_) => return false,
+		};
+		let pubkeys = match pubkeys
+			.iter()
+			.map(|pubkey| PublicKey::from_bytes(&pubkey.0).ok())
+			.collect::<Option<Vec<_>>>()
+		{
+			Some(pubkeys) if !pubkeys.is_empty() => pubkeys,
+			_ => return false,
+		};
+		let pubkeys = pubkeys.iter().collect::<Vec<_>>();
+		sig.fast_aggregate_verify(true, message.as_ref(), DST, &pubkeys) == BLST_ERROR::BLST_SUCCESS
+	}
+}
+
+impl CryptoType for Public {
+	#[cfg(feature = "full_crypto")]
+	type Pair = Pair;
+}
+
+impl CryptoType for Signature {
+	#[cfg(feature = "full_crypto")]
+	type Pair = Pair;
+}
+
+#[cfg(feature = "full_crypto")]
+impl CryptoType for Pair {
+	type Pair = Pair;
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::crypto::DEV_PHRASE;
+
+	#[test]
+	fn generated_pair_should_work() {
+		let (pair, _) = Pair::generate();
+		let public = pair.public();
+		let message = b"Something important";
+		let signature = pair.sign(&message[..]);
+		assert!(Pair::verify(&signature, &message[..], &public));
+		assert!(!Pair::verify(&signature, b"Something else", &public));
+		assert!(!Pair::verify(&signature, &message[..], &Pair::generate().0.public()));
+	}
+
+	#[test]
+	fn seed_and_derive_should_work() {
+		let seed = [7u8; 32];
+		let pair = Pair::from_seed(&seed);
+		assert_eq!(pair.seed(), seed);
+		assert_eq!(Pair::from_seed(&seed).public(), pair.public());
+
+		let path = vec![DeriveJunction::Hard([0u8; 32])];
+		let derived = pair.derive(path.clone().into_iter(), None).ok().unwrap().0;
+		assert_eq!(derived.public(), pair.derive(path.into_iter(), None).ok().unwrap().0.public());
+		assert_ne!(derived.public(), pair.public());
+		assert!(pair.derive(vec![DeriveJunction::soft(1)].into_iter(), None).is_err());
+	}
+
+	#[test]
+	fn phrase_and_ss58_should_work() {
+		let pair = Pair::from_string(&format!("{}//Alice", DEV_PHRASE), None).unwrap();
+		let public = pair.public();
+		assert_eq!(Public::from_ss58check(&public.to_ss58check()), Ok(public));
+	}
+
+	#[test]
+	fn aggregated_signatures_should_verify() {
+		let pairs = (0..3u8).map(|i| Pair::from_seed(&[i; 32])).collect::<Vec<_>>();
+		let publics = pairs.iter().map(|pair| pair.public()).collect::<Vec<_>>();
+		let message = b"commitment";
+		let signatures = pairs.iter().map(|pair| pair.sign(&message[..])).collect::<Vec<_>>();
+
+		let aggregate = Signature::aggregate(&signatures).unwrap();
+		assert!(Pair::verify_aggregate(&aggregate, &message[..], &publics));
+		assert!(!Pair::verify_aggregate(&aggregate, &message[..], &publics[..2]));
+		assert!(!Pair::verify_aggregate(&aggregate, b"other", &publics));
+		assert!(!Pair::verify_aggregate(&aggregate, &message[..], &[]));
+		assert_eq!(Signature::aggregate(&[]), None);
+	}
+
+	#[test]
+	fn proof_of_possession_should_verify() {
+		let pair = Pair::from_seed(&[1; 32]);
+		let other = Pair::from_seed(&[2; 32]);
+		let proof = pair.pop_prove();
+
+		assert!(Pair::pop_verify(&proof, &pair.public()));
+		assert!(!Pair::pop_verify(&proof, &other.public()));
+		assert!(!Pair::pop_verify(&other.pop_prove(), &pair.public()));
+
+		// proofs and signatures of the public key are not interchangeable.
+		let signature = pair.sign(pair.public().as_ref());
+		assert!(!Pair::pop_verify(&signature, &pair.public()));
+		assert!(!Pair::verify(&proof, pair.public().as_ref(), &pair.public()));
+	}
+}
@@ -34,12 +34,14 @@ use crate::crypto::{
 #[cfg(feature = "full_crypto")]
 use crate::crypto::{DeriveJunction, Pair as TraitPair, SecretStringError};
 #[cfg(feature = "std")]
-use bip39::{Language, Mnemonic, MnemonicType};
+use bip39::{Language, Mnemonic, MnemonicType, Seed as Bip39Seed};
 #[cfg(feature = "full_crypto")]
 use core::convert::TryFrom;
 #[cfg(feature = "full_crypto")]
 use ed25519_dalek::{Signer as _, Verifier as _};
 #[cfg(feature = "std")]
+use hmac::{Hmac, Mac, NewMac};
+#[cfg(feature = "std")]
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use sp_runtime_interface::pass_by::PassByInner;
 use sp_std::ops::Deref;
@@ -398,6 +400,25 @@ impl Public {
 	pub fn as_array_ref(&self) -> &[u8; 32] {
 		self.as_ref()
 	}
+
+	/// The public key of the BIP39 `phrase`, derived along the BIP44 `path` by the BIP32-Ed25519
+	/// derivation of the Ledger apps.
+	///
+	/// Every index of `path` must be hardened. The key is the one of the account shown by a device
+	/// set up with the same phrase and password, e.g. at `m/44'/354'/0'/0'/0'` for Polkadot.
+	#[cfg(feature = "std")]
+	pub fn from_ledger_phrase(
+		phrase: &str,
+		password: Option<&str>,
+		path: &[u32],
+	) -> Result<Public, SecretStringError> {
+		let secret = ledger_secret(phrase, password, path)?;
+		// The derived secret is already an expanded key, `kL` being the clamped scalar, so it must
+		// not be hashed again like a seed. Clamping `kL` again leaves it unchanged.
+		let expanded = ed25519_dalek::ExpandedSecretKey::from_bytes(&secret)
+			.expect("the secret is 64 bytes long; qed");
+		Ok(Public(ed25519_dalek::PublicKey::from(&expanded).to_bytes()))
+	}
 }
 
 impl TraitPublic for Public {
@@ -440,6 +461,109 @@ fn derive_hard_junction(secret_seed: &Seed, cc: &[u8; 32]) -> Seed {
 	})
 }
 
+/// The flag of the hardened indices of a BIP44 path.
+#[cfg(feature = "std")]
+pub const BIP44_HARDENED: u32 = 0x8000_0000;
+
+/// The key of the HMACs of the master key of the BIP32-Ed25519 derivation.
+#[cfg(feature = "std")]
+const LEDGER_MASTER_KEY: &[u8] = b"ed25519 seed";
+
+/// HMAC-SHA512 of the concatenation of `data`, with `key`.
+#[cfg(feature = "std")]
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+	let mut mac =
+		Hmac::<sha2::Sha512>::new_varkey(key).expect("HMAC accepts keys of any length; qed");
+	data.iter().for_each(|data| mac.update(data));
+	let mut res = [0u8; 64];
+	res.copy_from_slice(&mac.finalize().into_bytes());
+	res
+}
+
+/// The extended master key of the BIP32-Ed25519 derivation of a BIP39 seed, i.e. the 64 bytes of
+/// the key followed by the 32 bytes of the chain code.
+#[cfg(feature = "std")]
+fn ledger_master(seed: &[u8]) -> [u8; 96] {
+	let mut mac = Hmac::<sha2::Sha256>::new_varkey(LEDGER_MASTER_KEY)
+		.expect("HMAC accepts keys of any length; qed");
+	mac.update(&[1]);
+	mac.update(seed);
+	let chain_code = mac.finalize().into_bytes();
+
+	let mut key = hmac_sha512(LEDGER_MASTER_KEY, &[seed]);
+	// The third highest bit of the last byte of the key must be cleared.
+	while key[31] & 0b0010_0000 != 0 {
+		key = hmac_sha512(LEDGER_MASTER_KEY, &[&key]);
+	}
+	key[0] &= 0b1111_1000;
+	key[31] &= 0b0111_1111;
+	key[31] |= 0b0100_0000;
+
+	let mut extended = [0u8; 96];
+	extended[..64].copy_from_slice(&key);
+	extended[64..].copy_from_slice(&chain_code);
+	extended
+}
+
+/// Derive the hardened child `index` of an extended key of the BIP32-Ed25519 derivation.
+#[cfg(feature = "std")]
+fn ledger_derive(extended: &[u8; 96], index: u32) -> [u8; 96] {
+	let (kl, kr, chain_code) = (&extended[..32], &extended[32..64], &extended[64..]);
+	let index = index.to_le_bytes();
+	let z = hmac_sha512(chain_code, &[&[0], kl, kr, &index]);
+
+	let mut child = [0u8; 96];
+	// The left half is `kl + 8 * z[..28]`, the right half `kr + z[32..]`, modulo `2^256`.
+	let mut zl8 = [0u8; 32];
+	let mut carry = 0u16;
+	for i in 0..28 {
+		let v = (z[i] as u16) << 3 | carry;
+		zl8[i] = v as u8;
+		carry = v >> 8;
+	}
+	zl8[28] = carry as u8;
+	add_le(&mut child[..32], kl, &zl8);
+	add_le(&mut child[32..64], kr, &z[32..]);
+	child[64..].copy_from_slice(&hmac_sha512(chain_code, &[&[1], kl, kr, &index])[32..]);
+	child
+}
+
+/// Write the sum of the little endian numbers `a` and `b` to `res`, modulo `2^(8 * res.len())`.
+#[cfg(feature = "std")]
+fn add_le(res: &mut [u8], a: &[u8], b: &[u8]) {
+	let mut carry = 0u16;
+	for i in 0..res.len() {
+		let v = a[i] as u16 + b[i] as u16 + carry;
+		res[i] = v as u8;
+		carry = v >> 8;
+	}
+}
+
+/// The extended secret key, i.e. the scalar `kL` followed by the nonce `kR`, of the BIP39
+/// `phrase` derived along the BIP44 `path` by the BIP32-Ed25519 derivation of the Ledger apps.
+///
+/// Every index of `path` must be hardened.
+#[cfg(feature = "std")]
+pub(crate) fn ledger_secret(
+	phrase: &str,
+	password: Option<&str>,
+	path: &[u32],
+) -> Result<[u8; 64], SecretStringError> {
+	if path.iter().any(|index| index & BIP44_HARDENED == 0) {
+		return Err(SecretStringError::InvalidPath)
+	}
+	let mnemonic = Mnemonic::from_phrase(phrase, Language::English)
+		.map_err(|_| SecretStringError::InvalidPhrase)?;
+	let seed = Bip39Seed::new(&mnemonic, password.unwrap_or(""));
+	let extended = path
+		.iter()
+		.fold(ledger_master(seed.as_bytes()), |extended, index| ledger_derive(&extended, *index));
+
+	let mut secret = [0u8; 64];
+	secret.copy_from_slice(&extended[..64]);
+	Ok(secret)
+}
+
 /// An error when deriving a key.
 #[cfg(feature = "full_crypto")]
 pub enum DeriveError {
@@ -613,6 +737,68 @@ mod test {
 		);
 	}
 
+	fn hardened(path: &[u32]) -> Vec<u32> {
+		path.iter().map(|index| index | BIP44_HARDENED).collect()
+	}
+
+	#[test]
+	fn ledger_derivation_should_work() {
+		let path = hardened(&[44, 354, 0, 0, 0]);
+		let public = Public::from_ledger_phrase(DEV_PHRASE, None, &path).unwrap();
+		assert_eq!(
+			public,
+			Public::from_raw(hex!(
+				"bf7d384c947dc90ddf66dc7d6c7e2893ff0934da256059bccb032294f7a93bc5"
+			)),
+		);
+		assert_eq!(
+			Public::from_ledger_phrase(DEV_PHRASE, Some("password"), &path).unwrap(),
+			Public::from_raw(hex!(
+				"102a7bc8f18b13feabf4729d556904c945c3ba36e9746d2385245243ed48a7fe"
+			)),
+		);
+		let other_account = hardened(&[44, 354, 1, 0, 0]);
+		assert_eq!(
+			Public::from_ledger_phrase(DEV_PHRASE, None, &other_account).unwrap(),
+			Public::from_raw(hex!(
+				"fc9caf579e520a2b628f97858661d2c9be9f35386503a4cbd63d2ef3e16bc548"
+			)),
+		);
+
+		// The derived scalar is not hashed again like a seed.
+		let secret = ledger_secret(DEV_PHRASE, None, &path).unwrap();
+		assert_ne!(public, Pair::from_seed_slice(&secret[..32]).unwrap().public());
+
+		assert_eq!(
+			Public::from_ledger_phrase(DEV_PHRASE, None, &[44, 354, 0, 0, 0]).err(),
+			Some(SecretStringError::InvalidPath),
+		);
+		assert_eq!(
+			Public::from_ledger_phrase("invalid phrase", None, &path).err(),
+			Some(SecretStringError::InvalidPhrase),
+		);
+	}
+
+	#[test]
+	fn ledger_master_key_is_clamped() {
+		let master = ledger_master(&[7u8; 64]);
+		assert_eq!(master[0] & 0b0000_0111, 0);
+		assert_eq!(master[31] & 0b1110_0000, 0b0100_0000);
+	}
+
+	#[test]
+	fn ledger_master_key_should_work() {
+		let mnemonic = Mnemonic::from_phrase(DEV_PHRASE, Language::English).unwrap();
+		let seed = Bip39Seed::new(&mnemonic, "");
+		assert_eq!(
+			ledger_master(seed.as_bytes()).to_vec(),
+			hex!(
+				"90c2d6f93acf769891593dcc6b698be6c876fd632bcdc5c08f82746c8139705ece451cf51283dfe2f7fe38202cab7bc17731c9e1e62c08412d3a51b2506827c8bec2c62799b87c8954bfe7c18f1be3ffa014ba77dedf15c2da13f46a755143d6"
+			)
+			.to_vec(),
+		);
+	}
+
 	#[test]
 	fn seed_and_derive_should_work() {
 		let seed = hex!("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60");
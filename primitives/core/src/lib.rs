@@ -58,6 +58,8 @@ pub mod hexdisplay;
 
 pub mod u32_trait;
 
+#[cfg(feature = "bls-experimental")]
+pub mod bls381;
 mod changes_trie;
 pub mod ecdsa;
 pub mod ed25519;
@@ -47,6 +47,20 @@ pub trait OffchainStorage: Clone + Send + Sync {
 	/// Retrieve a value from storage under given key and prefix.
 	fn get(&self, prefix: &[u8], key: &[u8]) -> Option<Vec<u8>>;
 
+	/// Retrieve at most `count` entries under given prefix whose key starts with `key_prefix`,
+	/// ordered by key, starting after the key `start_after` if any.
+	///
+	/// Returns `None` if the storage can't enumerate its keys, which is the default.
+	fn entries_with_prefix(
+		&self,
+		_prefix: &[u8],
+		_key_prefix: &[u8],
+		_start_after: Option<&[u8]>,
+		_count: usize,
+	) -> Option<Vec<(Vec<u8>, Vec<u8>)>> {
+		None
+	}
+
 	/// Replace the value in storage if given old_value matches the current one.
 	///
 	/// Returns `true` if the value has been set and false otherwise.
@@ -63,6 +63,28 @@ impl OffchainStorage for InMemOffchainStorage {
 		self.storage.get(&key).cloned()
 	}
 
+	fn entries_with_prefix(
+		&self,
+		prefix: &[u8],
+		key_prefix: &[u8],
+		start_after: Option<&[u8]>,
+		count: usize,
+	) -> Option<Vec<(Vec<u8>, Vec<u8>)>> {
+		let mut entries = self
+			.storage
+			.iter()
+			.filter_map(|(key, value)| {
+				let key = key.strip_prefix(prefix)?;
+				let selected = key.starts_with(key_prefix) &&
+					start_after.map_or(true, |start_after| key > start_after);
+				selected.then(|| (key.to_vec(), value.clone()))
+			})
+			.collect::<Vec<_>>();
+		entries.sort();
+		entries.truncate(count);
+		Some(entries)
+	}
+
 	fn compare_and_set(
 		&mut self,
 		prefix: &[u8],
@@ -106,6 +106,18 @@ impl OffchainStorage for TestPersistentOffchainDB {
 		self.persistent.read().get(prefix, key)
 	}
 
+	fn entries_with_prefix(
+		&self,
+		prefix: &[u8],
+		key_prefix: &[u8],
+		start_after: Option<&[u8]>,
+		count: usize,
+	) -> Option<Vec<(Vec<u8>, Vec<u8>)>> {
+		self.persistent
+			.read()
+			.entries_with_prefix(prefix, key_prefix, start_after, count)
+	}
+
 	fn compare_and_set(
 		&mut self,
 		prefix: &[u8],
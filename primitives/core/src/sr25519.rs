@@ -616,6 +616,22 @@ impl Pair {
 		(Pair(kp), mini_key.to_bytes())
 	}
 
+	/// Make a new key pair from a BIP39 `phrase`, derived along the BIP44 `path` by the
+	/// BIP32-Ed25519 derivation of the Ledger apps.
+	///
+	/// Every index of `path` must be hardened. Like the Ledger apps, the sr25519 secret key is
+	/// the derived ed25519 extended secret key, its scalar divided by the cofactor.
+	pub fn from_ledger_phrase(
+		phrase: &str,
+		password: Option<&str>,
+		path: &[u32],
+	) -> Result<Pair, SecretStringError> {
+		let secret = crate::ed25519::ledger_secret(phrase, password, path)?;
+		SecretKey::from_ed25519_bytes(&secret)
+			.map(Into::into)
+			.map_err(|_| SecretStringError::InvalidSeed)
+	}
+
 	/// Verify a signature on a message. Returns `true` if the signature is good.
 	/// Supports old 0.1.1 deprecated signatures and should be used only for backward
 	/// compatibility.
@@ -744,6 +760,40 @@ mod test {
 		);
 	}
 
+	#[test]
+	fn ledger_derivation_should_work() {
+		use crate::ed25519::BIP44_HARDENED;
+		let hardened = |path: &[u32]| -> Vec<u32> {
+			path.iter().map(|index| index | BIP44_HARDENED).collect()
+		};
+
+		let pair =
+			Pair::from_ledger_phrase(DEV_PHRASE, None, &hardened(&[44, 354, 0, 0, 0])).unwrap();
+		assert_eq!(
+			pair.public(),
+			Public::from_raw(hex!(
+				"ca8671ccff676bd961285d535ec2f93b5ac64787c210ac8e69481316152bda2a"
+			)),
+		);
+		let kusama =
+			Pair::from_ledger_phrase(DEV_PHRASE, None, &hardened(&[44, 434, 0, 0, 0])).unwrap();
+		assert_eq!(
+			kusama.public(),
+			Public::from_raw(hex!(
+				"7a1a1671391d59f583955af9f924c648d9583116704a37be354dd676e6b00a65"
+			)),
+		);
+
+		let message = b"Something important";
+		let signature = pair.sign(&message[..]);
+		assert!(Pair::verify(&signature, &message[..], &pair.public()));
+
+		assert_eq!(
+			Pair::from_ledger_phrase(DEV_PHRASE, None, &[44, 354, 0, 0, 0]).err(),
+			Some(SecretStringError::InvalidPath),
+		);
+	}
+
 	#[test]
 	fn default_address_should_be_used() {
 		assert_eq!(
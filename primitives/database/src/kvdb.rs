@@ -115,4 +115,21 @@ impl<D: KeyValueDB, H: Clone + AsRef<[u8]>> Database<H> for DbAdapter<D> {
 	fn contains(&self, col: ColumnId, key: &[u8]) -> bool {
 		handle_err(self.0.has_key(col, key))
 	}
+
+	fn iter_with_prefix(
+		&self,
+		col: ColumnId,
+		prefix: &[u8],
+		start_after: Option<&[u8]>,
+		f: &mut dyn FnMut(&[u8], &[u8]) -> bool,
+	) -> bool {
+		// `KeyValueDB` can't seek, the keys up to `start_after` are skipped without being
+		// collected.
+		self.0
+			.iter_with_prefix(col, prefix)
+			.skip_while(|(key, _)| start_after.map_or(false, |start| &key[..] <= start))
+			.take_while(|(key, value)| f(&key[..], &value[..]))
+			.for_each(drop);
+		true
+	}
 }
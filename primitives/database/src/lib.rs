@@ -104,6 +104,21 @@ pub trait Database<H: Clone + AsRef<[u8]>>: Send + Sync {
 		self.get(col, key).map(|v| f(&v));
 	}
 
+	/// Call `f` with each key and value of `col` whose key starts with `prefix`, in key order,
+	/// starting after the key `start_after` if any, until `f` returns `false`.
+	///
+	/// Returns `false` without calling `f` if the database doesn't support iteration, which is
+	/// the default.
+	fn iter_with_prefix(
+		&self,
+		_col: ColumnId,
+		_prefix: &[u8],
+		_start_after: Option<&[u8]>,
+		_f: &mut dyn FnMut(&[u8], &[u8]) -> bool,
+	) -> bool {
+		false
+	}
+
 	/// Check if database supports internal ref counting for state data.
 	///
 	/// For backwards compatibility returns `false` by default.
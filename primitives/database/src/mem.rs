@@ -73,6 +73,31 @@ where
 		let s = self.0.read();
 		s.get(&col).and_then(|c| c.get(key).map(|(_, v)| v.clone()))
 	}
+
+	fn iter_with_prefix(
+		&self,
+		col: ColumnId,
+		prefix: &[u8],
+		start_after: Option<&[u8]>,
+		f: &mut dyn FnMut(&[u8], &[u8]) -> bool,
+	) -> bool {
+		let s = self.0.read();
+		let mut entries = s
+			.get(&col)
+			.into_iter()
+			.flatten()
+			.filter(|(key, _)| {
+				key.starts_with(prefix) && start_after.map_or(true, |start| &key[..] > start)
+			})
+			.collect::<Vec<_>>();
+		entries.sort_by(|a, b| a.0.cmp(b.0));
+		for (key, (_, value)) in entries {
+			if !f(key, value) {
+				break
+			}
+		}
+		true
+	}
 }
 
 impl MemDb {
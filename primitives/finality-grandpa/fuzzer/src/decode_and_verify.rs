@@ -0,0 +1,52 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Running
+//! Running this fuzzer can be done with `cargo hfuzz run decode_and_verify`. `honggfuzz` CLI
+//! options can be used by setting `HFUZZ_RUN_ARGS`, such as `-n 4` to use 4 threads.
+//!
+//! # Debugging a panic
+//! Once a panic is found, it can be debugged with
+//! `cargo hfuzz run-debug decode_and_verify hfuzz_workspace/decode_and_verify/*.fuzz`.
+//!
+//! # More information
+//! Justifications are received from untrusted sources, so verifying arbitrary bytes must never
+//! panic. As the fuzzer cannot forge signatures, no input is expected to verify successfully.
+
+use honggfuzz::fuzz;
+use sp_finality_grandpa::{justification::GrandpaJustification, AuthorityId, AuthorityList};
+use sp_keyring::Ed25519Keyring;
+use sp_runtime::testing::Header;
+
+fn main() {
+	let authorities: AuthorityList = [Ed25519Keyring::Alice, Ed25519Keyring::Bob]
+		.iter()
+		.map(|k| (AuthorityId::from(k.public()), 1))
+		.collect();
+
+	loop {
+		fuzz!(|data: &[u8]| {
+			let justification =
+				match <GrandpaJustification<Header> as codec::Decode>::decode(&mut &*data) {
+					Ok(justification) => justification,
+					Err(_) => return,
+				};
+
+			assert!(justification.verify(1, &authorities).is_err());
+		})
+	}
+}
@@ -0,0 +1,127 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Running
+//! Running this fuzzer can be done with `cargo hfuzz run verify_commit`. `honggfuzz` CLI options
+//! can be used by setting `HFUZZ_RUN_ARGS`, such as `-n 4` to use 4 threads.
+//!
+//! # Debugging a panic
+//! Once a panic is found, it can be debugged with
+//! `cargo hfuzz run-debug verify_commit hfuzz_workspace/verify_commit/*.fuzz`.
+//!
+//! # More information
+//! This fuzzer builds properly signed justifications from the fuzzed input, varying which
+//! authorities precommit, the targets of their precommits and the headers included in the
+//! ancestry proof. It then checks that a justification verifies if and only if a supermajority
+//! precommitted and the ancestry proof contains exactly the routes to the precommit targets.
+
+use finality_grandpa::{Commit, Message, Precommit, SignedPrecommit};
+use honggfuzz::fuzz;
+use sp_finality_grandpa::{
+	justification::GrandpaJustification, localized_payload, AuthorityId, AuthorityList,
+};
+use sp_keyring::Ed25519Keyring;
+use sp_runtime::{testing::Header, traits::Header as _};
+
+const SET_ID: u64 = 1;
+const ROUND: u64 = 1;
+
+const AUTHORITIES: [Ed25519Keyring; 4] =
+	[Ed25519Keyring::Alice, Ed25519Keyring::Bob, Ed25519Keyring::Charlie, Ed25519Keyring::Dave];
+
+/// The number of blocks on top of the commit target a precommit may target.
+const MAX_DEPTH: u8 = 8;
+
+fn main() {
+	let authorities: AuthorityList =
+		AUTHORITIES.iter().map(|k| (AuthorityId::from(k.public()), 1)).collect();
+
+	// the commit target is block 1, the headers after it may be needed as ancestry proof.
+	let mut chain = vec![Header::new(
+		0,
+		Default::default(),
+		Default::default(),
+		Default::default(),
+		Default::default(),
+	)];
+	for number in 1..=(MAX_DEPTH as u64 + 2) {
+		let parent_hash = chain.last().unwrap().hash();
+		chain.push(Header::new(
+			number,
+			Default::default(),
+			Default::default(),
+			parent_hash,
+			Default::default(),
+		));
+	}
+
+	loop {
+		fuzz!(|data: (u8, [u8; 4], Option<u8>, bool)| {
+			let (signers, depths, omitted, extra) = data;
+
+			let mut precommits = Vec::new();
+			let mut max_target = 1;
+			for (i, keyring) in AUTHORITIES.iter().enumerate() {
+				if signers & (1 << i) == 0 {
+					continue
+				}
+				let target = &chain[1 + (depths[i] % MAX_DEPTH) as usize];
+				max_target = max_target.max(target.number as usize);
+				let precommit =
+					Precommit { target_hash: target.hash(), target_number: target.number };
+				let payload = localized_payload(
+					ROUND,
+					SET_ID,
+					&Message::<_, u64>::Precommit(precommit.clone()),
+				);
+				precommits.push(SignedPrecommit {
+					precommit,
+					signature: keyring.sign(&payload).into(),
+					id: keyring.public().into(),
+				});
+			}
+
+			let mut votes_ancestries = chain[2..=max_target].to_vec();
+			let mut valid_ancestry = true;
+			if let Some(omitted) = omitted {
+				if !votes_ancestries.is_empty() {
+					votes_ancestries.remove(omitted as usize % votes_ancestries.len());
+					valid_ancestry = false;
+				}
+			}
+			if extra {
+				votes_ancestries.push(chain[MAX_DEPTH as usize + 2].clone());
+				valid_ancestry = false;
+			}
+
+			let justification = GrandpaJustification::<Header> {
+				round: ROUND,
+				commit: Commit { target_hash: chain[1].hash(), target_number: 1, precommits },
+				votes_ancestries,
+			};
+
+			// the supermajority of four authorities with equal weights is three.
+			let expected = (signers & 0b1111).count_ones() >= 3 && valid_ancestry;
+			assert_eq!(
+				justification.verify(SET_ID, &authorities).is_ok(),
+				expected,
+				"unexpected verification result for {:?}",
+				justification,
+			);
+		})
+	}
+}
@@ -0,0 +1,352 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verification of GRANDPA justifications.
+//!
+//! The verification only depends on the authority set which is expected to have finalized the
+//! block and on the headers included in the justification. It is therefore usable from within
+//! the runtime, e.g. by light clients of other chains implemented as pallets.
+
+use crate::{AuthorityId, AuthorityList, AuthoritySignature, RoundNumber, SetId};
+use codec::{Decode, Encode};
+use grandpa::voter_set::VoterSet;
+use sp_runtime::{traits::Header as HeaderT, RuntimeDebug};
+use sp_std::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	prelude::*,
+};
+
+/// A GRANDPA commit for the block with the given header type.
+pub type Commit<Header> = grandpa::Commit<
+	<Header as HeaderT>::Hash,
+	<Header as HeaderT>::Number,
+	AuthoritySignature,
+	AuthorityId,
+>;
+
+/// A GRANDPA justification for block finality, it includes a commit message and
+/// an ancestry proof including all headers routing all precommit target blocks
+/// to the commit target block.
+///
+/// The encoding is the same as the one of the justifications stored by the client.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug)]
+pub struct GrandpaJustification<Header: HeaderT> {
+	/// The round in which the commit was made.
+	pub round: RoundNumber,
+	/// The commit finalizing the target block.
+	pub commit: Commit<Header>,
+	/// The headers routing all precommit targets to the commit target.
+	pub votes_ancestries: Vec<Header>,
+}
+
+/// The reason a justification failed to verify.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Error {
+	/// The justification could not be decoded.
+	JustificationDecode,
+	/// The justification finalizes a different block than expected.
+	InvalidTarget,
+	/// The authority set is empty or all of its authorities have a weight of zero.
+	InvalidAuthorityList,
+	/// The precommits do not reach a supermajority for the commit target.
+	InvalidCommit,
+	/// A precommit is not signed by the authority it claims to be from.
+	InvalidPrecommitSignature,
+	/// The route from a precommit target to the commit target is not part of the ancestry.
+	InvalidPrecommitAncestry,
+	/// The ancestry contains headers which are not on the route of any precommit.
+	UnusedAncestry,
+}
+
+impl<Header: HeaderT> GrandpaJustification<Header>
+where
+	Header::Number: grandpa::BlockNumberOps,
+{
+	/// The target block number and hash that this justification proves finality for.
+	pub fn target(&self) -> (Header::Number, Header::Hash) {
+		(self.commit.target_number, self.commit.target_hash)
+	}
+
+	/// Decode a GRANDPA justification and validate the commit and the votes'
+	/// ancestry proofs finalize the given block.
+	pub fn decode_and_verify_finalizes(
+		encoded: &[u8],
+		finalized_target: (Header::Hash, Header::Number),
+		set_id: SetId,
+		voters: &VoterSet<AuthorityId>,
+	) -> Result<Self, Error> {
+		let justification = Self::decode(&mut &*encoded).map_err(|_| Error::JustificationDecode)?;
+
+		if (justification.commit.target_hash, justification.commit.target_number) !=
+			finalized_target
+		{
+			return Err(Error::InvalidTarget)
+		}
+
+		justification.verify_with_voter_set(set_id, voters).map(|_| justification)
+	}
+
+	/// Validate the commit and the votes' ancestry proofs.
+	pub fn verify(&self, set_id: SetId, authorities: &AuthorityList) -> Result<(), Error> {
+		let voters =
+			VoterSet::new(authorities.iter().cloned()).ok_or(Error::InvalidAuthorityList)?;
+
+		self.verify_with_voter_set(set_id, &voters)
+	}
+
+	/// Validate the commit and the votes' ancestry proofs.
+	pub fn verify_with_voter_set(
+		&self,
+		set_id: SetId,
+		voters: &VoterSet<AuthorityId>,
+	) -> Result<(), Error> {
+		use grandpa::Chain;
+
+		let ancestry_chain = AncestryChain::<Header>::new(&self.votes_ancestries);
+
+		match grandpa::validate_commit(&self.commit, voters, &ancestry_chain) {
+			Ok(ref result) if result.ghost().is_some() => {},
+			_ => return Err(Error::InvalidCommit),
+		}
+
+		let mut buf = Vec::new();
+		let mut visited_hashes = BTreeSet::new();
+		for signed in self.commit.precommits.iter() {
+			if !crate::check_message_signature_with_buffer(
+				&grandpa::Message::Precommit(signed.precommit.clone()),
+				&signed.id,
+				&signed.signature,
+				self.round,
+				set_id,
+				&mut buf,
+			) {
+				return Err(Error::InvalidPrecommitSignature)
+			}
+
+			if self.commit.target_hash == signed.precommit.target_hash {
+				continue
+			}
+
+			match ancestry_chain.ancestry(self.commit.target_hash, signed.precommit.target_hash) {
+				Ok(route) => {
+					// ancestry starts from parent hash but the precommit target hash has been
+					// visited
+					visited_hashes.insert(signed.precommit.target_hash);
+					visited_hashes.extend(route);
+				},
+				_ => return Err(Error::InvalidPrecommitAncestry),
+			}
+		}
+
+		let ancestry_hashes = self.votes_ancestries.iter().map(|h: &Header| h.hash()).collect();
+
+		if visited_hashes != ancestry_hashes {
+			return Err(Error::UnusedAncestry)
+		}
+
+		Ok(())
+	}
+}
+
+/// A utility trait implementing `grandpa::Chain` using a given set of headers.
+/// This is useful when validating commits, using the given set of headers to
+/// verify a valid ancestry route to the target commit block.
+struct AncestryChain<Header: HeaderT> {
+	ancestry: BTreeMap<Header::Hash, Header>,
+}
+
+impl<Header: HeaderT> AncestryChain<Header> {
+	fn new(ancestry: &[Header]) -> AncestryChain<Header> {
+		let ancestry = ancestry.iter().cloned().map(|h: Header| (h.hash(), h)).collect();
+
+		AncestryChain { ancestry }
+	}
+}
+
+impl<Header: HeaderT> grandpa::Chain<Header::Hash, Header::Number> for AncestryChain<Header>
+where
+	Header::Number: grandpa::BlockNumberOps,
+{
+	fn ancestry(
+		&self,
+		base: Header::Hash,
+		block: Header::Hash,
+	) -> Result<Vec<Header::Hash>, grandpa::Error> {
+		let mut route = Vec::new();
+		let mut current_hash = block;
+		loop {
+			if current_hash == base {
+				break
+			}
+			match self.ancestry.get(&current_hash) {
+				Some(current_header) => {
+					current_hash = *current_header.parent_hash();
+					route.push(current_hash);
+				},
+				_ => return Err(grandpa::Error::NotDescendent),
+			}
+		}
+		route.pop(); // remove the base
+
+		Ok(route)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_keyring::Ed25519Keyring;
+	use sp_runtime::testing::Header;
+
+	const SET_ID: SetId = 1;
+	const ROUND: RoundNumber = 42;
+
+	fn authorities() -> AuthorityList {
+		[Ed25519Keyring::Alice, Ed25519Keyring::Bob, Ed25519Keyring::Charlie]
+			.iter()
+			.map(|k| (AuthorityId::from(k.public()), 1))
+			.collect()
+	}
+
+	/// A chain of `len` headers on top of a genesis header, starting with the genesis header.
+	fn chain(len: u64) -> Vec<Header> {
+		let mut headers = vec![Header::new(
+			0,
+			Default::default(),
+			Default::default(),
+			Default::default(),
+			Default::default(),
+		)];
+		for number in 1..=len {
+			let parent_hash = headers.last().unwrap().hash();
+			headers.push(Header::new(
+				number,
+				Default::default(),
+				Default::default(),
+				parent_hash,
+				Default::default(),
+			));
+		}
+		headers
+	}
+
+	fn signed_precommit(
+		keyring: Ed25519Keyring,
+		target: &Header,
+	) -> grandpa::SignedPrecommit<<Header as HeaderT>::Hash, u64, AuthoritySignature, AuthorityId>
+	{
+		let precommit =
+			grandpa::Precommit { target_hash: target.hash(), target_number: target.number };
+		let payload = crate::localized_payload(
+			ROUND,
+			SET_ID,
+			&grandpa::Message::<_, u64>::Precommit(precommit.clone()),
+		);
+		grandpa::SignedPrecommit {
+			precommit,
+			signature: keyring.sign(&payload).into(),
+			id: keyring.public().into(),
+		}
+	}
+
+	/// A justification finalizing `chain[1]`, where Charlie precommits `chain[3]`.
+	fn justification(chain: &[Header]) -> GrandpaJustification<Header> {
+		GrandpaJustification {
+			round: ROUND,
+			commit: grandpa::Commit {
+				target_hash: chain[1].hash(),
+				target_number: 1,
+				precommits: vec![
+					signed_precommit(Ed25519Keyring::Alice, &chain[1]),
+					signed_precommit(Ed25519Keyring::Bob, &chain[1]),
+					signed_precommit(Ed25519Keyring::Charlie, &chain[3]),
+				],
+			},
+			votes_ancestries: vec![chain[3].clone(), chain[2].clone()],
+		}
+	}
+
+	#[test]
+	fn valid_justification_is_accepted() {
+		let chain = chain(3);
+		let justification = justification(&chain);
+		let voters = VoterSet::new(authorities()).unwrap();
+
+		assert_eq!(justification.verify(SET_ID, &authorities()), Ok(()));
+		assert_eq!(
+			GrandpaJustification::<Header>::decode_and_verify_finalizes(
+				&justification.encode(),
+				(chain[1].hash(), 1),
+				SET_ID,
+				&voters,
+			),
+			Ok(justification.clone()),
+		);
+		assert_eq!(
+			GrandpaJustification::<Header>::decode_and_verify_finalizes(
+				&justification.encode(),
+				(chain[2].hash(), 2),
+				SET_ID,
+				&voters,
+			),
+			Err(Error::InvalidTarget),
+		);
+		assert_eq!(
+			GrandpaJustification::<Header>::decode_and_verify_finalizes(
+				&justification.encode()[1..],
+				(chain[1].hash(), 1),
+				SET_ID,
+				&voters,
+			),
+			Err(Error::JustificationDecode),
+		);
+	}
+
+	#[test]
+	fn justification_with_wrong_set_id_is_rejected() {
+		let justification = justification(&chain(3));
+
+		assert_eq!(
+			justification.verify(SET_ID + 1, &authorities()),
+			Err(Error::InvalidPrecommitSignature),
+		);
+		assert_eq!(justification.verify(SET_ID, &vec![]), Err(Error::InvalidAuthorityList));
+	}
+
+	#[test]
+	fn justification_without_supermajority_is_rejected() {
+		let mut justification = justification(&chain(3));
+		justification.commit.precommits.remove(0);
+
+		assert_eq!(justification.verify(SET_ID, &authorities()), Err(Error::InvalidCommit));
+	}
+
+	#[test]
+	fn justification_with_invalid_ancestry_is_rejected() {
+		let chain = chain(4);
+
+		// the route from Charlie's target is incomplete.
+		let mut justification = justification(&chain);
+		justification.votes_ancestries.pop();
+		assert_eq!(justification.verify(SET_ID, &authorities()), Err(Error::InvalidCommit));
+
+		// the ancestry contains a header which is not on the route of any precommit.
+		let mut justification = self::justification(&chain);
+		justification.votes_ancestries.push(chain[4].clone());
+		assert_eq!(justification.verify(SET_ID, &authorities()), Err(Error::UnusedAncestry));
+	}
+}
@@ -35,6 +35,8 @@ use sp_std::{borrow::Cow, vec::Vec};
 #[cfg(feature = "std")]
 use log::debug;
 
+pub mod justification;
+
 /// Key type for GRANDPA module.
 pub const KEY_TYPE: sp_core::crypto::KeyTypeId = sp_application_crypto::key_types::GRANDPA;
 
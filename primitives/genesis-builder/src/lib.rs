@@ -0,0 +1,45 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The genesis builder runtime api.
+//!
+//! It allows to build the genesis storage of a runtime from its wasm blob alone, the genesis
+//! config being given as JSON. The node doesn't need to link the native runtime to generate the
+//! genesis of a chain.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::vec::Vec;
+
+/// The result of building the genesis storage.
+pub type Result = core::result::Result<(), sp_runtime::RuntimeString>;
+
+sp_api::decl_runtime_apis! {
+	/// The `GenesisBuilder` api trait that provides the genesis config of the runtime.
+	pub trait GenesisBuilder {
+		/// The JSON encoded default genesis config of the runtime.
+		///
+		/// It can be patched by the user, then given to `build_config`.
+		fn create_default_config() -> Vec<u8>;
+
+		/// Build the genesis storage from the given JSON encoded genesis config.
+		///
+		/// The config must be complete, i.e. every field must be given. The storage is written to
+		/// the externalities of the call.
+		fn build_config(json: Vec<u8>) -> Result;
+	}
+}
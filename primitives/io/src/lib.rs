@@ -842,6 +842,121 @@ pub trait Crypto {
 	}
 }
 
+/// Interface that provides experimental BLS crypto functions.
+///
+/// These functions are not part of [`SubstrateHostFunctions`], the nodes supporting them add
+/// [`bls::HostFunctions`] to their executor and store the BLS12-381 keys in a keystore
+/// built with the `bls-experimental` feature.
+#[cfg(feature = "bls-experimental")]
+#[runtime_interface]
+pub trait Bls {
+	/// Returns all `bls381` public keys for the given key id from the keystore.
+	fn bls381_public_keys(&mut self, id: KeyTypeId) -> Vec<sp_core::bls381::Public> {
+		use std::convert::TryFrom;
+
+		let keystore = &***self
+			.extension::<KeystoreExt>()
+			.expect("No `keystore` associated for the current context!");
+		SyncCryptoStore::keys(keystore, id)
+			.unwrap_or_default()
+			.into_iter()
+			.filter(|key| key.0 == sp_core::bls381::CRYPTO_ID)
+			// keys of the other crypto types are filtered out by their size
+			.filter_map(|key| sp_core::bls381::Public::try_from(&key.1[..]).ok())
+			.collect()
+	}
+
+	/// Generate a `bls381` key for the given key type using an optional `seed` and
+	/// store it in the keystore.
+	///
+	/// The `seed` needs to be a valid utf8. Unlike the keys of the other crypto types, the key
+	/// generated from a seed is stored like any other key.
+	///
+	/// Returns the public key.
+	fn bls381_generate(&mut self, id: KeyTypeId, seed: Option<Vec<u8>>) -> sp_core::bls381::Public {
+		let suri = match seed {
+			Some(seed) => String::from_utf8(seed).expect("Seed is valid utf8!"),
+			None => sp_core::bls381::Pair::generate_with_phrase(None).1,
+		};
+		let public = sp_core::bls381::Pair::from_string(&suri, None)
+			.expect("`bls381` seed is valid")
+			.public();
+		let keystore = &***self
+			.extension::<KeystoreExt>()
+			.expect("No `keystore` associated for the current context!");
+		SyncCryptoStore::insert_unknown(keystore, id, &suri, public.as_ref())
+			.expect("`bls381_generate` failed");
+		public
+	}
+
+	/// Sign the given `msg` with the `bls381` key that corresponds to the given public key and
+	/// key type in the keystore.
+	///
+	/// Returns the signature.
+	fn bls381_sign(
+		&mut self,
+		id: KeyTypeId,
+		pub_key: &sp_core::bls381::Public,
+		msg: &[u8],
+	) -> Option<sp_core::bls381::Signature> {
+		use std::convert::TryFrom;
+
+		let keystore = &***self
+			.extension::<KeystoreExt>()
+			.expect("No `keystore` associated for the current context!");
+		SyncCryptoStore::sign_with(keystore, id, &pub_key.into(), msg)
+			.ok()
+			.flatten()
+			.and_then(|sig| sp_core::bls381::Signature::try_from(sig.as_slice()).ok())
+	}
+
+	/// Verify `bls381` signature.
+	///
+	/// Returns `true` when the verification was successful.
+	fn bls381_verify(
+		sig: &sp_core::bls381::Signature,
+		msg: &[u8],
+		pub_key: &sp_core::bls381::Public,
+	) -> bool {
+		sp_core::bls381::Pair::verify(sig, msg, pub_key)
+	}
+
+	/// Aggregate `bls381` signatures into a single one.
+	///
+	/// Returns `None` if there is no signature or if any of them is invalid.
+	fn bls381_aggregate(
+		signatures: Vec<sp_core::bls381::Signature>,
+	) -> Option<sp_core::bls381::Signature> {
+		sp_core::bls381::Signature::aggregate(&signatures)
+	}
+
+	/// Verify a `bls381` signature of `msg` aggregated from the signatures of all the given
+	/// public keys.
+	///
+	/// The public keys must have proven the possession of their secret key, see
+	/// [`Self::bls381_pop_verify`].
+	///
+	/// Returns `true` when the verification was successful.
+	fn bls381_verify_aggregate(
+		sig: &sp_core::bls381::Signature,
+		msg: &[u8],
+		pub_keys: Vec<sp_core::bls381::Public>,
+	) -> bool {
+		sp_core::bls381::Pair::verify_aggregate(sig, msg, &pub_keys)
+	}
+
+	/// Verify a `bls381` proof of possession of the secret key of `pub_key`, see
+	/// [`sp_core::bls381::Pair::pop_verify`].
+	///
+	/// Returns `true` when the verification was successful.
+	fn bls381_pop_verify(
+		proof: &sp_core::bls381::Signature,
+		pub_key: &sp_core::bls381::Public,
+	) -> bool {
+		sp_core::bls381::Pair::pop_verify(proof, pub_key)
+	}
+}
+
 /// Interface that provides functions for hashing with different algorithms.
 #[runtime_interface]
 pub trait Hashing {
@@ -1174,6 +1289,16 @@ pub trait Logging {
 	fn max_level() -> LogLevelFilter {
 		log::max_level().into()
 	}
+
+	/// Returns `true` if the host would display a log message with the given level and target.
+	///
+	/// This takes the per target filtering of the host into account and allows the runtime to
+	/// skip formatting log messages that would be thrown away anyway. It is only called by
+	/// runtimes built with the `log-target-filter` feature of `sp-runtime`, so that the runtimes
+	/// built without it still run on nodes that don't provide this function.
+	fn enabled(level: LogLevel, target: &str) -> bool {
+		log::log_enabled!(target: target, log::Level::from(level))
+	}
 }
 
 #[derive(Encode, Decode)]
@@ -1530,6 +1655,29 @@ mod tests {
 	use sp_state_machine::BasicExternalities;
 	use std::any::TypeId;
 
+	#[test]
+	fn logging_enabled_respects_the_host_target_filter() {
+		if std::env::var("RUN_TEST").is_ok() {
+			sp_tracing::try_init_simple();
+
+			assert!(logging::enabled(LogLevel::Debug, "enabled"));
+			assert!(logging::enabled(LogLevel::Warn, "disabled"));
+			assert!(!logging::enabled(LogLevel::Debug, "disabled"));
+			// The level configured for a target applies to it.
+			assert!(!logging::enabled(LogLevel::Trace, "enabled"));
+		} else {
+			let executable = std::env::current_exe().unwrap();
+			let output = std::process::Command::new(executable)
+				.env("RUN_TEST", "1")
+				.env("RUST_LOG", "warn,enabled=debug")
+				.args(&["--nocapture", "logging_enabled_respects_the_host_target_filter"])
+				.output()
+				.unwrap();
+
+			assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+		}
+	}
+
 	#[test]
 	fn storage_works() {
 		let mut t = BasicExternalities::default();
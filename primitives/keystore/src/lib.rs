@@ -22,6 +22,8 @@ pub mod vrf;
 use crate::vrf::{VRFSignature, VRFTranscriptData};
 use async_trait::async_trait;
 use futures::{executor::block_on, future::join_all};
+#[cfg(feature = "bls-experimental")]
+use sp_core::bls381;
 use sp_core::{
 	crypto::{CryptoTypePublicPair, KeyTypeId},
 	ecdsa, ed25519, sr25519,
@@ -84,6 +86,20 @@ pub trait CryptoStore: Send + Sync {
 		id: KeyTypeId,
 		seed: Option<&str>,
 	) -> Result<ecdsa::Public, Error>;
+	/// Returns all bls381 public keys for the given key type.
+	#[cfg(feature = "bls-experimental")]
+	async fn bls381_public_keys(&self, id: KeyTypeId) -> Vec<bls381::Public>;
+	/// Generate a new bls381 key pair for the given key type and an optional seed.
+	///
+	/// If the given seed is `Some(_)`, the key pair will only be stored in memory.
+	///
+	/// Returns the public key of the generated key pair.
+	#[cfg(feature = "bls-experimental")]
+	async fn bls381_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<bls381::Public, Error>;
 
 	/// Insert a new key. This doesn't require any known of the crypto; but a public key must be
 	/// manually provided.
@@ -257,6 +273,22 @@ pub trait SyncCryptoStore: CryptoStore + Send + Sync {
 	fn ecdsa_generate_new(&self, id: KeyTypeId, seed: Option<&str>)
 		-> Result<ecdsa::Public, Error>;
 
+	/// Returns all bls381 public keys for the given key type.
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_public_keys(&self, id: KeyTypeId) -> Vec<bls381::Public>;
+
+	/// Generate a new bls381 key pair for the given key type and an optional seed.
+	///
+	/// If the given seed is `Some(_)`, the key pair will only be stored in memory.
+	///
+	/// Returns the public key of the generated key pair.
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<bls381::Public, Error>;
+
 	/// Insert a new key. This doesn't require any known of the crypto; but a public key must be
 	/// manually provided.
 	///
@@ -17,6 +17,8 @@
 
 //! Types that should only be used for testing!
 
+#[cfg(feature = "bls-experimental")]
+use sp_core::bls381;
 use sp_core::{
 	crypto::{CryptoTypePublicPair, KeyTypeId, Pair, Public},
 	ecdsa, ed25519, sr25519,
@@ -69,6 +71,15 @@ impl KeyStore {
 				.map(|s| ecdsa::Pair::from_string(s, None).expect("`ecdsa` seed slice is valid"))
 		})
 	}
+
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_key_pair(&self, id: KeyTypeId, pub_key: &bls381::Public) -> Option<bls381::Pair> {
+		self.keys.read().get(&id).and_then(|inner| {
+			inner
+				.get(pub_key.as_slice())
+				.map(|s| bls381::Pair::from_string(s, None).expect("`bls381` seed slice is valid"))
+		})
+	}
 }
 
 #[async_trait]
@@ -113,6 +124,20 @@ impl CryptoStore for KeyStore {
 		SyncCryptoStore::ecdsa_generate_new(self, id, seed)
 	}
 
+	#[cfg(feature = "bls-experimental")]
+	async fn bls381_public_keys(&self, id: KeyTypeId) -> Vec<bls381::Public> {
+		SyncCryptoStore::bls381_public_keys(self, id)
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	async fn bls381_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<bls381::Public, Error> {
+		SyncCryptoStore::bls381_generate_new(self, id, seed)
+	}
+
 	async fn insert_unknown(&self, id: KeyTypeId, suri: &str, public: &[u8]) -> Result<(), ()> {
 		SyncCryptoStore::insert_unknown(self, id, suri, public)
 	}
@@ -167,6 +192,8 @@ impl SyncCryptoStore for KeyStore {
 					v.push(CryptoTypePublicPair(sr25519::CRYPTO_ID, k.clone()));
 					v.push(CryptoTypePublicPair(ed25519::CRYPTO_ID, k.clone()));
 					v.push(CryptoTypePublicPair(ecdsa::CRYPTO_ID, k.clone()));
+					#[cfg(feature = "bls-experimental")]
+					v.push(CryptoTypePublicPair(bls381::CRYPTO_ID, k.clone()));
 					v
 				}))
 			})
@@ -304,6 +331,51 @@ impl SyncCryptoStore for KeyStore {
 		}
 	}
 
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_public_keys(&self, id: KeyTypeId) -> Vec<bls381::Public> {
+		self.keys
+			.read()
+			.get(&id)
+			.map(|keys| {
+				keys.values()
+					.map(|s| {
+						bls381::Pair::from_string(s, None).expect("`bls381` seed slice is valid")
+					})
+					.map(|p| p.public())
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<bls381::Public, Error> {
+		match seed {
+			Some(seed) => {
+				let pair = bls381::Pair::from_string(seed, None)
+					.map_err(|_| Error::ValidationError("Generates a `bls381` pair.".to_owned()))?;
+				self.keys
+					.write()
+					.entry(id)
+					.or_default()
+					.insert(pair.public().to_raw_vec(), seed.into());
+				Ok(pair.public())
+			},
+			None => {
+				let (pair, phrase, _) = bls381::Pair::generate_with_phrase(None);
+				self.keys
+					.write()
+					.entry(id)
+					.or_default()
+					.insert(pair.public().to_raw_vec(), phrase);
+				Ok(pair.public())
+			},
+		}
+	}
+
 	fn insert_unknown(&self, id: KeyTypeId, suri: &str, public: &[u8]) -> Result<(), ()> {
 		self.keys
 			.write()
@@ -357,6 +429,13 @@ impl SyncCryptoStore for KeyStore {
 
 				key_pair.map(|k| k.sign(msg).encode()).map(Ok).transpose()
 			},
+			#[cfg(feature = "bls-experimental")]
+			bls381::CRYPTO_ID => {
+				let key_pair =
+					self.bls381_key_pair(id, &bls381::Public::from_slice(key.1.as_slice()));
+
+				key_pair.map(|k| k.sign(msg).encode()).map(Ok).transpose()
+			},
 			_ => Err(Error::KeyNotSupported(id)),
 		}
 	}
@@ -490,4 +569,23 @@ mod tests {
 			SyncCryptoStore::ecdsa_sign_prehashed(&store, ECDSA, &pair.public(), &msg).unwrap();
 		assert!(res.is_some());
 	}
+
+	#[test]
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_sign_with_works() {
+		use codec::Decode;
+		use sp_core::testing::BLS381;
+
+		let store = KeyStore::new();
+
+		let public = SyncCryptoStore::bls381_generate_new(&store, BLS381, Some("//Alice")).unwrap();
+		assert_eq!(SyncCryptoStore::bls381_public_keys(&store, BLS381), vec![public]);
+
+		let msg = b"this should be signed";
+		let signature = SyncCryptoStore::sign_with(&store, BLS381, &public.into(), &msg[..])
+			.unwrap()
+			.unwrap();
+		let signature = bls381::Signature::decode(&mut &signature[..]).unwrap();
+		assert!(bls381::Pair::verify(&signature, &msg[..], &public));
+	}
 }
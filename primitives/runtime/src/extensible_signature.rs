@@ -0,0 +1,286 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! [`MultiSignature`] and [`MultiSigner`] extended with signature schemes defined by the runtime.
+//!
+//! The additional schemes are given as a single type implementing [`Verify`], for accounts of
+//! type [`AccountId32`]. A runtime supporting several additional schemes wraps them in its own
+//! enum, dispatching the verification to the matching scheme.
+//!
+//! The builtin schemes are encoded as in [`MultiSignature`] and [`MultiSigner`], so extending
+//! the signature type of a runtime keeps the existing signed extrinsics valid.
+
+use crate::{
+	traits::{IdentifyAccount, Lazy, Verify},
+	AccountId32, MultiSignature, MultiSigner,
+};
+use codec::{Decode, Encode};
+use sp_core::{ecdsa, ed25519, sr25519};
+use sp_std::convert::TryFrom;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Signature of any builtin signature scheme, or of the additional scheme `S`.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Clone, Encode, Decode, crate::RuntimeDebug, scale_info::TypeInfo)]
+pub enum ExtensibleMultiSignature<S> {
+	/// An Ed25519 signature.
+	Ed25519(ed25519::Signature),
+	/// An Sr25519 signature.
+	Sr25519(sr25519::Signature),
+	/// An ECDSA/SECP256k1 signature.
+	Ecdsa(ecdsa::Signature),
+	/// A signature of the additional scheme.
+	Custom(S),
+}
+
+/// Public key of any builtin signature scheme, or of an additional one.
+#[derive(
+	Eq, PartialEq, Ord, PartialOrd, Clone, Encode, Decode, crate::RuntimeDebug, scale_info::TypeInfo,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ExtensibleMultiSigner<P> {
+	/// An Ed25519 identity.
+	Ed25519(ed25519::Public),
+	/// An Sr25519 identity.
+	Sr25519(sr25519::Public),
+	/// An SECP256k1/ECDSA identity (actually, the Blake2 hash of the compressed pub key).
+	Ecdsa(ecdsa::Public),
+	/// An identity of the additional scheme.
+	Custom(P),
+}
+
+impl<S> From<MultiSignature> for ExtensibleMultiSignature<S> {
+	fn from(x: MultiSignature) -> Self {
+		match x {
+			MultiSignature::Ed25519(x) => Self::Ed25519(x),
+			MultiSignature::Sr25519(x) => Self::Sr25519(x),
+			MultiSignature::Ecdsa(x) => Self::Ecdsa(x),
+		}
+	}
+}
+
+impl<S> TryFrom<ExtensibleMultiSignature<S>> for MultiSignature {
+	type Error = ();
+	fn try_from(m: ExtensibleMultiSignature<S>) -> Result<Self, Self::Error> {
+		match m {
+			ExtensibleMultiSignature::Ed25519(x) => Ok(Self::Ed25519(x)),
+			ExtensibleMultiSignature::Sr25519(x) => Ok(Self::Sr25519(x)),
+			ExtensibleMultiSignature::Ecdsa(x) => Ok(Self::Ecdsa(x)),
+			ExtensibleMultiSignature::Custom(_) => Err(()),
+		}
+	}
+}
+
+impl<S> From<ed25519::Signature> for ExtensibleMultiSignature<S> {
+	fn from(x: ed25519::Signature) -> Self {
+		Self::Ed25519(x)
+	}
+}
+
+impl<S> From<sr25519::Signature> for ExtensibleMultiSignature<S> {
+	fn from(x: sr25519::Signature) -> Self {
+		Self::Sr25519(x)
+	}
+}
+
+impl<S> From<ecdsa::Signature> for ExtensibleMultiSignature<S> {
+	fn from(x: ecdsa::Signature) -> Self {
+		Self::Ecdsa(x)
+	}
+}
+
+impl<S> Default for ExtensibleMultiSignature<S> {
+	fn default() -> Self {
+		Self::Ed25519(Default::default())
+	}
+}
+
+impl<P> From<MultiSigner> for ExtensibleMultiSigner<P> {
+	fn from(x: MultiSigner) -> Self {
+		match x {
+			MultiSigner::Ed25519(x) => Self::Ed25519(x),
+			MultiSigner::Sr25519(x) => Self::Sr25519(x),
+			MultiSigner::Ecdsa(x) => Self::Ecdsa(x),
+		}
+	}
+}
+
+impl<P> TryFrom<ExtensibleMultiSigner<P>> for MultiSigner {
+	type Error = ();
+	fn try_from(m: ExtensibleMultiSigner<P>) -> Result<Self, Self::Error> {
+		match m {
+			ExtensibleMultiSigner::Ed25519(x) => Ok(Self::Ed25519(x)),
+			ExtensibleMultiSigner::Sr25519(x) => Ok(Self::Sr25519(x)),
+			ExtensibleMultiSigner::Ecdsa(x) => Ok(Self::Ecdsa(x)),
+			ExtensibleMultiSigner::Custom(_) => Err(()),
+		}
+	}
+}
+
+impl<P> From<ed25519::Public> for ExtensibleMultiSigner<P> {
+	fn from(x: ed25519::Public) -> Self {
+		Self::Ed25519(x)
+	}
+}
+
+impl<P> From<sr25519::Public> for ExtensibleMultiSigner<P> {
+	fn from(x: sr25519::Public) -> Self {
+		Self::Sr25519(x)
+	}
+}
+
+impl<P> From<ecdsa::Public> for ExtensibleMultiSigner<P> {
+	fn from(x: ecdsa::Public) -> Self {
+		Self::Ecdsa(x)
+	}
+}
+
+impl<P> Default for ExtensibleMultiSigner<P> {
+	fn default() -> Self {
+		Self::Ed25519(Default::default())
+	}
+}
+
+impl<P: AsRef<[u8]>> AsRef<[u8]> for ExtensibleMultiSigner<P> {
+	fn as_ref(&self) -> &[u8] {
+		match *self {
+			Self::Ed25519(ref who) => who.as_ref(),
+			Self::Sr25519(ref who) => who.as_ref(),
+			Self::Ecdsa(ref who) => who.as_ref(),
+			Self::Custom(ref who) => who.as_ref(),
+		}
+	}
+}
+
+impl<P: IdentifyAccount<AccountId = AccountId32>> IdentifyAccount for ExtensibleMultiSigner<P> {
+	type AccountId = AccountId32;
+	fn into_account(self) -> AccountId32 {
+		match self {
+			Self::Ed25519(who) => MultiSigner::Ed25519(who).into_account(),
+			Self::Sr25519(who) => MultiSigner::Sr25519(who).into_account(),
+			Self::Ecdsa(who) => MultiSigner::Ecdsa(who).into_account(),
+			Self::Custom(who) => who.into_account(),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl<P: std::fmt::Display> std::fmt::Display for ExtensibleMultiSigner<P> {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match *self {
+			Self::Ed25519(ref who) => write!(fmt, "ed25519: {}", who),
+			Self::Sr25519(ref who) => write!(fmt, "sr25519: {}", who),
+			Self::Ecdsa(ref who) => write!(fmt, "ecdsa: {}", who),
+			Self::Custom(ref who) => write!(fmt, "custom: {}", who),
+		}
+	}
+}
+
+impl<S> Verify for ExtensibleMultiSignature<S>
+where
+	S: Verify + Clone,
+	S::Signer: IdentifyAccount<AccountId = AccountId32>,
+{
+	type Signer = ExtensibleMultiSigner<S::Signer>;
+	fn verify<L: Lazy<[u8]>>(&self, msg: L, signer: &AccountId32) -> bool {
+		match self {
+			Self::Custom(ref sig) => sig.verify(msg, signer),
+			builtin => MultiSignature::try_from(builtin.clone())
+				.map_or(false, |sig| sig.verify(msg, signer)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::crypto::Pair;
+
+	/// An Ethereum-like scheme: ECDSA over the Keccak-256 hash of the message, the account
+	/// being the Keccak-256 hash of the public key.
+	#[derive(Eq, PartialEq, Clone, Encode, Decode, Debug)]
+	struct KeccakSignature(ecdsa::Signature);
+
+	#[derive(Eq, PartialEq, Clone, Encode, Decode, Debug)]
+	struct KeccakSigner(ecdsa::Public);
+
+	impl IdentifyAccount for KeccakSigner {
+		type AccountId = AccountId32;
+		fn into_account(self) -> AccountId32 {
+			sp_io::hashing::keccak_256(self.0.as_ref()).into()
+		}
+	}
+
+	impl Verify for KeccakSignature {
+		type Signer = KeccakSigner;
+		fn verify<L: Lazy<[u8]>>(&self, mut msg: L, signer: &AccountId32) -> bool {
+			let m = sp_io::hashing::keccak_256(msg.get());
+			match sp_io::crypto::secp256k1_ecdsa_recover_compressed(self.0.as_ref(), &m) {
+				Ok(pubkey) =>
+					&sp_io::hashing::keccak_256(pubkey.as_ref()) ==
+						<dyn AsRef<[u8; 32]>>::as_ref(signer),
+				_ => false,
+			}
+		}
+	}
+
+	type Signature = ExtensibleMultiSignature<KeccakSignature>;
+
+	#[test]
+	fn builtin_schemes_are_verified() {
+		let msg = &b"test-message"[..];
+		let pair = sr25519::Pair::from_seed(&[1; 32]);
+
+		let sig = Signature::from(pair.sign(msg));
+		let signer = ExtensibleMultiSigner::<KeccakSigner>::from(pair.public());
+		assert!(sig.verify(msg, &signer.clone().into_account()));
+		assert!(!sig.verify(&b"other-message"[..], &signer.into_account()));
+
+		// builtin signatures and signers are encoded as `MultiSignature` and `MultiSigner`.
+		let multi_sig = MultiSignature::from(pair.sign(msg));
+		assert_eq!(Signature::from(multi_sig.clone()).encode(), multi_sig.encode());
+		assert_eq!(
+			ExtensibleMultiSigner::<KeccakSigner>::from(pair.public()).encode(),
+			MultiSigner::from(pair.public()).encode(),
+		);
+	}
+
+	#[test]
+	fn custom_scheme_is_verified() {
+		let msg = &b"test-message"[..];
+		let pair = ecdsa::Pair::from_seed(&[1; 32]);
+		let keccak_sig = pair.sign_prehashed(&sp_io::hashing::keccak_256(msg));
+
+		let sig = Signature::Custom(KeccakSignature(keccak_sig.clone()));
+		let signer = ExtensibleMultiSigner::Custom(KeccakSigner(pair.public()));
+		let account = signer.into_account();
+		assert!(sig.verify(msg, &account));
+		assert!(!sig.verify(&b"other-message"[..], &account));
+
+		// the same signature is not valid for the builtin ECDSA scheme.
+		let sig = Signature::Ecdsa(keccak_sig);
+		let signer = ExtensibleMultiSigner::<KeccakSigner>::from(pair.public());
+		assert!(!sig.verify(msg, &signer.into_account()));
+		assert!(!sig.verify(msg, &account));
+
+		assert!(MultiSignature::try_from(sig).is_ok());
+		assert!(MultiSignature::try_from(Signature::Custom(KeccakSignature(Default::default())))
+			.is_err());
+	}
+}
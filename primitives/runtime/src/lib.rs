@@ -55,6 +55,7 @@ use codec::{Decode, Encode};
 use scale_info::TypeInfo;
 
 pub mod curve;
+mod extensible_signature;
 pub mod generic;
 mod multiaddress;
 pub mod offchain;
@@ -70,6 +71,9 @@ pub use crate::runtime_string::*;
 // Re-export Multiaddress
 pub use multiaddress::MultiAddress;
 
+/// Re-export of the signature types extensible with custom signature schemes.
+pub use extensible_signature::{ExtensibleMultiSignature, ExtensibleMultiSigner};
+
 /// Re-export these since they're only "kind of" generic.
 pub use generic::{Digest, DigestItem};
 
@@ -222,6 +226,8 @@ impl BuildStorage for () {
 pub type ConsensusEngineId = [u8; 4];
 
 /// Signature verify that can work with any known signature types..
+///
+/// Runtimes accepting other signature schemes can use [`ExtensibleMultiSignature`] instead.
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Eq, PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub enum MultiSignature {
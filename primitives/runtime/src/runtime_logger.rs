@@ -46,6 +46,13 @@ impl RuntimeLogger {
 }
 
 impl log::Log for RuntimeLogger {
+	#[cfg(feature = "log-target-filter")]
+	fn enabled(&self, metadata: &log::Metadata) -> bool {
+		// Ask the host, as the log level may be configured per target.
+		sp_io::logging::enabled(metadata.level().into(), metadata.target())
+	}
+
+	#[cfg(not(feature = "log-target-filter"))]
 	fn enabled(&self, _: &log::Metadata) -> bool {
 		// The final filtering is done by the host. This is not perfect, as we would still call into
 		// the host for log lines that will be thrown away.
@@ -53,6 +60,12 @@ impl log::Log for RuntimeLogger {
 	}
 
 	fn log(&self, record: &log::Record) {
+		// The `log` macros only check the max level, so we need to check the target ourselves to
+		// not format messages that the host would throw away.
+		if !self.enabled(record.metadata()) {
+			return
+		}
+
 		use sp_std::fmt::Write;
 		let mut w = sp_std::Writer::default();
 		let _ = ::core::write!(&mut w, "{}", record.args());
@@ -66,7 +79,11 @@ impl log::Log for RuntimeLogger {
 #[cfg(test)]
 mod tests {
 	use sp_api::{BlockId, ProvideRuntimeApi};
-	use std::{env, str::FromStr};
+	use std::{
+		env,
+		str::FromStr,
+		sync::atomic::{AtomicUsize, Ordering},
+	};
 	use substrate_test_runtime_client::{
 		runtime::TestAPI, DefaultTestClientBuilderExt, ExecutionStrategy, TestClientBuilder,
 		TestClientBuilderExt,
@@ -99,4 +116,89 @@ mod tests {
 			}
 		}
 	}
+
+	#[test]
+	fn ensure_runtime_logger_respects_host_target_filter() {
+		if env::var("RUN_TEST").is_ok() {
+			sp_tracing::try_init_simple();
+			log::set_max_level(log::LevelFilter::Trace);
+
+			let client = TestClientBuilder::new()
+				.set_execution_strategy(ExecutionStrategy::AlwaysWasm)
+				.build();
+			let runtime_api = client.runtime_api();
+			let block_id = BlockId::Number(0);
+			runtime_api.do_trace_log(&block_id).expect("Logging should not fail");
+		} else {
+			for (directives, should_print) in &[
+				("substrate_test_runtime=trace", true),
+				("substrate_test_runtime=info", false),
+				("info,substrate_test_runtime=trace", true),
+				("trace,substrate_test_runtime=info", false),
+			] {
+				let executable = std::env::current_exe().unwrap();
+				let output = std::process::Command::new(executable)
+					.env("RUN_TEST", "1")
+					.env("RUST_LOG", directives)
+					.args(&["--nocapture", "ensure_runtime_logger_respects_host_target_filter"])
+					.output()
+					.unwrap();
+
+				let output = String::from_utf8(output.stderr).unwrap();
+				assert!(output.contains("Hey I'm runtime") == *should_print);
+			}
+		}
+	}
+
+	#[test]
+	fn ensure_runtime_logger_consults_the_host_target_filter() {
+		if env::var("RUN_TEST").is_ok() {
+			// Rejects the messages of the runtime, counting how often the host is asked about them.
+			struct RejectingLogger {
+				enabled: AtomicUsize,
+				logged: AtomicUsize,
+			}
+
+			impl log::Log for RejectingLogger {
+				fn enabled(&self, metadata: &log::Metadata) -> bool {
+					if metadata.target() == "substrate_test_runtime" {
+						self.enabled.fetch_add(1, Ordering::SeqCst);
+					}
+					false
+				}
+
+				fn log(&self, record: &log::Record) {
+					if record.target() == "substrate_test_runtime" {
+						self.logged.fetch_add(1, Ordering::SeqCst);
+					}
+				}
+
+				fn flush(&self) {}
+			}
+
+			static LOGGER: RejectingLogger =
+				RejectingLogger { enabled: AtomicUsize::new(0), logged: AtomicUsize::new(0) };
+			log::set_logger(&LOGGER).unwrap();
+			log::set_max_level(log::LevelFilter::Trace);
+
+			let client = TestClientBuilder::new()
+				.set_execution_strategy(ExecutionStrategy::AlwaysWasm)
+				.build();
+			let runtime_api = client.runtime_api();
+			let block_id = BlockId::Number(0);
+			runtime_api.do_trace_log(&block_id).expect("Logging should not fail");
+
+			assert!(LOGGER.enabled.load(Ordering::SeqCst) > 0);
+			assert_eq!(LOGGER.logged.load(Ordering::SeqCst), 0);
+		} else {
+			let executable = std::env::current_exe().unwrap();
+			let output = std::process::Command::new(executable)
+				.env("RUN_TEST", "1")
+				.args(&["--nocapture", "ensure_runtime_logger_consults_the_host_target_filter"])
+				.output()
+				.unwrap();
+
+			assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+		}
+	}
 }
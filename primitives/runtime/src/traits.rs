@@ -308,6 +308,26 @@ impl<A, B: From<A>> Convert<A, B> for ConvertInto {
 	}
 }
 
+/// Extensible conversion trait where the destination type is determined by the implementor.
+///
+/// Unlike `Convert`, the outcome is an associated type, which makes it usable in positions where
+/// the destination type must not be a free generic parameter (e.g. when mapping the `Success`
+/// value of an `EnsureOrigin`).
+pub trait Morph<A> {
+	/// The type into which `A` is mutated.
+	type Outcome;
+
+	/// Make conversion.
+	fn morph(a: A) -> Self::Outcome;
+}
+
+impl<T> Morph<T> for Identity {
+	type Outcome = T;
+	fn morph(a: T) -> T {
+		a
+	}
+}
+
 /// Convenience type to work around the highly unergonomic syntax needed
 /// to invoke the functions of overloaded generic traits, in this case
 /// `TryFrom` and `TryInto`.
@@ -87,6 +87,39 @@ pub trait Offence<Offender> {
 	fn slash_fraction(offenders_count: u32, validator_set_count: u32) -> Perbill;
 }
 
+/// The severity of an offence.
+///
+/// Severity is expressed as the fraction of the offender's exposure that the offence slashes,
+/// which is how the severity of an offence is propagated from the offences pallet to the handler
+/// of the offence. It is used to decide how harshly an offender is punished beyond the slash,
+/// e.g. for how long it is disabled.
+#[derive(
+	Clone,
+	Copy,
+	Default,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Encode,
+	Decode,
+	sp_runtime::RuntimeDebug,
+	scale_info::TypeInfo,
+)]
+pub struct OffenceSeverity(pub Perbill);
+
+impl OffenceSeverity {
+	/// The most severe offence, slashing the entire exposure.
+	pub fn max_severity() -> Self {
+		Self(Perbill::one())
+	}
+
+	/// The least severe offence, not slashing anything.
+	pub fn min_severity() -> Self {
+		Self(Perbill::zero())
+	}
+}
+
 /// Errors that may happen on offence reports.
 #[derive(PartialEq, sp_runtime::RuntimeDebug)]
 pub enum OffenceError {
@@ -257,9 +257,9 @@ pub trait Backend<H: Hasher>: sp_std::fmt::Debug {
 	/// Update the whitelist for tracking db reads/writes
 	fn set_whitelist(&self, _: Vec<TrackedStorageKey>) {}
 
-	/// Estimate proof size
+	/// Estimate proof size, `None` if the backend doesn't record a proof.
 	fn proof_size(&self) -> Option<u32> {
-		unimplemented!()
+		None
 	}
 
 	/// Extend storage info for benchmarking db
@@ -37,9 +37,11 @@ pub fn decl_runtime_version_impl(input: proc_macro::TokenStream) -> proc_macro::
 }
 
 fn decl_runtime_version_impl_inner(item: ItemConst) -> Result<TokenStream> {
-	let runtime_version = ParseRuntimeVersion::parse_expr(&*item.expr)?.build(item.expr.span())?;
+	let parsed = ParseRuntimeVersion::parse_expr(&*item.expr)?;
+	let features = parsed.features.clone();
+	let runtime_version = parsed.build(item.expr.span())?;
 	let link_section =
-		generate_emit_link_section_decl(&runtime_version.encode(), "runtime_version");
+		generate_emit_link_section_decl(&runtime_version.encode(), &features, "runtime_version");
 
 	Ok(quote! {
 		#item
@@ -54,6 +56,9 @@ fn decl_runtime_version_impl_inner(item: ItemConst) -> Result<TokenStream> {
 /// One difference from the original definition is the `apis` field. Since we don't actually parse
 /// `apis` from this macro it will always be emitteed as empty. An empty vector can be encoded as
 /// a zero-byte, thus `u8` is sufficient here.
+///
+/// The `features` field is missing as well, since it can only be evaluated by the compiler. Its
+/// encoding is appended to the custom section by the generated code.
 #[derive(Encode)]
 struct RuntimeVersion {
 	spec_name: String,
@@ -73,6 +78,7 @@ struct ParseRuntimeVersion {
 	spec_version: Option<u32>,
 	impl_version: Option<u32>,
 	transaction_version: Option<u32>,
+	features: Option<Expr>,
 }
 
 impl ParseRuntimeVersion {
@@ -122,6 +128,8 @@ impl ParseRuntimeVersion {
 			parse_once(&mut self.impl_version, field_value, Self::parse_num_literal)?;
 		} else if field_name == "transaction_version" {
 			parse_once(&mut self.transaction_version, field_value, Self::parse_num_literal)?;
+		} else if field_name == "features" {
+			parse_once(&mut self.features, field_value, |expr| Ok(expr.clone()))?;
 		} else if field_name == "apis" {
 			// Intentionally ignored
 			//
@@ -182,6 +190,7 @@ impl ParseRuntimeVersion {
 			spec_version,
 			impl_version,
 			transaction_version,
+			features: _,
 		} = self;
 
 		Ok(RuntimeVersion {
@@ -196,13 +205,30 @@ impl ParseRuntimeVersion {
 	}
 }
 
-fn generate_emit_link_section_decl(contents: &[u8], section_name: &str) -> TokenStream {
-	let len = contents.len();
+fn generate_emit_link_section_decl(
+	contents: &[u8],
+	features: &Option<Expr>,
+	section_name: &str,
+) -> TokenStream {
+	let features = match features {
+		Some(features) => quote!( (#features).bits() ),
+		None => quote!(0u32),
+	};
+	// The features are encoded as a little endian `u32`, following the parsed fields.
+	let len = contents.len() + 4;
 	quote! {
 		const _: () = {
+			const FEATURES: u32 = #features;
+
 			#[cfg(not(feature = "std"))]
 			#[link_section = #section_name]
-			static SECTION_CONTENTS: [u8; #len] = [#(#contents),*];
+			static SECTION_CONTENTS: [u8; #len] = [
+				#(#contents,)*
+				FEATURES as u8,
+				(FEATURES >> 8) as u8,
+				(FEATURES >> 16) as u8,
+				(FEATURES >> 24) as u8,
+			];
 		};
 	}
 }
@@ -226,8 +252,10 @@ mod tests {
 		}
 		.encode();
 
+		// the features are only encoded from version 4 of the `Core` api.
 		assert_eq!(
-			sp_version::RuntimeVersion::decode_all(&mut &version_bytes[..]).unwrap(),
+			sp_version::RuntimeVersion::decode_with_version_hint(&mut &version_bytes[..], Some(3))
+				.unwrap(),
 			sp_version::RuntimeVersion {
 				spec_name: "hello".into(),
 				impl_name: "world".into(),
@@ -236,7 +264,28 @@ mod tests {
 				impl_version: 1,
 				apis: Cow::Owned(vec![]),
 				transaction_version: 2,
+				features: sp_version::RuntimeFeatures::empty(),
 			},
 		);
 	}
+
+	#[test]
+	fn version_with_features_can_be_deserialized() {
+		let features = sp_version::RuntimeFeatures::TRY_RUNTIME;
+		let mut version_bytes = RuntimeVersion {
+			spec_name: "hello".to_string(),
+			impl_name: "world".to_string(),
+			authoring_version: 10,
+			spec_version: 265,
+			impl_version: 1,
+			apis: 0,
+			transaction_version: 2,
+		}
+		.encode();
+		version_bytes.extend_from_slice(&features.bits().to_le_bytes());
+
+		let version = sp_version::RuntimeVersion::decode_all(&mut &version_bytes[..]).unwrap();
+		assert_eq!(version.features, features);
+		assert_eq!(version.features.names(), vec!["try-runtime".to_string()]);
+	}
 }
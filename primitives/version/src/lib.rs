@@ -26,7 +26,7 @@ use std::collections::HashSet;
 #[cfg(feature = "std")]
 use std::fmt;
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, Input};
 use scale_info::TypeInfo;
 pub use sp_runtime::create_runtime_str;
 use sp_runtime::RuntimeString;
@@ -65,6 +65,7 @@ pub mod embed;
 /// 	impl_version: 1,
 /// 	apis: RUNTIME_API_VERSIONS,
 /// 	transaction_version: 2,
+/// 	features: sp_version::runtime_features!(),
 /// };
 ///
 /// # const RUNTIME_API_VERSIONS: sp_version::ApisVec = sp_version::create_apis_vec!([]);
@@ -85,6 +86,9 @@ pub mod embed;
 /// - `apis` doesn't have any specific constraints. This is because this information doesn't
 ///   get into the custom section and is not parsed.
 ///
+/// - `features` must be a constant expression, usually [`runtime_features!`]. It is not
+///   parsed, but evaluated by the compiler when emitting the custom section.
+///
 /// # Compilation Target & "std" feature
 ///
 /// This macro assumes it will be used within a runtime. By convention, a runtime crate defines
@@ -118,13 +122,94 @@ macro_rules! create_apis_vec {
 	};
 }
 
+/// The optional features a runtime was compiled with, e.g. `try-runtime`.
+///
+/// Runtimes populate this through [`runtime_features!`], so that it can be checked that a
+/// production runtime doesn't carry testing or benchmarking code.
+#[derive(
+	Clone, Copy, PartialEq, Eq, Encode, Decode, Default, sp_runtime::RuntimeDebug, TypeInfo,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RuntimeFeatures(u32);
+
+impl RuntimeFeatures {
+	/// Compiled with `try-runtime`.
+	pub const TRY_RUNTIME: Self = Self(1 << 0);
+	/// Compiled with `runtime-benchmarks`.
+	pub const RUNTIME_BENCHMARKS: Self = Self(1 << 1);
+
+	/// All known features along with their names, as used in the runtime's `Cargo.toml`.
+	pub const KNOWN: &'static [(Self, &'static str)] =
+		&[(Self::TRY_RUNTIME, "try-runtime"), (Self::RUNTIME_BENCHMARKS, "runtime-benchmarks")];
+
+	/// No features.
+	pub const fn empty() -> Self {
+		Self(0)
+	}
+
+	/// Create from the raw bits.
+	pub const fn from_bits(bits: u32) -> Self {
+		Self(bits)
+	}
+
+	/// The raw bits.
+	pub const fn bits(&self) -> u32 {
+		self.0
+	}
+
+	/// Add the `other` features if `condition` holds.
+	pub const fn with_if(self, condition: bool, other: Self) -> Self {
+		if condition {
+			Self(self.0 | other.0)
+		} else {
+			self
+		}
+	}
+
+	/// Whether all of the `other` features are set.
+	pub const fn contains(&self, other: Self) -> bool {
+		self.0 & other.0 == other.0
+	}
+
+	/// The names of the set features. Unknown features are named by their bit.
+	#[cfg(feature = "std")]
+	pub fn names(&self) -> Vec<String> {
+		(0..32)
+			.map(|bit| Self(1 << bit))
+			.filter(|feature| self.contains(*feature))
+			.map(|feature| {
+				Self::KNOWN
+					.iter()
+					.find(|(known, _)| *known == feature)
+					.map(|(_, name)| name.to_string())
+					.unwrap_or_else(|| format!("unknown-{}", feature.0.trailing_zeros()))
+			})
+			.collect()
+	}
+}
+
+/// The [`RuntimeFeatures`] enabled in the crate this macro is used in.
+///
+/// This is meant to be used in the runtime crate, for the `features` of its [`RuntimeVersion`].
+#[macro_export]
+macro_rules! runtime_features {
+	() => {
+		$crate::RuntimeFeatures::empty()
+			.with_if(cfg!(feature = "try-runtime"), $crate::RuntimeFeatures::TRY_RUNTIME)
+			.with_if(
+				cfg!(feature = "runtime-benchmarks"),
+				$crate::RuntimeFeatures::RUNTIME_BENCHMARKS,
+			)
+	};
+}
+
 /// Runtime version.
 /// This should not be thought of as classic Semver (major/minor/tiny).
 /// This triplet have different semantics and mis-interpretation could cause problems.
 /// In particular: bug fixes should result in an increment of `spec_version` and possibly
 /// `authoring_version`, absolutely not `impl_version` since they change the semantics of the
 /// runtime.
-#[derive(Clone, PartialEq, Eq, Encode, Decode, Default, sp_runtime::RuntimeDebug, TypeInfo)]
+#[derive(Clone, PartialEq, Eq, Encode, Default, sp_runtime::RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub struct RuntimeVersion {
@@ -177,6 +262,63 @@ pub struct RuntimeVersion {
 	///
 	/// It need *not* change when a new module is added or when a dispatchable is added.
 	pub transaction_version: u32,
+
+	/// The optional features the runtime was compiled with.
+	///
+	/// Only encoded by runtimes implementing version 4 or later of the `Core` api, older ones
+	/// decode with no features.
+	#[cfg_attr(feature = "std", serde(default))]
+	pub features: RuntimeFeatures,
+}
+
+/// The id of the `Core` runtime api, i.e. `blake2_64(b"Core")`.
+const CORE_API_ID: ApiId = [223, 106, 203, 104, 153, 7, 96, 155];
+
+/// The first version of the `Core` runtime api encoding the `features` of the [`RuntimeVersion`].
+const CORE_VERSION_WITH_FEATURES: u32 = 4;
+
+impl RuntimeVersion {
+	/// Decode a version, whose encoding depends on the version of the `Core` runtime api.
+	///
+	/// The `Core` version is looked up in the decoded `apis`, unless given by `core_version`. If
+	/// it can't be found either way, the version is decoded as encoded by the latest `Core` api.
+	pub fn decode_with_version_hint<I: Input>(
+		input: &mut I,
+		core_version: Option<u32>,
+	) -> Result<Self, codec::Error> {
+		let spec_name = Decode::decode(input)?;
+		let impl_name = Decode::decode(input)?;
+		let authoring_version = Decode::decode(input)?;
+		let spec_version = Decode::decode(input)?;
+		let impl_version = Decode::decode(input)?;
+		let apis: ApisVec = Decode::decode(input)?;
+		let transaction_version = Decode::decode(input)?;
+
+		let core_version = core_version.or_else(|| {
+			apis.iter().find_map(|(id, version)| (*id == CORE_API_ID).then(|| *version))
+		});
+		let features = match core_version {
+			Some(version) if version < CORE_VERSION_WITH_FEATURES => RuntimeFeatures::empty(),
+			_ => Decode::decode(input)?,
+		};
+
+		Ok(Self {
+			spec_name,
+			impl_name,
+			authoring_version,
+			spec_version,
+			impl_version,
+			apis,
+			transaction_version,
+			features,
+		})
+	}
+}
+
+impl Decode for RuntimeVersion {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+		Self::decode_with_version_hint(input, None)
+	}
 }
 
 #[cfg(feature = "std")]
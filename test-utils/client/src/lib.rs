@@ -86,6 +86,7 @@ pub struct TestClientBuilder<Block: BlockT, ExecutorDispatch, Backend, G: Genesi
 	bad_blocks: BadBlocks<Block>,
 	enable_offchain_indexing_api: bool,
 	no_genesis: bool,
+	stale_import_limit: Option<u32>,
 }
 
 impl<Block: BlockT, ExecutorDispatch, G: GenesisInit> Default
@@ -138,6 +139,7 @@ impl<Block: BlockT, ExecutorDispatch, Backend, G: GenesisInit>
 			bad_blocks: None,
 			enable_offchain_indexing_api: false,
 			no_genesis: false,
+			stale_import_limit: None,
 		}
 	}
 
@@ -207,6 +209,12 @@ impl<Block: BlockT, ExecutorDispatch, Backend, G: GenesisInit>
 		self
 	}
 
+	/// Reject unknown blocks more than `limit` blocks below the last finalized block.
+	pub fn set_stale_import_limit(mut self, limit: u32) -> Self {
+		self.stale_import_limit = Some(limit);
+		self
+	}
+
 	/// Build the test client with the given native executor.
 	pub fn build_with_executor<RuntimeApi>(
 		self,
@@ -253,6 +261,7 @@ impl<Block: BlockT, ExecutorDispatch, Backend, G: GenesisInit>
 			ClientConfig {
 				offchain_indexing_api: self.enable_offchain_indexing_api,
 				no_genesis: self.no_genesis,
+				stale_import_limit: self.stale_import_limit,
 				..Default::default()
 			},
 		)
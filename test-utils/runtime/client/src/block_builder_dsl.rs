@@ -0,0 +1,243 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A concise way to build test-runtime blocks.
+//!
+//! ```ignore
+//! let block = client.build_block(|b| {
+//! 	b.transfer(AccountKeyring::Alice, AccountKeyring::Bob, 10)
+//! 		.transfer(AccountKeyring::Alice, AccountKeyring::Charlie, 20)
+//! 		.authorities_change(&[Sr25519Keyring::Alice])
+//! })?;
+//! ```
+
+use sc_block_builder::{BlockBuilderApi, BlockBuilderProvider};
+use sc_client_api::{backend, StorageProvider};
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{blake2_256, sr25519, storage::StorageKey, ChangesTrieConfiguration};
+use sp_runtime::generic::BlockId;
+use std::collections::HashMap;
+use substrate_test_client::{AccountKeyring, Sr25519Keyring};
+use substrate_test_runtime::{AccountId, Block, Extrinsic, Transfer};
+
+/// The accounts endowed at genesis by the test-runtime client.
+pub const ENDOWED_ACCOUNTS: [AccountKeyring; 3] =
+	[AccountKeyring::Alice, AccountKeyring::Bob, AccountKeyring::Charlie];
+
+/// The extrinsics of a block being built, see [`ClientBlockBuilderExt::build_block`].
+///
+/// Transfer nonces are tracked per sender, starting from their nonce at the parent block.
+pub struct BlockDsl<'a> {
+	nonce_at_parent: &'a dyn Fn(&AccountId) -> u64,
+	nonces: HashMap<AccountId, u64>,
+	extrinsics: Vec<Extrinsic>,
+}
+
+impl<'a> BlockDsl<'a> {
+	/// Create an empty block whose transfer nonces start at `nonce_at_parent`.
+	pub fn new(nonce_at_parent: &'a dyn Fn(&AccountId) -> u64) -> Self {
+		Self { nonce_at_parent, nonces: HashMap::new(), extrinsics: Vec::new() }
+	}
+
+	/// Add the given extrinsic.
+	pub fn push(&mut self, extrinsic: Extrinsic) -> &mut Self {
+		self.extrinsics.push(extrinsic);
+		self
+	}
+
+	/// Add a signed transfer of `amount` from `from` to `to`, using the next nonce of `from`.
+	pub fn transfer(&mut self, from: AccountKeyring, to: AccountKeyring, amount: u64) -> &mut Self {
+		let from = AccountId::from(from);
+		let nonce_at_parent = self.nonce_at_parent;
+		let nonce = self.nonces.entry(from).or_insert_with(|| nonce_at_parent(&from));
+		let transfer = Transfer { from, to: to.into(), amount, nonce: *nonce };
+		*nonce += 1;
+
+		self.push(transfer.into_signed_tx())
+	}
+
+	/// Replace the authority set with the given keys.
+	pub fn authorities_change(&mut self, authorities: &[Sr25519Keyring]) -> &mut Self {
+		let authorities = authorities
+			.iter()
+			.map(|authority| sr25519::Public::from(*authority).into())
+			.collect();
+		self.push(Extrinsic::AuthoritiesChange(authorities))
+	}
+
+	/// Set or clear the value of the given storage key.
+	pub fn storage_change(&mut self, key: &[u8], value: Option<&[u8]>) -> &mut Self {
+		self.push(Extrinsic::StorageChange(key.to_vec(), value.map(|v| v.to_vec())))
+	}
+
+	/// Include the given data in the block, without any effect on the state.
+	pub fn include_data(&mut self, data: &[u8]) -> &mut Self {
+		self.push(Extrinsic::IncludeData(data.to_vec()))
+	}
+
+	/// Store the given data as an indexed transaction.
+	pub fn store(&mut self, data: &[u8]) -> &mut Self {
+		self.push(Extrinsic::Store(data.to_vec()))
+	}
+
+	/// Set the given key in the offchain index.
+	pub fn offchain_index_set(&mut self, key: &[u8], value: &[u8]) -> &mut Self {
+		self.push(Extrinsic::OffchainIndexSet(key.to_vec(), value.to_vec()))
+	}
+
+	/// Clear the given key from the offchain index.
+	pub fn offchain_index_clear(&mut self, key: &[u8]) -> &mut Self {
+		self.push(Extrinsic::OffchainIndexClear(key.to_vec()))
+	}
+
+	/// Update the changes trie configuration.
+	pub fn changes_trie_config_update(
+		&mut self,
+		config: Option<ChangesTrieConfiguration>,
+	) -> &mut Self {
+		self.push(Extrinsic::ChangesTrieConfigUpdate(config))
+	}
+
+	/// The extrinsics added so far.
+	pub fn into_extrinsics(self) -> Vec<Extrinsic> {
+		self.extrinsics
+	}
+}
+
+/// Extension trait to build test-runtime blocks with a [`BlockDsl`], for clients with the backend
+/// `B`.
+pub trait ClientBlockBuilderExt<B> {
+	/// Build a block on top of the best block, with the extrinsics added by `f`.
+	///
+	/// The block is not imported.
+	fn build_block(
+		&self,
+		f: impl for<'a, 'b> FnOnce(&'b mut BlockDsl<'a>) -> &'b mut BlockDsl<'a>,
+	) -> Result<Block, sp_blockchain::Error>;
+
+	/// Build a block on top of `parent`, with the extrinsics added by `f`.
+	///
+	/// The block is not imported.
+	fn build_block_at(
+		&self,
+		parent: &BlockId<Block>,
+		f: impl for<'a, 'b> FnOnce(&'b mut BlockDsl<'a>) -> &'b mut BlockDsl<'a>,
+	) -> Result<Block, sp_blockchain::Error>;
+}
+
+impl<C, B> ClientBlockBuilderExt<B> for C
+where
+	B: backend::Backend<Block>,
+	C: BlockBuilderProvider<B, Block, C>
+		+ ProvideRuntimeApi<Block>
+		+ StorageProvider<Block, B>
+		+ HeaderBackend<Block>,
+	C::Api:
+		BlockBuilderApi<Block> + ApiExt<Block, StateBackend = backend::StateBackendFor<B, Block>>,
+{
+	fn build_block(
+		&self,
+		f: impl for<'a, 'b> FnOnce(&'b mut BlockDsl<'a>) -> &'b mut BlockDsl<'a>,
+	) -> Result<Block, sp_blockchain::Error> {
+		self.build_block_at(&BlockId::Hash(self.info().best_hash), f)
+	}
+
+	fn build_block_at(
+		&self,
+		parent: &BlockId<Block>,
+		f: impl for<'a, 'b> FnOnce(&'b mut BlockDsl<'a>) -> &'b mut BlockDsl<'a>,
+	) -> Result<Block, sp_blockchain::Error> {
+		let nonce_at_parent = |who: &AccountId| {
+			let key = blake2_256(&substrate_test_runtime::system::nonce_of_key(*who)).to_vec();
+			self.storage(parent, &StorageKey(key))
+				.ok()
+				.flatten()
+				.and_then(|nonce| codec::Decode::decode(&mut &nonce.0[..]).ok())
+				.unwrap_or(0)
+		};
+		let mut dsl = BlockDsl::new(&nonce_at_parent);
+		f(&mut dsl);
+
+		let mut builder = self.new_block_at(parent, Default::default(), false)?;
+		for extrinsic in dsl.into_extrinsics() {
+			builder.push(extrinsic)?;
+		}
+
+		builder.build().map(|built| built.block)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{ClientBlockImportExt, DefaultTestClientBuilderExt, TestClientBuilderExt};
+	use futures::executor::block_on;
+	use sp_consensus::BlockOrigin;
+	use substrate_test_runtime::Header;
+
+	fn balance_of(client: &crate::TestClient, who: AccountKeyring) -> u64 {
+		let key = blake2_256(&substrate_test_runtime::system::balance_of_key(who.into()));
+		let balance = client
+			.storage(&BlockId::Hash(client.info().best_hash), &StorageKey(key.to_vec()))
+			.unwrap()
+			.unwrap();
+		codec::Decode::decode(&mut &balance.0[..]).unwrap()
+	}
+
+	#[test]
+	fn build_block_tracks_nonces_across_blocks() {
+		let mut client = crate::TestClientBuilder::new().build();
+
+		let block = client
+			.build_block(|b| {
+				b.transfer(AccountKeyring::Alice, AccountKeyring::Bob, 10).transfer(
+					AccountKeyring::Alice,
+					AccountKeyring::Charlie,
+					20,
+				)
+			})
+			.unwrap();
+		block_on(client.import(BlockOrigin::Own, block)).unwrap();
+
+		let block = client
+			.build_block(|b| {
+				b.transfer(AccountKeyring::Alice, AccountKeyring::Bob, 30).include_data(b"data")
+			})
+			.unwrap();
+		assert_eq!(block.extrinsics[0].transfer().nonce, 2);
+		block_on(client.import(BlockOrigin::Own, block)).unwrap();
+
+		assert_eq!(balance_of(&client, AccountKeyring::Alice), 940);
+		assert_eq!(balance_of(&client, AccountKeyring::Bob), 1040);
+		assert_eq!(balance_of(&client, AccountKeyring::Charlie), 1020);
+	}
+
+	#[test]
+	fn build_block_at_builds_on_the_given_parent() {
+		let mut client = crate::TestClientBuilder::new().build();
+		let genesis = client.info().genesis_hash;
+
+		let block = client.build_block(|b| b.storage_change(b"key", Some(b"value"))).unwrap();
+		block_on(client.import(BlockOrigin::Own, block)).unwrap();
+
+		let fork = client.build_block_at(&BlockId::Hash(genesis), |b| b).unwrap();
+		let header: &Header = &fork.header;
+		assert_eq!(header.parent_hash, genesis);
+		assert_eq!(header.number, 1);
+	}
+}
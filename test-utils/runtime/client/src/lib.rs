@@ -21,6 +21,7 @@
 
 pub mod trait_tests;
 
+mod block_builder_dsl;
 mod block_builder_ext;
 
 pub use sc_consensus::LongestChain;
@@ -28,7 +29,10 @@ use std::{collections::HashMap, sync::Arc};
 pub use substrate_test_client::*;
 pub use substrate_test_runtime as runtime;
 
-pub use self::block_builder_ext::BlockBuilderExt;
+pub use self::{
+	block_builder_dsl::{BlockDsl, ClientBlockBuilderExt, ENDOWED_ACCOUNTS},
+	block_builder_ext::BlockBuilderExt,
+};
 
 use sc_client_api::light::{
 	Fetcher, RemoteBodyRequest, RemoteCallRequest, RemoteChangesRequest, RemoteHeaderRequest,
@@ -46,8 +50,8 @@ use substrate_test_runtime::genesismap::{additional_storage_with_genesis, Genesi
 pub mod prelude {
 	// Trait extensions
 	pub use super::{
-		BlockBuilderExt, ClientBlockImportExt, ClientExt, DefaultTestClientBuilderExt,
-		TestClientBuilderExt,
+		BlockBuilderExt, ClientBlockBuilderExt, ClientBlockImportExt, ClientExt,
+		DefaultTestClientBuilderExt, TestClientBuilderExt,
 	};
 	// Client structs
 	pub use super::{
@@ -55,7 +59,9 @@ pub mod prelude {
 		NativeElseWasmExecutor, TestClient, TestClientBuilder, WasmExecutionMethod,
 	};
 	// Keyring
-	pub use super::{AccountKeyring, Sr25519Keyring};
+	pub use super::{AccountKeyring, Sr25519Keyring, ENDOWED_ACCOUNTS};
+	// Block building
+	pub use super::BlockDsl;
 }
 
 /// A unit struct which implements `NativeExecutionDispatch` feeding in the
@@ -118,11 +124,7 @@ impl GenesisParameters {
 				sr25519::Public::from(Sr25519Keyring::Bob).into(),
 				sr25519::Public::from(Sr25519Keyring::Charlie).into(),
 			],
-			vec![
-				AccountKeyring::Alice.into(),
-				AccountKeyring::Bob.into(),
-				AccountKeyring::Charlie.into(),
-			],
+			ENDOWED_ACCOUNTS.iter().map(|account| (*account).into()).collect(),
 			1000,
 			self.heap_pages_override,
 			self.extra_storage.clone(),
@@ -101,6 +101,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	impl_version: 2,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
+	features: sp_version::runtime_features!(),
 };
 
 fn version() -> RuntimeVersion {
@@ -574,11 +575,13 @@ impl pallet_timestamp::Config for Runtime {
 parameter_types! {
 	pub const EpochDuration: u64 = 6;
 	pub const ExpectedBlockTime: u64 = 10_000;
+	pub const MaxTimestampDrift: u64 = 0;
 }
 
 impl pallet_babe::Config for Runtime {
 	type EpochDuration = EpochDuration;
 	type ExpectedBlockTime = ExpectedBlockTime;
+	type MaxTimestampDrift = MaxTimestampDrift;
 	// there is no actual runtime in this test-runtime, so testing crates
 	// are manually adding the digests. normally in this situation you'd use
 	// pallet_babe::SameAuthoritiesForever.
@@ -600,6 +603,31 @@ impl pallet_babe::Config for Runtime {
 	type WeightInfo = ();
 }
 
+/// The metadata of the runtime, which only describes the storage of the `System` pallet.
+fn runtime_metadata() -> OpaqueMetadata {
+	use frame_support::metadata::{
+		ExtrinsicMetadata, PalletMetadata, RuntimeMetadataLastVersion, RuntimeMetadataPrefixed,
+	};
+	use scale_info::meta_type;
+
+	let system = PalletMetadata {
+		name: "System",
+		index: 0,
+		storage: Some(frame_system::Pallet::<Runtime>::storage_metadata()),
+		calls: None,
+		event: None,
+		constants: Vec::new(),
+		error: None,
+	};
+	let metadata: RuntimeMetadataPrefixed = RuntimeMetadataLastVersion::new(
+		vec![system],
+		ExtrinsicMetadata { ty: meta_type::<()>(), version: 4, signed_extensions: Vec::new() },
+		meta_type::<()>(),
+	)
+	.into();
+	OpaqueMetadata::new(metadata.encode())
+}
+
 /// Adds one to the given input and returns the final result.
 #[inline(never)]
 fn benchmark_add_one(i: u64) -> u64 {
@@ -677,7 +705,7 @@ cfg_if! {
 
 			impl sp_api::Metadata<Block> for Runtime {
 				fn metadata() -> OpaqueMetadata {
-					unimplemented!()
+					runtime_metadata()
 				}
 			}
 
@@ -817,6 +845,13 @@ cfg_if! {
 						AuraId::from(authority)
 					}).collect()
 				}
+
+				fn next_authorities() -> Vec<AuraId> {
+					system::authorities().into_iter().map(|a| {
+						let authority: sr25519::Public = a.into();
+						AuraId::from(authority)
+					}).collect()
+				}
 			}
 
 			impl sp_consensus_babe::BabeApi<Block> for Runtime {
@@ -880,6 +915,20 @@ cfg_if! {
 				}
 			}
 
+			impl sp_genesis_builder::GenesisBuilder<Block> for Runtime {
+				fn create_default_config() -> Vec<u8> {
+					frame_support::genesis_builder_helper::create_default_config::<
+						system::GenesisBuilderConfig,
+					>()
+				}
+
+				fn build_config(config: Vec<u8>) -> sp_genesis_builder::Result {
+					frame_support::genesis_builder_helper::build_config::<
+						system::GenesisBuilderConfig,
+					>(config)
+				}
+			}
+
 			impl sp_finality_grandpa::GrandpaApi<Block> for Runtime {
 				fn grandpa_authorities() -> sp_finality_grandpa::AuthorityList {
 					Vec::new()
@@ -931,7 +980,7 @@ cfg_if! {
 
 			impl sp_api::Metadata<Block> for Runtime {
 				fn metadata() -> OpaqueMetadata {
-					unimplemented!()
+					runtime_metadata()
 				}
 			}
 
@@ -1075,6 +1124,13 @@ cfg_if! {
 						AuraId::from(authority)
 					}).collect()
 				}
+
+				fn next_authorities() -> Vec<AuraId> {
+					system::authorities().into_iter().map(|a| {
+						let authority: sr25519::Public = a.into();
+						AuraId::from(authority)
+					}).collect()
+				}
 			}
 
 			impl sp_consensus_babe::BabeApi<Block> for Runtime {
@@ -1138,6 +1194,20 @@ cfg_if! {
 				}
 			}
 
+			impl sp_genesis_builder::GenesisBuilder<Block> for Runtime {
+				fn create_default_config() -> Vec<u8> {
+					frame_support::genesis_builder_helper::create_default_config::<
+						system::GenesisBuilderConfig,
+					>()
+				}
+
+				fn build_config(config: Vec<u8>) -> sp_genesis_builder::Result {
+					frame_support::genesis_builder_helper::build_config::<
+						system::GenesisBuilderConfig,
+					>(config)
+				}
+			}
+
 			impl frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Index> for Runtime {
 				fn account_nonce(_account: AccountId) -> Index {
 					0
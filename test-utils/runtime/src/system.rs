@@ -22,11 +22,12 @@ use crate::{
 	AccountId, AuthorityId, Block, BlockNumber, Digest, Extrinsic, Header, Transfer, H256 as Hash,
 };
 use codec::{Decode, Encode, KeyedVec};
-use frame_support::{decl_module, decl_storage, storage};
+use frame_support::{decl_module, decl_storage, storage, traits::BuildGenesisConfig};
 use frame_system::Config;
-use sp_core::{storage::well_known_keys, ChangesTrieConfiguration};
+use serde::{Deserialize, Serialize};
+use sp_core::{sr25519, storage::well_known_keys, ChangesTrieConfiguration};
 use sp_io::{
-	hashing::blake2_256,
+	hashing::{blake2_256, twox_128},
 	storage::{changes_root as storage_changes_root, root as storage_root},
 	trie,
 };
@@ -36,7 +37,7 @@ use sp_runtime::{
 	transaction_validity::{
 		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
 	},
-	ApplyExtrinsicResult,
+	ApplyExtrinsicResult, RuntimeString,
 };
 use sp_std::prelude::*;
 
@@ -60,6 +61,39 @@ decl_storage! {
 	}
 }
 
+/// The genesis config given to the `GenesisBuilder` runtime api.
+///
+/// Unlike the `GenesisConfig` of `genesismap`, it is available without `std`, so that the genesis
+/// storage can be built by the wasm runtime alone. The code is not part of it.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct GenesisBuilderConfig {
+	/// The raw public keys of the authorities.
+	pub authorities: Vec<[u8; 32]>,
+	/// The raw public keys of the endowed accounts, with their balance.
+	pub balances: Vec<([u8; 32], u64)>,
+	/// The number of heap pages, 16 if not given.
+	pub heap_pages: Option<u64>,
+}
+
+impl BuildGenesisConfig for GenesisBuilderConfig {
+	fn build(&self) -> Result<(), RuntimeString> {
+		for (who, balance) in &self.balances {
+			storage::hashed::put(&blake2_256, &balance_of_key(sr25519::Public(*who)), balance);
+		}
+		storage::unhashed::put(well_known_keys::HEAP_PAGES, &self.heap_pages.unwrap_or(16));
+
+		let authorities = self
+			.authorities
+			.iter()
+			.map(|who| AuthorityId::from(sr25519::Public(*who)))
+			.collect::<Vec<_>>();
+		storage::unhashed::put(&twox_128(b"sys:auth"), &authorities);
+		<Authorities>::put(authorities);
+		Ok(())
+	}
+}
+
 pub fn balance_of_key(who: AccountId) -> Vec<u8> {
 	who.to_keyed_vec(BALANCE_OF)
 }
@@ -68,8 +102,12 @@ pub fn balance_of(who: AccountId) -> u64 {
 	storage::hashed::get_or(&blake2_256, &balance_of_key(who), 0)
 }
 
+pub fn nonce_of_key(who: AccountId) -> Vec<u8> {
+	who.to_keyed_vec(NONCE_OF)
+}
+
 pub fn nonce_of(who: AccountId) -> u64 {
-	storage::hashed::get_or(&blake2_256, &who.to_keyed_vec(NONCE_OF), 0)
+	storage::hashed::get_or(&blake2_256, &nonce_of_key(who), 0)
 }
 
 pub fn initialize_block(header: &Header) {
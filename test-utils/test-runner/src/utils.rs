@@ -78,6 +78,7 @@ pub fn default_config(tokio_handle: Handle, mut chain_spec: Box<dyn ChainSpec>)
 		database: DatabaseSource::RocksDb { path: root_path.join("db"), cache_size: 128 },
 		state_cache_size: 16777216,
 		state_cache_child_ratio: None,
+		trie_cache_size: 16777216,
 		chain_spec,
 		wasm_method: WasmExecutionMethod::Interpreted,
 		execution_strategies: ExecutionStrategies {
@@ -94,6 +95,7 @@ pub fn default_config(tokio_handle: Handle, mut chain_spec: Box<dyn ChainSpec>)
 		rpc_cors: None,
 		rpc_methods: Default::default(),
 		rpc_max_payload: None,
+		rpc_limits: Default::default(),
 		prometheus_config: None,
 		telemetry_endpoints: None,
 		default_heap_pages: None,
@@ -107,6 +109,7 @@ pub fn default_config(tokio_handle: Handle, mut chain_spec: Box<dyn ChainSpec>)
 		announce_block: true,
 		base_path: Some(base_path),
 		wasm_runtime_overrides: None,
+		stale_import_limit: None,
 		informant_output_format,
 		disable_log_reloading: false,
 		keystore_remote: None,
@@ -0,0 +1,123 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hardware requirements checked by the `benchmark-machine` command.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The hardware requirements of a reference Substrate validator, measured on its reference
+/// machine.
+const SUBSTRATE_REFERENCE_HARDWARE: &str = include_str!("./reference_hardware.json");
+
+/// A metric measured by the `benchmark-machine` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Metric {
+	/// BLAKE2-256 hashing throughput, in MiB/s.
+	Blake2256,
+	/// Verifications of SR25519 signatures, per second.
+	Sr25519Verify,
+	/// Memory copy throughput, in MiB/s.
+	MemCopy,
+	/// Random reads of the disk, in MiB/s.
+	DiskRndRead,
+	/// Random synchronous writes to the disk, in MiB/s.
+	DiskRndWrite,
+}
+
+impl Metric {
+	/// The category of the metric.
+	pub fn category(&self) -> &'static str {
+		match self {
+			Self::Blake2256 | Self::Sr25519Verify => "CPU",
+			Self::MemCopy => "Memory",
+			Self::DiskRndRead | Self::DiskRndWrite => "Disk",
+		}
+	}
+
+	/// The unit of the scores of the metric.
+	pub fn unit(&self) -> &'static str {
+		match self {
+			Self::Sr25519Verify => "verifications/s",
+			_ => "MiB/s",
+		}
+	}
+}
+
+impl fmt::Display for Metric {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let name = match self {
+			Self::Blake2256 => "BLAKE2-256",
+			Self::Sr25519Verify => "SR25519-Verify",
+			Self::MemCopy => "Copy",
+			Self::DiskRndRead => "Random Read",
+			Self::DiskRndWrite => "Random Write",
+		};
+		f.write_str(name)
+	}
+}
+
+/// The minimum score of a metric.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Requirement {
+	/// The measured metric.
+	pub metric: Metric,
+	/// The minimum score, in the unit of the metric.
+	pub minimum: f64,
+}
+
+/// Hardware requirements, as a list of minimum scores.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Requirements(pub Vec<Requirement>);
+
+impl Requirements {
+	/// The requirements of a reference Substrate validator.
+	pub fn substrate_reference_hardware() -> Self {
+		serde_json::from_str(SUBSTRATE_REFERENCE_HARDWARE)
+			.expect("the reference hardware requirements are valid JSON; qed")
+	}
+}
+
+impl Requirement {
+	/// Whether the measured `score` meets the requirement, allowing it to be `tolerance` percent
+	/// below the minimum.
+	pub fn is_met(&self, score: f64, tolerance: f64) -> bool {
+		score >= self.minimum * (1.0 - tolerance / 100.0)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn reference_hardware_is_valid() {
+		let requirements = Requirements::substrate_reference_hardware();
+		assert_eq!(requirements.0.len(), 5);
+		assert!(requirements.0.iter().all(|requirement| requirement.minimum > 0.0));
+	}
+
+	#[test]
+	fn requirements_are_met_within_tolerance() {
+		let requirement = Requirement { metric: Metric::MemCopy, minimum: 1000.0 };
+		assert!(requirement.is_met(1000.0, 0.0));
+		assert!(!requirement.is_met(950.0, 0.0));
+		assert!(requirement.is_met(950.0, 10.0));
+		assert!(!requirement.is_met(850.0, 10.0));
+	}
+}
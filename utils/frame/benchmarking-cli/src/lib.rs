@@ -16,8 +16,17 @@
 // limitations under the License.
 
 mod command;
+mod hardware;
+mod machine;
+mod overhead;
+mod storage;
 mod writer;
 
+pub use hardware::{Metric, Requirement, Requirements};
+pub use machine::{BenchResult, MachineCmd};
+pub use overhead::{ExtrinsicBuilder, OverheadCmd};
+pub use storage::{StatSelect, Stats, StorageCmd};
+
 use sc_cli::{ExecutionStrategy, WasmExecutionMethod};
 use std::fmt::Debug;
 
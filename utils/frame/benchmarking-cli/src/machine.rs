@@ -0,0 +1,303 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `benchmark-machine` command, checking that the host meets hardware requirements.
+//!
+//! The CPU, the memory and the disk of the database are benchmarked, each score being compared
+//! with the minimum of the requirements. The command fails if a requirement is not met.
+
+use crate::hardware::{Metric, Requirements};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use sc_cli::{CliConfiguration, Result, SharedParams};
+use sc_service::Configuration;
+use sp_core::{sr25519, Pair};
+use std::{
+	fs::{File, OpenOptions},
+	io::{Read, Seek, SeekFrom, Write},
+	path::Path,
+	time::{Duration, Instant},
+};
+use structopt::StructOpt;
+
+/// Number of bytes in a MiB.
+const MIB: f64 = (1024 * 1024) as f64;
+
+/// Size of the chunks read from and written to the disk.
+const DISK_CHUNK_SIZE: usize = 4 * 1024;
+
+/// Size of the file used by the disk benchmarks.
+const DISK_FILE_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Name of the file used by the disk benchmarks, in a temporary directory.
+const DISK_FILE_NAME: &str = "benchmark";
+
+/// The `benchmark-machine` command used to check the hardware of the host.
+#[derive(Debug, StructOpt)]
+pub struct MachineCmd {
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	/// Don't fail if the hardware requirements are not met.
+	#[structopt(long)]
+	pub allow_fail: bool,
+
+	/// How far below the minimum of a requirement a score may be, in percent.
+	#[structopt(long, default_value = "10")]
+	pub tolerance: f64,
+
+	/// Duration of each benchmark, in seconds.
+	#[structopt(long, default_value = "5")]
+	pub duration: u64,
+}
+
+/// The score of a metric and whether it meets its requirement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchResult {
+	/// The measured metric.
+	pub metric: Metric,
+	/// The measured score, in the unit of the metric.
+	pub score: f64,
+	/// The minimum score of the requirement.
+	pub minimum: f64,
+	/// Whether the score meets the requirement.
+	pub passed: bool,
+}
+
+impl MachineCmd {
+	/// Benchmark the host and check the scores against the `requirements`.
+	///
+	/// The disk benchmarks use a temporary file in the database directory.
+	pub fn run(&self, cfg: &Configuration, requirements: Requirements) -> Result<()> {
+		let dir = cfg.database.path().ok_or("The database is not stored on disk")?;
+		std::fs::create_dir_all(dir)?;
+		let duration = Duration::from_secs(self.duration);
+
+		let mut results = Vec::new();
+		for requirement in requirements.0 {
+			let score = measure(requirement.metric, duration, dir)?;
+			results.push(BenchResult {
+				metric: requirement.metric,
+				score,
+				minimum: requirement.minimum,
+				passed: requirement.is_met(score, self.tolerance),
+			});
+		}
+
+		println!(
+			"{:<8} | {:<16} | {:>26} | {:>26} | {}",
+			"Category", "Function", "Score", "Minimum", "Result"
+		);
+		for result in &results {
+			let unit = result.metric.unit();
+			println!(
+				"{:<8} | {:<16} | {:>26} | {:>26} | {}",
+				result.metric.category(),
+				result.metric.to_string(),
+				format!("{:.2} {}", result.score, unit),
+				format!("{:.2} {}", result.minimum, unit),
+				if result.passed { "Pass" } else { "Fail" },
+			);
+		}
+
+		let failed = results.iter().filter(|result| !result.passed).count();
+		match failed {
+			0 => {
+				log::info!("✅ The hardware meets the requirements");
+				Ok(())
+			},
+			_ if self.allow_fail => {
+				log::warn!("⚠️  The hardware fails {} of the requirements", failed);
+				Ok(())
+			},
+			_ => Err(format!("The hardware fails {} of the requirements", failed).into()),
+		}
+	}
+}
+
+/// Measure the score of `metric` during `duration`, the disk benchmarks using a file in `dir`.
+fn measure(metric: Metric, duration: Duration, dir: &Path) -> Result<f64> {
+	Ok(match metric {
+		Metric::Blake2256 => benchmark_blake2_256(duration),
+		Metric::Sr25519Verify => benchmark_sr25519_verify(duration),
+		Metric::MemCopy => benchmark_memory(duration),
+		Metric::DiskRndRead => benchmark_disk_random_reads(duration, dir)?,
+		Metric::DiskRndWrite => benchmark_disk_random_writes(duration, dir)?,
+	})
+}
+
+/// Call `f` repeatedly during `duration`, returning the number of calls per second.
+fn calls_per_sec(duration: Duration, mut f: impl FnMut()) -> f64 {
+	let start = Instant::now();
+	let mut calls = 0u64;
+	while start.elapsed() < duration {
+		f();
+		calls += 1;
+	}
+	calls as f64 / start.elapsed().as_secs_f64()
+}
+
+/// BLAKE2-256 hashing throughput, in MiB/s.
+pub fn benchmark_blake2_256(duration: Duration) -> f64 {
+	let mut data = vec![0u8; 32 * 1024];
+	let calls = calls_per_sec(duration, || {
+		// Hash the previous hash, so that no call can be optimized away.
+		let hash = sp_core::hashing::blake2_256(&data);
+		data[..32].copy_from_slice(&hash);
+	});
+	calls * data.len() as f64 / MIB
+}
+
+/// Verifications of SR25519 signatures per second.
+pub fn benchmark_sr25519_verify(duration: Duration) -> f64 {
+	let pair = sr25519::Pair::from_seed(&[1; 32]);
+	let message = [0u8; 32];
+	let signature = pair.sign(&message);
+	calls_per_sec(duration, || {
+		assert!(sr25519::Pair::verify(&signature, &message, &pair.public()));
+	})
+}
+
+/// Memory copy throughput, in MiB/s.
+pub fn benchmark_memory(duration: Duration) -> f64 {
+	let mut src = vec![0u8; 64 * 1024 * 1024];
+	let mut dst = vec![0u8; src.len()];
+	let calls = calls_per_sec(duration, || {
+		dst.copy_from_slice(&src);
+		src[0] = dst[dst.len() - 1].wrapping_add(1);
+	});
+	calls * src.len() as f64 / MIB
+}
+
+/// A chunk of the disk benchmarks, aligned as required by direct I/O.
+#[repr(align(4096))]
+struct AlignedChunk([u8; DISK_CHUNK_SIZE]);
+
+/// Random reads of a file in `dir`, in MiB/s.
+///
+/// The file is read bypassing the page cache of the system where supported, i.e. with `O_DIRECT`
+/// on Linux, otherwise the reads may be served from memory.
+pub fn benchmark_disk_random_reads(duration: Duration, dir: &Path) -> Result<f64> {
+	let (_, dir) = create_disk_file(dir)?;
+	let mut file = open_uncached(&dir.path().join(DISK_FILE_NAME))?;
+	let mut rng = StdRng::from_entropy();
+	let mut chunk = AlignedChunk([0u8; DISK_CHUNK_SIZE]);
+	let mut result = Ok(());
+	let calls = calls_per_sec(duration, || {
+		if result.is_ok() {
+			result = file
+				.seek(SeekFrom::Start(random_offset(&mut rng)))
+				.and_then(|_| file.read_exact(&mut chunk.0));
+		}
+	});
+	result?;
+	Ok(calls * DISK_CHUNK_SIZE as f64 / MIB)
+}
+
+/// Random synchronous writes to a file in `dir`, in MiB/s.
+pub fn benchmark_disk_random_writes(duration: Duration, dir: &Path) -> Result<f64> {
+	let (mut file, _dir) = create_disk_file(dir)?;
+	let mut rng = StdRng::from_entropy();
+	let mut chunk = [0u8; DISK_CHUNK_SIZE];
+	let mut result = Ok(());
+	let calls = calls_per_sec(duration, || {
+		if result.is_ok() {
+			rng.fill(&mut chunk[..]);
+			result = file
+				.seek(SeekFrom::Start(random_offset(&mut rng)))
+				.and_then(|_| file.write_all(&chunk))
+				.and_then(|_| file.sync_data());
+		}
+	});
+	result?;
+	Ok(calls * DISK_CHUNK_SIZE as f64 / MIB)
+}
+
+/// Create a file filled with random data in a new temporary directory of `dir`, which is removed
+/// once dropped.
+fn create_disk_file(dir: &Path) -> Result<(File, tempfile::TempDir)> {
+	let temp_dir = tempfile::tempdir_in(dir)?;
+	let mut file = OpenOptions::new()
+		.read(true)
+		.write(true)
+		.create(true)
+		.open(temp_dir.path().join(DISK_FILE_NAME))?;
+
+	let mut rng = StdRng::from_entropy();
+	let mut data = vec![0u8; 1024 * 1024];
+	for _ in 0..DISK_FILE_SIZE / data.len() as u64 {
+		rng.fill(&mut data[..]);
+		file.write_all(&data)?;
+	}
+	file.sync_all()?;
+	Ok((file, temp_dir))
+}
+
+/// Open the file at `path` for reading, bypassing the page cache if the file system supports it.
+#[cfg(target_os = "linux")]
+fn open_uncached(path: &Path) -> Result<File> {
+	use std::os::unix::fs::OpenOptionsExt;
+
+	match OpenOptions::new().read(true).custom_flags(libc::O_DIRECT).open(path) {
+		Ok(file) => Ok(file),
+		// E.g. `tmpfs` doesn't support direct I/O, its files are in memory anyway.
+		Err(e) if e.raw_os_error() == Some(libc::EINVAL) => {
+			log::warn!(
+				"Direct I/O is not supported in {}, reading through the page cache",
+				path.display()
+			);
+			Ok(File::open(path)?)
+		},
+		Err(e) => Err(e.into()),
+	}
+}
+
+/// Open the file at `path` for reading, the page cache can't be bypassed on this platform.
+#[cfg(not(target_os = "linux"))]
+fn open_uncached(path: &Path) -> Result<File> {
+	log::warn!("Direct I/O is not supported on this platform, reading through the page cache");
+	Ok(File::open(path)?)
+}
+
+/// A random offset of a chunk in the file of the disk benchmarks.
+fn random_offset(rng: &mut StdRng) -> u64 {
+	let chunks = DISK_FILE_SIZE / DISK_CHUNK_SIZE as u64;
+	rng.gen_range(0, chunks) * DISK_CHUNK_SIZE as u64
+}
+
+impl CliConfiguration for MachineCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn benchmarks_report_positive_scores() {
+		let duration = Duration::from_millis(10);
+		let dir = tempfile::tempdir().unwrap();
+
+		assert!(benchmark_blake2_256(duration) > 0.0);
+		assert!(benchmark_sr25519_verify(duration) > 0.0);
+		assert!(benchmark_memory(duration) > 0.0);
+		assert!(benchmark_disk_random_reads(duration, dir.path()).unwrap() > 0.0);
+		assert!(benchmark_disk_random_writes(duration, dir.path()).unwrap() > 0.0);
+	}
+}
@@ -0,0 +1,235 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `benchmark-overhead` command, measuring the execution weight of an empty block and the
+//! base weight of an extrinsic.
+//!
+//! An empty block and a block filled with no-op remarks are built on top of the best block, then
+//! executed repeatedly by the runtime of the node. The base weight of an extrinsic is the
+//! execution time of the full block, minus the one of the empty block, per remark.
+
+use crate::{
+	storage::{StatSelect, Stats},
+	writer::{write_constants, VERSION},
+};
+use sc_block_builder::{BlockBuilderApi, BlockBuilderProvider};
+use sc_cli::{CliConfiguration, DatabaseParams, Result, SharedParams};
+use sc_client_api::Backend as ClientBackend;
+use sc_service::Configuration;
+use serde::Serialize;
+use sp_api::{ApiExt, Core, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_inherents::InherentData;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Header as HeaderT},
+	OpaqueExtrinsic,
+};
+use std::{fs, path::PathBuf, sync::Arc, time::Instant};
+use structopt::StructOpt;
+
+const TEMPLATE: &str = include_str!("./overhead_template.hbs");
+
+/// The `benchmark-overhead` command used to benchmark the base weights of blocks and extrinsics.
+#[derive(Debug, StructOpt)]
+pub struct OverheadCmd {
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub database_params: DatabaseParams,
+
+	/// Number of executions of each block before the measurements.
+	#[structopt(long, default_value = "10")]
+	pub warmup: u32,
+
+	/// Number of measured executions of each block.
+	#[structopt(long, default_value = "100")]
+	pub repeat: u32,
+
+	/// Maximum number of remarks in the full block, less are pushed if the block is full.
+	#[structopt(long, default_value = "10000")]
+	pub max_ext_per_block: u32,
+
+	/// Statistic of the latencies used as weight: average, median, p95 or p99.
+	#[structopt(long, default_value = "average")]
+	pub weight_metric: StatSelect,
+
+	/// Output the weights to a Rust file at the given path.
+	#[structopt(long)]
+	pub weight_path: Option<PathBuf>,
+
+	/// Add a header file to the outputted weights.
+	#[structopt(long)]
+	pub header: Option<PathBuf>,
+}
+
+/// Creates the extrinsics benchmarked by the `benchmark-overhead` command.
+pub trait ExtrinsicBuilder {
+	/// A signed `System::remark` with an empty remark, sent by an account with the given nonce.
+	///
+	/// The account must be able to pay the fees of the remarks of a full block.
+	fn remark(&self, nonce: u32) -> std::result::Result<OpaqueExtrinsic, &'static str>;
+}
+
+/// Data of the weights template.
+#[derive(Serialize, Default, Debug, Clone)]
+struct TemplateData {
+	args: Vec<String>,
+	date: String,
+	version: String,
+	header: String,
+	warmup: u32,
+	repeat: u32,
+	num_extrinsics: usize,
+	metric: String,
+	block_stats: String,
+	extrinsic_stats: String,
+	block_weight: u64,
+	extrinsic_weight: u64,
+}
+
+impl OverheadCmd {
+	/// Run the overhead benchmark on top of the best block of `client`.
+	///
+	/// The blocks are built with the given inherents, e.g. the timestamp.
+	pub fn run<Block, BA, C>(
+		&self,
+		_cfg: Configuration,
+		client: Arc<C>,
+		inherent_data: InherentData,
+		ext_builder: &dyn ExtrinsicBuilder,
+	) -> Result<()>
+	where
+		Block: BlockT<Extrinsic = OpaqueExtrinsic>,
+		BA: ClientBackend<Block>,
+		C: BlockBuilderProvider<BA, Block, C> + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+		C::Api: ApiExt<Block, StateBackend = BA::State> + BlockBuilderApi<Block> + Core<Block>,
+	{
+		let (empty_block, _) = build_block(&*client, inherent_data.clone(), None)?;
+		let (full_block, num_extrinsics) =
+			build_block(&*client, inherent_data, Some((ext_builder, self.max_ext_per_block)))?;
+		log::info!("Benchmarking blocks with 0 and {} remarks", num_extrinsics);
+
+		let block_stats = Stats::new(&self.measure(&*client, &empty_block)?);
+		let extrinsic_times = self
+			.measure(&*client, &full_block)?
+			.into_iter()
+			.map(|time| time.saturating_sub(block_stats.average) / num_extrinsics as u64)
+			.collect::<Vec<_>>();
+		let extrinsic_stats = Stats::new(&extrinsic_times);
+		println!("Empty block execution: {}", block_stats);
+		println!("Remark execution: {}", extrinsic_stats);
+
+		if let Some(path) = &self.weight_path {
+			let data = TemplateData {
+				args: std::env::args().collect(),
+				date: chrono::Utc::now().format("%Y-%m-%d").to_string(),
+				version: VERSION.to_string(),
+				header: match &self.header {
+					Some(header) => fs::read_to_string(header)?,
+					None => String::new(),
+				},
+				warmup: self.warmup,
+				repeat: self.repeat,
+				num_extrinsics,
+				metric: format!("{:?}", self.weight_metric),
+				block_stats: block_stats.to_string(),
+				extrinsic_stats: extrinsic_stats.to_string(),
+				block_weight: block_stats.select(self.weight_metric),
+				extrinsic_weight: extrinsic_stats.select(self.weight_metric),
+			};
+			let mut path = path.clone();
+			if path.is_dir() {
+				path.push("block_weights.rs");
+			}
+			write_constants(TEMPLATE, &data, &path)?;
+			println!("Weights written to {}", path.display());
+		}
+		Ok(())
+	}
+
+	/// Execution times of `block` on top of its parent, in nanoseconds.
+	fn measure<Block, C>(&self, client: &C, block: &Block) -> Result<Vec<u64>>
+	where
+		Block: BlockT,
+		C: ProvideRuntimeApi<Block>,
+		C::Api: Core<Block>,
+	{
+		let parent = BlockId::Hash(*block.header().parent_hash());
+		let mut times = Vec::with_capacity(self.repeat as usize);
+		for i in 0..self.warmup + self.repeat {
+			// The changes of the runtime API are discarded, every execution starts from the parent.
+			let start = Instant::now();
+			client
+				.runtime_api()
+				.execute_block(&parent, block.clone())
+				.map_err(|e| format!("Failed to execute the block: {:?}", e))?;
+			if i >= self.warmup {
+				times.push(start.elapsed().as_nanos() as u64);
+			}
+		}
+		Ok(times)
+	}
+}
+
+/// Build a block on top of the best block, with as many remarks as fit in the block if an
+/// extrinsic builder is given. Returns the block and the number of remarks in it.
+fn build_block<Block, BA, C>(
+	client: &C,
+	inherent_data: InherentData,
+	remarks: Option<(&dyn ExtrinsicBuilder, u32)>,
+) -> Result<(Block, usize)>
+where
+	Block: BlockT<Extrinsic = OpaqueExtrinsic>,
+	BA: ClientBackend<Block>,
+	C: BlockBuilderProvider<BA, Block, C> + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: ApiExt<Block, StateBackend = BA::State> + BlockBuilderApi<Block>,
+{
+	let mut builder = client.new_block(Default::default())?;
+	for inherent in builder.create_inherents(inherent_data)? {
+		builder.push(inherent)?;
+	}
+
+	let mut num_extrinsics = 0;
+	if let Some((ext_builder, max)) = remarks {
+		for nonce in 0..max {
+			if builder.push(ext_builder.remark(nonce)?).is_err() {
+				break
+			}
+			num_extrinsics += 1;
+		}
+		if num_extrinsics == 0 {
+			return Err("Failed to push any remark into the block".into())
+		}
+	}
+
+	let block = builder.build()?.block;
+	Ok((block, num_extrinsics))
+}
+
+impl CliConfiguration for OverheadCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -0,0 +1,372 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `benchmark-storage` command, measuring the weight of the storage reads and writes on the
+//! database of a node.
+//!
+//! Random keys of the state of the best block are read and written, the weights being derived
+//! from the latencies measured. The writes are reverted once measured.
+//!
+//! The caches of the node and of the database are disabled, so that the reads hit the disk. The
+//! page cache of the operating system is not though, it should be dropped before running the
+//! command, e.g. with `echo 3 > /proc/sys/vm/drop_caches` on Linux.
+
+use crate::writer::{write_constants, VERSION};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, RngCore, SeedableRng};
+use sc_cli::{CliConfiguration, DatabaseParams, Result, SharedParams};
+use sc_client_api::Backend as _;
+use sc_client_db::{Backend, DbState};
+use sc_service::Configuration;
+use serde::Serialize;
+use sp_blockchain::HeaderBackend;
+use sp_core::Hasher;
+use sp_database::Transaction;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Header as HeaderT},
+};
+use sp_state_machine::Backend as StateBackend;
+use std::{fmt, fs, path::PathBuf, str::FromStr, sync::Arc, time::Instant};
+use structopt::StructOpt;
+
+const TEMPLATE: &str = include_str!("./storage_template.hbs");
+
+/// The `benchmark-storage` command used to benchmark the storage of a node.
+#[derive(Debug, StructOpt)]
+pub struct StorageCmd {
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub database_params: DatabaseParams,
+
+	/// Number of keys of the state to read and write.
+	#[structopt(long, default_value = "10000")]
+	pub samples: usize,
+
+	/// Seed of the selection of the keys and of the written values.
+	#[structopt(long)]
+	pub seed: Option<u64>,
+
+	/// Skip the read benchmark.
+	#[structopt(long)]
+	pub skip_read: bool,
+
+	/// Skip the write benchmark.
+	#[structopt(long)]
+	pub skip_write: bool,
+
+	/// Statistic of the latencies used as weight: average, median, p95 or p99.
+	#[structopt(long, default_value = "average")]
+	pub weight_metric: StatSelect,
+
+	/// Output the weights to a Rust file at the given path.
+	#[structopt(long)]
+	pub weight_path: Option<PathBuf>,
+
+	/// Add a header file to the outputted weights.
+	#[structopt(long)]
+	pub header: Option<PathBuf>,
+}
+
+/// Statistic of the measured latencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatSelect {
+	/// The average latency.
+	Average,
+	/// The median latency.
+	Median,
+	/// The 95th percentile of the latencies.
+	P95,
+	/// The 99th percentile of the latencies.
+	P99,
+}
+
+impl FromStr for StatSelect {
+	type Err = &'static str;
+
+	fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+		match s.to_lowercase().as_str() {
+			"average" => Ok(Self::Average),
+			"median" => Ok(Self::Median),
+			"p95" => Ok(Self::P95),
+			"p99" => Ok(Self::P99),
+			_ => Err("expected one of: average, median, p95, p99"),
+		}
+	}
+}
+
+/// Statistics of latencies in nanoseconds.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stats {
+	/// The lowest latency.
+	pub min: u64,
+	/// The highest latency.
+	pub max: u64,
+	/// The average latency.
+	pub average: u64,
+	/// The median latency.
+	pub median: u64,
+	/// The 95th percentile of the latencies.
+	pub p95: u64,
+	/// The 99th percentile of the latencies.
+	pub p99: u64,
+}
+
+impl Stats {
+	/// Compute the statistics of the given latencies.
+	pub fn new(samples: &[u64]) -> Self {
+		if samples.is_empty() {
+			return Self::default()
+		}
+		let mut sorted = samples.to_vec();
+		sorted.sort_unstable();
+		let percentile = |p: usize| sorted[((sorted.len() - 1) * p) / 100];
+
+		Stats {
+			min: sorted[0],
+			max: sorted[sorted.len() - 1],
+			average: sorted.iter().sum::<u64>() / sorted.len() as u64,
+			median: percentile(50),
+			p95: percentile(95),
+			p99: percentile(99),
+		}
+	}
+
+	/// The selected statistic.
+	pub fn select(&self, stat: StatSelect) -> u64 {
+		match stat {
+			StatSelect::Average => self.average,
+			StatSelect::Median => self.median,
+			StatSelect::P95 => self.p95,
+			StatSelect::P99 => self.p99,
+		}
+	}
+}
+
+impl fmt::Display for Stats {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"min {} ns, max {} ns, average {} ns, median {} ns, p95 {} ns, p99 {} ns",
+			self.min, self.max, self.average, self.median, self.p95, self.p99,
+		)
+	}
+}
+
+/// Data of the weights template.
+#[derive(Serialize, Default, Debug, Clone)]
+struct TemplateData {
+	args: Vec<String>,
+	date: String,
+	version: String,
+	header: String,
+	db_name: String,
+	samples: usize,
+	metric: String,
+	read_stats: String,
+	write_stats: String,
+	read_weight: u64,
+	write_weight: u64,
+}
+
+impl StorageCmd {
+	/// Run the storage benchmark on the state of the best block of `backend`.
+	pub fn run<Block: BlockT>(
+		&self,
+		_cfg: Configuration,
+		backend: Arc<Backend<Block>>,
+	) -> Result<()> {
+		let best_hash = backend.blockchain().info().best_hash;
+		let header = backend
+			.blockchain()
+			.header(BlockId::Hash(best_hash))?
+			.ok_or("The header of the best block is missing")?;
+		let state = DbState::<Block>::new(backend.expose_storage(), *header.state_root());
+		let (db, state_column) = backend.expose_db();
+		let db_name = if db.supports_ref_counting() { "ParityDb" } else { "RocksDb" };
+
+		let mut rng = match self.seed {
+			Some(seed) => StdRng::seed_from_u64(seed),
+			None => StdRng::from_entropy(),
+		};
+		let mut keys = sample_keys(&state, self.samples, &mut rng);
+		if keys.is_empty() {
+			return Err("The state of the best block is empty".into())
+		}
+		// The sampled keys are kept in the order of the trie, the reads must not be sequential.
+		keys.shuffle(&mut rng);
+		log::info!("Benchmarking {} keys of the state of block {:?}", keys.len(), best_hash);
+
+		let mut reads = Vec::with_capacity(keys.len());
+		if !self.skip_read {
+			for key in &keys {
+				let start = Instant::now();
+				state.storage(key)?;
+				reads.push(start.elapsed().as_nanos() as u64);
+			}
+		}
+
+		let mut writes = Vec::with_capacity(keys.len());
+		if !self.skip_write {
+			for key in &keys {
+				let mut value = state.storage(key)?.unwrap_or_default();
+				rng.fill_bytes(&mut value);
+
+				let start = Instant::now();
+				let (_, mut trie_changes) =
+					state.storage_root(std::iter::once((&key[..], Some(&value[..]))));
+				// Only write the new trie nodes, so that they can be removed afterwards.
+				let mut added = Vec::new();
+				let mut tx = Transaction::new();
+				for (node_key, (node, rc)) in trie_changes.drain() {
+					if rc > 0 && !db.contains(state_column, &node_key) {
+						tx.set_from_vec(state_column, &node_key, node);
+						added.push(node_key);
+					}
+				}
+				db.commit(tx).map_err(|e| format!("Failed to write the trie nodes: {}", e))?;
+				writes.push(start.elapsed().as_nanos() as u64);
+
+				let mut revert = Transaction::new();
+				added.iter().for_each(|node_key| revert.remove(state_column, node_key));
+				db.commit(revert)
+					.map_err(|e| format!("Failed to revert the trie nodes: {}", e))?;
+			}
+		}
+
+		let read_stats = Stats::new(&reads);
+		let write_stats = Stats::new(&writes);
+		println!("{} reads: {}", db_name, read_stats);
+		println!("{} writes: {}", db_name, write_stats);
+
+		if let Some(path) = &self.weight_path {
+			let data = TemplateData {
+				args: std::env::args().collect(),
+				date: chrono::Utc::now().format("%Y-%m-%d").to_string(),
+				version: VERSION.to_string(),
+				header: match &self.header {
+					Some(header) => fs::read_to_string(header)?,
+					None => String::new(),
+				},
+				db_name: db_name.into(),
+				samples: keys.len(),
+				metric: format!("{:?}", self.weight_metric),
+				read_stats: read_stats.to_string(),
+				write_stats: write_stats.to_string(),
+				read_weight: read_stats.select(self.weight_metric),
+				write_weight: write_stats.select(self.weight_metric),
+			};
+			self.write_weights(path, &data)?;
+		}
+		Ok(())
+	}
+
+	fn write_weights(&self, path: &PathBuf, data: &TemplateData) -> Result<()> {
+		let mut path = path.clone();
+		if path.is_dir() {
+			path.push(format!("{}_weights.rs", data.db_name.to_lowercase()));
+		}
+
+		write_constants(TEMPLATE, data, &path)?;
+		println!("Weights written to {}", path.display());
+		Ok(())
+	}
+}
+
+/// Select `samples` keys of `state` uniformly at random, iterating over its keys once.
+fn sample_keys<H: Hasher>(
+	state: &impl StateBackend<H>,
+	samples: usize,
+	rng: &mut impl Rng,
+) -> Vec<Vec<u8>> {
+	let mut keys = Vec::with_capacity(samples);
+	let mut seen = 0usize;
+	state.apply_to_keys_while(None, None, |key| {
+		// Reservoir sampling: the `seen + 1`th key replaces a sampled one with a probability of
+		// `samples / (seen + 1)`.
+		if keys.len() < samples {
+			keys.push(key.to_vec());
+		} else {
+			let idx = rng.gen_range(0, seen + 1);
+			if idx < samples {
+				keys[idx] = key.to_vec();
+			}
+		}
+		seen += 1;
+		true
+	});
+	keys
+}
+
+impl CliConfiguration for StorageCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+
+	// The caches are disabled, so that the keys read while sampling them aren't cached.
+	fn database_cache_size(&self) -> Result<Option<usize>> {
+		Ok(Some(0))
+	}
+
+	fn state_cache_size(&self) -> Result<usize> {
+		Ok(0)
+	}
+
+	fn trie_cache_size(&self) -> Result<usize> {
+		Ok(0)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn stats_are_computed() {
+		let samples = (1..=100).rev().collect::<Vec<u64>>();
+		let stats = Stats::new(&samples);
+		assert_eq!(stats, Stats { min: 1, max: 100, average: 50, median: 50, p95: 95, p99: 99 });
+		assert_eq!(stats.select(StatSelect::P95), 95);
+		assert_eq!(Stats::new(&[]), Stats::default());
+		assert_eq!("P99".parse(), Ok(StatSelect::P99));
+	}
+
+	#[test]
+	fn keys_are_sampled() {
+		let state = sp_state_machine::InMemoryBackend::<sp_runtime::traits::BlakeTwo256>::from(
+			(0u32..100)
+				.map(|i| (i.to_be_bytes().to_vec(), vec![1]))
+				.collect::<std::collections::BTreeMap<_, _>>(),
+		);
+		let mut rng = StdRng::seed_from_u64(0);
+
+		let mut keys = sample_keys(&state, 10, &mut rng);
+		assert_eq!(keys.len(), 10);
+		keys.sort();
+		keys.dedup();
+		assert_eq!(keys.len(), 10);
+		assert!(keys.iter().all(|key| state.storage(key).unwrap().is_some()));
+		assert_eq!(sample_keys(&state, 1000, &mut rng).len(), 100);
+	}
+}
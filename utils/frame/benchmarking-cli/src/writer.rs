@@ -21,7 +21,7 @@ use core::convert::TryInto;
 use std::{
 	collections::{HashMap, HashSet},
 	fs,
-	path::PathBuf,
+	path::{Path, PathBuf},
 };
 
 use inflector::Inflector;
@@ -36,7 +36,7 @@ use frame_support::traits::StorageInfo;
 use sp_core::hexdisplay::HexDisplay;
 use sp_runtime::traits::Zero;
 
-const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+pub(crate) const VERSION: &'static str = env!("CARGO_PKG_VERSION");
 const TEMPLATE: &str = include_str!("./template.hbs");
 
 // This is the final structure we will pass to the Handlebars template.
@@ -339,6 +339,23 @@ pub fn write_results(
 	Ok(())
 }
 
+// Renders a template of weight constants, as generated by the `benchmark-storage` and
+// `benchmark-overhead` commands, to the file at `path`.
+pub(crate) fn write_constants<T: Serialize>(
+	template: &str,
+	data: &T,
+	path: &Path,
+) -> Result<(), std::io::Error> {
+	let mut handlebars = handlebars::Handlebars::new();
+	// Don't HTML escape any characters.
+	handlebars.register_escape_fn(|s| -> String { s.to_string() });
+
+	let mut output_file = fs::File::create(path)?;
+	handlebars
+		.render_template_to_write(template, data, &mut output_file)
+		.map_err(|e| io_error(&e.to_string()))
+}
+
 // This function looks at the keys touched during the benchmark, and the storage info we collected
 // from the pallets, and creates comments with information about the storage keys touched during
 // each benchmark.
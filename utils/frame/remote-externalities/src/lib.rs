@@ -19,6 +19,9 @@
 //!
 //! An equivalent of `sp_io::TestExternalities` that can load its state from a remote substrate
 //! based chain, or a local state snapshot file.
+//!
+//! The default child tries of the remote state, e.g. the ones of crowdloans and contracts, are
+//! scraped along with the top trie, and saved in the state snapshot.
 
 use codec::{Decode, Encode};
 use jsonrpsee_ws_client::{types::v2::params::JsonRpcParams, WsClient, WsClientBuilder};
@@ -26,7 +29,10 @@ use log::*;
 use sp_core::{
 	hashing::twox_128,
 	hexdisplay::HexDisplay,
-	storage::{StorageData, StorageKey},
+	storage::{
+		well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX, ChildInfo, ChildType,
+		PrefixedStorageKey, StorageData, StorageKey,
+	},
 };
 pub use sp_io::TestExternalities;
 use sp_runtime::traits::Block as BlockT;
@@ -42,6 +48,12 @@ type KeyPair = (StorageKey, StorageData);
 const LOG_TARGET: &str = "remote-ext";
 const DEFAULT_TARGET: &str = "wss://rpc.polkadot.io";
 const BATCH_SIZE: usize = 1000;
+/// Number of times a failed rpc request is retried before giving up.
+const MAX_RETRIES: usize = 5;
+/// Prefix of the versioned state snapshot files, distinguishing them from the unversioned ones.
+const SNAPSHOT_MAGIC: &[u8; 4] = b"rext";
+/// Version of the state snapshot format, bumped on every incompatible change.
+const SNAPSHOT_VERSION: u16 = 1;
 
 jsonrpsee_proc_macros::rpc_client_api! {
 	RpcApi<B: BlockT> {
@@ -54,6 +66,14 @@ jsonrpsee_proc_macros::rpc_client_api! {
 			start_key: Option<StorageKey>,
 			hash: Option<B::Hash>,
 		) -> Vec<StorageKey>;
+		#[rpc(method = "childstate_getKeysPaged", positional_params)]
+		fn get_child_keys_paged(
+			child_storage_key: PrefixedStorageKey,
+			prefix: Option<StorageKey>,
+			count: u32,
+			start_key: Option<StorageKey>,
+			hash: Option<B::Hash>,
+		) -> Vec<StorageKey>;
 		#[rpc(method = "chain_getFinalizedHead", positional_params)]
 		fn finalized_head() -> B::Hash;
 	}
@@ -112,8 +132,13 @@ pub struct OnlineConfig<B: BlockT> {
 	pub at: Option<B::Hash>,
 	/// An optional state snapshot file to WRITE to, not for reading. Not written if set to `None`.
 	pub state_snapshot: Option<SnapshotConfig>,
-	/// The modules to scrape. If empty, entire chain state will be scraped.
+	/// The modules to scrape. If empty along with `hashed_prefixes`, entire chain state will be
+	/// scraped.
 	pub modules: Vec<String>,
+	/// The hashed prefixes of the keys to scrape, in addition to the ones of the modules.
+	pub hashed_prefixes: Vec<Vec<u8>>,
+	/// Whether to scrape the default child tries of which the root is scraped.
+	pub scrape_children: bool,
 	/// Transport config.
 	pub transport: Transport,
 }
@@ -135,6 +160,8 @@ impl<B: BlockT> Default for OnlineConfig<B> {
 			at: None,
 			state_snapshot: None,
 			modules: vec![],
+			hashed_prefixes: vec![],
+			scrape_children: true,
 		}
 	}
 }
@@ -158,6 +185,15 @@ impl Default for SnapshotConfig {
 	}
 }
 
+/// The key-values of a state, as scraped from a remote node or stored in a state snapshot.
+#[derive(Encode, Decode, Default, Debug, Clone, PartialEq)]
+struct Snapshot {
+	/// The key-values of the top trie.
+	top: Vec<KeyPair>,
+	/// The key-values of the default child tries, along with their prefixed storage key.
+	children: Vec<(StorageKey, Vec<KeyPair>)>,
+}
+
 /// Builder for remote-externalities.
 pub struct Builder<B: BlockT> {
 	/// Custom key-pairs to be injected into the externalities.
@@ -225,28 +261,63 @@ impl<B: BlockT> Builder<B> {
 		})
 	}
 
-	/// Get all the keys at `prefix` at `hash` using the paged, safe RPC methods.
+	/// Get all the keys at `prefix` at `hash` using the paged, safe RPC methods, in the given
+	/// child trie if any.
+	///
+	/// A failed page is requested again, resuming from the last key received, up to
+	/// `MAX_RETRIES` times.
 	async fn get_keys_paged(
 		&self,
 		prefix: StorageKey,
+		child: Option<&PrefixedStorageKey>,
 		at: B::Hash,
 	) -> Result<Vec<StorageKey>, &'static str> {
 		const PAGE: u32 = 512;
 		let mut last_key: Option<StorageKey> = None;
 		let mut all_keys: Vec<StorageKey> = vec![];
 		let keys = loop {
-			let page = RpcApi::<B>::get_keys_paged(
-				self.as_online().rpc_client(),
-				Some(prefix.clone()),
-				PAGE,
-				last_key.clone(),
-				Some(at),
-			)
-			.await
-			.map_err(|e| {
-				error!(target: LOG_TARGET, "Error = {:?}", e);
-				"rpc get_keys failed"
-			})?;
+			let mut retries = 0;
+			let page = loop {
+				let client = self.as_online().rpc_client();
+				let page = match child {
+					None =>
+						RpcApi::<B>::get_keys_paged(
+							client,
+							Some(prefix.clone()),
+							PAGE,
+							last_key.clone(),
+							Some(at),
+						)
+						.await,
+					Some(child) =>
+						RpcApi::<B>::get_child_keys_paged(
+							client,
+							child.clone(),
+							Some(prefix.clone()),
+							PAGE,
+							last_key.clone(),
+							Some(at),
+						)
+						.await,
+				};
+				match page {
+					Ok(page) => break page,
+					Err(e) if retries < MAX_RETRIES => {
+						retries += 1;
+						warn!(
+							target: LOG_TARGET,
+							"failed to get a page of keys: {:?}, retrying ({}/{})",
+							e,
+							retries,
+							MAX_RETRIES,
+						);
+					},
+					Err(e) => {
+						error!(target: LOG_TARGET, "Error = {:?}", e);
+						return Err("rpc get_keys failed")
+					},
+				}
+			};
 			let page_len = page.len();
 			all_keys.extend(page);
 
@@ -277,38 +348,85 @@ impl<B: BlockT> Builder<B> {
 		&self,
 		prefix: StorageKey,
 		at: B::Hash,
+	) -> Result<Vec<KeyPair>, &'static str> {
+		let keys = self.get_keys_paged(prefix, None, at).await?;
+		self.rpc_get_values(keys, None, at).await
+	}
+
+	/// Get all the key-values of the given child trie, using paged queries like
+	/// `rpc_get_pairs_paged`.
+	pub(crate) async fn rpc_child_get_pairs_paged(
+		&self,
+		child: &PrefixedStorageKey,
+		at: B::Hash,
+	) -> Result<Vec<KeyPair>, &'static str> {
+		let keys = self.get_keys_paged(StorageKey(vec![]), Some(child), at).await?;
+		self.rpc_get_values(keys, Some(child), at).await
+	}
+
+	/// Get the values of `keys`, in the given child trie if any, in batches of `BATCH_SIZE`.
+	///
+	/// A failed batch is requested again, up to `MAX_RETRIES` times.
+	async fn rpc_get_values(
+		&self,
+		keys: Vec<StorageKey>,
+		child: Option<&PrefixedStorageKey>,
+		at: B::Hash,
 	) -> Result<Vec<KeyPair>, &'static str> {
 		use jsonrpsee_ws_client::types::traits::Client;
 		use serde_json::to_value;
-		let keys = self.get_keys_paged(prefix, at).await?;
 		let keys_count = keys.len();
 		debug!(target: LOG_TARGET, "Querying a total of {} keys", keys.len());
 
 		let mut key_values: Vec<KeyPair> = vec![];
 		let client = self.as_online().rpc_client();
 		for chunk_keys in keys.chunks(BATCH_SIZE) {
-			let batch = chunk_keys
-				.iter()
-				.cloned()
-				.map(|key| {
-					(
-						"state_getStorage",
-						JsonRpcParams::Array(vec![
-							to_value(key).expect("json serialization will work; qed."),
-							to_value(at).expect("json serialization will work; qed."),
-						]),
-					)
-				})
-				.collect::<Vec<_>>();
-			let values = client.batch_request::<Option<StorageData>>(batch).await.map_err(|e| {
-				log::error!(
-					target: LOG_TARGET,
-					"failed to execute batch: {:?}. Error: {:?}",
-					chunk_keys,
-					e
-				);
-				"batch failed."
-			})?;
+			let batch = || {
+				chunk_keys
+					.iter()
+					.cloned()
+					.map(|key| {
+						let key = to_value(key).expect("json serialization will work; qed.");
+						let at = to_value(at).expect("json serialization will work; qed.");
+						match child {
+							None => ("state_getStorage", JsonRpcParams::Array(vec![key, at])),
+							Some(child) => {
+								let child =
+									to_value(child).expect("json serialization will work; qed.");
+								(
+									"childstate_getStorage",
+									JsonRpcParams::Array(vec![child, key, at]),
+								)
+							},
+						}
+					})
+					.collect::<Vec<_>>()
+			};
+			let mut retries = 0;
+			let values = loop {
+				match client.batch_request::<Option<StorageData>>(batch()).await {
+					Ok(values) => break values,
+					Err(e) if retries < MAX_RETRIES => {
+						retries += 1;
+						warn!(
+							target: LOG_TARGET,
+							"failed to execute batch: {:?}, retrying ({}/{})",
+							e,
+							retries,
+							MAX_RETRIES,
+						);
+					},
+					Err(e) => {
+						log::error!(
+							target: LOG_TARGET,
+							"failed to execute batch: {:?}. Error: {:?}",
+							chunk_keys,
+							e
+						);
+						return Err("batch failed.")
+					},
+				}
+			};
 			assert_eq!(chunk_keys.len(), values.len());
 			for (idx, key) in chunk_keys.into_iter().enumerate() {
 				let maybe_value = values[idx].clone();
@@ -336,22 +454,70 @@ impl<B: BlockT> Builder<B> {
 
 // Internal methods
 impl<B: BlockT> Builder<B> {
-	/// Save the given data as state snapshot.
-	fn save_state_snapshot(&self, data: &[KeyPair], path: &Path) -> Result<(), &'static str> {
+	/// Save the given data as state snapshot, prefixed with `SNAPSHOT_MAGIC` and
+	/// `SNAPSHOT_VERSION`.
+	fn save_state_snapshot(&self, data: &Snapshot, path: &Path) -> Result<(), &'static str> {
 		debug!(target: LOG_TARGET, "writing to state snapshot file {:?}", path);
-		fs::write(path, data.encode()).map_err(|_| "fs::write failed.")?;
+		let mut bytes = SNAPSHOT_MAGIC.to_vec();
+		SNAPSHOT_VERSION.encode_to(&mut bytes);
+		data.encode_to(&mut bytes);
+		fs::write(path, bytes).map_err(|_| "fs::write failed.")?;
 		Ok(())
 	}
 
 	/// initialize `Self` from state snapshot. Panics if the file does not exist.
-	fn load_state_snapshot(&self, path: &Path) -> Result<Vec<KeyPair>, &'static str> {
+	///
+	/// The unversioned snapshots, only containing the key-values of the top trie, are supported.
+	fn load_state_snapshot(&self, path: &Path) -> Result<Snapshot, &'static str> {
 		info!(target: LOG_TARGET, "scraping key-pairs from state snapshot {:?}", path);
 		let bytes = fs::read(path).map_err(|_| "fs::read failed.")?;
-		Decode::decode(&mut &*bytes).map_err(|_| "decode failed")
+		match bytes.strip_prefix(&SNAPSHOT_MAGIC[..]) {
+			Some(mut versioned) => {
+				let version = u16::decode(&mut versioned).map_err(|_| "decode failed")?;
+				if version != SNAPSHOT_VERSION {
+					error!(
+						target: LOG_TARGET,
+						"state snapshot version {} is not supported, expected {}",
+						version,
+						SNAPSHOT_VERSION,
+					);
+					return Err("unsupported state snapshot version")
+				}
+				Snapshot::decode(&mut versioned).map_err(|_| "decode failed")
+			},
+			None => {
+				let top = Decode::decode(&mut &*bytes).map_err(|_| "decode failed")?;
+				Ok(Snapshot { top, children: vec![] })
+			},
+		}
+	}
+
+	/// Scrape the default child tries of which the root is in `top`.
+	async fn load_child_remote(
+		&self,
+		top: &[KeyPair],
+		at: B::Hash,
+	) -> Result<Vec<(StorageKey, Vec<KeyPair>)>, &'static str> {
+		let mut children = vec![];
+		for (key, _) in
+			top.iter().filter(|(k, _)| k.0.starts_with(DEFAULT_CHILD_STORAGE_KEY_PREFIX))
+		{
+			let child = PrefixedStorageKey::new(key.0.clone());
+			let child_kv = self.rpc_child_get_pairs_paged(&child, at).await?;
+			info!(
+				target: LOG_TARGET,
+				"downloaded data for child trie {:?} (count: {}).",
+				HexDisplay::from(&key.0),
+				child_kv.len(),
+			);
+			children.push((key.clone(), child_kv));
+		}
+
+		Ok(children)
 	}
 
 	/// Build `Self` from a network node denoted by `uri`.
-	async fn load_remote(&self) -> Result<Vec<KeyPair>, &'static str> {
+	async fn load_remote(&self) -> Result<Snapshot, &'static str> {
 		let config = self.as_online();
 		let at = self
 			.as_online()
@@ -360,7 +526,7 @@ impl<B: BlockT> Builder<B> {
 			.clone();
 		info!(target: LOG_TARGET, "scraping key-pairs from remote @ {:?}", at);
 
-		let mut keys_and_values = if config.modules.len() > 0 {
+		let mut keys_and_values = if config.modules.len() > 0 || config.hashed_prefixes.len() > 0 {
 			let mut filtered_kv = vec![];
 			for f in config.modules.iter() {
 				let hashed_prefix = StorageKey(twox_128(f.as_bytes()).to_vec());
@@ -374,6 +540,17 @@ impl<B: BlockT> Builder<B> {
 				);
 				filtered_kv.extend(module_kv);
 			}
+			for prefix in config.hashed_prefixes.iter() {
+				let hashed_prefix = StorageKey(prefix.clone());
+				let prefix_kv = self.rpc_get_pairs_paged(hashed_prefix.clone(), at).await?;
+				info!(
+					target: LOG_TARGET,
+					"downloaded data for prefix {:?} (count: {}).",
+					HexDisplay::from(&hashed_prefix),
+					prefix_kv.len(),
+				);
+				filtered_kv.extend(prefix_kv);
+			}
 			filtered_kv
 		} else {
 			info!(target: LOG_TARGET, "downloading data for all modules.");
@@ -398,7 +575,13 @@ impl<B: BlockT> Builder<B> {
 			keys_and_values.push((key, value));
 		}
 
-		Ok(keys_and_values)
+		let children = if config.scrape_children {
+			self.load_child_remote(&keys_and_values, at).await?
+		} else {
+			vec![]
+		};
+
+		Ok(Snapshot { top: keys_and_values, children })
 	}
 
 	pub(crate) async fn init_remote_client(&mut self) -> Result<(), &'static str> {
@@ -422,8 +605,8 @@ impl<B: BlockT> Builder<B> {
 		Ok(())
 	}
 
-	pub(crate) async fn pre_build(mut self) -> Result<Vec<KeyPair>, &'static str> {
-		let mut base_kv = match self.mode.clone() {
+	pub(crate) async fn pre_build(mut self) -> Result<Snapshot, &'static str> {
+		let mut snapshot = match self.mode.clone() {
 			Mode::Offline(config) => self.load_state_snapshot(&config.state_snapshot.path)?,
 			Mode::Online(config) => {
 				self.init_remote_client().await?;
@@ -440,8 +623,8 @@ impl<B: BlockT> Builder<B> {
 			"extending externalities with {} manually injected key-values",
 			self.inject.len()
 		);
-		base_kv.extend(self.inject.clone());
-		Ok(base_kv)
+		snapshot.top.extend(self.inject.clone());
+		Ok(snapshot)
 	}
 }
 
@@ -484,18 +667,40 @@ impl<B: BlockT> Builder<B> {
 
 	/// Build the test externalities.
 	pub async fn build(self) -> Result<TestExternalities, &'static str> {
-		let kv = self.pre_build().await?;
-		let mut ext = TestExternalities::new_empty();
-
-		debug!(target: LOG_TARGET, "injecting a total of {} keys", kv.len());
-		for (k, v) in kv {
-			let (k, v) = (k.0, v.0);
-			// Insert the key,value pair into the test trie backend
-			ext.insert(k, v);
-		}
+		let snapshot = self.pre_build().await?;
+		Ok(snapshot_into_ext(snapshot))
+	}
+}
 
-		Ok(ext)
+/// Insert the key-values of `snapshot` into the trie backend of new test externalities.
+fn snapshot_into_ext(snapshot: Snapshot) -> TestExternalities {
+	let mut ext = TestExternalities::new_empty();
+	debug!(
+		target: LOG_TARGET,
+		"injecting a total of {} keys and {} child tries",
+		snapshot.top.len(),
+		snapshot.children.len(),
+	);
+
+	let into_changes =
+		|kv: Vec<KeyPair>| kv.into_iter().map(|(k, v)| (k.0, Some(v.0))).collect::<Vec<_>>();
+	let mut changes = vec![(None, into_changes(snapshot.top))];
+	for (child_key, child_kv) in snapshot.children {
+		let child_key = PrefixedStorageKey::new(child_key.0);
+		match ChildType::from_prefixed_key(&child_key) {
+			Some((ChildType::ParentKeyId, storage_key)) =>
+				changes.push((Some(ChildInfo::new_default(storage_key)), into_changes(child_kv))),
+			None => warn!(
+				target: LOG_TARGET,
+				"skipping child trie with unknown prefix {:?}",
+				HexDisplay::from(&*child_key),
+			),
+		}
 	}
+	// The roots of the child tries are written into the top trie once inserted.
+	ext.backend.insert(changes);
+
+	ext
 }
 
 #[cfg(test)]
@@ -530,6 +735,51 @@ mod tests {
 			.expect("Can't read state snapshot file")
 			.execute_with(|| {});
 	}
+
+	#[tokio::test]
+	async fn can_save_and_load_child_tries() {
+		init_logger();
+		let child_info = ChildInfo::new_default(b"crowdloan");
+		let kv = |k: &[u8], v: &[u8]| (StorageKey(k.to_vec()), StorageData(v.to_vec()));
+		let snapshot = Snapshot {
+			top: vec![kv(b"key", b"value")],
+			children: vec![(
+				StorageKey(child_info.prefixed_storage_key().into_inner()),
+				vec![kv(b"contribution", b"42")],
+			)],
+		};
+
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("snapshot");
+		let builder = Builder::<Block>::new();
+		builder.save_state_snapshot(&snapshot, &path).unwrap();
+		assert_eq!(builder.load_state_snapshot(&path).unwrap(), snapshot);
+
+		Builder::<Block>::new()
+			.mode(Mode::Offline(OfflineConfig { state_snapshot: SnapshotConfig::new(path) }))
+			.build()
+			.await
+			.expect("Can't read state snapshot file")
+			.execute_with(|| {
+				assert_eq!(sp_io::storage::get(b"key"), Some(b"value".to_vec()));
+				assert_eq!(
+					sp_io::default_child_storage::get(b"crowdloan", b"contribution"),
+					Some(b"42".to_vec()),
+				);
+			});
+	}
+
+	#[test]
+	fn unsupported_snapshot_version_is_rejected() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("snapshot");
+		let mut bytes = SNAPSHOT_MAGIC.to_vec();
+		(SNAPSHOT_VERSION + 1).encode_to(&mut bytes);
+		Snapshot::default().encode_to(&mut bytes);
+		fs::write(&path, bytes).unwrap();
+
+		assert!(Builder::<Block>::new().load_state_snapshot(&path).is_err());
+	}
 }
 
 #[cfg(all(test, feature = "remote-test"))]
@@ -568,6 +818,20 @@ mod remote_tests {
 			.execute_with(|| {});
 	}
 
+	#[tokio::test]
+	async fn can_build_hashed_prefix() {
+		init_logger();
+		Builder::<Block>::new()
+			.mode(Mode::Online(OnlineConfig {
+				hashed_prefixes: vec![[twox_128(b"System"), twox_128(b"Account")].concat()],
+				..Default::default()
+			}))
+			.build()
+			.await
+			.expect("Can't reach the remote node. Is it running?")
+			.execute_with(|| {});
+	}
+
 	#[tokio::test]
 	async fn sanity_check_decoding() {
 		use pallet_elections_phragmen::SeatHolder;
@@ -0,0 +1,214 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Development RPC methods, introspecting imported blocks.
+
+use std::sync::Arc;
+
+use codec::{Decode, Encode};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result as RpcResult};
+use jsonrpc_derive::rpc;
+use sc_client_api::{Backend, BlockBackend, StorageProvider};
+use sc_rpc_api::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+use sp_api::{ApiExt, Core, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{hashing::twox_128, storage::StorageKey};
+use sp_runtime::{
+	generic::{BlockId, DigestItem},
+	traits::{Block as BlockT, HashFor, Header as HeaderT},
+};
+
+use crate::{ConsumedWeight, Error};
+
+pub use self::gen_client::Client as DevClient;
+
+/// Development RPC methods.
+#[rpc]
+pub trait DevApi<BlockHash> {
+	/// Re-execute the given block with storage proof recording and return its statistics.
+	///
+	/// Returns `None` if the block or its parent is unknown, which includes the genesis block.
+	#[rpc(name = "dev_getBlockStats")]
+	fn block_stats(&self, hash: BlockHash) -> RpcResult<Option<BlockStats>>;
+}
+
+/// The statistics of a block, as returned by `dev_getBlockStats`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockStats {
+	/// The encoded size of the storage proof recorded while executing the block.
+	pub witness_len: u64,
+	/// The encoded size of the compact storage proof, as included in a proof of validity.
+	pub witness_compact_len: u64,
+	/// The number of extrinsics in the block.
+	pub num_extrinsics: u64,
+	/// The sum of the encoded sizes of the extrinsics in the block.
+	pub extrinsics_len: u64,
+	/// The weight consumed by the block, if the runtime uses `frame_system`.
+	pub weight: Option<ConsumedWeight>,
+}
+
+/// An implementation of the development RPC methods on full client.
+pub struct Dev<Block, BE, C> {
+	client: Arc<C>,
+	deny_unsafe: DenyUnsafe,
+	_marker: std::marker::PhantomData<(Block, BE)>,
+}
+
+impl<Block, BE, C> Dev<Block, BE, C> {
+	/// Create new `Dev` given client.
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+		Dev { client, deny_unsafe, _marker: Default::default() }
+	}
+}
+
+impl<Block, BE, C> DevApi<<Block as BlockT>::Hash> for Dev<Block, BE, C>
+where
+	Block: BlockT,
+	BE: Backend<Block> + 'static,
+	C: ProvideRuntimeApi<Block>
+		+ BlockBackend<Block>
+		+ HeaderBackend<Block>
+		+ StorageProvider<Block, BE>
+		+ Send
+		+ Sync
+		+ 'static,
+	C::Api: Core<Block>,
+{
+	fn block_stats(&self, hash: <Block as BlockT>::Hash) -> RpcResult<Option<BlockStats>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let block = match self.client.block(&BlockId::Hash(hash)).map_err(stats_error)? {
+			Some(block) => block.block,
+			None => return Ok(None),
+		};
+		let (mut header, extrinsics) = block.deconstruct();
+		let parent_hash = *header.parent_hash();
+		let parent_header =
+			match self.client.header(BlockId::Hash(parent_hash)).map_err(stats_error)? {
+				Some(header) => header,
+				None => return Ok(None),
+			};
+		// The seal is added after the block was built, so the runtime does not expect it.
+		header.digest_mut().logs.retain(|item| !matches!(item, DigestItem::Seal(_, _)));
+
+		let num_extrinsics = extrinsics.len() as u64;
+		let extrinsics_len = extrinsics.iter().map(|xt| xt.encoded_size() as u64).sum();
+
+		let mut runtime_api = self.client.runtime_api();
+		runtime_api.record_proof();
+		runtime_api
+			.execute_block(&BlockId::Hash(parent_hash), Block::new(header, extrinsics))
+			.map_err(stats_error)?;
+		let witness = runtime_api
+			.extract_proof()
+			.ok_or_else(|| stats_error("No storage proof was recorded."))?;
+		let witness_len = witness.encoded_size() as u64;
+		let witness_compact_len = witness
+			.into_compact_proof::<HashFor<Block>>(*parent_header.state_root())
+			.map_err(stats_error)?
+			.encoded_size() as u64;
+
+		let weight_key = StorageKey([twox_128(b"System"), twox_128(b"BlockWeight")].concat());
+		let weight = self
+			.client
+			.storage(&BlockId::Hash(hash), &weight_key)
+			.map_err(stats_error)?
+			.and_then(|weight| ConsumedWeight::decode(&mut &weight.0[..]).ok());
+
+		Ok(Some(BlockStats {
+			witness_len,
+			witness_compact_len,
+			num_extrinsics,
+			extrinsics_len,
+			weight,
+		}))
+	}
+}
+
+fn stats_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: "Unable to compute block stats.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use futures::executor::block_on;
+	use sp_consensus::BlockOrigin;
+	use substrate_test_runtime_client::{prelude::*, runtime::Transfer};
+
+	#[test]
+	fn block_stats_should_work() {
+		// given
+		let mut client = Arc::new(substrate_test_runtime_client::new());
+		let mut builder = client.new_block(Default::default()).unwrap();
+		builder
+			.push_transfer(Transfer {
+				from: AccountKeyring::Alice.into(),
+				to: AccountKeyring::Bob.into(),
+				amount: 5,
+				nonce: 0,
+			})
+			.unwrap();
+		let block = builder.build().unwrap().block;
+		let extrinsics_len =
+			block.extrinsics.iter().map(|xt| xt.encoded_size() as u64).sum::<u64>();
+		let hash = block.header.hash();
+		block_on(client.import(BlockOrigin::Own, block)).unwrap();
+
+		let dev = Dev::<_, substrate_test_runtime_client::Backend, _>::new(
+			client.clone(),
+			DenyUnsafe::No,
+		);
+
+		// when
+		let stats = dev.block_stats(hash).unwrap().unwrap();
+
+		// then
+		assert_eq!(stats.num_extrinsics, 1);
+		assert_eq!(stats.extrinsics_len, extrinsics_len);
+		assert!(stats.witness_len > 0);
+		assert!(stats.witness_compact_len > 0);
+		assert!(stats.witness_compact_len <= stats.witness_len);
+		// the test runtime does not use `frame_system`.
+		assert_eq!(stats.weight, None);
+		// the genesis block has no parent to execute it on.
+		assert_eq!(dev.block_stats(client.info().genesis_hash).unwrap(), None);
+	}
+
+	#[test]
+	fn block_stats_should_deny_unsafe() {
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let dev = Dev::<_, substrate_test_runtime_client::Backend, _>::new(
+			client.clone(),
+			DenyUnsafe::Yes,
+		);
+
+		// when
+		let res = dev.block_stats(client.info().genesis_hash);
+
+		// then
+		assert_eq!(res, Err(RpcError::method_not_found()));
+	}
+}
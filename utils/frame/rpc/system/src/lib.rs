@@ -17,26 +17,64 @@
 
 //! System FRAME specific RPC methods.
 
-use std::sync::Arc;
+use std::{
+	collections::HashMap,
+	sync::Arc,
+	time::{Duration, Instant},
+};
 
 use codec::{Codec, Decode, Encode};
 use futures::{future::ready, FutureExt, TryFutureExt};
 use jsonrpc_core::{Error as RpcError, ErrorCode};
 use jsonrpc_derive::rpc;
+use parking_lot::Mutex;
 use sc_client_api::light::{future_header, Fetcher, RemoteBlockchain, RemoteCallRequest};
 use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::{InPoolTransaction, TransactionPool};
+use serde::{Deserialize, Serialize};
+use sp_api::Core;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as ClientError, HeaderBackend};
 use sp_core::{hexdisplay::HexDisplay, Bytes};
-use sp_runtime::{generic::BlockId, traits};
-
-pub use self::gen_client::Client as SystemClient;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{self, UniqueSaturatedFrom, UniqueSaturatedInto},
+};
+
+pub use self::{
+	dev::{BlockStats, Dev, DevApi, DevClient},
+	gen_client::Client as SystemClient,
+	preview::{BlockPreview, ConsumedWeight, Preview, PreviewApi, PreviewClient},
+};
 pub use frame_system_rpc_runtime_api::AccountNonceApi;
 
+mod dev;
+mod preview;
+
 /// Future that resolves to account nonce.
 type FutureResult<T> = jsonrpc_core::BoxFuture<Result<T, RpcError>>;
 
+/// The maximum number of nonces that can be reserved by a single call to `author_reserveNonces`.
+pub const MAX_RESERVED_NONCES: u32 = 1024;
+
+/// The time after which the unused nonces of a reservation are handed out again.
+pub const NONCE_RESERVATION_TTL: Duration = Duration::from_secs(60);
+
+/// A range of nonces reserved for an account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NonceReservation<Index> {
+	/// The first reserved nonce.
+	pub first: Index,
+	/// The number of reserved nonces, starting with `first`.
+	pub count: u32,
+	/// The number of milliseconds after which the reservation expires.
+	///
+	/// Nonces of an expired reservation which were not used by a transaction in the pool or on
+	/// chain may be handed out again.
+	pub expires_in: u64,
+}
+
 /// System RPC methods.
 #[rpc]
 pub trait SystemApi<BlockHash, AccountId, Index> {
@@ -49,8 +87,24 @@ pub trait SystemApi<BlockHash, AccountId, Index> {
 	fn nonce(&self, account: AccountId) -> FutureResult<Index>;
 
 	/// Dry run an extrinsic at a given block. Return SCALE encoded ApplyExtrinsicResult.
+	///
+	/// The extrinsic is applied as part of a new block built on top of `at`, like it would be by
+	/// a block author, and the resulting state changes are discarded. The result includes the
+	/// details of a failed dispatch.
 	#[rpc(name = "system_dryRun", alias("system_dryRunAt"))]
 	fn dry_run(&self, extrinsic: Bytes, at: Option<BlockHash>) -> FutureResult<Bytes>;
+
+	/// Reserve `count` consecutive nonces for the given account.
+	///
+	/// The reserved range starts after all nonces used by the account on chain, in the
+	/// transaction pool and by earlier reservations which did not expire yet. This allows
+	/// to sign and submit multiple transactions of the same account in parallel.
+	#[rpc(name = "author_reserveNonces")]
+	fn reserve_nonces(
+		&self,
+		account: AccountId,
+		count: u32,
+	) -> FutureResult<NonceReservation<Index>>;
 }
 
 /// Error type of this RPC api.
@@ -59,6 +113,8 @@ pub enum Error {
 	DecodeError,
 	/// The call to runtime failed.
 	RuntimeError,
+	/// Building a block failed.
+	ProposalError,
 }
 
 impl From<Error> for i64 {
@@ -66,6 +122,7 @@ impl From<Error> for i64 {
 		match e {
 			Error::RuntimeError => 1,
 			Error::DecodeError => 2,
+			Error::ProposalError => 3,
 		}
 	}
 }
@@ -75,13 +132,21 @@ pub struct FullSystem<P: TransactionPool, C, B> {
 	client: Arc<C>,
 	pool: Arc<P>,
 	deny_unsafe: DenyUnsafe,
+	/// The end of the nonce range reserved for each encoded account, and its expiry.
+	reservations: Mutex<HashMap<Vec<u8>, (u128, Instant)>>,
 	_marker: std::marker::PhantomData<B>,
 }
 
 impl<P: TransactionPool, C, B> FullSystem<P, C, B> {
 	/// Create new `FullSystem` given client and transaction pool.
 	pub fn new(client: Arc<C>, pool: Arc<P>, deny_unsafe: DenyUnsafe) -> Self {
-		FullSystem { client, pool, deny_unsafe, _marker: Default::default() }
+		FullSystem {
+			client,
+			pool,
+			deny_unsafe,
+			reservations: Default::default(),
+			_marker: Default::default(),
+		}
 	}
 }
 
@@ -93,6 +158,7 @@ where
 	C: Send + Sync + 'static,
 	C::Api: AccountNonceApi<Block, AccountId, Index>,
 	C::Api: BlockBuilder<Block>,
+	C::Api: Core<Block>,
 	P: TransactionPool + 'static,
 	Block: traits::Block,
 	AccountId: Clone + std::fmt::Display + Codec,
@@ -128,9 +194,9 @@ where
 
 		let dry_run = || {
 			let api = self.client.runtime_api();
-			let at = BlockId::<Block>::hash(at.unwrap_or_else(||
-				// If the block hash is not supplied assume the best block.
-				self.client.info().best_hash));
+			// If the block hash is not supplied assume the best block.
+			let parent_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+			let at = BlockId::<Block>::hash(parent_hash);
 
 			let uxt: <Block as traits::Block>::Extrinsic = Decode::decode(&mut &*extrinsic)
 				.map_err(|e| RpcError {
@@ -139,6 +205,25 @@ where
 					data: Some(format!("{:?}", e).into()),
 				})?;
 
+			let parent_number =
+				self.client.number(parent_hash).ok().flatten().ok_or_else(|| {
+					RpcError::invalid_params(format!("Unknown block {:?}", parent_hash))
+				})?;
+			let header = <Block::Header as traits::Header>::new(
+				parent_number + traits::One::one(),
+				Default::default(),
+				Default::default(),
+				parent_hash,
+				Default::default(),
+			);
+			// The changes of the runtime api instance are kept between calls, so the extrinsic is
+			// applied on top of the initialized block.
+			api.initialize_block(&at, &header).map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to dry run extrinsic.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})?;
+
 			let result = api.apply_extrinsic(&at, uxt).map_err(|e| RpcError {
 				code: ErrorCode::ServerError(Error::RuntimeError.into()),
 				message: "Unable to dry run extrinsic.".into(),
@@ -152,6 +237,55 @@ where
 
 		async move { res }.boxed()
 	}
+
+	fn reserve_nonces(
+		&self,
+		account: AccountId,
+		count: u32,
+	) -> FutureResult<NonceReservation<Index>> {
+		if let Err(err) = self.deny_unsafe.check_if_safe() {
+			return async move { Err(err.into()) }.boxed()
+		}
+
+		let reserve_nonces = || {
+			if count == 0 || count > MAX_RESERVED_NONCES {
+				return Err(RpcError::invalid_params(format!(
+					"Expected to reserve between 1 and {} nonces, got {}",
+					MAX_RESERVED_NONCES, count,
+				)))
+			}
+
+			let api = self.client.runtime_api();
+			let at = BlockId::hash(self.client.info().best_hash);
+			let nonce = api.account_nonce(&at, account.clone()).map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to query nonce.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})?;
+
+			// The lock is held while inspecting the pool, so that concurrent reservations
+			// never overlap.
+			let mut reservations = self.reservations.lock();
+			let now = Instant::now();
+			reservations.retain(|_, (_, expiry)| *expiry > now);
+
+			let next: u128 =
+				adjust_nonce(&*self.pool, account.clone(), nonce).unique_saturated_into();
+			let reservation = reservations.entry(account.encode()).or_insert((next, now));
+			let first = reservation.0.max(next);
+			*reservation = (first.saturating_add(count.into()), now + NONCE_RESERVATION_TTL);
+
+			Ok(NonceReservation {
+				first: Index::unique_saturated_from(first),
+				count,
+				expires_in: NONCE_RESERVATION_TTL.as_millis() as u64,
+			})
+		};
+
+		let res = reserve_nonces();
+
+		async move { res }.boxed()
+	}
 }
 
 /// An implementation of System-specific RPC methods on light client.
@@ -236,6 +370,21 @@ where
 		}
 		.boxed()
 	}
+
+	fn reserve_nonces(
+		&self,
+		_account: AccountId,
+		_count: u32,
+	) -> FutureResult<NonceReservation<Index>> {
+		async {
+			Err(RpcError {
+				code: ErrorCode::MethodNotFound,
+				message: "Unable to reserve nonces.".into(),
+				data: None,
+			})
+		}
+		.boxed()
+	}
 }
 
 /// Adjust account nonce from state, so that tx with the nonce will be
@@ -280,11 +429,15 @@ mod tests {
 
 	use futures::executor::block_on;
 	use sc_transaction_pool::BasicPool;
+	use sp_core::{storage::well_known_keys, H256};
 	use sp_runtime::{
 		transaction_validity::{InvalidTransaction, TransactionValidityError},
 		ApplyExtrinsicResult,
 	};
-	use substrate_test_runtime_client::{runtime::Transfer, AccountKeyring};
+	use substrate_test_runtime_client::{
+		runtime::Transfer, AccountKeyring, DefaultTestClientBuilderExt, TestClientBuilder,
+		TestClientBuilderExt,
+	};
 
 	#[test]
 	fn should_return_next_nonce_for_some_account() {
@@ -321,6 +474,72 @@ mod tests {
 		assert_eq!(block_on(nonce).unwrap(), 2);
 	}
 
+	#[test]
+	fn reserve_nonces_should_not_overlap() {
+		sp_tracing::try_init_simple();
+
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let pool =
+			BasicPool::new_full(Default::default(), true.into(), None, spawner, client.clone());
+
+		let source = sp_runtime::transaction_validity::TransactionSource::External;
+		let new_transaction = |nonce: u64| {
+			let t = Transfer {
+				from: AccountKeyring::Alice.into(),
+				to: AccountKeyring::Bob.into(),
+				amount: 5,
+				nonce,
+			};
+			t.into_signed_tx()
+		};
+		// Populate the pool
+		let ext0 = new_transaction(0);
+		block_on(pool.submit_one(&BlockId::number(0), source, ext0)).unwrap();
+
+		let accounts = FullSystem::new(client, pool, DenyUnsafe::No);
+
+		// when
+		let first = block_on(accounts.reserve_nonces(AccountKeyring::Alice.into(), 3)).unwrap();
+		let second = block_on(accounts.reserve_nonces(AccountKeyring::Alice.into(), 2)).unwrap();
+		let other = block_on(accounts.reserve_nonces(AccountKeyring::Bob.into(), 1)).unwrap();
+
+		// then
+		assert_eq!((first.first, first.count), (1, 3));
+		assert_eq!((second.first, second.count), (4, 2));
+		assert_eq!((other.first, other.count), (0, 1));
+		assert_eq!(first.expires_in, NONCE_RESERVATION_TTL.as_millis() as u64);
+
+		// the regular nonce is not affected by reservations.
+		assert_eq!(block_on(accounts.nonce(AccountKeyring::Alice.into())).unwrap(), 1);
+
+		assert!(block_on(accounts.reserve_nonces(AccountKeyring::Alice.into(), 0)).is_err());
+		assert!(block_on(
+			accounts.reserve_nonces(AccountKeyring::Alice.into(), MAX_RESERVED_NONCES + 1)
+		)
+		.is_err());
+	}
+
+	#[test]
+	fn reserve_nonces_should_deny_unsafe() {
+		sp_tracing::try_init_simple();
+
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let pool =
+			BasicPool::new_full(Default::default(), true.into(), None, spawner, client.clone());
+
+		let accounts = FullSystem::new(client, pool, DenyUnsafe::Yes);
+
+		// when
+		let res = accounts.reserve_nonces(AccountKeyring::Alice.into(), 1);
+
+		// then
+		assert_eq!(block_on(res), Err(RpcError::method_not_found()));
+	}
+
 	#[test]
 	fn dry_run_should_deny_unsafe() {
 		sp_tracing::try_init_simple();
@@ -397,4 +616,70 @@ mod tests {
 		let apply_res: ApplyExtrinsicResult = Decode::decode(&mut bytes.as_slice()).unwrap();
 		assert_eq!(apply_res, Err(TransactionValidityError::Invalid(InvalidTransaction::Stale)));
 	}
+
+	#[test]
+	fn dry_run_should_initialize_the_block() {
+		sp_tracing::try_init_simple();
+
+		// given
+		// The extrinsic index is reset when initializing the block.
+		let client = Arc::new(
+			TestClientBuilder::new()
+				.add_extra_storage(well_known_keys::EXTRINSIC_INDEX.to_vec(), 1u32.encode())
+				.build(),
+		);
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let pool =
+			BasicPool::new_full(Default::default(), true.into(), None, spawner, client.clone());
+
+		let accounts = FullSystem::new(client, pool, DenyUnsafe::No);
+
+		// Only valid as the first extrinsic of a block.
+		let tx = Transfer {
+			from: AccountKeyring::Alice.into(),
+			to: AccountKeyring::Bob.into(),
+			amount: 5,
+			nonce: 0,
+		}
+		.into_resources_exhausting_tx();
+
+		// when
+		let res = accounts.dry_run(tx.encode().into(), None);
+
+		// then
+		let bytes = block_on(res).unwrap().0;
+		let apply_res: ApplyExtrinsicResult = Decode::decode(&mut bytes.as_slice()).unwrap();
+		assert_eq!(apply_res, Ok(Ok(())));
+	}
+
+	#[test]
+	fn dry_run_should_reject_unknown_block() {
+		sp_tracing::try_init_simple();
+
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let pool =
+			BasicPool::new_full(Default::default(), true.into(), None, spawner, client.clone());
+
+		let accounts = FullSystem::new(client, pool, DenyUnsafe::No);
+
+		let tx = Transfer {
+			from: AccountKeyring::Alice.into(),
+			to: AccountKeyring::Bob.into(),
+			amount: 5,
+			nonce: 0,
+		}
+		.into_signed_tx();
+		let unknown = H256::repeat_byte(1);
+
+		// when
+		let res = accounts.dry_run(tx.encode().into(), Some(unknown));
+
+		// then
+		assert_eq!(
+			block_on(res),
+			Err(RpcError::invalid_params(format!("Unknown block {:?}", unknown))),
+		);
+	}
 }
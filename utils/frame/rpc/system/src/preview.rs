@@ -0,0 +1,275 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Preview of the next block authored by the node.
+
+use std::{sync::Arc, time::Duration};
+
+use codec::{Decode, Encode};
+use futures::FutureExt;
+use jsonrpc_core::{Error as RpcError, ErrorCode};
+use jsonrpc_derive::rpc;
+use parking_lot::Mutex;
+use sc_rpc_api::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+use sp_blockchain::HeaderBackend;
+use sp_consensus::{Environment, Proposer};
+use sp_core::{hashing::twox_128, Bytes};
+use sp_inherents::{CreateInherentDataProviders, InherentDataProvider};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{self, Block as BlockT, DigestFor},
+};
+
+use crate::{Error, FutureResult};
+
+pub use self::gen_client::Client as PreviewClient;
+
+/// The maximum time spent building a preview block.
+const PREVIEW_DURATION: Duration = Duration::from_secs(2);
+
+/// Block preview RPC methods.
+#[rpc]
+pub trait PreviewApi<BlockHash> {
+	/// Build the block the node would author next on top of the best block, without importing
+	/// it.
+	///
+	/// The block contains the inherents and the ready transactions of the pool. `digest` is the
+	/// SCALE encoded digest passed to the block, which is required by consensus engines expecting
+	/// a pre-runtime digest in every block.
+	#[rpc(name = "author_previewBlock")]
+	fn preview_block(&self, digest: Option<Bytes>) -> FutureResult<BlockPreview<BlockHash>>;
+}
+
+/// The weight consumed by a block, per dispatch class.
+///
+/// Mirrors the encoding of `frame_system::ConsumedWeight`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, Serialize, Deserialize)]
+pub struct ConsumedWeight {
+	/// Weight consumed by `Normal` extrinsics.
+	pub normal: u64,
+	/// Weight consumed by `Operational` extrinsics.
+	pub operational: u64,
+	/// Weight consumed by `Mandatory` extrinsics.
+	pub mandatory: u64,
+}
+
+/// A block built by `author_previewBlock`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockPreview<Hash> {
+	/// The hash of the block the preview was built on.
+	pub parent_hash: Hash,
+	/// The SCALE encoded extrinsics of the block, including inherents.
+	pub extrinsics: Vec<Bytes>,
+	/// The weight consumed by the block, if the runtime uses `frame_system`.
+	pub weight: Option<ConsumedWeight>,
+	/// The SCALE encoded events deposited by the block, if the runtime uses `frame_system`.
+	pub events: Option<Bytes>,
+}
+
+/// An implementation of the block preview RPC methods on full client.
+pub struct Preview<Block, C, E, CIDP> {
+	client: Arc<C>,
+	env: Arc<Mutex<E>>,
+	create_inherent_data_providers: Arc<CIDP>,
+	deny_unsafe: DenyUnsafe,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<Block, C, E, CIDP> Preview<Block, C, E, CIDP> {
+	/// Create new `Preview` given client, proposer factory and inherent data providers.
+	pub fn new(
+		client: Arc<C>,
+		env: E,
+		create_inherent_data_providers: CIDP,
+		deny_unsafe: DenyUnsafe,
+	) -> Self {
+		Preview {
+			client,
+			env: Arc::new(Mutex::new(env)),
+			create_inherent_data_providers: Arc::new(create_inherent_data_providers),
+			deny_unsafe,
+			_marker: Default::default(),
+		}
+	}
+}
+
+impl<Block, C, E, CIDP> PreviewApi<<Block as traits::Block>::Hash> for Preview<Block, C, E, CIDP>
+where
+	Block: traits::Block,
+	C: HeaderBackend<Block> + Send + Sync + 'static,
+	E: Environment<Block> + Send + 'static,
+	E::Proposer: Proposer<Block>,
+	CIDP: CreateInherentDataProviders<Block, ()> + 'static,
+{
+	fn preview_block(
+		&self,
+		digest: Option<Bytes>,
+	) -> FutureResult<BlockPreview<<Block as traits::Block>::Hash>> {
+		if let Err(err) = self.deny_unsafe.check_if_safe() {
+			return async move { Err(err.into()) }.boxed()
+		}
+
+		let client = self.client.clone();
+		let env = self.env.clone();
+		let create_inherent_data_providers = self.create_inherent_data_providers.clone();
+
+		async move {
+			let digest = match digest {
+				Some(digest) =>
+					DigestFor::<Block>::decode(&mut &*digest).map_err(|e| RpcError {
+						code: ErrorCode::ServerError(Error::DecodeError.into()),
+						message: "Unable to decode digest.".into(),
+						data: Some(format!("{:?}", e).into()),
+					})?,
+				None => Default::default(),
+			};
+
+			let parent_hash = client.info().best_hash;
+			let parent = client
+				.header(BlockId::Hash(parent_hash))
+				.ok()
+				.flatten()
+				.ok_or_else(|| proposal_error("Best block header not found."))?;
+
+			let inherent_data = create_inherent_data_providers
+				.create_inherent_data_providers(parent_hash, ())
+				.await
+				.map_err(|e| proposal_error(e))?
+				.create_inherent_data()
+				.map_err(|e| proposal_error(e))?;
+
+			// The proposer factory must not be locked across an await point.
+			let create_proposer = env.lock().init(&parent);
+			let proposal = create_proposer
+				.await
+				.map_err(|e| proposal_error(e))?
+				.propose(inherent_data, digest, PREVIEW_DURATION, None)
+				.await
+				.map_err(|e| proposal_error(e))?;
+
+			let storage_value = |item: &[u8]| {
+				let key = [twox_128(b"System"), twox_128(item)].concat();
+				proposal
+					.storage_changes
+					.main_storage_changes
+					.iter()
+					.find(|(k, _)| *k == key)
+					.and_then(|(_, value)| value.clone())
+			};
+
+			Ok(BlockPreview {
+				parent_hash,
+				extrinsics: proposal
+					.block
+					.extrinsics()
+					.iter()
+					.map(|extrinsic| extrinsic.encode().into())
+					.collect(),
+				weight: storage_value(b"BlockWeight")
+					.and_then(|weight| ConsumedWeight::decode(&mut &weight[..]).ok()),
+				events: storage_value(b"Events").map(Into::into),
+			})
+		}
+		.boxed()
+	}
+}
+
+fn proposal_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::ProposalError.into()),
+		message: "Unable to build block.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use futures::executor::block_on;
+	use sc_basic_authorship::ProposerFactory;
+	use sc_transaction_pool::BasicPool;
+	use sc_transaction_pool_api::TransactionPool;
+	use substrate_test_runtime_client::{runtime::Transfer, AccountKeyring};
+
+	#[test]
+	fn preview_block_should_contain_ready_transactions() {
+		sp_tracing::try_init_simple();
+
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let pool = BasicPool::new_full(
+			Default::default(),
+			true.into(),
+			None,
+			spawner.clone(),
+			client.clone(),
+		);
+
+		let source = sp_runtime::transaction_validity::TransactionSource::External;
+		let tx = Transfer {
+			from: AccountKeyring::Alice.into(),
+			to: AccountKeyring::Bob.into(),
+			amount: 5,
+			nonce: 0,
+		}
+		.into_signed_tx();
+		block_on(pool.submit_one(&BlockId::number(0), source, tx.clone())).unwrap();
+
+		let env = ProposerFactory::new(spawner, client.clone(), pool, None, None);
+		let preview = Preview::new(client.clone(), env, |_, ()| async { Ok(()) }, DenyUnsafe::No);
+
+		// when
+		let res = block_on(preview.preview_block(None)).unwrap();
+
+		// then
+		assert_eq!(res.parent_hash, client.info().genesis_hash);
+		assert_eq!(res.extrinsics, vec![Bytes(tx.encode())]);
+		// the test runtime does not use `frame_system`.
+		assert_eq!(res.weight, None);
+		// the block is not imported.
+		assert_eq!(client.info().best_number, 0);
+	}
+
+	#[test]
+	fn preview_block_should_deny_unsafe() {
+		sp_tracing::try_init_simple();
+
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let pool = BasicPool::new_full(
+			Default::default(),
+			true.into(),
+			None,
+			spawner.clone(),
+			client.clone(),
+		);
+
+		let env = ProposerFactory::new(spawner, client.clone(), pool, None, None);
+		let preview = Preview::new(client, env, |_, ()| async { Ok(()) }, DenyUnsafe::Yes);
+
+		// when
+		let res = preview.preview_block(None);
+
+		// then
+		assert_eq!(block_on(res), Err(RpcError::method_not_found()));
+	}
+}
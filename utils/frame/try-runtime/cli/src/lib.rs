@@ -17,6 +17,10 @@
 
 //! `Structopt`-ready structs for `try-runtime`.
 
+use jsonrpsee_ws_client::{
+	types::{traits::SubscriptionClient, v2::params::JsonRpcParams, Subscription},
+	WsClientBuilder,
+};
 use parity_scale_codec::{Decode, Encode};
 use remote_externalities::{rpc_api, Builder, Mode, OfflineConfig, OnlineConfig, SnapshotConfig};
 use sc_chain_spec::ChainSpec;
@@ -30,9 +34,14 @@ use sp_core::{
 		OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
 	},
 	storage::{well_known_keys, StorageData, StorageKey},
+	traits::{RuntimeCode, WrappedRuntimeCode},
 };
+use sp_externalities::Extensions;
 use sp_keystore::{testing::KeyStore, KeystoreExt};
-use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor};
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT, NumberFor, One},
+	RuntimeString,
+};
 use sp_state_machine::StateMachine;
 use std::{fmt::Debug, path::PathBuf, str::FromStr, sync::Arc};
 
@@ -48,6 +57,9 @@ pub enum Command {
 	/// Execute "Core_execute_block" using the given block and the runtime state of the parent
 	/// block.
 	ExecuteBlock(ExecuteBlockCmd),
+	/// Follow the finalized blocks of the live chain, executing each of them with the runtime of
+	/// the given chain spec and comparing the resulting state roots with the ones of the chain.
+	FollowChain(FollowChainCmd),
 }
 
 #[derive(Debug, Clone, structopt::StructOpt)]
@@ -68,6 +80,13 @@ pub struct ExecuteBlockCmd {
 	pub state: State,
 }
 
+#[derive(Debug, Clone, structopt::StructOpt)]
+pub struct FollowChainCmd {
+	/// Stop after executing the given number of blocks.
+	#[structopt(long)]
+	pub max_blocks: Option<u32>,
+}
+
 #[derive(Debug, Clone, structopt::StructOpt)]
 pub struct SharedParams {
 	/// The shared parameters
@@ -167,9 +186,15 @@ pub enum State {
 		#[structopt(short, long)]
 		snapshot_path: Option<PathBuf>,
 
-		/// The modules to scrape. If empty, entire chain state will be scraped.
+		/// The modules to scrape. If empty along with `prefixes`, entire chain state will be
+		/// scraped.
 		#[structopt(short, long, require_delimiter = true)]
 		modules: Option<Vec<String>>,
+
+		/// The hex encoded hashed prefixes of the keys to scrape, in addition to the modules, e.g.
+		/// the prefix of a single storage item.
+		#[structopt(long, require_delimiter = true, parse(try_from_str = parse::hashed_prefix))]
+		prefixes: Option<Vec<Vec<u8>>>,
 	},
 }
 
@@ -206,11 +231,12 @@ where
 				Builder::<Block>::new().mode(Mode::Offline(OfflineConfig {
 					state_snapshot: SnapshotConfig::new(snapshot_path),
 				})),
-			State::Live { snapshot_path, modules } =>
+			State::Live { snapshot_path, modules, prefixes } =>
 				Builder::<Block>::new().mode(Mode::Online(OnlineConfig {
 					transport: shared.url.to_owned().into(),
 					state_snapshot: snapshot_path.as_ref().map(SnapshotConfig::new),
 					modules: modules.to_owned().unwrap_or_default(),
+					hashed_prefixes: prefixes.to_owned().unwrap_or_default(),
 					at: Some(shared.block_at::<Block>()?),
 					..Default::default()
 				})),
@@ -238,10 +264,13 @@ where
 	.execute(execution.into())
 	.map_err(|e| format!("failed to execute 'TryRuntime_on_runtime_upgrade': {:?}", e))?;
 
-	let (weight, total_weight) = <(u64, u64) as Decode>::decode(&mut &*encoded_result)
-		.map_err(|e| format!("failed to decode output: {:?}", e))?;
+	let (weight, total_weight) =
+		<Result<(u64, u64), RuntimeString> as Decode>::decode(&mut &*encoded_result)
+			.map_err(|e| format!("failed to decode output: {:?}", e))?
+			.map_err(|e| format!("pre or post upgrade checks failed: {}", e))?;
 	log::info!(
-		"TryRuntime_on_runtime_upgrade executed without errors. Consumed weight = {}, total weight = {} ({})",
+		"TryRuntime_on_runtime_upgrade executed without errors, pre and post upgrade checks passed. \
+		Consumed weight = {}, total weight = {} ({})",
 		weight,
 		total_weight,
 		weight as f64 / total_weight as f64
@@ -279,12 +308,13 @@ where
 	check_spec_name::<Block>(shared.url.clone(), config.chain_spec.name().to_string()).await;
 
 	let mode = match command.state {
-		State::Live { snapshot_path, modules } => {
+		State::Live { snapshot_path, modules, prefixes } => {
 			let at = shared.block_at::<Block>()?;
 			let online_config = OnlineConfig {
 				transport: shared.url.to_owned().into(),
 				state_snapshot: snapshot_path.as_ref().map(SnapshotConfig::new),
 				modules: modules.to_owned().unwrap_or_default(),
+				hashed_prefixes: prefixes.to_owned().unwrap_or_default(),
 				at: Some(at),
 				..Default::default()
 			};
@@ -374,13 +404,14 @@ where
 
 			mode
 		},
-		State::Live { snapshot_path, modules } => {
+		State::Live { snapshot_path, modules, prefixes } => {
 			let parent_hash = block.header().parent_hash();
 
 			let mode = Mode::Online(OnlineConfig {
 				transport: shared.url.to_owned().into(),
 				state_snapshot: snapshot_path.as_ref().map(SnapshotConfig::new),
 				modules: modules.to_owned().unwrap_or_default(),
+				hashed_prefixes: prefixes.to_owned().unwrap_or_default(),
 				at: Some(parent_hash.to_owned()),
 				..Default::default()
 			});
@@ -438,6 +469,193 @@ where
 	Ok(())
 }
 
+async fn follow_chain<Block, ExecDispatch>(
+	shared: SharedParams,
+	command: FollowChainCmd,
+	config: Configuration,
+) -> sc_cli::Result<()>
+where
+	Block: BlockT + serde::de::DeserializeOwned,
+	Block::Header: serde::de::DeserializeOwned,
+	ExecDispatch: NativeExecutionDispatch + 'static,
+{
+	let wasm_method = shared.wasm_method;
+	let execution = shared.execution;
+	let heap_pages = shared.heap_pages.or(config.default_heap_pages);
+
+	let max_runtime_instances = config.max_runtime_instances;
+	let executor = NativeElseWasmExecutor::<ExecDispatch>::new(
+		wasm_method.into(),
+		heap_pages,
+		max_runtime_instances,
+	);
+
+	check_spec_name::<Block>(shared.url.clone(), config.chain_spec.name().to_string()).await;
+
+	let client = WsClientBuilder::default()
+		.max_request_body_size(u32::MAX)
+		.build(&shared.url)
+		.await
+		.map_err(|e| format!("failed to connect to {}: {:?}", shared.url, e))?;
+	let mut subscription: Subscription<Block::Header> = client
+		.subscribe(
+			"chain_subscribeFinalizedHeads",
+			JsonRpcParams::NoParams,
+			"chain_unsubscribeFinalizedHeads",
+		)
+		.await
+		.map_err(|e| format!("failed to subscribe to the finalized heads: {:?}", e))?;
+
+	// The blocks are executed with the code of the chain spec, while the state keeps the code of
+	// the chain, so that the storage roots remain comparable.
+	let (_, code) = extract_code(config.chain_spec)?;
+	let code_fetcher = WrappedRuntimeCode(code.0.as_slice().into());
+	let runtime_code = RuntimeCode {
+		code_fetcher: &code_fetcher,
+		heap_pages: None,
+		hash: sp_core::blake2_256(&code.0).to_vec(),
+	};
+
+	let mut maybe_ext = None;
+	let mut last_executed: Option<(Block::Hash, NumberFor<Block>)> = None;
+	let mut executed = 0u32;
+	let mut diverged = 0u32;
+	'follow: while command.max_blocks.map_or(true, |max| executed < max) {
+		let head = match subscription.next().await {
+			Ok(Some(header)) => header,
+			Ok(None) => break,
+			Err(e) => return Err(format!("failed to receive a finalized head: {:?}", e).into()),
+		};
+		if last_executed.map_or(false, |(_, last_number)| *head.number() <= last_number) {
+			continue
+		}
+
+		// Finality may advance several blocks at once, so all the blocks since the last executed
+		// one are fetched, from the newest to the oldest.
+		let mut blocks = Vec::new();
+		let mut hash = head.hash();
+		loop {
+			let block: Block = rpc_api::get_block::<Block, _>(shared.url.clone(), hash).await?;
+			let (number, parent_hash) = (*block.header().number(), *block.header().parent_hash());
+			blocks.push(block);
+
+			match last_executed {
+				Some((last_hash, _)) if parent_hash == last_hash => break,
+				Some((_, last_number)) if number > last_number + One::one() => hash = parent_hash,
+				Some((last_hash, last_number)) =>
+					return Err(format!(
+						"finalized block {:?} doesn't descend from the last executed block {:?} \
+						(#{:?})",
+						hash, last_hash, last_number,
+					)
+					.into()),
+				None => break,
+			}
+		}
+
+		for block in blocks.into_iter().rev() {
+			if command.max_blocks.map_or(false, |max| executed >= max) {
+				break 'follow
+			}
+
+			let (hash, number, parent_hash) =
+				(block.header().hash(), *block.header().number(), *block.header().parent_hash());
+			if let Some((last_hash, _)) = last_executed {
+				if parent_hash != last_hash {
+					return Err(format!(
+						"the parent {:?} of block {:?} is not the last executed block {:?}",
+						parent_hash, hash, last_hash,
+					)
+					.into())
+				}
+			}
+
+			// The state is fetched at the parent of the first block, then kept up to date by
+			// applying the changes of every executed block.
+			if maybe_ext.is_none() {
+				let ext = Builder::<Block>::new()
+					.mode(Mode::Online(OnlineConfig {
+						transport: shared.url.to_owned().into(),
+						at: Some(parent_hash),
+						..Default::default()
+					}))
+					.build()
+					.await?;
+				log::info!(
+					"initialized the state at block {:?}, storage root {:?}",
+					parent_hash,
+					ext.backend.root(),
+				);
+				maybe_ext = Some(ext);
+			}
+			let ext = maybe_ext.as_mut().expect("the state is initialized above; qed");
+
+			// A digest item gets added when the runtime is processing the block, so we need to pop
+			// the last one to be consistent with what a gossiped block would contain.
+			let (mut header, extrinsics) = block.deconstruct();
+			header.digest_mut().pop();
+			let block = Block::new(header.clone(), extrinsics);
+
+			let mut changes = Default::default();
+			let encoded_result = StateMachine::<_, _, NumberFor<Block>, _>::new(
+				&ext.backend,
+				None,
+				&mut changes,
+				&executor,
+				"TryRuntime_execute_block_no_check",
+				block.encode().as_ref(),
+				full_extensions(),
+				&runtime_code,
+				sp_core::testing::TaskExecutor::new(),
+			)
+			.execute(execution.into())
+			.map_err(|e| {
+				format!("failed to execute 'TryRuntime_execute_block_no_check': {:?}", e)
+			})?;
+			let weight = <u64 as Decode>::decode(&mut &*encoded_result)
+				.map_err(|e| format!("failed to decode output: {:?}", e))?;
+
+			let storage_changes = changes
+				.drain_storage_changes::<_, _, NumberFor<Block>>(
+					&ext.backend,
+					None,
+					Default::default(),
+					&mut Default::default(),
+				)
+				.map_err(|e| format!("failed to drain the storage changes: {:?}", e))?;
+			ext.backend.apply_transaction(
+				storage_changes.transaction_storage_root,
+				storage_changes.transaction,
+			);
+			executed += 1;
+			last_executed = Some((hash, number));
+
+			let root = ext.backend.root();
+			if root.as_ref() == header.state_root().as_ref() {
+				log::info!(
+					"executed block {:?}, consumed weight = {}, storage root matches",
+					number,
+					weight,
+				);
+			} else {
+				diverged += 1;
+				log::warn!(
+					"executed block {:?}, consumed weight = {}, storage root diverges: computed \
+					{:?}, expected {:?}",
+					number,
+					weight,
+					root,
+					header.state_root(),
+				);
+			}
+		}
+	}
+
+	log::info!("executed {} blocks, {} of them diverging from the chain.", executed, diverged);
+
+	Ok(())
+}
+
 impl TryRuntimeCmd {
 	pub async fn run<Block, ExecDispatch>(&self, config: Configuration) -> sc_cli::Result<()>
 	where
@@ -458,6 +676,8 @@ impl TryRuntimeCmd {
 					.await,
 			Command::ExecuteBlock(cmd) =>
 				execute_block::<Block, ExecDispatch>(self.shared.clone(), cmd.clone(), config).await,
+			Command::FollowChain(cmd) =>
+				follow_chain::<Block, ExecDispatch>(self.shared.clone(), cmd.clone(), config).await,
 		}
 	}
 }
@@ -475,6 +695,19 @@ impl CliConfiguration for TryRuntimeCmd {
 	}
 }
 
+/// The externality extensions providing the host interface of offchain workers to the runtime.
+fn full_extensions() -> Extensions {
+	let mut extensions = Extensions::default();
+	let (offchain, _offchain_state) = TestOffchainExt::new();
+	let (pool, _pool_state) = TestTransactionPoolExt::new();
+	extensions.register(OffchainDbExt::new(offchain.clone()));
+	extensions.register(OffchainWorkerExt::new(offchain));
+	extensions.register(KeystoreExt(Arc::new(KeyStore::new())));
+	extensions.register(TransactionPoolExt::new(pool));
+
+	extensions
+}
+
 /// Extract `:code` from the given chain spec and return as `StorageData` along with the
 /// corresponding `StorageKey`.
 fn extract_code(spec: Box<dyn ChainSpec>) -> sc_cli::Result<(StorageKey, StorageData)> {
@@ -31,6 +31,10 @@ pub(crate) fn hash(block_hash: &str) -> Result<String, String> {
 	}
 }
 
+pub(crate) fn hashed_prefix(s: &str) -> Result<Vec<u8>, String> {
+	sp_core::bytes::from_hex(s).map_err(|e| format!("Expected a hex encoded prefix: {}", e))
+}
+
 pub(crate) fn url(s: &str) -> Result<String, &'static str> {
 	if s.starts_with("ws://") || s.starts_with("wss://") {
 		// could use Url crate as well, but lets keep it simple for now.
@@ -0,0 +1,273 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Metadata describing the inputs of a wasm build.
+//!
+//! The metadata is embedded as a custom section into the wasm binary. It allows to find out which
+//! inputs differ when rebuilding a runtime does not reproduce a given wasm binary byte for byte.
+
+use parity_wasm::elements::{deserialize_buffer, serialize, Module};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, fmt, fs, io, path::Path};
+
+/// The name of the custom section containing the [`BuildMetadata`].
+pub const BUILD_METADATA_SECTION: &str = "build_metadata";
+
+/// The inputs of a wasm build, e.g. the `rustc` version and digests of the source code.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildMetadata(BTreeMap<String, String>);
+
+/// An input of a wasm build which differs between two [`BuildMetadata`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputDifference {
+	/// The name of the input.
+	pub input: String,
+	/// The value of the input in the expected build, if present.
+	pub expected: Option<String>,
+	/// The value of the input in the actual build, if present.
+	pub actual: Option<String>,
+}
+
+impl fmt::Display for InputDifference {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"`{}`: expected `{}`, found `{}`",
+			self.input,
+			self.expected.as_deref().unwrap_or("<missing>"),
+			self.actual.as_deref().unwrap_or("<missing>"),
+		)
+	}
+}
+
+impl BuildMetadata {
+	/// Record the `value` of the given build `input`.
+	pub fn insert(&mut self, input: impl Into<String>, value: impl Into<String>) {
+		self.0.insert(input.into(), value.into());
+	}
+
+	/// Returns the value of the given build `input`.
+	pub fn get(&self, input: &str) -> Option<&str> {
+		self.0.get(input).map(|v| v.as_str())
+	}
+
+	/// Encode the metadata as the contents of the custom section, one `input=value` per line.
+	pub fn encode(&self) -> Vec<u8> {
+		self.0
+			.iter()
+			.map(|(input, value)| format!("{}={}\n", input, value))
+			.collect::<String>()
+			.into_bytes()
+	}
+
+	/// Decode the metadata from the contents of the custom section.
+	pub fn decode(contents: &[u8]) -> Result<Self, String> {
+		let contents = std::str::from_utf8(contents)
+			.map_err(|_| "Build metadata is not valid UTF-8".to_string())?;
+
+		contents
+			.lines()
+			.map(|line| {
+				line.split_once('=')
+					.map(|(input, value)| (input.to_string(), value.to_string()))
+					.ok_or_else(|| format!("Invalid build metadata line: `{}`", line))
+			})
+			.collect::<Result<_, _>>()
+			.map(Self)
+	}
+
+	/// Read the metadata from the given, possibly compressed, wasm binary.
+	///
+	/// Returns `Ok(None)` if the binary was built without metadata.
+	pub fn from_wasm(wasm: &[u8]) -> Result<Option<Self>, String> {
+		decompressed_module(wasm)?
+			.custom_sections()
+			.find(|section| section.name() == BUILD_METADATA_SECTION)
+			.map(|section| Self::decode(section.payload()))
+			.transpose()
+	}
+
+	/// Returns all inputs which differ between the `expected` metadata and `self`.
+	pub fn diff(&self, expected: &Self) -> Vec<InputDifference> {
+		let mut inputs = self.0.keys().chain(expected.0.keys()).collect::<Vec<_>>();
+		inputs.sort();
+		inputs.dedup();
+
+		inputs
+			.into_iter()
+			.filter_map(|input| {
+				let expected = expected.get(input);
+				let actual = self.get(input);
+				(expected != actual).then(|| InputDifference {
+					input: input.clone(),
+					expected: expected.map(Into::into),
+					actual: actual.map(Into::into),
+				})
+			})
+			.collect()
+	}
+}
+
+/// Returns the hex encoded SHA-256 digest of `data`.
+pub(crate) fn digest(data: &[u8]) -> String {
+	to_hex(&Sha256::digest(data))
+}
+
+/// Returns the hex encoded SHA-256 digest of the given `files`, identified by their path relative
+/// to `root`, or the error of the first file which can't be read.
+pub(crate) fn digest_files<'a>(
+	root: &Path,
+	files: impl Iterator<Item = &'a Path>,
+) -> io::Result<String> {
+	let mut files = files
+		.map(|file| (file.strip_prefix(root).unwrap_or(file).display().to_string(), file))
+		.collect::<Vec<_>>();
+	files.sort();
+
+	let mut hasher = Sha256::new();
+	for (relative_path, file) in files {
+		let contents = fs::read(file)?;
+		hasher.update(relative_path.as_bytes());
+		hasher.update((contents.len() as u64).to_le_bytes());
+		hasher.update(&contents);
+	}
+	Ok(to_hex(&hasher.finalize()))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+	bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn deserialize_module(wasm: &[u8]) -> Result<Module, String> {
+	deserialize_buffer(wasm).map_err(|e| format!("Failed to deserialize wasm binary: {}", e))
+}
+
+fn serialize_module(module: Module) -> Result<Vec<u8>, String> {
+	serialize(module).map_err(|e| format!("Failed to serialize wasm binary: {}", e))
+}
+
+/// Deserialize the given, possibly compressed, wasm binary.
+fn decompressed_module(wasm: &[u8]) -> Result<Module, String> {
+	let wasm =
+		sp_maybe_compressed_blob::decompress(wasm, sp_maybe_compressed_blob::CODE_BLOB_BOMB_LIMIT)
+			.map_err(|e| format!("Failed to decompress wasm binary: {}", e))?;
+
+	deserialize_module(&wasm)
+}
+
+/// Set the custom section with the given `name` to `contents`, replacing any existing one.
+pub(crate) fn set_custom_section(
+	wasm: &[u8],
+	name: &str,
+	contents: &[u8],
+) -> Result<Vec<u8>, String> {
+	let mut module = deserialize_module(wasm)?;
+	module.set_custom_section(name, contents.to_vec());
+	serialize_module(module)
+}
+
+/// Returns `true` if the given, possibly compressed, wasm binaries are equal once their
+/// [`BuildMetadata`] is removed.
+///
+/// This allows to verify a binary built with metadata against one built without it and the other
+/// way around.
+pub(crate) fn equal_without_build_metadata(a: &[u8], b: &[u8]) -> Result<bool, String> {
+	let without_build_metadata = |wasm: &[u8]| {
+		let mut module = decompressed_module(wasm)?;
+		module.clear_custom_section(BUILD_METADATA_SECTION);
+		serialize_module(module)
+	};
+
+	Ok(without_build_metadata(a)? == without_build_metadata(b)?)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// An empty wasm module.
+	const EMPTY_WASM: &[u8] = b"\0asm\x01\0\0\0";
+
+	#[test]
+	fn custom_section_roundtrip() {
+		let mut metadata = BuildMetadata::default();
+		metadata.insert("rustc", "rustc 1.57.0-nightly");
+		metadata.insert("features", "");
+
+		let wasm =
+			set_custom_section(EMPTY_WASM, BUILD_METADATA_SECTION, &metadata.encode()).unwrap();
+		assert_eq!(BuildMetadata::from_wasm(&wasm).unwrap(), Some(metadata.clone()));
+
+		// setting the section again replaces it.
+		metadata.insert("rustc", "rustc 1.58.0-nightly");
+		let wasm = set_custom_section(&wasm, BUILD_METADATA_SECTION, &metadata.encode()).unwrap();
+		assert_eq!(deserialize_module(&wasm).unwrap().custom_sections().count(), 1);
+		assert_eq!(BuildMetadata::from_wasm(&wasm).unwrap(), Some(metadata));
+
+		assert_eq!(BuildMetadata::from_wasm(EMPTY_WASM).unwrap(), None);
+	}
+
+	#[test]
+	fn binaries_are_compared_without_build_metadata() {
+		let mut metadata = BuildMetadata::default();
+		metadata.insert("rustc", "rustc 1.57.0-nightly");
+		let with_metadata =
+			set_custom_section(EMPTY_WASM, BUILD_METADATA_SECTION, &metadata.encode()).unwrap();
+
+		// A binary built without metadata is equal to the same binary with metadata.
+		assert!(equal_without_build_metadata(EMPTY_WASM, &with_metadata).unwrap());
+		assert!(equal_without_build_metadata(&with_metadata, EMPTY_WASM).unwrap());
+		let compressed = sp_maybe_compressed_blob::compress(
+			&with_metadata,
+			sp_maybe_compressed_blob::CODE_BLOB_BOMB_LIMIT,
+		)
+		.unwrap();
+		assert!(equal_without_build_metadata(EMPTY_WASM, &compressed).unwrap());
+
+		// Other custom sections are still compared.
+		let other = set_custom_section(&with_metadata, "other", b"other").unwrap();
+		assert!(!equal_without_build_metadata(EMPTY_WASM, &other).unwrap());
+	}
+
+	#[test]
+	fn diff_reports_differing_inputs() {
+		let mut expected = BuildMetadata::default();
+		expected.insert("rustc", "rustc 1.57.0-nightly");
+		expected.insert("source:node-runtime-3.0.0", "aa");
+
+		let mut actual = expected.clone();
+		assert!(actual.diff(&expected).is_empty());
+
+		actual.insert("rustc", "rustc 1.58.0-nightly");
+		actual.insert("cargo-lock", "bb");
+		assert_eq!(
+			actual.diff(&expected),
+			vec![
+				InputDifference {
+					input: "cargo-lock".into(),
+					expected: None,
+					actual: Some("bb".into()),
+				},
+				InputDifference {
+					input: "rustc".into(),
+					expected: Some("rustc 1.57.0-nightly".into()),
+					actual: Some("rustc 1.58.0-nightly".into()),
+				},
+			]
+		);
+	}
+}
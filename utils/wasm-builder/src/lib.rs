@@ -83,11 +83,31 @@
 //!   needs to be absolute.
 //! - `WASM_BUILD_TOOLCHAIN` - The toolchain that should be used to build the Wasm binaries. The
 //!   format needs to be the same as used by cargo, e.g. `nightly-2020-02-20`.
+//! - `WASM_BUILD_METADATA` - Embeds the inputs of the build into a `build_metadata` custom section
+//!   of the Wasm binaries. The recorded inputs are the `rustc` version, the build type, the
+//!   `RUSTFLAGS`, the enabled features, the digest of the `Cargo.lock` and the digests of the
+//!   sources of all path and git dependencies.
+//! - `WASM_BUILD_VERIFY_AGAINST` - Path to a Wasm binary that the build should reproduce byte for
+//!   byte, set by the `verify` subcommand described below. Implies `WASM_BUILD_METADATA`.
 //!
 //! Each project can be skipped individually by using the environment variable
 //! `SKIP_PROJECT_NAME_WASM_BUILD`. Where `PROJECT_NAME` needs to be replaced by the name of the
 //! cargo project, e.g. `node-runtime` will be `NODE_RUNTIME`.
 //!
+//! ## Verifying a Wasm binary
+//!
+//! The `verify` subcommand of the `substrate-wasm-builder` binary rebuilds the runtime of a
+//! package and verifies that it is equal to a given Wasm binary, e.g. the runtime of an on-chain
+//! upgrade proposal:
+//!
+//! ```sh
+//! cargo run -p substrate-wasm-builder -- verify --package node-runtime proposal.wasm
+//! ```
+//!
+//! If the build differs, the command fails and reports all inputs that differ from the ones
+//! recorded in the given Wasm binary. The `metadata` subcommand prints the inputs recorded in a
+//! Wasm binary.
+//!
 //! ## Prerequisites:
 //!
 //! Wasm builder requires the following prerequisites for building the Wasm binary:
@@ -106,10 +126,12 @@ use std::{
 	process::Command,
 };
 
+mod build_metadata;
 mod builder;
 mod prerequisites;
 mod wasm_project;
 
+pub use build_metadata::{BuildMetadata, InputDifference, BUILD_METADATA_SECTION};
 pub use builder::{WasmBuilder, WasmBuilderSelectProject};
 
 /// Environment variable that tells us to skip building the wasm binary.
@@ -135,8 +157,16 @@ const WASM_BUILD_NO_COLOR: &str = "WASM_BUILD_NO_COLOR";
 /// Environment variable to set the toolchain used to compile the wasm binary.
 const WASM_BUILD_TOOLCHAIN: &str = "WASM_BUILD_TOOLCHAIN";
 
+/// Environment variable to embed the build metadata into the wasm binary.
+const WASM_BUILD_METADATA: &str = "WASM_BUILD_METADATA";
+
+/// Environment variable to set the path of a wasm binary the build is verified against.
+///
+/// Implies [`WASM_BUILD_METADATA`].
+pub const WASM_BUILD_VERIFY_AGAINST: &str = "WASM_BUILD_VERIFY_AGAINST";
+
 /// Environment variable that makes sure the WASM build is triggered.
-const FORCE_WASM_BUILD_ENV: &str = "FORCE_WASM_BUILD";
+pub const FORCE_WASM_BUILD_ENV: &str = "FORCE_WASM_BUILD";
 
 /// Write to the given `file` if the `content` is different.
 fn write_file_if_changed(file: impl AsRef<Path>, content: impl AsRef<str>) {
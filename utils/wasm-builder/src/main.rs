@@ -0,0 +1,121 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Command line interface of the wasm builder.
+//!
+//! - `verify` rebuilds the runtime of a package and compares it byte for byte to a given wasm
+//!   binary, e.g. the runtime of an on-chain upgrade proposal. If they differ, all inputs of the
+//!   build which differ from the ones recorded in the given wasm binary are reported.
+//! - `metadata` prints the build metadata recorded in a wasm binary.
+
+use std::{
+	env, fs,
+	path::PathBuf,
+	process::{self, Command},
+	time::{SystemTime, UNIX_EPOCH},
+};
+use substrate_wasm_builder::{BuildMetadata, FORCE_WASM_BUILD_ENV, WASM_BUILD_VERIFY_AGAINST};
+
+const USAGE: &str = "\
+USAGE:
+    substrate-wasm-builder verify --package <PACKAGE> [--manifest-path <PATH>] <WASM> [-- <CARGO_ARGS>...]
+    substrate-wasm-builder metadata <WASM>
+
+SUBCOMMANDS:
+    verify      Rebuild the runtime of <PACKAGE> and verify that it is equal to <WASM>, reporting
+                the inputs of the build which differ otherwise. The build is a release build,
+                unless overridden by `WASM_BUILD_TYPE`.
+    metadata    Print the build metadata recorded in <WASM>";
+
+fn main() {
+	let mut args = env::args().skip(1);
+	let result = match args.next().as_deref() {
+		Some("verify") => verify(args),
+		Some("metadata") => metadata(args),
+		_ => Err(USAGE.into()),
+	};
+
+	if let Err(e) = result {
+		eprintln!("{}", e);
+		process::exit(1)
+	}
+}
+
+/// The `verify` subcommand.
+fn verify(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+	let mut package = None;
+	let mut manifest_path = None;
+	let mut wasm = None;
+	let mut cargo_args = Vec::new();
+
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"-p" | "--package" => package = args.next(),
+			"--manifest-path" => manifest_path = args.next(),
+			"--" => cargo_args.extend(&mut args),
+			_ if wasm.is_none() && !arg.starts_with('-') => wasm = Some(PathBuf::from(arg)),
+			_ => return Err(format!("Unexpected argument `{}`\n\n{}", arg, USAGE)),
+		}
+	}
+	let package = package.ok_or_else(|| format!("Missing `--package`\n\n{}", USAGE))?;
+	let wasm = wasm.ok_or_else(|| format!("Missing the wasm binary to verify\n\n{}", USAGE))?;
+	// The build script runs in another directory.
+	let wasm = fs::canonicalize(&wasm)
+		.map_err(|e| format!("Failed to find `{}`: {}", wasm.display(), e))?;
+
+	let mut build_cmd = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".into()));
+	build_cmd.args(&["build", "--release", "--package", &package]);
+	if let Some(manifest_path) = manifest_path {
+		build_cmd.args(&["--manifest-path", &manifest_path]);
+	}
+	// `cargo` only reruns the build script if the path of the wasm binary changed, but not if its
+	// contents changed.
+	let force = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos();
+	build_cmd
+		.args(&cargo_args)
+		.env(WASM_BUILD_VERIFY_AGAINST, &wasm)
+		.env(FORCE_WASM_BUILD_ENV, force.to_string());
+
+	let status = build_cmd.status().map_err(|e| format!("Failed to run `cargo`: {}", e))?;
+	if !status.success() {
+		return Err(format!(
+			"Failed to verify `{}` against the build of `{}`.",
+			wasm.display(),
+			package
+		))
+	}
+
+	println!("Verified `{}` against the build of `{}`.", wasm.display(), package);
+	Ok(())
+}
+
+/// The `metadata` subcommand.
+fn metadata(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+	let wasm = match (args.next(), args.next()) {
+		(Some(wasm), None) => PathBuf::from(wasm),
+		_ => return Err(USAGE.into()),
+	};
+
+	let wasm =
+		fs::read(&wasm).map_err(|e| format!("Failed to read `{}`: {}", wasm.display(), e))?;
+	match BuildMetadata::from_wasm(&wasm)? {
+		Some(metadata) => print!("{}", String::from_utf8_lossy(&metadata.encode())),
+		None => return Err("The wasm binary does not contain build metadata.".into()),
+	}
+
+	Ok(())
+}
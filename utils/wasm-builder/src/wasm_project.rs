@@ -15,7 +15,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{write_file_if_changed, CargoCommandVersioned};
+use crate::{
+	build_metadata::{
+		digest, digest_files, equal_without_build_metadata, set_custom_section, BuildMetadata,
+		BUILD_METADATA_SECTION,
+	},
+	write_file_if_changed, CargoCommandVersioned,
+};
 
 use std::{
 	borrow::ToOwned,
@@ -109,6 +115,16 @@ pub(crate) fn create_and_compile(
 	let wasm_workspace = wasm_workspace_root.join("wbuild");
 
 	let crate_metadata = crate_metadata(project_cargo_toml);
+	let verify_against = env::var_os(crate::WASM_BUILD_VERIFY_AGAINST).map(PathBuf::from);
+	let record_build_metadata =
+		verify_against.is_some() || env::var_os(crate::WASM_BUILD_METADATA).is_some();
+
+	// The features are consumed by `create_project`, so capture the build inputs up front.
+	let build_inputs = record_build_metadata.then(|| {
+		let mut enabled_features = project_enabled_features(project_cargo_toml, &crate_metadata);
+		enabled_features.extend(features_to_enable.iter().cloned());
+		(cargo_cmd.rustc_version().to_owned(), enabled_features)
+	});
 
 	let project = create_project(
 		project_cargo_toml,
@@ -118,9 +134,29 @@ pub(crate) fn create_and_compile(
 		features_to_enable,
 	);
 
+	let build_metadata = build_inputs.map(|(rustc_version, enabled_features)| {
+		collect_build_metadata(
+			project_cargo_toml,
+			&project,
+			&wasm_workspace,
+			&rustc_version,
+			default_rustflags,
+			enabled_features,
+		)
+	});
+
 	build_project(&project, default_rustflags, cargo_cmd);
 	let (wasm_binary, wasm_binary_compressed, bloaty) =
-		compact_wasm_file(&project, project_cargo_toml, wasm_binary_name);
+		compact_wasm_file(&project, project_cargo_toml, wasm_binary_name, build_metadata.as_ref());
+
+	if let (Some(verify_against), Some(build_metadata)) = (verify_against, &build_metadata) {
+		let built = wasm_binary_compressed
+			.as_ref()
+			.or_else(|| wasm_binary.as_ref())
+			.map(|b| b.wasm_binary_path())
+			.unwrap_or(&bloaty.0);
+		verify_build(built, &verify_against, build_metadata);
+	}
 
 	wasm_binary
 		.as_ref()
@@ -417,16 +453,21 @@ fn is_release_build() -> bool {
 	}
 }
 
+/// Returns the `RUSTFLAGS` used to build the WASM binary.
+fn wasm_rustflags(default_rustflags: &str) -> String {
+	format!(
+		"-C link-arg=--export-table {} {}",
+		default_rustflags,
+		env::var(crate::WASM_BUILD_RUSTFLAGS_ENV).unwrap_or_default(),
+	)
+}
+
 /// Build the project to create the WASM binary.
 fn build_project(project: &Path, default_rustflags: &str, cargo_cmd: CargoCommandVersioned) {
 	let manifest_path = project.join("Cargo.toml");
 	let mut build_cmd = cargo_cmd.command();
 
-	let rustflags = format!(
-		"-C link-arg=--export-table {} {}",
-		default_rustflags,
-		env::var(crate::WASM_BUILD_RUSTFLAGS_ENV).unwrap_or_default(),
-	);
+	let rustflags = wasm_rustflags(default_rustflags);
 
 	build_cmd
 		.args(&["rustc", "--target=wasm32-unknown-unknown"])
@@ -459,10 +500,13 @@ fn build_project(project: &Path, default_rustflags: &str, cargo_cmd: CargoComman
 }
 
 /// Compact the WASM binary using `wasm-gc` and compress it using zstd.
+///
+/// If `build_metadata` is given, it is embedded into the bloaty and the compact WASM binary.
 fn compact_wasm_file(
 	project: &Path,
 	cargo_manifest: &Path,
 	wasm_binary_name: Option<String>,
+	build_metadata: Option<&BuildMetadata>,
 ) -> (Option<WasmBinary>, Option<WasmBinary>, WasmBinaryBloaty) {
 	let is_release_build = is_release_build();
 	let target = if is_release_build { "release" } else { "debug" };
@@ -479,6 +523,9 @@ fn compact_wasm_file(
 		));
 		wasm_gc::garbage_collect_file(&wasm_file, &wasm_compact_file)
 			.expect("Failed to compact generated WASM binary.");
+		if let Some(build_metadata) = build_metadata {
+			embed_build_metadata(&wasm_compact_file, build_metadata);
+		}
 		Some(WasmBinary(wasm_compact_file))
 	} else {
 		None
@@ -506,10 +553,115 @@ fn compact_wasm_file(
 
 	let bloaty_file = project.join(bloaty_file_name);
 	fs::copy(wasm_file, &bloaty_file).expect("Copying the bloaty file to the project dir.");
+	if let Some(build_metadata) = build_metadata {
+		embed_build_metadata(&bloaty_file, build_metadata);
+	}
 
 	(wasm_compact_file, wasm_compact_compressed_file, WasmBinaryBloaty(bloaty_file))
 }
 
+/// Embed the `build_metadata` as custom section into the given WASM binary.
+fn embed_build_metadata(wasm_binary_path: &Path, build_metadata: &BuildMetadata) {
+	let data = fs::read(wasm_binary_path).expect("Failed to read WASM binary");
+	let data = set_custom_section(&data, BUILD_METADATA_SECTION, &build_metadata.encode())
+		.expect("Failed to embed build metadata into WASM binary");
+	fs::write(wasm_binary_path, data).expect("Failed to write WASM binary");
+}
+
+/// Collect the inputs of the WASM build.
+fn collect_build_metadata(
+	cargo_manifest: &Path,
+	project_folder: &Path,
+	wasm_workspace: &Path,
+	rustc_version: &str,
+	default_rustflags: &str,
+	mut enabled_features: Vec<String>,
+) -> BuildMetadata {
+	let mut build_metadata = BuildMetadata::default();
+	build_metadata.insert("rustc", rustc_version);
+	build_metadata.insert("build-type", if is_release_build() { "release" } else { "debug" });
+	build_metadata.insert("rustflags", wasm_rustflags(default_rustflags).trim());
+
+	enabled_features.sort();
+	enabled_features.dedup();
+	build_metadata.insert("features", enabled_features.join(","));
+
+	if let Some(cargo_lock) = find_cargo_lock(cargo_manifest) {
+		let cargo_lock = fs::read(cargo_lock).expect("Failed to read `Cargo.lock`");
+		build_metadata.insert("cargo-lock", digest(&cargo_lock));
+	}
+
+	// Dependencies from a registry are pinned by their checksum in the `Cargo.lock`, so we only
+	// need to record the sources of path and git dependencies.
+	let metadata = project_metadata(project_folder);
+	project_packages(cargo_manifest, wasm_workspace, &metadata)
+		.iter()
+		.filter(|p| p.source.as_ref().map(|s| s.repr.starts_with("git+")).unwrap_or(true))
+		.for_each(|package| {
+			let files = package_files(package).filter(|p| p.is_file()).collect::<Vec<_>>();
+			let digest = digest_files(&package_root(package), files.iter().map(|p| p.as_path()))
+				.unwrap_or_else(|e| {
+					panic!("Failed to read the sources of `{}`: {}", package.name, e)
+				});
+			build_metadata.insert(format!("source:{}-{}", package.name, package.version), digest);
+		});
+
+	build_metadata
+}
+
+/// Verify that the `built` WASM binary is equal to the one at `expected`.
+///
+/// Exits the process with an error if they differ, after reporting all inputs of the build which
+/// differ from the ones recorded in the `expected` WASM binary.
+fn verify_build(built: &Path, expected: &Path, build_metadata: &BuildMetadata) {
+	let expected_wasm = fs::read(expected).unwrap_or_else(|e| {
+		panic!(
+			"Failed to read `{}` given by `{}`: {}",
+			expected.display(),
+			crate::WASM_BUILD_VERIFY_AGAINST,
+			e,
+		)
+	});
+
+	let built_wasm = fs::read(built).expect("Failed to read WASM binary");
+	// The build metadata is only used to report the differences, binaries built with and without
+	// it are equal.
+	let equal = equal_without_build_metadata(&built_wasm, &expected_wasm).unwrap_or_else(|e| {
+		panic!("Failed to compare `{}` with `{}`: {}", built.display(), expected.display(), e)
+	});
+	if equal {
+		println!(
+			"{} {}",
+			colorize_info_message("Verified WASM binary against:"),
+			expected.display(),
+		);
+		return
+	}
+
+	eprintln!("The WASM binary `{}` differs from `{}`.", built.display(), expected.display());
+	match BuildMetadata::from_wasm(&expected_wasm) {
+		Ok(Some(expected_metadata)) => {
+			let differences = build_metadata.diff(&expected_metadata);
+			if differences.is_empty() {
+				eprintln!(
+					"All recorded inputs of the build are equal, the build is not reproducible."
+				);
+			} else {
+				eprintln!("The following inputs of the build differ:");
+				differences.iter().for_each(|d| eprintln!("  {}", d));
+			}
+		},
+		Ok(None) => eprintln!(
+			"`{}` does not contain build metadata, inputs of the build can not be compared.",
+			expected.display(),
+		),
+		Err(e) => eprintln!("Failed to read build metadata of `{}`: {}", expected.display(), e),
+	}
+
+	// Use `process.exit(1)` to have a clean error output.
+	process::exit(1)
+}
+
 fn compress_wasm(wasm_binary_path: &Path, compressed_binary_out_path: &Path) -> bool {
 	use sp_maybe_compressed_blob::CODE_BLOB_BOMB_LIMIT;
 
@@ -580,11 +732,37 @@ fn generate_rerun_if_changed_instructions(
 		rerun_if_changed(cargo_lock);
 	}
 
-	let metadata = MetadataCommand::new()
+	let metadata = project_metadata(project_folder);
+
+	// Make sure that if any file/folder of a dependency change, we need to rerun the `build.rs`
+	project_packages(cargo_manifest, wasm_workspace, &metadata)
+		.iter()
+		.for_each(package_rerun_if_changed);
+
+	// Register our env variables
+	println!("cargo:rerun-if-env-changed={}", crate::SKIP_BUILD_ENV);
+	println!("cargo:rerun-if-env-changed={}", crate::WASM_BUILD_TYPE_ENV);
+	println!("cargo:rerun-if-env-changed={}", crate::WASM_BUILD_RUSTFLAGS_ENV);
+	println!("cargo:rerun-if-env-changed={}", crate::WASM_TARGET_DIRECTORY);
+	println!("cargo:rerun-if-env-changed={}", crate::WASM_BUILD_TOOLCHAIN);
+	println!("cargo:rerun-if-env-changed={}", crate::WASM_BUILD_METADATA);
+	println!("cargo:rerun-if-env-changed={}", crate::WASM_BUILD_VERIFY_AGAINST);
+}
+
+/// Returns the `cargo metadata` of the wasm project.
+fn project_metadata(project_folder: &Path) -> Metadata {
+	MetadataCommand::new()
 		.manifest_path(project_folder.join("Cargo.toml"))
 		.exec()
-		.expect("`cargo metadata` can not fail!");
+		.expect("`cargo metadata` can not fail!")
+}
 
+/// Returns the crate we want to compile for wasm and all its dependencies.
+fn project_packages<'a>(
+	cargo_manifest: &Path,
+	wasm_workspace: &Path,
+	metadata: &'a Metadata,
+) -> HashSet<DeduplicatePackage<'a>> {
 	let package = metadata
 		.packages
 		.iter()
@@ -620,27 +798,25 @@ fn generate_rerun_if_changed_instructions(
 		}
 	}
 
-	// Make sure that if any file/folder of a dependency change, we need to rerun the `build.rs`
-	packages.iter().for_each(package_rerun_if_changed);
-
-	// Register our env variables
-	println!("cargo:rerun-if-env-changed={}", crate::SKIP_BUILD_ENV);
-	println!("cargo:rerun-if-env-changed={}", crate::WASM_BUILD_TYPE_ENV);
-	println!("cargo:rerun-if-env-changed={}", crate::WASM_BUILD_RUSTFLAGS_ENV);
-	println!("cargo:rerun-if-env-changed={}", crate::WASM_TARGET_DIRECTORY);
-	println!("cargo:rerun-if-env-changed={}", crate::WASM_BUILD_TOOLCHAIN);
+	packages
 }
 
-/// Track files and paths related to the given package to rerun `build.rs` on any relevant change.
-fn package_rerun_if_changed(package: &DeduplicatePackage) {
+/// Returns the root folder of the given package.
+fn package_root(package: &cargo_metadata::Package) -> PathBuf {
 	let mut manifest_path = package.manifest_path.clone();
 	if manifest_path.ends_with("Cargo.toml") {
 		manifest_path.pop();
 	}
+	manifest_path.into()
+}
+
+/// Returns all files and folders relevant for building the given package.
+fn package_files(package: &cargo_metadata::Package) -> impl Iterator<Item = PathBuf> {
+	let manifest_path = package_root(package);
 
-	WalkDir::new(&manifest_path)
+	WalkDir::new(manifest_path.clone())
 		.into_iter()
-		.filter_entry(|p| {
+		.filter_entry(move |p| {
 			// Ignore this entry if it is a directory that contains a `Cargo.toml` that is not the
 			// `Cargo.toml` related to the current package. This is done to ignore sub-crates of a
 			// crate. If such a sub-crate is a dependency, it will be processed independently
@@ -651,7 +827,11 @@ fn package_rerun_if_changed(package: &DeduplicatePackage) {
 		.filter(|p| {
 			p.is_dir() || p.extension().map(|e| e == "rs" || e == "toml").unwrap_or_default()
 		})
-		.for_each(|p| rerun_if_changed(p));
+}
+
+/// Track files and paths related to the given package to rerun `build.rs` on any relevant change.
+fn package_rerun_if_changed(package: &DeduplicatePackage) {
+	package_files(package).for_each(|p| rerun_if_changed(p));
 }
 
 /// Copy the WASM binary to the target directory set in `WASM_TARGET_DIRECTORY` environment